@@ -8,7 +8,7 @@
  * them to JSON for the Rust binary.
  */
 
-const { spawn } = require('child_process');
+const { spawn, spawnSync } = require('child_process');
 const path = require('path');
 const fs = require('fs');
 const os = require('os');
@@ -30,6 +30,10 @@ function loadCosmiconfig() {
 
 const JS_TS_CONFIG_EXTENSIONS = new Set(['.js', '.cjs', '.mjs', '.ts']);
 
+// `watch` exits with this code when the config file changed; the config is
+// evaluated again and the watcher restarted
+const RELOAD_EXIT_CODE = 75;
+
 // Detect platform and architecture
 const platform = os.platform();
 const arch = os.arch();
@@ -98,9 +102,14 @@ async function main() {
     }
   }
 
+  // `extract --stdin` / `lint --stdin` read source code from stdin, so the
+  // config goes on the command line instead
+  const sourceOnStdin = args.includes('--stdin');
   const rustArgs = [];
   if (configJson) {
-    rustArgs.push('--config-stdin');
+    if (!sourceOnStdin) {
+      rustArgs.push('--config-stdin');
+    }
     if (configPathHint) {
       rustArgs.push('--config-path-hint', configPathHint);
     }
@@ -125,7 +134,23 @@ async function main() {
   const astCapture = prepareAstEventCapture(plugins, commandName);
   let exitCode = 1;
   try {
-    exitCode = await runRustBinary(binaryPath, rustArgs, configJson, astCapture.env);
+    if (configJson && sourceOnStdin) {
+      rustArgs.push('--config-json', configJson);
+    }
+    exitCode = await runRustBinary(
+      binaryPath,
+      rustArgs,
+      sourceOnStdin ? null : configJson,
+      astCapture.env
+    );
+    while (exitCode === RELOAD_EXIT_CODE && commandName === 'watch' && configJson) {
+      const reloaded = await reloadWatchConfig(binaryPath, configPathHint, runtimeConfig);
+      if (reloaded) {
+        runtimeConfig = reloaded.runtimeConfig;
+        configJson = reloaded.configJson;
+      }
+      exitCode = await runRustBinary(binaryPath, rustArgs, configJson, astCapture.env);
+    }
   } finally {
     if (onLoadPrep && typeof onLoadPrep.cleanup === 'function') {
       onLoadPrep.cleanup();
@@ -242,6 +267,75 @@ async function loadConfigFromFile(filepath) {
   return formatExplorerResult(result);
 }
 
+/**
+ * Evaluate the changed config of a running `watch` again, check it with the
+ * binary and log the changed options. Returns null to keep the previous config.
+ */
+async function reloadWatchConfig(binaryPath, configPath, previousConfig) {
+  let normalized = null;
+  try {
+    normalized = normalizeConfig(await loadConfigFresh(configPath));
+  } catch (error) {
+    console.warn(`Config reload failed, keeping the previous config: ${error.message}`);
+    return null;
+  }
+  if (!normalized) {
+    console.warn('Config reload failed, keeping the previous config: the config is not an object');
+    return null;
+  }
+
+  const configJson = serializeConfigForRust(normalized);
+  const check = spawnSync(binaryPath, ['--config-stdin', 'config', 'show'], {
+    input: configJson,
+    encoding: 'utf8'
+  });
+  if (check.status !== 0) {
+    const reason = (check.stderr || '').trim() || `exit code ${check.status}`;
+    console.warn(`Config reload failed, keeping the previous config: ${reason}`);
+    return null;
+  }
+
+  console.log(`\n--- Config reloaded: ${configPath} ---`);
+  const previous = previousConfig || {};
+  const keys = new Set([...Object.keys(previous), ...Object.keys(normalized)]);
+  for (const key of [...keys].sort()) {
+    if (key === '__runtime') {
+      continue;
+    }
+    const before = JSON.stringify(previous[key]);
+    const after = JSON.stringify(normalized[key]);
+    if (before !== after) {
+      console.log(`  ${key}: ${before ?? 'null'} -> ${after ?? 'null'}`);
+    }
+  }
+  return { runtimeConfig: normalized, configJson };
+}
+
+/**
+ * Load a config file bypassing the module caches, so edits made since the
+ * first load are seen
+ */
+async function loadConfigFresh(filepath) {
+  const ext = path.extname(filepath).toLowerCase();
+  if (ext === '.ts') {
+    return loadTypeScriptConfig(filepath, { requireCache: false });
+  }
+  if (ext === '.mjs') {
+    return loadEsmConfig(filepath);
+  }
+  if (ext === '.js' || ext === '.cjs') {
+    try {
+      return loadCommonJsConfig(filepath);
+    } catch (error) {
+      if (ext === '.js' && error.code === 'ERR_REQUIRE_ESM') {
+        return loadEsmConfig(filepath);
+      }
+      throw error;
+    }
+  }
+  return JSON.parse(fs.readFileSync(filepath, 'utf8'));
+}
+
 function createExplorer() {
   const { cosmiconfig, defaultLoaders } = loadCosmiconfig();
   return cosmiconfig('i18next-turbo', {
@@ -262,7 +356,7 @@ function createExplorer() {
       '.json': defaultLoaders['.json'],
       '.cjs': loadCommonJsConfig,
       '.mjs': loadEsmConfig,
-      '.ts': loadTypeScriptConfig
+      '.ts': (filepath) => loadTypeScriptConfig(filepath)
     }
   });
 }
@@ -281,12 +375,13 @@ function shouldLoadWithNode(filepath) {
   return JS_TS_CONFIG_EXTENSIONS.has(ext);
 }
 
-function loadTypeScriptConfig(filepath) {
+function loadTypeScriptConfig(filepath, options = {}) {
   let jiti;
   try {
     jiti = require('jiti')(process.cwd(), {
       esmResolve: true,
-      interopDefault: true
+      interopDefault: true,
+      ...options
     });
   } catch (error) {
     throw new Error(
@@ -351,8 +446,21 @@ function buildRuntimeMetadata(rawConfig) {
   };
 }
 
+// Global flags whose value is passed as the next argument
+const GLOBAL_VALUE_FLAGS = new Set(['--log-level', '--set']);
+
 function resolveCommandName(args) {
-  for (const arg of args) {
+  for (let i = 0; i < args.length; i++) {
+    const arg = args[i];
+    if (GLOBAL_VALUE_FLAGS.has(arg)) {
+      i++;
+      continue;
+    }
+    // `--summary` takes an optional format
+    if (arg === '--summary' && (args[i + 1] === 'text' || args[i + 1] === 'json')) {
+      i++;
+      continue;
+    }
     if (!arg.startsWith('-')) {
       return arg;
     }
@@ -931,6 +1039,14 @@ function mapCliExtractConfig(rawConfig) {
     mapped.useTranslationNames = extract.useTranslationNames;
   }
 
+  if (Array.isArray(extract.messageFactoryFunctions)) {
+    mapped.messageFactoryFunctions = extract.messageFactoryFunctions;
+  }
+
+  if (Array.isArray(extract.keyProducingFunctions)) {
+    mapped.keyProducingFunctions = extract.keyProducingFunctions;
+  }
+
   if (typeof extract.defaultNS === 'string') {
     mapped.defaultNamespace = extract.defaultNS;
   } else if (extract.defaultNS === false) {
@@ -1069,8 +1185,9 @@ function resolveMergedNamespaceFilename(outputPattern) {
   if (!ext) {
     return null;
   }
-  const stem = path.basename(normalized, ext).trim();
-  if (!stem || stem.includes('{{') || stem.includes('}}')) {
+  // A per-locale filename (`{{language}}.json`) maps to turbo's `{{lng}}` placeholder
+  const stem = path.basename(normalized, ext).trim().replace(/\{\{language\}\}/g, '{{lng}}');
+  if (!stem || stem.replace(/\{\{lng\}\}/g, '').match(/\{\{|\}\}/)) {
     return null;
   }
   return stem;
@@ -0,0 +1,123 @@
+use anyhow::{bail, Result};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::config::Config;
+use crate::extractor::ExtractedKey;
+
+/// A namespace whose key count is above its configured budget
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BudgetViolation {
+    pub namespace: String,
+    pub key_count: usize,
+    pub budget: usize,
+}
+
+/// Count unique keys per namespace and compare them against `config.budgets`
+pub fn check_budgets(config: &Config, keys: &[ExtractedKey]) -> Vec<BudgetViolation> {
+    if config.budgets.is_empty() {
+        return Vec::new();
+    }
+
+    let mut keys_by_namespace: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for key in keys {
+        let namespace = key
+            .namespace
+            .as_deref()
+            .unwrap_or(config.effective_default_namespace());
+        keys_by_namespace
+            .entry(namespace)
+            .or_default()
+            .insert(key.key.as_str());
+    }
+
+    config
+        .budgets
+        .iter()
+        .filter_map(|(namespace, &budget)| {
+            let key_count = keys_by_namespace
+                .get(namespace.as_str())
+                .map(|keys| keys.len())
+                .unwrap_or(0);
+            (key_count > budget).then(|| BudgetViolation {
+                namespace: namespace.clone(),
+                key_count,
+                budget,
+            })
+        })
+        .collect()
+}
+
+/// Print budget violations and fail when `failOnBudgetExceeded` is enabled
+pub fn report_budgets(config: &Config, violations: &[BudgetViolation]) -> Result<()> {
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    eprintln!();
+    eprintln!(
+        "\x1b[33m⚠ Warning: {} namespace(s) exceed their key budget:\x1b[0m",
+        violations.len()
+    );
+    for violation in violations {
+        eprintln!(
+            "  {}: {} keys (budget {}, over by {})",
+            violation.namespace,
+            violation.key_count,
+            violation.budget,
+            violation.key_count - violation.budget
+        );
+    }
+
+    if config.fail_on_budget_exceeded {
+        bail!(
+            "{} namespace(s) exceed their key budget (failOnBudgetExceeded enabled)",
+            violations.len()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extractor::test_key as key;
+
+    #[test]
+    fn check_budgets_counts_unique_keys_per_namespace() {
+        let mut config = Config::default();
+        config.budgets.insert("common".to_string(), 1);
+        config.budgets.insert("translation".to_string(), 2);
+
+        let keys = vec![
+            key("a", Some("common")),
+            key("a", Some("common")),
+            key("b", Some("common")),
+            key("x", None),
+            key("y", None),
+        ];
+
+        let violations = check_budgets(&config, &keys);
+        assert_eq!(
+            violations,
+            vec![BudgetViolation {
+                namespace: "common".to_string(),
+                key_count: 2,
+                budget: 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn report_budgets_fails_only_when_enabled() {
+        let mut config = Config::default();
+        let violations = vec![BudgetViolation {
+            namespace: "common".to_string(),
+            key_count: 5,
+            budget: 3,
+        }];
+        assert!(report_budgets(&config, &violations).is_ok());
+
+        config.fail_on_budget_exceeded = true;
+        assert!(report_budgets(&config, &violations).is_err());
+    }
+}
@@ -0,0 +1,78 @@
+//! Cooperative cancellation of long extractions, e.g. an editor dropping an
+//! in-flight run when the user types again.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Shared flag checked between files while globbing and parsing. Clones share
+/// the flag, so one clone can be cancelled from another thread.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    deadline: Mutex<Option<Instant>>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Cancel once `timeout` has elapsed; the earliest deadline wins
+    pub fn cancel_after(&self, timeout: Duration) {
+        let deadline = Instant::now() + timeout;
+        let mut current = self
+            .inner
+            .deadline
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if current.is_none_or(|current| deadline < current) {
+            *current = Some(deadline);
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        if self.inner.cancelled.load(Ordering::Relaxed) {
+            return true;
+        }
+        let deadline = *self
+            .inner
+            .deadline
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            self.cancel();
+            return true;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clones_share_cancellation_and_deadlines_expire() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!clone.is_cancelled());
+        token.cancel();
+        assert!(clone.is_cancelled());
+
+        let token = CancellationToken::new();
+        token.cancel_after(Duration::from_secs(3600));
+        assert!(!token.is_cancelled());
+        token.cancel_after(Duration::ZERO);
+        assert!(token.is_cancelled());
+    }
+}
@@ -0,0 +1,930 @@
+#![allow(clippy::too_many_arguments)]
+
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::config::{Config, OutputFormat, PathStyle};
+use crate::extractor::{DynamicKeyPattern, ExtractedKey};
+use crate::json_sync::{
+    self, is_marker_key, marker_patterns, split_merged_namespaces, PreserveMatcher,
+};
+use crate::locale_store::LocaleStore;
+use crate::metadata;
+
+/// Result of dead key detection
+#[derive(Debug, Default)]
+pub struct CleanupResult {
+    pub dead_keys: Vec<DeadKey>,
+    pub removed_count: usize,
+}
+
+/// A dead key found in translation files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadKey {
+    pub file_path: String,
+    pub key_path: String,
+    pub namespace: String,
+}
+
+/// Find dead keys that exist in JSON but not in source code
+pub fn find_dead_keys(
+    locales_dir: &Path,
+    extracted_keys: &[ExtractedKey],
+    default_namespace: &str,
+    namespace_less_mode: bool,
+    merge_namespaces: bool,
+    merged_namespace_prefix: Option<&str>,
+    sharded: bool,
+    preserve_context_variants: bool,
+    context_separator: &str,
+    plural_separator: &str,
+    locale: &str,
+    path_style: PathStyle,
+) -> Result<Vec<DeadKey>> {
+    find_dead_keys_with_store(
+        &LocaleStore::new(),
+        locales_dir,
+        extracted_keys,
+        default_namespace,
+        namespace_less_mode,
+        merge_namespaces,
+        merged_namespace_prefix,
+        sharded,
+        preserve_context_variants,
+        context_separator,
+        plural_separator,
+        locale,
+        path_style,
+    )
+}
+
+/// [`find_dead_keys`] reading the locale files through `store`
+pub fn find_dead_keys_with_store(
+    store: &LocaleStore,
+    locales_dir: &Path,
+    extracted_keys: &[ExtractedKey],
+    default_namespace: &str,
+    namespace_less_mode: bool,
+    merge_namespaces: bool,
+    merged_namespace_prefix: Option<&str>,
+    sharded: bool,
+    preserve_context_variants: bool,
+    context_separator: &str,
+    plural_separator: &str,
+    locale: &str,
+    path_style: PathStyle,
+) -> Result<Vec<DeadKey>> {
+    let mut dead_keys = Vec::new();
+
+    // Build a set of extracted key paths (namespace:key format)
+    let mut extracted_set: HashSet<String> = HashSet::new();
+    let mut object_root_set: HashSet<String> = HashSet::new();
+    for key in extracted_keys {
+        let ns = key.namespace.as_deref().unwrap_or(default_namespace);
+        if let Some(root) = key.key.strip_suffix(".*") {
+            object_root_set.insert(format_key_id(ns, root, namespace_less_mode));
+        } else {
+            extracted_set.insert(format_key_id(ns, &key.key, namespace_less_mode));
+        }
+    }
+
+    // Scan locale directory, or the single `<locale>.json` of a flat layout
+    let locale_dir = locales_dir.join(locale);
+    let flat_file = json_sync::flat_locale_file(locales_dir, locale, "json");
+    let paths: Vec<PathBuf> = if let Some(path) = &flat_file {
+        vec![path.clone()]
+    } else if locale_dir.exists() {
+        std::fs::read_dir(&locale_dir)
+            .with_context(|| format!("Failed to read: {}", locale_dir.display()))?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<_>>()?
+    } else {
+        return Ok(dead_keys);
+    };
+
+    for path in paths {
+        if path.extension().map(|e| e == "json").unwrap_or(false) {
+            let namespace = if flat_file.is_some() {
+                default_namespace.to_string()
+            } else {
+                json_sync::file_namespace(&path, sharded)
+                    .unwrap_or("translation")
+                    .to_string()
+            };
+
+            let Some(json) = store.get(&path)? else {
+                continue;
+            };
+
+            if let Value::Object(obj) = json.as_ref() {
+                let file_path = crate::paths::display(&path, path_style);
+                if merge_namespaces && !namespace_less_mode {
+                    for (root_ns, value) in
+                        split_merged_namespaces(obj.clone(), merged_namespace_prefix)
+                    {
+                        if is_marker_key(&root_ns) {
+                            continue;
+                        }
+                        match value {
+                            Value::Object(nested) => {
+                                find_dead_keys_in_object(
+                                    &nested,
+                                    &root_ns,
+                                    "",
+                                    &extracted_set,
+                                    &object_root_set,
+                                    namespace_less_mode,
+                                    preserve_context_variants,
+                                    context_separator,
+                                    plural_separator,
+                                    &[],
+                                    &file_path,
+                                    &mut dead_keys,
+                                );
+                            }
+                            Value::String(_) => {
+                                let full_key = format_key_id(&root_ns, "", namespace_less_mode);
+                                if !extracted_set.contains(&full_key) {
+                                    dead_keys.push(DeadKey {
+                                        file_path: file_path.clone(),
+                                        key_path: root_ns.clone(),
+                                        namespace: root_ns.clone(),
+                                    });
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                } else {
+                    find_dead_keys_in_object(
+                        obj,
+                        &namespace,
+                        "",
+                        &extracted_set,
+                        &object_root_set,
+                        namespace_less_mode,
+                        preserve_context_variants,
+                        context_separator,
+                        plural_separator,
+                        &[],
+                        &file_path,
+                        &mut dead_keys,
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(dead_keys)
+}
+
+/// Recursively find dead keys in a JSON object
+fn find_dead_keys_in_object(
+    obj: &Map<String, Value>,
+    namespace: &str,
+    prefix: &str,
+    extracted_set: &HashSet<String>,
+    object_root_set: &HashSet<String>,
+    namespace_less_mode: bool,
+    preserve_context_variants: bool,
+    context_separator: &str,
+    plural_separator: &str,
+    inherited_markers: &[glob::Pattern],
+    file_path: &str,
+    dead_keys: &mut Vec<DeadKey>,
+) {
+    let mut markers = inherited_markers.to_vec();
+    markers.extend(marker_patterns(obj, prefix, "."));
+
+    for (key, value) in obj {
+        let key_path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        if is_marker_key(key) || markers.iter().any(|pattern| pattern.matches(&key_path)) {
+            continue;
+        }
+
+        match value {
+            Value::Object(nested) => {
+                // Recurse into nested objects
+                find_dead_keys_in_object(
+                    nested,
+                    namespace,
+                    &key_path,
+                    extracted_set,
+                    object_root_set,
+                    namespace_less_mode,
+                    preserve_context_variants,
+                    context_separator,
+                    plural_separator,
+                    &markers,
+                    file_path,
+                    dead_keys,
+                );
+            }
+            Value::String(_) => {
+                // Check if this leaf key exists in extracted keys
+                let full_key = format_key_id(namespace, &key_path, namespace_less_mode);
+                let covered_by_object_root = object_root_set
+                    .iter()
+                    .any(|root| full_key == *root || full_key.starts_with(&format!("{}.", root)));
+                let covered_by_context_variant = preserve_context_variants
+                    && is_covered_by_context_variant(
+                        namespace,
+                        &key_path,
+                        extracted_set,
+                        namespace_less_mode,
+                        context_separator,
+                        plural_separator,
+                    );
+                if !extracted_set.contains(&full_key)
+                    && !covered_by_object_root
+                    && !covered_by_context_variant
+                {
+                    dead_keys.push(DeadKey {
+                        file_path: file_path.to_string(),
+                        key_path: key_path.clone(),
+                        namespace: namespace.to_string(),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Whether stripping trailing context/plural segments (in either order) reaches an extracted key
+fn is_covered_by_context_variant(
+    namespace: &str,
+    key_path: &str,
+    extracted_set: &HashSet<String>,
+    namespace_less_mode: bool,
+    context_separator: &str,
+    plural_separator: &str,
+) -> bool {
+    let separators: Vec<&str> = [context_separator, plural_separator]
+        .into_iter()
+        .filter(|sep| !sep.is_empty())
+        .collect();
+    if separators.is_empty() {
+        return false;
+    }
+
+    let mut candidate = key_path;
+    while let Some(cut) = separators
+        .iter()
+        .filter_map(|sep| candidate.rfind(sep))
+        .max()
+    {
+        candidate = &candidate[..cut];
+        let full_base = format_key_id(namespace, candidate, namespace_less_mode);
+        if extracted_set.contains(&full_base) {
+            return true;
+        }
+    }
+    false
+}
+
+fn format_key_id(namespace: &str, key_path: &str, namespace_less_mode: bool) -> String {
+    if namespace_less_mode {
+        key_path.to_string()
+    } else {
+        format!("{}:{}", namespace, key_path)
+    }
+}
+
+/// A `preservePatterns` entry suggested for dead keys a dynamic key may produce
+#[derive(Debug)]
+pub struct PreserveSuggestion {
+    pub pattern: String,
+    /// `file:line` of each dynamic template literal producing the pattern
+    pub sites: Vec<String>,
+    /// Dead keys the pattern covers
+    pub keys: Vec<DeadKey>,
+}
+
+/// Drop dead keys covered by `preserve_patterns`, and set aside those a dynamic
+/// template literal key may produce as preserve suggestions rather than dead keys.
+pub fn triage_dead_keys(
+    dead_keys: Vec<DeadKey>,
+    preserve_patterns: &[String],
+    dynamic_keys: &[DynamicKeyPattern],
+    ns_separator: &str,
+) -> Result<(Vec<DeadKey>, Vec<PreserveSuggestion>)> {
+    let preserve = PreserveMatcher::new(preserve_patterns, ns_separator)?;
+
+    let mut suggestions: Vec<PreserveSuggestion> = Vec::new();
+    for dynamic in dynamic_keys {
+        let site = format!("{}:{}", dynamic.file_path, dynamic.line);
+        match suggestions
+            .iter_mut()
+            .find(|s| s.pattern == dynamic.pattern)
+        {
+            Some(suggestion) => suggestion.sites.push(site),
+            None => suggestions.push(PreserveSuggestion {
+                pattern: dynamic.pattern.clone(),
+                sites: vec![site],
+                keys: Vec::new(),
+            }),
+        }
+    }
+    suggestions.sort_by(|a, b| a.pattern.cmp(&b.pattern));
+    let matchers = suggestions
+        .iter()
+        .map(|s| PreserveMatcher::new(std::slice::from_ref(&s.pattern), ns_separator))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut remaining = Vec::new();
+    for dk in dead_keys {
+        if preserve.matches(&dk.namespace, &dk.key_path) {
+            continue;
+        }
+        match matchers
+            .iter()
+            .position(|m| m.matches(&dk.namespace, &dk.key_path))
+        {
+            Some(index) => suggestions[index].keys.push(dk),
+            None => remaining.push(dk),
+        }
+    }
+    suggestions.retain(|s| !s.keys.is_empty());
+
+    Ok((remaining, suggestions))
+}
+
+/// Split `requested` keys into those that are still dead (taken from
+/// `dead_keys`) and those that are not, so a stale selection never removes a
+/// key that is in use again.
+pub fn select_dead_keys(
+    dead_keys: &[DeadKey],
+    requested: &[DeadKey],
+) -> (Vec<DeadKey>, Vec<DeadKey>) {
+    let mut selected = Vec::new();
+    let mut skipped = Vec::new();
+    for key in requested {
+        let dead = dead_keys
+            .iter()
+            .find(|dead| dead.file_path == key.file_path && dead.key_path == key.key_path);
+        match dead {
+            Some(dead) if !selected.contains(dead) => selected.push(dead.clone()),
+            Some(_) => {}
+            None => skipped.push(key.clone()),
+        }
+    }
+    (selected, skipped)
+}
+
+/// The innermost of the locale `roots` holding `path`
+pub fn root_of<'a>(roots: &[&'a str], path: &Path) -> Option<&'a str> {
+    roots
+        .iter()
+        .filter(|root| path.starts_with(root))
+        .max_by_key(|root| root.len())
+        .copied()
+}
+
+/// Remove the `dead_keys` of the locale files in `locales_dir` from those files,
+/// rewriting them in the configured format and style. Keys of files elsewhere
+/// are left alone.
+pub fn purge_dead_keys(
+    config: &Config,
+    locales_dir: &Path,
+    dead_keys: &[DeadKey],
+) -> Result<usize> {
+    let mut keys_by_file: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for dk in dead_keys {
+        if Path::new(&dk.file_path).starts_with(locales_dir) {
+            keys_by_file
+                .entry(dk.file_path.as_str())
+                .or_default()
+                .push(dk.key_path.as_str());
+        }
+    }
+
+    let format = config.output_format();
+    let mut removed_count = 0;
+    for (file_path, key_paths) in keys_by_file {
+        let path = Path::new(file_path);
+        if !path.exists() {
+            continue;
+        }
+
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read: {}", path.display()))?;
+        let Value::Object(mut map) = json_sync::parse_locale_value_str(&content, format, path)?
+        else {
+            continue;
+        };
+        let removed = key_paths
+            .iter()
+            .filter(|key_path| remove_nested_key(&mut map, key_path))
+            .count();
+        if removed == 0 {
+            continue;
+        }
+        removed_count += removed;
+
+        let style = (format == OutputFormat::Json)
+            .then(|| json_sync::configured_json_style(config, &content));
+        json_sync::write_locale_file(path, &map, format, config.key_sort, style.as_ref())?;
+    }
+
+    Ok(removed_count)
+}
+
+/// [`purge_dead_keys`] in each of the locale `roots` under `removalGraceDays`:
+/// dead keys still inside their grace period stay in the locale files and are
+/// recorded as pending in `metadataFile`. Returns the number of keys removed
+/// and the keys kept.
+pub fn purge_expired_dead_keys(
+    config: &Config,
+    roots: &[&str],
+    dead_keys: &[DeadKey],
+) -> Result<(usize, Vec<DeadKey>)> {
+    // Metadata keys use the configured separator, dead key paths always "."
+    let id = |dk: &DeadKey| {
+        let key = match config.key_separator.as_str() {
+            "" | "." => dk.key_path.clone(),
+            separator => dk.key_path.replace('.', separator),
+        };
+        (dk.namespace.clone(), key)
+    };
+    let expired = metadata::expired_dead_keys(config)?;
+    let (removable, kept): (Vec<DeadKey>, Vec<DeadKey>) =
+        dead_keys.iter().cloned().partition(|dk| {
+            expired
+                .as_ref()
+                .is_none_or(|expired| expired.contains(&id(dk)))
+        });
+    let mut removed = 0;
+    for root in roots {
+        // A root nested in another one purges its own files
+        let in_root: Vec<DeadKey> = removable
+            .iter()
+            .filter(|dk| root_of(roots, Path::new(&dk.file_path)) == Some(*root))
+            .cloned()
+            .collect();
+        removed += purge_dead_keys(config, Path::new(root), &in_root)?;
+    }
+    // Only extraction knows which keys are in use again
+    metadata::record_removals(
+        config,
+        |_, _| false,
+        &kept.iter().map(id).collect(),
+        &removable.iter().map(id).collect(),
+    )?;
+    Ok((removed, kept))
+}
+
+/// Whether a locale file's content holds no translations: `{}`, or nothing
+/// but empty objects
+pub fn is_empty_locale(value: &Value) -> bool {
+    match value {
+        Value::Object(map) => map.values().all(is_empty_locale),
+        _ => false,
+    }
+}
+
+/// Of `files`, those that exist and hold no translations
+pub fn empty_locale_files(config: &Config, files: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut empty = Vec::new();
+    for path in files {
+        if !path.is_file() {
+            continue;
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read locale file: {}", path.display()))?;
+        let value = json_sync::parse_locale_value_str(&content, config.output_format(), path)?;
+        if is_empty_locale(&value) {
+            empty.push(path.clone());
+        }
+    }
+    Ok(empty)
+}
+
+/// Delete the empty locale `files`, then the directories below `locales_dir`
+/// they leave empty (`removeEmptyFiles`). Returns the deleted paths; with
+/// `dry_run` nothing is deleted and only the files are returned.
+pub fn delete_empty_files(
+    locales_dir: &Path,
+    files: &[PathBuf],
+    dry_run: bool,
+) -> Result<Vec<PathBuf>> {
+    let mut deleted = Vec::new();
+    for path in files {
+        if dry_run {
+            deleted.push(path.clone());
+            continue;
+        }
+        if !path.is_file() {
+            continue;
+        }
+        std::fs::remove_file(path)
+            .with_context(|| format!("Failed to delete: {}", path.display()))?;
+        deleted.push(path.clone());
+
+        let mut dir = path.parent();
+        while let Some(current) =
+            dir.filter(|dir| dir.starts_with(locales_dir) && *dir != locales_dir)
+        {
+            let is_empty = std::fs::read_dir(current)
+                .map(|mut entries| entries.next().is_none())
+                .unwrap_or(false);
+            if !is_empty {
+                break;
+            }
+            std::fs::remove_dir(current)
+                .with_context(|| format!("Failed to delete: {}", current.display()))?;
+            deleted.push(current.to_path_buf());
+            dir = current.parent();
+        }
+    }
+    Ok(deleted)
+}
+
+/// Remove a nested key from a JSON object
+fn remove_nested_key(obj: &mut Map<String, Value>, key_path: &str) -> bool {
+    let parts: Vec<&str> = key_path.split('.').collect();
+
+    if parts.is_empty() {
+        return false;
+    }
+
+    if parts.len() == 1 {
+        return obj.remove(parts[0]).is_some();
+    }
+
+    // Navigate to parent
+    let mut current = obj;
+    for part in &parts[..parts.len() - 1] {
+        match current.get_mut(*part) {
+            Some(Value::Object(nested)) => {
+                current = nested;
+            }
+            _ => return false,
+        }
+    }
+
+    // Remove the final key
+    let last_key = parts[parts.len() - 1];
+    current.remove(last_key).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_nested_key_simple() {
+        let mut obj = Map::new();
+        obj.insert("hello".to_string(), Value::String("world".to_string()));
+
+        assert!(remove_nested_key(&mut obj, "hello"));
+        assert!(!obj.contains_key("hello"));
+    }
+
+    #[test]
+    fn test_remove_nested_key_deep() {
+        let mut inner = Map::new();
+        inner.insert("submit".to_string(), Value::String("Submit".to_string()));
+        inner.insert("cancel".to_string(), Value::String("Cancel".to_string()));
+
+        let mut obj = Map::new();
+        obj.insert("button".to_string(), Value::Object(inner));
+
+        assert!(remove_nested_key(&mut obj, "button.submit"));
+
+        let button = obj.get("button").unwrap().as_object().unwrap();
+        assert!(!button.contains_key("submit"));
+        assert!(button.contains_key("cancel"));
+    }
+
+    #[test]
+    fn test_context_variant_is_preserved_when_base_key_exists() {
+        let mut extracted_set = HashSet::new();
+        extracted_set.insert("common:friend".to_string());
+
+        assert!(is_covered_by_context_variant(
+            "common",
+            "friend_male",
+            &extracted_set,
+            false,
+            "_",
+            "_",
+        ));
+        assert!(is_covered_by_context_variant(
+            "common",
+            "friend_male_one",
+            &extracted_set,
+            false,
+            "_",
+            "_",
+        ));
+    }
+
+    #[test]
+    fn test_context_variant_is_preserved_with_plural_before_context() {
+        let mut extracted_set = HashSet::new();
+        extracted_set.insert("common:friend".to_string());
+
+        assert!(is_covered_by_context_variant(
+            "common",
+            "friend#one_male",
+            &extracted_set,
+            false,
+            "_",
+            "#",
+        ));
+        assert!(!is_covered_by_context_variant(
+            "common",
+            "enemy#one_male",
+            &extracted_set,
+            false,
+            "_",
+            "#",
+        ));
+    }
+
+    #[test]
+    fn test_find_dead_keys_skips_in_file_markers() {
+        let tmp = tempfile::tempdir().unwrap();
+        let locale_dir = tmp.path().join("en");
+        std::fs::create_dir_all(&locale_dir).unwrap();
+        std::fs::write(
+            locale_dir.join("translation.json"),
+            r#"{
+  "//": "comment",
+  "__preserve__": ["dynamic.*"],
+  "dynamic": { "a": "A" },
+  "stale": "Old"
+}"#,
+        )
+        .unwrap();
+
+        let dead = find_dead_keys(
+            tmp.path(),
+            &[],
+            "translation",
+            false,
+            false,
+            None,
+            false,
+            false,
+            "_",
+            "_",
+            "en",
+            PathStyle::Native,
+        )
+        .unwrap();
+
+        let paths: Vec<&str> = dead.iter().map(|d| d.key_path.as_str()).collect();
+        assert_eq!(paths, vec!["stale"]);
+    }
+
+    #[test]
+    fn test_find_dead_keys_with_merge_namespaces_layout() {
+        let tmp = tempfile::tempdir().unwrap();
+        let locale_dir = tmp.path().join("en");
+        std::fs::create_dir_all(&locale_dir).unwrap();
+        std::fs::write(
+            locale_dir.join("all.json"),
+            r#"{
+  "common": { "hello": "Hello", "stale": "Old" },
+  "home": { "title": "Title" }
+}"#,
+        )
+        .unwrap();
+
+        let extracted_keys = vec![
+            ExtractedKey {
+                key: "hello".to_string(),
+                namespace: Some("common".to_string()),
+                default_value: None,
+            },
+            ExtractedKey {
+                key: "title".to_string(),
+                namespace: Some("home".to_string()),
+                default_value: None,
+            },
+        ];
+
+        let dead = find_dead_keys(
+            tmp.path(),
+            &extracted_keys,
+            "translation",
+            false,
+            true,
+            None,
+            false,
+            false,
+            "_",
+            "_",
+            "en",
+            PathStyle::Native,
+        )
+        .unwrap();
+
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].namespace, "common");
+        assert_eq!(dead[0].key_path, "stale");
+    }
+
+    #[test]
+    fn test_find_dead_keys_with_prefixed_merge_layout() {
+        let tmp = tempfile::tempdir().unwrap();
+        let locale_dir = tmp.path().join("en");
+        std::fs::create_dir_all(&locale_dir).unwrap();
+        std::fs::write(
+            locale_dir.join("all.json"),
+            r#"{
+  "common:button.save": "Save",
+  "common:stale": "Old",
+  "home:title": "Title"
+}"#,
+        )
+        .unwrap();
+
+        let extracted_keys = vec![
+            ExtractedKey {
+                key: "button.save".to_string(),
+                namespace: Some("common".to_string()),
+                default_value: None,
+            },
+            ExtractedKey {
+                key: "title".to_string(),
+                namespace: Some("home".to_string()),
+                default_value: None,
+            },
+        ];
+
+        let dead = find_dead_keys(
+            tmp.path(),
+            &extracted_keys,
+            "translation",
+            false,
+            true,
+            Some(":"),
+            false,
+            false,
+            "_",
+            "_",
+            "en",
+            PathStyle::Native,
+        )
+        .unwrap();
+
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].namespace, "common");
+        assert_eq!(dead[0].key_path, "stale");
+    }
+
+    #[test]
+    fn test_select_dead_keys_skips_keys_no_longer_dead() {
+        let dead = |key_path: &str| DeadKey {
+            file_path: "locales/en/common.json".to_string(),
+            key_path: key_path.to_string(),
+            namespace: "common".to_string(),
+        };
+        let mut outside = dead("old.banner");
+        outside.file_path = "package.json".to_string();
+
+        let (selected, skipped) = select_dead_keys(
+            &[dead("old.banner"), dead("old.footer")],
+            &[
+                dead("old.banner"),
+                dead("old.banner"),
+                dead("home.title"),
+                outside,
+            ],
+        );
+
+        assert_eq!(selected, vec![dead("old.banner")]);
+        assert_eq!(skipped.len(), 2);
+        assert_eq!(skipped[0].key_path, "home.title");
+        assert_eq!(skipped[1].file_path, "package.json");
+    }
+
+    #[test]
+    fn test_triage_dead_keys_suggests_patterns_for_dynamic_keys() {
+        let dead = |namespace: &str, key_path: &str| DeadKey {
+            file_path: format!("locales/en/{}.json", namespace),
+            key_path: key_path.to_string(),
+            namespace: namespace.to_string(),
+        };
+        let dynamic = |pattern: &str, line: usize| DynamicKeyPattern {
+            file_path: "src/app.ts".to_string(),
+            line,
+            pattern: pattern.to_string(),
+        };
+
+        let (remaining, suggestions) = triage_dead_keys(
+            vec![
+                dead("translation", "status.active"),
+                dead("translation", "status.paused"),
+                dead("translation", "legacy.title"),
+                dead("translation", "old.banner"),
+                dead("admin", "role.owner"),
+            ],
+            &["legacy.*".to_string()],
+            &[
+                dynamic("status.*", 3),
+                dynamic("status.*", 9),
+                dynamic("admin:role.*", 4),
+                dynamic("menu.*", 5),
+            ],
+            ":",
+        )
+        .unwrap();
+
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].key_path, "old.banner");
+
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0].pattern, "admin:role.*");
+        assert_eq!(suggestions[0].keys.len(), 1);
+        assert_eq!(suggestions[1].pattern, "status.*");
+        assert_eq!(suggestions[1].sites, vec!["src/app.ts:3", "src/app.ts:9"]);
+        assert_eq!(suggestions[1].keys.len(), 2);
+    }
+
+    #[test]
+    fn test_purge_dead_keys_in_every_root_with_the_configured_style() {
+        let tmp = tempfile::tempdir().unwrap();
+        let app = tmp.path().join("locales");
+        let ui = tmp.path().join("packages/ui/locales");
+        for root in [&app, &ui] {
+            std::fs::create_dir_all(root.join("en")).unwrap();
+            std::fs::write(
+                root.join("en/translation.json"),
+                "{\n  \"kept\": \"Kept\",\n  \"old\": \"Old\"\n}\n",
+            )
+            .unwrap();
+        }
+        let dead = |root: &Path| DeadKey {
+            file_path: root.join("en/translation.json").display().to_string(),
+            key_path: "old".to_string(),
+            namespace: "translation".to_string(),
+        };
+        let dead_keys = vec![dead(&app), dead(&ui)];
+        let mut config = Config::default();
+        config.minify = true;
+
+        // Only the files inside the given root are touched
+        assert_eq!(purge_dead_keys(&config, &ui, &dead_keys).unwrap(), 1);
+        assert_eq!(
+            std::fs::read_to_string(ui.join("en/translation.json")).unwrap(),
+            r#"{"kept":"Kept"}"#
+        );
+        assert!(std::fs::read_to_string(app.join("en/translation.json"))
+            .unwrap()
+            .contains("old"));
+
+        let roots = [app.to_str().unwrap(), ui.to_str().unwrap()];
+        let (removed, kept) = purge_expired_dead_keys(&config, &roots, &dead_keys).unwrap();
+        assert_eq!((removed, kept), (1, Vec::new()));
+        assert_eq!(
+            std::fs::read_to_string(app.join("en/translation.json")).unwrap(),
+            r#"{"kept":"Kept"}"#
+        );
+    }
+
+    #[test]
+    fn test_delete_empty_files_removes_emptied_locale_dirs() {
+        assert!(is_empty_locale(&serde_json::json!({ "a": { "b": {} } })));
+        assert!(!is_empty_locale(&serde_json::json!({ "a": { "b": "" } })));
+
+        let tmp = tempfile::tempdir().unwrap();
+        let locales = tmp.path().join("locales");
+        std::fs::create_dir_all(locales.join("en")).unwrap();
+        std::fs::create_dir_all(locales.join("de")).unwrap();
+        std::fs::write(locales.join("en/legacy.json"), "{}").unwrap();
+        std::fs::write(locales.join("en/common.json"), r#"{"ok":"OK"}"#).unwrap();
+        std::fs::write(locales.join("de/legacy.json"), r#"{"old":{}}"#).unwrap();
+
+        let config = Config::default();
+        let files = vec![
+            locales.join("en/legacy.json"),
+            locales.join("en/common.json"),
+            locales.join("de/legacy.json"),
+            locales.join("fr/missing.json"),
+        ];
+        let empty = empty_locale_files(&config, &files).unwrap();
+        assert_eq!(empty, vec![files[0].clone(), files[2].clone()]);
+
+        let planned = delete_empty_files(&locales, &empty, true).unwrap();
+        assert_eq!(planned, empty);
+        assert!(locales.join("en/legacy.json").exists());
+
+        let deleted = delete_empty_files(&locales, &empty, false).unwrap();
+        assert_eq!(
+            deleted,
+            vec![files[0].clone(), files[2].clone(), locales.join("de")]
+        );
+        assert!(locales.join("en/common.json").exists());
+        assert!(locales.exists());
+    }
+}
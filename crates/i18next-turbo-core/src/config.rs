@@ -0,0 +1,3440 @@
+#![allow(clippy::items_after_test_module)]
+
+use anyhow::{bail, Context, Result};
+use glob::Pattern;
+use icu_locid::Locale;
+use icu_plurals::{PluralCategory, PluralRules};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Configuration for i18next-turbo
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Framework preset providing defaults for options the config does not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<Preset>,
+
+    /// Glob patterns for input files (e.g., ["src/**/*.tsx", "src/**/*.ts"])
+    #[serde(default = "default_input")]
+    pub input: Vec<String>,
+
+    /// Output directory for translation files
+    #[serde(default = "default_output")]
+    pub output: String,
+
+    /// Per-package output roots; the first entry whose `match` glob matches a
+    /// source file receives its keys, unmatched files use `output`
+    #[serde(default)]
+    pub outputs: Vec<OutputTarget>,
+
+    /// Output format for translation files (json, json5, ...)
+    #[serde(default)]
+    pub output_format: OutputFormat,
+
+    /// Store each locale in one `<output>/<locale>.<ext>` file instead of a
+    /// `<output>/<locale>/` directory (needs `mergeNamespaces` or namespace-less mode)
+    #[serde(default)]
+    pub flat_locale_files: bool,
+
+    /// List of language codes (e.g., ["en", "ja"])
+    #[serde(default = "default_locales")]
+    pub locales: Vec<String>,
+
+    /// Default namespace (`false`/empty with `nsSeparator: false` enables namespace-less mode)
+    #[serde(
+        default = "default_namespace",
+        deserialize_with = "deserialize_optional_default_namespace"
+    )]
+    pub default_namespace: String,
+
+    /// Function names to extract (e.g., ["t", "i18n.t"])
+    #[serde(default = "default_functions")]
+    pub functions: Vec<String>,
+
+    /// Hook-like function names that return a translation function (t)
+    /// Supports string entries or objects with custom argument positions.
+    #[serde(default = "default_use_translation_names")]
+    pub use_translation_names: Vec<UseTranslationName>,
+
+    /// Message descriptor factories to extract from (e.g., ["defineMessages"]).
+    /// Each `{ id, defaultMessage }` descriptor becomes a key; empty disables this mode.
+    #[serde(default)]
+    pub message_factory_functions: Vec<String>,
+
+    /// Helpers returning a key rather than a translation (e.g., ["k"]).
+    /// `k('ns:key')` is extracted like `t('ns:key')`, without options.
+    #[serde(default)]
+    pub key_producing_functions: Vec<String>,
+
+    /// Key separator (e.g., "." for "button.submit")
+    #[serde(default = "default_key_separator")]
+    pub key_separator: String,
+
+    /// Namespace separator (e.g., ":" for "common:greeting")
+    /// Set to false or "" to disable namespace separation
+    #[serde(
+        default = "default_ns_separator",
+        deserialize_with = "deserialize_optional_separator"
+    )]
+    pub ns_separator: String,
+
+    /// Context separator (e.g., "_" for "friend_male")
+    #[serde(default = "default_context_separator")]
+    pub context_separator: String,
+
+    /// Plural separator (e.g., "_" for "item_one")
+    #[serde(default = "default_plural_separator")]
+    pub plural_separator: String,
+
+    /// Order of context and plural suffixes: "context-plural" (`key_male_one`, default)
+    /// or "plural-context" (`key_one_male`)
+    #[serde(default)]
+    pub context_plural_order: ContextPluralOrder,
+
+    /// Plural suffixes to generate (e.g., ["one", "other"] for English)
+    /// Supported values: zero, one, two, few, many, other
+    /// Examples:
+    ///   - English: ["one", "other"]
+    ///   - Arabic: ["zero", "one", "two", "few", "many", "other"]
+    ///   - Russian: ["one", "few", "many", "other"]
+    ///   - Japanese: ["other"] (no plural forms)
+    #[serde(default = "default_plural_suffixes")]
+    pub plural_suffixes: Vec<String>,
+
+    /// Whether to completely disable plural key generation
+    /// When true, keys with `count` will not generate `_one`, `_other` etc.
+    /// Default: false
+    #[serde(default)]
+    pub disable_plurals: bool,
+
+    /// Whether to generate base plural forms (key without suffix) alongside plural keys
+    /// When true, generates both "item" and "item_one", "item_other"
+    /// Default: false
+    #[serde(default)]
+    pub generate_base_plural_forms: bool,
+
+    /// Whether to extract keys from comments (e.g., // t('key'))
+    /// Default: true
+    #[serde(default = "default_extract_from_comments")]
+    pub extract_from_comments: bool,
+
+    /// Severity of translation calls with a dynamic template literal key, such
+    /// as t(`status.${state}`): off, warn (default) or error
+    #[serde(default)]
+    pub dynamic_key_severity: DiagnosticSeverity,
+
+    /// Per-file dynamic key severities; the first entry whose `match` glob
+    /// matches a source file wins, other files use `dynamicKeySeverity`
+    #[serde(default)]
+    pub dynamic_key_rules: Vec<SeverityRule>,
+
+    /// Whether to auto-detect plural categories from locale rules
+    #[serde(default = "default_use_locale_plural_rules")]
+    pub use_locale_plural_rules: bool,
+
+    /// Plural key format: "v4" (CLDR suffixes, default) or the legacy "v3"/"v2"
+    /// format (`key`/`key_plural`, or `key_0`..`key_N` for other form counts)
+    #[serde(default)]
+    pub plural_compat: PluralCompat,
+
+    /// Plural keys written for single-category locales, per locale: "base"
+    /// (default), "other" or "copyPrimary" (e.g. `{ "ja": "other" }`)
+    #[serde(default)]
+    pub single_category_plurals: BTreeMap<String, SingleCategoryPlural>,
+
+    /// Files/globs to ignore when extracting
+    #[serde(default)]
+    pub ignore: Vec<String>,
+
+    /// Globs of generated sources (codegen output, vendored bundles). Their
+    /// keys and parse errors are always skipped silently.
+    #[serde(default)]
+    pub generated_files: Vec<String>,
+
+    /// Glob patterns for keys that should always be preserved when pruning
+    #[serde(default)]
+    pub preserve_patterns: Vec<String>,
+
+    /// Key globs of a targeted run (`--filter-key`): only matching keys are added
+    /// or pruned. Set from the command line, never read from config files.
+    #[serde(skip)]
+    pub key_filter: Vec<String>,
+
+    /// Keep context/plural variants when base key exists (e.g., friend -> friend_male)
+    #[serde(default)]
+    pub preserve_context_variants: bool,
+
+    /// Whether to remove keys that were not found in source files (default: true)
+    #[serde(default = "default_remove_unused_keys")]
+    pub remove_unused_keys: bool,
+
+    /// Merge all namespaces into a single locale file
+    #[serde(default)]
+    pub merge_namespaces: bool,
+
+    /// Output filename (without extension) used when `mergeNamespaces` is enabled.
+    /// Example: "translation" writes `<output>/<locale>/translation.<ext>`;
+    /// `{{lng}}` is replaced with the locale (e.g. "messages.{{lng}}")
+    #[serde(default)]
+    pub merged_namespace_filename: Option<String>,
+
+    /// How namespaces are stored in a merged file: "nested" (`{ "common": { "ok": .. } }`)
+    /// or "prefixed" (`{ "common:ok": .. }`, using `nsSeparator`)
+    #[serde(default)]
+    pub merged_namespace_layout: MergedNamespaceLayout,
+
+    /// Maximum object nesting of keys inside a merged file's namespace; deeper key
+    /// segments are kept joined with `keySeparator` (e.g. 1 writes `"button.save"` flat)
+    #[serde(default)]
+    pub merged_nesting_depth: Option<usize>,
+
+    /// Default value to use when no explicit defaultValue is provided
+    #[serde(default)]
+    pub default_value: Option<String>,
+
+    /// Trans components to detect (names, or objects with custom attribute names)
+    #[serde(default = "default_trans_components")]
+    pub trans_components: Vec<TransComponent>,
+
+    /// HTML tags that should be preserved inside Trans components
+    #[serde(default = "default_trans_keep_nodes")]
+    pub trans_keep_basic_html_nodes_for: Vec<String>,
+
+    /// Decode HTML entities (`&nbsp;`, `&amp;`, ...) in Trans children as JSX
+    /// does. Disable to keep them as written, for components rendered with
+    /// `shouldUnescape`. Default: true
+    #[serde(default = "default_trans_unescape")]
+    pub trans_unescape: bool,
+
+    /// Whether keys derived from Trans children are decoded too; when false
+    /// only default values are. Default: true
+    #[serde(default = "default_trans_unescape")]
+    pub trans_unescape_keys: bool,
+
+    /// Prefix for nested translation calls inside strings (default: "$t(")
+    #[serde(default = "default_nesting_prefix")]
+    pub nesting_prefix: String,
+
+    /// Suffix for nested translation calls inside strings (default: ")")
+    #[serde(default = "default_nesting_suffix")]
+    pub nesting_suffix: String,
+
+    /// Separator between nested key and nested options (default: ",")
+    #[serde(default = "default_nesting_options_separator")]
+    pub nesting_options_separator: String,
+
+    /// Interpolation prefix used in placeholder serialization (default: "{{")
+    #[serde(default = "default_interpolation_prefix")]
+    pub interpolation_prefix: String,
+
+    /// Interpolation suffix used in placeholder serialization (default: "}}")
+    #[serde(default = "default_interpolation_suffix")]
+    pub interpolation_suffix: String,
+
+    /// Interpolation formatter names (`{{price, currency}}`) allowed besides
+    /// i18next's built-in ones; when set, `check --nesting` reports any other
+    #[serde(default)]
+    pub allowed_formatters: Option<Vec<String>>,
+
+    /// Type generation configuration
+    #[serde(default)]
+    pub types: TypesConfig,
+
+    /// Locize integration settings
+    #[serde(default)]
+    pub locize: Option<LocizeConfig>,
+
+    /// Translation management system used by `pull` and `push`
+    #[serde(default)]
+    pub tms: Option<TmsConfig>,
+
+    /// Primary language for type generation and sync operations
+    /// When not set, the first locale in the `locales` array is used
+    #[serde(default)]
+    pub primary_language: Option<String>,
+
+    /// Explicit secondary languages for sync operations
+    /// When not set, `locales` except primary language are used
+    #[serde(default)]
+    pub secondary_languages: Option<Vec<String>>,
+
+    /// JSON indentation setting
+    /// Examples: 2 (spaces), 4 (spaces), "\t" (tab)
+    /// When not set, existing file's indentation is preserved or defaults to 2 spaces
+    #[serde(default)]
+    pub indentation: Option<Indentation>,
+
+    /// Write JSON locale files on a single line, without indentation or trailing
+    /// newline. Usually set per environment (`I18NEXT_TURBO_MINIFY=true` or `--minify`)
+    #[serde(default)]
+    pub minify: bool,
+
+    /// Order of keys in written locale files: `byte` (default, code point
+    /// order), `folded` (accent- and case-insensitive, the same for every
+    /// locale) or `natural` (`folded` with digit runs compared by value,
+    /// `step2` before `step10`)
+    #[serde(default)]
+    pub key_sort: KeySort,
+
+    /// Line endings of written JSON locale files: `auto` (default, keep the
+    /// file's own), `lf` or `crlf`
+    #[serde(default)]
+    pub end_of_line: EndOfLine,
+
+    /// Follow symlinks when walking `input` globs and locale directories.
+    /// Symlinks resolving outside the project root are skipped either way.
+    #[serde(default = "default_follow_symlinks")]
+    pub follow_symlinks: bool,
+
+    /// Path separators in reports: `native` (default) or `forward` (`/` everywhere)
+    #[serde(default)]
+    pub path_style: PathStyle,
+
+    /// Split a namespace file with more keys than this into `<ns>.shard-1.json`,
+    /// `<ns>.shard-2.json`, ...; top-level keys are never split across shards. Readers
+    /// merge the shards back into one namespace.
+    #[serde(default)]
+    pub shard_max_keys: Option<usize>,
+
+    /// Delete locale files left without keys by `check --remove` or
+    /// `sync --remove-unused`, and the locale directories they leave empty
+    #[serde(default)]
+    pub remove_empty_files: bool,
+
+    /// What a NAPI call does while another call in the same process writes to
+    /// the same locale directory: `queue` (default) waits, `reject` fails
+    #[serde(default)]
+    pub concurrent_runs: ConcurrentRuns,
+
+    /// Lint behavior configuration
+    #[serde(default)]
+    pub lint: LintConfig,
+
+    /// Log level (`error`, `warn`, `info`, `debug`)
+    #[serde(default = "default_log_level")]
+    pub log_level: String,
+
+    /// Maximum number of keys allowed per namespace (e.g., { "common": 300 })
+    #[serde(default)]
+    pub budgets: BTreeMap<String, usize>,
+
+    /// Fail `extract`/`check` when a namespace exceeds its budget (default: warn only)
+    #[serde(default)]
+    pub fail_on_budget_exceeded: bool,
+
+    /// Fail `check` when a namespace used in source has no file in a locale or
+    /// looks misspelled (default: warn only)
+    #[serde(default)]
+    pub strict_namespaces: bool,
+
+    /// Minimum translation completeness per locale for `gate` (e.g., { "de": 0.95 })
+    #[serde(default)]
+    pub release_gates: BTreeMap<String, f64>,
+
+    /// Sidecar JSON file recording per-key metadata such as when each key was
+    /// first extracted and last seen (e.g. "locales/.i18next-turbo-meta.json")
+    #[serde(default)]
+    pub metadata_file: Option<String>,
+
+    /// Days a dead key stays in the locale files before extraction, `check
+    /// --remove` or `sync --remove-unused` removes it.
+    /// The day it was found dead is recorded in `metadataFile` (required), so
+    /// keys only used seasonally survive a run without their code.
+    #[serde(default)]
+    pub removal_grace_days: Option<u32>,
+
+    /// Rewrites applied in order to extracted keys before they are written to
+    /// locale files, so sources can keep legacy keys while the locale files use a
+    /// new convention (e.g. `[{ "stripPrefix": "app." }, "lowercaseFirstSegment"]`)
+    #[serde(default)]
+    pub key_transforms: Vec<KeyTransform>,
+
+    /// JSON file receiving the reverse mapping of `keyTransforms` (namespace ->
+    /// locale key -> source key) for configuring the runtime
+    #[serde(default)]
+    pub key_map_file: Option<String>,
+
+    /// JSON file receiving the valid keys, namespaces and per-file namespaces for
+    /// ESLint rules, regenerated by `extract` and `watch` (see `export-eslint-data`)
+    #[serde(default)]
+    pub eslint_data_file: Option<String>,
+
+    /// Directory receiving a content-hashed bundle per locale and a
+    /// `manifest.json` for runtime loading, republished by `extract` and
+    /// `watch` (see `publish-dir`)
+    #[serde(default)]
+    pub publish_dir: Option<String>,
+
+    /// Case every key segment must follow, checked by `check --key-case`:
+    /// "camelCase", "PascalCase", "snake_case", "kebab-case" or "lowercase"
+    #[serde(default)]
+    pub key_case: Option<KeyCase>,
+
+    /// Second extraction pass over email templates (MJML, Handlebars) with its
+    /// own functions, locale root and format, run by the same `extract`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email_templates: Option<EmailTemplatesConfig>,
+}
+
+/// Optional separator configuration
+/// Supports both string (e.g., ":") and boolean false (disabled) formats
+/// When false is provided, it's converted to an empty string to disable the separator
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct OptionalSeparator(pub String);
+
+impl OptionalSeparator {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum UseTranslationName {
+    Name(String),
+    Detailed(UseTranslationNameDetails),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UseTranslationNameDetails {
+    pub name: String,
+    #[serde(default = "default_ns_arg")]
+    pub ns_arg: usize,
+    #[serde(default = "default_key_prefix_arg")]
+    pub key_prefix_arg: usize,
+}
+
+impl UseTranslationName {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Name(name) => name,
+            Self::Detailed(details) => details.name.as_str(),
+        }
+    }
+
+    pub fn ns_arg(&self) -> usize {
+        match self {
+            Self::Name(_) => default_ns_arg(),
+            Self::Detailed(details) => details.ns_arg,
+        }
+    }
+
+    pub fn key_prefix_arg(&self) -> usize {
+        match self {
+            Self::Name(_) => default_key_prefix_arg(),
+            Self::Detailed(details) => details.key_prefix_arg,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum TransComponent {
+    Name(String),
+    Detailed(TransComponentDetails),
+}
+
+/// Trans-like component whose props differ from `i18nKey` / `ns` / `defaults`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TransComponentDetails {
+    pub name: String,
+    #[serde(default = "default_trans_key_attr")]
+    pub key_attr: String,
+    #[serde(default = "default_trans_ns_attr")]
+    pub ns_attr: String,
+    #[serde(default = "default_trans_default_attr")]
+    pub default_attr: String,
+}
+
+impl TransComponentDetails {
+    /// Component `name` with the default `i18nKey` / `ns` / `defaults` props
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            key_attr: default_trans_key_attr(),
+            ns_attr: default_trans_ns_attr(),
+            default_attr: default_trans_default_attr(),
+        }
+    }
+}
+
+impl TransComponent {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Name(name) => name,
+            Self::Detailed(details) => details.name.as_str(),
+        }
+    }
+
+    pub fn key_attr(&self) -> &str {
+        match self {
+            Self::Name(_) => "i18nKey",
+            Self::Detailed(details) => details.key_attr.as_str(),
+        }
+    }
+
+    pub fn ns_attr(&self) -> &str {
+        match self {
+            Self::Name(_) => "ns",
+            Self::Detailed(details) => details.ns_attr.as_str(),
+        }
+    }
+
+    pub fn default_attr(&self) -> &str {
+        match self {
+            Self::Name(_) => "defaults",
+            Self::Detailed(details) => details.default_attr.as_str(),
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for OptionalSeparator {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::{self, Visitor};
+
+        struct OptionalSeparatorVisitor;
+
+        impl<'de> Visitor<'de> for OptionalSeparatorVisitor {
+            type Value = OptionalSeparator;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a string or boolean false")
+            }
+
+            fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                if v {
+                    Err(E::custom("separator cannot be true, use a string or false"))
+                } else {
+                    // false means disabled (empty string)
+                    Ok(OptionalSeparator(String::new()))
+                }
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(OptionalSeparator(v.to_string()))
+            }
+
+            fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(OptionalSeparator(v))
+            }
+        }
+
+        deserializer.deserialize_any(OptionalSeparatorVisitor)
+    }
+}
+
+impl serde::Serialize for OptionalSeparator {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if self.0.is_empty() {
+            serializer.serialize_bool(false)
+        } else {
+            serializer.serialize_str(&self.0)
+        }
+    }
+}
+
+/// JSON indentation configuration
+/// Supports both numeric (spaces) and string (e.g., "\t") formats
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Indentation {
+    /// Number of spaces for indentation
+    Spaces(usize),
+    /// Custom indentation string (e.g., "\t")
+    Custom(String),
+}
+
+impl std::fmt::Display for Indentation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Indentation::Spaces(n) => write!(f, "{}", " ".repeat(*n)),
+            Indentation::Custom(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Indentation {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::{self, Visitor};
+
+        struct IndentationVisitor;
+
+        impl<'de> Visitor<'de> for IndentationVisitor {
+            type Value = Indentation;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a positive integer or a string")
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                if v >= 0 {
+                    Ok(Indentation::Spaces(v as usize))
+                } else {
+                    Err(E::custom("indentation must be non-negative"))
+                }
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(Indentation::Spaces(v as usize))
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(Indentation::Custom(v.to_string()))
+            }
+
+            fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(Indentation::Custom(v))
+            }
+        }
+
+        deserializer.deserialize_any(IndentationVisitor)
+    }
+}
+
+impl serde::Serialize for Indentation {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Indentation::Spaces(n) => serializer.serialize_u64(*n as u64),
+            Indentation::Custom(s) => serializer.serialize_str(s),
+        }
+    }
+}
+
+/// Framework conventions selectable with `preset`
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Preset {
+    /// Next.js App Router with next-intl style `useTranslations` / `getTranslations`
+    /// and `messages/{locale}.json` files
+    Nextjs,
+}
+
+impl Preset {
+    /// Config values (camelCase keys) applied unless the config sets them itself
+    pub fn defaults(self) -> serde_json::Map<String, serde_json::Value> {
+        let defaults = match self {
+            Preset::Nextjs => serde_json::json!({
+                "input": [
+                    "app/**/*.{ts,tsx,js,jsx}",
+                    "src/**/*.{ts,tsx,js,jsx}",
+                    "components/**/*.{ts,tsx,js,jsx}"
+                ],
+                "ignore": [".next/**", "**/node_modules/**", "**/*.d.ts"],
+                "output": "messages",
+                "flatLocaleFiles": true,
+                // next-intl namespaces are key prefixes inside one messages file
+                "defaultNamespace": false,
+                "nsSeparator": false,
+                "useTranslationNames": [
+                    // Client components
+                    { "name": "useTranslations", "nsArg": 1, "keyPrefixArg": 0 },
+                    // Server components: const t = await getTranslations('ns')
+                    { "name": "getTranslations", "nsArg": 1, "keyPrefixArg": 0 },
+                    "useTranslation"
+                ]
+            }),
+        };
+        match defaults {
+            serde_json::Value::Object(map) => map,
+            _ => unreachable!("preset defaults are an object"),
+        }
+    }
+}
+
+impl std::fmt::Display for Preset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Preset::Nextjs => write!(f, "nextjs"),
+        }
+    }
+}
+
+/// A key rewrite of `keyTransforms`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum KeyTransform {
+    /// Remove a leading prefix: `{ "stripPrefix": "app." }`
+    StripPrefix(String),
+    /// Swap a leading prefix: `{ "replacePrefix": { "from": "legacy.", "to": "core." } }`
+    ReplacePrefix { from: String, to: String },
+    /// Lowercase the segment before the first `keySeparator`: `"lowercaseFirstSegment"`
+    LowercaseFirstSegment,
+}
+
+/// Case policy of `keyCase`, applied to each key segment without its plural suffix
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum KeyCase {
+    #[serde(rename = "camelCase")]
+    Camel,
+    #[serde(rename = "PascalCase")]
+    Pascal,
+    #[serde(rename = "snake_case")]
+    Snake,
+    #[serde(rename = "kebab-case")]
+    Kebab,
+    #[serde(rename = "lowercase")]
+    Lower,
+}
+
+impl KeyCase {
+    pub fn parse_str(value: &str) -> Result<Self> {
+        match value {
+            "camelCase" => Ok(KeyCase::Camel),
+            "PascalCase" => Ok(KeyCase::Pascal),
+            "snake_case" => Ok(KeyCase::Snake),
+            "kebab-case" => Ok(KeyCase::Kebab),
+            "lowercase" => Ok(KeyCase::Lower),
+            other => bail!(
+                "Configuration error: unsupported keyCase '{}'. Supported: camelCase, PascalCase, snake_case, kebab-case, lowercase",
+                other
+            ),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeyCase::Camel => "camelCase",
+            KeyCase::Pascal => "PascalCase",
+            KeyCase::Snake => "snake_case",
+            KeyCase::Kebab => "kebab-case",
+            KeyCase::Lower => "lowercase",
+        }
+    }
+
+    /// Whether one key segment follows the policy
+    pub fn matches(&self, segment: &str) -> bool {
+        let words = |separator: char| {
+            segment.split(separator).all(|word| {
+                !word.is_empty()
+                    && word
+                        .chars()
+                        .all(|c| c.is_numeric() || (c.is_alphabetic() && !c.is_uppercase()))
+            })
+        };
+        let mut chars = segment.chars();
+        match self {
+            KeyCase::Camel | KeyCase::Pascal => {
+                let first_ok = chars.next().is_some_and(|c| {
+                    c.is_alphabetic()
+                        && if *self == KeyCase::Camel {
+                            !c.is_uppercase()
+                        } else {
+                            c.is_uppercase()
+                        }
+                });
+                first_ok && chars.all(char::is_alphanumeric)
+            }
+            KeyCase::Snake => words('_'),
+            KeyCase::Kebab => words('-'),
+            KeyCase::Lower => !segment.chars().any(char::is_uppercase),
+        }
+    }
+}
+
+/// Shape of the namespace level in a merged-namespaces locale file
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MergedNamespaceLayout {
+    /// Namespaces are top-level objects
+    #[default]
+    Nested,
+    /// Top-level keys carry the namespace and `nsSeparator` as a prefix
+    Prefixed,
+}
+
+impl MergedNamespaceLayout {
+    pub fn parse_str(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "nested" => Ok(MergedNamespaceLayout::Nested),
+            "prefixed" => Ok(MergedNamespaceLayout::Prefixed),
+            other => bail!(
+                "Configuration error: unsupported mergedNamespaceLayout '{}'. Supported: nested, prefixed",
+                other
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputFormat {
+    #[default]
+    Json,
+    Json5,
+    #[serde(alias = "js")]
+    JsEsm,
+    JsCjs,
+    Ts,
+}
+
+impl OutputFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Json => "json",
+            OutputFormat::Json5 => "json5",
+            OutputFormat::JsEsm | OutputFormat::JsCjs => "js",
+            OutputFormat::Ts => "ts",
+        }
+    }
+
+    pub fn parse_str(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "json" => Ok(OutputFormat::Json),
+            "json5" => Ok(OutputFormat::Json5),
+            "js" | "js-esm" => Ok(OutputFormat::JsEsm),
+            "js-cjs" => Ok(OutputFormat::JsCjs),
+            "ts" => Ok(OutputFormat::Ts),
+            other => bail!(
+                "Configuration error: unsupported outputFormat '{}'. Supported: json, json5, js, js-esm, js-cjs, ts",
+                other
+            ),
+        }
+    }
+}
+
+/// Separators of file paths in CLI output, JSON reports and NAPI results
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PathStyle {
+    /// The platform separator (`\` on Windows)
+    #[default]
+    Native,
+    /// Always `/`
+    Forward,
+}
+
+impl PathStyle {
+    pub fn parse_str(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "native" => Ok(PathStyle::Native),
+            "forward" => Ok(PathStyle::Forward),
+            other => bail!(
+                "Configuration error: unsupported pathStyle '{}'. Supported: native, forward",
+                other
+            ),
+        }
+    }
+}
+
+/// Order of keys in written locale files
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum KeySort {
+    /// Unicode code point order: uppercase before lowercase, `Ä` after `z`
+    #[default]
+    Byte,
+    /// Letters compared without accents and case first (`Äpfel`, `apple`,
+    /// `Zebra`); accents, then code points break ties. This folding ignores
+    /// the locale: `Ä` sorts with `A` even where the alphabet puts it after `Z`
+    Folded,
+    /// `folded` with digit runs compared by value (`step2` before `step10`)
+    Natural,
+}
+
+impl KeySort {
+    pub fn parse_str(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "byte" => Ok(KeySort::Byte),
+            "folded" => Ok(KeySort::Folded),
+            "natural" => Ok(KeySort::Natural),
+            other => bail!(
+                "Configuration error: unsupported keySort '{}'. Supported: byte, folded, natural",
+                other
+            ),
+        }
+    }
+}
+
+/// Line endings of written JSON locale files
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum EndOfLine {
+    /// Keep the line endings of the existing file (LF for new files)
+    #[default]
+    Auto,
+    Lf,
+    Crlf,
+}
+
+impl EndOfLine {
+    pub fn parse_str(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "auto" => Ok(EndOfLine::Auto),
+            "lf" => Ok(EndOfLine::Lf),
+            "crlf" => Ok(EndOfLine::Crlf),
+            other => bail!(
+                "Configuration error: unsupported endOfLine '{}'. Supported: auto, lf, crlf",
+                other
+            ),
+        }
+    }
+}
+
+/// Handling of overlapping NAPI calls that write the same locale directory
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ConcurrentRuns {
+    /// Wait until the other call is done
+    #[default]
+    Queue,
+    /// Fail right away
+    Reject,
+}
+
+impl ConcurrentRuns {
+    pub fn parse_str(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "queue" => Ok(ConcurrentRuns::Queue),
+            "reject" => Ok(ConcurrentRuns::Reject),
+            other => bail!(
+                "Configuration error: unsupported concurrentRuns '{}'. Supported: queue, reject",
+                other
+            ),
+        }
+    }
+}
+
+/// Plural keys of a language with a single plural category (`ja`, `zh`, ...)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum SingleCategoryPlural {
+    /// Only the base key when every locale has a single category, otherwise the
+    /// forms of the other locales
+    #[default]
+    Base,
+    /// The `other` form as well, so the key set matches multi-category locales
+    Other,
+    /// Like `other`, with new forms filled from the primary locale's translation
+    CopyPrimary,
+}
+
+impl SingleCategoryPlural {
+    pub fn parse_str(value: &str) -> Result<Self> {
+        match value {
+            "base" => Ok(SingleCategoryPlural::Base),
+            "other" => Ok(SingleCategoryPlural::Other),
+            "copyPrimary" => Ok(SingleCategoryPlural::CopyPrimary),
+            other => bail!(
+                "Configuration error: unsupported singleCategoryPlurals mode '{}'. Supported: base, other, copyPrimary",
+                other
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PluralCompat {
+    #[default]
+    V4,
+    V3,
+    V2,
+}
+
+impl PluralCompat {
+    /// Whether keys use the pre-v4 `_plural`/numeric suffixes
+    pub fn is_legacy(&self) -> bool {
+        matches!(self, PluralCompat::V3 | PluralCompat::V2)
+    }
+
+    pub fn parse_str(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "v4" => Ok(PluralCompat::V4),
+            "v3" => Ok(PluralCompat::V3),
+            "v2" => Ok(PluralCompat::V2),
+            other => bail!(
+                "Configuration error: unsupported pluralCompat '{}'. Supported: v4, v3, v2",
+                other
+            ),
+        }
+    }
+}
+
+/// Order in which context and plural suffixes are appended to a key
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum ContextPluralOrder {
+    /// `key_context_plural` (i18next default)
+    #[default]
+    ContextPlural,
+    /// `key_plural_context`
+    PluralContext,
+}
+
+impl ContextPluralOrder {
+    pub fn parse_str(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "context-plural" => Ok(ContextPluralOrder::ContextPlural),
+            "plural-context" => Ok(ContextPluralOrder::PluralContext),
+            other => bail!(
+                "Configuration error: unsupported contextPluralOrder '{}'. Supported: context-plural, plural-context",
+                other
+            ),
+        }
+    }
+}
+
+/// Build a context/plural variant key; an empty or missing suffix leaves the key unsuffixed
+pub fn plural_variant_key(
+    base: &str,
+    context: Option<&str>,
+    suffix: Option<&str>,
+    context_separator: &str,
+    plural_separator: &str,
+    order: ContextPluralOrder,
+) -> String {
+    let context_part = context
+        .map(|ctx| format!("{}{}", context_separator, ctx))
+        .unwrap_or_default();
+    let plural_part = suffix
+        .filter(|suffix| !suffix.is_empty())
+        .map(|suffix| format!("{}{}", plural_separator, suffix))
+        .unwrap_or_default();
+    match order {
+        ContextPluralOrder::ContextPlural => format!("{}{}{}", base, context_part, plural_part),
+        ContextPluralOrder::PluralContext => format!("{}{}{}", base, plural_part, context_part),
+    }
+}
+
+/// HTML entity decoding of `<Trans>` children during extraction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransUnescape {
+    /// Decode entities in default values taken from children
+    pub defaults: bool,
+    /// Decode entities in keys taken from children
+    pub keys: bool,
+}
+
+impl Default for TransUnescape {
+    fn default() -> Self {
+        Self {
+            defaults: true,
+            keys: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PluralConfig {
+    pub separator: String,
+    pub suffixes: Vec<String>,
+    /// Suffixes of ordinal forms (`place_ordinal_two`), from the locales' ordinal
+    /// rules, which differ from the cardinal ones (English: one, two, few, other)
+    pub ordinal_suffixes: Vec<String>,
+    /// Whether to generate base key alongside plural keys
+    pub generate_base: bool,
+    /// Context separator (e.g., "_" for "friend_male")
+    pub context_separator: String,
+    /// Order of context and plural suffixes
+    pub order: ContextPluralOrder,
+    /// Plural key format (CLDR suffixes or legacy v3/v2 suffixes)
+    pub compat: PluralCompat,
+    /// Also generate the `other` form next to the base key when every locale has
+    /// a single plural category (see `singleCategoryPlurals`)
+    pub single_category_other: bool,
+}
+
+impl PluralConfig {
+    /// Build a context/plural variant key using the configured separators and order
+    pub fn variant_key(&self, base: &str, context: Option<&str>, suffix: Option<&str>) -> String {
+        plural_variant_key(
+            base,
+            context,
+            suffix,
+            &self.context_separator,
+            &self.separator,
+            self.order,
+        )
+    }
+
+    /// Remove the plural suffix from a variant key, keeping any context
+    /// (`item_male_one` -> `item_male`). Returns `None` for keys without a plural suffix.
+    pub fn strip_plural_suffix(&self, key: &str) -> Option<String> {
+        let ordinals: &[String] = if self.compat.is_legacy() {
+            &[]
+        } else {
+            &self.ordinal_suffixes
+        };
+        let suffixes: Vec<String> = self
+            .suffixes
+            .iter()
+            .filter(|suffix| !suffix.is_empty())
+            .cloned()
+            .chain(ordinals.iter().map(|suffix| format!("ordinal_{}", suffix)))
+            .collect();
+        let strip = |key: &str| {
+            suffixes.iter().find_map(|suffix| {
+                key.strip_suffix(suffix.as_str())
+                    .and_then(|rest| rest.strip_suffix(self.separator.as_str()))
+                    .filter(|base| !base.is_empty())
+                    .map(str::to_string)
+            })
+        };
+
+        if let Some(base) = strip(key) {
+            return Some(base);
+        }
+        if self.order == ContextPluralOrder::PluralContext && !self.context_separator.is_empty() {
+            let (head, context) = key.rsplit_once(self.context_separator.as_str())?;
+            let base = strip(head)?;
+            return Some(format!("{}{}{}", base, self.context_separator, context));
+        }
+        None
+    }
+
+    /// Whether keys follow the layout i18next's own TypeScript types understand
+    /// (`_` separators, context before plural, v4 suffixes)
+    pub fn uses_default_layout(&self) -> bool {
+        self.order == ContextPluralOrder::ContextPlural
+            && self.separator == "_"
+            && self.context_separator == "_"
+            && !self.compat.is_legacy()
+    }
+}
+
+impl Default for PluralConfig {
+    fn default() -> Self {
+        Self {
+            separator: "_".to_string(),
+            suffixes: vec!["one".to_string(), "other".to_string()],
+            ordinal_suffixes: ["few", "one", "other", "two"].map(String::from).to_vec(),
+            generate_base: false,
+            context_separator: "_".to_string(),
+            order: ContextPluralOrder::default(),
+            compat: PluralCompat::default(),
+            single_category_other: false,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TypesConfig {
+    pub input: Option<Vec<String>>,
+    pub output: Option<String>,
+    pub resources_file: Option<String>,
+    pub enable_selector: Option<EnableSelector>,
+    pub default_locale: Option<String>,
+    pub locales_dir: Option<String>,
+    pub indentation: Option<Indentation>,
+    /// Namespaces (names or globs) to declare; all of them when unset
+    pub include_namespaces: Option<Vec<String>>,
+    /// Namespaces (names or globs) left out of the declarations
+    pub exclude_namespaces: Option<Vec<String>>,
+    /// Write one `<namespace>.d.ts` per namespace and an `index.d.ts` declaring
+    /// `Resources` into this directory instead of the single `output` file
+    pub output_dir: Option<String>,
+    /// Directory of the key constant modules written by `typegen --constants`
+    pub constants_dir: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum EnableSelector {
+    Bool(bool),
+    Mode(String),
+}
+
+impl EnableSelector {
+    pub fn enabled(&self) -> bool {
+        match self {
+            Self::Bool(v) => *v,
+            Self::Mode(mode) => mode == "optimize",
+        }
+    }
+
+    pub fn optimize(&self) -> bool {
+        matches!(self, Self::Mode(mode) if mode == "optimize")
+    }
+}
+
+/// How an extraction diagnostic is reported
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticSeverity {
+    /// Not reported
+    Off,
+    /// Printed and counted as a warning
+    #[default]
+    Warn,
+    /// Printed; `extract` fails before writing locale files
+    Error,
+}
+
+impl DiagnosticSeverity {
+    pub fn parse_str(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "off" => Ok(DiagnosticSeverity::Off),
+            "warn" => Ok(DiagnosticSeverity::Warn),
+            "error" => Ok(DiagnosticSeverity::Error),
+            other => bail!(
+                "Configuration error: unsupported severity '{}'. Supported: off, warn, error",
+                other
+            ),
+        }
+    }
+}
+
+/// Severity for source files matching a glob
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SeverityRule {
+    #[serde(rename = "match")]
+    pub pattern: String,
+    pub severity: DiagnosticSeverity,
+}
+
+impl SeverityRule {
+    /// Severity of the first rule whose glob matches `source`, or `default`
+    pub fn resolve(
+        rules: &[SeverityRule],
+        default: DiagnosticSeverity,
+        source: &Path,
+    ) -> DiagnosticSeverity {
+        rules
+            .iter()
+            .find(|rule| {
+                crate::extractor::matches_input_patterns(
+                    source,
+                    std::slice::from_ref(&rule.pattern),
+                )
+            })
+            .map_or(default, |rule| rule.severity)
+    }
+}
+
+/// Locale root for source files matching a glob (monorepo packages)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct OutputTarget {
+    #[serde(rename = "match")]
+    pub pattern: String,
+    pub output: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LintConfig {
+    #[serde(default = "default_lint_ignored_attributes")]
+    pub ignored_attributes: Vec<String>,
+    #[serde(default = "default_lint_ignored_tags")]
+    pub ignored_tags: Vec<String>,
+    #[serde(default = "default_lint_accepted_attributes")]
+    pub accepted_attributes: Vec<String>,
+    #[serde(default = "default_lint_accepted_tags")]
+    pub accepted_tags: Vec<String>,
+    #[serde(default)]
+    pub ignore: Vec<String>,
+    /// Maximum translation length in characters per key glob (e.g. `{ "buttons.*": 24 }`);
+    /// the smallest limit wins when several globs match
+    #[serde(default)]
+    pub max_length: BTreeMap<String, usize>,
+    /// Flag translations longer than this many times the primary-locale value
+    #[serde(default)]
+    pub max_length_ratio: Option<f64>,
+    /// Flag values matching the built-in secret/PII rules (API keys, emails,
+    /// internal URLs, `${...}` template injection)
+    #[serde(default = "default_lint_builtin_secret_rules")]
+    pub builtin_secret_rules: bool,
+    /// Extra rules flagging values that must never ship (rule name -> regex);
+    /// a rule named like a built-in one replaces it
+    #[serde(default)]
+    pub secret_rules: BTreeMap<String, String>,
+    /// Key globs exempt from secret/PII rules (e.g. `footer.supportEmail`)
+    #[serde(default)]
+    pub secret_allow_keys: Vec<String>,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            ignored_attributes: default_lint_ignored_attributes(),
+            ignored_tags: default_lint_ignored_tags(),
+            accepted_attributes: default_lint_accepted_attributes(),
+            accepted_tags: default_lint_accepted_tags(),
+            ignore: Vec::new(),
+            max_length: BTreeMap::new(),
+            max_length_ratio: None,
+            builtin_secret_rules: default_lint_builtin_secret_rules(),
+            secret_rules: BTreeMap::new(),
+            secret_allow_keys: Vec::new(),
+        }
+    }
+}
+
+/// Email template sources extracted independently of the web app sources
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailTemplatesConfig {
+    /// Glob patterns for template files (e.g. ["emails/**/*.mjml", "emails/partials/*.hbs"])
+    pub input: Vec<String>,
+    #[serde(default)]
+    pub ignore: Vec<String>,
+    /// Helper and function names to extract (`{{tEmail "key"}}`, `tEmail('key')`)
+    #[serde(default = "default_email_functions")]
+    pub functions: Vec<String>,
+    /// Locale root for email keys; must differ from the web `output`
+    pub output: String,
+    #[serde(default)]
+    pub output_format: OutputFormat,
+    /// Key separator for email locale files; flat keys by default
+    #[serde(default, deserialize_with = "deserialize_optional_separator")]
+    pub key_separator: String,
+    #[serde(default = "default_email_namespace")]
+    pub default_namespace: String,
+    /// Locales to write; the web `locales` when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locales: Option<Vec<String>>,
+}
+
+fn default_email_functions() -> Vec<String> {
+    vec!["tEmail".to_string()]
+}
+
+fn default_email_namespace() -> String {
+    "email".to_string()
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LocizeConfig {
+    pub project_id: String,
+    pub api_key: Option<String>,
+    pub version: Option<String>,
+    pub source_language: Option<String>,
+    pub namespaces: Option<Vec<String>>,
+    pub update_values: Option<bool>,
+    pub source_language_only: Option<bool>,
+    pub compare_modification_time: Option<bool>,
+    pub cdn_type: Option<String>,
+    pub dry_run: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TmsConfig {
+    pub provider: TmsProvider,
+    pub project_id: String,
+    /// Falls back to the provider's environment variable (`LOCIZE_API_KEY`,
+    /// `CROWDIN_PERSONAL_TOKEN` or `PHRASE_ACCESS_TOKEN`)
+    pub api_key: Option<String>,
+    /// API root, for Crowdin Enterprise, self-hosted proxies or tests
+    pub base_url: Option<String>,
+    /// Locize version (default: latest)
+    pub version: Option<String>,
+    /// Namespaces to pull and push (default: every local namespace file)
+    pub namespaces: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TmsProvider {
+    Locize,
+    Crowdin,
+    Phrase,
+}
+
+impl TmsProvider {
+    pub fn parse_str(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "locize" => Ok(TmsProvider::Locize),
+            "crowdin" => Ok(TmsProvider::Crowdin),
+            "phrase" => Ok(TmsProvider::Phrase),
+            other => bail!(
+                "Configuration error: unsupported tms.provider '{}'. Supported: locize, crowdin, phrase",
+                other
+            ),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TmsProvider::Locize => "Locize",
+            TmsProvider::Crowdin => "Crowdin",
+            TmsProvider::Phrase => "Phrase",
+        }
+    }
+}
+
+/// Deserialize a separator that can be either a string or `false` (disabled)
+/// When `false` is provided, it's converted to an empty string
+fn deserialize_optional_separator<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::{self, Visitor};
+
+    struct OptionalSeparatorVisitor;
+
+    impl<'de> Visitor<'de> for OptionalSeparatorVisitor {
+        type Value = String;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a string or boolean false")
+        }
+
+        fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            if v {
+                Err(E::custom("separator cannot be true, use a string or false"))
+            } else {
+                // false means disabled (empty string)
+                Ok(String::new())
+            }
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(v.to_string())
+        }
+
+        fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(v)
+        }
+    }
+
+    deserializer.deserialize_any(OptionalSeparatorVisitor)
+}
+
+/// Deserialize defaultNamespace that can be either a string or `false` (namespace-less mode)
+fn deserialize_optional_default_namespace<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::{self, Visitor};
+
+    struct OptionalDefaultNamespaceVisitor;
+
+    impl<'de> Visitor<'de> for OptionalDefaultNamespaceVisitor {
+        type Value = String;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a string or boolean false")
+        }
+
+        fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            if v {
+                Err(E::custom(
+                    "defaultNamespace cannot be true, use a string or false",
+                ))
+            } else {
+                Ok(String::new())
+            }
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(v.to_string())
+        }
+
+        fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(v)
+        }
+    }
+
+    deserializer.deserialize_any(OptionalDefaultNamespaceVisitor)
+}
+
+/// `path` joined to `dir`, unless it is absolute or a glob matching anywhere (`**/...`)
+fn resolve_relative(dir: &Path, path: &str) -> String {
+    let dir = dir.to_string_lossy().replace('\\', "/");
+    let dir = dir.trim_end_matches('/');
+    if dir.is_empty() || dir == "." || path.starts_with("**") || Path::new(path).is_absolute() {
+        return path.to_string();
+    }
+    let path = path.strip_prefix("./").unwrap_or(path);
+    if path.is_empty() || path == "." {
+        dir.to_string()
+    } else {
+        format!("{}/{}", dir, path)
+    }
+}
+
+fn default_input() -> Vec<String> {
+    vec!["src/**/*.{ts,tsx,js,jsx}".to_string()]
+}
+
+fn default_output() -> String {
+    "locales".to_string()
+}
+
+fn default_locales() -> Vec<String> {
+    vec!["en".to_string()]
+}
+
+fn default_namespace() -> String {
+    "translation".to_string()
+}
+
+fn default_functions() -> Vec<String> {
+    vec!["t".to_string()]
+}
+
+fn default_use_translation_names() -> Vec<UseTranslationName> {
+    vec![
+        UseTranslationName::Name("useTranslation".to_string()),
+        UseTranslationName::Name("getT".to_string()),
+        UseTranslationName::Name("useT".to_string()),
+        // next-intl: const t = await getTranslations('ns')
+        UseTranslationName::Name("getTranslations".to_string()),
+        // next-i18next: serverSideTranslations(locale, ['ns'])
+        UseTranslationName::Detailed(UseTranslationNameDetails {
+            name: "serverSideTranslations".to_string(),
+            ns_arg: 1,
+            key_prefix_arg: 2,
+        }),
+    ]
+}
+
+fn default_ns_arg() -> usize {
+    0
+}
+
+fn default_key_prefix_arg() -> usize {
+    1
+}
+
+fn default_key_separator() -> String {
+    ".".to_string()
+}
+
+fn default_ns_separator() -> String {
+    ":".to_string()
+}
+
+fn default_context_separator() -> String {
+    "_".to_string()
+}
+
+fn default_plural_separator() -> String {
+    "_".to_string()
+}
+
+fn default_plural_suffixes() -> Vec<String> {
+    vec!["one".to_string(), "other".to_string()]
+}
+
+fn default_extract_from_comments() -> bool {
+    true
+}
+
+fn default_use_locale_plural_rules() -> bool {
+    true
+}
+
+fn default_remove_unused_keys() -> bool {
+    true
+}
+
+fn default_follow_symlinks() -> bool {
+    true
+}
+
+fn default_trans_components() -> Vec<TransComponent> {
+    vec![TransComponent::Name("Trans".to_string())]
+}
+
+fn default_trans_key_attr() -> String {
+    "i18nKey".to_string()
+}
+
+fn default_trans_ns_attr() -> String {
+    "ns".to_string()
+}
+
+fn default_trans_default_attr() -> String {
+    "defaults".to_string()
+}
+
+fn default_trans_keep_nodes() -> Vec<String> {
+    vec!["br".to_string(), "strong".to_string(), "i".to_string()]
+}
+
+fn default_trans_unescape() -> bool {
+    true
+}
+
+fn default_nesting_prefix() -> String {
+    "$t(".to_string()
+}
+
+fn default_nesting_suffix() -> String {
+    ")".to_string()
+}
+
+fn default_nesting_options_separator() -> String {
+    ",".to_string()
+}
+
+fn default_interpolation_prefix() -> String {
+    "{{".to_string()
+}
+
+fn default_interpolation_suffix() -> String {
+    "}}".to_string()
+}
+
+fn default_types_output() -> String {
+    "src/@types/i18next.d.ts".to_string()
+}
+
+fn default_lint_builtin_secret_rules() -> bool {
+    true
+}
+
+fn default_lint_ignored_attributes() -> Vec<String> {
+    Vec::new()
+}
+
+fn default_lint_ignored_tags() -> Vec<String> {
+    vec![
+        "script".to_string(),
+        "style".to_string(),
+        "code".to_string(),
+        "pre".to_string(),
+    ]
+}
+
+fn default_lint_accepted_attributes() -> Vec<String> {
+    vec![
+        "alt".to_string(),
+        "title".to_string(),
+        "placeholder".to_string(),
+        "aria-label".to_string(),
+        "aria-description".to_string(),
+    ]
+}
+
+fn default_lint_accepted_tags() -> Vec<String> {
+    vec![
+        "p".to_string(),
+        "span".to_string(),
+        "div".to_string(),
+        "button".to_string(),
+        "label".to_string(),
+        "img".to_string(),
+    ]
+}
+
+fn default_log_level() -> String {
+    "info".to_string()
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            preset: None,
+            input: default_input(),
+            output: default_output(),
+            outputs: Vec::new(),
+            output_format: OutputFormat::default(),
+            flat_locale_files: false,
+            locales: default_locales(),
+            default_namespace: default_namespace(),
+            functions: default_functions(),
+            use_translation_names: default_use_translation_names(),
+            message_factory_functions: Vec::new(),
+            key_producing_functions: Vec::new(),
+            key_separator: default_key_separator(),
+            ns_separator: default_ns_separator(),
+            context_separator: default_context_separator(),
+            plural_separator: default_plural_separator(),
+            context_plural_order: ContextPluralOrder::default(),
+            plural_suffixes: default_plural_suffixes(),
+            disable_plurals: false,
+            generate_base_plural_forms: false,
+            single_category_plurals: BTreeMap::new(),
+            extract_from_comments: default_extract_from_comments(),
+            dynamic_key_severity: DiagnosticSeverity::default(),
+            dynamic_key_rules: Vec::new(),
+            use_locale_plural_rules: default_use_locale_plural_rules(),
+            plural_compat: PluralCompat::default(),
+            ignore: Vec::new(),
+            generated_files: Vec::new(),
+            preserve_patterns: Vec::new(),
+            key_filter: Vec::new(),
+            preserve_context_variants: false,
+            remove_unused_keys: default_remove_unused_keys(),
+            merge_namespaces: false,
+            merged_namespace_filename: None,
+            merged_namespace_layout: MergedNamespaceLayout::default(),
+            merged_nesting_depth: None,
+            default_value: None,
+            types: TypesConfig::default(),
+            trans_components: default_trans_components(),
+            trans_keep_basic_html_nodes_for: default_trans_keep_nodes(),
+            trans_unescape: default_trans_unescape(),
+            trans_unescape_keys: default_trans_unescape(),
+            nesting_prefix: default_nesting_prefix(),
+            nesting_suffix: default_nesting_suffix(),
+            nesting_options_separator: default_nesting_options_separator(),
+            interpolation_prefix: default_interpolation_prefix(),
+            interpolation_suffix: default_interpolation_suffix(),
+            allowed_formatters: None,
+            locize: None,
+            tms: None,
+            primary_language: None,
+            secondary_languages: None,
+            indentation: None,
+            minify: false,
+            key_sort: KeySort::default(),
+            end_of_line: EndOfLine::default(),
+            follow_symlinks: default_follow_symlinks(),
+            path_style: PathStyle::default(),
+            shard_max_keys: None,
+            remove_empty_files: false,
+            concurrent_runs: ConcurrentRuns::default(),
+            lint: LintConfig::default(),
+            log_level: default_log_level(),
+            budgets: BTreeMap::new(),
+            fail_on_budget_exceeded: false,
+            strict_namespaces: false,
+            release_gates: BTreeMap::new(),
+            metadata_file: None,
+            removal_grace_days: None,
+            key_transforms: Vec::new(),
+            key_map_file: None,
+            eslint_data_file: None,
+            publish_dir: None,
+            key_case: None,
+            email_templates: None,
+        }
+    }
+}
+
+impl Config {
+    /// Globs skipped by extraction: `ignore` plus `generatedFiles`
+    pub fn extraction_ignore(&self) -> Vec<String> {
+        // Email templates belong to their own pass only; ignore globs do not
+        // expand braces, so input globs are expanded here
+        let email_inputs = self
+            .email_templates
+            .iter()
+            .flat_map(|email| &email.input)
+            .flat_map(|pattern| crate::extractor::expand_brace_patterns(pattern));
+        self.ignore
+            .iter()
+            .chain(&self.generated_files)
+            .cloned()
+            .chain(email_inputs)
+            .collect()
+    }
+
+    /// Configuration of the `emailTemplates` pass: this config with the email
+    /// input, functions, locale root and format, and without the web-only
+    /// options (`outputs`, key transforms, metadata, merged namespaces)
+    pub fn email_templates_config(&self) -> Option<Config> {
+        let email = self.email_templates.as_ref()?;
+        let mut config = self.clone();
+        config.input = email.input.clone();
+        config.ignore = email.ignore.clone();
+        config.generated_files = Vec::new();
+        config.functions = email.functions.clone();
+        config.message_factory_functions = Vec::new();
+        config.key_producing_functions = Vec::new();
+        config.output = email.output.clone();
+        config.outputs = Vec::new();
+        config.output_format = email.output_format;
+        config.key_separator = email.key_separator.clone();
+        config.default_namespace = email.default_namespace.clone();
+        if let Some(locales) = &email.locales {
+            config.locales = locales.clone();
+        }
+        config.flat_locale_files = false;
+        config.merge_namespaces = false;
+        config.key_filter = Vec::new();
+        config.key_transforms = Vec::new();
+        config.key_map_file = None;
+        config.eslint_data_file = None;
+        config.publish_dir = None;
+        config.metadata_file = None;
+        config.removal_grace_days = None;
+        config.budgets = BTreeMap::new();
+        config.email_templates = None;
+        Some(config)
+    }
+
+    /// Entity decoding applied to Trans children during extraction
+    pub fn trans_unescape(&self) -> TransUnescape {
+        TransUnescape {
+            defaults: self.trans_unescape,
+            keys: self.trans_unescape && self.trans_unescape_keys,
+        }
+    }
+
+    pub fn plural_config(&self) -> PluralConfig {
+        // If plurals are disabled, return empty suffixes
+        if self.disable_plurals {
+            return PluralConfig {
+                separator: self.plural_separator.clone(),
+                suffixes: Vec::new(),
+                ordinal_suffixes: Vec::new(),
+                generate_base: false,
+                context_separator: self.context_separator.clone(),
+                order: self.context_plural_order,
+                compat: self.plural_compat,
+                single_category_other: false,
+            };
+        }
+
+        if self.plural_compat.is_legacy() {
+            return PluralConfig {
+                separator: self.plural_separator.clone(),
+                suffixes: self.legacy_plural_suffixes(),
+                ordinal_suffixes: Vec::new(),
+                generate_base: self.generate_base_plural_forms,
+                context_separator: self.context_separator.clone(),
+                order: self.context_plural_order,
+                compat: self.plural_compat,
+                single_category_other: false,
+            };
+        }
+
+        let (suffixes, ordinal_suffixes) = if self.use_locale_plural_rules {
+            (
+                compute_plural_suffixes_from_locales(&self.locales, false),
+                compute_plural_suffixes_from_locales(&self.locales, true),
+            )
+        } else {
+            (self.plural_suffixes.clone(), Vec::new())
+        };
+
+        let mut final_suffixes = if suffixes.is_empty() {
+            vec!["one".to_string(), "other".to_string()]
+        } else {
+            suffixes
+        };
+
+        if !final_suffixes.iter().any(|s| s == "other") {
+            final_suffixes.push("other".to_string());
+        }
+
+        // Without locale rules, ordinals use the configured suffixes
+        let ordinal_suffixes = if ordinal_suffixes.is_empty() {
+            final_suffixes.clone()
+        } else {
+            ordinal_suffixes
+        };
+
+        PluralConfig {
+            separator: self.plural_separator.clone(),
+            suffixes: final_suffixes,
+            ordinal_suffixes,
+            generate_base: self.generate_base_plural_forms,
+            context_separator: self.context_separator.clone(),
+            order: self.context_plural_order,
+            compat: self.plural_compat,
+            single_category_other: self
+                .single_category_plurals
+                .values()
+                .any(|mode| *mode != SingleCategoryPlural::Base),
+        }
+    }
+
+    /// Legacy suffixes for all configured locales, in first-seen order ("" is the bare key)
+    fn legacy_plural_suffixes(&self) -> Vec<String> {
+        if !self.use_locale_plural_rules {
+            return crate::plurals::legacy_plural_suffixes_for_count(
+                self.plural_suffixes.len().max(1),
+            );
+        }
+
+        let mut suffixes: Vec<String> = Vec::new();
+        for locale in &self.locales {
+            for suffix in crate::plurals::legacy_plural_suffixes(locale, self.plural_compat) {
+                if !suffixes.contains(&suffix) {
+                    suffixes.push(suffix);
+                }
+            }
+        }
+        suffixes
+    }
+
+    /// Validate configuration values
+    pub fn validate(&self) -> Result<()> {
+        // Check locales is not empty
+        if self.locales.is_empty() {
+            bail!(
+                "Configuration error: 'locales' must contain at least one locale.\n\
+                 Example: \"locales\": [\"en\", \"ja\"]"
+            );
+        }
+
+        // Check for empty locale strings
+        for (i, locale) in self.locales.iter().enumerate() {
+            if locale.trim().is_empty() {
+                bail!(
+                    "Configuration error: 'locales[{}]' is empty.\n\
+                     Each locale must be a non-empty string like \"en\" or \"ja\".",
+                    i
+                );
+            }
+            if !crate::paths::is_plain_file_name(locale) {
+                bail!(
+                    "Configuration error: 'locales[{}]' ('{}') must be a locale code, not a path.\n\
+                     Locale codes name directories in 'output' and cannot contain separators or '..'.",
+                    i,
+                    locale
+                );
+            }
+        }
+
+        if let Some(secondary_languages) = &self.secondary_languages {
+            for (i, locale) in secondary_languages.iter().enumerate() {
+                if locale.trim().is_empty() {
+                    bail!(
+                        "Configuration error: 'secondaryLanguages[{}]' is empty.\n\
+                         Each locale must be a non-empty string like \"ja\".",
+                        i
+                    );
+                }
+                if !crate::paths::is_plain_file_name(locale) {
+                    bail!(
+                        "Configuration error: 'secondaryLanguages[{}]' ('{}') must be a locale code, not a path.",
+                        i,
+                        locale
+                    );
+                }
+            }
+        }
+
+        // Check input patterns are not empty
+        if self.input.is_empty() {
+            bail!(
+                "Configuration error: 'input' must contain at least one glob pattern.\n\
+                 Example: \"input\": [\"src/**/*.tsx\", \"src/**/*.ts\"]"
+            );
+        }
+
+        // Validate each input pattern is a valid glob
+        for pattern in &self.input {
+            if pattern.trim().is_empty() {
+                bail!(
+                    "Configuration error: empty input pattern found.\n\
+                     Each input pattern must be a non-empty glob like \"src/**/*.tsx\"."
+                );
+            }
+            // Try to compile the glob pattern to catch syntax errors early
+            if let Err(e) = glob::Pattern::new(pattern) {
+                bail!(
+                    "Configuration error: invalid glob pattern '{}'.\n\
+                     Glob error: {}\n\
+                     Example of valid patterns: \"src/**/*.tsx\", \"lib/*.js\"",
+                    pattern,
+                    e
+                );
+            }
+        }
+
+        // Validate ignore patterns
+        for pattern in &self.ignore {
+            if pattern.trim().is_empty() {
+                bail!(
+                    "Configuration error: empty pattern found in 'ignore'.\n\
+                     Remove empty entries or provide a glob like \"**/*.test.tsx\"."
+                );
+            }
+            if let Err(e) = Pattern::new(pattern) {
+                bail!(
+                    "Configuration error: invalid glob in 'ignore': '{}'.\n\
+                     Glob error: {}",
+                    pattern,
+                    e
+                );
+            }
+        }
+
+        for pattern in &self.generated_files {
+            if pattern.trim().is_empty() {
+                bail!(
+                    "Configuration error: empty pattern found in 'generatedFiles'.\n\
+                     Remove empty entries or provide a glob like \"src/__generated__/**\"."
+                );
+            }
+            if let Err(e) = Pattern::new(pattern) {
+                bail!(
+                    "Configuration error: invalid glob in 'generatedFiles': '{}'.\n\
+                     Glob error: {}",
+                    pattern,
+                    e
+                );
+            }
+        }
+
+        for pattern in &self.lint.ignore {
+            if pattern.trim().is_empty() {
+                bail!(
+                    "Configuration error: empty pattern found in 'lint.ignore'.\n\
+                     Remove empty entries or provide a glob like \"**/*.stories.tsx\"."
+                );
+            }
+            if let Err(e) = Pattern::new(pattern) {
+                bail!(
+                    "Configuration error: invalid glob in 'lint.ignore': '{}'.\n\
+                     Glob error: {}",
+                    pattern,
+                    e
+                );
+            }
+        }
+
+        for pattern in self.lint.max_length.keys() {
+            if pattern.trim().is_empty() {
+                bail!(
+                    "Configuration error: empty key pattern found in 'lint.maxLength'.\n\
+                     Example: {{ \"buttons.*\": 24 }}"
+                );
+            }
+            if let Err(e) = Pattern::new(pattern) {
+                bail!(
+                    "Configuration error: invalid glob in 'lint.maxLength': '{}'.\n\
+                     Glob error: {}",
+                    pattern,
+                    e
+                );
+            }
+        }
+
+        if let Some(ratio) = self.lint.max_length_ratio {
+            if !ratio.is_finite() || ratio <= 0.0 {
+                bail!(
+                    "Configuration error: 'lint.maxLengthRatio' must be a positive number, got {}.",
+                    ratio
+                );
+            }
+        }
+
+        for (name, pattern) in &self.lint.secret_rules {
+            if let Err(e) = regex::Regex::new(pattern) {
+                bail!(
+                    "Configuration error: invalid regex for 'lint.secretRules.{}': '{}'.\n\
+                     Regex error: {}",
+                    name,
+                    pattern,
+                    e
+                );
+            }
+        }
+
+        for pattern in &self.lint.secret_allow_keys {
+            if pattern.trim().is_empty() {
+                bail!(
+                    "Configuration error: empty key pattern found in 'lint.secretAllowKeys'.\n\
+                     Remove empty entries or provide a key glob like \"footer.supportEmail\"."
+                );
+            }
+            if let Err(e) = Pattern::new(pattern) {
+                bail!(
+                    "Configuration error: invalid glob in 'lint.secretAllowKeys': '{}'.\n\
+                     Glob error: {}",
+                    pattern,
+                    e
+                );
+            }
+        }
+
+        // Validate preservePatterns entries
+        for pattern in &self.preserve_patterns {
+            if pattern.trim().is_empty() {
+                bail!(
+                    "Configuration error: empty entry found in 'preservePatterns'.\n\
+                     Example: \"common:*\" or \"auth.login.*\""
+                );
+            }
+            if let Err(e) = Pattern::new(pattern) {
+                bail!(
+                    "Configuration error: invalid glob in 'preservePatterns': '{}'.\n\
+                     Glob error: {}",
+                    pattern,
+                    e
+                );
+            }
+        }
+
+        // Check output is not empty
+        if self.output.trim().is_empty() {
+            bail!(
+                "Configuration error: 'output' must be a non-empty directory path.\n\
+                 Example: \"output\": \"locales\""
+            );
+        }
+
+        // Check for potentially problematic output path characters
+        let invalid_chars = ['<', '>', '|', '\0'];
+        for c in invalid_chars {
+            if self.output.contains(c) {
+                bail!(
+                    "Configuration error: 'output' contains invalid character '{}'.\n\
+                     Please use a valid directory path.",
+                    c
+                );
+            }
+        }
+
+        if let Some(file_stem) = &self.merged_namespace_filename {
+            if file_stem.trim().is_empty() {
+                bail!(
+                    "Configuration error: 'mergedNamespaceFilename' must be a non-empty string when specified."
+                );
+            }
+            if !crate::paths::is_plain_file_name(file_stem) {
+                bail!(
+                    "Configuration error: 'mergedNamespaceFilename' must be a filename stem, not a path."
+                );
+            }
+        }
+
+        if self.flat_locale_files && !self.merge_namespaces && !self.namespace_less_mode() {
+            bail!(
+                "Configuration error: 'flatLocaleFiles' stores one file per locale and needs 'mergeNamespaces' or namespace-less mode."
+            );
+        }
+
+        if self.merged_nesting_depth == Some(0) {
+            bail!("Configuration error: 'mergedNestingDepth' must be at least 1.");
+        }
+
+        if self.shard_max_keys == Some(0) {
+            bail!("Configuration error: 'shardMaxKeys' must be at least 1.");
+        }
+
+        if self.merged_namespace_layout == MergedNamespaceLayout::Prefixed
+            && self.ns_separator.is_empty()
+        {
+            bail!(
+                "Configuration error: 'mergedNamespaceLayout' \"prefixed\" requires a non-empty 'nsSeparator'."
+            );
+        }
+
+        // Check functions is not empty
+        if self.functions.is_empty() {
+            bail!(
+                "Configuration error: 'functions' must contain at least one function name.\n\
+                 Example: \"functions\": [\"t\", \"i18n.t\"]"
+            );
+        }
+
+        for (i, hook) in self.use_translation_names.iter().enumerate() {
+            if hook.name().trim().is_empty() {
+                bail!(
+                    "Configuration error: 'useTranslationNames[{}]' must contain a non-empty function name.",
+                    i
+                );
+            }
+        }
+
+        for (i, name) in self.message_factory_functions.iter().enumerate() {
+            if name.trim().is_empty() {
+                bail!(
+                    "Configuration error: 'messageFactoryFunctions[{}]' must be a non-empty function name.",
+                    i
+                );
+            }
+        }
+
+        for (i, name) in self.key_producing_functions.iter().enumerate() {
+            if name.trim().is_empty() {
+                bail!(
+                    "Configuration error: 'keyProducingFunctions[{}]' must be a non-empty function name.",
+                    i
+                );
+            }
+        }
+
+        for (i, target) in self.outputs.iter().enumerate() {
+            if target.pattern.trim().is_empty() || target.output.trim().is_empty() {
+                bail!(
+                    "Configuration error: 'outputs[{}]' must have a non-empty 'match' and 'output'.",
+                    i
+                );
+            }
+            Pattern::new(target.pattern.trim_start_matches("./")).with_context(|| {
+                format!(
+                    "Configuration error: 'outputs[{}].match' is not a valid glob: {}",
+                    i, target.pattern
+                )
+            })?;
+        }
+
+        for (i, rule) in self.dynamic_key_rules.iter().enumerate() {
+            Pattern::new(rule.pattern.trim_start_matches("./")).with_context(|| {
+                format!(
+                    "Configuration error: 'dynamicKeyRules[{}].match' is not a valid glob: {}",
+                    i, rule.pattern
+                )
+            })?;
+        }
+
+        if let Some(email) = &self.email_templates {
+            if email.input.iter().all(|pattern| pattern.trim().is_empty()) {
+                bail!("Configuration error: 'emailTemplates.input' must list at least one glob.");
+            }
+            if email.output.trim().is_empty() {
+                bail!("Configuration error: 'emailTemplates.output' must not be empty.");
+            }
+            let web_roots = std::iter::once(&self.output)
+                .chain(self.outputs.iter().map(|target| &target.output));
+            for root in web_roots {
+                if root.trim_end_matches('/') == email.output.trim_end_matches('/') {
+                    bail!(
+                        "Configuration error: 'emailTemplates.output' ({}) must differ from the web locale roots.",
+                        email.output
+                    );
+                }
+            }
+            if email.functions.iter().any(|name| name.trim().is_empty()) {
+                bail!(
+                    "Configuration error: 'emailTemplates.functions' must not contain empty names."
+                );
+            }
+        }
+
+        for (i, component) in self.trans_components.iter().enumerate() {
+            let attrs = [
+                component.name(),
+                component.key_attr(),
+                component.ns_attr(),
+                component.default_attr(),
+            ];
+            if attrs.iter().any(|value| value.trim().is_empty()) {
+                bail!(
+                    "Configuration error: 'transComponents[{}]' must have a non-empty name and attribute names.",
+                    i
+                );
+            }
+        }
+
+        // defaultNamespace can be empty only in namespace-less mode (with nsSeparator disabled)
+        if self.default_namespace.trim().is_empty() && !self.ns_separator.is_empty() {
+            bail!(
+                "Configuration error: 'defaultNamespace' is empty but 'nsSeparator' is enabled.\n\
+                 Use namespace-less mode with both: \"defaultNamespace\": false and \"nsSeparator\": false."
+            );
+        }
+        if !self.default_namespace.is_empty()
+            && !crate::paths::is_plain_file_name(&self.default_namespace)
+        {
+            bail!(
+                "Configuration error: 'defaultNamespace' ('{}') must be a namespace name, not a path.",
+                self.default_namespace
+            );
+        }
+
+        if self.nesting_prefix.is_empty() || self.nesting_suffix.is_empty() {
+            bail!(
+                "Configuration error: 'nestingPrefix' and 'nestingSuffix' must be non-empty strings."
+            );
+        }
+        if self.interpolation_prefix.is_empty() || self.interpolation_suffix.is_empty() {
+            bail!(
+                "Configuration error: 'interpolationPrefix' and 'interpolationSuffix' must be non-empty strings."
+            );
+        }
+
+        if let Some(output) = &self.types.output {
+            if output.trim().is_empty() {
+                bail!("Configuration error: 'types.output' must be a non-empty string when specified.");
+            }
+        }
+        if let Some(resources_file) = &self.types.resources_file {
+            if resources_file.trim().is_empty() {
+                bail!("Configuration error: 'types.resourcesFile' must be a non-empty string when specified.");
+            }
+        }
+        if let Some(input_patterns) = &self.types.input {
+            for (i, pattern) in input_patterns.iter().enumerate() {
+                if pattern.trim().is_empty() {
+                    bail!(
+                        "Configuration error: 'types.input[{}]' must be a non-empty glob pattern.",
+                        i
+                    );
+                }
+            }
+        }
+        if let Some(EnableSelector::Mode(mode)) = &self.types.enable_selector {
+            if mode != "optimize" {
+                bail!(
+                    "Configuration error: 'types.enableSelector' must be true, false, or 'optimize'."
+                );
+            }
+        }
+
+        if let Some(locize) = &self.locize {
+            if locize.project_id.trim().is_empty() {
+                bail!(
+                    "Configuration error: 'locize.projectId' must be a non-empty string when Locize integration is configured."
+                );
+            }
+            if let Some(cdn_type) = &locize.cdn_type {
+                if cdn_type != "standard" && cdn_type != "pro" {
+                    bail!("Configuration error: 'locize.cdnType' must be 'standard' or 'pro'.");
+                }
+            }
+        }
+
+        if let Some(tms) = &self.tms {
+            if tms.project_id.trim().is_empty() {
+                bail!(
+                    "Configuration error: 'tms.projectId' must be a non-empty string when a TMS is configured."
+                );
+            }
+        }
+
+        for namespace in self.budgets.keys() {
+            if namespace.trim().is_empty() {
+                bail!("Configuration error: 'budgets' contains an empty namespace name.");
+            }
+        }
+
+        for (locale, threshold) in &self.release_gates {
+            if locale.trim().is_empty() {
+                bail!("Configuration error: 'releaseGates' contains an empty locale name.");
+            }
+            if !(0.0..=1.0).contains(threshold) {
+                bail!(
+                    "Configuration error: 'releaseGates.{}' must be between 0 and 1 (got {}).",
+                    locale,
+                    threshold
+                );
+            }
+        }
+
+        if self
+            .metadata_file
+            .as_ref()
+            .is_some_and(|path| path.trim().is_empty())
+        {
+            bail!("Configuration error: 'metadataFile' must be a non-empty path when specified.");
+        }
+        if self.removal_grace_days.is_some() && self.metadata_file.is_none() {
+            bail!("Configuration error: 'removalGraceDays' requires 'metadataFile' to record when keys died.");
+        }
+
+        for (i, transform) in self.key_transforms.iter().enumerate() {
+            let prefix = match transform {
+                KeyTransform::StripPrefix(prefix) => prefix,
+                KeyTransform::ReplacePrefix { from, .. } => from,
+                KeyTransform::LowercaseFirstSegment => continue,
+            };
+            if prefix.is_empty() {
+                bail!(
+                    "Configuration error: 'keyTransforms[{}]' must have a non-empty prefix.",
+                    i
+                );
+            }
+        }
+        if self
+            .key_map_file
+            .as_ref()
+            .is_some_and(|path| path.trim().is_empty())
+        {
+            bail!("Configuration error: 'keyMapFile' must be a non-empty path when specified.");
+        }
+        if self
+            .eslint_data_file
+            .as_ref()
+            .is_some_and(|path| path.trim().is_empty())
+        {
+            bail!("Configuration error: 'eslintDataFile' must be a non-empty path when specified.");
+        }
+        if self
+            .publish_dir
+            .as_ref()
+            .is_some_and(|path| path.trim().is_empty())
+        {
+            bail!("Configuration error: 'publishDir' must be a non-empty path when specified.");
+        }
+
+        match self.log_level.as_str() {
+            "error" | "warn" | "info" | "debug" => {}
+            _ => bail!("Configuration error: 'logLevel' must be one of: error, warn, info, debug."),
+        }
+
+        Ok(())
+    }
+
+    /// Load configuration from a JSON file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+
+        let config = serde_json::from_str(&content)
+            .map_err(anyhow::Error::from)
+            .and_then(Self::from_json_value)
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Load configuration from a JSON string
+    pub fn from_json_string(json_str: &str) -> Result<Self> {
+        let config = serde_json::from_str(json_str)
+            .map_err(anyhow::Error::from)
+            .and_then(Self::from_json_value)
+            .with_context(|| "Failed to parse config JSON string")?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Deserialize a config object, filling options it leaves out from its `preset`
+    fn from_json_value(mut value: serde_json::Value) -> Result<Self> {
+        if let Some(map) = value.as_object_mut() {
+            if let Some(preset) = map.get("preset").cloned() {
+                let preset: Preset = serde_json::from_value(preset)
+                    .context("Configuration error: unsupported 'preset'. Supported: nextjs")?;
+                for (key, default) in preset.defaults() {
+                    map.entry(key).or_insert(default);
+                }
+            }
+        }
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Try to load from default config file, or return default config
+    pub fn load_or_default<P: AsRef<Path>>(path: Option<P>) -> Result<Self> {
+        match path {
+            Some(p) => Self::load(p),
+            None => {
+                let default_path = Path::new("i18next-turbo.json");
+                if default_path.exists() {
+                    Self::load(default_path)
+                } else {
+                    // Default config is pre-validated, no need to validate again
+                    Ok(Self::default())
+                }
+            }
+        }
+    }
+}
+
+impl Config {
+    /// Resolve relative paths and globs (input, ignore, outputs, ...) against
+    /// `dir`, as if the tool were run from the config's own directory
+    pub fn resolve_paths_from(&mut self, dir: &Path) {
+        let resolve = |path: &mut String| *path = resolve_relative(dir, path);
+        self.input.iter_mut().for_each(resolve);
+        self.ignore.iter_mut().for_each(resolve);
+        self.generated_files.iter_mut().for_each(resolve);
+        resolve(&mut self.output);
+        for target in &mut self.outputs {
+            resolve(&mut target.pattern);
+            resolve(&mut target.output);
+        }
+        if let Some(email) = &mut self.email_templates {
+            email.input.iter_mut().for_each(resolve);
+            email.ignore.iter_mut().for_each(resolve);
+            resolve(&mut email.output);
+        }
+        let optional_paths = [
+            &mut self.metadata_file,
+            &mut self.key_map_file,
+            &mut self.eslint_data_file,
+            &mut self.publish_dir,
+            &mut self.types.output,
+            &mut self.types.output_dir,
+            &mut self.types.constants_dir,
+            &mut self.types.resources_file,
+            &mut self.types.locales_dir,
+        ];
+        for path in optional_paths.into_iter().flatten() {
+            resolve(path);
+        }
+        if let Some(inputs) = &mut self.types.input {
+            inputs.iter_mut().for_each(resolve);
+        }
+    }
+
+    pub fn output_format(&self) -> OutputFormat {
+        self.output_format
+    }
+
+    pub fn output_extension(&self) -> &'static str {
+        self.output_format.extension()
+    }
+
+    pub fn types_output_path(&self) -> String {
+        self.types
+            .output
+            .clone()
+            .unwrap_or_else(default_types_output)
+    }
+
+    pub fn types_input_patterns(&self) -> Option<Vec<String>> {
+        self.types.input.clone()
+    }
+
+    pub fn types_resources_file(&self) -> Option<String> {
+        self.types.resources_file.clone()
+    }
+
+    pub fn types_enable_selector(&self) -> Option<EnableSelector> {
+        self.types.enable_selector.clone()
+    }
+
+    pub fn types_default_locale(&self) -> Option<String> {
+        self.types.default_locale.clone()
+    }
+
+    pub fn types_locales_dir(&self) -> Option<String> {
+        self.types.locales_dir.clone()
+    }
+
+    pub fn types_indentation_string(&self) -> Option<String> {
+        self.types.indentation.as_ref().map(|i| i.to_string())
+    }
+
+    pub fn types_output_dir(&self) -> Option<String> {
+        self.types.output_dir.clone()
+    }
+
+    pub fn types_constants_dir(&self) -> String {
+        self.types
+            .constants_dir
+            .clone()
+            .unwrap_or_else(|| "src/i18n/keys".to_string())
+    }
+
+    /// Namespace filter and per-namespace output of type generation
+    pub fn types_namespaces(&self) -> crate::typegen::TypegenNamespaces<'_> {
+        crate::typegen::TypegenNamespaces {
+            include: self.types.include_namespaces.as_deref(),
+            exclude: self.types.exclude_namespaces.as_deref(),
+            output_dir: self.types.output_dir.as_deref().map(Path::new),
+        }
+    }
+
+    pub fn default_types_output() -> String {
+        default_types_output()
+    }
+
+    /// Locale root for keys extracted from `source`: the first matching `outputs`
+    /// entry, or `None` when the global output applies
+    pub fn output_target_for(&self, source: &Path) -> Option<&str> {
+        self.outputs
+            .iter()
+            .find(|target| {
+                crate::extractor::matches_input_patterns(
+                    source,
+                    std::slice::from_ref(&target.pattern),
+                )
+            })
+            .map(|target| target.output.as_str())
+    }
+
+    /// Get the primary language for this configuration
+    /// Returns `primary_language` if set, otherwise the first locale
+    pub fn primary_language(&self) -> &str {
+        self.primary_language
+            .as_deref()
+            .unwrap_or_else(|| self.locales.first().map(|s| s.as_str()).unwrap_or("en"))
+    }
+
+    pub fn secondary_languages(&self) -> Vec<String> {
+        if let Some(explicit) = &self.secondary_languages {
+            let primary = self.primary_language();
+            return explicit
+                .iter()
+                .filter(|locale| locale.as_str() != primary)
+                .cloned()
+                .collect();
+        }
+
+        let primary = self.primary_language();
+        self.locales
+            .iter()
+            .filter(|locale| locale.as_str() != primary)
+            .cloned()
+            .collect()
+    }
+
+    /// Separator joining namespace and key at the top level of merged files,
+    /// `None` unless `mergeNamespaces` uses the "prefixed" layout
+    pub fn merged_namespace_prefix(&self) -> Option<&str> {
+        (self.merge_namespaces && self.merged_namespace_layout == MergedNamespaceLayout::Prefixed)
+            .then_some(self.ns_separator.as_str())
+    }
+
+    pub fn namespace_less_mode(&self) -> bool {
+        self.default_namespace.is_empty()
+    }
+
+    /// Whether namespace files may be split into shards: `shardMaxKeys` is set
+    /// and each namespace has its own file
+    pub fn sharded_locale_files(&self) -> bool {
+        self.shard_max_keys.is_some() && !self.merge_namespaces && !self.flat_locale_files
+    }
+
+    pub fn effective_default_namespace(&self) -> &str {
+        if self.default_namespace.is_empty() {
+            "translation"
+        } else {
+            self.default_namespace.as_str()
+        }
+    }
+
+    /// Get the indentation string for JSON output
+    /// Returns the configured indentation or None if not set
+    pub fn indentation_string(&self) -> Option<String> {
+        self.indentation.as_ref().map(|i| i.to_string())
+    }
+}
+
+/// Prefix of environment variables overriding config values (`I18NEXT_TURBO_OUTPUT`, ...)
+pub const ENV_PREFIX: &str = "I18NEXT_TURBO_";
+
+/// Layer a resolved top-level config value came from, lowest precedence first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    Default,
+    /// Filled in by locale/source auto-detection (no config file)
+    Detected,
+    File,
+    /// Default of the configured `preset`
+    Preset(Preset),
+    /// Environment variable with the given name
+    Env(String),
+    /// `--set key=value`
+    Cli,
+}
+
+impl std::fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigSource::Default => write!(f, "default"),
+            ConfigSource::Detected => write!(f, "auto-detected"),
+            ConfigSource::File => write!(f, "config file"),
+            ConfigSource::Preset(preset) => write!(f, "preset {}", preset),
+            ConfigSource::Env(name) => write!(f, "env {}", name),
+            ConfigSource::Cli => write!(f, "--set"),
+        }
+    }
+}
+
+impl Config {
+    /// Environment variable overriding the top-level `key` (`defaultNamespace` ->
+    /// `I18NEXT_TURBO_DEFAULT_NAMESPACE`)
+    pub fn env_var_name(key: &str) -> String {
+        let mut name = ENV_PREFIX.to_string();
+        for (index, c) in key.chars().enumerate() {
+            if c.is_ascii_uppercase() && index > 0 {
+                name.push('_');
+            }
+            name.push(c.to_ascii_uppercase());
+        }
+        name
+    }
+
+    /// Apply environment variables, then `--set key=value` overrides, on top of this
+    /// (file) config. Returns the overridden keys and their layer, in application order.
+    pub fn apply_overrides(
+        &mut self,
+        env: impl Fn(&str) -> Option<String>,
+        cli_overrides: &[String],
+    ) -> Result<Vec<(String, ConfigSource)>> {
+        let serde_json::Value::Object(mut values) = serde_json::to_value(&*self)? else {
+            bail!("Configuration error: config did not serialize to an object");
+        };
+
+        let mut applied = Vec::new();
+        let keys: Vec<String> = values.keys().cloned().collect();
+        for key in keys {
+            let name = Self::env_var_name(&key);
+            if let Some(raw) = env(&name) {
+                let value = parse_override_value(&values[&key], &raw)
+                    .with_context(|| format!("Invalid value in environment variable {}", name))?;
+                values.insert(key.clone(), value);
+                applied.push((key, ConfigSource::Env(name)));
+            }
+        }
+
+        for entry in cli_overrides {
+            let Some((key, raw)) = entry.split_once('=') else {
+                bail!("Invalid --set '{}': expected key=value", entry);
+            };
+            let key = key.trim();
+            let Some(current) = values.get(key) else {
+                bail!("Invalid --set '{}': unknown config key '{}'", entry, key);
+            };
+            let value = parse_override_value(current, raw)
+                .with_context(|| format!("Invalid value in --set {}", entry))?;
+            values.insert(key.to_string(), value);
+            applied.push((key.to_string(), ConfigSource::Cli));
+        }
+
+        if applied.is_empty() {
+            return Ok(applied);
+        }
+        let key_filter = std::mem::take(&mut self.key_filter);
+        *self = serde_json::from_value(serde_json::Value::Object(values))
+            .context("Configuration error: overrides produced an invalid config")?;
+        self.key_filter = key_filter;
+        self.validate()?;
+        Ok(applied)
+    }
+
+    /// Options whose value differs in `other`, as (key, old value, new value)
+    /// in key order
+    pub fn changed_options(
+        &self,
+        other: &Config,
+    ) -> Result<Vec<(String, serde_json::Value, serde_json::Value)>> {
+        let serde_json::Value::Object(old) = serde_json::to_value(self)? else {
+            bail!("Configuration error: config did not serialize to an object");
+        };
+        let serde_json::Value::Object(mut new) = serde_json::to_value(other)? else {
+            bail!("Configuration error: config did not serialize to an object");
+        };
+        let mut changed: Vec<_> = old
+            .into_iter()
+            .filter_map(|(key, old_value)| {
+                let new_value = new.remove(&key).unwrap_or(serde_json::Value::Null);
+                (old_value != new_value).then_some((key, old_value, new_value))
+            })
+            .collect();
+        changed.extend(
+            new.into_iter()
+                .map(|(key, value)| (key, serde_json::Value::Null, value)),
+        );
+        changed.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(changed)
+    }
+}
+
+/// Parse an override string into the JSON shape of the value it replaces:
+/// booleans accept true/false/1/0, lists are comma-separated (or a JSON array),
+/// and objects or optional values take JSON, falling back to a plain string.
+fn parse_override_value(current: &serde_json::Value, raw: &str) -> Result<serde_json::Value> {
+    use serde_json::Value;
+    let trimmed = raw.trim();
+    Ok(match current {
+        Value::Bool(_) => match trimmed.to_ascii_lowercase().as_str() {
+            "true" | "1" | "yes" => Value::Bool(true),
+            "false" | "0" | "no" => Value::Bool(false),
+            _ => bail!("expected true or false, got '{}'", raw),
+        },
+        Value::Number(_) => serde_json::from_str(trimmed)
+            .ok()
+            .filter(Value::is_number)
+            .ok_or_else(|| anyhow::anyhow!("expected a number, got '{}'", raw))?,
+        Value::Array(_) if trimmed.starts_with('[') => {
+            serde_json::from_str(trimmed).context("expected a JSON array")?
+        }
+        Value::Array(_) => Value::Array(
+            trimmed
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| Value::String(item.to_string()))
+                .collect(),
+        ),
+        Value::String(_) => Value::String(raw.to_string()),
+        Value::Object(_) => serde_json::from_str(trimmed).context("expected a JSON object")?,
+        Value::Null => {
+            serde_json::from_str(trimmed).unwrap_or_else(|_| Value::String(raw.to_string()))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changed_options_lists_differing_keys() {
+        let old = Config::default();
+        let new = Config {
+            locales: vec!["en".to_string(), "de".to_string()],
+            output: "public/locales".to_string(),
+            ..Config::default()
+        };
+        let changed = old.changed_options(&new).unwrap();
+        let keys: Vec<&str> = changed.iter().map(|(key, _, _)| key.as_str()).collect();
+        assert_eq!(keys, vec!["locales", "output"]);
+        assert_eq!(changed[0].2, serde_json::json!(["en", "de"]));
+        assert!(old.changed_options(&old.clone()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn types_output_defaults_to_standard_path() {
+        let config = Config::default();
+        assert_eq!(config.types_output_path(), Config::default_types_output());
+    }
+
+    #[test]
+    fn types_output_can_be_overridden_via_json() {
+        let json = r#"{ "types": { "output": "generated/types.d.ts" } }"#;
+        let config = Config::from_json_string(json).unwrap();
+        assert_eq!(config.types_output_path(), "generated/types.d.ts");
+    }
+
+    #[test]
+    fn plural_config_uses_locale_rules_when_enabled() {
+        let mut config = Config::default();
+        config.locales = vec!["ru".to_string()];
+        config.use_locale_plural_rules = true;
+        let plural = config.plural_config();
+        assert!(plural.suffixes.contains(&"few".to_string()));
+        assert!(plural.suffixes.contains(&"many".to_string()));
+        assert!(plural.suffixes.contains(&"one".to_string()));
+        assert!(plural.suffixes.contains(&"other".to_string()));
+    }
+
+    #[test]
+    fn plural_config_uses_ordinal_rules_for_ordinal_suffixes() {
+        let config = Config::from_json_string(r#"{ "locales": ["en", "ja"] }"#).unwrap();
+        let plural = config.plural_config();
+        assert_eq!(plural.suffixes, vec!["one", "other"]);
+        assert_eq!(plural.ordinal_suffixes, vec!["few", "one", "other", "two"]);
+        assert_eq!(
+            plural.strip_plural_suffix("place_ordinal_two").as_deref(),
+            Some("place")
+        );
+    }
+
+    #[test]
+    fn plural_config_uses_explicit_suffixes_when_disabled() {
+        let mut config = Config::default();
+        config.use_locale_plural_rules = false;
+        config.plural_suffixes = vec!["zero".to_string(), "other".to_string()];
+        let plural = config.plural_config();
+        assert_eq!(
+            plural.suffixes,
+            vec!["zero".to_string(), "other".to_string()]
+        );
+    }
+
+    #[test]
+    fn plural_config_uses_legacy_suffixes_in_compat_mode() {
+        let mut config =
+            Config::from_json_string(r#"{ "locales": ["en", "ru"], "pluralCompat": "v3" }"#)
+                .unwrap();
+        config.use_locale_plural_rules = true;
+        let plural = config.plural_config();
+        assert_eq!(plural.compat, PluralCompat::V3);
+        assert_eq!(plural.suffixes, vec!["", "plural", "0", "1", "2"]);
+
+        let err = Config::from_json_string(r#"{ "pluralCompat": "v1" }"#).unwrap_err();
+        assert!(format!("{:#}", err).contains("unknown variant `v1`"));
+    }
+
+    #[test]
+    fn single_category_plurals_parse_per_locale() {
+        let config = Config::from_json_string(
+            r#"{ "locales": ["en", "ja"], "singleCategoryPlurals": { "ja": "copyPrimary" } }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.single_category_plurals.get("ja"),
+            Some(&SingleCategoryPlural::CopyPrimary)
+        );
+        assert!(config.plural_config().single_category_other);
+        assert!(!Config::default().plural_config().single_category_other);
+
+        let err = Config::from_json_string(r#"{ "singleCategoryPlurals": { "ja": "all" } }"#)
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("unknown variant `all`"));
+    }
+
+    #[test]
+    fn path_style_defaults_to_native_and_accepts_overrides() {
+        let mut config = Config::default();
+        assert_eq!(config.path_style, PathStyle::Native);
+        config
+            .apply_overrides(
+                |name| (name == "I18NEXT_TURBO_PATH_STYLE").then(|| "forward".to_string()),
+                &[],
+            )
+            .unwrap();
+        assert_eq!(config.path_style, PathStyle::Forward);
+        assert!(PathStyle::parse_str("backslash").is_err());
+    }
+
+    #[test]
+    fn concurrent_runs_default_to_queue() {
+        assert_eq!(Config::default().concurrent_runs, ConcurrentRuns::Queue);
+        let config = Config::from_json_string(r#"{ "concurrentRuns": "reject" }"#).unwrap();
+        assert_eq!(config.concurrent_runs, ConcurrentRuns::Reject);
+        assert!(ConcurrentRuns::parse_str("parallel").is_err());
+    }
+
+    #[test]
+    fn plural_variant_keys_follow_context_plural_order() {
+        let config = Config::from_json_string(
+            r##"{ "contextPluralOrder": "plural-context", "contextSeparator": "#" }"##,
+        )
+        .unwrap();
+        let plural = config.plural_config();
+        assert_eq!(plural.order, ContextPluralOrder::PluralContext);
+        assert_eq!(
+            plural.variant_key("friend", Some("male"), Some("one")),
+            "friend_one#male"
+        );
+        assert_eq!(
+            plural.strip_plural_suffix("friend_one#male").as_deref(),
+            Some("friend#male")
+        );
+        assert_eq!(
+            plural.strip_plural_suffix("friend_other").as_deref(),
+            Some("friend")
+        );
+        assert_eq!(plural.strip_plural_suffix("friend#male"), None);
+
+        let default_plural = Config::default().plural_config();
+        assert_eq!(
+            default_plural.variant_key("friend", Some("male"), Some("one")),
+            "friend_male_one"
+        );
+        assert!(default_plural.uses_default_layout());
+    }
+
+    #[test]
+    fn budgets_are_parsed_from_json() {
+        let json = r#"{ "budgets": { "common": 300 }, "failOnBudgetExceeded": true }"#;
+        let config = Config::from_json_string(json).unwrap();
+        assert_eq!(config.budgets.get("common"), Some(&300));
+        assert!(config.fail_on_budget_exceeded);
+
+        let err = Config::from_json_string(r#"{ "budgets": { "": 1 } }"#).unwrap_err();
+        assert!(err.to_string().contains("'budgets'"));
+    }
+
+    #[test]
+    fn trans_unescape_keys_only_apply_when_decoding() {
+        let config = Config::from_json_string(r#"{ "transUnescapeKeys": false }"#).unwrap();
+        assert_eq!(
+            config.trans_unescape(),
+            TransUnescape {
+                defaults: true,
+                keys: false,
+            }
+        );
+        let config = Config::from_json_string(r#"{ "transUnescape": false }"#).unwrap();
+        assert_eq!(
+            config.trans_unescape(),
+            TransUnescape {
+                defaults: false,
+                keys: false,
+            }
+        );
+    }
+
+    #[test]
+    fn generated_files_extend_the_extraction_ignore_list() {
+        let config = Config::from_json_string(
+            r#"{ "ignore": ["**/*.test.ts"], "generatedFiles": ["src/__generated__/**"] }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.extraction_ignore(),
+            vec!["**/*.test.ts", "src/__generated__/**"]
+        );
+        let err = Config::from_json_string(r#"{ "generatedFiles": [" "] }"#).unwrap_err();
+        assert!(err.to_string().contains("'generatedFiles'"));
+    }
+
+    #[test]
+    fn locales_and_default_namespace_must_not_be_paths() {
+        for json in [
+            r#"{ "locales": ["en", "../ja"] }"#,
+            r#"{ "locales": ["/tmp"] }"#,
+            r#"{ "secondaryLanguages": [".."] }"#,
+            r#"{ "defaultNamespace": "../common" }"#,
+            r#"{ "mergeNamespaces": true, "mergedNamespaceFilename": ".." }"#,
+        ] {
+            let err = Config::from_json_string(json).unwrap_err();
+            assert!(err.to_string().contains("not a path"), "{}: {}", json, err);
+        }
+        assert!(Config::from_json_string(r#"{ "locales": ["en-US", "zh_Hant"] }"#).is_ok());
+    }
+
+    #[test]
+    fn resolve_paths_from_rebases_relative_paths_and_globs() {
+        let mut config = Config::from_json_string(
+            r#"{
+                "input": ["./src/**/*.tsx", "/abs/app/**/*.ts"],
+                "ignore": ["**/*.test.ts", "src/legacy/**"],
+                "output": "locales",
+                "outputs": [{ "match": "src/admin/**", "output": "admin/locales" }],
+                "keyMapFile": "key-map.json"
+            }"#,
+        )
+        .unwrap();
+        config.resolve_paths_from(Path::new("packages/ui"));
+        assert_eq!(
+            config.input,
+            vec!["packages/ui/src/**/*.tsx", "/abs/app/**/*.ts"]
+        );
+        assert_eq!(
+            config.ignore,
+            vec!["**/*.test.ts", "packages/ui/src/legacy/**"]
+        );
+        assert_eq!(config.output, "packages/ui/locales");
+        assert_eq!(config.outputs[0].pattern, "packages/ui/src/admin/**");
+        assert_eq!(config.outputs[0].output, "packages/ui/admin/locales");
+        assert_eq!(
+            config.key_map_file.as_deref(),
+            Some("packages/ui/key-map.json")
+        );
+        assert_eq!(config.types.output, None);
+    }
+
+    #[test]
+    fn key_transforms_are_parsed_from_json() {
+        let json = r#"{
+            "keyTransforms": [
+                { "stripPrefix": "app." },
+                { "replacePrefix": { "from": "legacy.", "to": "core." } },
+                "lowercaseFirstSegment"
+            ],
+            "keyMapFile": "src/i18n/key-map.json"
+        }"#;
+        let config = Config::from_json_string(json).unwrap();
+        assert_eq!(
+            config.key_transforms,
+            vec![
+                KeyTransform::StripPrefix("app.".to_string()),
+                KeyTransform::ReplacePrefix {
+                    from: "legacy.".to_string(),
+                    to: "core.".to_string()
+                },
+                KeyTransform::LowercaseFirstSegment,
+            ]
+        );
+        assert_eq!(
+            config.key_map_file.as_deref(),
+            Some("src/i18n/key-map.json")
+        );
+
+        let err = Config::from_json_string(r#"{ "keyTransforms": [{ "stripPrefix": "" }] }"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("'keyTransforms[0]'"));
+    }
+
+    #[test]
+    fn release_gates_must_be_ratios() {
+        let config = Config::from_json_string(r#"{ "releaseGates": { "de": 0.95 } }"#).unwrap();
+        assert_eq!(config.release_gates.get("de"), Some(&0.95));
+
+        let err = Config::from_json_string(r#"{ "releaseGates": { "de": 95 } }"#).unwrap_err();
+        assert!(err.to_string().contains("'releaseGates.de'"));
+    }
+
+    #[test]
+    fn lint_length_limits_are_validated() {
+        let json = r#"{ "lint": { "maxLength": { "buttons.*": 24 }, "maxLengthRatio": 2.5 } }"#;
+        let config = Config::from_json_string(json).unwrap();
+        assert_eq!(config.lint.max_length.get("buttons.*"), Some(&24));
+        assert_eq!(config.lint.max_length_ratio, Some(2.5));
+
+        let err = Config::from_json_string(r#"{ "lint": { "maxLengthRatio": 0 } }"#).unwrap_err();
+        assert!(err.to_string().contains("'lint.maxLengthRatio'"));
+    }
+
+    #[test]
+    fn lint_secret_rules_are_validated() {
+        let config = Config::default();
+        assert!(config.lint.builtin_secret_rules);
+        let json = r#"{ "lint": { "builtinSecretRules": false, "secretRules": { "ticket": "JIRA-\\d+" }, "secretAllowKeys": ["footer.*"] } }"#;
+        let config = Config::from_json_string(json).unwrap();
+        assert!(!config.lint.builtin_secret_rules);
+        assert_eq!(config.lint.secret_rules["ticket"], r"JIRA-\d+");
+
+        let err = Config::from_json_string(r#"{ "lint": { "secretRules": { "bad": "(" } } }"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("'lint.secretRules.bad'"));
+    }
+
+    #[test]
+    fn preset_fills_defaults_without_overriding_config() {
+        let config =
+            Config::from_json_string(r#"{ "preset": "nextjs", "output": "i18n" }"#).unwrap();
+        assert_eq!(config.preset, Some(Preset::Nextjs));
+        assert_eq!(config.output, "i18n");
+        assert!(config.flat_locale_files);
+        assert!(config.namespace_less_mode());
+        assert!(config
+            .input
+            .iter()
+            .any(|pattern| pattern.starts_with("app/")));
+        assert!(config
+            .use_translation_names
+            .iter()
+            .any(|name| name.name() == "getTranslations"));
+
+        let err = Config::from_json_string(r#"{ "preset": "remix" }"#).unwrap_err();
+        assert!(format!("{:#}", err).contains("unsupported 'preset'"));
+
+        let err = Config::from_json_string(r#"{ "flatLocaleFiles": true }"#).unwrap_err();
+        assert!(err.to_string().contains("'flatLocaleFiles'"));
+    }
+
+    #[test]
+    fn outputs_route_sources_to_package_locale_roots() {
+        let config = Config::from_json_string(
+            r#"{
+              "outputs": [
+                { "match": "./packages/ui/**", "output": "packages/ui/locales" },
+                { "match": "packages/*/src/**/*.{ts,tsx}", "output": "packages/shared/locales" }
+              ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.output_target_for(Path::new("packages/ui/src/Button.tsx")),
+            Some("packages/ui/locales")
+        );
+        assert_eq!(
+            config.output_target_for(Path::new("./packages/forms/src/Field.ts")),
+            Some("packages/shared/locales")
+        );
+        assert_eq!(config.output_target_for(Path::new("src/app.ts")), None);
+
+        let err = Config::from_json_string(r#"{ "outputs": [{ "match": "", "output": "x" }] }"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("'outputs[0]'"));
+    }
+
+    #[test]
+    fn email_templates_config_overrides_web_options() {
+        let config = Config::from_json_string(
+            r#"{
+              "input": ["src/**/*.tsx"],
+              "output": "locales",
+              "locales": ["en", "de"],
+              "keyTransforms": [{ "stripPrefix": "app." }],
+              "emailTemplates": {
+                "input": ["emails/**/*.mjml"],
+                "output": "emails/locales",
+                "locales": ["en"]
+              }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.extraction_ignore(),
+            vec!["emails/**/*.mjml".to_string()]
+        );
+
+        let email = config.email_templates_config().unwrap();
+        assert_eq!(email.input, vec!["emails/**/*.mjml".to_string()]);
+        assert_eq!(email.output, "emails/locales");
+        assert_eq!(email.functions, vec!["tEmail".to_string()]);
+        assert_eq!(email.key_separator, "");
+        assert_eq!(email.default_namespace, "email");
+        assert_eq!(email.locales, vec!["en".to_string()]);
+        assert!(email.key_transforms.is_empty());
+        assert!(email.extraction_ignore().is_empty());
+        assert!(Config::default().email_templates_config().is_none());
+
+        let err = Config::from_json_string(
+            r#"{ "output": "locales", "emailTemplates": { "input": ["e/*.hbs"], "output": "locales/" } }"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("'emailTemplates.output'"));
+    }
+
+    #[test]
+    fn plural_config_returns_empty_when_disable_plurals_is_true() {
+        let mut config = Config::default();
+        config.disable_plurals = true;
+        let plural = config.plural_config();
+        assert!(plural.suffixes.is_empty());
+    }
+
+    #[test]
+    fn parses_use_translation_names_and_nesting_settings() {
+        let json = r#"
+        {
+          "useTranslationNames": [
+            "useTranslation",
+            { "name": "loadPageTranslations", "nsArg": 1, "keyPrefixArg": 2 }
+          ],
+          "nestingPrefix": "__nest__(",
+          "nestingSuffix": ")",
+          "nestingOptionsSeparator": "|"
+        }
+        "#;
+        let config = Config::from_json_string(json).unwrap();
+        assert_eq!(config.use_translation_names.len(), 2);
+        assert_eq!(
+            config.use_translation_names[1].name(),
+            "loadPageTranslations"
+        );
+        assert_eq!(config.use_translation_names[1].ns_arg(), 1);
+        assert_eq!(config.use_translation_names[1].key_prefix_arg(), 2);
+        assert_eq!(config.nesting_prefix, "__nest__(");
+        assert_eq!(config.nesting_suffix, ")");
+        assert_eq!(config.nesting_options_separator, "|");
+    }
+
+    #[test]
+    fn parses_message_factory_functions() {
+        let config = Config::default();
+        assert!(config.message_factory_functions.is_empty());
+
+        let config =
+            Config::from_json_string(r#"{ "messageFactoryFunctions": ["defineMessages"] }"#)
+                .unwrap();
+        assert_eq!(config.message_factory_functions, vec!["defineMessages"]);
+
+        let err = Config::from_json_string(r#"{ "messageFactoryFunctions": [" "] }"#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("messageFactoryFunctions[0]"));
+
+        let config = Config::from_json_string(r#"{ "keyProducingFunctions": ["k"] }"#).unwrap();
+        assert_eq!(config.key_producing_functions, vec!["k"]);
+        assert!(Config::from_json_string(r#"{ "keyProducingFunctions": [""] }"#).is_err());
+    }
+
+    #[test]
+    fn env_var_names_use_upper_snake_case() {
+        assert_eq!(Config::env_var_name("output"), "I18NEXT_TURBO_OUTPUT");
+        assert_eq!(
+            Config::env_var_name("defaultNamespace"),
+            "I18NEXT_TURBO_DEFAULT_NAMESPACE"
+        );
+    }
+
+    #[test]
+    fn overrides_layer_env_then_cli_over_file_values() {
+        let mut config =
+            Config::from_json_string(r#"{ "output": "file-locales", "locales": ["en"] }"#).unwrap();
+        let env = |name: &str| match name {
+            "I18NEXT_TURBO_OUTPUT" => Some("env-locales".to_string()),
+            "I18NEXT_TURBO_LOCALES" => Some("en, de".to_string()),
+            "I18NEXT_TURBO_REMOVE_UNUSED_KEYS" => Some("false".to_string()),
+            _ => None,
+        };
+
+        let applied = config
+            .apply_overrides(env, &["output=cli-locales".to_string()])
+            .unwrap();
+        assert_eq!(config.output, "cli-locales");
+        assert_eq!(config.locales, vec!["en", "de"]);
+        assert!(!config.remove_unused_keys);
+        assert_eq!(
+            applied.last(),
+            Some(&("output".to_string(), ConfigSource::Cli))
+        );
+
+        let err = config
+            .apply_overrides(|_| None, &["unknownKey=1".to_string()])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown config key"));
+
+        let err = config
+            .apply_overrides(|_| None, &["mergedNestingDepth=0".to_string()])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("mergedNestingDepth"));
+    }
+
+    #[test]
+    fn parses_merged_namespace_layout_and_depth() {
+        let config = Config::from_json_string(
+            r#"{ "mergeNamespaces": true, "mergedNamespaceLayout": "prefixed", "mergedNestingDepth": 1 }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.merged_namespace_layout,
+            MergedNamespaceLayout::Prefixed
+        );
+        assert_eq!(config.merged_nesting_depth, Some(1));
+        assert_eq!(config.merged_namespace_prefix(), Some(":"));
+
+        let nested = Config::from_json_string(r#"{ "mergeNamespaces": true }"#).unwrap();
+        assert_eq!(nested.merged_namespace_prefix(), None);
+
+        let err = Config::from_json_string(r#"{ "mergedNestingDepth": 0 }"#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("mergedNestingDepth"));
+
+        let err = Config::from_json_string(
+            r#"{ "mergedNamespaceLayout": "prefixed", "nsSeparator": false }"#,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("mergedNamespaceLayout"));
+    }
+
+    #[test]
+    fn parses_trans_components_with_custom_attribute_names() {
+        let json = r#"
+        {
+          "transComponents": [
+            "Trans",
+            { "name": "T", "keyAttr": "k", "nsAttr": "namespace", "defaultAttr": "fallback" },
+            { "name": "Message", "keyAttr": "id" }
+          ]
+        }
+        "#;
+        let config = Config::from_json_string(json).unwrap();
+        assert_eq!(config.trans_components.len(), 3);
+        assert_eq!(config.trans_components[0].key_attr(), "i18nKey");
+        assert_eq!(config.trans_components[1].name(), "T");
+        assert_eq!(config.trans_components[1].key_attr(), "k");
+        assert_eq!(config.trans_components[1].ns_attr(), "namespace");
+        assert_eq!(config.trans_components[1].default_attr(), "fallback");
+        assert_eq!(config.trans_components[2].ns_attr(), "ns");
+        assert_eq!(config.trans_components[2].default_attr(), "defaults");
+
+        let invalid = r#"{ "transComponents": [{ "name": "T", "keyAttr": "" }] }"#;
+        let err = Config::from_json_string(invalid).unwrap_err();
+        assert!(err.to_string().contains("transComponents[0]"));
+    }
+
+    #[test]
+    fn supports_namespace_less_mode_with_default_namespace_false() {
+        let json = r#"
+        {
+          "defaultNamespace": false,
+          "nsSeparator": false
+        }
+        "#;
+        let config = Config::from_json_string(json).unwrap();
+        assert!(config.namespace_less_mode());
+        assert_eq!(config.effective_default_namespace(), "translation");
+    }
+
+    #[test]
+    fn resolves_secondary_languages_from_explicit_or_locales() {
+        let mut config = Config::default();
+        config.locales = vec!["en".to_string(), "ja".to_string(), "fr".to_string()];
+        config.primary_language = Some("en".to_string());
+        assert_eq!(
+            config.secondary_languages(),
+            vec!["ja".to_string(), "fr".to_string()]
+        );
+
+        config.secondary_languages = Some(vec!["fr".to_string()]);
+        assert_eq!(config.secondary_languages(), vec!["fr".to_string()]);
+    }
+
+    #[test]
+    fn parses_types_enable_selector() {
+        let json = r#"{ "types": { "enableSelector": "optimize" } }"#;
+        let config = Config::from_json_string(json).unwrap();
+        assert!(matches!(
+            config.types_enable_selector(),
+            Some(EnableSelector::Mode(mode)) if mode == "optimize"
+        ));
+    }
+}
+
+fn compute_plural_suffixes_from_locales(locales: &[String], ordinal: bool) -> Vec<String> {
+    let mut categories = BTreeSet::new();
+
+    for locale in locales {
+        if let Some(locale_categories) = crate::plurals::plural_categories(locale, ordinal) {
+            for cat in locale_categories {
+                categories.insert(cat);
+            }
+        }
+    }
+
+    if categories.is_empty() {
+        return vec!["one".to_string(), "other".to_string()];
+    }
+
+    if !categories.contains("other") {
+        categories.insert("other".to_string());
+    }
+
+    categories.into_iter().collect()
+}
+
+pub(crate) fn categories_for_locale(locale: &str) -> Option<Vec<String>> {
+    let trimmed = locale.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    let parsed: Locale = trimmed.parse().ok()?;
+    let data_locale = parsed.into();
+    let rules = PluralRules::try_new_cardinal(&data_locale).ok()?;
+    let supported: Vec<PluralCategory> = rules.categories().collect();
+
+    let mut result = Vec::new();
+    for category in [
+        PluralCategory::Zero,
+        PluralCategory::One,
+        PluralCategory::Two,
+        PluralCategory::Few,
+        PluralCategory::Many,
+        PluralCategory::Other,
+    ] {
+        if supported.contains(&category) {
+            result.push(plural_category_to_str(category).to_string());
+        }
+    }
+    Some(result)
+}
+
+pub(crate) fn plural_category_to_str(category: PluralCategory) -> &'static str {
+    match category {
+        PluralCategory::Zero => "zero",
+        PluralCategory::One => "one",
+        PluralCategory::Two => "two",
+        PluralCategory::Few => "few",
+        PluralCategory::Many => "many",
+        PluralCategory::Other => "other",
+    }
+}
@@ -0,0 +1,292 @@
+//! Machine-readable report of keys skipped for conflicts, written by
+//! `extract --conflict-report` so an unattended run can be resolved later.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use crate::config::Config;
+use crate::extractor::ExtractedKey;
+use crate::json_sync::{KeyConflict, SyncResult};
+
+pub const REPORT_VERSION: u32 = 1;
+
+/// Child key holding the value moved aside by the `nest` strategy
+pub const NESTED_VALUE_KEY: &str = "_value";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConflictReport {
+    pub version: u32,
+    pub conflicts: Vec<ConflictEntry>,
+}
+
+/// One key skipped in one locale file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConflictEntry {
+    /// Locale file the key was skipped in
+    pub file: String,
+    pub namespace: String,
+    pub key: String,
+    /// Object path of the key inside the locale file
+    pub path: Vec<String>,
+    /// Path of the value in the way
+    pub conflict_path: String,
+    pub existing_value: Value,
+    pub wanted_value: Value,
+    /// Source files using the key
+    pub used_in: Vec<String>,
+}
+
+impl ConflictReport {
+    /// Conflicts of a sync run; `files` are the extracted source files, used to
+    /// list where each key is used
+    pub fn from_sync_results(
+        config: &Config,
+        results: &[SyncResult],
+        files: &[(String, Vec<ExtractedKey>)],
+    ) -> Self {
+        let default_namespace = config.effective_default_namespace();
+        let mut used_in: BTreeMap<(&str, &str), BTreeSet<&str>> = BTreeMap::new();
+        for (file_path, keys) in files {
+            for key in keys {
+                let namespace = key.namespace.as_deref().unwrap_or(default_namespace);
+                used_in
+                    .entry((namespace, key.key.as_str()))
+                    .or_default()
+                    .insert(file_path.as_str());
+            }
+        }
+
+        let conflicts = results
+            .iter()
+            .flat_map(|result| {
+                result.conflicts.iter().map(|skipped| {
+                    let conflict_path = match &skipped.conflict {
+                        KeyConflict::ValueIsNotObject { key_path, .. }
+                        | KeyConflict::ObjectIsValue { key_path } => key_path.clone(),
+                    };
+                    ConflictEntry {
+                        file: result.file_path.clone(),
+                        namespace: skipped.namespace.clone(),
+                        key: skipped.key.clone(),
+                        path: skipped.path.clone(),
+                        conflict_path,
+                        existing_value: skipped.existing_value.clone(),
+                        wanted_value: skipped.value.clone(),
+                        used_in: used_in
+                            .get(&(skipped.namespace.as_str(), skipped.key.as_str()))
+                            .into_iter()
+                            .flatten()
+                            .map(|file| file.to_string())
+                            .collect(),
+                    }
+                })
+            })
+            .collect();
+        Self {
+            version: REPORT_VERSION,
+            conflicts,
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read conflict report: {}", path.display()))?;
+        let report: Self = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid conflict report: {}", path.display()))?;
+        if report.version != REPORT_VERSION {
+            bail!(
+                "Unsupported conflict report version {} in {} (expected {})",
+                report.version,
+                path.display(),
+                REPORT_VERSION
+            );
+        }
+        Ok(report)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)? + "\n")
+            .with_context(|| format!("Failed to write conflict report: {}", path.display()))
+    }
+}
+
+/// How `resolve-conflicts` settles a conflict
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolveStrategy {
+    /// Leave the locale file as it is; the key stays unwritten
+    KeepExisting,
+    /// Replace the value in the way with the wanted key
+    Overwrite,
+    /// Keep both: a scalar in the way moves under `_value` of the new object.
+    /// An object in the way of a scalar key is left as it is.
+    Nest,
+}
+
+impl ResolveStrategy {
+    /// `keep-existing`, `overwrite` or `nest`, optionally written as
+    /// `strategy=<name>`
+    pub fn parse_str(value: &str) -> Result<Self> {
+        let name = value.strip_prefix("strategy=").unwrap_or(value);
+        match name.to_lowercase().as_str() {
+            "keep-existing" => Ok(ResolveStrategy::KeepExisting),
+            "overwrite" => Ok(ResolveStrategy::Overwrite),
+            "nest" => Ok(ResolveStrategy::Nest),
+            other => bail!(
+                "Unsupported conflict strategy '{}'. Supported: keep-existing, overwrite, nest",
+                other
+            ),
+        }
+    }
+}
+
+/// Apply `strategy` to the conflict of `entry` in the locale `map`. Returns
+/// whether the map changed; conflicts no longer present are left alone.
+pub fn resolve_in_map(
+    map: &mut Map<String, Value>,
+    entry: &ConflictEntry,
+    strategy: ResolveStrategy,
+) -> bool {
+    if strategy == ResolveStrategy::KeepExisting {
+        return false;
+    }
+    let mut current = map;
+    for (i, part) in entry.path.iter().enumerate() {
+        let rest = &entry.path[i + 1..];
+        match current.get(part) {
+            None | Some(Value::Array(_)) => return false,
+            Some(Value::Object(_)) if !rest.is_empty() => {}
+            Some(Value::Object(_)) => {
+                // An object holds the children of the wanted scalar key; the
+                // key cannot become an object, so only `overwrite` applies
+                if strategy != ResolveStrategy::Overwrite {
+                    return false;
+                }
+                current.insert(part.clone(), entry.wanted_value.clone());
+                return true;
+            }
+            // The key already holds a value
+            Some(_) if rest.is_empty() => return false,
+            Some(scalar) => {
+                let mut replacement = nested(rest, entry.wanted_value.clone());
+                if strategy == ResolveStrategy::Nest {
+                    replacement
+                        .entry(NESTED_VALUE_KEY)
+                        .or_insert_with(|| scalar.clone());
+                }
+                current.insert(part.clone(), Value::Object(replacement));
+                return true;
+            }
+        }
+        current = match current.get_mut(part) {
+            Some(Value::Object(nested)) => nested,
+            _ => return false,
+        };
+    }
+    false
+}
+
+/// `{ path[0]: { path[1]: ... value } }`
+fn nested(path: &[String], value: Value) -> Map<String, Value> {
+    let mut map = Map::new();
+    match path.split_first() {
+        Some((first, [])) => {
+            map.insert(first.clone(), value);
+        }
+        Some((first, rest)) => {
+            map.insert(first.clone(), Value::Object(nested(rest, value)));
+        }
+        None => {}
+    }
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(path: &[&str], wanted: &str) -> ConflictEntry {
+        ConflictEntry {
+            file: "locales/en/translation.json".to_string(),
+            namespace: "translation".to_string(),
+            key: path.join("."),
+            path: path.iter().map(|part| part.to_string()).collect(),
+            conflict_path: path[0].to_string(),
+            existing_value: Value::Null,
+            wanted_value: Value::from(wanted),
+            used_in: Vec::new(),
+        }
+    }
+
+    fn resolved(strategy: ResolveStrategy, existing: Value, entry: &ConflictEntry) -> Value {
+        let Value::Object(mut map) = existing else {
+            panic!("locale root must be an object");
+        };
+        resolve_in_map(&mut map, entry, strategy);
+        Value::Object(map)
+    }
+
+    #[test]
+    fn resolves_scalar_in_the_way_of_nested_key() {
+        let existing = json!({ "button": "Button" });
+        let entry = entry(&["button", "submit"], "Submit");
+        assert_eq!(
+            resolved(ResolveStrategy::KeepExisting, existing.clone(), &entry),
+            existing
+        );
+        assert_eq!(
+            resolved(ResolveStrategy::Overwrite, existing.clone(), &entry),
+            json!({ "button": { "submit": "Submit" } })
+        );
+        assert_eq!(
+            resolved(ResolveStrategy::Nest, existing, &entry),
+            json!({ "button": { "_value": "Button", "submit": "Submit" } })
+        );
+    }
+
+    #[test]
+    fn resolves_object_in_the_way_of_scalar_key() {
+        let existing = json!({ "menu": { "open": "Open" } });
+        let entry = entry(&["menu"], "Menu");
+        assert_eq!(
+            resolved(ResolveStrategy::Overwrite, existing.clone(), &entry),
+            json!({ "menu": "Menu" })
+        );
+        assert_eq!(
+            resolved(ResolveStrategy::Nest, existing.clone(), &entry),
+            existing
+        );
+
+        // Conflicts fixed by hand in the meantime are left alone
+        let Value::Object(mut map) = json!({ "menu": "Menu" }) else {
+            unreachable!()
+        };
+        assert!(!resolve_in_map(
+            &mut map,
+            &entry,
+            ResolveStrategy::Overwrite
+        ));
+    }
+
+    #[test]
+    fn parses_strategies_with_optional_prefix() {
+        assert_eq!(
+            ResolveStrategy::parse_str("strategy=keep-existing").unwrap(),
+            ResolveStrategy::KeepExisting
+        );
+        assert_eq!(
+            ResolveStrategy::parse_str("nest").unwrap(),
+            ResolveStrategy::Nest
+        );
+        assert!(ResolveStrategy::parse_str("merge").is_err());
+    }
+}
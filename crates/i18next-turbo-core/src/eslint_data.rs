@@ -0,0 +1,152 @@
+//! `eslintDataFile`: the valid keys and namespaces of the project, and the
+//! namespaces each source file uses, as plain JSON for lint rules in the style
+//! of eslint-plugin-i18next. Editors then flag unknown keys through ESLint
+//! without a language server.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use crate::config::Config;
+use crate::extractor::ExtractedKey;
+use crate::fs::{FileSystem, RealFileSystem};
+use crate::json_sync;
+
+/// Bumped when the shape of the file changes
+pub const FORMAT_VERSION: u64 = 1;
+
+/// Build the rule data from the primary-locale catalog and the extracted
+/// `files`. Plural keys are also listed by their base (`item` for `item_one`),
+/// the form source code uses with `count`.
+pub fn build<'a, P: AsRef<Path>>(
+    config: &Config,
+    files: impl IntoIterator<Item = (P, &'a Vec<ExtractedKey>)>,
+) -> Result<Value> {
+    let catalog =
+        json_sync::load_locale_namespaces(config, &config.output, config.primary_language())?;
+    let plural_config = config.plural_config();
+    let mut keys: BTreeMap<String, BTreeSet<String>> = catalog
+        .keys()
+        .map(|namespace| (namespace.clone(), BTreeSet::new()))
+        .collect();
+    for (namespace, key) in
+        json_sync::flatten_leaf_values(&catalog, &config.key_separator).into_keys()
+    {
+        let entries = keys.entry(namespace).or_default();
+        if let Some(base) = plural_config.strip_plural_suffix(&key) {
+            entries.insert(base);
+        }
+        entries.insert(key);
+    }
+
+    let mut scopes: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for (path, extracted) in files {
+        if extracted.is_empty() {
+            continue;
+        }
+        let namespaces = scopes.entry(file_key(path.as_ref())).or_default();
+        for key in extracted {
+            namespaces.insert(
+                key.namespace
+                    .clone()
+                    .unwrap_or_else(|| config.effective_default_namespace().to_string()),
+            );
+        }
+    }
+
+    Ok(json!({
+        "version": FORMAT_VERSION,
+        "nsSeparator": config.ns_separator,
+        "keySeparator": config.key_separator,
+        "defaultNamespace": config.effective_default_namespace(),
+        "namespaces": keys.keys().collect::<Vec<_>>(),
+        "keys": keys,
+        "files": scopes
+            .into_iter()
+            .map(|(path, namespaces)| (path, json!({ "namespaces": namespaces })))
+            .collect::<Map<String, Value>>(),
+    }))
+}
+
+/// Write the rule data to `path`, leaving the file untouched when nothing
+/// changed so ESLint caches stay valid. Returns whether the file was written.
+pub fn write<'a, P: AsRef<Path>>(
+    config: &Config,
+    path: &Path,
+    files: impl IntoIterator<Item = (P, &'a Vec<ExtractedKey>)>,
+) -> Result<bool> {
+    let mut content = serde_json::to_string_pretty(&build(config, files)?)?;
+    content.push('\n');
+    let fs = RealFileSystem;
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs.create_dir_all(parent)?;
+    }
+    if fs.read_to_string(path).ok().as_deref() == Some(content.as_str()) {
+        return Ok(false);
+    }
+    fs.atomic_write(path, content.as_bytes())
+        .with_context(|| format!("Failed to write ESLint data: {}", path.display()))?;
+    Ok(true)
+}
+
+/// Regenerate the configured `eslintDataFile`. Does nothing when it is not set.
+pub fn write_configured<'a, P: AsRef<Path>>(
+    config: &Config,
+    files: impl IntoIterator<Item = (P, &'a Vec<ExtractedKey>)>,
+) -> Result<()> {
+    if let Some(path) = config.eslint_data_file.as_deref() {
+        write(config, Path::new(path), files)?;
+    }
+    Ok(())
+}
+
+/// Source paths as lint rules see them: forward slashes, no leading `./`
+fn file_key(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    path.strip_prefix("./").unwrap_or(&path).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extractor::test_key as key;
+
+    #[test]
+    fn build_lists_keys_plural_bases_and_file_namespaces() {
+        let tmp = tempfile::tempdir().unwrap();
+        let output = tmp.path().join("locales");
+        std::fs::create_dir_all(output.join("en")).unwrap();
+        std::fs::write(
+            output.join("en/translation.json"),
+            r#"{"title":"Title","item_one":"Item","item_other":"Items","nav":{"home":"Home"}}"#,
+        )
+        .unwrap();
+        std::fs::write(output.join("en/common.json"), r#"{"save":"Save"}"#).unwrap();
+        let config = Config {
+            output: output.to_string_lossy().to_string(),
+            locales: vec!["en".to_string()],
+            ..Config::default()
+        };
+        let files = [
+            (
+                "./src/App.tsx".to_string(),
+                vec![key("title", None), key("save", Some("common"))],
+            ),
+            ("src/empty.ts".to_string(), vec![]),
+        ];
+
+        let data = build(&config, files.iter().map(|(path, keys)| (path, keys))).unwrap();
+        assert_eq!(data["version"], 1);
+        assert_eq!(data["defaultNamespace"], "translation");
+        assert_eq!(data["namespaces"], json!(["common", "translation"]));
+        assert_eq!(
+            data["keys"]["translation"],
+            json!(["item", "item_one", "item_other", "nav.home", "title"])
+        );
+        assert_eq!(
+            data["files"],
+            json!({ "src/App.tsx": { "namespaces": ["common", "translation"] } })
+        );
+    }
+}
@@ -0,0 +1,8167 @@
+#![allow(clippy::too_many_arguments)]
+
+use crate::cancel::CancellationToken;
+use crate::config::{
+    plural_variant_key, Config, ContextPluralOrder, DiagnosticSeverity, PathStyle, PluralCompat,
+    PluralConfig, SeverityRule, TransComponent, TransUnescape, UseTranslationName,
+};
+use crate::handlebars;
+use crate::lint::{self, LintIssue, LintOptions, LintResult};
+use crate::logging;
+use crate::mdx;
+use crate::paths;
+use crate::summary;
+use crate::vue;
+use crate::walk;
+use anyhow::{Context, Result};
+use bytes::Bytes;
+use bytes_str::BytesStr;
+use glob::Pattern;
+use memmap2::Mmap;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{json, Value as JsonValue};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use unicode_normalization::{is_nfc_quick, IsNormalized, UnicodeNormalization};
+
+/// Normalize a string to NFC form for consistent key handling.
+/// This ensures that keys like "が" (NFD: か+゛) and "が" (NFC) are treated as identical.
+///
+/// Optimization: Uses `is_nfc_quick()` to check if the string is already in NFC form.
+/// For most Latin/ASCII text (and pre-normalized CJK), this avoids any allocation.
+/// Only strings that actually need normalization will allocate a new String.
+fn normalize_key(key: &str) -> Cow<'_, str> {
+    // Fast path: check if already normalized (no allocation needed)
+    match is_nfc_quick(key.chars()) {
+        IsNormalized::Yes => Cow::Borrowed(key),
+        // Maybe or No: need to normalize
+        _ => Cow::Owned(key.nfc().collect()),
+    }
+}
+
+// =============================================================================
+// Static regex patterns (compiled once via OnceLock for thread-safe lazy init)
+// =============================================================================
+//
+// All regex patterns are validated at compile time via tests (see test_regex_initialization).
+// If any pattern is invalid, the test will fail during CI, preventing runtime panics.
+
+static SCRIPT_BLOCK_REGEX: OnceLock<Regex> = OnceLock::new();
+static STYLE_BLOCK_REGEX: OnceLock<Regex> = OnceLock::new();
+static AST_EVENT_WRITER: OnceLock<Option<Mutex<std::fs::File>>> = OnceLock::new();
+
+const AST_EVENT_PATH_ENV: &str = "I18NEXT_TURBO_AST_EVENTS_PATH";
+/// Source files at or above this size are memory-mapped instead of read into memory.
+const MMAP_THRESHOLD_BYTES: u64 = 64 * 1024;
+
+/// Namespace of keys marked developer-only with `devOnly: true` or an
+/// `i18next-dev-only` comment; `extract --production` leaves it out
+pub const DEV_NAMESPACE: &str = "dev";
+/// Comment directive marking the translation call it precedes (or ends the
+/// line of) as developer-only
+const DEV_ONLY_DIRECTIVE: &str = "i18next-dev-only";
+/// Comment directive before the first statement of a file setting the namespace
+/// of its keys that name none (`/* i18next-extract-namespace: checkout */`)
+const NAMESPACE_DIRECTIVE: &str = "i18next-extract-namespace";
+
+/// Translation calls written in comments (`// t('key')`), for the configured
+/// `functions`. A name only matches as a whole, so with `functions: ["t"]`
+/// neither `i18n.t('key')` nor `format('key')` is read, as in code.
+struct CommentCallPatterns {
+    /// `t('key')`; group 1 is the key
+    single_arg: Regex,
+    /// `t('key', 'default')`; groups 1 and 2 are the key and default value
+    with_default: Regex,
+    /// `t('key', {`; group 1 is the key, group 2 the opening brace
+    with_options: Regex,
+}
+
+impl CommentCallPatterns {
+    /// `None` without function names, as nothing can match then
+    fn new<'a>(functions: impl IntoIterator<Item = &'a str>) -> Option<Self> {
+        let mut names: Vec<&str> = functions.into_iter().collect();
+        if names.is_empty() {
+            return None;
+        }
+        // Longest first, so `i18n.t` is tried before `t`
+        names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+        let call = format!(
+            r#"(?:^|[^\w$.])(?:{})\s*\(\s*['"`]([^'"`]+)['"`]\s*"#,
+            names
+                .iter()
+                .map(|name| regex::escape(name))
+                .collect::<Vec<_>>()
+                .join("|")
+        );
+        let compile = |pattern: String| {
+            Regex::new(&pattern).expect("comment call pattern is invalid - this is a bug")
+        };
+        Some(Self {
+            single_arg: compile(format!(r"{}\)", call)),
+            with_default: compile(format!(r#"{},\s*['"`]([^'"`]+)['"`]\s*\)"#, call)),
+            with_options: compile(format!(r"(?s){},\s*(\{{)", call)),
+        })
+    }
+}
+
+fn get_script_block_regex() -> &'static Regex {
+    SCRIPT_BLOCK_REGEX.get_or_init(|| {
+        Regex::new(r#"(?is)<script\b[^>]*>(.*?)</script>"#)
+            .expect("SCRIPT_BLOCK_REGEX pattern is invalid - this is a bug")
+    })
+}
+
+fn get_style_block_regex() -> &'static Regex {
+    STYLE_BLOCK_REGEX.get_or_init(|| {
+        Regex::new(r#"(?is)<style\b[^>]*>.*?</style>"#)
+            .expect("STYLE_BLOCK_REGEX pattern is invalid - this is a bug")
+    })
+}
+
+fn get_ast_event_writer() -> Option<&'static Mutex<std::fs::File>> {
+    AST_EVENT_WRITER
+        .get_or_init(|| {
+            let path = std::env::var(AST_EVENT_PATH_ENV).ok()?;
+            if path.trim().is_empty() {
+                return None;
+            }
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .ok()?;
+            Some(Mutex::new(file))
+        })
+        .as_ref()
+}
+
+fn write_ast_event_line(event: &JsonValue) {
+    let Some(writer) = get_ast_event_writer() else {
+        return;
+    };
+    let Ok(mut file) = writer.lock() else {
+        return;
+    };
+    let Ok(line) = serde_json::to_string(event) else {
+        return;
+    };
+    let _ = writeln!(file, "{}", line);
+}
+
+#[derive(Debug, Clone)]
+struct TagBlock {
+    content: String,
+    range: Range<usize>,
+}
+
+fn extract_tag_blocks(source: &str, regex: &Regex) -> Vec<TagBlock> {
+    regex
+        .captures_iter(source)
+        .filter_map(|caps| {
+            let full = caps.get(0)?;
+            let inner = caps.get(1).unwrap_or(full);
+            Some(TagBlock {
+                content: inner.as_str().to_string(),
+                range: full.start()..full.end(),
+            })
+        })
+        .collect()
+}
+
+#[derive(Default)]
+struct CommentOptionsData {
+    default_value: Option<DefaultValue>,
+    namespace: Option<String>,
+    context: Option<String>,
+    has_count: bool,
+    has_ordinal: bool,
+}
+
+impl CommentOptionsData {
+    fn from_text(text: &str) -> Self {
+        Self {
+            default_value: extract_comment_string_option(text, "defaultValue")
+                .map(DefaultValue::from),
+            namespace: extract_comment_string_option(text, "ns"),
+            context: extract_comment_string_option(text, "context"),
+            has_count: comment_option_exists(text, "count"),
+            has_ordinal: comment_option_truthy(text, "ordinal"),
+        }
+    }
+}
+
+fn extract_braced_block(text: &str, start_index: usize) -> Option<(String, usize)> {
+    let mut depth = 0usize;
+    let mut in_string: Option<char> = None;
+    let mut is_escaped = false;
+
+    for (offset, ch) in text[start_index..].char_indices() {
+        let abs_index = start_index + offset;
+
+        if let Some(quote) = in_string {
+            if is_escaped {
+                is_escaped = false;
+                continue;
+            }
+            if ch == '\\' {
+                is_escaped = true;
+                continue;
+            }
+            if ch == quote {
+                in_string = None;
+            }
+            continue;
+        }
+
+        match ch {
+            '\'' | '"' | '`' => {
+                in_string = Some(ch);
+            }
+            '{' => {
+                depth += 1;
+            }
+            '}' => {
+                if depth == 0 {
+                    return None;
+                }
+                depth -= 1;
+                if depth == 0 {
+                    let end = abs_index + ch.len_utf8();
+                    return Some((text[start_index..end].to_string(), end));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn extract_parenthesized_expression(text: &str, start_index: usize) -> Option<(String, usize)> {
+    let mut depth = 0usize;
+    let mut in_string: Option<char> = None;
+    let mut is_escaped = false;
+
+    for (offset, ch) in text[start_index..].char_indices() {
+        let abs_index = start_index + offset;
+
+        if let Some(quote) = in_string {
+            if is_escaped {
+                is_escaped = false;
+                continue;
+            }
+            if ch == '\\' {
+                is_escaped = true;
+                continue;
+            }
+            if ch == quote {
+                in_string = None;
+            }
+            continue;
+        }
+
+        match ch {
+            '\'' | '"' | '`' => in_string = Some(ch),
+            '(' => {
+                depth += 1;
+            }
+            ')' => {
+                if depth == 0 {
+                    return None;
+                }
+                depth -= 1;
+                if depth == 0 {
+                    let end = abs_index + ch.len_utf8();
+                    return Some((text[start_index..end].to_string(), end));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn extract_translation_calls(
+    text: &str,
+    functions: &[String],
+    include_dollar_alias: bool,
+) -> Vec<String> {
+    let mut names: Vec<String> = functions.to_vec();
+    if include_dollar_alias && !names.iter().any(|name| name == "$t") {
+        names.push("$t".to_string());
+    }
+    names.sort_by_key(|b| std::cmp::Reverse(b.len()));
+    names.dedup();
+
+    let mut result = Vec::new();
+    let mut index = 0usize;
+    let len = text.len();
+
+    while index < len {
+        let slice = &text[index..];
+        let mut matched: Option<&str> = None;
+        for name in &names {
+            if slice.starts_with(name) && function_boundary_ok(text, index, index + name.len()) {
+                matched = Some(name);
+                break;
+            }
+        }
+
+        if let Some(name) = matched {
+            let after_name = index + name.len();
+            if let Some(open_index) = skip_whitespace_to_paren(text, after_name) {
+                if let Some((paren_block, end_index)) =
+                    extract_parenthesized_expression(text, open_index)
+                {
+                    result.push(format!("{}{}", name, paren_block));
+                    index = end_index;
+                    continue;
+                }
+            }
+        }
+
+        if let Some(ch) = text[index..].chars().next() {
+            index += ch.len_utf8();
+        } else {
+            break;
+        }
+    }
+
+    result
+}
+
+fn skip_whitespace_to_paren(text: &str, mut index: usize) -> Option<usize> {
+    while index < text.len() {
+        let mut iter = text[index..].char_indices();
+        if let Some((offset, ch)) = iter.next() {
+            if ch.is_whitespace() {
+                index += ch.len_utf8();
+                continue;
+            }
+            if ch == '(' {
+                return Some(index + offset);
+            }
+            return None;
+        } else {
+            break;
+        }
+    }
+    None
+}
+
+fn function_boundary_ok(text: &str, start: usize, end: usize) -> bool {
+    if start > 0 {
+        if let Some(prev) = text[..start].chars().next_back() {
+            if is_identifier_char(prev) {
+                return false;
+            }
+        }
+    }
+
+    if end < text.len() {
+        if let Some(next) = text[end..].chars().next() {
+            if is_identifier_char(next) {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+fn is_identifier_char(ch: char) -> bool {
+    ch == '_' || ch == '$' || ch == '.' || ch.is_alphanumeric()
+}
+
+pub(crate) fn extract_comment_string_option(text: &str, key: &str) -> Option<String> {
+    let base = format!(
+        r#"(?s)(?:^|[^a-zA-Z0-9_])["']?{}["']?\s*:\s*"#,
+        regex::escape(key)
+    );
+    let variants = [("'", "[^']+"), ("\"", "[^\"]+"), ("`", "[^`]+")];
+
+    for (quote, inner) in &variants {
+        let pattern = format!("{}{}({}){}", base, quote, inner, quote);
+        if let Ok(re) = Regex::new(&pattern) {
+            if let Some(cap) = re.captures(text) {
+                return cap.get(1).map(|m| m.as_str().to_string());
+            }
+        }
+    }
+
+    None
+}
+
+pub(crate) fn comment_option_exists(text: &str, key: &str) -> bool {
+    let pattern = format!(
+        r#"(?s)(?:^|[^a-zA-Z0-9_])["']?{}["']?\s*:"#,
+        regex::escape(key)
+    );
+    Regex::new(&pattern)
+        .ok()
+        .and_then(|re| re.find(text))
+        .is_some()
+}
+
+fn comment_option_truthy(text: &str, key: &str) -> bool {
+    let pattern = format!(
+        r#"(?s)(?:^|[^a-zA-Z0-9_])["']?{}["']?\s*:\s*true(?:[^a-zA-Z0-9_]|$)"#,
+        regex::escape(key)
+    );
+    Regex::new(&pattern)
+        .ok()
+        .and_then(|re| re.find(text))
+        .is_some()
+}
+
+fn dedup_strings(values: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut result = Vec::new();
+    for value in values {
+        if seen.insert(value.clone()) {
+            result.push(value);
+        }
+    }
+    result
+}
+
+pub(crate) fn split_top_level_once<'a>(
+    text: &'a str,
+    separator: &str,
+) -> Option<(&'a str, &'a str)> {
+    if separator.is_empty() {
+        return None;
+    }
+    let mut depth_paren = 0usize;
+    let mut depth_brace = 0usize;
+    let mut in_string: Option<char> = None;
+    let mut escaped = false;
+
+    for (idx, ch) in text.char_indices() {
+        if let Some(quote) = in_string {
+            if escaped {
+                escaped = false;
+                continue;
+            }
+            if ch == '\\' {
+                escaped = true;
+                continue;
+            }
+            if ch == quote {
+                in_string = None;
+            }
+            continue;
+        }
+
+        match ch {
+            '\'' | '"' | '`' => in_string = Some(ch),
+            '(' => depth_paren += 1,
+            ')' => depth_paren = depth_paren.saturating_sub(1),
+            '{' => depth_brace += 1,
+            '}' => depth_brace = depth_brace.saturating_sub(1),
+            _ => {}
+        }
+
+        if depth_paren == 0 && depth_brace == 0 && text[idx..].starts_with(separator) {
+            let right = &text[idx + separator.len()..];
+            return Some((&text[..idx], right));
+        }
+    }
+    None
+}
+
+pub(crate) fn parse_nested_key_token(token: &str) -> Option<&str> {
+    let t = token.trim();
+    if t.is_empty() {
+        return None;
+    }
+    if (t.starts_with('\'') && t.ends_with('\'')) || (t.starts_with('"') && t.ends_with('"')) {
+        return Some(&t[1..t.len() - 1]);
+    }
+    Some(t)
+}
+
+use swc_common::comments::SingleThreadedComments;
+use swc_common::sync::Lrc;
+use swc_common::{BytePos, FileName, SourceMap, SourceMapper, Span, Spanned};
+use swc_ecma_ast::{
+    AssignExpr, AssignTarget, BinaryOp, BlockStmt, CallExpr, Callee, ClassProp, CondExpr, Expr,
+    JSXAttrName, JSXAttrOrSpread, JSXAttrValue, JSXElement, JSXElementChild, JSXElementName,
+    JSXExpr, JSXOpeningElement, Lit, MemberExpr, MemberProp, Module, ObjectLit, OptChainBase,
+    OptChainExpr, ParenExpr, Pat, Prop, PropName, PropOrSpread, SimpleAssignTarget, Tpl, UnaryOp,
+    VarDeclarator,
+};
+use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsSyntax};
+use swc_ecma_visit::{Visit, VisitWith};
+
+/// Extracted translation key with metadata
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExtractedKey {
+    pub key: String,
+    pub namespace: Option<String>,
+    pub default_value: Option<DefaultValue>,
+}
+
+/// An `ExtractedKey` without a default value, for tests
+#[cfg(test)]
+pub(crate) fn test_key(key: &str, namespace: Option<&str>) -> ExtractedKey {
+    ExtractedKey {
+        key: key.to_string(),
+        namespace: namespace.map(str::to_string),
+        default_value: None,
+    }
+}
+
+/// A literal default value, keeping its JSON type
+/// (e.g. `t('retries', { defaultValue: 3 })` writes `3`, not `"3"`)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DefaultValue {
+    String(String),
+    Number(serde_json::Number),
+    Bool(bool),
+    Null,
+}
+
+impl DefaultValue {
+    /// The text of a string default
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            DefaultValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Literal default of an options property (`"text"`, `3`, `-1.5`, `true`, `null`)
+    fn from_expr(expr: &Expr) -> Option<Self> {
+        let number = |value: f64| {
+            let number = if value.fract() == 0.0 && value.abs() < 9_007_199_254_740_992.0 {
+                serde_json::Number::from(value as i64)
+            } else {
+                serde_json::Number::from_f64(value)?
+            };
+            Some(DefaultValue::Number(number))
+        };
+        match expr {
+            Expr::Lit(Lit::Str(s)) => s.value.as_str().map(DefaultValue::from),
+            Expr::Lit(Lit::Num(n)) => number(n.value),
+            Expr::Lit(Lit::Bool(b)) => Some(DefaultValue::Bool(b.value)),
+            Expr::Lit(Lit::Null(_)) => Some(DefaultValue::Null),
+            Expr::Unary(unary) if unary.op == UnaryOp::Minus => match unary.arg.as_ref() {
+                Expr::Lit(Lit::Num(n)) => number(-n.value),
+                _ => None,
+            },
+            Expr::Paren(paren) => Self::from_expr(&paren.expr),
+            _ => None,
+        }
+    }
+}
+
+impl From<String> for DefaultValue {
+    fn from(value: String) -> Self {
+        DefaultValue::String(value)
+    }
+}
+
+impl From<&str> for DefaultValue {
+    fn from(value: &str) -> Self {
+        DefaultValue::String(value.to_string())
+    }
+}
+
+impl From<DefaultValue> for serde_json::Value {
+    fn from(value: DefaultValue) -> Self {
+        match value {
+            DefaultValue::String(value) => serde_json::Value::String(value),
+            DefaultValue::Number(value) => serde_json::Value::Number(value),
+            DefaultValue::Bool(value) => serde_json::Value::Bool(value),
+            DefaultValue::Null => serde_json::Value::Null,
+        }
+    }
+}
+
+/// Error encountered during extraction
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractionError {
+    pub file_path: String,
+    pub message: String,
+    pub kind: ExtractionErrorKind,
+    /// 1-based line of a parse error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// 1-based column of a parse error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+}
+
+/// What kind of failure an `ExtractionError` records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExtractionErrorKind {
+    /// The source could not be parsed
+    Parse,
+    /// The file could not be read or processed
+    File,
+    /// A glob pattern was invalid or could not be expanded
+    Glob,
+}
+
+impl ExtractionError {
+    /// Build an error for a file, taking the position from a `ParseError` when present
+    pub fn from_file_error(file_path: String, error: &anyhow::Error) -> Self {
+        match error.downcast_ref::<ParseError>() {
+            Some(parse_error) => Self {
+                file_path,
+                message: parse_error.message.clone(),
+                kind: ExtractionErrorKind::Parse,
+                line: Some(parse_error.line),
+                column: Some(parse_error.column),
+            },
+            None => Self {
+                file_path,
+                message: error.to_string(),
+                kind: ExtractionErrorKind::File,
+                line: None,
+                column: None,
+            },
+        }
+    }
+
+    /// Build an error for a glob pattern
+    pub fn glob(pattern: String, message: String) -> Self {
+        Self {
+            file_path: pattern,
+            message,
+            kind: ExtractionErrorKind::Glob,
+            line: None,
+            column: None,
+        }
+    }
+}
+
+impl std::fmt::Display for ExtractionError {
+    /// `file:line:column: message`, the location format editors recognize
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(
+                f,
+                "{}:{}:{}: {}",
+                self.file_path, line, column, self.message
+            ),
+            _ => write!(f, "{}: {}", self.file_path, self.message),
+        }
+    }
+}
+
+/// Syntax error that prevented a source file from being parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line
+    pub line: usize,
+    /// 1-based column
+    pub column: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Parse error at {}:{}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Result of extraction from multiple files
+#[derive(Debug, Default)]
+pub struct ExtractionResult {
+    pub files: Vec<(String, Vec<ExtractedKey>)>,
+    pub errors: Vec<ExtractionError>,
+    /// Warnings and errors of the run, including one per entry of `errors`
+    pub diagnostics: Vec<Diagnostic>,
+    /// Messages declared in Vue `<i18n>` custom blocks
+    pub messages: Vec<ComponentMessage>,
+    /// Key patterns of translation calls with dynamic template literal keys
+    pub dynamic_keys: Vec<DynamicKeyPattern>,
+    /// Translation calls and components skipped, with the reason
+    pub skipped_calls: Vec<SkippedCall>,
+    /// Plural/context variants that are also used as literal keys
+    pub key_collisions: Vec<KeyCollision>,
+    /// Keys extracted from code and from comments with different default values
+    pub duplicate_keys: Vec<DuplicateKey>,
+    /// Where each extracted key comes from, before `keyTransforms`
+    pub key_sources: Vec<KeySource>,
+    /// The run was cancelled: only the files processed before then are included
+    pub cancelled: bool,
+    /// Hardcoded strings in JSX, found while parsing for keys when the request
+    /// has lint options (see [`ExtractRequestBuilder::lint`])
+    pub lint: Option<LintResult>,
+}
+
+impl ExtractionResult {
+    /// Dynamic keys reported at `warn` severity
+    pub fn dynamic_key_warnings(&self) -> usize {
+        self.count_diagnostics(Some(DiagnosticKind::DynamicKey), DiagnosticSeverity::Warn)
+    }
+
+    /// Dynamic keys reported at `error` severity
+    pub fn dynamic_key_errors(&self) -> usize {
+        self.count_diagnostics(Some(DiagnosticKind::DynamicKey), DiagnosticSeverity::Error)
+    }
+
+    /// Diagnostics at `warn` severity, as `--fail-on-warnings` sees them
+    pub fn total_warnings(&self) -> usize {
+        self.count_diagnostics(None, DiagnosticSeverity::Warn)
+    }
+
+    /// Number of diagnostics of each kind that occurred
+    pub fn diagnostic_counts(&self) -> BTreeMap<DiagnosticKind, usize> {
+        let mut counts = BTreeMap::new();
+        for diagnostic in &self.diagnostics {
+            *counts.entry(diagnostic.kind).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    fn count_diagnostics(
+        &self,
+        kind: Option<DiagnosticKind>,
+        severity: DiagnosticSeverity,
+    ) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| {
+                diagnostic.severity == severity && kind.is_none_or(|kind| diagnostic.kind == kind)
+            })
+            .count()
+    }
+}
+
+/// Where an extracted key comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeySource {
+    pub file_path: String,
+    pub line: usize,
+    /// 1-based column where the call, element or comment starts
+    pub column: usize,
+    /// Byte range of the call, element or comment in the parsed source
+    pub span: Range<usize>,
+    pub namespace: Option<String>,
+    pub key: String,
+    /// Base key when the key is a variant generated by plural/context expansion
+    pub generated_from: Option<String>,
+    pub default_value: Option<DefaultValue>,
+    /// Extracted from a `// t('key')` comment rather than code
+    pub from_comment: bool,
+}
+
+/// A key generated by plural/context expansion that is also written literally,
+/// e.g. `t('item', { count })` next to `t('item_other')`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyCollision {
+    pub namespace: Option<String>,
+    pub key: String,
+    /// The call whose expansion produced the key
+    pub generated: KeySource,
+    /// The call using the key literally
+    pub literal: KeySource,
+}
+
+/// A key extracted both from code and from a comment with a different default
+/// value; the locale file gets whichever is synced last
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateKey {
+    pub namespace: Option<String>,
+    pub key: String,
+    pub code: KeySource,
+    pub comment: KeySource,
+}
+
+/// A translation call whose key is a template literal with interpolations,
+/// e.g. t(`status.${state}`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynamicKeyPattern {
+    pub file_path: String,
+    pub line: usize,
+    /// Glob over the keys the call can produce, e.g. `status.*`
+    pub pattern: String,
+}
+
+/// Why a translation call or component gave no key, or not the key it names
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkipReason {
+    /// Inside an `i18next-extract-disable` region without a matching enable
+    DisabledRegion,
+    /// Marked by this `i18next-extract-disable...` comment
+    DisabledComment(String),
+    /// The key is a template literal with interpolations; holds the glob over
+    /// the keys it can produce
+    DynamicKey(String),
+    /// The `ns` option, or the namespace of the `useTranslation`/`getFixedT`
+    /// scope, is not a string literal, so the keys went to the default namespace
+    UnresolvedScope,
+}
+
+/// A translation call or component the extractor passed over, or whose keys
+/// it could not put into the namespace the code asks for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedCall {
+    pub file_path: String,
+    pub line: usize,
+    /// 1-based column where the call or element starts
+    pub column: usize,
+    /// Byte range of the call or element in the parsed source
+    pub span: Range<usize>,
+    /// The key as written, when it is a plain string
+    pub key: Option<String>,
+    pub reason: SkipReason,
+}
+
+/// What an extraction [`Diagnostic`] is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DiagnosticKind {
+    /// A key built from a template literal with interpolations, e.g. t(`status.${state}`)
+    DynamicKey,
+    /// A `context` option whose values could not be resolved
+    DynamicContext,
+    /// An `ns` option that is not a string literal
+    UnresolvedNamespace,
+    /// Options that contradict each other, e.g. `t('common:save', { ns: 'admin' })`
+    ConflictingOptions,
+    /// A namespace that is not a plain file name, e.g. `t('../../etc:key')`; its
+    /// keys are dropped so nothing is written outside the output directory
+    UnsafeNamespace,
+    /// A source file or component block that could not be parsed
+    ParseError,
+    /// A file that could not be read or a glob that could not be expanded
+    FileError,
+}
+
+impl DiagnosticKind {
+    pub const ALL: [DiagnosticKind; 7] = [
+        DiagnosticKind::DynamicKey,
+        DiagnosticKind::DynamicContext,
+        DiagnosticKind::UnresolvedNamespace,
+        DiagnosticKind::ConflictingOptions,
+        DiagnosticKind::UnsafeNamespace,
+        DiagnosticKind::ParseError,
+        DiagnosticKind::FileError,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DiagnosticKind::DynamicKey => "dynamic-key",
+            DiagnosticKind::DynamicContext => "dynamic-context",
+            DiagnosticKind::UnresolvedNamespace => "unresolved-namespace",
+            DiagnosticKind::ConflictingOptions => "conflicting-options",
+            DiagnosticKind::UnsafeNamespace => "unsafe-namespace",
+            DiagnosticKind::ParseError => "parse-error",
+            DiagnosticKind::FileError => "file-error",
+        }
+    }
+
+    pub fn parse_str(value: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == value)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Unknown diagnostic kind '{}'. Supported: {}",
+                    value,
+                    Self::ALL.map(DiagnosticKind::as_str).join(", ")
+                )
+            })
+    }
+}
+
+impl std::fmt::Display for DiagnosticKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A problem found while extracting. Files that fail to parse are skipped and
+/// the run goes on, so every diagnostic is a warning except dynamic keys that
+/// `dynamicKeySeverity` / `dynamicKeyRules` raise to errors.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    pub severity: DiagnosticSeverity,
+    pub file_path: String,
+    /// 1-based line, when the diagnostic points into the file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// 1-based column, when the diagnostic points into the file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    /// Human-readable description, including the location
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn from_error(error: &ExtractionError) -> Self {
+        Self {
+            kind: match error.kind {
+                ExtractionErrorKind::Parse => DiagnosticKind::ParseError,
+                ExtractionErrorKind::File | ExtractionErrorKind::Glob => DiagnosticKind::FileError,
+            },
+            severity: DiagnosticSeverity::Warn,
+            file_path: error.file_path.clone(),
+            line: error.line,
+            column: error.column,
+            message: error.to_string(),
+        }
+    }
+
+    /// Print the diagnostic to stderr, labelled with its severity
+    fn print(&self) {
+        let label = match self.severity {
+            DiagnosticSeverity::Error => "Error",
+            _ => "Warning",
+        };
+        eprintln!("{}: {}", label, self.message);
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// A message declared in a component `<i18n>` block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentMessage {
+    pub file_path: String,
+    pub locale: String,
+    /// Dot-separated key path
+    pub key: String,
+    pub value: String,
+}
+
+/// Keys, diagnostics and component messages extracted from one file
+#[derive(Debug, Default)]
+struct FileExtraction {
+    keys: Vec<ExtractedKey>,
+    diagnostics: Vec<Diagnostic>,
+    messages: Vec<ComponentMessage>,
+    dynamic_keys: Vec<DynamicKeyPattern>,
+    skipped_calls: Vec<SkippedCall>,
+    key_sources: Vec<KeySource>,
+    /// Hardcoded strings found in the same parse, when linting was requested
+    lint_issues: Vec<LintIssue>,
+}
+
+impl FileExtraction {
+    /// Merge the extraction of a sub-block (e.g. a component script) into this one
+    fn append(&mut self, mut other: FileExtraction) {
+        self.keys.append(&mut other.keys);
+        self.diagnostics.append(&mut other.diagnostics);
+        self.messages.append(&mut other.messages);
+        self.dynamic_keys.append(&mut other.dynamic_keys);
+        self.skipped_calls.append(&mut other.skipped_calls);
+        self.key_sources.append(&mut other.key_sources);
+        self.lint_issues.append(&mut other.lint_issues);
+    }
+
+    /// Append the extraction of one embedded block of a component; a block
+    /// that fails to parse is reported without dropping the other blocks
+    fn append_block(
+        &mut self,
+        file_path: &Path,
+        block_path: &str,
+        block: Result<FileExtraction>,
+        path_style: PathStyle,
+    ) {
+        match block {
+            Ok(block) => self.append(block),
+            Err(err) => self.diagnostics.push(Diagnostic {
+                kind: DiagnosticKind::ParseError,
+                severity: DiagnosticSeverity::Warn,
+                file_path: paths::display(file_path, path_style),
+                line: None,
+                column: None,
+                message: format!("Skipping {}: {:#}", block_path, err),
+            }),
+        }
+    }
+
+    /// Drop the keys whose namespace is not a plain file name. Such a namespace
+    /// would become a path like `locales/en/../../x.json`, so each one is
+    /// reported at the first call using it.
+    fn drop_unsafe_namespaces(&mut self, path: &Path, path_style: PathStyle) {
+        let is_unsafe = |namespace: &Option<String>| {
+            namespace
+                .as_deref()
+                .is_some_and(|ns| !paths::is_plain_file_name(ns))
+        };
+        if !self.keys.iter().any(|key| is_unsafe(&key.namespace)) {
+            return;
+        }
+        let file_path = paths::display(path, path_style);
+        let mut reported = HashSet::new();
+        for key in self.keys.iter().filter(|key| is_unsafe(&key.namespace)) {
+            let namespace = key.namespace.as_deref().unwrap_or_default();
+            if !reported.insert(namespace.to_string()) {
+                continue;
+            }
+            let line = self
+                .key_sources
+                .iter()
+                .find(|source| source.namespace == key.namespace && source.key == key.key)
+                .map(|source| source.line);
+            let location = match line {
+                Some(line) => format!("{}:{}", file_path, line),
+                None => file_path.clone(),
+            };
+            self.diagnostics.push(Diagnostic {
+                kind: DiagnosticKind::UnsafeNamespace,
+                severity: DiagnosticSeverity::Warn,
+                file_path: file_path.clone(),
+                line,
+                column: None,
+                message: format!(
+                    "Namespace '{}' at {} is not a plain file name; its keys are skipped so nothing is written outside the output directory.",
+                    namespace, location
+                ),
+            });
+        }
+        self.keys.retain(|key| !is_unsafe(&key.namespace));
+        self.key_sources
+            .retain(|source| !is_unsafe(&source.namespace));
+    }
+}
+
+/// Scope information for useTranslation hook
+#[derive(Debug, Clone, Default)]
+pub struct ScopeInfo {
+    /// Namespace from useTranslation('namespace')
+    pub namespace: Option<String>,
+    /// Key prefix from useTranslation({ keyPrefix: 'prefix' })
+    pub key_prefix: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct ContextInfo {
+    values: Vec<String>,
+    is_dynamic: bool,
+}
+
+/// Visitor that traverses the AST and extracts translation keys
+pub struct TranslationVisitor {
+    /// Set of function names to look for (e.g., "t", "i18n.t")
+    functions: HashSet<String>,
+    /// Trans component names to look for
+    trans_components: Vec<TransComponent>,
+    /// HTML tags that should remain as literal tags in Trans defaults/children extraction.
+    trans_keep_basic_html_nodes_for: HashSet<String>,
+    /// HTML entity decoding of Trans children
+    trans_unescape: TransUnescape,
+    /// Extracted keys
+    pub keys: Vec<ExtractedKey>,
+    /// Source map for line number lookup
+    source_map: Lrc<SourceMap>,
+    /// Comments for magic comment detection
+    comments: Option<SingleThreadedComments>,
+    /// Byte ranges between `i18next-extract-disable` and `i18next-extract-enable`
+    /// comments (or the end of the enclosing block), set by
+    /// [`Self::scan_disabled_regions`]
+    disabled_regions: Vec<(BytePos, BytePos)>,
+    /// Lines of `i18next-dev-only` comments, and whether the comment has the
+    /// line to itself (marking the next line rather than its own)
+    dev_only_directives: Vec<(usize, bool)>,
+    /// Scope info for variables bound from useTranslation/getFixedT
+    scope_bindings: HashMap<String, ScopeInfo>,
+    /// Hook-like functions that produce a bound t function.
+    use_translation_names: Vec<UseTranslationName>,
+    /// Factory functions taking message descriptor tables (e.g. `defineMessages`)
+    message_factory_functions: HashSet<String>,
+    /// Helpers returning a key rather than a translation (e.g. `k('ns:key')`)
+    key_producing_functions: HashSet<String>,
+    /// Keys of `const labels = { save: 'button.save' }` maps, by binding name
+    /// and property path
+    key_maps: HashMap<String, Vec<(String, String)>>,
+    /// File path being processed (for warning messages)
+    file_path: Option<String>,
+    /// Non-extractable patterns and contradicting options; dynamic keys get
+    /// their severity (and are printed) once the run knows the file's rules
+    diagnostics: Vec<Diagnostic>,
+    /// Key patterns of dynamic template literal calls
+    pub dynamic_keys: Vec<DynamicKeyPattern>,
+    /// Translation calls and components skipped, with the reason
+    skipped_calls: Vec<SkippedCall>,
+    /// Bound t functions whose scope namespace is not a string literal
+    unresolved_scopes: HashSet<String>,
+    /// Origins of keys from `t()` calls and Trans components
+    key_sources: Vec<KeySource>,
+    /// Context separator (e.g., "_" for "friend_male")
+    context_separator: String,
+    /// Plural separator (e.g., "_" for "item_one")
+    plural_separator: String,
+    /// Plural suffixes to generate (e.g., ["one", "other"])
+    plural_suffixes: Vec<String>,
+    /// Ordinal plural suffixes (e.g., ["few", "one", "other", "two"])
+    ordinal_suffixes: Vec<String>,
+    /// Whether to generate base key alongside plural keys
+    generate_base_plural: bool,
+    /// Whether single-category plurals also get the `other` form
+    single_category_other: bool,
+    /// Order of context and plural suffixes in generated keys
+    context_plural_order: ContextPluralOrder,
+    /// Plural key format (CLDR suffixes or legacy v3/v2 suffixes)
+    plural_compat: PluralCompat,
+    /// Prefix/suffix settings for nested translation extraction.
+    nesting_prefix: String,
+    nesting_suffix: String,
+    nesting_options_separator: String,
+    interpolation_prefix: String,
+    interpolation_suffix: String,
+    /// Separator of `ns:key` prefixes; empty when keys carry no namespace
+    ns_separator: String,
+    /// Separator joining a `keyPrefix` to its keys
+    key_separator: String,
+}
+
+impl TranslationVisitor {
+    pub fn new(
+        functions: Vec<String>,
+        trans_components: Vec<TransComponent>,
+        trans_keep_basic_html_nodes_for: Vec<String>,
+        trans_unescape: TransUnescape,
+        use_translation_names: Vec<UseTranslationName>,
+        message_factory_functions: Vec<String>,
+        key_producing_functions: Vec<String>,
+        source_map: Lrc<SourceMap>,
+        comments: Option<SingleThreadedComments>,
+        plural_config: PluralConfig,
+        nesting_prefix: String,
+        nesting_suffix: String,
+        nesting_options_separator: String,
+        interpolation_prefix: String,
+        interpolation_suffix: String,
+    ) -> Self {
+        let dev_only_directives = comments
+            .as_ref()
+            .map(|comments| {
+                let (leading, trailing) = comments.borrow_all();
+                leading
+                    .values()
+                    .chain(trailing.values())
+                    .flatten()
+                    .filter(|comment| comment.text.trim() == DEV_ONLY_DIRECTIVE)
+                    .map(|comment| {
+                        let loc = source_map.lookup_char_pos(comment.span.lo);
+                        let standalone = loc.file.get_line(loc.line - 1).is_some_and(|line| {
+                            line.chars().take(loc.col.0).all(char::is_whitespace)
+                        });
+                        (loc.line, standalone)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            functions: functions.into_iter().collect(),
+            trans_components,
+            trans_keep_basic_html_nodes_for: trans_keep_basic_html_nodes_for.into_iter().collect(),
+            trans_unescape,
+            keys: Vec::new(),
+            source_map,
+            comments,
+            disabled_regions: Vec::new(),
+            dev_only_directives,
+            scope_bindings: HashMap::new(),
+            use_translation_names,
+            message_factory_functions: message_factory_functions.into_iter().collect(),
+            key_producing_functions: key_producing_functions.into_iter().collect(),
+            key_maps: HashMap::new(),
+            file_path: None,
+            diagnostics: Vec::new(),
+            dynamic_keys: Vec::new(),
+            skipped_calls: Vec::new(),
+            unresolved_scopes: HashSet::new(),
+            key_sources: Vec::new(),
+            context_separator: plural_config.context_separator,
+            plural_separator: plural_config.separator,
+            plural_suffixes: plural_config.suffixes,
+            ordinal_suffixes: plural_config.ordinal_suffixes,
+            generate_base_plural: plural_config.generate_base,
+            single_category_other: plural_config.single_category_other,
+            context_plural_order: plural_config.order,
+            plural_compat: plural_config.compat,
+            nesting_prefix,
+            nesting_suffix,
+            nesting_options_separator,
+            interpolation_prefix,
+            interpolation_suffix,
+            ns_separator: ":".to_string(),
+            key_separator: ".".to_string(),
+        }
+    }
+
+    /// Parse `i18next-extract-disable` / `i18next-extract-enable` comment pairs
+    /// of `module` into disabled byte ranges. A disable without a matching
+    /// enable in its block runs to the end of that block, or to the end of the
+    /// file at the top level.
+    fn scan_disabled_regions(&mut self, module: &Module) {
+        let Some(comments) = &self.comments else {
+            return;
+        };
+        let directives: Vec<(Span, bool)> = {
+            let (leading, trailing) = comments.borrow_all();
+            let mut directives: Vec<(Span, bool)> = leading
+                .values()
+                .chain(trailing.values())
+                .flatten()
+                .filter_map(|comment| match comment.text.split_whitespace().next() {
+                    Some("i18next-extract-disable") => Some((comment.span, true)),
+                    Some("i18next-extract-enable") => Some((comment.span, false)),
+                    _ => None,
+                })
+                .collect();
+            directives.sort_by_key(|(span, _)| span.lo);
+            directives
+        };
+        if !directives.iter().any(|&(_, disable)| disable) {
+            return;
+        }
+
+        let mut blocks = BlockSpans::default();
+        module.visit_with(&mut blocks);
+        let block_end = |pos: BytePos| {
+            blocks
+                .0
+                .iter()
+                .filter(|block| block.lo < pos && pos < block.hi)
+                .min_by_key(|block| block.hi - block.lo)
+                .map_or(BytePos(u32::MAX), |block| block.hi)
+        };
+
+        let mut regions = Vec::new();
+        // Start of the open region, and the end of its enclosing block
+        let mut start: Option<(BytePos, BytePos)> = None;
+        for (span, disable) in directives {
+            if let Some((lo, end)) = start.filter(|&(_, end)| span.lo >= end) {
+                regions.push((lo, end));
+                start = None;
+            }
+            match (disable, start) {
+                (true, None) => start = Some((span.hi, block_end(span.lo))),
+                (false, Some((lo, _))) => {
+                    regions.push((lo, span.lo));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        regions.extend(start);
+        self.disabled_regions = regions;
+    }
+
+    /// Namespace of the first `i18next-extract-namespace` comment placed before
+    /// `first_item` (the first statement of the file, if any)
+    fn namespace_directive(&self, first_item: Option<BytePos>) -> Option<String> {
+        let comments = self.comments.as_ref()?;
+        let (leading, trailing) = comments.borrow_all();
+        leading
+            .values()
+            .chain(trailing.values())
+            .flatten()
+            .filter(|comment| first_item.is_none_or(|lo| comment.span.lo < lo))
+            .filter_map(|comment| {
+                let namespace = comment
+                    .text
+                    .trim()
+                    .strip_prefix(NAMESPACE_DIRECTIVE)?
+                    .trim_start()
+                    .strip_prefix(':')?
+                    .trim();
+                (!namespace.is_empty()).then(|| (comment.span.lo, namespace.to_string()))
+            })
+            .min()
+            .map(|(_, namespace)| namespace)
+    }
+
+    /// Put the keys that name no namespace (no `ns:` prefix, `ns` option or
+    /// hook namespace) into the file's `i18next-extract-namespace`
+    fn apply_namespace_directive(&mut self, first_item: Option<BytePos>) {
+        let Some(namespace) = self.namespace_directive(first_item) else {
+            return;
+        };
+        for key in self.keys.iter_mut().filter(|key| key.namespace.is_none()) {
+            key.namespace = Some(namespace.clone());
+        }
+        for source in self
+            .key_sources
+            .iter_mut()
+            .filter(|source| source.namespace.is_none())
+        {
+            source.namespace = Some(namespace.clone());
+        }
+    }
+
+    /// Why magic comments disable the node at `span`, if they do
+    fn disabled_reason(&self, span: Span) -> Option<SkipReason> {
+        use swc_common::comments::Comments;
+
+        if self
+            .disabled_regions
+            .iter()
+            .any(|&(lo, hi)| lo <= span.lo && span.lo < hi)
+        {
+            return Some(SkipReason::DisabledRegion);
+        }
+
+        let comments = self.comments.as_ref()?;
+        // Leading comments with any disable directive, trailing ones with disable-line
+        let leading = comments.get_leading(span.lo).unwrap_or_default();
+        let trailing = comments.get_trailing(span.hi).unwrap_or_default();
+        leading
+            .iter()
+            .find(|comment| comment.text.contains("i18next-extract-disable"))
+            .or_else(|| {
+                trailing
+                    .iter()
+                    .find(|comment| comment.text.contains("i18next-extract-disable-line"))
+            })
+            .map(|comment| SkipReason::DisabledComment(comment.text.trim().to_string()))
+    }
+
+    /// Remember that the call or element at `span` was skipped for `reason`
+    fn record_skipped_call(&mut self, span: Span, key: Option<String>, reason: SkipReason) {
+        let loc = self.source_map.lookup_char_pos(span.lo);
+        self.skipped_calls.push(SkippedCall {
+            file_path: self
+                .file_path
+                .clone()
+                .unwrap_or_else(|| "<unknown>".to_string()),
+            line: loc.line,
+            column: loc.col_display + 1,
+            span: self.byte_range(span),
+            key,
+            reason,
+        });
+    }
+
+    /// Check if a call expression matches our target functions
+    fn is_translation_call(&self, callee: &Callee) -> bool {
+        // t('key'), i18n.t('key'), this.t('key'), also as t!(...), i18n?.t(...)
+        self.get_callee_name(callee)
+            .is_some_and(|name| self.functions.contains(&name))
+    }
+
+    /// Extract string literal or template literal from the first argument
+    fn extract_key_from_args(&mut self, call: &CallExpr) -> Option<String> {
+        call.args.first().and_then(|arg| {
+            // t('key' as const) reads like t('key')
+            match unwrap_expr(arg.expr.as_ref()) {
+                // String literal: t('key')
+                Expr::Lit(Lit::Str(s)) => s.value.as_str().map(|s| s.to_string()),
+                // Template literal: t(`key`)
+                Expr::Tpl(tpl) => self.extract_simple_template_literal(tpl, call.span),
+                // Selector API: t($ => $.user.profile)
+                Expr::Arrow(arrow) => self.extract_selector_key(arrow),
+                _ => None,
+            }
+        })
+    }
+
+    fn extract_selector_key(&self, arrow: &swc_ecma_ast::ArrowExpr) -> Option<String> {
+        if arrow.params.len() != 1 {
+            return None;
+        }
+
+        let root_param = match &arrow.params[0] {
+            Pat::Ident(ident) => ident.id.sym.to_string(),
+            _ => return None,
+        };
+
+        let expr = match &*arrow.body {
+            swc_ecma_ast::BlockStmtOrExpr::Expr(expr) => expr.as_ref(),
+            _ => return None,
+        };
+
+        let mut parts = Vec::new();
+        if self.collect_selector_parts(expr, &root_param, &mut parts) {
+            if parts.is_empty() {
+                None
+            } else {
+                Some(parts.join("."))
+            }
+        } else {
+            None
+        }
+    }
+
+    fn collect_selector_parts(&self, expr: &Expr, root: &str, parts: &mut Vec<String>) -> bool {
+        match expr {
+            Expr::Ident(ident) => ident.sym.as_ref() == root,
+            Expr::Member(member) => {
+                if !self.collect_selector_parts(member.obj.as_ref(), root, parts) {
+                    return false;
+                }
+                match &member.prop {
+                    MemberProp::Ident(ident) => parts.push(ident.sym.to_string()),
+                    MemberProp::Computed(computed) => {
+                        if let Expr::Lit(Lit::Str(s)) = computed.expr.as_ref() {
+                            if let Some(value) = s.value.as_str() {
+                                parts.push(value.to_string());
+                            } else {
+                                return false;
+                            }
+                        } else {
+                            return false;
+                        }
+                    }
+                    _ => return false,
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Extract key from a template literal (only if it's a simple string without expressions)
+    fn extract_simple_template_literal(&mut self, tpl: &Tpl, span: Span) -> Option<String> {
+        // Only handle simple template literals without expressions
+        // e.g., t(`hello`) is OK, but t(`hello ${name}`) is not
+        if !tpl.exprs.is_empty() {
+            // Warn about dynamic template literals that cannot be extracted
+            self.warn_dynamic_template_literal(span);
+            self.record_dynamic_key_pattern(tpl, span);
+            self.record_skipped_call(span, None, SkipReason::DynamicKey(dynamic_key_pattern(tpl)));
+            return None; // Has interpolations, skip
+        }
+
+        // Template literal with no expressions should have exactly one quasi
+        if tpl.quasis.len() == 1 {
+            let quasi = &tpl.quasis[0];
+            if let Some(cooked) = quasi.cooked.as_ref() {
+                return cooked.as_str().map(|s| s.to_string());
+            }
+            return Some(quasi.raw.to_string());
+        }
+
+        None
+    }
+
+    /// Record a diagnostic at `span`; `describe` gets the `file:line:column`
+    /// location and returns the message
+    fn diagnose(
+        &mut self,
+        kind: DiagnosticKind,
+        span: Span,
+        describe: impl FnOnce(&str) -> String,
+    ) {
+        let loc = self.source_map.lookup_char_pos(span.lo);
+        let file_path = self
+            .file_path
+            .clone()
+            .unwrap_or_else(|| "<unknown>".to_string());
+        let column = loc.col_display + 1;
+        let message = describe(&format!("{}:{}:{}", file_path, loc.line, column));
+        self.diagnostics.push(Diagnostic {
+            kind,
+            severity: DiagnosticSeverity::Warn,
+            file_path,
+            line: Some(loc.line),
+            column: Some(column),
+            message,
+        });
+    }
+
+    /// Note a dynamic template literal that cannot be extracted
+    fn warn_dynamic_template_literal(&mut self, span: Span) {
+        self.diagnose(DiagnosticKind::DynamicKey, span, |location| {
+            format!(
+                "Dynamic template literal found at {}. Translation key extraction skipped. Consider using i18next-extract-disable-line if intentional.",
+                location
+            )
+        });
+    }
+
+    /// Remember the glob of keys a dynamic template literal can produce, so dead keys
+    /// it may reference can be told apart from truly unused ones.
+    fn record_dynamic_key_pattern(&mut self, tpl: &Tpl, span: Span) {
+        let pattern = dynamic_key_pattern(tpl);
+        // A key built entirely from expressions says nothing about which keys are used.
+        if pattern.chars().all(|c| c == '*') {
+            return;
+        }
+        let loc = self.source_map.lookup_char_pos(span.lo);
+        self.dynamic_keys.push(DynamicKeyPattern {
+            file_path: self
+                .file_path
+                .clone()
+                .unwrap_or_else(|| "<unknown>".to_string()),
+            line: loc.line,
+            pattern,
+        });
+    }
+
+    /// Record where the keys pushed since `first` come from. Keys other than
+    /// `base_key` (and `.*` object roots) are plural/context variants.
+    fn record_key_sources(&mut self, first: usize, span: Span, base_key: &str) {
+        let loc = self.source_map.lookup_char_pos(span.lo);
+        let range = self.byte_range(span);
+        let file_path = self
+            .file_path
+            .clone()
+            .unwrap_or_else(|| "<unknown>".to_string());
+        let sources: Vec<KeySource> = self.keys[first..]
+            .iter()
+            .map(|key| KeySource {
+                file_path: file_path.clone(),
+                line: loc.line,
+                column: loc.col_display + 1,
+                span: range.clone(),
+                namespace: key.namespace.clone(),
+                key: key.key.clone(),
+                generated_from: (key.key != base_key && !key.key.ends_with(".*"))
+                    .then(|| base_key.to_string()),
+                default_value: key.default_value.clone(),
+                from_comment: false,
+            })
+            .collect();
+        self.key_sources.extend(sources);
+    }
+
+    /// Byte offsets of `span` in the parsed source
+    fn byte_range(&self, span: Span) -> Range<usize> {
+        let start = self.source_map.lookup_byte_offset(span.lo).pos.0 as usize;
+        start..start + (span.hi.0 - span.lo.0) as usize
+    }
+
+    fn warn_unresolved_dynamic_context(&mut self, span: Span) {
+        self.diagnose(DiagnosticKind::DynamicContext, span, |location| {
+            format!(
+                "Unresolved dynamic context at {}. Falling back to base key extraction.",
+                location
+            )
+        });
+    }
+
+    /// Record Trans metadata that does not change the extracted keys: extra
+    /// namespaces of an `ns` array and placeholder names from `values`.
+    fn emit_trans_metadata_event(&self, span: Span, namespaces: &[String], values: &[String]) {
+        let loc = self.source_map.lookup_char_pos(span.lo);
+        let event = json!({
+            "type": "TransMetadata",
+            "filePath": self.file_path.as_deref().unwrap_or("<unknown>"),
+            "line": loc.line,
+            "column": loc.col_display + 1,
+            "namespace": namespaces.first(),
+            "additionalNamespaces": namespaces.iter().skip(1).collect::<Vec<_>>(),
+            "placeholders": values,
+        });
+        write_ast_event_line(&event);
+    }
+
+    fn emit_ast_visit_event(
+        &self,
+        span: Span,
+        node_type: &str,
+        callee: Option<&str>,
+        component: Option<&str>,
+        extracted_key: Option<&str>,
+    ) {
+        let loc = self.source_map.lookup_char_pos(span.lo);
+        let file_path = self.file_path.as_deref().unwrap_or("<unknown>");
+        let snippet = self.source_map.span_to_snippet(span).ok().map(|s| {
+            let trimmed = s.trim().replace('\n', " ");
+            if trimmed.len() > 240 {
+                format!("{}...", &trimmed[..240])
+            } else {
+                trimmed
+            }
+        });
+
+        let event = json!({
+            "type": "AstNodeVisit",
+            "nodeType": node_type,
+            "filePath": file_path,
+            "line": loc.line,
+            "column": loc.col_display + 1,
+            "callee": callee,
+            "component": component,
+            "key": extracted_key,
+            "snippet": snippet,
+        });
+        write_ast_event_line(&event);
+    }
+
+    /// Check if call has count option (for plurals)
+    #[allow(dead_code)]
+    fn has_count_option(&self, call: &CallExpr) -> bool {
+        self.get_option_value(call, "count").is_some()
+    }
+
+    /// Generate plural keys based on configuration
+    /// Returns a list of keys with the appropriate plural suffixes
+    ///
+    /// For single-category languages (e.g., Japanese with only "other"),
+    /// only the base key is generated without any suffix, plus the `other` form
+    /// when a locale asks for it in `singleCategoryPlurals`.
+    ///
+    /// If `generate_base_plural` is enabled, the base key (without suffix) is also
+    /// generated alongside the plural keys.
+    fn generate_plural_keys(
+        &self,
+        base_key: &str,
+        context: Option<&str>,
+        namespace: Option<String>,
+        default_value: Option<DefaultValue>,
+        ordinal: bool,
+    ) -> Vec<ExtractedKey> {
+        if self.plural_compat.is_legacy() && !self.plural_suffixes.is_empty() {
+            return self.generate_legacy_plural_keys(base_key, context, namespace, default_value);
+        }
+
+        // For single-category languages (only "other"), use base key without suffix
+        let is_single_category =
+            self.plural_suffixes.len() == 1 && self.plural_suffixes[0] == "other";
+
+        if is_single_category {
+            let mut keys = vec![ExtractedKey {
+                key: self.plural_variant_key(base_key, context, None),
+                namespace: namespace.clone(),
+                default_value: default_value.clone(),
+            }];
+            if self.single_category_other {
+                keys.push(ExtractedKey {
+                    key: self.plural_variant_key(base_key, context, Some("other")),
+                    namespace,
+                    default_value,
+                });
+            }
+            return keys;
+        }
+
+        let mut keys: Vec<ExtractedKey> = Vec::new();
+
+        // Optionally generate base key (without plural suffix)
+        if self.generate_base_plural {
+            keys.push(ExtractedKey {
+                key: self.plural_variant_key(base_key, context, None),
+                namespace: namespace.clone(),
+                default_value: default_value.clone(),
+            });
+        }
+
+        // Generate plural keys with suffixes; ordinals follow their own rules
+        // (`_ordinal_two` exists in English, `_two` does not)
+        let suffixes = if ordinal {
+            &self.ordinal_suffixes
+        } else {
+            &self.plural_suffixes
+        };
+        keys.extend(suffixes.iter().map(|suffix| {
+            let suffix = if ordinal {
+                format!("ordinal_{}", suffix)
+            } else {
+                suffix.clone()
+            };
+            let key = self.plural_variant_key(base_key, context, Some(&suffix));
+            ExtractedKey {
+                key,
+                namespace: namespace.clone(),
+                default_value: default_value.clone(),
+            }
+        }));
+
+        keys
+    }
+
+    fn plural_variant_key(
+        &self,
+        base_key: &str,
+        context: Option<&str>,
+        suffix: Option<&str>,
+    ) -> String {
+        plural_variant_key(
+            base_key,
+            context,
+            suffix,
+            &self.context_separator,
+            &self.plural_separator,
+            self.context_plural_order,
+        )
+    }
+
+    /// Generate keys in the i18next v3/v2 JSON format (`key`/`key_plural`, `key_0`, ...).
+    ///
+    /// In legacy mode `plural_suffixes` already holds the legacy suffixes, where an
+    /// empty suffix stands for the bare key. Ordinals did not exist in that format.
+    fn generate_legacy_plural_keys(
+        &self,
+        base_key: &str,
+        context: Option<&str>,
+        namespace: Option<String>,
+        default_value: Option<DefaultValue>,
+    ) -> Vec<ExtractedKey> {
+        let mut keys: Vec<String> = Vec::new();
+        if self.generate_base_plural && !self.plural_suffixes.iter().any(|s| s.is_empty()) {
+            keys.push(self.plural_variant_key(base_key, context, None));
+        }
+        keys.extend(
+            self.plural_suffixes
+                .iter()
+                .map(|suffix| self.plural_variant_key(base_key, context, Some(suffix))),
+        );
+
+        keys.into_iter()
+            .map(|key| ExtractedKey {
+                key,
+                namespace: namespace.clone(),
+                default_value: default_value.clone(),
+            })
+            .collect()
+    }
+
+    fn generate_plural_keys_with_context(
+        &mut self,
+        base_key: &str,
+        namespace: Option<String>,
+        default_value: Option<DefaultValue>,
+        context_info: Option<&ContextInfo>,
+        ordinal: bool,
+    ) {
+        match context_info {
+            Some(info) if !info.values.is_empty() => {
+                for ctx in &info.values {
+                    let plural_keys = self.generate_plural_keys(
+                        base_key,
+                        Some(ctx.as_str()),
+                        namespace.clone(),
+                        default_value.clone(),
+                        ordinal,
+                    );
+                    self.keys.extend(plural_keys);
+                }
+
+                if info.is_dynamic {
+                    let plural_keys = self.generate_plural_keys(
+                        base_key,
+                        None,
+                        namespace,
+                        default_value,
+                        ordinal,
+                    );
+                    self.keys.extend(plural_keys);
+                }
+            }
+            _ => {
+                let plural_keys =
+                    self.generate_plural_keys(base_key, None, namespace, default_value, ordinal);
+                self.keys.extend(plural_keys);
+            }
+        }
+    }
+
+    fn options_object<'a>(&self, call: &'a CallExpr) -> Option<&'a ObjectLit> {
+        if call.args.len() < 2 {
+            return None;
+        }
+        if let Expr::Object(obj) = call.args[1].expr.as_ref() {
+            Some(obj)
+        } else {
+            None
+        }
+    }
+
+    /// Check if call has context option (supports literal and simple dynamic expressions)
+    fn get_context_info(&self, call: &CallExpr) -> Option<ContextInfo> {
+        self.context_info_from_object(self.options_object(call)?)
+    }
+
+    /// Read the `context` property of an options object literal
+    fn context_info_from_object(&self, obj: &ObjectLit) -> Option<ContextInfo> {
+        for prop in &obj.props {
+            if let PropOrSpread::Prop(prop) = prop {
+                if let Prop::KeyValue(kv) = prop.as_ref() {
+                    let prop_key = match &kv.key {
+                        PropName::Ident(ident) => Some(ident.sym.to_string()),
+                        PropName::Str(s) => s.value.as_str().map(|s| s.to_string()),
+                        _ => None,
+                    };
+                    if prop_key.as_deref() != Some("context") {
+                        continue;
+                    }
+
+                    if let Expr::Lit(Lit::Str(s)) = kv.value.as_ref() {
+                        if let Some(value) = s.value.as_str() {
+                            if value.is_empty() {
+                                return None;
+                            }
+                            return Some(ContextInfo {
+                                values: vec![value.to_string()],
+                                is_dynamic: false,
+                            });
+                        }
+                    }
+
+                    let values = self.resolve_possible_context_values(kv.value.as_ref());
+                    return Some(ContextInfo {
+                        values,
+                        is_dynamic: true,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    /// Get the literal defaultValue option from t() call
+    fn get_default_value_option(&self, call: &CallExpr) -> Option<DefaultValue> {
+        let obj = self.options_object(call)?;
+        obj.props.iter().find_map(|prop| {
+            let PropOrSpread::Prop(prop) = prop else {
+                return None;
+            };
+            let Prop::KeyValue(kv) = prop.as_ref() else {
+                return None;
+            };
+            let is_default_value = match &kv.key {
+                PropName::Ident(ident) => ident.sym.as_ref() == "defaultValue",
+                PropName::Str(s) => s.value.as_str() == Some("defaultValue"),
+                _ => false,
+            };
+            is_default_value
+                .then(|| DefaultValue::from_expr(&kv.value))
+                .flatten()
+        })
+    }
+
+    /// Diagnose an `ns` option that cannot be read or that names another
+    /// namespace than the key, and `returnObjects` next to `count`
+    fn check_call_options(&mut self, call: &CallExpr) {
+        let Some(obj) = self.options_object(call) else {
+            return;
+        };
+        if self.has_prop(obj, "ns") {
+            match self.find_string_prop(obj, "ns") {
+                None => {
+                    self.diagnose(DiagnosticKind::UnresolvedNamespace, call.span, |location| {
+                        format!(
+                            "The ns option at {} is not a string literal; the call's keys are extracted without it.",
+                            location
+                        )
+                    });
+                    let key = call.args.first().and_then(|arg| static_string(&arg.expr));
+                    self.record_skipped_call(call.span, key, SkipReason::UnresolvedScope);
+                }
+                Some(option) => {
+                    let key_namespace = call
+                        .args
+                        .first()
+                        .and_then(|arg| static_string(&arg.expr))
+                        .and_then(|key| self.parse_key_with_namespace(&key).0);
+                    if let Some(key_namespace) = key_namespace.filter(|ns| *ns != option) {
+                        self.diagnose(DiagnosticKind::ConflictingOptions, call.span, |location| {
+                            format!(
+                                "Key namespace '{}' and ns option '{}' disagree at {}; the key's namespace is used.",
+                                key_namespace, option, location
+                            )
+                        });
+                    }
+                }
+            }
+        }
+        if self.has_return_objects_option(call) && self.has_prop(obj, "count") {
+            self.diagnose(DiagnosticKind::ConflictingOptions, call.span, |location| {
+                format!(
+                    "returnObjects and count at {}; the key is kept as an object and no plural keys are generated.",
+                    location
+                )
+            });
+        }
+    }
+
+    fn has_return_objects_option(&self, call: &CallExpr) -> bool {
+        let Some(obj) = self.options_object(call) else {
+            return false;
+        };
+        self.find_bool_prop(obj, "returnObjects").unwrap_or(false)
+    }
+
+    /// `devOnly: true` in the options, or an `i18next-dev-only` comment ending
+    /// the line of the call or on its own on the line above
+    fn is_dev_only(&self, call: &CallExpr) -> bool {
+        if self
+            .options_object(call)
+            .and_then(|obj| self.find_bool_prop(obj, "devOnly"))
+            .unwrap_or(false)
+        {
+            return true;
+        }
+        if self.dev_only_directives.is_empty() {
+            return false;
+        }
+        let line = self.source_map.lookup_char_pos(call.span.lo).line;
+        self.dev_only_directives
+            .iter()
+            .any(|&(directive_line, standalone)| {
+                directive_line == line || (standalone && directive_line + 1 == line)
+            })
+    }
+
+    fn has_ordinal_option(&self, call: &CallExpr) -> bool {
+        let Some(obj) = self.options_object(call) else {
+            return false;
+        };
+        self.find_bool_prop(obj, "ordinal").unwrap_or(false)
+    }
+
+    /// Get a string option value from the second argument object
+    fn get_option_value(&self, call: &CallExpr, key: &str) -> Option<String> {
+        if call.args.len() < 2 {
+            return None;
+        }
+
+        if let Expr::Object(obj) = call.args[1].expr.as_ref() {
+            return self.find_string_prop(obj, key);
+        }
+        None
+    }
+
+    /// Find a string property in an object literal
+    fn find_string_prop(&self, obj: &ObjectLit, key: &str) -> Option<String> {
+        for prop in &obj.props {
+            if let PropOrSpread::Prop(prop) = prop {
+                if let Prop::KeyValue(kv) = prop.as_ref() {
+                    let prop_key = match &kv.key {
+                        PropName::Ident(ident) => Some(ident.sym.to_string()),
+                        PropName::Str(s) => s.value.as_str().map(|s| s.to_string()),
+                        _ => None,
+                    };
+
+                    if prop_key.as_deref() == Some(key) {
+                        if let Expr::Lit(Lit::Str(s)) = kv.value.as_ref() {
+                            return s.value.as_str().map(|s| s.to_string());
+                        }
+                        // For count, just return a marker if it exists
+                        if key == "count" {
+                            return Some("__count__".to_string());
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn find_bool_prop(&self, obj: &ObjectLit, key: &str) -> Option<bool> {
+        for prop in &obj.props {
+            if let PropOrSpread::Prop(prop) = prop {
+                match prop.as_ref() {
+                    Prop::KeyValue(kv) => {
+                        let prop_key = match &kv.key {
+                            PropName::Ident(ident) => Some(ident.sym.to_string()),
+                            PropName::Str(s) => s.value.as_str().map(|s| s.to_string()),
+                            _ => None,
+                        };
+                        if prop_key.as_deref() != Some(key) {
+                            continue;
+                        }
+                        if let Expr::Lit(Lit::Bool(b)) = kv.value.as_ref() {
+                            return Some(b.value);
+                        }
+                        return Some(true);
+                    }
+                    Prop::Shorthand(ident) if ident.sym.as_ref() == key => return Some(true),
+                    _ => {}
+                }
+            }
+        }
+        None
+    }
+
+    /// Extract `{ id, defaultMessage }` descriptors passed to a message factory, either a
+    /// table as in `defineMessages({ title: { id, defaultMessage } })` or a single one.
+    fn extract_message_descriptors(&mut self, call: &CallExpr) {
+        let Some(Expr::Object(arg)) = call.args.first().map(|arg| arg.expr.as_ref()) else {
+            return;
+        };
+        let descriptors: Vec<&ObjectLit> = if self.find_string_prop(arg, "id").is_some() {
+            vec![arg]
+        } else {
+            arg.props
+                .iter()
+                .filter_map(|prop| match prop {
+                    PropOrSpread::Prop(prop) => match prop.as_ref() {
+                        Prop::KeyValue(kv) => match kv.value.as_ref() {
+                            Expr::Object(obj) => Some(obj),
+                            _ => None,
+                        },
+                        _ => None,
+                    },
+                    _ => None,
+                })
+                .collect()
+        };
+
+        for descriptor in descriptors {
+            let Some(id) = self.find_string_prop(descriptor, "id") else {
+                continue;
+            };
+            let (namespace, key) = self.parse_key_with_namespace(&id);
+            self.keys.push(ExtractedKey {
+                key,
+                namespace,
+                default_value: self
+                    .find_string_prop(descriptor, "defaultMessage")
+                    .map(DefaultValue::from),
+            });
+        }
+    }
+
+    /// Key of a `k('ns:key')` call to one of `keyProducingFunctions`
+    fn produced_key(&self, call: &CallExpr) -> Option<String> {
+        let name = self.get_callee_name(&call.callee)?;
+        if !self.key_producing_functions.contains(&name) {
+            return None;
+        }
+        static_string(&call.args.first()?.expr)
+    }
+
+    /// Entries of a key map bound as `const labels = { save: 'button.save' }`,
+    /// also inside `Object.freeze(...)`, `as const`, `satisfies` or a call to a
+    /// configured function. Values are key strings or `k('...')` calls.
+    fn collect_key_map(&self, init: &Expr) -> Vec<(String, String)> {
+        let obj = match unwrap_expr(init) {
+            Expr::Object(obj) => obj,
+            Expr::Call(call) => {
+                let wraps = self.get_callee_name(&call.callee).is_some_and(|name| {
+                    name == "Object.freeze"
+                        || self.functions.contains(&name)
+                        || self.key_producing_functions.contains(&name)
+                });
+                match call.args.first().map(|arg| unwrap_expr(&arg.expr)) {
+                    Some(Expr::Object(obj)) if wraps => obj,
+                    _ => return Vec::new(),
+                }
+            }
+            _ => return Vec::new(),
+        };
+        let mut entries = Vec::new();
+        self.collect_key_map_entries(obj, "", &mut entries);
+        entries
+    }
+
+    fn collect_key_map_entries(
+        &self,
+        obj: &ObjectLit,
+        prefix: &str,
+        entries: &mut Vec<(String, String)>,
+    ) {
+        for prop in &obj.props {
+            let PropOrSpread::Prop(prop) = prop else {
+                continue;
+            };
+            let Prop::KeyValue(kv) = prop.as_ref() else {
+                continue;
+            };
+            let name = match &kv.key {
+                PropName::Ident(ident) => ident.sym.to_string(),
+                PropName::Str(s) => match s.value.as_str() {
+                    Some(name) => name.to_string(),
+                    None => continue,
+                },
+                PropName::Num(num) => num.value.to_string(),
+                _ => continue,
+            };
+            let path = if prefix.is_empty() {
+                name
+            } else {
+                format!("{}.{}", prefix, name)
+            };
+            match unwrap_expr(&kv.value) {
+                Expr::Object(nested) => self.collect_key_map_entries(nested, &path, entries),
+                Expr::Call(call) => {
+                    if let Some(key) = self.produced_key(call) {
+                        entries.push((path, key));
+                    }
+                }
+                value => {
+                    if let Some(key) = static_string(value) {
+                        entries.push((path, key));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Keys a `labels.save` argument stands for when `labels` is a key map.
+    /// A computed property such as `labels[kind]` takes every key below it.
+    fn key_map_lookup(&self, expr: &Expr) -> Vec<String> {
+        let mut path = Vec::new();
+        let mut dynamic = false;
+        let mut current = unwrap_expr(expr);
+        let binding = loop {
+            match current {
+                Expr::Member(member) => {
+                    let part = match &member.prop {
+                        MemberProp::Ident(prop) => Some(prop.sym.to_string()),
+                        MemberProp::Computed(computed) => static_string(&computed.expr),
+                        _ => return Vec::new(),
+                    };
+                    match part {
+                        Some(part) => path.push(part),
+                        None => {
+                            // Whatever follows the computed property is unknown
+                            dynamic = true;
+                            path.clear();
+                        }
+                    }
+                    current = unwrap_expr(&member.obj);
+                }
+                Expr::Ident(ident) => break ident.sym.to_string(),
+                _ => return Vec::new(),
+            }
+        };
+        let Some(entries) = self.key_maps.get(&binding) else {
+            return Vec::new();
+        };
+        path.reverse();
+        let path = path.join(".");
+        entries
+            .iter()
+            .filter(|(entry_path, _)| {
+                if !dynamic {
+                    *entry_path == path
+                } else {
+                    path.is_empty()
+                        || entry_path
+                            .strip_prefix(path.as_str())
+                            .is_some_and(|rest| rest.starts_with('.'))
+                }
+            })
+            .map(|(_, key)| key.clone())
+            .collect()
+    }
+
+    /// Check if an object has a property (for count detection)
+    fn has_prop(&self, obj: &ObjectLit, key: &str) -> bool {
+        for prop in &obj.props {
+            if let PropOrSpread::Prop(prop) = prop {
+                if let Prop::KeyValue(kv) = prop.as_ref() {
+                    let prop_key = match &kv.key {
+                        PropName::Ident(ident) => Some(ident.sym.to_string()),
+                        PropName::Str(s) => s.value.as_str().map(|s| s.to_string()),
+                        _ => None,
+                    };
+                    if prop_key.as_deref() == Some(key) {
+                        return true;
+                    }
+                }
+                // Handle shorthand: { count }
+                if let Prop::Shorthand(ident) = prop.as_ref() {
+                    if ident.sym.as_ref() == key {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    /// Extract nested translation keys from a string value.
+    /// Detects patterns like $t(key), $t('key'), or configurable nestingPrefix/suffix forms.
+    fn extract_nested_translations(&self, text: &str) -> Vec<ExtractedKey> {
+        let mut keys = Vec::new();
+        let mut cursor = 0usize;
+
+        while let Some(start_rel) = text[cursor..].find(&self.nesting_prefix) {
+            let inner_start = cursor + start_rel + self.nesting_prefix.len();
+            let Some(end_rel) = text[inner_start..].find(&self.nesting_suffix) else {
+                break;
+            };
+            let inner_end = inner_start + end_rel;
+            let inner = text[inner_start..inner_end].trim();
+
+            let (raw_key_part, raw_options_part) =
+                split_top_level_once(inner, self.nesting_options_separator.as_str())
+                    .unwrap_or((inner, ""));
+
+            let Some(raw_key) = parse_nested_key_token(raw_key_part.trim()) else {
+                cursor = inner_end + self.nesting_suffix.len();
+                continue;
+            };
+
+            let options = CommentOptionsData::from_text(raw_options_part);
+            let (namespace, base_key) = self.parse_key_with_namespace(raw_key);
+
+            if options.has_count {
+                keys.extend(self.generate_plural_keys(
+                    &base_key,
+                    options.context.as_deref(),
+                    namespace,
+                    None,
+                    options.has_ordinal,
+                ));
+            } else if let Some(ctx) = options.context {
+                keys.push(ExtractedKey {
+                    key: format!("{}{}{}", base_key, self.context_separator, ctx),
+                    namespace,
+                    default_value: None,
+                });
+            } else {
+                keys.push(ExtractedKey {
+                    key: base_key,
+                    namespace,
+                    default_value: None,
+                });
+            }
+
+            cursor = inner_end + self.nesting_suffix.len();
+        }
+
+        let mut dedup = HashSet::new();
+        keys.into_iter()
+            .filter(|k| dedup.insert((k.namespace.clone(), k.key.clone())))
+            .collect()
+    }
+
+    /// Split the `nsSeparator` prefix off a key, with Unicode normalization
+    fn parse_key_with_namespace(&self, key: &str) -> (Option<String>, String) {
+        // Normalize the key to NFC form for consistent handling
+        let normalized = normalize_key(key);
+        match normalized.split_once(self.ns_separator.as_str()) {
+            Some((namespace, key)) if !self.ns_separator.is_empty() => {
+                (Some(namespace.to_string()), key.to_string())
+            }
+            _ => (None, normalized.into_owned()),
+        }
+    }
+
+    /// Extract string from JSX attribute value
+    fn extract_jsx_attr_string(&self, value: &JSXAttrValue) -> Option<String> {
+        match value {
+            // i18nKey="hello"
+            JSXAttrValue::Str(s) => s.value.as_str().map(|s| s.to_string()),
+            // i18nKey={"hello"}
+            JSXAttrValue::JSXExprContainer(container) => {
+                if let swc_ecma_ast::JSXExpr::Expr(expr) = &container.expr {
+                    if let Expr::Lit(Lit::Str(s)) = expr.as_ref() {
+                        return s.value.as_str().map(|s| s.to_string());
+                    }
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn resolve_possible_context_values(&self, expr: &Expr) -> Vec<String> {
+        let mut values = self.resolve_possible_string_values(expr);
+        values.retain(|v| !v.is_empty());
+        dedup_strings(values)
+    }
+
+    #[allow(clippy::only_used_in_recursion)]
+    fn resolve_possible_string_values(&self, expr: &Expr) -> Vec<String> {
+        match expr {
+            Expr::Lit(Lit::Str(s)) => s
+                .value
+                .as_str()
+                .map(|s| s.to_string())
+                .into_iter()
+                .collect(),
+            Expr::Tpl(tpl) => {
+                if tpl.exprs.is_empty() {
+                    let mut text = String::new();
+                    for quasi in &tpl.quasis {
+                        if let Some(cooked) = &quasi.cooked {
+                            text.push_str(&cooked.to_string_lossy());
+                        }
+                    }
+                    if text.is_empty() {
+                        Vec::new()
+                    } else {
+                        vec![text]
+                    }
+                } else {
+                    Vec::new()
+                }
+            }
+            Expr::Paren(ParenExpr { expr, .. }) => {
+                self.resolve_possible_string_values(expr.as_ref())
+            }
+            Expr::Cond(CondExpr { cons, alt, .. }) => {
+                let mut values = self.resolve_possible_string_values(cons.as_ref());
+                values.extend(self.resolve_possible_string_values(alt.as_ref()));
+                values
+            }
+            Expr::Bin(bin) if matches!(bin.op, BinaryOp::Add) => {
+                let left = self.resolve_possible_string_values(bin.left.as_ref());
+                let right = self.resolve_possible_string_values(bin.right.as_ref());
+                let mut combined = Vec::new();
+                for l in &left {
+                    for r in &right {
+                        combined.push(format!("{}{}", l, r));
+                    }
+                }
+                combined
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Extract i18nKey from Trans component attributes
+    fn extract_trans_key(&self, elem: &JSXOpeningElement, key_attr: &str) -> Option<String> {
+        self.find_jsx_attr(elem, key_attr)
+            .and_then(|value| self.extract_jsx_attr_string(value))
+    }
+
+    /// Extract ns (namespace) from Trans component attributes.
+    /// `ns={["common", "glossary"]}` returns every entry; the first one owns the key.
+    fn extract_trans_namespaces(&self, elem: &JSXOpeningElement, ns_attr: &str) -> Vec<String> {
+        let Some(value) = self.find_jsx_attr(elem, ns_attr) else {
+            return Vec::new();
+        };
+        if let Some(ns) = self.extract_jsx_attr_string(value) {
+            return vec![ns];
+        }
+        if let JSXAttrValue::JSXExprContainer(container) = value {
+            if let JSXExpr::Expr(expr) = &container.expr {
+                if let Expr::Array(array) = expr.as_ref() {
+                    return array
+                        .elems
+                        .iter()
+                        .flatten()
+                        .filter_map(|elem| match elem.expr.as_ref() {
+                            Expr::Lit(Lit::Str(s)) => s.value.as_str().map(|s| s.to_string()),
+                            _ => None,
+                        })
+                        .collect();
+                }
+            }
+        }
+        Vec::new()
+    }
+
+    /// Find the value of a JSX attribute by name
+    fn find_jsx_attr<'a>(
+        &self,
+        elem: &'a JSXOpeningElement,
+        attr_name: &str,
+    ) -> Option<&'a JSXAttrValue> {
+        elem.attrs.iter().find_map(|attr| match attr {
+            JSXAttrOrSpread::JSXAttr(jsx_attr) => match &jsx_attr.name {
+                JSXAttrName::Ident(name) if name.sym.as_ref() == attr_name => {
+                    jsx_attr.value.as_ref()
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+    }
+
+    /// Object literal passed to a JSX attribute (e.g. `tOptions={{ context: 'x' }}`)
+    fn find_jsx_attr_object<'a>(
+        &self,
+        elem: &'a JSXOpeningElement,
+        attr_name: &str,
+    ) -> Option<&'a ObjectLit> {
+        match self.find_jsx_attr(elem, attr_name)? {
+            JSXAttrValue::JSXExprContainer(container) => match &container.expr {
+                JSXExpr::Expr(expr) => match expr.as_ref() {
+                    Expr::Object(obj) => Some(obj),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Property names of an object literal (`{ name, count: n }` -> ["name", "count"])
+    fn object_prop_names(&self, obj: &ObjectLit) -> Vec<String> {
+        obj.props
+            .iter()
+            .filter_map(|prop| match prop {
+                PropOrSpread::Prop(prop) => match prop.as_ref() {
+                    Prop::Shorthand(ident) => Some(ident.sym.to_string()),
+                    Prop::KeyValue(kv) => match &kv.key {
+                        PropName::Ident(ident) => Some(ident.sym.to_string()),
+                        PropName::Str(s) => s.value.as_str().map(|s| s.to_string()),
+                        _ => None,
+                    },
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Check if Trans component has count attribute (for plurals)
+    fn trans_has_count(&self, elem: &JSXOpeningElement) -> bool {
+        for attr in &elem.attrs {
+            if let JSXAttrOrSpread::JSXAttr(jsx_attr) = attr {
+                if let JSXAttrName::Ident(name) = &jsx_attr.name {
+                    if name.sym.as_ref() == "count" {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    /// Extract context attribute info from Trans component (supports dynamic expressions)
+    fn extract_trans_context_info(&self, elem: &JSXOpeningElement) -> Option<ContextInfo> {
+        for attr in &elem.attrs {
+            if let JSXAttrOrSpread::JSXAttr(jsx_attr) = attr {
+                if let JSXAttrName::Ident(name) = &jsx_attr.name {
+                    if name.sym.as_ref() == "context" {
+                        if let Some(value) = &jsx_attr.value {
+                            if let Some(literal) = self.extract_jsx_attr_string(value) {
+                                if literal.is_empty() {
+                                    return None;
+                                }
+                                return Some(ContextInfo {
+                                    values: vec![literal],
+                                    is_dynamic: false,
+                                });
+                            }
+
+                            if let JSXAttrValue::JSXExprContainer(container) = value {
+                                if let JSXExpr::Expr(expr) = &container.expr {
+                                    let values = self.resolve_possible_context_values(expr);
+                                    return Some(ContextInfo {
+                                        values,
+                                        is_dynamic: true,
+                                    });
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Extract text content from JSX children. With `decode`, HTML entities are
+    /// decoded as JSX does (`&amp;` -> `&`); otherwise they are kept as written.
+    #[allow(clippy::only_used_in_recursion)]
+    fn extract_jsx_children_text(
+        &self,
+        children: &[JSXElementChild],
+        decode: bool,
+    ) -> Option<String> {
+        let mut text_parts: Vec<String> = Vec::new();
+
+        for child in children {
+            match child {
+                JSXElementChild::JSXText(text) => {
+                    let s = if decode {
+                        text.value.to_string()
+                    } else {
+                        text.raw.to_string()
+                    };
+                    let trimmed = s.trim();
+                    if !trimmed.is_empty() {
+                        text_parts.push(trimmed.to_string());
+                    }
+                }
+                JSXElementChild::JSXExprContainer(container) => {
+                    // Handle {variable} and {{ variable }} - keep as placeholder
+                    if let swc_ecma_ast::JSXExpr::Expr(expr) = &container.expr {
+                        match expr.as_ref() {
+                            Expr::Ident(ident) => text_parts.push(format!(
+                                "{}{}{}",
+                                self.interpolation_prefix, ident.sym, self.interpolation_suffix
+                            )),
+                            Expr::Object(obj) => {
+                                for name in self.object_prop_names(obj) {
+                                    text_parts.push(format!(
+                                        "{}{}{}",
+                                        self.interpolation_prefix, name, self.interpolation_suffix
+                                    ));
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                JSXElementChild::JSXElement(element) => {
+                    // Recursively extract text from nested elements
+                    // Keep tag names like <strong>, <br>, etc.
+                    if let JSXElementName::Ident(ident) = &element.opening.name {
+                        let tag = ident.sym.to_string();
+                        let keep_tag = self.trans_keep_basic_html_nodes_for.contains(&tag);
+                        if keep_tag {
+                            // For configured inline tags, keep wrapper
+                            if let Some(inner) =
+                                self.extract_jsx_children_text(&element.children, decode)
+                            {
+                                text_parts.push(format!("<{}>{}</{}>", tag, inner, tag));
+                            } else if element.closing.is_none() {
+                                text_parts.push(format!("<{}/>", tag));
+                            }
+                        } else if let Some(inner) =
+                            self.extract_jsx_children_text(&element.children, decode)
+                        {
+                            // For non-kept tags, keep only inner text
+                            text_parts.push(inner);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if text_parts.is_empty() {
+            None
+        } else {
+            Some(text_parts.join(" "))
+        }
+    }
+
+    /// Extract defaults attribute from Trans component
+    fn extract_trans_defaults(
+        &self,
+        elem: &JSXOpeningElement,
+        default_attr: &str,
+    ) -> Option<String> {
+        self.find_jsx_attr(elem, default_attr)
+            .and_then(|value| self.extract_jsx_attr_string(value))
+    }
+
+    /// Scope info of a call producing a bound t function (useTranslation-like or getFixedT)
+    fn parse_scope_call(&self, call: &CallExpr) -> Option<ScopeInfo> {
+        self.parse_use_translation_call(call)
+            .or_else(|| self.parse_get_fixed_t_call(call))
+    }
+
+    /// Treat `name` (e.g. `this.t`) as a translation function scoped by `call`
+    fn bind_scoped_function(&mut self, name: String, call: &CallExpr) {
+        if self
+            .scope_namespace_arg(call)
+            .is_some_and(|expr| !is_static_namespace(expr))
+        {
+            self.diagnose(DiagnosticKind::UnresolvedNamespace, call.span, |location| {
+                format!(
+                    "Namespace at {} is not a string literal; keys of the bound function go to the default namespace.",
+                    location
+                )
+            });
+            self.unresolved_scopes.insert(name.clone());
+        } else {
+            self.unresolved_scopes.remove(&name);
+        }
+        if let Some(scope_info) = self.parse_scope_call(call) {
+            self.functions.insert(name.clone());
+            self.scope_bindings.insert(name, scope_info);
+        }
+    }
+
+    /// The namespace argument of `useTranslation(ns)` (or a configured hook)
+    /// and `getFixedT(lng, ns)`
+    fn scope_namespace_arg<'a>(&self, call: &'a CallExpr) -> Option<&'a Expr> {
+        let Callee::Expr(callee) = &call.callee else {
+            return None;
+        };
+        let index = match callee.as_ref() {
+            Expr::Ident(ident) => match self
+                .use_translation_names
+                .iter()
+                .find(|entry| entry.name() == ident.sym.as_ref())
+            {
+                Some(entry) => entry.ns_arg(),
+                None if ident.sym.as_ref() == "getFixedT" => 1,
+                None => return None,
+            },
+            Expr::Member(member) if matches!(&member.prop, MemberProp::Ident(prop) if prop.sym.as_ref() == "getFixedT") => {
+                1
+            }
+            _ => return None,
+        };
+        call.args.get(index).map(|arg| arg.expr.as_ref())
+    }
+
+    /// Check if a call is useTranslation and extract scope info
+    fn parse_use_translation_call(&self, call: &CallExpr) -> Option<ScopeInfo> {
+        let (ns_arg_idx, key_prefix_arg_idx) = match &call.callee {
+            Callee::Expr(expr) => match expr.as_ref() {
+                Expr::Ident(ident) => {
+                    let entry = self
+                        .use_translation_names
+                        .iter()
+                        .find(|entry| entry.name() == ident.sym.as_ref())?;
+                    (entry.ns_arg(), entry.key_prefix_arg())
+                }
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        let mut scope_info = ScopeInfo::default();
+        for (i, arg) in call.args.iter().enumerate() {
+            if i == ns_arg_idx {
+                match arg.expr.as_ref() {
+                    Expr::Lit(Lit::Str(s)) => {
+                        scope_info.namespace = s.value.as_str().map(|s| s.to_string());
+                    }
+                    // useTranslation(['common', 'home']): the first namespace is the default
+                    Expr::Array(array) => {
+                        if let Some(Expr::Lit(Lit::Str(s))) = array
+                            .elems
+                            .first()
+                            .and_then(|elem| elem.as_ref())
+                            .map(|elem| elem.expr.as_ref())
+                        {
+                            scope_info.namespace = s.value.as_str().map(|s| s.to_string());
+                        }
+                    }
+                    Expr::Object(obj) => {
+                        // next-intl style: getTranslations({ locale, namespace })
+                        if let Some(ns) = self
+                            .find_string_prop(obj, "ns")
+                            .or_else(|| self.find_string_prop(obj, "namespace"))
+                        {
+                            scope_info.namespace = Some(ns);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            if i == key_prefix_arg_idx {
+                if let Expr::Object(obj) = arg.expr.as_ref() {
+                    scope_info.key_prefix = self.find_string_prop(obj, "keyPrefix");
+                } else if let Expr::Lit(Lit::Str(s)) = arg.expr.as_ref() {
+                    scope_info.key_prefix = s.value.as_str().map(|s| s.to_string());
+                }
+            }
+        }
+
+        if call.args.len() == 1 {
+            if let Expr::Object(obj) = call.args[0].expr.as_ref() {
+                if let Some(prefix) = self.find_string_prop(obj, "keyPrefix") {
+                    scope_info.key_prefix = Some(prefix);
+                }
+                if let Some(ns) = self
+                    .find_string_prop(obj, "ns")
+                    .or_else(|| self.find_string_prop(obj, "namespace"))
+                {
+                    scope_info.namespace = Some(ns);
+                }
+            }
+        }
+
+        Some(scope_info)
+    }
+
+    /// Check if a call is getFixedT and extract scope info
+    fn parse_get_fixed_t_call(&self, call: &CallExpr) -> Option<ScopeInfo> {
+        // Check if this is getFixedT() or i18n.getFixedT()
+        let is_get_fixed_t = match &call.callee {
+            Callee::Expr(expr) => match expr.as_ref() {
+                Expr::Ident(ident) => ident.sym.as_ref() == "getFixedT",
+                Expr::Member(member) => {
+                    if let MemberProp::Ident(prop) = &member.prop {
+                        prop.sym.as_ref() == "getFixedT"
+                    } else {
+                        false
+                    }
+                }
+                _ => false,
+            },
+            _ => false,
+        };
+
+        if !is_get_fixed_t {
+            return None;
+        }
+
+        let mut scope_info = ScopeInfo::default();
+
+        // getFixedT(locale, namespace, keyPrefix)
+        // or getFixedT(locale, { ns, keyPrefix })
+        for (i, arg) in call.args.iter().enumerate() {
+            match i {
+                0 => {
+                    // First arg is locale, skip it
+                }
+                1 => {
+                    // Second arg: namespace (string) or options object
+                    if let Expr::Lit(Lit::Str(s)) = arg.expr.as_ref() {
+                        scope_info.namespace = s.value.as_str().map(|s| s.to_string());
+                    }
+                    if let Expr::Object(obj) = arg.expr.as_ref() {
+                        if let Some(ns) = self.find_string_prop(obj, "ns") {
+                            scope_info.namespace = Some(ns);
+                        }
+                        scope_info.key_prefix = self.find_string_prop(obj, "keyPrefix");
+                    }
+                }
+                2 => {
+                    // Third arg: keyPrefix (string)
+                    if let Expr::Lit(Lit::Str(s)) = arg.expr.as_ref() {
+                        scope_info.key_prefix = s.value.as_str().map(|s| s.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Some(scope_info)
+    }
+
+    /// Extract bound variable names from a pattern
+    fn extract_bound_t_name(&self, pat: &Pat) -> Option<String> {
+        match pat {
+            // const t = useTranslation()
+            Pat::Ident(ident) => Some(ident.id.sym.to_string()),
+            // const { t } = useTranslation()
+            Pat::Object(obj) => {
+                for prop in &obj.props {
+                    if let swc_ecma_ast::ObjectPatProp::Assign(assign) = prop {
+                        let name = assign.key.sym.to_string();
+                        if name == "t" {
+                            return Some(name);
+                        }
+                    }
+                    if let swc_ecma_ast::ObjectPatProp::KeyValue(kv) = prop {
+                        if let PropName::Ident(key) = &kv.key {
+                            if key.sym.as_ref() == "t" {
+                                // { t: customName } -> return customName
+                                if let Pat::Ident(ident) = kv.value.as_ref() {
+                                    return Some(ident.id.sym.to_string());
+                                }
+                            }
+                        }
+                    }
+                }
+                // Check for shorthand { t }
+                for prop in &obj.props {
+                    if let swc_ecma_ast::ObjectPatProp::Assign(assign) = prop {
+                        if assign.key.sym.as_ref() == "t" {
+                            return Some("t".to_string());
+                        }
+                    }
+                }
+                None
+            }
+            // const [t] = useTranslation()
+            Pat::Array(arr) => {
+                if let Some(Pat::Ident(ident)) = arr.elems.first().and_then(|elem| elem.as_ref()) {
+                    return Some(ident.id.sym.to_string());
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// `key` under a `keyPrefix`, joined like i18next does: with the key
+    /// separator, or `.` when keys are flat
+    fn prefixed_key(&self, prefix: &str, key: &str) -> String {
+        let separator = if self.key_separator.is_empty() {
+            "."
+        } else {
+            self.key_separator.as_str()
+        };
+        format!("{}{}{}", prefix, separator, key)
+    }
+
+    /// Apply scope info to a key
+    fn apply_scope_to_key(&self, key: &str, func_name: &str) -> (Option<String>, String) {
+        if let Some(scope) = self.scope_bindings.get(func_name) {
+            let final_key = if let Some(prefix) = &scope.key_prefix {
+                self.prefixed_key(prefix, key)
+            } else {
+                key.to_string()
+            };
+            (scope.namespace.clone(), final_key)
+        } else {
+            self.parse_key_with_namespace(key)
+        }
+    }
+
+    /// Get the function name from a callee
+    fn get_callee_name(&self, callee: &Callee) -> Option<String> {
+        match callee {
+            Callee::Expr(expr) => self.get_expr_function_name(expr),
+            _ => None,
+        }
+    }
+
+    fn get_expr_function_name(&self, expr: &Expr) -> Option<String> {
+        callee_name(expr)
+    }
+
+    /// Extract keys from comments (e.g., // t('key') or /* t('key', 'default') */)
+    pub fn extract_from_comments(&mut self) {
+        // Collect all comment texts first to avoid borrow issues
+        let comments: Vec<(String, Span)> = if let Some(comments) = &self.comments {
+            let (leading, trailing) = comments.borrow_all();
+
+            // Leading and trailing comments
+            leading
+                .values()
+                .chain(trailing.values())
+                .flatten()
+                .map(|comment| (comment.text.to_string(), comment.span))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        if !comments.iter().any(|(text, _)| text.contains('(')) {
+            return;
+        }
+        // Functions bound in the file (`const { t: tr } = ...`) count as well
+        let Some(patterns) = CommentCallPatterns::new(self.functions.iter().map(String::as_str))
+        else {
+            return;
+        };
+        for (text, span) in &comments {
+            let first = self.keys.len();
+            self.extract_keys_from_comment_text(text, &patterns);
+            self.record_comment_sources(first, *span);
+        }
+    }
+
+    /// Record the comment at `span` as the source of the keys pushed since `first`
+    fn record_comment_sources(&mut self, first: usize, span: Span) {
+        let loc = self.source_map.lookup_char_pos(span.lo);
+        let range = self.byte_range(span);
+        let file_path = self
+            .file_path
+            .clone()
+            .unwrap_or_else(|| "<unknown>".to_string());
+        let sources: Vec<KeySource> = self.keys[first..]
+            .iter()
+            .map(|key| KeySource {
+                file_path: file_path.clone(),
+                line: loc.line,
+                column: loc.col_display + 1,
+                span: range.clone(),
+                namespace: key.namespace.clone(),
+                key: key.key.clone(),
+                generated_from: None,
+                default_value: key.default_value.clone(),
+                from_comment: true,
+            })
+            .collect();
+        self.key_sources.extend(sources);
+    }
+
+    /// Extract translation keys from a comment string
+    fn extract_keys_from_comment_text(&mut self, text: &str, patterns: &CommentCallPatterns) {
+        // Look for patterns like t('key'), t("key"), t('key', 'default'), t('key', { defaultValue: '...' })
+        // with any configured function name (i18n.t('key'), translate('key'))
+
+        let first = self.keys.len();
+        let CommentCallPatterns {
+            single_arg: single_arg_pattern,
+            with_default: with_default_pattern,
+            with_options: with_options_pattern,
+        } = patterns;
+
+        // Extract with options pattern first (most specific)
+        for cap in with_options_pattern.captures_iter(text) {
+            if let Some(key_match) = cap.get(1) {
+                let key = key_match.as_str();
+                if let Some(object_match) = cap.get(2) {
+                    if let Some((options_text, _)) =
+                        extract_braced_block(text, object_match.start())
+                    {
+                        let CommentOptionsData {
+                            default_value,
+                            namespace: namespace_override,
+                            context,
+                            has_count,
+                            has_ordinal,
+                        } = CommentOptionsData::from_text(&options_text);
+
+                        let (namespace, base_key) =
+                            self.resolve_comment_key_scope(key, namespace_override);
+
+                        if has_count {
+                            let plural_keys = self.generate_plural_keys(
+                                &base_key,
+                                context.as_deref(),
+                                namespace.clone(),
+                                default_value.clone(),
+                                has_ordinal,
+                            );
+                            self.keys.extend(plural_keys);
+                            continue;
+                        }
+
+                        if let Some(ctx) = context {
+                            self.keys.push(ExtractedKey {
+                                key: format!("{}{}{}", base_key, self.context_separator, ctx),
+                                namespace,
+                                default_value,
+                            });
+                        } else {
+                            self.keys.push(ExtractedKey {
+                                key: base_key,
+                                namespace,
+                                default_value,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        // Extract with default pattern
+        for cap in with_default_pattern.captures_iter(text) {
+            if let Some(key_match) = cap.get(1) {
+                let key = key_match.as_str();
+                // Check if already captured by options pattern; keys from code
+                // are kept so differing defaults can be reported
+                let (namespace, base_key) = self.resolve_comment_key_scope(key, None);
+                if !self.keys[first..]
+                    .iter()
+                    .any(|k| k.key == base_key && k.namespace == namespace)
+                {
+                    let default_value = cap.get(2).map(|m| DefaultValue::from(m.as_str()));
+                    self.keys.push(ExtractedKey {
+                        key: base_key,
+                        namespace,
+                        default_value,
+                    });
+                }
+            }
+        }
+
+        // Extract single arg pattern
+        for cap in single_arg_pattern.captures_iter(text) {
+            if let Some(key_match) = cap.get(1) {
+                let key = key_match.as_str();
+                let (namespace, base_key) = self.resolve_comment_key_scope(key, None);
+                // Check if already captured
+                if !self
+                    .keys
+                    .iter()
+                    .any(|k| k.key == base_key && k.namespace == namespace)
+                {
+                    self.keys.push(ExtractedKey {
+                        key: base_key,
+                        namespace,
+                        default_value: None,
+                    });
+                }
+            }
+        }
+    }
+
+    fn inferred_comment_scope(&self) -> Option<ScopeInfo> {
+        if self.scope_bindings.len() == 1 {
+            self.scope_bindings.values().next().cloned()
+        } else {
+            None
+        }
+    }
+
+    fn resolve_comment_key_scope(
+        &self,
+        raw_key: &str,
+        namespace_override: Option<String>,
+    ) -> (Option<String>, String) {
+        let (ns_from_key, base_key) = self.parse_key_with_namespace(raw_key);
+        let mut namespace = namespace_override.or(ns_from_key);
+        let mut final_key = base_key;
+
+        if let Some(scope) = self.inferred_comment_scope() {
+            if namespace.is_none() {
+                namespace = scope.namespace;
+            }
+            if let Some(prefix) = scope.key_prefix {
+                final_key = self.prefixed_key(&prefix, &final_key);
+            }
+        }
+
+        (namespace, final_key)
+    }
+}
+
+/// Dotted name of a member expression on an identifier or `this` (`i18n.t`, `this.t`)
+fn member_name(member: &MemberExpr) -> Option<String> {
+    let MemberProp::Ident(prop) = &member.prop else {
+        return None;
+    };
+    match unwrap_expr(&member.obj) {
+        Expr::Ident(obj) => Some(format!("{}.{}", obj.sym, prop.sym)),
+        Expr::This(_) => Some(format!("this.{}", prop.sym)),
+        _ => None,
+    }
+}
+
+/// Name of a called function (`t`, `i18n.t`, `this.t`) through non-null
+/// assertions (`t!`), type arguments (`t<'ns'>`) and optional chaining (`i18n?.t`)
+fn callee_name(expr: &Expr) -> Option<String> {
+    match unwrap_expr(expr) {
+        Expr::Ident(ident) => Some(ident.sym.to_string()),
+        Expr::Member(member) => member_name(member),
+        Expr::OptChain(chain) => match chain.base.as_ref() {
+            OptChainBase::Member(member) => member_name(member),
+            OptChainBase::Call(_) => None,
+        },
+        Expr::TsInstantiation(instantiation) => callee_name(&instantiation.expr),
+        _ => None,
+    }
+}
+
+/// Strip `await`, parentheses and TypeScript assertions around an expression
+fn unwrap_expr(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Await(await_expr) => unwrap_expr(&await_expr.arg),
+        Expr::Paren(paren) => unwrap_expr(&paren.expr),
+        Expr::TsAs(ts_as) => unwrap_expr(&ts_as.expr),
+        Expr::TsConstAssertion(assertion) => unwrap_expr(&assertion.expr),
+        Expr::TsSatisfies(satisfies) => unwrap_expr(&satisfies.expr),
+        Expr::TsNonNull(non_null) => unwrap_expr(&non_null.expr),
+        _ => expr,
+    }
+}
+
+/// A string literal or a template literal without expressions
+/// Glob over the keys a template literal can produce, e.g. `status.*`
+fn dynamic_key_pattern(tpl: &Tpl) -> String {
+    tpl.quasis
+        .iter()
+        .map(|quasi| {
+            let text = quasi
+                .cooked
+                .as_ref()
+                .and_then(|cooked| cooked.as_str())
+                .unwrap_or(quasi.raw.as_ref());
+            glob::Pattern::escape(text)
+        })
+        .collect::<Vec<_>>()
+        .join("*")
+}
+
+fn static_string(expr: &Expr) -> Option<String> {
+    match unwrap_expr(expr) {
+        Expr::Lit(Lit::Str(s)) => s.value.as_str().map(|s| s.to_string()),
+        Expr::Tpl(tpl) if tpl.exprs.is_empty() && tpl.quasis.len() == 1 => tpl.quasis[0]
+            .cooked
+            .as_ref()
+            .and_then(|cooked| cooked.as_str())
+            .map(|s| s.to_string()),
+        _ => None,
+    }
+}
+
+/// A namespace argument the extractor reads (a string, an array or options
+/// object) or that stands for the default namespace
+fn is_static_namespace(expr: &Expr) -> bool {
+    match unwrap_expr(expr) {
+        Expr::Lit(Lit::Str(_) | Lit::Null(_)) | Expr::Array(_) | Expr::Object(_) => true,
+        Expr::Ident(ident) => ident.sym.as_ref() == "undefined",
+        Expr::Tpl(tpl) => tpl.exprs.is_empty(),
+        _ => false,
+    }
+}
+
+/// Spans of the block statements of a module
+#[derive(Default)]
+struct BlockSpans(Vec<Span>);
+
+impl Visit for BlockSpans {
+    fn visit_block_stmt(&mut self, block: &BlockStmt) {
+        self.0.push(block.span);
+        block.visit_children_with(self);
+    }
+}
+
+impl Visit for TranslationVisitor {
+    fn visit_var_declarator(&mut self, decl: &VarDeclarator) {
+        // Key maps: const labels = { save: 'button.save' } as const
+        if let (Pat::Ident(binding), Some(init)) = (&decl.name, &decl.init) {
+            let entries = self.collect_key_map(init);
+            if !entries.is_empty() {
+                self.key_maps.insert(binding.id.sym.to_string(), entries);
+            }
+        }
+
+        // Check for useTranslation() or getFixedT() calls, also behind `await`
+        if let Some(init) = &decl.init {
+            if let Expr::Call(call) = unwrap_expr(init) {
+                if let Some(t_name) = self.extract_bound_t_name(&decl.name) {
+                    self.bind_scoped_function(t_name, call);
+                }
+            } else if let Some(alias_name) = self.extract_bound_t_name(&decl.name) {
+                // Alias tracking: const translate = t / const tr = i18n.t
+                if let Some(source_name) = self.get_expr_function_name(init.as_ref()) {
+                    if self.functions.contains(&source_name)
+                        || self.scope_bindings.contains_key(&source_name)
+                    {
+                        self.functions.insert(alias_name.clone());
+                        if self.unresolved_scopes.contains(&source_name) {
+                            self.unresolved_scopes.insert(alias_name.clone());
+                        }
+                        if let Some(scope_info) = self.scope_bindings.get(&source_name).cloned() {
+                            self.scope_bindings.insert(alias_name, scope_info);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Continue visiting
+        decl.visit_children_with(self);
+    }
+
+    fn visit_assign_expr(&mut self, assign: &AssignExpr) {
+        // this.t = i18n.getFixedT(...) / ctx.t = await getTranslations(...)
+        if let Expr::Call(call) = unwrap_expr(&assign.right) {
+            let target = match &assign.left {
+                AssignTarget::Simple(SimpleAssignTarget::Ident(ident)) => {
+                    Some(ident.id.sym.to_string())
+                }
+                AssignTarget::Simple(SimpleAssignTarget::Member(member)) => member_name(member),
+                _ => None,
+            };
+            if let Some(name) = target {
+                self.bind_scoped_function(name, call);
+            }
+        }
+
+        assign.visit_children_with(self);
+    }
+
+    fn visit_class_prop(&mut self, prop: &ClassProp) {
+        // class Page { t = i18n.getFixedT(...) } -> this.t('key')
+        if let (PropName::Ident(key), Some(value)) = (&prop.key, &prop.value) {
+            if let Expr::Call(call) = unwrap_expr(value) {
+                self.bind_scoped_function(format!("this.{}", key.sym), call);
+            }
+        }
+
+        prop.visit_children_with(self);
+    }
+
+    /// `props.t?.('key')` and `i18n?.t('key')` are handled as plain calls
+    fn visit_opt_chain_expr(&mut self, chain: &OptChainExpr) {
+        let OptChainBase::Call(call) = chain.base.as_ref() else {
+            chain.visit_children_with(self);
+            return;
+        };
+        self.visit_call_expr(&CallExpr {
+            span: call.span,
+            ctxt: call.ctxt,
+            callee: Callee::Expr(call.callee.clone()),
+            args: call.args.clone(),
+            type_args: call.type_args.clone(),
+        });
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        // Check magic comments
+        if let Some(reason) = self.disabled_reason(call.span) {
+            if self.is_translation_call(&call.callee) {
+                let key = call.args.first().and_then(|arg| static_string(&arg.expr));
+                self.record_skipped_call(call.span, key, reason);
+            }
+            call.visit_children_with(self);
+            return;
+        }
+
+        if self.is_translation_call(&call.callee) {
+            self.check_call_options(call);
+            let callee_name = self.get_callee_name(&call.callee);
+            self.emit_ast_visit_event(
+                call.span,
+                "CallExpression",
+                callee_name.as_deref(),
+                None,
+                None,
+            );
+            // t(labels.save) with `labels` a key map stands for the keys it holds
+            let keys = match self.extract_key_from_args(call) {
+                Some(key) => vec![key],
+                None => call
+                    .args
+                    .first()
+                    .map(|arg| self.key_map_lookup(&arg.expr))
+                    .unwrap_or_default(),
+            };
+            for key in keys {
+                if callee_name
+                    .as_ref()
+                    .is_some_and(|name| self.unresolved_scopes.contains(name))
+                {
+                    self.record_skipped_call(
+                        call.span,
+                        Some(key.clone()),
+                        SkipReason::UnresolvedScope,
+                    );
+                }
+                // Check if the callee is bound to a scope
+                let (namespace_from_scope, base_key) = if let Some(name) = &callee_name {
+                    self.apply_scope_to_key(&key, name)
+                } else {
+                    self.parse_key_with_namespace(&key)
+                };
+                self.emit_ast_visit_event(
+                    call.span,
+                    "TranslationKey",
+                    callee_name.as_deref(),
+                    None,
+                    Some(base_key.as_str()),
+                );
+
+                // Check for count option (plurals)
+                let has_count = if call.args.len() >= 2 {
+                    if let Expr::Object(obj) = call.args[1].expr.as_ref() {
+                        self.has_prop(obj, "count")
+                    } else {
+                        false
+                    }
+                } else {
+                    false
+                };
+
+                // Check for context option
+                let context_info = self.get_context_info(call);
+
+                // Check for ordinal option (plural variant naming)
+                let is_ordinal = self.has_ordinal_option(call);
+
+                // Check for defaultValue option
+                let default_value = self.get_default_value_option(call);
+
+                // returnObjects=true means this key is an object root and should preserve children.
+                let has_return_objects = self.has_return_objects_option(call);
+
+                // Extract nested translations from defaultValue (e.g., $t('key'))
+                if let Some(dv) = default_value.as_ref().and_then(DefaultValue::as_str) {
+                    let nested_keys = self.extract_nested_translations(dv);
+                    for nested_key in nested_keys {
+                        self.keys.push(nested_key);
+                    }
+                }
+
+                let first = self.keys.len();
+                let source_key = base_key.clone();
+                if has_return_objects {
+                    self.keys.push(ExtractedKey {
+                        key: format!("{}.*", base_key),
+                        namespace: namespace_from_scope,
+                        default_value: None,
+                    });
+                } else if has_count {
+                    // Generate plural keys based on configuration
+                    self.generate_plural_keys_with_context(
+                        &base_key,
+                        namespace_from_scope,
+                        default_value,
+                        context_info.as_ref(),
+                        is_ordinal,
+                    );
+                } else if let Some(info) = context_info {
+                    if info.values.is_empty() {
+                        if info.is_dynamic {
+                            self.warn_unresolved_dynamic_context(call.span);
+                        }
+                        self.keys.push(ExtractedKey {
+                            key: base_key,
+                            namespace: namespace_from_scope,
+                            default_value,
+                        });
+                    } else {
+                        for ctx in &info.values {
+                            self.keys.push(ExtractedKey {
+                                key: format!("{}{}{}", base_key, self.context_separator, ctx),
+                                namespace: namespace_from_scope.clone(),
+                                default_value: default_value.clone(),
+                            });
+                        }
+                        if info.is_dynamic {
+                            self.keys.push(ExtractedKey {
+                                key: base_key,
+                                namespace: namespace_from_scope,
+                                default_value,
+                            });
+                        }
+                    }
+                } else {
+                    // Regular key
+                    self.keys.push(ExtractedKey {
+                        key: base_key,
+                        namespace: namespace_from_scope,
+                        default_value,
+                    });
+                }
+                if self.is_dev_only(call) {
+                    for key in &mut self.keys[first..] {
+                        key.namespace = Some(DEV_NAMESPACE.to_string());
+                    }
+                }
+                self.record_key_sources(first, call.span, &source_key);
+            }
+        } else if let Some(key) = self.produced_key(call) {
+            let (namespace, key) = self.parse_key_with_namespace(&key);
+            let first = self.keys.len();
+            self.keys.push(ExtractedKey {
+                key: key.clone(),
+                namespace,
+                default_value: None,
+            });
+            self.record_key_sources(first, call.span, &key);
+        } else if !self.message_factory_functions.is_empty() {
+            let is_factory = self
+                .get_callee_name(&call.callee)
+                .is_some_and(|name| self.message_factory_functions.contains(&name));
+            if is_factory {
+                self.extract_message_descriptors(call);
+            }
+        }
+
+        // Continue visiting child nodes
+        call.visit_children_with(self);
+    }
+
+    fn visit_jsx_element(&mut self, elem: &JSXElement) {
+        // Check magic comments
+        if let Some(reason) = self.disabled_reason(elem.span) {
+            if let JSXElementName::Ident(ident) = &elem.opening.name {
+                let key_attr = self
+                    .trans_components
+                    .iter()
+                    .find(|component| component.name() == ident.sym.as_ref())
+                    .map(|component| component.key_attr().to_string());
+                if let Some(key_attr) = key_attr {
+                    let key = self.extract_trans_key(&elem.opening, &key_attr);
+                    self.record_skipped_call(elem.span, key, reason);
+                }
+            }
+            elem.visit_children_with(self);
+            return;
+        }
+
+        // Check if this is a Trans component
+        if let JSXElementName::Ident(ident) = &elem.opening.name {
+            let component = self
+                .trans_components
+                .iter()
+                .find(|component| component.name() == ident.sym.as_ref())
+                .cloned();
+            if let Some(component) = component {
+                self.emit_ast_visit_event(
+                    elem.span,
+                    "JSXElement",
+                    None,
+                    Some(ident.sym.as_ref()),
+                    None,
+                );
+                // Extract i18nKey attribute (primary key source)
+                let i18n_key = self.extract_trans_key(&elem.opening, component.key_attr());
+
+                // tOptions={{ context, count, ordinal, ns, defaultValue }} mirror t() options
+                let t_options = self.find_jsx_attr_object(&elem.opening, "tOptions");
+
+                // Extract ns attribute (first entry of an array wins)
+                let namespaces = self.extract_trans_namespaces(&elem.opening, component.ns_attr());
+                let ns_from_attr = namespaces
+                    .first()
+                    .cloned()
+                    .or_else(|| t_options.and_then(|obj| self.find_string_prop(obj, "ns")));
+
+                // Extract defaults attribute
+                let defaults = self
+                    .extract_trans_defaults(&elem.opening, component.default_attr())
+                    .or_else(|| {
+                        t_options.and_then(|obj| self.find_string_prop(obj, "defaultValue"))
+                    });
+
+                // Extract children text (used as key if i18nKey not present, or as default value)
+                let children_text = self.extract_jsx_children_text(&elem.children, true);
+                // HTML entities as written in the source, unless decoded like JSX does
+                let children_text_for = |decode: bool| {
+                    if decode {
+                        children_text.clone()
+                    } else {
+                        self.extract_jsx_children_text(&elem.children, false)
+                    }
+                };
+                let children_default = children_text_for(self.trans_unescape.defaults);
+                let children_key = children_text_for(self.trans_unescape.keys);
+
+                // Check for count attribute (plurals)
+                let has_count = self.trans_has_count(&elem.opening)
+                    || t_options.is_some_and(|obj| self.has_prop(obj, "count"));
+                let ordinal = t_options
+                    .and_then(|obj| self.find_bool_prop(obj, "ordinal"))
+                    .unwrap_or(false);
+
+                // Check for context attribute (supports dynamic expressions)
+                let context_info = self
+                    .extract_trans_context_info(&elem.opening)
+                    .or_else(|| t_options.and_then(|obj| self.context_info_from_object(obj)));
+
+                // Interpolation values supplied through `values={{ ... }}`
+                let value_names = self
+                    .find_jsx_attr_object(&elem.opening, "values")
+                    .map(|obj| self.object_prop_names(obj))
+                    .unwrap_or_default();
+                if namespaces.len() > 1 || !value_names.is_empty() {
+                    self.emit_trans_metadata_event(elem.span, &namespaces, &value_names);
+                }
+
+                // Determine the key and default value
+                let (key, default_value) = if let Some(key) = i18n_key {
+                    // i18nKey is present - use it as key
+                    // Use defaults attribute or children as default value
+                    let dv = defaults.or(children_default);
+                    (key, dv.map(DefaultValue::from))
+                } else if let (Some(children), Some(default)) = (children_key, children_default) {
+                    // No i18nKey - use children text as key
+                    (children, Some(DefaultValue::from(default)))
+                } else {
+                    // No key available, skip
+                    elem.visit_children_with(self);
+                    return;
+                };
+
+                // Extract nested translations from default value (e.g., $t('key'))
+                if let Some(dv) = default_value.as_ref().and_then(DefaultValue::as_str) {
+                    let nested_keys = self.extract_nested_translations(dv);
+                    for nested_key in nested_keys {
+                        self.keys.push(nested_key);
+                    }
+                }
+
+                // Parse namespace from key (e.g., "common:greeting")
+                let (namespace_from_key, base_key) = self.parse_key_with_namespace(&key);
+                self.emit_ast_visit_event(
+                    elem.span,
+                    "TranslationKey",
+                    None,
+                    Some(ident.sym.as_ref()),
+                    Some(base_key.as_str()),
+                );
+
+                // Use ns attribute if present, otherwise use namespace from key
+                let namespace = ns_from_attr.or(namespace_from_key);
+
+                // Generate keys based on count and context attributes
+                let first = self.keys.len();
+                let source_key = base_key.clone();
+                if has_count {
+                    self.generate_plural_keys_with_context(
+                        &base_key,
+                        namespace.clone(),
+                        default_value.clone(),
+                        context_info.as_ref(),
+                        ordinal,
+                    );
+                } else if let Some(info) = context_info {
+                    if info.values.is_empty() {
+                        if info.is_dynamic {
+                            self.warn_unresolved_dynamic_context(elem.span);
+                        }
+                        self.keys.push(ExtractedKey {
+                            key: base_key,
+                            namespace,
+                            default_value,
+                        });
+                    } else {
+                        for ctx in &info.values {
+                            self.keys.push(ExtractedKey {
+                                key: format!("{}{}{}", base_key, self.context_separator, ctx),
+                                namespace: namespace.clone(),
+                                default_value: default_value.clone(),
+                            });
+                        }
+                        if info.is_dynamic {
+                            self.keys.push(ExtractedKey {
+                                key: base_key,
+                                namespace,
+                                default_value,
+                            });
+                        }
+                    }
+                } else {
+                    // No modifiers: base key
+                    self.keys.push(ExtractedKey {
+                        key: base_key,
+                        namespace,
+                        default_value,
+                    });
+                }
+                self.record_key_sources(first, elem.span, &source_key);
+            }
+        }
+
+        // Continue visiting child nodes
+        elem.visit_children_with(self);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExtractorStrategy {
+    JavaScript,
+    Vue,
+    Svelte,
+    Mdx,
+    EmailTemplate,
+}
+
+struct StrategyContext<'a> {
+    functions: &'a [String],
+    trans_components: &'a [TransComponent],
+    trans_keep_basic_html_nodes_for: &'a [String],
+    trans_unescape: TransUnescape,
+    use_translation_names: &'a [UseTranslationName],
+    message_factory_functions: &'a [String],
+    key_producing_functions: &'a [String],
+    extract_from_comments: bool,
+    plural_config: &'a PluralConfig,
+    nesting_prefix: &'a str,
+    nesting_suffix: &'a str,
+    nesting_options_separator: &'a str,
+    interpolation_prefix: &'a str,
+    interpolation_suffix: &'a str,
+    ns_separator: &'a str,
+    key_separator: &'a str,
+    /// Lint JSX files in the extraction parse instead of parsing them again
+    lint: Option<&'a LintOptions>,
+    path_style: PathStyle,
+}
+
+impl<'a> StrategyContext<'a> {
+    fn new(
+        functions: &'a [String],
+        trans_components: &'a [TransComponent],
+        trans_keep_basic_html_nodes_for: &'a [String],
+        trans_unescape: TransUnescape,
+        use_translation_names: &'a [UseTranslationName],
+        message_factory_functions: &'a [String],
+        key_producing_functions: &'a [String],
+        extract_from_comments: bool,
+        plural_config: &'a PluralConfig,
+        nesting_prefix: &'a str,
+        nesting_suffix: &'a str,
+        nesting_options_separator: &'a str,
+        interpolation_prefix: &'a str,
+        interpolation_suffix: &'a str,
+        ns_separator: &'a str,
+        key_separator: &'a str,
+    ) -> Self {
+        Self {
+            functions,
+            trans_components,
+            trans_keep_basic_html_nodes_for,
+            trans_unescape,
+            use_translation_names,
+            message_factory_functions,
+            key_producing_functions,
+            extract_from_comments,
+            plural_config,
+            nesting_prefix,
+            nesting_suffix,
+            nesting_options_separator,
+            interpolation_prefix,
+            interpolation_suffix,
+            ns_separator,
+            key_separator,
+            lint: None,
+            path_style: PathStyle::default(),
+        }
+    }
+
+    fn with_lint(mut self, lint: Option<&'a LintOptions>) -> Self {
+        self.lint = lint;
+        self
+    }
+
+    fn with_path_style(mut self, path_style: PathStyle) -> Self {
+        self.path_style = path_style;
+        self
+    }
+
+    #[allow(clippy::iter_cloned_collect)]
+    fn template_functions(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.functions.iter().cloned().collect();
+        if !names.iter().any(|name| name == "$t") {
+            names.push("$t".to_string());
+        }
+        names.sort();
+        names.dedup();
+        names
+    }
+}
+
+impl ExtractorStrategy {
+    fn from_path(path: &Path) -> Self {
+        match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|s| s.to_ascii_lowercase())
+        {
+            Some(ext) if ext == "vue" => ExtractorStrategy::Vue,
+            Some(ext) if ext == "svelte" => ExtractorStrategy::Svelte,
+            Some(ext) if ext == "mdx" => ExtractorStrategy::Mdx,
+            Some(ext) if matches!(ext.as_str(), "mjml" | "hbs" | "handlebars") => {
+                ExtractorStrategy::EmailTemplate
+            }
+            _ => ExtractorStrategy::JavaScript,
+        }
+    }
+
+    fn extract(
+        &self,
+        path: &Path,
+        source_code: BytesStr,
+        ctx: &StrategyContext,
+    ) -> Result<FileExtraction> {
+        let mut extraction = match self {
+            ExtractorStrategy::JavaScript => {
+                let parsed = parse_source(source_code, path)?;
+                let lint_issues = match ctx.lint {
+                    Some(options) if lint::is_lint_target(path) => {
+                        lint::lint_module(&parsed.module, parsed.source_map.clone(), path, options)
+                    }
+                    _ => Vec::new(),
+                };
+                let mut extraction = visit_parsed_source(
+                    parsed,
+                    path,
+                    ctx.functions,
+                    ctx.trans_components,
+                    ctx.trans_keep_basic_html_nodes_for,
+                    ctx.trans_unescape,
+                    ctx.use_translation_names,
+                    ctx.message_factory_functions,
+                    ctx.key_producing_functions,
+                    ctx.extract_from_comments,
+                    ctx.plural_config,
+                    ctx.nesting_prefix,
+                    ctx.nesting_suffix,
+                    ctx.nesting_options_separator,
+                    ctx.interpolation_prefix,
+                    ctx.interpolation_suffix,
+                    ctx.ns_separator,
+                    ctx.key_separator,
+                    ctx.path_style,
+                );
+                extraction.lint_issues = lint_issues;
+                extraction
+            }
+            ExtractorStrategy::Vue => extract_vue_component(path, &source_code, ctx)?,
+            ExtractorStrategy::Svelte => extract_svelte_component(path, &source_code, ctx)?,
+            ExtractorStrategy::Mdx => extract_mdx_document(path, &source_code, ctx)?,
+            ExtractorStrategy::EmailTemplate => extract_email_template(path, &source_code, ctx)?,
+        };
+        extraction.drop_unsafe_namespaces(path, ctx.path_style);
+        Ok(extraction)
+    }
+}
+
+/// Extract translation keys from a TypeScript/JavaScript file
+/// Note: This function always extracts from comments for backward compatibility.
+pub fn extract_from_file<P: AsRef<Path>>(
+    path: P,
+    functions: &[String],
+    plural_config: &PluralConfig,
+) -> Result<Vec<ExtractedKey>> {
+    let default_trans_components = vec![TransComponent::Name("Trans".to_string())];
+    let default_trans_keep_basic_html_nodes_for =
+        vec!["br".to_string(), "strong".to_string(), "i".to_string()];
+    let default_use_translation_names =
+        vec![UseTranslationName::Name("useTranslation".to_string())];
+    let FileExtraction { keys, .. } = extract_from_file_with_warnings(
+        path,
+        functions,
+        &default_trans_components,
+        &default_trans_keep_basic_html_nodes_for,
+        TransUnescape::default(),
+        &default_use_translation_names,
+        &[],
+        &[],
+        true,
+        plural_config,
+        "$t(",
+        ")",
+        ",",
+        "{{",
+        "}}",
+        ":",
+        ".",
+        None,
+        PathStyle::default(),
+    )?;
+    Ok(keys)
+}
+
+/// Extract translation keys from a file with configurable options
+pub fn extract_from_file_with_options<P: AsRef<Path>>(
+    path: P,
+    functions: &[String],
+    extract_from_comments: bool,
+    plural_config: &PluralConfig,
+) -> Result<Vec<ExtractedKey>> {
+    let default_trans_components = vec![TransComponent::Name("Trans".to_string())];
+    let default_trans_keep_basic_html_nodes_for =
+        vec!["br".to_string(), "strong".to_string(), "i".to_string()];
+    let default_use_translation_names =
+        vec![UseTranslationName::Name("useTranslation".to_string())];
+    let FileExtraction { keys, .. } = extract_from_file_with_warnings(
+        path,
+        functions,
+        &default_trans_components,
+        &default_trans_keep_basic_html_nodes_for,
+        TransUnescape::default(),
+        &default_use_translation_names,
+        &[],
+        &[],
+        extract_from_comments,
+        plural_config,
+        "$t(",
+        ")",
+        ",",
+        "{{",
+        "}}",
+        ":",
+        ".",
+        None,
+        PathStyle::default(),
+    )?;
+    Ok(keys)
+}
+
+fn extract_from_file_with_warnings<P: AsRef<Path>>(
+    path: P,
+    functions: &[String],
+    trans_components: &[TransComponent],
+    trans_keep_basic_html_nodes_for: &[String],
+    trans_unescape: TransUnescape,
+    use_translation_names: &[UseTranslationName],
+    message_factory_functions: &[String],
+    key_producing_functions: &[String],
+    extract_from_comments: bool,
+    plural_config: &PluralConfig,
+    nesting_prefix: &str,
+    nesting_suffix: &str,
+    nesting_options_separator: &str,
+    interpolation_prefix: &str,
+    interpolation_suffix: &str,
+    ns_separator: &str,
+    key_separator: &str,
+    lint: Option<&LintOptions>,
+    path_style: PathStyle,
+) -> Result<FileExtraction> {
+    let path = path.as_ref();
+    let source_code = read_source_file(path)?;
+    let strategy = ExtractorStrategy::from_path(path);
+    let ctx = StrategyContext::new(
+        functions,
+        trans_components,
+        trans_keep_basic_html_nodes_for,
+        trans_unescape,
+        use_translation_names,
+        message_factory_functions,
+        key_producing_functions,
+        extract_from_comments,
+        plural_config,
+        nesting_prefix,
+        nesting_suffix,
+        nesting_options_separator,
+        interpolation_prefix,
+        interpolation_suffix,
+        ns_separator,
+        key_separator,
+    )
+    .with_lint(lint)
+    .with_path_style(path_style);
+    strategy.extract(path, source_code, &ctx)
+}
+
+/// Read a source file into a buffer that SWC can take ownership of without another copy.
+/// Large files are memory-mapped; small ones are cheaper to read into a heap buffer.
+fn read_source_file(path: &Path) -> Result<BytesStr> {
+    let mut file =
+        File::open(path).with_context(|| format!("Failed to read file: {}", path.display()))?;
+    let len = file.metadata().map(|meta| meta.len()).unwrap_or(0);
+
+    if len < MMAP_THRESHOLD_BYTES {
+        let mut source = String::with_capacity(len as usize);
+        file.read_to_string(&mut source)
+            .with_context(|| format!("Failed to read file: {}", path.display()))?;
+        return Ok(BytesStr::from(source));
+    }
+
+    // SAFETY: the mapping is read-only and owned by the returned buffer. Truncating the file
+    // while it is being parsed is not supported, as with any mmap-based reader.
+    let mmap = unsafe { Mmap::map(&file) }
+        .with_context(|| format!("Failed to map file: {}", path.display()))?;
+    BytesStr::from_utf8(Bytes::from_owner(mmap)).map_err(|_| {
+        anyhow::anyhow!(
+            "Failed to read file: {}: stream did not contain valid UTF-8",
+            path.display()
+        )
+    })
+}
+
+/// Extract translation keys from source code string
+/// Note: This function always extracts from comments for backward compatibility.
+/// Use `extract_from_glob` with config for production use.
+pub fn extract_from_source<P: AsRef<Path>>(
+    source: &str,
+    path: P,
+    functions: &[String],
+) -> Result<Vec<ExtractedKey>> {
+    let plural_config = PluralConfig::default();
+    let default_trans_components = vec![TransComponent::Name("Trans".to_string())];
+    let default_trans_keep_basic_html_nodes_for =
+        vec!["br".to_string(), "strong".to_string(), "i".to_string()];
+    let default_use_translation_names =
+        vec![UseTranslationName::Name("useTranslation".to_string())];
+    let FileExtraction { keys, .. } = extract_from_source_with_warnings(
+        source.to_string(),
+        path,
+        functions,
+        &default_trans_components,
+        &default_trans_keep_basic_html_nodes_for,
+        TransUnescape::default(),
+        &default_use_translation_names,
+        &[],
+        &[],
+        true,
+        &plural_config,
+        "$t(",
+        ")",
+        ",",
+        "{{",
+        "}}",
+        ":",
+        ".",
+        PathStyle::default(),
+    )?;
+    Ok(keys)
+}
+
+/// Extract translation keys from a source string with configurable options
+pub fn extract_from_source_with_options<P: AsRef<Path>>(
+    source: &str,
+    path: P,
+    functions: &[String],
+    extract_from_comments: bool,
+    plural_config: &PluralConfig,
+) -> Result<Vec<ExtractedKey>> {
+    let default_trans_components = vec![TransComponent::Name("Trans".to_string())];
+    let default_trans_keep_basic_html_nodes_for =
+        vec!["br".to_string(), "strong".to_string(), "i".to_string()];
+    let default_use_translation_names =
+        vec![UseTranslationName::Name("useTranslation".to_string())];
+    let FileExtraction { keys, .. } = extract_from_source_with_warnings(
+        source.to_string(),
+        path,
+        functions,
+        &default_trans_components,
+        &default_trans_keep_basic_html_nodes_for,
+        TransUnescape::default(),
+        &default_use_translation_names,
+        &[],
+        &[],
+        extract_from_comments,
+        plural_config,
+        "$t(",
+        ")",
+        ",",
+        "{{",
+        "}}",
+        ":",
+        ".",
+        PathStyle::default(),
+    )?;
+    Ok(keys)
+}
+
+fn extract_from_source_with_warnings<P: AsRef<Path>>(
+    source: impl Into<BytesStr>,
+    path: P,
+    functions: &[String],
+    trans_components: &[TransComponent],
+    trans_keep_basic_html_nodes_for: &[String],
+    trans_unescape: TransUnescape,
+    use_translation_names: &[UseTranslationName],
+    message_factory_functions: &[String],
+    key_producing_functions: &[String],
+    should_extract_from_comments: bool,
+    plural_config: &PluralConfig,
+    nesting_prefix: &str,
+    nesting_suffix: &str,
+    nesting_options_separator: &str,
+    interpolation_prefix: &str,
+    interpolation_suffix: &str,
+    ns_separator: &str,
+    key_separator: &str,
+    path_style: PathStyle,
+) -> Result<FileExtraction> {
+    let path = path.as_ref();
+    let parsed = parse_source(source, path)?;
+    let mut extraction = visit_parsed_source(
+        parsed,
+        path,
+        functions,
+        trans_components,
+        trans_keep_basic_html_nodes_for,
+        trans_unescape,
+        use_translation_names,
+        message_factory_functions,
+        key_producing_functions,
+        should_extract_from_comments,
+        plural_config,
+        nesting_prefix,
+        nesting_suffix,
+        nesting_options_separator,
+        interpolation_prefix,
+        interpolation_suffix,
+        ns_separator,
+        key_separator,
+        path_style,
+    );
+    extraction.drop_unsafe_namespaces(path, path_style);
+    Ok(extraction)
+}
+
+/// A parsed JavaScript/TypeScript module with its source map and comments
+struct ParsedSource {
+    source_map: Lrc<SourceMap>,
+    comments: SingleThreadedComments,
+    module: Module,
+}
+
+fn parse_source(source: impl Into<BytesStr>, path: &Path) -> Result<ParsedSource> {
+    let cm: Lrc<SourceMap> = Default::default();
+
+    // Owned and memory-mapped buffers are handed over as-is; only borrowed input is copied.
+    let fm = cm.new_source_file(FileName::Real(path.to_path_buf()).into(), source);
+
+    // Determine syntax based on file extension
+    let is_tsx = path
+        .extension()
+        .map(|ext| ext == "tsx" || ext == "jsx")
+        .unwrap_or(false);
+
+    let syntax = Syntax::Typescript(TsSyntax {
+        tsx: is_tsx,
+        decorators: true,
+        ..Default::default()
+    });
+
+    // Create comments container for magic comment detection
+    let comments = SingleThreadedComments::default();
+
+    let lexer = Lexer::new(
+        syntax,
+        Default::default(),
+        StringInput::from(&*fm),
+        Some(&comments),
+    );
+
+    let mut parser = Parser::new_from(lexer);
+
+    // Parse the module, handling errors gracefully with user-friendly error messages
+    let module = match parser.parse_module() {
+        Ok(module) => module,
+        Err(e) => {
+            // Keep the position so callers can report file:line:column
+            let loc = cm.lookup_char_pos(e.span().lo);
+            return Err(ParseError {
+                line: loc.line,
+                column: loc.col_display + 1, // 1-based column for user display
+                message: format!("{:?}", e.kind()),
+            }
+            .into());
+        }
+    };
+
+    Ok(ParsedSource {
+        source_map: cm,
+        comments,
+        module,
+    })
+}
+
+fn visit_parsed_source(
+    parsed: ParsedSource,
+    path: &Path,
+    functions: &[String],
+    trans_components: &[TransComponent],
+    trans_keep_basic_html_nodes_for: &[String],
+    trans_unescape: TransUnescape,
+    use_translation_names: &[UseTranslationName],
+    message_factory_functions: &[String],
+    key_producing_functions: &[String],
+    should_extract_from_comments: bool,
+    plural_config: &PluralConfig,
+    nesting_prefix: &str,
+    nesting_suffix: &str,
+    nesting_options_separator: &str,
+    interpolation_prefix: &str,
+    interpolation_suffix: &str,
+    ns_separator: &str,
+    key_separator: &str,
+    path_style: PathStyle,
+) -> FileExtraction {
+    let ParsedSource {
+        source_map: cm,
+        comments,
+        module,
+    } = parsed;
+
+    // Visit the AST and extract keys
+    let mut visitor = TranslationVisitor::new(
+        functions.to_vec(),
+        trans_components.to_vec(),
+        trans_keep_basic_html_nodes_for.to_vec(),
+        trans_unescape,
+        use_translation_names.to_vec(),
+        message_factory_functions.to_vec(),
+        key_producing_functions.to_vec(),
+        cm,
+        Some(comments),
+        plural_config.clone(),
+        nesting_prefix.to_string(),
+        nesting_suffix.to_string(),
+        nesting_options_separator.to_string(),
+        interpolation_prefix.to_string(),
+        interpolation_suffix.to_string(),
+    );
+    visitor.file_path = Some(paths::display(path, path_style));
+    visitor.ns_separator = ns_separator.to_string();
+    visitor.key_separator = key_separator.to_string();
+    visitor.scan_disabled_regions(&module);
+    module.visit_with(&mut visitor);
+
+    // Also extract keys from comments (if enabled)
+    if should_extract_from_comments {
+        visitor.extract_from_comments();
+    }
+    visitor.apply_namespace_directive(module.body.first().map(|item| item.span().lo));
+
+    FileExtraction {
+        keys: visitor.keys,
+        diagnostics: visitor.diagnostics,
+        dynamic_keys: visitor.dynamic_keys,
+        skipped_calls: visitor.skipped_calls,
+        key_sources: visitor.key_sources,
+        ..FileExtraction::default()
+    }
+}
+
+fn extract_vue_component(
+    file_path: &Path,
+    source_code: &str,
+    ctx: &StrategyContext,
+) -> Result<FileExtraction> {
+    let mut result = FileExtraction::default();
+    let blocks = vue::parse_sfc(source_code);
+    let blocks_named = |tag: &'static str| {
+        blocks
+            .iter()
+            .filter(move |block| block.tag.eq_ignore_ascii_case(tag))
+    };
+
+    for (idx, block) in blocks_named("script").enumerate() {
+        let virtual_path = format!("{}#script{}", file_path.display(), idx + 1);
+        result.append_block(
+            file_path,
+            &virtual_path,
+            extract_from_source_with_warnings(
+                block.content.clone(),
+                &virtual_path,
+                ctx.functions,
+                ctx.trans_components,
+                ctx.trans_keep_basic_html_nodes_for,
+                ctx.trans_unescape,
+                ctx.use_translation_names,
+                ctx.message_factory_functions,
+                ctx.key_producing_functions,
+                ctx.extract_from_comments,
+                ctx.plural_config,
+                ctx.nesting_prefix,
+                ctx.nesting_suffix,
+                ctx.nesting_options_separator,
+                ctx.interpolation_prefix,
+                ctx.interpolation_suffix,
+                ctx.ns_separator,
+                ctx.key_separator,
+                ctx.path_style,
+            ),
+            ctx.path_style,
+        );
+    }
+
+    let template_functions = ctx.template_functions();
+    for (block_idx, block) in blocks_named("template").enumerate() {
+        let mut calls = Vec::new();
+        for expression in vue::template_expressions(&block.content) {
+            match expression {
+                vue::TemplateExpression::Code(code) => {
+                    calls.extend(extract_translation_calls(&code, &template_functions, true));
+                }
+                vue::TemplateExpression::TranslateDirective(value) => {
+                    calls.extend(vue::translate_directive_call(&value));
+                }
+            }
+        }
+        for (expr_idx, expr) in calls.iter().enumerate() {
+            let virtual_source = format!(
+                "function __i18n_tpl_{}() {{ return {}; }}",
+                expr_idx + 1,
+                expr
+            );
+            let virtual_path = format!(
+                "{}#template{}:{}",
+                file_path.display(),
+                block_idx + 1,
+                expr_idx + 1
+            );
+            result.append_block(
+                file_path,
+                &virtual_path,
+                extract_from_source_with_warnings(
+                    virtual_source,
+                    &virtual_path,
+                    &template_functions,
+                    ctx.trans_components,
+                    ctx.trans_keep_basic_html_nodes_for,
+                    ctx.trans_unescape,
+                    ctx.use_translation_names,
+                    ctx.message_factory_functions,
+                    ctx.key_producing_functions,
+                    false,
+                    ctx.plural_config,
+                    ctx.nesting_prefix,
+                    ctx.nesting_suffix,
+                    ctx.nesting_options_separator,
+                    ctx.interpolation_prefix,
+                    ctx.interpolation_suffix,
+                    ctx.ns_separator,
+                    ctx.key_separator,
+                    ctx.path_style,
+                ),
+                ctx.path_style,
+            );
+        }
+    }
+
+    for block in blocks_named("i18n") {
+        let messages = match vue::parse_i18n_block(block) {
+            Ok(messages) => messages,
+            Err(err) => {
+                result.diagnostics.push(Diagnostic {
+                    kind: DiagnosticKind::ParseError,
+                    severity: DiagnosticSeverity::Warn,
+                    file_path: paths::display(file_path, ctx.path_style),
+                    line: None,
+                    column: None,
+                    message: format!(
+                        "Skipping <i18n> block in {}: {:#}",
+                        file_path.display(),
+                        err
+                    ),
+                });
+                continue;
+            }
+        };
+        let mut seen = HashSet::new();
+        for (locale, key, value) in messages {
+            if seen.insert(key.clone()) {
+                result.keys.push(ExtractedKey {
+                    key: key.clone(),
+                    namespace: None,
+                    default_value: None,
+                });
+            }
+            result.messages.push(ComponentMessage {
+                file_path: paths::display(file_path, ctx.path_style),
+                locale,
+                key,
+                value,
+            });
+        }
+    }
+
+    let has_sfc_blocks = ["script", "template", "i18n"]
+        .iter()
+        .any(|tag| blocks_named(tag).next().is_some());
+    if !has_sfc_blocks {
+        return extract_from_source_with_warnings(
+            source_code.to_string(),
+            file_path,
+            ctx.functions,
+            ctx.trans_components,
+            ctx.trans_keep_basic_html_nodes_for,
+            ctx.trans_unescape,
+            ctx.use_translation_names,
+            ctx.message_factory_functions,
+            ctx.key_producing_functions,
+            ctx.extract_from_comments,
+            ctx.plural_config,
+            ctx.nesting_prefix,
+            ctx.nesting_suffix,
+            ctx.nesting_options_separator,
+            ctx.interpolation_prefix,
+            ctx.interpolation_suffix,
+            ctx.ns_separator,
+            ctx.key_separator,
+            ctx.path_style,
+        );
+    }
+
+    Ok(result)
+}
+
+fn extract_svelte_component(
+    file_path: &Path,
+    source_code: &str,
+    ctx: &StrategyContext,
+) -> Result<FileExtraction> {
+    let mut result = FileExtraction::default();
+
+    let script_blocks = extract_tag_blocks(source_code, get_script_block_regex());
+    for (idx, block) in script_blocks.iter().enumerate() {
+        let virtual_path = format!("{}#script{}", file_path.display(), idx + 1);
+        result.append_block(
+            file_path,
+            &virtual_path,
+            extract_from_source_with_warnings(
+                block.content.clone(),
+                &virtual_path,
+                ctx.functions,
+                ctx.trans_components,
+                ctx.trans_keep_basic_html_nodes_for,
+                ctx.trans_unescape,
+                ctx.use_translation_names,
+                ctx.message_factory_functions,
+                ctx.key_producing_functions,
+                ctx.extract_from_comments,
+                ctx.plural_config,
+                ctx.nesting_prefix,
+                ctx.nesting_suffix,
+                ctx.nesting_options_separator,
+                ctx.interpolation_prefix,
+                ctx.interpolation_suffix,
+                ctx.ns_separator,
+                ctx.key_separator,
+                ctx.path_style,
+            ),
+            ctx.path_style,
+        );
+    }
+
+    let mut trimmed_template = source_code.to_string();
+    let mut removal_ranges: Vec<Range<usize>> = script_blocks
+        .iter()
+        .map(|block| block.range.clone())
+        .collect();
+    let style_blocks = extract_tag_blocks(source_code, get_style_block_regex());
+    for block in style_blocks {
+        removal_ranges.push(block.range);
+    }
+    removal_ranges.sort_by_key(|range| std::cmp::Reverse(range.start));
+    for range in removal_ranges {
+        let len = range.end.saturating_sub(range.start);
+        if len == 0 || range.end > trimmed_template.len() {
+            continue;
+        }
+        trimmed_template.replace_range(range, &" ".repeat(len));
+    }
+
+    let template_functions = ctx.template_functions();
+    let template_exprs = extract_translation_calls(&trimmed_template, &template_functions, true);
+    for (idx, expr) in template_exprs.iter().enumerate() {
+        let virtual_source = format!("function __svelte_tpl_{}() {{ return {}; }}", idx + 1, expr);
+        let virtual_path = format!("{}#template:{}", file_path.display(), idx + 1);
+        result.append_block(
+            file_path,
+            &virtual_path,
+            extract_from_source_with_warnings(
+                virtual_source,
+                &virtual_path,
+                &template_functions,
+                ctx.trans_components,
+                ctx.trans_keep_basic_html_nodes_for,
+                ctx.trans_unescape,
+                ctx.use_translation_names,
+                ctx.message_factory_functions,
+                ctx.key_producing_functions,
+                false,
+                ctx.plural_config,
+                ctx.nesting_prefix,
+                ctx.nesting_suffix,
+                ctx.nesting_options_separator,
+                ctx.interpolation_prefix,
+                ctx.interpolation_suffix,
+                ctx.ns_separator,
+                ctx.key_separator,
+                ctx.path_style,
+            ),
+            ctx.path_style,
+        );
+    }
+
+    if script_blocks.is_empty() && template_exprs.is_empty() {
+        return extract_from_source_with_warnings(
+            source_code.to_string(),
+            file_path,
+            ctx.functions,
+            ctx.trans_components,
+            ctx.trans_keep_basic_html_nodes_for,
+            ctx.trans_unescape,
+            ctx.use_translation_names,
+            ctx.message_factory_functions,
+            ctx.key_producing_functions,
+            ctx.extract_from_comments,
+            ctx.plural_config,
+            ctx.nesting_prefix,
+            ctx.nesting_suffix,
+            ctx.nesting_options_separator,
+            ctx.interpolation_prefix,
+            ctx.interpolation_suffix,
+            ctx.ns_separator,
+            ctx.key_separator,
+            ctx.path_style,
+        );
+    }
+
+    Ok(result)
+}
+
+/// Extract from the JSX islands and ESM blocks of an MDX document. Prose and
+/// fenced code are blanked, keeping line numbers.
+fn extract_mdx_document(
+    file_path: &Path,
+    source_code: &str,
+    ctx: &StrategyContext,
+) -> Result<FileExtraction> {
+    let virtual_path = format!("{}#jsx.tsx", file_path.display());
+    extract_from_source_with_warnings(
+        mdx::to_tsx_module(source_code),
+        &virtual_path,
+        ctx.functions,
+        ctx.trans_components,
+        ctx.trans_keep_basic_html_nodes_for,
+        ctx.trans_unescape,
+        ctx.use_translation_names,
+        ctx.message_factory_functions,
+        ctx.key_producing_functions,
+        ctx.extract_from_comments,
+        ctx.plural_config,
+        ctx.nesting_prefix,
+        ctx.nesting_suffix,
+        ctx.nesting_options_separator,
+        ctx.interpolation_prefix,
+        ctx.interpolation_suffix,
+        ctx.ns_separator,
+        ctx.key_separator,
+        ctx.path_style,
+    )
+}
+
+fn extract_email_template(
+    file_path: &Path,
+    source_code: &str,
+    ctx: &StrategyContext,
+) -> Result<FileExtraction> {
+    let virtual_path = format!("{}#helpers.js", file_path.display());
+    extract_from_source_with_warnings(
+        handlebars::to_js_module(source_code, ctx.functions),
+        &virtual_path,
+        ctx.functions,
+        ctx.trans_components,
+        ctx.trans_keep_basic_html_nodes_for,
+        ctx.trans_unescape,
+        ctx.use_translation_names,
+        ctx.message_factory_functions,
+        ctx.key_producing_functions,
+        false,
+        ctx.plural_config,
+        ctx.nesting_prefix,
+        ctx.nesting_suffix,
+        ctx.nesting_options_separator,
+        ctx.interpolation_prefix,
+        ctx.interpolation_suffix,
+        ctx.ns_separator,
+        ctx.key_separator,
+        ctx.path_style,
+    )
+}
+
+/// Result type for a single file extraction (used internally for lock-free processing)
+enum FileExtractionResult {
+    Success {
+        file_path: String,
+        keys: Vec<ExtractedKey>,
+        diagnostics: Vec<Diagnostic>,
+        messages: Vec<ComponentMessage>,
+        dynamic_keys: Vec<DynamicKeyPattern>,
+        skipped_calls: Vec<SkippedCall>,
+        key_sources: Vec<KeySource>,
+        lint_issues: Vec<LintIssue>,
+    },
+    Error(ExtractionError),
+    Empty {
+        diagnostics: Vec<Diagnostic>,
+        dynamic_keys: Vec<DynamicKeyPattern>,
+        skipped_calls: Vec<SkippedCall>,
+        lint_issues: Vec<LintIssue>,
+    },
+    /// Skipped because the run was cancelled
+    Cancelled,
+}
+
+/// Options of one extraction run. Start from [`ExtractRequest::builder`] and
+/// set what differs from the defaults (or take everything from a [`Config`]
+/// with [`ExtractRequestBuilder::config`]), so that new options do not change
+/// the signature for existing callers.
+///
+/// ```no_run
+/// use i18next_turbo_core::extractor::ExtractRequest;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let result = ExtractRequest::builder()
+///     .patterns(["src/**/*.{ts,tsx}"])
+///     .functions(["t", "i18n.t"])
+///     .nesting("$t(", ")", ",")
+///     .build()
+///     .run()?;
+/// println!("{} file(s) with keys", result.files.len());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExtractRequest {
+    patterns: Vec<String>,
+    ignore_patterns: Vec<String>,
+    functions: Vec<String>,
+    extract_from_comments: bool,
+    plural_config: PluralConfig,
+    trans_components: Vec<TransComponent>,
+    trans_keep_basic_html_nodes_for: Vec<String>,
+    trans_unescape: TransUnescape,
+    use_translation_names: Vec<UseTranslationName>,
+    message_factory_functions: Vec<String>,
+    key_producing_functions: Vec<String>,
+    nesting_prefix: String,
+    nesting_suffix: String,
+    nesting_options_separator: String,
+    interpolation_prefix: String,
+    interpolation_suffix: String,
+    ns_separator: String,
+    key_separator: String,
+    follow_symlinks: bool,
+    dynamic_key_severity: DiagnosticSeverity,
+    dynamic_key_rules: Vec<SeverityRule>,
+    /// Diagnostic kinds to report; empty reports every kind
+    diagnostic_kinds: Vec<DiagnosticKind>,
+    cancellation: CancellationToken,
+    lint: Option<LintOptions>,
+    path_style: PathStyle,
+}
+
+impl Default for ExtractRequest {
+    /// No input patterns; every option as in a config file that leaves it out
+    fn default() -> Self {
+        Self::with_config(Vec::new(), Vec::new(), &Config::default())
+    }
+}
+
+impl ExtractRequest {
+    pub fn builder() -> ExtractRequestBuilder {
+        ExtractRequestBuilder::default()
+    }
+
+    fn with_config(patterns: Vec<String>, ignore_patterns: Vec<String>, config: &Config) -> Self {
+        Self {
+            patterns,
+            ignore_patterns,
+            functions: config.functions.clone(),
+            extract_from_comments: config.extract_from_comments,
+            plural_config: config.plural_config(),
+            trans_components: config.trans_components.clone(),
+            trans_keep_basic_html_nodes_for: config.trans_keep_basic_html_nodes_for.clone(),
+            trans_unescape: config.trans_unescape(),
+            use_translation_names: config.use_translation_names.clone(),
+            message_factory_functions: config.message_factory_functions.clone(),
+            key_producing_functions: config.key_producing_functions.clone(),
+            nesting_prefix: config.nesting_prefix.clone(),
+            nesting_suffix: config.nesting_suffix.clone(),
+            nesting_options_separator: config.nesting_options_separator.clone(),
+            interpolation_prefix: config.interpolation_prefix.clone(),
+            interpolation_suffix: config.interpolation_suffix.clone(),
+            ns_separator: config.ns_separator.clone(),
+            key_separator: config.key_separator.clone(),
+            follow_symlinks: config.follow_symlinks,
+            dynamic_key_severity: config.dynamic_key_severity,
+            dynamic_key_rules: config.dynamic_key_rules.clone(),
+            diagnostic_kinds: Vec::new(),
+            cancellation: CancellationToken::default(),
+            lint: None,
+            path_style: config.path_style,
+        }
+    }
+
+    /// Extract keys from every file matching the patterns.
+    ///
+    /// This implementation uses streaming parallel processing:
+    /// - Uses `par_bridge()` to stream file paths directly into worker threads
+    /// - No upfront collection of all file paths (O(1) memory for paths)
+    /// - Lock-free error collection (each thread returns Result enum)
+    /// - Optimized for large monorepos (millions of files)
+    ///
+    /// Once the cancellation token fires, globbing stops and files not yet
+    /// parsed are skipped; the result holds what was extracted until then.
+    pub fn run(&self) -> Result<ExtractionResult> {
+        use rayon::iter::ParallelBridge;
+        use rayon::prelude::*;
+
+        let started = std::time::Instant::now();
+        let expanded_patterns: Vec<String> = self
+            .patterns
+            .iter()
+            .flat_map(|pattern| expand_brace_patterns(pattern))
+            .collect();
+        let ignore_matchers = Arc::new(compile_ignore_patterns(&self.ignore_patterns)?);
+        let lint_ignore: Vec<Pattern> = self
+            .lint
+            .iter()
+            .flat_map(|options| &options.ignore_patterns)
+            .filter_map(|pattern| Pattern::new(pattern).ok())
+            .collect();
+        let linted = std::sync::atomic::AtomicUsize::new(0);
+
+        // Create a streaming iterator that chains all glob patterns
+        // This avoids collecting all file paths into memory upfront
+        let pattern_refs: Vec<&str> = expanded_patterns.iter().map(|s| s.as_str()).collect();
+
+        // Enum to represent either a valid path or an error during glob iteration
+        enum GlobItem {
+            Path(std::path::PathBuf),
+            GlobError { pattern: String, message: String },
+            PatternError { pattern: String, message: String },
+        }
+
+        // Process files using streaming parallel processing with par_bridge()
+        // Files are fed to worker threads as they are discovered by glob
+        let mut symlink_guard = walk::SymlinkGuard::new(self.follow_symlinks);
+        // Set when a file is left out because the token fired
+        let stopped = std::sync::atomic::AtomicBool::new(false);
+        let scanned = std::sync::atomic::AtomicUsize::new(0);
+        let keep_going = || {
+            let cancelled = self.cancellation.is_cancelled();
+            if cancelled {
+                stopped.store(true, std::sync::atomic::Ordering::Relaxed);
+            }
+            !cancelled
+        };
+        let mut file_results: Vec<(usize, FileExtractionResult)> = pattern_refs
+            .into_iter()
+            .take_while(|_| keep_going())
+            .flat_map(|pattern| {
+                let ignore_for_pattern = Arc::clone(&ignore_matchers);
+                let base = walk::glob_base(pattern);
+                // Create iterator for this pattern (may error)
+                match glob::glob(pattern) {
+                    Ok(paths) => {
+                        // Map each path result to GlobItem
+                        paths
+                            .take_while(|_| keep_going())
+                            .filter_map(|entry| match entry {
+                                Ok(path)
+                                    if path.is_file()
+                                        && !matches_ignore_path(
+                                            &path,
+                                            ignore_for_pattern.as_ref(),
+                                        ) =>
+                                {
+                                    // Skip unsafe symlinks and files already seen through one
+                                    match symlink_guard.admit(&base, &path) {
+                                        Ok(()) => Some(GlobItem::Path(path)),
+                                        Err(reason) => {
+                                            logging::debug(&format!(
+                                                "skipping {}: {}",
+                                                path.display(),
+                                                reason
+                                            ));
+                                            None
+                                        }
+                                    }
+                                }
+                                Ok(_) => None, // Skip directories and ignored files
+                                Err(e) => Some(GlobItem::GlobError {
+                                    pattern: pattern.to_string(),
+                                    message: e.to_string(),
+                                }),
+                            })
+                            .collect::<Vec<_>>()
+                    }
+                    Err(e) => {
+                        // Return pattern error as a single-element vec
+                        vec![GlobItem::PatternError {
+                            pattern: pattern.to_string(),
+                            message: e.to_string(),
+                        }]
+                    }
+                }
+            })
+            .enumerate()
+            .par_bridge() // Stream directly into parallel processing
+            .map(|(index, item)| {
+                (
+                    index,
+                    match item {
+                        GlobItem::Path(_) if !keep_going() => FileExtractionResult::Cancelled,
+                        GlobItem::Path(path) => {
+                            scanned.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                            let lint = self.lint.as_ref().filter(|_| {
+                                !lint_ignore
+                                    .iter()
+                                    .any(|pattern| pattern.matches_path(&path))
+                            });
+                            if lint.is_some() {
+                                linted.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                            }
+                            match extract_from_file_with_warnings(
+                                &path,
+                                &self.functions,
+                                &self.trans_components,
+                                &self.trans_keep_basic_html_nodes_for,
+                                self.trans_unescape,
+                                &self.use_translation_names,
+                                &self.message_factory_functions,
+                                &self.key_producing_functions,
+                                self.extract_from_comments,
+                                &self.plural_config,
+                                &self.nesting_prefix,
+                                &self.nesting_suffix,
+                                &self.nesting_options_separator,
+                                &self.interpolation_prefix,
+                                &self.interpolation_suffix,
+                                &self.ns_separator,
+                                &self.key_separator,
+                                lint,
+                                self.path_style,
+                            ) {
+                                Ok(FileExtraction {
+                                    keys,
+                                    diagnostics,
+                                    messages,
+                                    dynamic_keys,
+                                    skipped_calls,
+                                    key_sources,
+                                    lint_issues,
+                                }) => {
+                                    if keys.is_empty() {
+                                        FileExtractionResult::Empty {
+                                            diagnostics,
+                                            dynamic_keys,
+                                            skipped_calls,
+                                            lint_issues,
+                                        }
+                                    } else {
+                                        FileExtractionResult::Success {
+                                            file_path: paths::display(&path, self.path_style),
+                                            keys,
+                                            diagnostics,
+                                            messages,
+                                            dynamic_keys,
+                                            skipped_calls,
+                                            key_sources,
+                                            lint_issues,
+                                        }
+                                    }
+                                }
+                                Err(e) => {
+                                    FileExtractionResult::Error(ExtractionError::from_file_error(
+                                        paths::display(&path, self.path_style),
+                                        &e,
+                                    ))
+                                }
+                            }
+                        }
+                        GlobItem::GlobError { pattern, message } => FileExtractionResult::Error(
+                            ExtractionError::glob(pattern, format!("Glob error: {}", message)),
+                        ),
+                        GlobItem::PatternError { pattern, message } => {
+                            FileExtractionResult::Error(ExtractionError::glob(
+                                pattern,
+                                format!("Invalid glob pattern: {}", message),
+                            ))
+                        }
+                    },
+                )
+            })
+            .collect();
+        // Worker threads finish in any order; restore glob order so the first
+        // file wins key conflicts (and reports list files) the same way every run
+        file_results.sort_by_key(|(index, _)| *index);
+
+        // Aggregate results (single-threaded, but O(n) - no lock contention)
+        let mut files: Vec<(String, Vec<ExtractedKey>)> = Vec::new();
+        let mut errors: Vec<ExtractionError> = Vec::new();
+        let mut messages: Vec<ComponentMessage> = Vec::new();
+        let mut dynamic_keys: Vec<DynamicKeyPattern> = Vec::new();
+        let mut skipped_calls: Vec<SkippedCall> = Vec::new();
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        let mut key_sources: Vec<KeySource> = Vec::new();
+        let mut lint_issues: Vec<LintIssue> = Vec::new();
+
+        for (_, result) in file_results {
+            match result {
+                FileExtractionResult::Success {
+                    file_path,
+                    keys,
+                    diagnostics: mut file_diagnostics,
+                    messages: mut file_messages,
+                    dynamic_keys: mut file_dynamic_keys,
+                    skipped_calls: mut file_skipped_calls,
+                    key_sources: mut file_key_sources,
+                    lint_issues: mut file_lint_issues,
+                } => {
+                    files.push((file_path, keys));
+                    lint_issues.append(&mut file_lint_issues);
+                    messages.append(&mut file_messages);
+                    dynamic_keys.append(&mut file_dynamic_keys);
+                    skipped_calls.append(&mut file_skipped_calls);
+                    diagnostics.append(&mut file_diagnostics);
+                    key_sources.append(&mut file_key_sources);
+                }
+                FileExtractionResult::Error(err) => {
+                    errors.push(err);
+                }
+                FileExtractionResult::Empty {
+                    diagnostics: mut file_diagnostics,
+                    dynamic_keys: mut file_dynamic_keys,
+                    skipped_calls: mut file_skipped_calls,
+                    lint_issues: mut file_lint_issues,
+                } => {
+                    dynamic_keys.append(&mut file_dynamic_keys);
+                    skipped_calls.append(&mut file_skipped_calls);
+                    diagnostics.append(&mut file_diagnostics);
+                    lint_issues.append(&mut file_lint_issues);
+                }
+                FileExtractionResult::Cancelled => {}
+            }
+        }
+        let diagnostics = self.report_diagnostics(diagnostics, &errors);
+        summary::add_files_scanned(scanned.into_inner());
+        summary::add_phase("extract", started.elapsed());
+
+        Ok(ExtractionResult {
+            files,
+            errors,
+            diagnostics,
+            messages,
+            dynamic_keys,
+            skipped_calls,
+            key_collisions: find_key_collisions(&key_sources),
+            duplicate_keys: find_duplicate_keys(&key_sources),
+            key_sources,
+            cancelled: stopped.into_inner(),
+            lint: self.lint.as_ref().map(|_| LintResult {
+                issues: lint_issues,
+                files_checked: linted.into_inner(),
+            }),
+        })
+    }
+}
+
+impl ExtractRequest {
+    /// Extract a source held in memory (e.g. read from stdin) as if it were the
+    /// file at `path`, whose extension picks the parser. Patterns are not used.
+    pub fn run_source(&self, source: impl Into<String>, path: &Path) -> Result<ExtractionResult> {
+        let ctx = StrategyContext::new(
+            &self.functions,
+            &self.trans_components,
+            &self.trans_keep_basic_html_nodes_for,
+            self.trans_unescape,
+            &self.use_translation_names,
+            &self.message_factory_functions,
+            &self.key_producing_functions,
+            self.extract_from_comments,
+            &self.plural_config,
+            &self.nesting_prefix,
+            &self.nesting_suffix,
+            &self.nesting_options_separator,
+            &self.interpolation_prefix,
+            &self.interpolation_suffix,
+            &self.ns_separator,
+            &self.key_separator,
+        )
+        .with_lint(self.lint.as_ref())
+        .with_path_style(self.path_style);
+        let file_path = paths::display(path, self.path_style);
+        let mut result = ExtractionResult {
+            lint: self.lint.as_ref().map(|_| LintResult {
+                files_checked: 1,
+                ..LintResult::default()
+            }),
+            ..ExtractionResult::default()
+        };
+        match ExtractorStrategy::from_path(path).extract(path, BytesStr::from(source.into()), &ctx)
+        {
+            Ok(file) => {
+                if let Some(lint) = &mut result.lint {
+                    lint.issues = file.lint_issues;
+                }
+                if !file.keys.is_empty() {
+                    result.files.push((file_path, file.keys));
+                }
+                result.diagnostics = self.report_diagnostics(file.diagnostics, &[]);
+                result.messages = file.messages;
+                result.dynamic_keys = file.dynamic_keys;
+                result.skipped_calls = file.skipped_calls;
+                result.key_collisions = find_key_collisions(&file.key_sources);
+                result.duplicate_keys = find_duplicate_keys(&file.key_sources);
+                result.key_sources = file.key_sources;
+            }
+            Err(e) => {
+                result
+                    .errors
+                    .push(ExtractionError::from_file_error(file_path, &e));
+                result.diagnostics = self.report_diagnostics(Vec::new(), &result.errors);
+            }
+        }
+        summary::add_files_scanned(1);
+        Ok(result)
+    }
+
+    /// Apply the configured severity to each dynamic key, drop the kinds
+    /// not selected with [`ExtractRequestBuilder::diagnostic_kinds`] and print
+    /// the rest. `errors` are printed by the caller and only added as
+    /// diagnostics.
+    fn report_diagnostics(
+        &self,
+        diagnostics: Vec<Diagnostic>,
+        errors: &[ExtractionError],
+    ) -> Vec<Diagnostic> {
+        let selected = |kind: DiagnosticKind| {
+            self.diagnostic_kinds.is_empty() || self.diagnostic_kinds.contains(&kind)
+        };
+        let mut reported: Vec<Diagnostic> = diagnostics
+            .into_iter()
+            .filter(|diagnostic| selected(diagnostic.kind))
+            .filter_map(|mut diagnostic| {
+                if diagnostic.kind == DiagnosticKind::DynamicKey {
+                    diagnostic.severity = SeverityRule::resolve(
+                        &self.dynamic_key_rules,
+                        self.dynamic_key_severity,
+                        Path::new(&diagnostic.file_path),
+                    );
+                }
+                if diagnostic.severity == DiagnosticSeverity::Off {
+                    return None;
+                }
+                diagnostic.print();
+                Some(diagnostic)
+            })
+            .collect();
+        reported.extend(
+            errors
+                .iter()
+                .map(Diagnostic::from_error)
+                .filter(|diagnostic| selected(diagnostic.kind)),
+        );
+        reported
+    }
+}
+
+/// Builder of an [`ExtractRequest`]
+#[derive(Debug, Clone, Default)]
+pub struct ExtractRequestBuilder {
+    request: ExtractRequest,
+}
+
+fn strings<S: Into<String>>(values: impl IntoIterator<Item = S>) -> Vec<String> {
+    values.into_iter().map(Into::into).collect()
+}
+
+impl ExtractRequestBuilder {
+    /// Take every extraction option from `config`; patterns, lint options,
+    /// diagnostic kinds and the cancellation token are left as they are
+    pub fn config(self, config: &Config) -> Self {
+        let ExtractRequest {
+            patterns,
+            ignore_patterns,
+            diagnostic_kinds,
+            cancellation,
+            lint,
+            ..
+        } = self.request;
+        Self {
+            request: ExtractRequest {
+                diagnostic_kinds,
+                cancellation,
+                lint,
+                ..ExtractRequest::with_config(patterns, ignore_patterns, config)
+            },
+        }
+    }
+
+    /// Glob patterns of the source files (`{a,b}` alternatives are expanded)
+    pub fn patterns<S: Into<String>>(mut self, patterns: impl IntoIterator<Item = S>) -> Self {
+        self.request.patterns = strings(patterns);
+        self
+    }
+
+    /// Glob patterns of files to skip
+    pub fn ignore<S: Into<String>>(mut self, patterns: impl IntoIterator<Item = S>) -> Self {
+        self.request.ignore_patterns = strings(patterns);
+        self
+    }
+
+    /// Translation function names (`t`, `i18n.t`, ...)
+    pub fn functions<S: Into<String>>(mut self, functions: impl IntoIterator<Item = S>) -> Self {
+        self.request.functions = strings(functions);
+        self
+    }
+
+    pub fn extract_from_comments(mut self, enabled: bool) -> Self {
+        self.request.extract_from_comments = enabled;
+        self
+    }
+
+    pub fn plural_config(mut self, plural_config: PluralConfig) -> Self {
+        self.request.plural_config = plural_config;
+        self
+    }
+
+    pub fn trans_components(
+        mut self,
+        components: impl IntoIterator<Item = TransComponent>,
+    ) -> Self {
+        self.request.trans_components = components.into_iter().collect();
+        self
+    }
+
+    pub fn trans_keep_basic_html_nodes_for<S: Into<String>>(
+        mut self,
+        nodes: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.request.trans_keep_basic_html_nodes_for = strings(nodes);
+        self
+    }
+
+    pub fn trans_unescape(mut self, trans_unescape: TransUnescape) -> Self {
+        self.request.trans_unescape = trans_unescape;
+        self
+    }
+
+    pub fn use_translation_names(
+        mut self,
+        names: impl IntoIterator<Item = UseTranslationName>,
+    ) -> Self {
+        self.request.use_translation_names = names.into_iter().collect();
+        self
+    }
+
+    pub fn message_factory_functions<S: Into<String>>(
+        mut self,
+        functions: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.request.message_factory_functions = strings(functions);
+        self
+    }
+
+    pub fn key_producing_functions<S: Into<String>>(
+        mut self,
+        functions: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.request.key_producing_functions = strings(functions);
+        self
+    }
+
+    /// Delimiters of nested keys in default values (`$t(`, `)`, `,`)
+    pub fn nesting(
+        mut self,
+        prefix: impl Into<String>,
+        suffix: impl Into<String>,
+        options_separator: impl Into<String>,
+    ) -> Self {
+        self.request.nesting_prefix = prefix.into();
+        self.request.nesting_suffix = suffix.into();
+        self.request.nesting_options_separator = options_separator.into();
+        self
+    }
+
+    /// Delimiters of interpolations in default values (`{{`, `}}`)
+    pub fn interpolation(mut self, prefix: impl Into<String>, suffix: impl Into<String>) -> Self {
+        self.request.interpolation_prefix = prefix.into();
+        self.request.interpolation_suffix = suffix.into();
+        self
+    }
+
+    /// Separators of namespace prefixes (`:`) and of key segments (`.`), which
+    /// split `t('ns:key')` and join a `keyPrefix` to its keys. Empty strings
+    /// turn namespace prefixes off and join prefixes with `.`, as i18next does.
+    pub fn separators(mut self, ns: impl Into<String>, key: impl Into<String>) -> Self {
+        self.request.ns_separator = ns.into();
+        self.request.key_separator = key.into();
+        self
+    }
+
+    pub fn follow_symlinks(mut self, follow: bool) -> Self {
+        self.request.follow_symlinks = follow;
+        self
+    }
+
+    /// Severity of dynamic template literal keys in files no rule matches
+    pub fn dynamic_key_severity(mut self, severity: DiagnosticSeverity) -> Self {
+        self.request.dynamic_key_severity = severity;
+        self
+    }
+
+    /// Per-file dynamic key severities, the first matching rule wins
+    pub fn dynamic_key_rules(mut self, rules: impl IntoIterator<Item = SeverityRule>) -> Self {
+        self.request.dynamic_key_rules = rules.into_iter().collect();
+        self
+    }
+
+    /// Only report diagnostics of these kinds; the others are neither printed
+    /// nor counted
+    pub fn diagnostic_kinds(mut self, kinds: impl IntoIterator<Item = DiagnosticKind>) -> Self {
+        self.request.diagnostic_kinds = kinds.into_iter().collect();
+        self
+    }
+
+    /// Also lint JSX files for hardcoded strings while they are parsed for
+    /// keys, instead of parsing them again; see [`ExtractionResult::lint`]
+    pub fn lint(mut self, options: LintOptions) -> Self {
+        self.request.lint = Some(options);
+        self
+    }
+
+    /// Separators of the file paths in keys and diagnostics (`pathStyle`)
+    pub fn path_style(mut self, style: PathStyle) -> Self {
+        self.request.path_style = style;
+        self
+    }
+
+    /// Stop globbing and parsing once `token` is cancelled
+    pub fn cancellation(mut self, token: CancellationToken) -> Self {
+        self.request.cancellation = token;
+        self
+    }
+
+    pub fn build(self) -> ExtractRequest {
+        self.request
+    }
+}
+
+/// Extract keys from multiple files using glob patterns, with the defaults of
+/// `<Trans>`, `useTranslation` and the i18next nesting/interpolation syntax
+pub fn extract_from_glob(
+    patterns: &[String],
+    ignore_patterns: &[String],
+    functions: &[String],
+    plural_config: &PluralConfig,
+) -> Result<ExtractionResult> {
+    ExtractRequest::builder()
+        .patterns(patterns.iter().cloned())
+        .ignore(ignore_patterns.iter().cloned())
+        .functions(functions.iter().cloned())
+        .extract_from_comments(true)
+        .plural_config(plural_config.clone())
+        .trans_components([TransComponent::Name("Trans".to_string())])
+        .trans_keep_basic_html_nodes_for(["br", "strong", "i"])
+        .trans_unescape(TransUnescape::default())
+        .use_translation_names([UseTranslationName::Name("useTranslation".to_string())])
+        .message_factory_functions(Vec::<String>::new())
+        .nesting("$t(", ")", ",")
+        .interpolation("{{", "}}")
+        .follow_symlinks(true)
+        .build()
+        .run()
+}
+
+/// Extract keys from the files matching `patterns` (minus `ignore_patterns`)
+/// with every extraction option taken from `config`. Pass `config.input` and
+/// `config.extraction_ignore()` for a full run. Key transforms are not applied;
+/// see [`crate::key_transforms::apply`].
+pub fn extract_with_config(
+    config: &Config,
+    patterns: &[String],
+    ignore_patterns: &[String],
+) -> Result<ExtractionResult> {
+    ExtractRequest::builder()
+        .config(config)
+        .patterns(patterns.iter().cloned())
+        .ignore(ignore_patterns.iter().cloned())
+        .build()
+        .run()
+}
+
+/// Extract keys from multiple files using glob patterns with configurable options.
+///
+/// Kept for existing callers; [`ExtractRequest`] takes the same options by name.
+pub fn extract_from_glob_with_options(
+    patterns: &[String],
+    ignore_patterns: &[String],
+    functions: &[String],
+    extract_from_comments: bool,
+    plural_config: &PluralConfig,
+    trans_components: &[TransComponent],
+    trans_keep_basic_html_nodes_for: &[String],
+    trans_unescape: TransUnescape,
+    use_translation_names: &[UseTranslationName],
+    message_factory_functions: &[String],
+    nesting_prefix: &str,
+    nesting_suffix: &str,
+    nesting_options_separator: &str,
+    interpolation_prefix: &str,
+    interpolation_suffix: &str,
+    follow_symlinks: bool,
+) -> Result<ExtractionResult> {
+    ExtractRequest::builder()
+        .patterns(patterns.iter().cloned())
+        .ignore(ignore_patterns.iter().cloned())
+        .functions(functions.iter().cloned())
+        .extract_from_comments(extract_from_comments)
+        .plural_config(plural_config.clone())
+        .trans_components(trans_components.iter().cloned())
+        .trans_keep_basic_html_nodes_for(trans_keep_basic_html_nodes_for.iter().cloned())
+        .trans_unescape(trans_unescape)
+        .use_translation_names(use_translation_names.iter().cloned())
+        .message_factory_functions(message_factory_functions.iter().cloned())
+        .nesting(nesting_prefix, nesting_suffix, nesting_options_separator)
+        .interpolation(interpolation_prefix, interpolation_suffix)
+        .follow_symlinks(follow_symlinks)
+        .build()
+        .run()
+}
+
+/// Keys produced by plural/context expansion in one place and written as a
+/// literal key in another, one entry per key
+fn find_key_collisions(sources: &[KeySource]) -> Vec<KeyCollision> {
+    let mut literal: HashMap<(Option<&str>, &str), &KeySource> = HashMap::new();
+    // Comments are not expanded like calls, so their keys are never the literal side
+    for source in sources
+        .iter()
+        .filter(|s| s.generated_from.is_none() && !s.from_comment)
+    {
+        literal
+            .entry((source.namespace.as_deref(), source.key.as_str()))
+            .or_insert(source);
+    }
+
+    let mut collisions: BTreeMap<(Option<&str>, &str), KeyCollision> = BTreeMap::new();
+    for generated in sources.iter().filter(|s| s.generated_from.is_some()) {
+        let id = (generated.namespace.as_deref(), generated.key.as_str());
+        if let Some(literal) = literal.get(&id) {
+            collisions.entry(id).or_insert_with(|| KeyCollision {
+                namespace: generated.namespace.clone(),
+                key: generated.key.clone(),
+                generated: generated.clone(),
+                literal: (*literal).clone(),
+            });
+        }
+    }
+    collisions.into_values().collect()
+}
+
+/// Keys with a default value in code and a different one in a comment, one
+/// entry per key with the first differing pair
+fn find_duplicate_keys(sources: &[KeySource]) -> Vec<DuplicateKey> {
+    let mut by_key: BTreeMap<(Option<&str>, &str), Vec<&KeySource>> = BTreeMap::new();
+    for source in sources.iter().filter(|s| s.default_value.is_some()) {
+        by_key
+            .entry((source.namespace.as_deref(), source.key.as_str()))
+            .or_default()
+            .push(source);
+    }
+
+    by_key
+        .into_values()
+        .filter_map(|sources| {
+            let (comments, code): (Vec<&KeySource>, Vec<&KeySource>) =
+                sources.into_iter().partition(|source| source.from_comment);
+            code.iter().find_map(|code| {
+                let comment = comments
+                    .iter()
+                    .find(|comment| comment.default_value != code.default_value)?;
+                Some(DuplicateKey {
+                    namespace: code.namespace.clone(),
+                    key: code.key.clone(),
+                    code: (*code).clone(),
+                    comment: (*comment).clone(),
+                })
+            })
+        })
+        .collect()
+}
+
+/// Unique keys, diagnostics and errors of a deduplicated extraction
+pub type DeduplicatedExtraction = (
+    HashMap<ExtractedKey, ()>,
+    Vec<Diagnostic>,
+    Vec<ExtractionError>,
+);
+
+/// Extract keys with early deduplication using fold/reduce pattern.
+/// This minimizes memory allocation for large codebases with many duplicate keys.
+///
+/// Returns a HashMap of unique keys instead of Vec, reducing O(N) to O(unique_keys).
+pub fn extract_from_glob_deduplicated(
+    patterns: &[String],
+    ignore_patterns: &[String],
+    functions: &[String],
+    plural_config: &PluralConfig,
+) -> Result<DeduplicatedExtraction> {
+    let default_trans_components = vec![TransComponent::Name("Trans".to_string())];
+    let default_trans_keep_basic_html_nodes_for =
+        vec!["br".to_string(), "strong".to_string(), "i".to_string()];
+    let default_use_translation_names =
+        vec![UseTranslationName::Name("useTranslation".to_string())];
+    extract_from_glob_deduplicated_with_options(
+        patterns,
+        ignore_patterns,
+        functions,
+        true,
+        plural_config,
+        &default_trans_components,
+        &default_trans_keep_basic_html_nodes_for,
+        TransUnescape::default(),
+        &default_use_translation_names,
+        &[],
+        "$t(",
+        ")",
+        ",",
+        "{{",
+        "}}",
+        ":",
+        ".",
+    )
+}
+
+/// Extract keys with early deduplication and configurable comment extraction.
+///
+/// Diagnostics are returned at the severity the parser gives them (dynamic keys
+/// at `warn`), followed by one per entry of the error list.
+pub fn extract_from_glob_deduplicated_with_options(
+    patterns: &[String],
+    ignore_patterns: &[String],
+    functions: &[String],
+    extract_from_comments: bool,
+    plural_config: &PluralConfig,
+    trans_components: &[TransComponent],
+    trans_keep_basic_html_nodes_for: &[String],
+    trans_unescape: TransUnescape,
+    use_translation_names: &[UseTranslationName],
+    message_factory_functions: &[String],
+    nesting_prefix: &str,
+    nesting_suffix: &str,
+    nesting_options_separator: &str,
+    interpolation_prefix: &str,
+    interpolation_suffix: &str,
+    ns_separator: &str,
+    key_separator: &str,
+) -> Result<DeduplicatedExtraction> {
+    use rayon::prelude::*;
+
+    let mut all_files: Vec<std::path::PathBuf> = Vec::new();
+    let mut glob_errors: Vec<ExtractionError> = Vec::new();
+    let expanded_patterns: Vec<String> = patterns
+        .iter()
+        .flat_map(|pattern| expand_brace_patterns(pattern))
+        .collect();
+    let ignore_matchers = compile_ignore_patterns(ignore_patterns)?;
+
+    let mut symlink_guard = walk::SymlinkGuard::new(true);
+    for pattern in &expanded_patterns {
+        let matches =
+            glob::glob(pattern).with_context(|| format!("Invalid glob pattern: {}", pattern))?;
+        let base = walk::glob_base(pattern);
+
+        for entry in matches {
+            match entry {
+                Ok(path) => {
+                    if path.is_file()
+                        && !matches_ignore_path(&path, &ignore_matchers)
+                        && symlink_guard.admit(&base, &path).is_ok()
+                    {
+                        all_files.push(path);
+                    }
+                }
+                Err(e) => {
+                    glob_errors.push(ExtractionError::glob(
+                        pattern.clone(),
+                        format!("Glob error: {}", e),
+                    ));
+                }
+            }
+        }
+    }
+
+    // Use fold + reduce for early deduplication during parallel processing
+    // Each thread maintains its own HashSet, then we merge at the end
+    type AccumulatorType = DeduplicatedExtraction;
+
+    let initial: AccumulatorType = (HashMap::new(), Vec::new(), Vec::new());
+    let trans_components = Arc::new(trans_components.to_vec());
+    let trans_keep_basic_html_nodes_for = Arc::new(trans_keep_basic_html_nodes_for.to_vec());
+    let use_translation_names = Arc::new(use_translation_names.to_vec());
+    let message_factory_functions = Arc::new(message_factory_functions.to_vec());
+    let nesting_prefix = Arc::new(nesting_prefix.to_string());
+    let nesting_suffix = Arc::new(nesting_suffix.to_string());
+    let nesting_options_separator = Arc::new(nesting_options_separator.to_string());
+    let interpolation_prefix = Arc::new(interpolation_prefix.to_string());
+    let interpolation_suffix = Arc::new(interpolation_suffix.to_string());
+
+    let (unique_keys, mut diagnostics, mut errors) = all_files
+        .par_iter()
+        .fold(|| initial.clone(), {
+            let trans_components = Arc::clone(&trans_components);
+            let trans_keep_basic_html_nodes_for = Arc::clone(&trans_keep_basic_html_nodes_for);
+            let use_translation_names = Arc::clone(&use_translation_names);
+            let message_factory_functions = Arc::clone(&message_factory_functions);
+            let nesting_prefix = Arc::clone(&nesting_prefix);
+            let nesting_suffix = Arc::clone(&nesting_suffix);
+            let nesting_options_separator = Arc::clone(&nesting_options_separator);
+            let interpolation_prefix = Arc::clone(&interpolation_prefix);
+            let interpolation_suffix = Arc::clone(&interpolation_suffix);
+            move |mut acc: AccumulatorType, path: &std::path::PathBuf| {
+                match extract_from_file_with_warnings(
+                    path,
+                    functions,
+                    &trans_components,
+                    &trans_keep_basic_html_nodes_for,
+                    trans_unescape,
+                    &use_translation_names,
+                    &message_factory_functions,
+                    &[],
+                    extract_from_comments,
+                    plural_config,
+                    &nesting_prefix,
+                    &nesting_suffix,
+                    &nesting_options_separator,
+                    &interpolation_prefix,
+                    &interpolation_suffix,
+                    ns_separator,
+                    key_separator,
+                    None,
+                    PathStyle::default(),
+                ) {
+                    Ok(FileExtraction {
+                        keys,
+                        mut diagnostics,
+                        ..
+                    }) => {
+                        acc.1.append(&mut diagnostics);
+                        // Insert into HashSet for deduplication
+                        for key in keys {
+                            acc.0.insert(key, ());
+                        }
+                    }
+                    Err(e) => {
+                        acc.2.push(ExtractionError::from_file_error(
+                            paths::display(path, PathStyle::default()),
+                            &e,
+                        ));
+                    }
+                }
+                acc
+            }
+        })
+        .reduce(
+            || initial.clone(),
+            |mut a, b| {
+                // Merge HashMaps from different threads
+                a.0.extend(b.0);
+                a.1.extend(b.1);
+                a.2.extend(b.2);
+                a
+            },
+        );
+
+    // Add glob errors
+    errors.extend(glob_errors);
+    diagnostics.extend(errors.iter().map(Diagnostic::from_error));
+
+    Ok((unique_keys, diagnostics, errors))
+}
+
+pub fn expand_brace_patterns(pattern: &str) -> Vec<String> {
+    let bytes = pattern.as_bytes();
+    let mut start = None;
+    let mut depth = 0usize;
+
+    for (idx, b) in bytes.iter().enumerate() {
+        if *b == b'{' {
+            if depth == 0 {
+                start = Some(idx);
+            }
+            depth += 1;
+            continue;
+        }
+        if *b == b'}' {
+            if depth == 0 {
+                continue;
+            }
+            depth -= 1;
+            if depth == 0 {
+                let Some(open_idx) = start else {
+                    break;
+                };
+                let prefix = &pattern[..open_idx];
+                let inner = &pattern[open_idx + 1..idx];
+                let suffix = &pattern[idx + 1..];
+
+                let options = split_brace_options(inner);
+                if options.len() <= 1 {
+                    return vec![pattern.to_string()];
+                }
+
+                let suffix_expanded = expand_brace_patterns(suffix);
+                let mut out = Vec::new();
+                for option in options {
+                    for tail in &suffix_expanded {
+                        out.push(format!("{}{}{}", prefix, option, tail));
+                    }
+                }
+                return out;
+            }
+        }
+    }
+
+    vec![pattern.to_string()]
+}
+
+fn split_brace_options(inner: &str) -> Vec<&str> {
+    let mut out = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0usize;
+
+    for (idx, ch) in inner.char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                out.push(&inner[start..idx]);
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+
+    out.push(&inner[start..]);
+    out
+}
+
+/// Check whether a path matches any of the configured input globs (brace patterns included)
+pub fn matches_input_patterns(path: &Path, patterns: &[String]) -> bool {
+    let path = path.strip_prefix("./").unwrap_or(path);
+    patterns
+        .iter()
+        .flat_map(|pattern| expand_brace_patterns(pattern.strip_prefix("./").unwrap_or(pattern)))
+        .filter_map(|pattern| Pattern::new(&pattern).ok())
+        .any(|pattern| pattern.matches_path(path))
+}
+
+/// Files matched by `filters` that `input` also covers, as escaped glob patterns
+/// for a `--filter-file` run
+pub fn filter_input_files(input: &[String], filters: &[String]) -> Result<Vec<String>> {
+    let mut files = std::collections::BTreeSet::new();
+    for pattern in filters
+        .iter()
+        .flat_map(|pattern| expand_brace_patterns(pattern))
+    {
+        // `dir/**` means every file below `dir`, as in fast-glob
+        let pattern = if pattern.ends_with("**") {
+            format!("{}/*", pattern)
+        } else {
+            pattern
+        };
+        let paths = glob::glob(&pattern)
+            .with_context(|| format!("Invalid --filter-file pattern: {}", pattern))?;
+        for path in paths.flatten() {
+            if path.is_file() && matches_input_patterns(&path, input) {
+                files.insert(Pattern::escape(&path.to_string_lossy()));
+            }
+        }
+    }
+    Ok(files.into_iter().collect())
+}
+
+/// Source files matched by `input` and not excluded by `ignore`, sorted by path.
+/// Symlinks are handled as in extraction (see [`walk::SymlinkGuard`]).
+pub fn input_files(
+    input: &[String],
+    ignore: &[String],
+    follow_symlinks: bool,
+) -> Result<Vec<PathBuf>> {
+    let ignore = compile_ignore_patterns(ignore)?;
+    let mut symlink_guard = walk::SymlinkGuard::new(follow_symlinks);
+    let mut files = std::collections::BTreeSet::new();
+    for pattern in input
+        .iter()
+        .flat_map(|pattern| expand_brace_patterns(pattern))
+    {
+        let paths =
+            glob::glob(&pattern).with_context(|| format!("Invalid input pattern: {}", pattern))?;
+        let base = walk::glob_base(&pattern);
+        for path in paths.flatten() {
+            if path.is_file()
+                && !matches_ignore_path(&path, &ignore)
+                && symlink_guard.admit(&base, &path).is_ok()
+            {
+                files.insert(path);
+            }
+        }
+    }
+    Ok(files.into_iter().collect())
+}
+
+fn matches_ignore_path(path: &Path, patterns: &[Pattern]) -> bool {
+    patterns.iter().any(|pattern| pattern.matches_path(path))
+}
+
+fn compile_ignore_patterns(patterns: &[String]) -> Result<Vec<Pattern>> {
+    let mut compiled = Vec::new();
+    for pattern in patterns {
+        let matcher = Pattern::new(pattern)
+            .with_context(|| format!("Invalid ignore glob pattern: {}", pattern))?;
+        compiled.push(matcher);
+    }
+    Ok(compiled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_extract_simple_t_call() {
+        let source = r#"
+            const text = t('hello.world');
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "hello.world");
+        assert_eq!(keys[0].namespace, None);
+    }
+
+    #[test]
+    fn test_extract_i18n_t_call() {
+        let source = r#"
+            const text = i18n.t('greeting');
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["i18n.t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "greeting");
+    }
+
+    #[test]
+    fn test_extract_with_namespace() {
+        let source = r#"
+            const text = t('common:button.submit');
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "button.submit");
+        assert_eq!(keys[0].namespace, Some("common".to_string()));
+    }
+
+    #[test]
+    fn test_extract_multiple_calls() {
+        let source = r#"
+            const a = t('key1');
+            const b = t('key2');
+            const c = i18n.t('key3');
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string(), "i18n.t".to_string()])
+            .unwrap();
+
+        assert_eq!(keys.len(), 3);
+    }
+
+    #[test]
+    fn test_tsx_jsx_support() {
+        let source = r#"
+            function Component() {
+                return <div>{t('jsx.key')}</div>;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "jsx.key");
+    }
+
+    #[test]
+    fn test_trans_component() {
+        let source = r#"
+            function Component() {
+                return <Trans i18nKey="hello">World</Trans>;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "hello");
+    }
+
+    #[test]
+    fn test_trans_with_namespace() {
+        let source = r#"
+            function Component() {
+                return <Trans i18nKey="common:greeting" />;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "greeting");
+        assert_eq!(keys[0].namespace, Some("common".to_string()));
+    }
+
+    #[test]
+    fn test_trans_with_defaults() {
+        let source = r#"
+            function Component() {
+                return <Trans i18nKey="welcome" defaults="Hello there!" />;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "welcome");
+        assert_eq!(keys[0].default_value, Some("Hello there!".into()));
+    }
+
+    #[test]
+    fn test_plurals_with_count() {
+        let source = r#"
+            const text = t('apple', { count: 5 });
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 2);
+        assert!(keys.iter().any(|k| k.key == "apple_one"));
+        assert!(keys.iter().any(|k| k.key == "apple_other"));
+    }
+
+    #[test]
+    fn test_plurals_with_count_shorthand() {
+        let source = r#"
+            const count = 5;
+            const text = t('item', { count });
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 2);
+        assert!(keys.iter().any(|k| k.key == "item_one"));
+        assert!(keys.iter().any(|k| k.key == "item_other"));
+    }
+
+    #[test]
+    fn test_context() {
+        let source = r#"
+            const text = t('friend', { context: 'male' });
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "friend_male");
+    }
+
+    #[test]
+    fn test_plurals_with_context() {
+        let source = r#"
+            const text = t('friend', { count: 2, context: 'female' });
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 2);
+        assert!(keys.iter().any(|k| k.key == "friend_female_one"));
+        assert!(keys.iter().any(|k| k.key == "friend_female_other"));
+    }
+
+    #[test]
+    fn test_template_literal_simple() {
+        let source = r#"
+            const text = t(`hello.world`);
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "hello.world");
+    }
+
+    #[test]
+    fn test_template_literal_with_namespace() {
+        let source = r#"
+            const text = t(`common:button.save`);
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "button.save");
+        assert_eq!(keys[0].namespace, Some("common".to_string()));
+    }
+
+    #[test]
+    fn test_template_literal_with_interpolation_ignored() {
+        let source = r#"
+            const key = 'dynamic';
+            const text = t(`hello.${key}`);
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        // Template literals with interpolations should be skipped
+        assert_eq!(keys.len(), 0);
+    }
+
+    #[test]
+    fn test_template_literal_dynamic_warning() {
+        let source = r#"
+            const id = 123;
+            const text = t(`key_${id}`);
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        // Dynamic template literals should be skipped (no keys extracted)
+        assert_eq!(keys.len(), 0);
+
+        // Note: The warning is printed to stderr via eprintln! in warn_dynamic_template_literal.
+        // The warning format is: "Warning: Dynamic template literal found at test.ts:3:XX.
+        // Translation key extraction skipped. Consider using i18next-extract-disable-line if intentional."
+        // This is verified by manual testing and code review.
+    }
+
+    #[test]
+    fn test_trans_children_as_key() {
+        let source = r#"
+            function Component() {
+                return <Trans>Hello World</Trans>;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "Hello World");
+        assert_eq!(keys[0].default_value, Some("Hello World".into()));
+    }
+
+    #[test]
+    fn test_trans_children_with_html() {
+        let source = r#"
+            function Component() {
+                return <Trans>Hello <strong>World</strong></Trans>;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert!(keys[0].key.contains("Hello"));
+        assert!(keys[0].key.contains("<strong>"));
+    }
+
+    #[test]
+    fn test_trans_children_respects_trans_keep_basic_html_nodes_for() {
+        let source = r#"
+            function Component() {
+                return <Trans>Hello <strong>World</strong></Trans>;
+            }
+        "#;
+        let plural_config = PluralConfig::default();
+        let trans_components = vec![TransComponent::Name("Trans".to_string())];
+        let hooks = vec![UseTranslationName::Name("useTranslation".to_string())];
+        let keep_nodes = vec!["br".to_string(), "i".to_string()]; // strong is intentionally excluded
+
+        let FileExtraction { keys, .. } = extract_from_source_with_warnings(
+            source,
+            "test.tsx",
+            &["t".to_string()],
+            &trans_components,
+            &keep_nodes,
+            TransUnescape::default(),
+            &hooks,
+            &[],
+            &[],
+            true,
+            &plural_config,
+            "$t(",
+            ")",
+            ",",
+            "{{",
+            "}}",
+            ":",
+            ".",
+            PathStyle::default(),
+        )
+        .unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert!(keys[0].key.contains("Hello"));
+        assert!(!keys[0].key.contains("<strong>"));
+        assert!(keys[0].key.contains("World"));
+    }
+
+    #[test]
+    fn test_trans_children_html_entities_follow_trans_unescape() {
+        let source = r#"
+            function Component() {
+                return (
+                    <>
+                        <Trans i18nKey="terms">Terms &amp; Conditions</Trans>
+                        <Trans>Save&nbsp;all</Trans>
+                    </>
+                );
+            }
+        "#;
+        let extract = |trans_unescape: TransUnescape| -> Vec<(String, Option<String>)> {
+            extract_from_source_with_warnings(
+                source,
+                "test.tsx",
+                &["t".to_string()],
+                &[TransComponent::Name("Trans".to_string())],
+                &[],
+                trans_unescape,
+                &[],
+                &[],
+                &[],
+                true,
+                &PluralConfig::default(),
+                "$t(",
+                ")",
+                ",",
+                "{{",
+                "}}",
+                ":",
+                ".",
+                PathStyle::default(),
+            )
+            .unwrap()
+            .keys
+            .into_iter()
+            .map(|k| {
+                (
+                    k.key,
+                    k.default_value.map(|v| v.as_str().unwrap().to_string()),
+                )
+            })
+            .collect()
+        };
+        let pair = |key: &str, default: &str| (key.to_string(), Some(default.to_string()));
+
+        // Decoded like JSX by default
+        assert_eq!(
+            extract(TransUnescape::default()),
+            vec![
+                pair("terms", "Terms & Conditions"),
+                pair("Save\u{a0}all", "Save\u{a0}all"),
+            ]
+        );
+        // Kept as written, for `shouldUnescape` rendering
+        assert_eq!(
+            extract(TransUnescape {
+                defaults: false,
+                keys: false,
+            }),
+            vec![
+                pair("terms", "Terms &amp; Conditions"),
+                pair("Save&nbsp;all", "Save&nbsp;all"),
+            ]
+        );
+        // Defaults decoded, keys as written
+        assert_eq!(
+            extract(TransUnescape {
+                defaults: true,
+                keys: false,
+            })[1],
+            pair("Save&nbsp;all", "Save\u{a0}all")
+        );
+    }
+
+    #[test]
+    fn test_disable_enable_comments_skip_regions() {
+        let source = r#"
+            t('before');
+            /* i18next-extract-disable */
+            function generated() {
+                t('generated.one');
+                return <Trans i18nKey="generated.two" />;
+            }
+            /* i18next-extract-enable */
+            t('after');
+            function Component() {
+                // i18next-extract-disable
+                t('rest.of.block');
+                if (ready) { t('nested.block'); }
+            }
+            function Other() {
+                t('other.block');
+                /* i18next-extract-enable */
+            }
+            t('top.level');
+            // i18next-extract-disable
+            t('rest.of.file');
+        "#;
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+        let keys: Vec<&str> = keys.iter().map(|k| k.key.as_str()).collect();
+        assert_eq!(keys, vec!["before", "after", "other.block", "top.level"]);
+    }
+
+    #[test]
+    fn test_skipped_calls_record_the_reason_and_span() {
+        let source = "// i18next-extract-disable-next-line
+t('one.key');
+t(`two.${id}`);
+const { t: tx } = useTranslation(ns);
+tx('three.key');
+/* i18next-extract-disable */
+<Trans i18nKey=\"four.key\" />;
+t('five.key');
+";
+        let extraction = ExtractRequest::builder()
+            .functions(["t"])
+            .build()
+            .run_source(source, Path::new("test.tsx"))
+            .unwrap();
+        let skipped: Vec<(usize, usize, Option<&str>, &SkipReason)> = extraction
+            .skipped_calls
+            .iter()
+            .map(|skip| (skip.line, skip.column, skip.key.as_deref(), &skip.reason))
+            .collect();
+        assert_eq!(
+            skipped,
+            vec![
+                (
+                    2,
+                    1,
+                    Some("one.key"),
+                    &SkipReason::DisabledComment("i18next-extract-disable-next-line".to_string())
+                ),
+                (3, 1, None, &SkipReason::DynamicKey("two.*".to_string())),
+                (5, 1, Some("three.key"), &SkipReason::UnresolvedScope),
+                (7, 1, Some("four.key"), &SkipReason::DisabledRegion),
+                (8, 1, Some("five.key"), &SkipReason::DisabledRegion),
+            ]
+        );
+        let dynamic = &extraction.skipped_calls[1];
+        assert_eq!(&source[dynamic.span.clone()], "t(`two.${id}`)");
+        assert!(extraction.files[0]
+            .1
+            .iter()
+            .any(|key| key.key == "three.key"));
+    }
+
+    #[test]
+    fn test_custom_trans_component_attribute_names() {
+        let source = r#"
+            function Component() {
+                return (
+                    <>
+                        <T k="design.title" namespace="ui" fallback="Title" />
+                        <T i18nKey="ignored.key">Ignored</T>
+                        <Trans i18nKey="plain.key">Plain</Trans>
+                    </>
+                );
+            }
+        "#;
+        let plural_config = PluralConfig::default();
+        let trans_components = vec![
+            TransComponent::Name("Trans".to_string()),
+            TransComponent::Detailed(crate::config::TransComponentDetails {
+                name: "T".to_string(),
+                key_attr: "k".to_string(),
+                ns_attr: "namespace".to_string(),
+                default_attr: "fallback".to_string(),
+            }),
+        ];
+        let hooks = vec![UseTranslationName::Name("useTranslation".to_string())];
+        let keep_nodes = vec!["br".to_string(), "strong".to_string(), "i".to_string()];
+
+        let FileExtraction { keys, .. } = extract_from_source_with_warnings(
+            source,
+            "test.tsx",
+            &["t".to_string()],
+            &trans_components,
+            &keep_nodes,
+            TransUnescape::default(),
+            &hooks,
+            &[],
+            &[],
+            false,
+            &plural_config,
+            "$t(",
+            ")",
+            ",",
+            "{{",
+            "}}",
+            ":",
+            ".",
+            PathStyle::default(),
+        )
+        .unwrap();
+
+        let design = keys
+            .iter()
+            .find(|k| k.key == "design.title")
+            .expect("custom attribute key");
+        assert_eq!(design.namespace, Some("ui".to_string()));
+        assert_eq!(design.default_value, Some("Title".into()));
+        assert!(keys.iter().any(|k| k.key == "plain.key"));
+        assert!(!keys.iter().any(|k| k.key == "ignored.key"));
+    }
+
+    #[test]
+    fn test_trans_children_uses_custom_interpolation_delimiters() {
+        let source = r#"
+            function Component({ name }) {
+                return <Trans>Hello {name}</Trans>;
+            }
+        "#;
+        let plural_config = PluralConfig::default();
+        let trans_components = vec![TransComponent::Name("Trans".to_string())];
+        let hooks = vec![UseTranslationName::Name("useTranslation".to_string())];
+        let keep_nodes = vec!["br".to_string(), "strong".to_string(), "i".to_string()];
+
+        let FileExtraction { keys, .. } = extract_from_source_with_warnings(
+            source,
+            "test.tsx",
+            &["t".to_string()],
+            &trans_components,
+            &keep_nodes,
+            TransUnescape::default(),
+            &hooks,
+            &[],
+            &[],
+            true,
+            &plural_config,
+            "$t(",
+            ")",
+            ",",
+            "<<",
+            ">>",
+            ":",
+            ".",
+            PathStyle::default(),
+        )
+        .unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert!(keys[0].key.contains("<<name>>"));
+    }
+
+    #[test]
+    fn test_trans_ns_attribute() {
+        let source = r#"
+            function Component() {
+                return <Trans ns="common" i18nKey="greeting">Hello</Trans>;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "greeting");
+        assert_eq!(keys[0].namespace, Some("common".to_string()));
+    }
+
+    #[test]
+    fn test_trans_count_attribute() {
+        let source = r#"
+            function Component() {
+                return <Trans i18nKey="item" count={5}>items</Trans>;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 2);
+        assert!(keys.iter().any(|k| k.key == "item_one"));
+        assert!(keys.iter().any(|k| k.key == "item_other"));
+    }
+
+    #[test]
+    fn test_trans_children_with_ns_and_count() {
+        let source = r#"
+            function Component() {
+                return <Trans ns="shop" i18nKey="product" count={3}>products</Trans>;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 2);
+        assert!(keys
+            .iter()
+            .any(|k| k.key == "product_one" && k.namespace == Some("shop".to_string())));
+        assert!(keys
+            .iter()
+            .any(|k| k.key == "product_other" && k.namespace == Some("shop".to_string())));
+    }
+
+    #[test]
+    fn test_trans_context_attribute() {
+        let source = r#"
+            function Component() {
+                return <Trans i18nKey="friend" context="male">Male friend</Trans>;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "friend_male");
+    }
+
+    #[test]
+    fn test_trans_context_with_count() {
+        let source = r#"
+            function Component() {
+                return <Trans i18nKey="friend" context="female" count={2}>Female friends</Trans>;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 2);
+        assert!(keys.iter().any(|k| k.key == "friend_female_one"));
+        assert!(keys.iter().any(|k| k.key == "friend_female_other"));
+    }
+
+    #[test]
+    fn test_trans_t_options_context_and_count() {
+        let source = r#"
+            function Component() {
+                return <Trans i18nKey="friend" tOptions={{ context: 'male', count: n }}>Friend</Trans>;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 2);
+        assert!(keys.iter().any(|k| k.key == "friend_male_one"));
+        assert!(keys.iter().any(|k| k.key == "friend_male_other"));
+    }
+
+    #[test]
+    fn test_trans_t_options_ordinal_and_ns() {
+        let source = r#"
+            function Component() {
+                return <Trans i18nKey="place" tOptions={{ count: n, ordinal: true, ns: 'ranking' }}>Place</Trans>;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+
+        assert!(keys.iter().any(|k| k.key == "place_ordinal_one"));
+        assert!(keys.iter().any(|k| k.key == "place_ordinal_other"));
+        assert!(keys
+            .iter()
+            .all(|k| k.namespace == Some("ranking".to_string())));
+    }
+
+    #[test]
+    fn test_trans_count_prop_with_ordinal_t_option_uses_ordinal_rules() {
+        let source = r#"
+            const a = <Trans i18nKey="place" count={n} tOptions={{ ordinal: true }}>Place</Trans>;
+            const b = t('step', { count: n, ordinal: true });
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+        let names: Vec<&str> = keys.iter().map(|k| k.key.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "place_ordinal_few",
+                "place_ordinal_one",
+                "place_ordinal_other",
+                "place_ordinal_two",
+                "step_ordinal_few",
+                "step_ordinal_one",
+                "step_ordinal_other",
+                "step_ordinal_two",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trans_ns_array_uses_first_entry() {
+        let source = r#"
+            function Component() {
+                return <Trans ns={["common", "glossary"]} i18nKey="term">Term</Trans>;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "term");
+        assert_eq!(keys[0].namespace, Some("common".to_string()));
+    }
+
+    #[test]
+    fn test_trans_object_children_become_placeholders() {
+        let source = r#"
+            function Component() {
+                return <Trans i18nKey="greeting" values={{ name }}>Hello {{ name }} and welcome</Trans>;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(
+            keys[0].default_value,
+            Some("Hello {{name}} and welcome".into())
+        );
+    }
+
+    #[test]
+    fn test_trans_context_with_ns() {
+        let source = r#"
+            function Component() {
+                return <Trans ns="common" i18nKey="user" context="admin">Admin user</Trans>;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "user_admin");
+        assert_eq!(keys[0].namespace, Some("common".to_string()));
+    }
+
+    #[test]
+    fn test_trans_dynamic_context_ternary() {
+        let source = r#"
+            function Component({ isMale }) {
+                return <Trans i18nKey="friend" context={isMale ? 'male' : 'female'}>Friend</Trans>;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+        assert!(keys.iter().any(|k| k.key == "friend_male"));
+        assert!(keys.iter().any(|k| k.key == "friend_female"));
+        assert!(keys.iter().any(|k| k.key == "friend"));
+    }
+
+    #[test]
+    fn test_trans_dynamic_context_with_count() {
+        let source = r#"
+            function Component({ count, variant }) {
+                return (
+                    <Trans i18nKey="fruit" count={count} context={variant ? 'ripe' : 'fresh'}>
+                        Fruit
+                    </Trans>
+                );
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+        assert!(keys.iter().any(|k| k.key == "fruit_ripe_one"));
+        assert!(keys.iter().any(|k| k.key == "fruit_ripe_other"));
+        assert!(keys.iter().any(|k| k.key == "fruit_fresh_one"));
+        assert!(keys.iter().any(|k| k.key == "fruit_fresh_other"));
+        assert!(keys.iter().any(|k| k.key == "fruit_one"));
+        assert!(keys.iter().any(|k| k.key == "fruit_other"));
+    }
+
+    #[test]
+    fn test_use_translation_with_namespace() {
+        let source = r#"
+            function Component() {
+                const { t } = useTranslation('common');
+                return <div>{t('greeting')}</div>;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "greeting");
+        assert_eq!(keys[0].namespace, Some("common".to_string()));
+    }
+
+    #[test]
+    fn test_use_translation_with_key_prefix() {
+        let source = r#"
+            function Component() {
+                const { t } = useTranslation('ns', { keyPrefix: 'user' });
+                return <div>{t('name')}</div>;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "user.name");
+        assert_eq!(keys[0].namespace, Some("ns".to_string()));
+    }
+
+    #[test]
+    fn test_use_translation_key_prefix_only() {
+        let source = r#"
+            function Component() {
+                const { t } = useTranslation({ keyPrefix: 'settings' });
+                return <div>{t('theme')}</div>;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "settings.theme");
+    }
+
+    #[test]
+    fn test_use_translation_array_destructure() {
+        let source = r#"
+            function Component() {
+                const [t] = useTranslation('common');
+                return <div>{t('hello')}</div>;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "hello");
+        assert_eq!(keys[0].namespace, Some("common".to_string()));
+    }
+
+    #[test]
+    fn test_use_translation_alias() {
+        let source = r#"
+            function Component() {
+                const { t: translate } = useTranslation('common');
+                return <div>{translate('world')}</div>;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["translate".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "world");
+        assert_eq!(keys[0].namespace, Some("common".to_string()));
+    }
+
+    #[test]
+    fn test_translation_function_alias_tracking() {
+        let source = r#"
+            function Component() {
+                const translate = t;
+                return <div>{translate('hello.alias')}</div>;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+        assert!(keys.iter().any(|k| k.key == "hello.alias"));
+    }
+
+    #[test]
+    fn test_use_translation_alias_inherits_scope() {
+        let source = r#"
+            function Component() {
+                const { t } = useTranslation('common', { keyPrefix: 'user' });
+                const translate = t;
+                return <div>{translate('name')}</div>;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+        assert!(keys
+            .iter()
+            .any(|k| k.namespace.as_deref() == Some("common") && k.key == "user.name"));
+    }
+
+    #[test]
+    fn test_get_fixed_t_with_namespace() {
+        let source = r#"
+            const t = i18n.getFixedT('en', 'common');
+            const text = t('greeting');
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "greeting");
+        assert_eq!(keys[0].namespace, Some("common".to_string()));
+    }
+
+    #[test]
+    fn test_unresolved_dynamic_context_emits_warning_and_falls_back() {
+        let source = r#"
+            function Component(user) {
+                return t('friend', { context: user.gender });
+            }
+        "#;
+        let plural_config = PluralConfig::default();
+        let trans_components = vec![TransComponent::Name("Trans".to_string())];
+        let keep_nodes = vec!["br".to_string(), "strong".to_string(), "i".to_string()];
+        let hooks = vec![UseTranslationName::Name("useTranslation".to_string())];
+
+        let FileExtraction {
+            keys, diagnostics, ..
+        } = extract_from_source_with_warnings(
+            source,
+            "test.ts",
+            &["t".to_string()],
+            &trans_components,
+            &keep_nodes,
+            TransUnescape::default(),
+            &hooks,
+            &[],
+            &[],
+            true,
+            &plural_config,
+            "$t(",
+            ")",
+            ",",
+            "{{",
+            "}}",
+            ":",
+            ".",
+            PathStyle::default(),
+        )
+        .unwrap();
+
+        assert!(diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.kind == DiagnosticKind::DynamicContext));
+        assert!(keys.iter().any(|k| k.key == "friend"));
+    }
+
+    #[test]
+    fn test_message_factory_descriptors() {
+        let source = r#"
+            const messages = defineMessages({
+                title: { id: 'app.title', defaultMessage: 'Welcome' },
+                greeting: { id: 'common:app.greeting', description: 'Shown on login' },
+                notADescriptor: 'ignored',
+            });
+            const single = defineMessage({ id: 'app.single', defaultMessage: 'One' });
+            const other = somethingElse({ x: { id: 'skipped' } });
+        "#;
+        let plural_config = PluralConfig::default();
+
+        let FileExtraction { keys, .. } = extract_from_source_with_warnings(
+            source,
+            "messages.ts",
+            &["t".to_string()],
+            &[],
+            &[],
+            TransUnescape::default(),
+            &[],
+            &["defineMessages".to_string(), "defineMessage".to_string()],
+            &[],
+            false,
+            &plural_config,
+            "$t(",
+            ")",
+            ",",
+            "{{",
+            "}}",
+            ":",
+            ".",
+            PathStyle::default(),
+        )
+        .unwrap();
+
+        let found: Vec<(Option<&str>, &str, Option<&str>)> = keys
+            .iter()
+            .map(|k| {
+                (
+                    k.namespace.as_deref(),
+                    k.key.as_str(),
+                    k.default_value.as_ref().and_then(DefaultValue::as_str),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (None, "app.title", Some("Welcome")),
+                (Some("common"), "app.greeting", None),
+                (None, "app.single", Some("One")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_key_maps_and_key_producing_functions() {
+        let source = r#"
+            const labels = Object.freeze({ save: t('label.save') });
+            const keys = { open: 'label.open', close: k('common:label.close') } as const;
+            const nav = {
+                home: { title: 'nav.home.title' },
+                about: { title: 'nav.about.title' },
+            } satisfies Record<string, { title: string }>;
+            tFromKey(keys.open, { defaultValue: 'Open' });
+            tFromKey(nav.home['title']);
+            tFromKey(nav[page].title);
+            tFromKey(unknown.key);
+        "#;
+        let plural_config = PluralConfig::default();
+
+        let FileExtraction { keys, .. } = extract_from_source_with_warnings(
+            source,
+            "labels.ts",
+            &["t".to_string(), "tFromKey".to_string()],
+            &[],
+            &[],
+            TransUnescape::default(),
+            &[],
+            &[],
+            &["k".to_string()],
+            false,
+            &plural_config,
+            "$t(",
+            ")",
+            ",",
+            "{{",
+            "}}",
+            ":",
+            ".",
+            PathStyle::default(),
+        )
+        .unwrap();
+
+        let found: Vec<(Option<&str>, &str, Option<&str>)> = keys
+            .iter()
+            .map(|k| {
+                (
+                    k.namespace.as_deref(),
+                    k.key.as_str(),
+                    k.default_value.as_ref().and_then(DefaultValue::as_str),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (None, "label.save", None),
+                (Some("common"), "label.close", None),
+                (None, "label.open", Some("Open")),
+                (None, "nav.home.title", None),
+                (None, "nav.home.title", None),
+                (None, "nav.about.title", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_namespace_directive_sets_the_file_namespace() {
+        let source = r#"
+            /* i18next-extract-namespace: checkout */
+            import { useTranslation } from 'react-i18next';
+            const title = t('title');
+            const save = t('common:save');
+            function Component() {
+                const { t: tf } = useTranslation('forms');
+                tf('label');
+                return <Trans i18nKey="summary" />;
+            }
+            // i18next-extract-namespace: ignored
+        "#;
+        let keys = extract_from_source(source, "checkout.tsx", &["t".to_string()]).unwrap();
+        let found: Vec<(Option<&str>, &str)> = keys
+            .iter()
+            .map(|k| (k.namespace.as_deref(), k.key.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (Some("checkout"), "title"),
+                (Some("common"), "save"),
+                (Some("forms"), "label"),
+                (Some("checkout"), "summary"),
+            ]
+        );
+
+        let source = "const title = t('title');\n// i18next-extract-namespace: late\n";
+        let keys = extract_from_source(source, "late.ts", &["t".to_string()]).unwrap();
+        assert_eq!(keys[0].namespace, None);
+    }
+
+    #[test]
+    fn test_dev_only_keys_go_to_dev_namespace() {
+        let source = r#"
+            const title = t('debug.panel.title', { devOnly: true });
+            const items = t('debug.items', { devOnly: true, count: 2 });
+            // i18next-dev-only
+            const flag = t('common:debug.flag');
+            const trace = t('debug.trace'); // i18next-dev-only
+            const shown = t('app.title', { devOnly: false });
+        "#;
+        let keys = extract_from_source(source, "debug.ts", &["t".to_string()]).unwrap();
+        let found: Vec<(Option<&str>, &str)> = keys
+            .iter()
+            .map(|k| (k.namespace.as_deref(), k.key.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (Some(DEV_NAMESPACE), "debug.panel.title"),
+                (Some(DEV_NAMESPACE), "debug.items_one"),
+                (Some(DEV_NAMESPACE), "debug.items_other"),
+                (Some(DEV_NAMESPACE), "debug.flag"),
+                (Some(DEV_NAMESPACE), "debug.trace"),
+                (None, "app.title"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_callees_with_type_args_optional_calls_and_non_null() {
+        let source = r#"
+            const a = t<'common'>('generic.key');
+            const b = props.t?.('optional.call');
+            const c = i18n?.t('optional.member');
+            const d = t!('non.null');
+            const e = i18n!.t('non.null.member');
+            const f = (t as TFunction)<'ns'>('as.key');
+            const g = props.t?.<'ns'>('optional.generic', { count: 1 });
+            const h = other?.('ignored');
+            const i = t('const.key' as const);
+        "#;
+        let functions = ["t".to_string(), "props.t".to_string(), "i18n.t".to_string()];
+        let keys = extract_from_source(source, "app.ts", &functions).unwrap();
+        let found: Vec<&str> = keys.iter().map(|k| k.key.as_str()).collect();
+        assert_eq!(
+            found,
+            vec![
+                "generic.key",
+                "optional.call",
+                "optional.member",
+                "non.null",
+                "non.null.member",
+                "as.key",
+                "optional.generic_one",
+                "optional.generic_other",
+                "const.key",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dynamic_template_literal_records_key_pattern() {
+        let source = r#"
+            function Badge(state, id) {
+                return [t(`status.${state}`), t(`items.${id}.title`), t(`${state}`)];
+            }
+        "#;
+        let plural_config = PluralConfig::default();
+        let trans_components = vec![TransComponent::Name("Trans".to_string())];
+        let hooks = vec![UseTranslationName::Name("useTranslation".to_string())];
+
+        let extraction = extract_from_source_with_warnings(
+            source,
+            "badge.ts",
+            &["t".to_string()],
+            &trans_components,
+            &[],
+            TransUnescape::default(),
+            &hooks,
+            &[],
+            &[],
+            false,
+            &plural_config,
+            "$t(",
+            ")",
+            ",",
+            "{{",
+            "}}",
+            ":",
+            ".",
+            PathStyle::default(),
+        )
+        .unwrap();
+
+        // Recorded at `warn`; the run applies the configured severity
+        assert_eq!(extraction.diagnostics.len(), 3);
+        assert!(extraction
+            .diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.kind == DiagnosticKind::DynamicKey));
+        let patterns: Vec<(&str, usize)> = extraction
+            .dynamic_keys
+            .iter()
+            .map(|d| (d.pattern.as_str(), d.line))
+            .collect();
+        assert_eq!(patterns, vec![("status.*", 3), ("items.*.title", 3)]);
+        assert_eq!(extraction.dynamic_keys[0].file_path, "badge.ts");
+    }
+
+    #[test]
+    fn test_plural_variants_colliding_with_literal_keys_are_reported() {
+        let source = r#"
+            const a = t('item', { count: n });
+            const b = t('item_other');
+            const c = t('friend', { context: 'male' });
+            const d = <Trans i18nKey="friend_male" />;
+            const e = t('list', { returnObjects: true });
+            const f = t('list.*');
+        "#;
+        let plural_config = PluralConfig::default();
+        let trans_components = vec![TransComponent::Name("Trans".to_string())];
+        let hooks = vec![UseTranslationName::Name("useTranslation".to_string())];
+
+        let extraction = extract_from_source_with_warnings(
+            source,
+            "list.tsx",
+            &["t".to_string()],
+            &trans_components,
+            &[],
+            TransUnescape::default(),
+            &hooks,
+            &[],
+            &[],
+            false,
+            &plural_config,
+            "$t(",
+            ")",
+            ",",
+            "{{",
+            "}}",
+            ":",
+            ".",
+            PathStyle::default(),
+        )
+        .unwrap();
+
+        let collisions = find_key_collisions(&extraction.key_sources);
+        let summary: Vec<(&str, Option<&str>, usize, usize)> = collisions
+            .iter()
+            .map(|c| {
+                (
+                    c.key.as_str(),
+                    c.generated.generated_from.as_deref(),
+                    c.generated.line,
+                    c.literal.line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("friend_male", Some("friend"), 4, 5),
+                ("item_other", Some("item"), 2, 3),
+            ]
+        );
+        assert_eq!(collisions[0].literal.file_path, "list.tsx");
+    }
+
+    #[test]
+    fn test_keys_in_code_and_comments_with_different_defaults_are_reported() {
+        let source = r#"
+            const a = t('title', { defaultValue: 'Title' });
+            // t('title', 'Heading')
+            const b = t('same', { defaultValue: 'Same' });
+            /* t('same', { defaultValue: 'Same' }) */
+            const c = t('item', { count: n });
+            // t('item', { count: n })
+        "#;
+        let plural_config = PluralConfig::default();
+
+        let extraction = extract_from_source_with_warnings(
+            source,
+            "page.ts",
+            &["t".to_string()],
+            &[],
+            &[],
+            TransUnescape::default(),
+            &[],
+            &[],
+            &[],
+            true,
+            &plural_config,
+            "$t(",
+            ")",
+            ",",
+            "{{",
+            "}}",
+            ":",
+            ".",
+            PathStyle::default(),
+        )
+        .unwrap();
+
+        let duplicates = find_duplicate_keys(&extraction.key_sources);
+        assert_eq!(duplicates.len(), 1, "{:?}", duplicates);
+        let duplicate = &duplicates[0];
+        assert_eq!(duplicate.key, "title");
+        assert_eq!((duplicate.code.line, duplicate.comment.line), (2, 3));
+        assert_eq!(
+            duplicate.comment.default_value,
+            Some(DefaultValue::from("Heading"))
+        );
+        assert!(duplicate.comment.from_comment && !duplicate.code.from_comment);
+        // Plural keys from a comment are not literal uses of the variants
+        assert!(find_key_collisions(&extraction.key_sources).is_empty());
+    }
+
+    #[test]
+    fn test_selector_api_extracts_key_path() {
+        let source = r#"
+            function Component() {
+                return t($ => $.user.profile.name);
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "user.profile.name");
+    }
+
+    #[test]
+    fn test_use_translation_names_custom_hook() {
+        let source = r#"
+            function Component() {
+                const { t } = loadPageTranslations('common', { keyPrefix: 'user' });
+                return t('name');
+            }
+        "#;
+        let plural_config = PluralConfig::default();
+        let trans_components = vec![TransComponent::Name("Trans".to_string())];
+        let keep_nodes = vec!["br".to_string(), "strong".to_string(), "i".to_string()];
+        let hooks = vec![UseTranslationName::Detailed(
+            crate::config::UseTranslationNameDetails {
+                name: "loadPageTranslations".to_string(),
+                ns_arg: 0,
+                key_prefix_arg: 1,
+            },
+        )];
+
+        let FileExtraction { keys, .. } = extract_from_source_with_warnings(
+            source,
+            "test.tsx",
+            &["t".to_string()],
+            &trans_components,
+            &keep_nodes,
+            TransUnescape::default(),
+            &hooks,
+            &[],
+            &[],
+            true,
+            &plural_config,
+            "$t(",
+            ")",
+            ",",
+            "{{",
+            "}}",
+            ":",
+            ".",
+            PathStyle::default(),
+        )
+        .unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].namespace, Some("common".to_string()));
+        assert_eq!(keys[0].key, "user.name");
+    }
+
+    #[test]
+    fn test_nested_translation_with_custom_nesting_syntax() {
+        let source = r#"
+            t('outer', { defaultValue: 'Nested: __nest__("inner.key")' });
+        "#;
+        let plural_config = PluralConfig::default();
+        let trans_components = vec![TransComponent::Name("Trans".to_string())];
+        let keep_nodes = vec!["br".to_string(), "strong".to_string(), "i".to_string()];
+        let hooks = vec![UseTranslationName::Name("useTranslation".to_string())];
+        let FileExtraction { keys, .. } = extract_from_source_with_warnings(
+            source,
+            "test.ts",
+            &["t".to_string()],
+            &trans_components,
+            &keep_nodes,
+            TransUnescape::default(),
+            &hooks,
+            &[],
+            &[],
+            true,
+            &plural_config,
+            "__nest__(",
+            ")",
+            ",",
+            "{{",
+            "}}",
+            ":",
+            ".",
+            PathStyle::default(),
+        )
+        .unwrap();
+
+        assert!(keys.iter().any(|k| k.key == "outer"));
+        assert!(keys.iter().any(|k| k.key == "inner.key"));
+    }
+
+    #[test]
+    fn test_ordinal_plural_generation() {
+        let source = r#"
+            t('rank', { count: n, ordinal: true });
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+        assert!(keys.iter().any(|k| k.key == "rank_ordinal_one"));
+        assert!(keys.iter().any(|k| k.key == "rank_ordinal_other"));
+    }
+
+    #[test]
+    fn test_legacy_plural_compat_uses_plural_suffix_for_two_forms() {
+        let source = r#"
+            t('apple', { count: n });
+            t('friend', { count: n, context: 'male' });
+        "#;
+        let plural_config = PluralConfig {
+            suffixes: vec![String::new(), "plural".to_string()],
+            compat: PluralCompat::V3,
+            ..PluralConfig::default()
+        };
+
+        let keys = extract_from_source_with_options(
+            source,
+            "test.ts",
+            &["t".to_string()],
+            false,
+            &plural_config,
+        )
+        .unwrap();
+        let names: Vec<&str> = keys.iter().map(|k| k.key.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["apple", "apple_plural", "friend_male", "friend_male_plural"]
+        );
+    }
+
+    #[test]
+    fn test_legacy_plural_compat_uses_numeric_suffixes() {
+        let source = r#"
+            t('item', { count: n });
+        "#;
+        let plural_config = PluralConfig {
+            suffixes: vec!["0".to_string(), "1".to_string(), "2".to_string()],
+            compat: PluralCompat::V3,
+            ..PluralConfig::default()
+        };
+
+        let keys = extract_from_source_with_options(
+            source,
+            "test.ts",
+            &["t".to_string()],
+            false,
+            &plural_config,
+        )
+        .unwrap();
+        let names: Vec<&str> = keys.iter().map(|k| k.key.as_str()).collect();
+        assert_eq!(names, vec!["item_0", "item_1", "item_2"]);
+    }
+
+    #[test]
+    fn test_single_category_plurals_can_add_other_form() {
+        let mut plural_config = PluralConfig {
+            suffixes: vec!["other".to_string()],
+            ..PluralConfig::default()
+        };
+        let extract = |plural_config: &PluralConfig| -> Vec<String> {
+            extract_from_source_with_options(
+                "t('item', { count: n });",
+                "test.ts",
+                &["t".to_string()],
+                false,
+                plural_config,
+            )
+            .unwrap()
+            .into_iter()
+            .map(|k| k.key)
+            .collect()
+        };
+        assert_eq!(extract(&plural_config), vec!["item"]);
+        plural_config.single_category_other = true;
+        assert_eq!(extract(&plural_config), vec!["item", "item_other"]);
+    }
+
+    #[test]
+    fn test_plural_context_order_puts_plural_suffix_first() {
+        let source = r#"
+            t('friend', { count: n, context: 'male' });
+        "#;
+        let plural_config = PluralConfig {
+            order: ContextPluralOrder::PluralContext,
+            ..PluralConfig::default()
+        };
+
+        let keys = extract_from_source_with_options(
+            source,
+            "test.ts",
+            &["t".to_string()],
+            false,
+            &plural_config,
+        )
+        .unwrap();
+        let names: Vec<&str> = keys.iter().map(|k| k.key.as_str()).collect();
+        assert_eq!(names, vec!["friend_one_male", "friend_other_male"]);
+    }
+
+    #[test]
+    fn test_return_objects_generates_preserve_marker() {
+        let source = r#"
+            t('countries', { returnObjects: true });
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+        assert!(keys.iter().any(|k| k.key == "countries.*"));
+        assert!(!keys.iter().any(|k| k.key == "countries"));
+    }
+
+    #[test]
+    fn test_scope_bindings_through_await_and_member_targets() {
+        let source = r#"
+            export default async function Page() {
+                const t = await getTranslations({ locale: 'de', namespace: 'dashboard' });
+                const { t: ts } = await serverSideTranslations(locale, ['settings', 'common']);
+                return t('title') + ts('save');
+            }
+            class Mailer {
+                t = i18next.getFixedT(null, 'mail', 'welcome');
+                constructor(ctx) {
+                    ctx.tr = i18next.getFixedT('en', 'auth');
+                    ctx.tr('login');
+                }
+                send() {
+                    return this.t('subject');
+                }
+            }
+        "#;
+        let plural_config = PluralConfig::default();
+        let hooks = crate::config::Config::default().use_translation_names;
+
+        let FileExtraction { keys, .. } = extract_from_source_with_warnings(
+            source,
+            "page.ts",
+            &["t".to_string()],
+            &[TransComponent::Name("Trans".to_string())],
+            &[],
+            TransUnescape::default(),
+            &hooks,
+            &[],
+            &[],
+            false,
+            &plural_config,
+            "$t(",
+            ")",
+            ",",
+            "{{",
+            "}}",
+            ":",
+            ".",
+            PathStyle::default(),
+        )
+        .unwrap();
+
+        let found: Vec<(Option<&str>, &str)> = keys
+            .iter()
+            .map(|k| (k.namespace.as_deref(), k.key.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (Some("dashboard"), "title"),
+                (Some("settings"), "save"),
+                (Some("auth"), "login"),
+                (Some("mail"), "welcome.subject"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_fixed_t_with_key_prefix() {
+        let source = r#"
+            const t = getFixedT('en', 'ns', 'user.profile');
+            const text = t('name');
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "user.profile.name");
+        assert_eq!(keys[0].namespace, Some("ns".to_string()));
+    }
+
+    #[test]
+    fn test_default_value_extraction() {
+        let source = r#"
+            const text = t('greeting', { defaultValue: 'Hello World!' });
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "greeting");
+        assert_eq!(keys[0].default_value, Some("Hello World!".into()));
+    }
+
+    #[test]
+    fn test_default_value_with_namespace() {
+        let source = r#"
+            const text = t('common:welcome', { defaultValue: 'Welcome back!' });
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "welcome");
+        assert_eq!(keys[0].namespace, Some("common".to_string()));
+        assert_eq!(keys[0].default_value, Some("Welcome back!".into()));
+    }
+
+    #[test]
+    fn test_default_value_with_count() {
+        let source = r#"
+            const text = t('item', { count: 5, defaultValue: '{{count}} items' });
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 2);
+        assert!(keys
+            .iter()
+            .any(|k| k.key == "item_one" && k.default_value == Some("{{count}} items".into())));
+        assert!(keys
+            .iter()
+            .any(|k| k.key == "item_other" && k.default_value == Some("{{count}} items".into())));
+    }
+
+    #[test]
+    fn test_non_string_default_values_keep_their_type() {
+        let source = r#"
+            t('retries', { defaultValue: 3 });
+            t('ratio', { defaultValue: -0.5 });
+            t('enabled', { defaultValue: false });
+            t('empty', { defaultValue: null });
+            t('dynamic', { defaultValue: fallback });
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+        let defaults: Vec<(&str, Option<serde_json::Value>)> = keys
+            .iter()
+            .map(|k| (k.key.as_str(), k.default_value.clone().map(Into::into)))
+            .collect();
+        assert_eq!(
+            defaults,
+            vec![
+                ("retries", Some(serde_json::json!(3))),
+                ("ratio", Some(serde_json::json!(-0.5))),
+                ("enabled", Some(serde_json::json!(false))),
+                ("empty", Some(serde_json::Value::Null)),
+                ("dynamic", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_from_single_line_comment() {
+        let source = r#"
+            // t('comment.key')
+            const x = 1;
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "comment.key");
+    }
+
+    #[test]
+    fn test_extract_from_block_comment() {
+        let source = r#"
+            /* t('block.key') */
+            const x = 1;
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "block.key");
+    }
+
+    #[test]
+    fn test_extract_from_comment_with_default() {
+        let source = r#"
+            // t('greeting', 'Hello!')
+            const x = 1;
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "greeting");
+        assert_eq!(keys[0].default_value, Some("Hello!".into()));
+    }
+
+    #[test]
+    fn test_extract_from_comment_with_options() {
+        let source = r#"
+            // t('message', { defaultValue: 'Default message' })
+            const x = 1;
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "message");
+        assert_eq!(keys[0].default_value, Some("Default message".into()));
+    }
+
+    #[test]
+    fn test_extract_from_comment_with_namespace() {
+        let source = r#"
+            // t('common:nav.home')
+            const x = 1;
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "nav.home");
+        assert_eq!(keys[0].namespace, Some("common".to_string()));
+    }
+
+    #[test]
+    fn test_extract_from_comment_with_count_option() {
+        let source = r#"
+            // t('notification', { count: items.length })
+            const x = 1;
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 2);
+        assert!(keys.iter().any(|k| k.key == "notification_one"));
+        assert!(keys.iter().any(|k| k.key == "notification_other"));
+    }
+
+    #[test]
+    fn test_extract_from_comment_with_context_option() {
+        let source = r#"
+            // t('greeting', { context: 'formal' })
+            const x = 1;
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "greeting_formal");
+    }
+
+    #[test]
+    fn test_extract_from_comment_with_ns_option() {
+        let source = r#"
+            // t('button.save', { ns: 'common' })
+            const x = 1;
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "button.save");
+        assert_eq!(keys[0].namespace, Some("common".to_string()));
+    }
+
+    #[test]
+    fn test_extract_from_comment_resolves_use_translation_scope() {
+        let source = r#"
+            function Component() {
+                const { t } = useTranslation('common', { keyPrefix: 'user' });
+                // t('name')
+                return null;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "user.name");
+        assert_eq!(keys[0].namespace, Some("common".to_string()));
+    }
+
+    #[test]
+    fn test_extract_from_comment_does_not_apply_scope_when_ambiguous() {
+        let source = r#"
+            function Component() {
+                const { t } = useTranslation('common', { keyPrefix: 'user' });
+                const { t: tOther } = useTranslation('admin', { keyPrefix: 'role' });
+                // t('name')
+                return tOther('title');
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+
+        assert!(keys
+            .iter()
+            .any(|k| k.key == "name" && k.namespace.is_none()));
+    }
+
+    #[test]
+    fn test_comments_follow_configured_functions_and_separators() {
+        let request = ExtractRequest::builder()
+            .functions(["translate", "i18n.t"])
+            .use_translation_names([UseTranslationName::Name("useTranslation".to_string())])
+            .extract_from_comments(true)
+            .separators("::", "/")
+            .build();
+        let keys = |source: &str| -> Vec<(Option<String>, String)> {
+            let result = request.run_source(source, Path::new("a.tsx")).unwrap();
+            result.files[0]
+                .1
+                .iter()
+                .map(|k| (k.namespace.clone(), k.key.clone()))
+                .collect()
+        };
+        let key = |ns: &str, key: &str| (Some(ns.to_string()), key.to_string());
+
+        let source = r#"
+            translate('common::save');
+            // translate('common::nav/home', 'Home')
+            // i18n.t('errors::unknown')
+            // t('ignored') this.translate('ignored')
+        "#;
+        assert_eq!(
+            keys(source),
+            vec![
+                key("common", "save"),
+                key("common", "nav/home"),
+                key("errors", "unknown"),
+            ]
+        );
+
+        let source = r#"
+            const { t } = useTranslation('account', { keyPrefix: 'profile' });
+            t('title');
+            // t('name')
+        "#;
+        assert_eq!(
+            keys(source),
+            vec![
+                key("account", "profile/title"),
+                key("account", "profile/name")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_translation_in_default_value() {
+        let source = r#"
+            const text = t('greeting', { defaultValue: 'Hello $t(world)!' });
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 2);
+        assert!(keys.iter().any(|k| k.key == "greeting"));
+        assert!(keys.iter().any(|k| k.key == "world"));
+    }
+
+    #[test]
+    fn test_nested_translation_with_namespace() {
+        let source = r#"
+            const text = t('message', { defaultValue: 'See $t(common:link)' });
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 2);
+        assert!(keys.iter().any(|k| k.key == "message"));
+        assert!(keys
+            .iter()
+            .any(|k| k.key == "link" && k.namespace == Some("common".to_string())));
+    }
+
+    #[test]
+    fn test_multiple_nested_translations() {
+        let source = r#"
+            const text = t('full', { defaultValue: '$t(hello), $t(world)!' });
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 3);
+        assert!(keys.iter().any(|k| k.key == "full"));
+        assert!(keys.iter().any(|k| k.key == "hello"));
+        assert!(keys.iter().any(|k| k.key == "world"));
+    }
+
+    #[test]
+    fn test_nested_translation_with_options() {
+        let source = r#"
+            const text = t('count_msg', { defaultValue: 'You have $t(item, { count: {{count}} })' });
+        "#;
+
+        let keys = extract_from_source(source, "test.ts", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 3);
+        assert!(keys.iter().any(|k| k.key == "count_msg"));
+        assert!(keys.iter().any(|k| k.key == "item_one"));
+        assert!(keys.iter().any(|k| k.key == "item_other"));
+    }
+
+    #[test]
+    fn test_nested_translation_in_trans_defaults() {
+        let source = r#"
+            function Component() {
+                return <Trans i18nKey="greeting" defaults="Hello $t(name)!" />;
+            }
+        "#;
+
+        let keys = extract_from_source(source, "test.tsx", &["t".to_string()]).unwrap();
+
+        assert_eq!(keys.len(), 2);
+        assert!(keys.iter().any(|k| k.key == "greeting"));
+        assert!(keys.iter().any(|k| k.key == "name"));
+    }
+
+    fn extract_from_virtual_file(
+        content: &str,
+        file_name: &str,
+        functions: &[String],
+    ) -> Vec<ExtractedKey> {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(file_name);
+        fs::write(&path, content).unwrap();
+        extract_from_file_with_options(&path, functions, true, &PluralConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn test_dynamic_key_severity_follows_rules_and_is_counted_apart() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("legacy")).unwrap();
+        fs::write(dir.path().join("app.ts"), "t(`status.${state}`);").unwrap();
+        fs::write(dir.path().join("legacy/old.ts"), "t(`old.${id}`);").unwrap();
+        let root = dir.path().display();
+
+        let run = |severity: DiagnosticSeverity| {
+            ExtractRequest::builder()
+                .patterns([format!("{}/**/*.ts", root)])
+                .dynamic_key_severity(severity)
+                .dynamic_key_rules([SeverityRule {
+                    pattern: format!("{}/legacy/**", root),
+                    severity: DiagnosticSeverity::Off,
+                }])
+                .build()
+                .run()
+                .unwrap()
+        };
+
+        let extraction = run(DiagnosticSeverity::Warn);
+        assert_eq!(extraction.diagnostics.len(), 1);
+        let diagnostic = &extraction.diagnostics[0];
+        assert_eq!(diagnostic.kind, DiagnosticKind::DynamicKey);
+        assert!(diagnostic.file_path.ends_with("app.ts"));
+        assert_eq!((diagnostic.line, diagnostic.column), (Some(1), Some(1)));
+        assert_eq!(extraction.dynamic_key_warnings(), 1);
+        assert_eq!(extraction.total_warnings(), 1);
+        // Patterns for dead-key triage are kept whatever the severity
+        assert_eq!(extraction.dynamic_keys.len(), 2);
+
+        let extraction = run(DiagnosticSeverity::Error);
+        assert_eq!(
+            (extraction.dynamic_key_errors(), extraction.total_warnings()),
+            (1, 0)
+        );
+        assert!(run(DiagnosticSeverity::Off).diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_deduplicated_extraction_returns_diagnostics_and_uses_separators() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("a.ts"),
+            "t('common::save');\nt(`status.${state}`);",
+        )
+        .unwrap();
+        fs::write(dir.path().join("b.ts"), "t('common::save');").unwrap();
+        fs::write(dir.path().join("broken.ts"), "t('x'").unwrap();
+
+        let (keys, diagnostics, errors) = extract_from_glob_deduplicated_with_options(
+            &[format!("{}/*.ts", dir.path().display())],
+            &[],
+            &["t".to_string()],
+            false,
+            &PluralConfig::default(),
+            &[TransComponent::Name("Trans".to_string())],
+            &[],
+            TransUnescape::default(),
+            &[],
+            &[],
+            "$t(",
+            ")",
+            ",",
+            "{{",
+            "}}",
+            "::",
+            ".",
+        )
+        .unwrap();
+        let keys: Vec<_> = keys.into_keys().collect();
+        assert_eq!(
+            keys,
+            vec![ExtractedKey {
+                key: "save".to_string(),
+                namespace: Some("common".to_string()),
+                default_value: None,
+            }]
+        );
+        assert_eq!(errors.len(), 1);
+        let kinds: Vec<_> = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.kind, diagnostic.line))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (DiagnosticKind::DynamicKey, Some(2)),
+                (DiagnosticKind::ParseError, errors[0].line),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diagnostics_are_structured_and_filtered_by_kind() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("app.ts"),
+            [
+                "const { t } = useTranslation(pageNamespace);",
+                "t('common:save', { ns: 'admin' });",
+                "t('list', { returnObjects: true, count: 2 });",
+                "t('title', { ns: currentNamespace });",
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+        fs::write(dir.path().join("broken.ts"), "const = ;").unwrap();
+        let pattern = format!("{}/*.ts", dir.path().display());
+
+        let extraction = ExtractRequest::builder()
+            .patterns([pattern.as_str()])
+            .build()
+            .run()
+            .unwrap();
+        let found: Vec<(DiagnosticKind, Option<usize>)> = extraction
+            .diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.kind, diagnostic.line))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (DiagnosticKind::UnresolvedNamespace, Some(1)),
+                (DiagnosticKind::ConflictingOptions, Some(2)),
+                (DiagnosticKind::ConflictingOptions, Some(3)),
+                (DiagnosticKind::UnresolvedNamespace, Some(4)),
+                (DiagnosticKind::ParseError, Some(1)),
+            ]
+        );
+        assert!(extraction.diagnostics[1]
+            .message
+            .contains("Key namespace 'common' and ns option 'admin' disagree"));
+        assert_eq!(extraction.total_warnings(), 5);
+        assert_eq!(
+            extraction
+                .diagnostic_counts()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![
+                (DiagnosticKind::UnresolvedNamespace, 2),
+                (DiagnosticKind::ConflictingOptions, 2),
+                (DiagnosticKind::ParseError, 1),
+            ]
+        );
+
+        let extraction = ExtractRequest::builder()
+            .patterns([pattern.as_str()])
+            .diagnostic_kinds([DiagnosticKind::ParseError])
+            .build()
+            .run()
+            .unwrap();
+        assert_eq!(extraction.total_warnings(), 1);
+        assert_eq!(extraction.errors.len(), 1);
+        assert_eq!(
+            DiagnosticKind::parse_str("conflicting-options").unwrap(),
+            DiagnosticKind::ConflictingOptions
+        );
+        assert!(DiagnosticKind::parse_str("typo").is_err());
+    }
+
+    #[test]
+    fn test_unsafe_namespaces_are_dropped_with_a_diagnostic() {
+        let source = [
+            "t('common:save');",
+            "t('../../etc:passwd');",
+            "const { t: tx } = useTranslation('..');",
+            "tx('title');",
+            "t('../../etc:shadow');",
+        ]
+        .join("\n");
+        let result = ExtractRequest::builder()
+            .build()
+            .run_source(source, Path::new("app.ts"))
+            .unwrap();
+        let keys: Vec<(Option<&str>, &str)> = result.files[0]
+            .1
+            .iter()
+            .map(|key| (key.namespace.as_deref(), key.key.as_str()))
+            .collect();
+        assert_eq!(keys, vec![(Some("common"), "save")]);
+        let found: Vec<(DiagnosticKind, Option<usize>)> = result
+            .diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.kind, diagnostic.line))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (DiagnosticKind::UnsafeNamespace, Some(2)),
+                (DiagnosticKind::UnsafeNamespace, Some(4)),
+            ]
+        );
+        assert!(result.diagnostics[0]
+            .message
+            .contains("Namespace '../../etc' at app.ts:2 is not a plain file name"));
+    }
+
+    #[test]
+    fn test_large_file_is_extracted_via_mmap() {
+        let padding = "// padding\n".repeat((MMAP_THRESHOLD_BYTES as usize / 11) + 1);
+        let source = format!(
+            "\u{feff}{}const a = t('large.first');\nt('large.second');\n",
+            padding
+        );
+        assert!(source.len() as u64 >= MMAP_THRESHOLD_BYTES);
+
+        let keys = extract_from_virtual_file(&source, "large.ts", &["t".to_string()]);
+        let key_names: Vec<_> = keys.iter().map(|k| k.key.as_str()).collect();
+        assert_eq!(key_names, vec!["large.first", "large.second"]);
+    }
+
+    #[test]
+    fn test_large_file_with_invalid_utf8_reports_read_error() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("invalid.ts");
+        let mut content = vec![b'a'; MMAP_THRESHOLD_BYTES as usize];
+        content.push(0xff);
+        fs::write(&path, content).unwrap();
+
+        let err = extract_from_file_with_options(
+            &path,
+            &["t".to_string()],
+            false,
+            &PluralConfig::default(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Failed to read file"));
+    }
+
+    #[test]
+    fn test_vue_component_script_and_template() {
+        let source = r#"
+            <template>
+              <div>{{ $t('template.title') }}</div>
+              <button :title="t('template.tooltip', { defaultValue: 'Tooltip' })"></button>
+            </template>
+            <script setup lang="ts">
+            const label = t('script.key');
+            </script>
+        "#;
+
+        let functions = vec!["t".to_string()];
+        let keys = extract_from_virtual_file(source, "component.vue", &functions);
+
+        assert_eq!(keys.len(), 3);
+        assert!(keys.iter().any(|k| k.key == "script.key"));
+        assert!(keys.iter().any(|k| k.key == "template.title"));
+        let tooltip = keys
+            .iter()
+            .find(|k| k.key == "template.tooltip")
+            .expect("template tooltip key");
+        assert_eq!(
+            tooltip
+                .default_value
+                .as_ref()
+                .and_then(DefaultValue::as_str),
+            Some("Tooltip")
+        );
+    }
+
+    #[test]
+    fn test_vue_component_directives_nested_templates_and_i18n_block() {
+        let source = r#"
+<template>
+  <div>
+    <template v-if="ready">
+      <span v-t="{ path: 'nested.directive', args: { n } }"></span>
+    </template>
+    <input
+      :placeholder="
+        t('multi.line', { defaultValue: 'Say &quot;hi&quot; > there' })
+      "
+      placeholder="t('static.attr')"
+    />
+  </div>
+</template>
+<i18n locale="en">{ "local": { "title": "Local title" } }</i18n>
+"#;
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("component.vue");
+        fs::write(&path, source).unwrap();
+        let functions = vec!["t".to_string()];
+        let plural_config = PluralConfig::default();
+        let ctx = StrategyContext::new(
+            &functions,
+            &[],
+            &[],
+            TransUnescape::default(),
+            &[],
+            &[],
+            &[],
+            false,
+            &plural_config,
+            "$t(",
+            ")",
+            ",",
+            "{{",
+            "}}",
+            ":",
+            ".",
+        );
+        let result = extract_vue_component(&path, source, &ctx).unwrap();
+
+        let key_names: Vec<&str> = result.keys.iter().map(|k| k.key.as_str()).collect();
+        assert_eq!(
+            key_names,
+            vec!["nested.directive", "multi.line", "local.title"]
+        );
+        assert_eq!(
+            result.keys[1]
+                .default_value
+                .as_ref()
+                .and_then(DefaultValue::as_str),
+            Some("Say \"hi\" > there")
+        );
+        assert_eq!(result.messages.len(), 1);
+        assert_eq!(result.messages[0].locale, "en");
+        assert_eq!(result.messages[0].value, "Local title");
+    }
+
+    #[test]
+    fn test_mdx_document_jsx_islands_and_esm() {
+        let source = r#"import { useTranslation } from 'react-i18next'
+export const meta = { title: t('mdx.meta') }
+
+# Uses t('prose.ignored') in prose
+
+<Trans i18nKey="mdx.trans">Hello <strong>world</strong></Trans>
+
+```jsx
+<Trans i18nKey="fenced.ignored" />
+```
+
+Count: {t('mdx.expr')}
+"#;
+
+        let functions = vec!["t".to_string()];
+        let keys = extract_from_virtual_file(source, "guide.mdx", &functions);
+        let mut key_names: Vec<_> = keys.iter().map(|k| k.key.as_str()).collect();
+        key_names.sort();
+        assert_eq!(key_names, vec!["mdx.expr", "mdx.meta", "mdx.trans"]);
+    }
+
+    #[test]
+    fn test_svelte_component_script_and_markup() {
+        let source = r#"
+            <script>
+              const heading = t('script.value', { defaultValue: 'Value' });
+            </script>
+
+            <h1>{$t('template.header')}</h1>
+        "#;
+
+        let functions = vec!["t".to_string()];
+        let keys = extract_from_virtual_file(source, "component.svelte", &functions);
+
+        assert_eq!(keys.len(), 2);
+        let script_key = keys
+            .iter()
+            .find(|k| k.key == "script.value")
+            .expect("script key");
+        assert_eq!(
+            script_key
+                .default_value
+                .as_ref()
+                .and_then(DefaultValue::as_str),
+            Some("Value")
+        );
+        assert!(keys.iter().any(|k| k.key == "template.header"));
+    }
+
+    #[test]
+    fn test_component_block_parse_errors_keep_the_other_blocks() {
+        let functions = vec!["t".to_string()];
+        let plural_config = PluralConfig::default();
+        let ctx = StrategyContext::new(
+            &functions,
+            &[],
+            &[],
+            TransUnescape::default(),
+            &[],
+            &[],
+            &[],
+            false,
+            &plural_config,
+            "$t(",
+            ")",
+            ",",
+            "{{",
+            "}}",
+            ":",
+            ".",
+        );
+        let key_names = |result: &FileExtraction| -> Vec<String> {
+            result.keys.iter().map(|k| k.key.clone()).collect()
+        };
+
+        let svelte = r#"<script>const a = t('script.key');</script>
+<p>{$t("tpl.broken", { count: })}</p>
+<p>{$t("tpl.ok")}</p>"#;
+        let result = extract_svelte_component(Path::new("App.svelte"), svelte, &ctx).unwrap();
+        assert_eq!(key_names(&result), vec!["script.key", "tpl.ok"]);
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].kind, DiagnosticKind::ParseError);
+        assert_eq!(result.diagnostics[0].file_path, "App.svelte");
+        assert!(result.diagnostics[0]
+            .message
+            .starts_with("Skipping App.svelte#template:1:"));
+
+        let vue = r#"<template>
+  <p>{{ $t("tpl.broken", { count: }) }}</p>
+  <p>{{ $t("tpl.ok") }}</p>
+</template>
+<script>const a = t('script.key'); const = ;</script>"#;
+        let result = extract_vue_component(Path::new("App.vue"), vue, &ctx).unwrap();
+        assert_eq!(key_names(&result), vec!["tpl.ok"]);
+        let messages: Vec<&str> = result
+            .diagnostics
+            .iter()
+            .map(|d| d.message.as_str())
+            .collect();
+        assert_eq!(messages.len(), 2, "{:?}", messages);
+        assert!(messages[0].starts_with("Skipping App.vue#script1:"));
+        assert!(messages[1].starts_with("Skipping App.vue#template1:1:"));
+    }
+
+    #[test]
+    fn test_expand_brace_patterns_simple() {
+        let expanded = expand_brace_patterns("src/**/*.{ts,tsx}");
+        assert_eq!(
+            expanded,
+            vec!["src/**/*.ts".to_string(), "src/**/*.tsx".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_matches_input_patterns_with_braces_and_dot_prefix() {
+        let patterns = vec!["./src/**/*.{ts,tsx}".to_string()];
+        assert!(matches_input_patterns(
+            Path::new("src/app/page.tsx"),
+            &patterns
+        ));
+        assert!(matches_input_patterns(Path::new("./src/a.ts"), &patterns));
+        assert!(!matches_input_patterns(Path::new("src/a.css"), &patterns));
+        assert!(!matches_input_patterns(Path::new("lib/a.ts"), &patterns));
+    }
+
+    #[test]
+    fn test_extract_from_glob_supports_brace_patterns() {
+        let dir = tempdir().unwrap();
+        let src_dir = dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+        fs::write(src_dir.join("a.ts"), "t('a.key')").unwrap();
+        fs::write(src_dir.join("b.tsx"), "const b = t('b.key');").unwrap();
+
+        let pattern = format!("{}/**/*.{{ts,tsx}}", src_dir.display());
+        let functions = vec!["t".to_string()];
+        let result = extract_from_glob_with_options(
+            &[pattern],
+            &[],
+            &functions,
+            true,
+            &PluralConfig::default(),
+            &[],
+            &[],
+            TransUnescape::default(),
+            &[],
+            &[],
+            "$t(",
+            ")",
+            ",",
+            "{{",
+            "}}",
+            true,
+        )
+        .unwrap();
+
+        let extracted: Vec<&ExtractedKey> = result
+            .files
+            .iter()
+            .flat_map(|(_, keys)| keys.iter())
+            .collect();
+        assert!(extracted.iter().any(|k| k.key == "a.key"));
+        assert!(extracted.iter().any(|k| k.key == "b.key"));
+    }
+
+    #[test]
+    fn test_extract_with_config_takes_options_from_config() {
+        let dir = tempdir().unwrap();
+        let src_dir = dir.path().join("src");
+        fs::create_dir_all(src_dir.join("generated")).unwrap();
+        fs::write(src_dir.join("a.ts"), "translate('a.key'); t('skipped')").unwrap();
+        fs::write(src_dir.join("generated/b.ts"), "translate('b.key')").unwrap();
+
+        let mut config = Config::default();
+        config.functions = vec!["translate".to_string()];
+        let result = extract_with_config(
+            &config,
+            &[format!("{}/**/*.ts", src_dir.display())],
+            &[format!("{}/generated/**", src_dir.display())],
+        )
+        .unwrap();
+
+        let keys: Vec<&str> = result
+            .files
+            .iter()
+            .flat_map(|(_, keys)| keys.iter().map(|key| key.key.as_str()))
+            .collect();
+        assert_eq!(keys, vec!["a.key"]);
+    }
+
+    #[test]
+    fn test_extract_request_builder_overrides_defaults_by_name() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("a.ts"),
+            "translate('a.key', { defaultValue: 'See [[t(b.key)]]' }); t('skipped')",
+        )
+        .unwrap();
+
+        let request = ExtractRequest::builder()
+            .patterns([format!("{}/*.ts", dir.path().display())])
+            .functions(["translate"])
+            .nesting("[[t(", ")]]", ",")
+            .build();
+        let result = request.run().unwrap();
+
+        let mut keys: Vec<&str> = result
+            .files
+            .iter()
+            .flat_map(|(_, keys)| keys.iter().map(|key| key.key.as_str()))
+            .collect();
+        keys.sort();
+        assert_eq!(keys, vec!["a.key", "b.key"]);
+        // Options left out keep the config defaults
+        assert!(request.extract_from_comments);
+        assert_eq!(request.interpolation_prefix, "{{");
+    }
+
+    #[test]
+    fn test_lint_shares_the_extraction_parse() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("App.tsx"),
+            "function App() { return <div><p>{t('title')}</p><p>Hello there</p></div>; }",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("Empty.tsx"),
+            "function Empty() { return <span>No keys here</span>; }",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("Legacy.tsx"),
+            "function Legacy() { return <span>Ignored text</span>; }",
+        )
+        .unwrap();
+        let pattern = format!("{}/*.tsx", dir.path().display());
+        let options = LintOptions {
+            ignore_patterns: vec![format!("{}/Legacy.tsx", dir.path().display())],
+            ..LintOptions::default()
+        };
+
+        let result = ExtractRequest::builder()
+            .patterns([pattern.as_str()])
+            .lint(options.clone())
+            .config(&Config::default())
+            .build()
+            .run()
+            .unwrap();
+        assert_eq!(result.files.len(), 1);
+        let shared = result.lint.unwrap();
+        let separate = lint::lint_from_glob_with_options(&[pattern], &options).unwrap();
+        assert_eq!(shared.files_checked, separate.files_checked);
+        let texts = |result: &LintResult| -> Vec<(String, usize, usize, String)> {
+            result
+                .issues
+                .iter()
+                .map(|issue| {
+                    (
+                        issue.file_path.clone(),
+                        issue.line,
+                        issue.column,
+                        issue.text.clone(),
+                    )
+                })
+                .collect()
+        };
+        assert_eq!(texts(&shared), texts(&separate));
+        assert_eq!(shared.issues.len(), 2);
+
+        // Without lint options nothing is linted
+        let result = ExtractRequest::default().run_source("t('a')", Path::new("a.tsx"));
+        assert!(result.unwrap().lint.is_none());
+    }
+
+    #[test]
+    fn test_run_source_extracts_in_memory_source_by_path() {
+        let request = ExtractRequest::default();
+        let result = request
+            .run_source(
+                "<template><p>{{ $t('vue.title') }}</p></template>",
+                Path::new("src/App.vue"),
+            )
+            .unwrap();
+        assert_eq!(result.files.len(), 1);
+        assert_eq!(result.files[0].0, "src/App.vue");
+        assert_eq!(result.files[0].1[0].key, "vue.title");
+
+        let result = request
+            .run_source("t('broken'", Path::new("src/broken.ts"))
+            .unwrap();
+        assert!(result.files.is_empty());
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].file_path, "src/broken.ts");
+    }
+
+    #[test]
+    fn test_cancelled_request_returns_partial_result() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("a.ts"), "t('a.key')").unwrap();
+        let pattern = format!("{}/*.ts", dir.path().display());
+
+        let token = CancellationToken::new();
+        let request = ExtractRequest::builder()
+            .cancellation(token.clone())
+            .patterns([pattern.as_str()])
+            .config(&Config::default())
+            .build();
+        let result = request.run().unwrap();
+        assert!(!result.cancelled);
+        assert_eq!(result.files.len(), 1);
+
+        token.cancel();
+        let result = request.run().unwrap();
+        assert!(result.cancelled);
+        assert!(result.files.is_empty());
+    }
+
+    #[test]
+    fn test_extraction_keeps_glob_order_across_threads() {
+        let dir = tempdir().unwrap();
+        for index in 0..40 {
+            // Every file has its own default for `shared`; the first file must win
+            fs::write(
+                dir.path().join(format!("file{:02}.ts", index)),
+                format!("t('shared', 'from {:02}'); t('key{:02}')", index, index),
+            )
+            .unwrap();
+        }
+        let pattern = format!("{}/*.ts", dir.path().display());
+
+        let runs: Vec<Vec<String>> = [1, 4]
+            .into_iter()
+            .map(|threads| {
+                let pool = rayon::ThreadPoolBuilder::new()
+                    .num_threads(threads)
+                    .build()
+                    .unwrap();
+                let result = pool
+                    .install(|| ExtractRequest::builder().patterns([&pattern]).build().run())
+                    .unwrap();
+                result.files.into_iter().map(|(path, _)| path).collect()
+            })
+            .collect();
+
+        let mut sorted = runs[0].clone();
+        sorted.sort();
+        assert_eq!(runs[0], sorted);
+        assert_eq!(runs[0], runs[1]);
+    }
+
+    /// Test that regex-based comment extractors compile successfully.
+    #[test]
+    fn test_regex_initialization() {
+        assert!(CommentCallPatterns::new([]).is_none());
+        let patterns = CommentCallPatterns::new(["t", "i18n.t", "$t"]).unwrap();
+
+        assert!(patterns.single_arg.is_match("t('key')"));
+        assert!(patterns.single_arg.is_match("t(\"key\")"));
+        assert!(patterns.single_arg.is_match("t(`key`)"));
+        assert!(patterns.single_arg.is_match("i18n.t('key')"));
+        assert!(patterns.single_arg.is_match("{{ $t('key') }}"));
+        assert!(!patterns.single_arg.is_match("this.t('key')"));
+        assert!(!patterns.single_arg.is_match("format('key')"));
+
+        assert!(patterns.with_default.is_match("t('key', 'default')"));
+        assert!(patterns.with_default.is_match("t(\"key\", \"default\")"));
+
+        assert!(patterns
+            .with_options
+            .is_match("t('key', { defaultValue: 'value' })"));
+        assert!(patterns
+            .with_options
+            .is_match("t('key', { other: 1, defaultValue: 'value' })"));
+    }
+}
@@ -28,17 +28,25 @@ pub trait FileSystem: Send + Sync {
     /// Rename (atomic move) a file
     fn rename(&self, from: &Path, to: &Path) -> Result<()>;
 
+    /// Remove a file
+    fn remove_file(&self, path: &Path) -> Result<()>;
+
     /// Open a file with exclusive lock for read-modify-write operations.
     /// Returns a LockedFile that provides read access and can be used with atomic_write.
     /// The lock is held until the LockedFile is dropped.
     fn open_locked(&self, path: &Path) -> Result<Box<dyn LockedFile>>;
 
+    /// Open an existing file with a shared lock for reading. Any number of readers
+    /// may hold it at once; it waits while a writer holds the lock of `open_locked`,
+    /// so readers never observe a half-written file.
+    fn open_shared(&self, path: &Path) -> Result<Box<dyn LockedFile>>;
+
     /// Atomically write bytes to a file (tempfile + rename pattern).
     /// This ensures that the file is never in an inconsistent state.
     fn atomic_write(&self, path: &Path, content: &[u8]) -> Result<()>;
 }
 
-/// A file handle with an exclusive lock.
+/// A file handle with an exclusive (`open_locked`) or shared (`open_shared`) lock.
 /// Provides read access to the file contents.
 /// The lock is released when this object is dropped.
 pub trait LockedFile: Read + Send {
@@ -50,6 +58,12 @@ pub trait LockedFile: Read + Send {
     }
 }
 
+/// Read a file under a shared lock (see [`FileSystem::open_shared`]). Used by
+/// analysis commands so they never see a locale file while `watch` writes it.
+pub fn read_to_string_shared(path: &Path) -> Result<String> {
+    RealFileSystem.open_shared(path)?.content_string()
+}
+
 /// Real file system implementation using std::fs
 #[derive(Debug, Default, Clone)]
 pub struct RealFileSystem;
@@ -110,6 +124,10 @@ impl FileSystem for RealFileSystem {
         Ok(std::fs::rename(from, to)?)
     }
 
+    fn remove_file(&self, path: &Path) -> Result<()> {
+        Ok(std::fs::remove_file(path)?)
+    }
+
     fn open_locked(&self, path: &Path) -> Result<Box<dyn LockedFile>> {
         use fs2::FileExt;
 
@@ -135,6 +153,19 @@ impl FileSystem for RealFileSystem {
         Ok(Box::new(RealLockedFile { file }))
     }
 
+    fn open_shared(&self, path: &Path) -> Result<Box<dyn LockedFile>> {
+        use fs2::FileExt;
+
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open file: {}", path.display()))?;
+
+        // Acquire shared lock (blocks while a writer holds the exclusive lock)
+        FileExt::lock_shared(&file)
+            .with_context(|| format!("Failed to acquire shared lock on: {}", path.display()))?;
+
+        Ok(Box::new(RealLockedFile { file }))
+    }
+
     fn atomic_write(&self, path: &Path, content: &[u8]) -> Result<()> {
         use std::io::BufWriter;
         use tempfile::NamedTempFile;
@@ -276,6 +307,13 @@ pub mod mock {
             }
         }
 
+        fn remove_file(&self, path: &Path) -> Result<()> {
+            match self.files.write().unwrap().remove(path) {
+                Some(_) => Ok(()),
+                None => Err(anyhow::anyhow!("File not found: {}", path.display())),
+            }
+        }
+
         fn open_locked(&self, path: &Path) -> Result<Box<dyn LockedFile>> {
             // For mock FS, we don't actually lock, just return the content
             // Create file if it doesn't exist (like the real implementation)
@@ -304,6 +342,13 @@ pub mod mock {
             }))
         }
 
+        fn open_shared(&self, path: &Path) -> Result<Box<dyn LockedFile>> {
+            let content = self.read_to_string(path)?;
+            Ok(Box::new(MockLockedFile {
+                cursor: Cursor::new(content.into_bytes()),
+            }))
+        }
+
         fn atomic_write(&self, path: &Path, content: &[u8]) -> Result<()> {
             // For mock FS, atomic write is just a regular write
             if let Some(parent) = path.parent() {
@@ -335,6 +380,35 @@ mod tests {
         assert!(fs.is_dir(Path::new(".")));
     }
 
+    #[test]
+    fn test_shared_read_waits_for_exclusive_lock() {
+        use std::sync::mpsc;
+        use std::time::Duration;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("translation.json");
+        std::fs::write(&path, "{}").unwrap();
+
+        let writer = RealFileSystem.open_locked(&path).unwrap();
+        let (sender, receiver) = mpsc::channel();
+        let reader_path = path.clone();
+        let reader = std::thread::spawn(move || {
+            let content = read_to_string_shared(&reader_path).unwrap();
+            sender.send(content).unwrap();
+        });
+        assert!(receiver.recv_timeout(Duration::from_millis(200)).is_err());
+
+        drop(writer);
+        assert_eq!(receiver.recv_timeout(Duration::from_secs(5)).unwrap(), "{}");
+        reader.join().unwrap();
+
+        // Shared locks do not exclude each other
+        let first = RealFileSystem.open_shared(&path).unwrap();
+        assert_eq!(read_to_string_shared(&path).unwrap(), "{}");
+        drop(first);
+        assert!(read_to_string_shared(&dir.path().join("missing.json")).is_err());
+    }
+
     #[test]
     fn test_in_memory_file_system() {
         use mock::InMemoryFileSystem;
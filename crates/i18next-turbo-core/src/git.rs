@@ -0,0 +1,28 @@
+use anyhow::{bail, Context, Result};
+use std::process::Command;
+
+/// Run a git command in the current directory and return its stdout
+pub fn output(args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Run a git command and return its non-empty stdout lines
+pub fn lines(args: &[&str]) -> Result<Vec<String>> {
+    Ok(output(args)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
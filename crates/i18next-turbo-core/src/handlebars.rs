@@ -0,0 +1,346 @@
+//! Handlebars and MJML email templates: translation helpers such as
+//! `{{tEmail "welcome.subject" name=user.name}}` become JS calls, everything
+//! else is blanked, so the result parses as a script.
+
+/// Rewrite a template into a script holding one call per translation helper.
+/// Mustaches, `(tEmail ...)` subexpressions and plain JS calls (`tEmail('key')`,
+/// as in `<script>` blocks or other template languages) are recognised. Every
+/// newline is kept, so line numbers of extracted keys match the template.
+pub fn to_js_module(source: &str, functions: &[String]) -> String {
+    let chars: Vec<char> = source.chars().collect();
+    let mut out = String::with_capacity(source.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        if starts_with(&chars, i, "{{!") {
+            let terminator = if starts_with(&chars, i, "{{!--") {
+                "--}}"
+            } else {
+                "}}"
+            };
+            let end = find(&chars, i + 3, terminator).map_or(chars.len(), |pos| pos + 4);
+            let end = end.min(chars.len());
+            blank(&chars[i..end], &mut out);
+            i = end;
+            continue;
+        }
+        if starts_with(&chars, i, "{{") {
+            let end = mustache_end(&chars, i);
+            let inner: String = chars[i..end].iter().collect();
+            for call in mustache_calls(&inner, functions) {
+                out.push(';');
+                out.push_str(&call);
+                out.push(';');
+            }
+            out.extend(chars[i..end].iter().filter(|c| **c == '\n'));
+            i = end;
+            continue;
+        }
+        if let Some(end) = js_call_end(&chars, i, functions) {
+            out.push(';');
+            out.extend(&chars[i..end]);
+            out.push(';');
+            i = end;
+            continue;
+        }
+        out.push(if chars[i] == '\n' { '\n' } else { ' ' });
+        i += 1;
+    }
+
+    out
+}
+
+/// A parsed helper parameter
+#[derive(Debug, Clone, PartialEq)]
+enum Param {
+    /// A string, number or keyword literal, already written as JS
+    Literal(String),
+    /// A context path such as `user.name`, `../title` or `@index`
+    Path(String),
+    Subexpr(Vec<Param>),
+    Hash(String, Box<Param>),
+}
+
+/// Calls for the translation helpers in one `{{...}}` mustache
+fn mustache_calls(mustache: &str, functions: &[String]) -> Vec<String> {
+    let inner = mustache
+        .trim_start_matches('{')
+        .trim_end_matches('}')
+        .trim_matches('~')
+        .trim();
+    if inner.starts_with('/') {
+        return Vec::new();
+    }
+    let inner = inner.trim_start_matches(['#', '^', '>', '&']).trim_start();
+
+    // `{{ tEmail('key') }}` is already a JS call
+    let chars: Vec<char> = inner.chars().collect();
+    if let Some(end) = js_call_end(&chars, 0, functions) {
+        if chars[end..].iter().all(|c| c.is_whitespace()) {
+            return vec![inner.to_string()];
+        }
+    }
+
+    let params = parse_params(&chars, &mut 0);
+    let mut calls = Vec::new();
+    collect_calls(&params, functions, &mut calls);
+    calls
+}
+
+fn collect_calls(params: &[Param], functions: &[String], calls: &mut Vec<String>) {
+    if let Some(call) = helper_call(params, functions) {
+        calls.push(call);
+        return;
+    }
+    for param in params {
+        let mut param = param;
+        if let Param::Hash(_, value) = param {
+            param = value;
+        }
+        if let Param::Subexpr(inner) = param {
+            collect_calls(inner, functions, calls);
+        }
+    }
+}
+
+/// `name(positional..., { hash })` when `params` invokes a translation helper
+fn helper_call(params: &[Param], functions: &[String]) -> Option<String> {
+    let Some(Param::Path(name)) = params.first() else {
+        return None;
+    };
+    if !functions.iter().any(|function| function == name) {
+        return None;
+    }
+    let mut args = Vec::new();
+    let mut hash = Vec::new();
+    for param in &params[1..] {
+        match param {
+            Param::Hash(key, value) => {
+                hash.push(format!("{}: {}", key, value_js(value, functions)))
+            }
+            param => args.push(value_js(param, functions)),
+        }
+    }
+    if !hash.is_empty() {
+        args.push(format!("{{ {} }}", hash.join(", ")));
+    }
+    Some(format!("{}({})", name, args.join(", ")))
+}
+
+/// A parameter as a JS expression; context values become an identifier so the
+/// extractor treats them as dynamic
+fn value_js(param: &Param, functions: &[String]) -> String {
+    match param {
+        Param::Literal(literal) => literal.clone(),
+        Param::Subexpr(inner) => {
+            helper_call(inner, functions).unwrap_or_else(|| "__value".to_string())
+        }
+        Param::Path(_) | Param::Hash(..) => "__value".to_string(),
+    }
+}
+
+/// Parameters up to the closing `)` of a subexpression or the end of `chars`
+fn parse_params(chars: &[char], pos: &mut usize) -> Vec<Param> {
+    let mut params = Vec::new();
+    loop {
+        while *pos < chars.len() && chars[*pos].is_whitespace() {
+            *pos += 1;
+        }
+        match chars.get(*pos) {
+            None => return params,
+            Some(')') => {
+                *pos += 1;
+                return params;
+            }
+            Some(_) => params.push(parse_param(chars, pos)),
+        }
+    }
+}
+
+fn parse_param(chars: &[char], pos: &mut usize) -> Param {
+    match chars[*pos] {
+        '"' | '\'' => {
+            let end = string_end(chars, *pos);
+            let quote = chars[*pos];
+            let body: String = chars[*pos + 1..end.saturating_sub(1).max(*pos + 1)]
+                .iter()
+                .collect();
+            *pos = end;
+            let value = body.replace(&format!("\\{}", quote), &quote.to_string());
+            Param::Literal(serde_json::to_string(&value).unwrap_or_default())
+        }
+        '(' => {
+            *pos += 1;
+            Param::Subexpr(parse_params(chars, pos))
+        }
+        _ => {
+            let start = *pos;
+            while *pos < chars.len()
+                && !chars[*pos].is_whitespace()
+                && !matches!(chars[*pos], '(' | ')' | '=')
+            {
+                *pos += 1;
+            }
+            let word: String = chars[start..*pos].iter().collect();
+            if chars.get(*pos) == Some(&'=') {
+                *pos += 1;
+                if *pos >= chars.len() {
+                    return Param::Hash(word, Box::new(Param::Path(String::new())));
+                }
+                return Param::Hash(word, Box::new(parse_param(chars, pos)));
+            }
+            if word.is_empty() {
+                // A stray `(` or `=`
+                *pos += 1;
+                return Param::Path(word);
+            }
+            let is_literal = matches!(word.as_str(), "true" | "false" | "null" | "undefined")
+                || word.parse::<f64>().is_ok();
+            if is_literal {
+                Param::Literal(word)
+            } else {
+                Param::Path(word)
+            }
+        }
+    }
+}
+
+/// End (after the closing braces) of the mustache opening at `start`
+fn mustache_end(chars: &[char], start: usize) -> usize {
+    let mut pos = start + 2;
+    while pos < chars.len() {
+        match chars[pos] {
+            '"' | '\'' => pos = string_end(chars, pos),
+            '}' if chars.get(pos + 1) == Some(&'}') => {
+                pos += 2;
+                while chars.get(pos) == Some(&'}') {
+                    pos += 1;
+                }
+                return pos;
+            }
+            _ => pos += 1,
+        }
+    }
+    chars.len()
+}
+
+/// End (after `)`) of a `name(...)` call to one of `functions` at `start`
+fn js_call_end(chars: &[char], start: usize, functions: &[String]) -> Option<usize> {
+    let boundary = start == 0 || {
+        let prev = chars[start - 1];
+        !(prev.is_alphanumeric() || matches!(prev, '_' | '$' | '.'))
+    };
+    if !boundary {
+        return None;
+    }
+    let name = functions
+        .iter()
+        .filter(|name| starts_with(chars, start, name))
+        .max_by_key(|name| name.len())?;
+    let open = start + name.chars().count();
+    if chars.get(open) != Some(&'(') {
+        return None;
+    }
+
+    let mut nesting = 0usize;
+    let mut pos = open;
+    while pos < chars.len() {
+        match chars[pos] {
+            '(' => nesting += 1,
+            ')' => {
+                nesting -= 1;
+                if nesting == 0 {
+                    return Some(pos + 1);
+                }
+            }
+            '"' | '\'' | '`' => {
+                pos = string_end(chars, pos);
+                continue;
+            }
+            _ => {}
+        }
+        pos += 1;
+    }
+    None
+}
+
+/// End (after the closing quote) of the string literal opening at `start`
+fn string_end(chars: &[char], start: usize) -> usize {
+    let quote = chars[start];
+    let mut pos = start + 1;
+    while pos < chars.len() {
+        match chars[pos] {
+            '\\' => pos += 2,
+            c if c == quote => return pos + 1,
+            _ => pos += 1,
+        }
+    }
+    chars.len()
+}
+
+fn find(chars: &[char], start: usize, needle: &str) -> Option<usize> {
+    (start..chars.len()).find(|pos| starts_with(chars, *pos, needle))
+}
+
+fn starts_with(chars: &[char], start: usize, prefix: &str) -> bool {
+    let mut rest = chars[start..].iter();
+    prefix
+        .chars()
+        .all(|expected| rest.next() == Some(&expected))
+}
+
+/// Replace everything but newlines with spaces
+fn blank(chars: &[char], out: &mut String) {
+    out.extend(chars.iter().map(|c| if *c == '\n' { '\n' } else { ' ' }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn functions() -> Vec<String> {
+        vec!["tEmail".to_string()]
+    }
+
+    #[test]
+    fn converts_helpers_subexpressions_and_hash_arguments() {
+        let source = "<mj-text>{{tEmail \"welcome.title\" name=user.name}}</mj-text>\n\
+            {{{tEmail 'welcome.body' defaultValue=\"Hi {{name}}\" count=items.length}}}\n\
+            {{> button label=(tEmail \"cta.open\")}}\n\
+            {{#if (tEmail \"flag\")}}{{/if}}\n";
+        let module = to_js_module(source, &functions());
+
+        assert_eq!(module.lines().count(), source.lines().count());
+        for kept in [
+            ";tEmail(\"welcome.title\", { name: __value });",
+            ";tEmail(\"welcome.body\", { defaultValue: \"Hi {{name}}\", count: __value });",
+            ";tEmail(\"cta.open\");",
+            ";tEmail(\"flag\");",
+        ] {
+            assert!(module.contains(kept), "missing {:?} in:\n{}", kept, module);
+        }
+        assert!(!module.contains("mj-text"));
+    }
+
+    #[test]
+    fn keeps_js_calls_and_skips_comments_and_other_helpers() {
+        let source = "{{!-- {{tEmail \"commented\"}} --}}\n\
+            {{ tEmail('inline.js', { count: 2 }) }}\n\
+            <script>tEmail('script.key')</script>\n\
+            {{formatDate sentAt}} {{t \"web.key\"}} {{/tEmail}}\n";
+        let module = to_js_module(source, &functions());
+
+        assert_eq!(module.lines().count(), source.lines().count());
+        assert!(module.contains(";tEmail('inline.js', { count: 2 });"));
+        assert!(module.contains(";tEmail('script.key');"));
+        for dropped in ["commented", "formatDate", "web.key"] {
+            assert!(
+                !module.contains(dropped),
+                "kept {:?} in:\n{}",
+                dropped,
+                module
+            );
+        }
+        assert_eq!(module.matches("tEmail").count(), 2);
+    }
+}
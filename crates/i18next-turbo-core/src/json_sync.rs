@@ -0,0 +1,4109 @@
+#![allow(clippy::too_many_arguments)]
+
+use anyhow::{bail, Context, Result};
+use glob::Pattern;
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::Serialize;
+use serde_json::ser::{Formatter, Serializer};
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::config::{Config, EndOfLine, KeySort, OutputFormat};
+use crate::extractor::{ComponentMessage, ExtractedKey};
+use crate::fs::FileSystem;
+use crate::locale_module;
+use crate::locale_store::LocaleStore;
+use crate::metadata;
+use crate::paths;
+use crate::plurals;
+use crate::walk::{Skipped, SymlinkGuard};
+
+fn effective_namespace(default_namespace: &str) -> &str {
+    if default_namespace.is_empty() {
+        "translation"
+    } else {
+        default_namespace
+    }
+}
+
+fn merged_namespace_filename(config: &Config) -> String {
+    config
+        .merged_namespace_filename
+        .as_deref()
+        .filter(|name| !name.trim().is_empty())
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| effective_namespace(&config.default_namespace).to_string())
+}
+
+/// Configured merged filename for `locale`, with `{{lng}}` expanded
+fn configured_merged_filename(config: &Config, locale: &str) -> Option<String> {
+    config
+        .merged_namespace_filename
+        .as_deref()
+        .map(|name| name.replace("{{lng}}", locale))
+}
+
+/// Locale file holding `namespace` of `locale` (the merged file with `mergeNamespaces`)
+pub fn locale_namespace_file_path(
+    config: &Config,
+    output_dir: &str,
+    locale: &str,
+    namespace: &str,
+) -> std::path::PathBuf {
+    let output_ext = config.output_extension();
+    if config.flat_locale_files {
+        return Path::new(output_dir).join(format!("{}.{}", locale, output_ext));
+    }
+    let file_stem = if config.merge_namespaces {
+        configured_merged_filename(config, locale)
+            .or_else(|| detect_existing_merged_filename(output_dir, locale, output_ext))
+            .unwrap_or_else(|| merged_namespace_filename(config))
+    } else {
+        namespace.to_string()
+    };
+    Path::new(output_dir)
+        .join(locale)
+        .join(format!("{}.{}", file_stem, output_ext))
+}
+
+/// Fail unless `locale` and `namespace` (empty for a locale's own file) are
+/// plain file names, so that the locale file built from them stays inside the
+/// output directory. Writers check names that come from sources, logs or
+/// command arguments before touching any file.
+pub fn ensure_plain_path_names(locale: &str, namespace: &str) -> Result<()> {
+    if !paths::is_plain_file_name(locale) {
+        bail!(
+            "Refusing to write outside the output directory: locale '{}' is not a plain file name",
+            locale
+        );
+    }
+    if !namespace.is_empty() && !paths::is_plain_file_name(namespace) {
+        bail!(
+            "Refusing to write outside the output directory: namespace '{}' is not a plain file name",
+            namespace
+        );
+    }
+    Ok(())
+}
+
+fn detect_existing_merged_filename(output_dir: &str, locale: &str, ext: &str) -> Option<String> {
+    let locale_dir = Path::new(output_dir).join(locale);
+    let entries = std::fs::read_dir(locale_dir).ok()?;
+
+    let mut stems: Vec<String> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter_map(|path| {
+            let file_ext = path.extension().and_then(|e| e.to_str())?;
+            if file_ext != ext {
+                return None;
+            }
+            path.file_stem()
+                .and_then(|s| s.to_str())
+                .map(|s| s.to_string())
+        })
+        .collect();
+
+    if stems.is_empty() {
+        return None;
+    }
+    stems.sort();
+    stems.dedup();
+
+    // Existing merged layouts often use one shared file name per locale (e.g. all.json).
+    if stems.len() == 1 {
+        return stems.into_iter().next();
+    }
+
+    if stems.iter().any(|s| s == "translation") {
+        return Some("translation".to_string());
+    }
+
+    None
+}
+
+fn merge_namespace_key(config: &Config, namespace: &str, key: &str) -> String {
+    let separator = if config.key_separator.is_empty() {
+        "."
+    } else {
+        config.key_separator.as_str()
+    };
+    merged_key_parts(config, namespace, key).join(separator)
+}
+
+/// Object path of `key` inside a merged-namespaces file, following
+/// `mergedNamespaceLayout` and `mergedNestingDepth`
+pub fn merged_key_parts(config: &Config, namespace: &str, key: &str) -> Vec<String> {
+    if key.is_empty() {
+        return vec![namespace.to_string()];
+    }
+    let mut segments: Vec<String> = if config.key_separator.is_empty() {
+        vec![key.to_string()]
+    } else {
+        key.split(config.key_separator.as_str())
+            .map(str::to_string)
+            .collect()
+    };
+    if let Some(depth) = config.merged_nesting_depth.filter(|depth| *depth > 0) {
+        if segments.len() > depth {
+            let rest = segments.split_off(depth - 1).join(&config.key_separator);
+            segments.push(rest);
+        }
+    }
+
+    match config.merged_namespace_prefix() {
+        Some(ns_separator) => {
+            segments[0] = format!("{}{}{}", namespace, ns_separator, segments[0]);
+            segments
+        }
+        None => {
+            let mut parts = vec![namespace.to_string()];
+            parts.extend(segments);
+            parts
+        }
+    }
+}
+
+/// Object path of `key` inside the locale file holding `namespace`
+pub fn key_parts(config: &Config, namespace: &str, key: &str) -> Vec<String> {
+    if config.merge_namespaces {
+        merged_key_parts(config, namespace, key)
+    } else if config.key_separator.is_empty() {
+        vec![key.to_string()]
+    } else {
+        key.split(config.key_separator.as_str())
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+/// Group the top level of a merged-namespaces file by namespace. With a prefix
+/// separator (the "prefixed" layout), `"ns:key"` entries move under `ns`.
+pub fn split_merged_namespaces(
+    root: Map<String, Value>,
+    prefix_separator: Option<&str>,
+) -> Map<String, Value> {
+    let Some(separator) = prefix_separator.filter(|s| !s.is_empty()) else {
+        return root;
+    };
+    let mut namespaces = Map::new();
+    for (key, value) in root {
+        let Some((namespace, rest)) = key
+            .split_once(separator)
+            .filter(|(namespace, rest)| !namespace.is_empty() && !rest.is_empty())
+        else {
+            namespaces.insert(key, value);
+            continue;
+        };
+        if let Value::Object(entries) = namespaces
+            .entry(namespace.to_string())
+            .or_insert_with(|| Value::Object(Map::new()))
+        {
+            entries.insert(rest.to_string(), value);
+        }
+    }
+    namespaces
+}
+
+// =============================================================================
+// JSON Style Detection and Custom Formatting
+// =============================================================================
+
+/// Detected JSON formatting style from existing file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonStyle {
+    /// Indentation string (e.g., "  ", "    ", "\t")
+    pub indent: String,
+    /// Whether the file uses CRLF line endings
+    pub use_crlf: bool,
+    /// Whether the file ends with a trailing newline
+    pub trailing_newline: bool,
+    /// Whether the file is written on a single line, without indentation or a
+    /// trailing newline
+    pub minify: bool,
+}
+
+impl Default for JsonStyle {
+    fn default() -> Self {
+        Self {
+            indent: "  ".to_string(), // 2 spaces is serde_json default
+            use_crlf: false,
+            trailing_newline: true,
+            minify: false,
+        }
+    }
+}
+
+impl JsonStyle {
+    /// Single-line output, as written with `minify`
+    pub fn minified() -> Self {
+        Self {
+            indent: String::new(),
+            use_crlf: false,
+            trailing_newline: false,
+            minify: true,
+        }
+    }
+}
+
+/// Detect JSON formatting style from file content
+pub fn detect_json_style(content: &str) -> JsonStyle {
+    // A non-empty object on one line was written minified
+    let trimmed = content.trim();
+    if trimmed.len() > 2 && !trimmed.contains('\n') {
+        return JsonStyle::minified();
+    }
+
+    let mut style = JsonStyle {
+        use_crlf: content.contains("\r\n"),
+        trailing_newline: content.ends_with('\n') || content.ends_with("\r\n"),
+        ..JsonStyle::default()
+    };
+
+    // Detect indentation by looking at the first indented line
+    // JSON objects start with "{" and the first key is indented
+    for line in content.lines() {
+        // Skip empty lines and the opening brace
+        if line.trim().is_empty() || line.trim() == "{" || line.trim() == "[" {
+            continue;
+        }
+
+        // Count leading whitespace
+        let trimmed = line.trim_start();
+        if trimmed.starts_with('"') || trimmed.starts_with('}') || trimmed.starts_with(']') {
+            let indent_len = line.len() - trimmed.len();
+            if indent_len > 0 {
+                style.indent = line[..indent_len].to_string();
+                break;
+            }
+        }
+    }
+
+    style
+}
+
+/// Style for rewriting a JSON locale file: `minify` when configured, otherwise
+/// the style detected from `existing` content (pretty-printed again if it was
+/// minified) with the configured indentation
+pub fn configured_json_style(config: &Config, existing: &str) -> JsonStyle {
+    if config.minify {
+        return JsonStyle::minified();
+    }
+    let mut style = if existing.trim().is_empty() {
+        JsonStyle::default()
+    } else {
+        detect_json_style(existing)
+    };
+    if style.minify {
+        style = JsonStyle::default();
+    }
+    if let Some(indent) = config.indentation_string() {
+        style.indent = indent;
+    }
+    match config.end_of_line {
+        EndOfLine::Auto => {}
+        EndOfLine::Lf => style.use_crlf = false,
+        EndOfLine::Crlf => style.use_crlf = true,
+    }
+    style
+}
+
+/// Custom JSON formatter that respects detected style
+struct StylePreservingFormatter {
+    indent: Vec<u8>,
+    newline: Vec<u8>,
+    current_indent: usize,
+}
+
+impl StylePreservingFormatter {
+    fn new(style: &JsonStyle) -> Self {
+        Self {
+            indent: style.indent.as_bytes().to_vec(),
+            newline: if style.use_crlf {
+                b"\r\n".to_vec()
+            } else {
+                b"\n".to_vec()
+            },
+            current_indent: 0,
+        }
+    }
+}
+
+impl Formatter for StylePreservingFormatter {
+    fn begin_array<W>(&mut self, writer: &mut W) -> std::io::Result<()>
+    where
+        W: ?Sized + Write,
+    {
+        self.current_indent += 1;
+        writer.write_all(b"[")
+    }
+
+    fn end_array<W>(&mut self, writer: &mut W) -> std::io::Result<()>
+    where
+        W: ?Sized + Write,
+    {
+        self.current_indent -= 1;
+        writer.write_all(&self.newline)?;
+        for _ in 0..self.current_indent {
+            writer.write_all(&self.indent)?;
+        }
+        writer.write_all(b"]")
+    }
+
+    fn begin_array_value<W>(&mut self, writer: &mut W, first: bool) -> std::io::Result<()>
+    where
+        W: ?Sized + Write,
+    {
+        if first {
+            writer.write_all(&self.newline)?;
+        } else {
+            writer.write_all(b",")?;
+            writer.write_all(&self.newline)?;
+        }
+        for _ in 0..self.current_indent {
+            writer.write_all(&self.indent)?;
+        }
+        Ok(())
+    }
+
+    fn end_array_value<W>(&mut self, _writer: &mut W) -> std::io::Result<()>
+    where
+        W: ?Sized + Write,
+    {
+        Ok(())
+    }
+
+    fn begin_object<W>(&mut self, writer: &mut W) -> std::io::Result<()>
+    where
+        W: ?Sized + Write,
+    {
+        self.current_indent += 1;
+        writer.write_all(b"{")
+    }
+
+    fn end_object<W>(&mut self, writer: &mut W) -> std::io::Result<()>
+    where
+        W: ?Sized + Write,
+    {
+        self.current_indent -= 1;
+        writer.write_all(&self.newline)?;
+        for _ in 0..self.current_indent {
+            writer.write_all(&self.indent)?;
+        }
+        writer.write_all(b"}")
+    }
+
+    fn begin_object_key<W>(&mut self, writer: &mut W, first: bool) -> std::io::Result<()>
+    where
+        W: ?Sized + Write,
+    {
+        if first {
+            writer.write_all(&self.newline)?;
+        } else {
+            writer.write_all(b",")?;
+            writer.write_all(&self.newline)?;
+        }
+        for _ in 0..self.current_indent {
+            writer.write_all(&self.indent)?;
+        }
+        Ok(())
+    }
+
+    fn end_object_key<W>(&mut self, _writer: &mut W) -> std::io::Result<()>
+    where
+        W: ?Sized + Write,
+    {
+        Ok(())
+    }
+
+    fn begin_object_value<W>(&mut self, writer: &mut W) -> std::io::Result<()>
+    where
+        W: ?Sized + Write,
+    {
+        writer.write_all(b": ")
+    }
+
+    fn end_object_value<W>(&mut self, _writer: &mut W) -> std::io::Result<()>
+    where
+        W: ?Sized + Write,
+    {
+        Ok(())
+    }
+}
+
+/// Serialize JSON with style preservation
+fn serialize_with_style<W: Write>(
+    writer: W,
+    value: &Value,
+    style: &JsonStyle,
+    key_sort: KeySort,
+) -> Result<()> {
+    let value = SortedKeys(value, key_sort);
+    if style.minify {
+        serde_json::to_writer(writer, &value)?;
+        return Ok(());
+    }
+    let formatter = StylePreservingFormatter::new(style);
+    let mut serializer = Serializer::with_formatter(writer, formatter);
+    value.serialize(&mut serializer)?;
+    Ok(())
+}
+
+/// Serializes a value with the object keys in `KeySort` order. `Map` keeps its
+/// keys in code point order, so any other order is applied while writing.
+struct SortedKeys<'a>(&'a Value, KeySort);
+
+impl Serialize for SortedKeys<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let SortedKeys(value, key_sort) = *self;
+        match value {
+            Value::Object(map) => {
+                let mut out = serializer.serialize_map(Some(map.len()))?;
+                for (key, value) in sorted_entries(map, key_sort) {
+                    out.serialize_entry(key, &SortedKeys(value, key_sort))?;
+                }
+                out.end()
+            }
+            Value::Array(items) => {
+                let mut out = serializer.serialize_seq(Some(items.len()))?;
+                for item in items {
+                    out.serialize_element(&SortedKeys(item, key_sort))?;
+                }
+                out.end()
+            }
+            other => other.serialize(serializer),
+        }
+    }
+}
+
+/// Pretty-printed JSON with the object keys in `key_sort` order
+pub fn to_string_pretty_sorted(value: &Value, key_sort: KeySort) -> Result<String> {
+    Ok(serde_json::to_string_pretty(&SortedKeys(value, key_sort))?)
+}
+
+fn sorted_entries(map: &Map<String, Value>, key_sort: KeySort) -> Vec<(&String, &Value)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    if key_sort != KeySort::Byte {
+        entries.sort_by(|(a, _), (b, _)| compare_keys(a, b, key_sort));
+    }
+    entries
+}
+
+/// Compare two locale keys in `key_sort` order
+pub fn compare_keys(a: &str, b: &str, key_sort: KeySort) -> Ordering {
+    let natural = match key_sort {
+        KeySort::Byte => return a.cmp(b),
+        KeySort::Folded => false,
+        KeySort::Natural => true,
+    };
+    folded_units(a, natural)
+        .cmp(&folded_units(b, natural))
+        .then_with(|| a.to_lowercase().cmp(&b.to_lowercase()))
+        .then_with(|| a.cmp(b))
+}
+
+/// One comparison unit of a key: punctuation sorts before digits, digits
+/// before letters
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum FoldedUnit {
+    Punctuation(char),
+    /// Significant digits, compared by length first so `10` follows `9`
+    Number(usize, String),
+    Char(char),
+}
+
+/// `key` without accents, lowercased, with digit runs as numbers when `natural`
+fn folded_units(key: &str, natural: bool) -> Vec<FoldedUnit> {
+    let folded: Vec<char> = key
+        .nfd()
+        .filter(|c| !unicode_normalization::char::is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .collect();
+    let mut units = Vec::with_capacity(folded.len());
+    let mut i = 0;
+    while i < folded.len() {
+        if natural && folded[i].is_ascii_digit() {
+            let start = i;
+            while i < folded.len() && folded[i].is_ascii_digit() {
+                i += 1;
+            }
+            let digits: String = folded[start..i].iter().collect();
+            let digits = digits.trim_start_matches('0').to_string();
+            units.push(FoldedUnit::Number(digits.len(), digits));
+            continue;
+        }
+        units.push(if folded[i].is_alphanumeric() {
+            FoldedUnit::Char(folded[i])
+        } else {
+            FoldedUnit::Punctuation(folded[i])
+        });
+        i += 1;
+    }
+    units
+}
+
+/// Represents a conflict when inserting a key into the translation map.
+/// Conflicts occur when the key path collides with existing data structures.
+#[derive(Debug, Clone)]
+pub enum KeyConflict {
+    /// Attempted to create a nested structure at a path that already has a scalar value.
+    /// Example: trying to add "button.submit" when "button" already exists as a string.
+    ValueIsNotObject {
+        /// The key path where the conflict occurred (e.g., "button")
+        key_path: String,
+        /// String representation of the existing value
+        existing_value: String,
+    },
+    /// Attempted to set a scalar value at a path that already has nested children.
+    /// Example: trying to add "button" as a string when "button.submit" already exists.
+    ObjectIsValue {
+        /// The key path where the conflict occurred
+        key_path: String,
+    },
+}
+
+impl std::fmt::Display for KeyConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyConflict::ValueIsNotObject {
+                key_path,
+                existing_value,
+            } => {
+                write!(
+                    f,
+                    "Cannot create nested key at '{}': existing value is '{}' (not an object)",
+                    key_path, existing_value
+                )
+            }
+            KeyConflict::ObjectIsValue { key_path } => {
+                write!(
+                    f,
+                    "Cannot set scalar value at '{}': path contains nested objects",
+                    key_path
+                )
+            }
+        }
+    }
+}
+
+/// Result of syncing keys to a locale file
+#[derive(Debug, Default)]
+pub struct SyncResult {
+    pub file_path: String,
+    pub added_keys: Vec<String>,
+    /// The extracted key behind each `added_keys` entry, in the same order
+    pub added: Vec<AddedKey>,
+    pub existing_keys: usize,
+    /// Keys that were skipped due to conflicts with existing data structures
+    pub conflicts: Vec<SkippedKey>,
+    pub removed_keys: Vec<String>,
+    /// Dead keys kept until their `removalGraceDays` are over
+    pub pending_removal: Vec<String>,
+}
+
+/// Namespace and key, as extracted, of a key added to a locale file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddedKey {
+    pub namespace: String,
+    pub key: String,
+}
+
+/// A key left out of a locale file because of a `KeyConflict`
+#[derive(Debug, Clone)]
+pub struct SkippedKey {
+    pub namespace: String,
+    pub key: String,
+    /// Object path of the key inside the locale file
+    pub path: Vec<String>,
+    /// Value the key would have been written with
+    pub value: Value,
+    /// Value in the way: the scalar where an object was needed, or the object
+    /// where the key's value belongs
+    pub existing_value: Value,
+    pub conflict: KeyConflict,
+}
+
+/// The value blocking `path`: the first scalar on the way, or the value at the
+/// end of the path
+fn blocking_value<'a>(map: &'a Map<String, Value>, path: &[&str]) -> Option<&'a Value> {
+    let (first, rest) = path.split_first()?;
+    let mut current = map.get(*first)?;
+    for part in rest {
+        current = match current {
+            Value::Object(nested) => nested.get(*part)?,
+            Value::Array(items) => items.get(part.parse::<usize>().ok()?)?,
+            scalar => return Some(scalar),
+        };
+    }
+    Some(current)
+}
+
+#[derive(Debug, Default)]
+pub struct PreserveMatcher {
+    key_patterns: Vec<Pattern>,
+    namespaced_patterns: Vec<Pattern>,
+    ns_separator: String,
+    /// `keyFilter` of a targeted run; keys outside it are never added or pruned
+    scope: Option<Box<PreserveMatcher>>,
+    /// With `removalGraceDays`, the only dead keys that may be pruned; the
+    /// others are kept and reported as pending removal
+    removable: Option<HashSet<(String, String)>>,
+}
+
+impl PreserveMatcher {
+    /// `preservePatterns` of `config`, scoped to its `keyFilter`
+    pub fn for_config(config: &Config) -> Result<Self> {
+        let mut matcher = Self::new(&config.preserve_patterns, &config.ns_separator)?;
+        if !config.key_filter.is_empty() {
+            let scope = Self::new(&config.key_filter, &config.ns_separator)
+                .context("Invalid --filter-key pattern")?;
+            matcher.scope = Some(Box::new(scope));
+        }
+        Ok(matcher)
+    }
+
+    /// Only prune the dead keys in `removable` (all of them when `None`)
+    pub fn with_removable(mut self, removable: Option<HashSet<(String, String)>>) -> Self {
+        self.removable = removable;
+        self
+    }
+
+    /// Whether the dead `key` is still inside its removal grace period
+    pub fn in_grace(&self, namespace: &str, key: &str) -> bool {
+        self.removable
+            .as_ref()
+            .is_some_and(|removable| !removable.contains(&(namespace.to_string(), key.to_string())))
+    }
+
+    /// Whether `key` is inside the run's key filter (always true without one)
+    pub fn in_scope(&self, namespace: &str, key: &str) -> bool {
+        self.scope
+            .as_ref()
+            .is_none_or(|scope| scope.matches(namespace, key))
+    }
+
+    pub fn new(patterns: &[String], ns_separator: &str) -> Result<Self> {
+        let mut key_patterns = Vec::new();
+        let mut namespaced_patterns = Vec::new();
+
+        for pattern in patterns {
+            let compiled = Pattern::new(pattern)
+                .with_context(|| format!("Invalid preserve pattern: {}", pattern))?;
+            if !ns_separator.is_empty() && pattern.contains(ns_separator) {
+                namespaced_patterns.push(compiled);
+            } else {
+                key_patterns.push(compiled);
+            }
+        }
+
+        Ok(Self {
+            key_patterns,
+            namespaced_patterns,
+            ns_separator: ns_separator.to_string(),
+            scope: None,
+            removable: None,
+        })
+    }
+
+    pub fn matches(&self, namespace: &str, key: &str) -> bool {
+        if self.key_patterns.iter().any(|pattern| pattern.matches(key)) {
+            return true;
+        }
+
+        if self.namespaced_patterns.is_empty() {
+            return false;
+        }
+
+        let namespaced_key = if self.ns_separator.is_empty() {
+            format!("{}{}", namespace, key)
+        } else {
+            format!("{}{}{}", namespace, self.ns_separator, key)
+        };
+
+        self.namespaced_patterns
+            .iter()
+            .any(|pattern| pattern.matches(&namespaced_key))
+    }
+}
+
+/// Locale file entry listing glob patterns (relative to the containing object)
+/// of sibling keys that must never be pruned, e.g. `"__preserve__": ["dynamic.*"]`
+pub const PRESERVE_MARKER_KEY: &str = "__preserve__";
+
+/// Whether `key` is a `__preserve__` marker or a `"//"` comment entry rather than a translation
+pub fn is_marker_key(key: &str) -> bool {
+    key == PRESERVE_MARKER_KEY || key.starts_with("//")
+}
+
+/// Remove marker entries from `map` and every object nested in it
+pub fn strip_marker_keys(map: &mut Map<String, Value>) {
+    map.retain(|key, _| !is_marker_key(key));
+    for value in map.values_mut() {
+        if let Value::Object(nested) = value {
+            strip_marker_keys(nested);
+        }
+    }
+}
+
+/// Patterns of the `__preserve__` marker in `node`, anchored at `parent_path`.
+/// Invalid patterns are ignored.
+pub(crate) fn marker_patterns(
+    node: &Map<String, Value>,
+    parent_path: &str,
+    key_separator: &str,
+) -> Vec<Pattern> {
+    let Some(Value::Array(entries)) = node.get(PRESERVE_MARKER_KEY) else {
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .filter_map(Value::as_str)
+        .filter_map(|pattern| {
+            let anchored = if parent_path.is_empty() {
+                pattern.to_string()
+            } else {
+                format!(
+                    "{}{}{}",
+                    Pattern::escape(parent_path),
+                    key_separator,
+                    pattern
+                )
+            };
+            Pattern::new(&anchored).ok()
+        })
+        .collect()
+}
+
+/// Read a JSON locale file, returning an empty map if it doesn't exist
+pub fn read_locale_file(path: &Path) -> Result<Map<String, Value>> {
+    read_locale_file_with_fs(path, &crate::fs::RealFileSystem)
+}
+
+/// Read a JSON locale file using the provided FileSystem
+pub fn read_locale_file_with_fs<F: FileSystem>(path: &Path, fs: &F) -> Result<Map<String, Value>> {
+    if !fs.exists(path) {
+        return Ok(Map::new());
+    }
+
+    let content = fs
+        .open_shared(path)
+        .and_then(|mut file| file.content_string())
+        .with_context(|| format!("Failed to read locale file: {}", path.display()))?;
+
+    // Handle empty files
+    if content.trim().is_empty() {
+        return Ok(Map::new());
+    }
+
+    let map: Map<String, Value> = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse JSON in: {}", path.display()))?;
+
+    Ok(map)
+}
+
+/// Single-file locale (`<output>/<locale>.<ext>`) when there is no locale directory
+pub fn flat_locale_file(output_dir: &Path, locale: &str, ext: &str) -> Option<PathBuf> {
+    let path = output_dir.join(format!("{}.{}", locale, ext));
+    (path.is_file() && !output_dir.join(locale).is_dir()).then_some(path)
+}
+
+/// Infix between a namespace and the index of one of its shards
+/// (`common.shard-2.json`)
+const SHARD_MARKER: &str = ".shard-";
+
+/// Namespace of a shard file stem (`common.shard-2` -> `common`)
+pub(crate) fn shard_namespace(stem: &str) -> Option<&str> {
+    let (namespace, index) = stem.rsplit_once(SHARD_MARKER)?;
+    (!namespace.is_empty() && !index.is_empty() && index.bytes().all(|b| b.is_ascii_digit()))
+        .then_some(namespace)
+}
+
+/// Namespace held by a file of a one-file-per-namespace layout; when
+/// `sharded` (see [`Config::sharded_locale_files`]), shards
+/// (`common.shard-1.json`, `common.shard-2.json`) all hold `common`
+pub fn file_namespace(path: &Path, sharded: bool) -> Option<&str> {
+    let stem = path.file_stem()?.to_str()?;
+    Some(
+        sharded
+            .then(|| shard_namespace(stem))
+            .flatten()
+            .unwrap_or(stem),
+    )
+}
+
+/// `<locale>/common.json` -> `<locale>/common.shard-<index>.json`
+fn shard_path(path: &Path, index: usize) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) => path.with_file_name(format!("{}{}{}.{}", stem, SHARD_MARKER, index, ext)),
+        None => path.with_file_name(format!("{}{}{}", stem, SHARD_MARKER, index)),
+    }
+}
+
+/// Shards of the namespace file `path` on disk, in index order
+fn existing_shards<F: FileSystem>(path: &Path, fs: &F) -> Vec<PathBuf> {
+    let (Some(parent), Some(stem)) = (path.parent(), path.file_stem().and_then(|s| s.to_str()))
+    else {
+        return Vec::new();
+    };
+    let mut shards: Vec<(usize, PathBuf)> = fs
+        .read_dir(parent)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|shard| shard.extension() == path.extension() && fs.is_file(shard))
+        .filter_map(|shard| {
+            let shard_stem = shard.file_stem()?.to_str()?;
+            if shard_namespace(shard_stem) != Some(stem) {
+                return None;
+            }
+            let index = shard_stem[stem.len() + SHARD_MARKER.len()..].parse().ok()?;
+            Some((index, shard))
+        })
+        .collect();
+    shards.sort();
+    shards.into_iter().map(|(_, shard)| shard).collect()
+}
+
+/// Split a namespace into shards of at most `max_keys` leaf keys, filling each
+/// shard with whole top-level entries in key order. A namespace within the limit
+/// (or a single oversized top-level entry) stays one shard.
+fn split_into_shards(content: &Map<String, Value>, max_keys: usize) -> Vec<Map<String, Value>> {
+    fn leaf_count(value: &Value) -> usize {
+        match value {
+            Value::Object(map) => map.values().map(leaf_count).sum(),
+            _ => 1,
+        }
+    }
+
+    let mut shards = vec![Map::new()];
+    let mut count = 0;
+    for (key, value) in content {
+        let leaves = leaf_count(value);
+        if count > 0 && count + leaves > max_keys {
+            shards.push(Map::new());
+            count = 0;
+        }
+        if let Some(shard) = shards.last_mut() {
+            shard.insert(key.clone(), value.clone());
+        }
+        count += leaves;
+    }
+    shards
+}
+
+/// Merge a shard read from disk into the namespace assembled so far
+pub(crate) fn merge_shard(into: &mut Map<String, Value>, shard: Map<String, Value>) {
+    for (key, value) in shard {
+        match (into.get_mut(&key), value) {
+            (Some(Value::Object(existing)), Value::Object(nested)) => merge_shard(existing, nested),
+            (_, value) => {
+                into.insert(key, value);
+            }
+        }
+    }
+}
+
+/// List the locale files (with the configured output extension) of one locale, sorted by path
+pub fn locale_files(config: &Config, output_dir: &str, locale: &str) -> Result<Vec<PathBuf>> {
+    let mut symlink_guard = SymlinkGuard::new(config.follow_symlinks);
+    // Several names for one file are kept: each is its own namespace
+    let mut admit = |base: &Path, path: &Path| {
+        matches!(
+            symlink_guard.admit(base, path),
+            Ok(()) | Err(Skipped::Duplicate)
+        )
+    };
+    if config.flat_locale_files {
+        let path = locale_namespace_file_path(config, output_dir, locale, "");
+        return Ok(if path.is_file() && admit(Path::new(output_dir), &path) {
+            vec![path]
+        } else {
+            Vec::new()
+        });
+    }
+    let locale_dir = Path::new(output_dir).join(locale);
+    if !locale_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let extension = config.output_extension();
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(&locale_dir)
+        .with_context(|| format!("Failed to read: {}", locale_dir.display()))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path.extension().and_then(|e| e.to_str()) == Some(extension)
+                && admit(&locale_dir, path)
+        })
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Why a file under the locales directory is never read or written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UntrackedReason {
+    /// Not the extension of `outputFormat`
+    Extension,
+    /// Directory or flat file of a locale missing from `locales`
+    UnknownLocale(String),
+    /// Not where the layout looks for locale files: below a locale directory,
+    /// or at the root when each locale has its own directory
+    Nesting,
+}
+
+impl std::fmt::Display for UntrackedReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UntrackedReason::Extension => write!(f, "extension is not read by outputFormat"),
+            UntrackedReason::UnknownLocale(locale) => {
+                write!(f, "'{}' is not a configured locale", locale)
+            }
+            UntrackedReason::Nesting => write!(f, "outside the locale file layout"),
+        }
+    }
+}
+
+/// A file or directory under the locales directory that no command reads or writes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UntrackedFile {
+    pub path: PathBuf,
+    pub reason: UntrackedReason,
+}
+
+/// Entries of `output_dir` outside the layout [`locale_files`] reads: wrong
+/// extensions, directories of unknown locales (reported once, not per file)
+/// and unexpected nesting. Hidden entries and `metadataFile` are skipped.
+pub fn untracked_locale_files(config: &Config, output_dir: &str) -> Result<Vec<UntrackedFile>> {
+    let root = Path::new(output_dir);
+    if !root.is_dir() {
+        return Ok(Vec::new());
+    }
+    let extension = config.output_extension();
+    let metadata_file = config.metadata_file.as_deref().map(normalized_path);
+    let entries = |dir: &Path| -> Result<Vec<PathBuf>> {
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+            .with_context(|| format!("Failed to read: {}", dir.display()))?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                !path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with('.'))
+                    && metadata_file.as_deref() != Some(&normalized_path(path))
+            })
+            .collect();
+        paths.sort();
+        Ok(paths)
+    };
+    let has_extension = |path: &Path| path.extension().and_then(|e| e.to_str()) == Some(extension);
+    let is_locale = |name: &str| config.locales.iter().any(|locale| locale == name);
+
+    let mut untracked = Vec::new();
+    let mut push = |path: PathBuf, reason| untracked.push(UntrackedFile { path, reason });
+    for path in entries(root)? {
+        if path.is_dir() && !config.flat_locale_files {
+            let name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default()
+                .to_string();
+            if !is_locale(&name) {
+                push(path, UntrackedReason::UnknownLocale(name));
+                continue;
+            }
+            for path in entries(&path)? {
+                if path.is_dir() {
+                    push(path, UntrackedReason::Nesting);
+                } else if !has_extension(&path) {
+                    push(path, UntrackedReason::Extension);
+                }
+            }
+        } else if path.is_dir() || !config.flat_locale_files {
+            push(path, UntrackedReason::Nesting);
+        } else if !has_extension(&path) {
+            push(path, UntrackedReason::Extension);
+        } else {
+            let stem = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or_default()
+                .to_string();
+            if !is_locale(&stem) {
+                push(path, UntrackedReason::UnknownLocale(stem));
+            }
+        }
+    }
+    Ok(untracked)
+}
+
+/// `path` without `.` components, for comparing configured paths
+fn normalized_path(path: impl AsRef<Path>) -> PathBuf {
+    path.as_ref()
+        .components()
+        .filter(|component| !matches!(component, std::path::Component::CurDir))
+        .collect()
+}
+
+/// Load every namespace of a locale from disk, keyed by namespace name.
+/// With `mergeNamespaces`, the top-level keys of the merged file are treated as namespaces.
+pub fn load_locale_namespaces(
+    config: &Config,
+    output_dir: &str,
+    locale: &str,
+) -> Result<BTreeMap<String, Map<String, Value>>> {
+    LocaleStore::new().namespaces(config, output_dir, locale)
+}
+
+/// String values of loaded namespaces keyed by (namespace, key path), skipping marker keys
+pub fn flatten_string_values(
+    namespaces: &BTreeMap<String, Map<String, Value>>,
+    key_separator: &str,
+) -> BTreeMap<(String, String), String> {
+    flatten_leaf_values(namespaces, key_separator)
+        .into_iter()
+        .filter_map(|(id, value)| match value {
+            Value::String(text) => Some((id, text)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Non-object values (strings, arrays, numbers, ...) of loaded namespaces keyed by
+/// (namespace, key path), skipping marker keys
+pub fn flatten_leaf_values(
+    namespaces: &BTreeMap<String, Map<String, Value>>,
+    key_separator: &str,
+) -> BTreeMap<(String, String), Value> {
+    fn collect(
+        map: &Map<String, Value>,
+        namespace: &str,
+        prefix: &str,
+        key_separator: &str,
+        out: &mut BTreeMap<(String, String), Value>,
+    ) {
+        for (key, value) in map {
+            if is_marker_key(key) {
+                continue;
+            }
+            let path = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{}{}{}", prefix, key_separator, key)
+            };
+            match value {
+                Value::Object(nested) => collect(nested, namespace, &path, key_separator, out),
+                value => {
+                    out.insert((namespace.to_string(), path), value.clone());
+                }
+            }
+        }
+    }
+
+    let mut out = BTreeMap::new();
+    for (namespace, map) in namespaces {
+        collect(map, namespace, "", key_separator, &mut out);
+    }
+    out
+}
+
+/// Result of inserting a nested key
+enum InsertResult {
+    /// Key was newly added
+    Added,
+    /// Key already existed (not modified)
+    Existed,
+    /// Conflict occurred (data structure mismatch)
+    Conflict(KeyConflict),
+}
+
+/// Insert a nested key path, creating intermediate objects as needed.
+/// Numeric segments address the entries of existing arrays (`steps.0.title`).
+/// Returns InsertResult indicating whether the key was added, existed, or conflicted.
+///
+/// This function uses iterative approach instead of recursion to prevent
+/// stack overflow with deeply nested keys (DoS protection).
+fn insert_nested_key(
+    obj: &mut Map<String, Value>,
+    path: &[&str],
+    default_value: Value,
+) -> InsertResult {
+    if path.is_empty() {
+        return InsertResult::Existed;
+    }
+
+    // Use iterative approach to prevent stack overflow with deep nesting
+    let mut current = obj;
+    let mut current_path = Vec::new();
+    let mut i = 0;
+
+    while i < path.len() {
+        let key = path[i];
+        current_path.push(key);
+
+        if i == path.len() - 1 {
+            // Leaf node - insert the value
+            if let Some(existing) = current.get(key) {
+                // Check if we're trying to set a scalar where an object exists
+                if existing.is_object() {
+                    return InsertResult::Conflict(KeyConflict::ObjectIsValue {
+                        key_path: current_path.join("."),
+                    });
+                }
+                return InsertResult::Existed;
+            } else {
+                current.insert(key.to_string(), default_value);
+                return InsertResult::Added;
+            }
+        }
+
+        // Intermediate node - ensure it's an object
+        let mut entry = current
+            .entry(key.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+
+        // Step into array entries addressed by index
+        loop {
+            let index = match &*entry {
+                Value::Array(items) => path
+                    .get(i + 1)
+                    .and_then(|segment| segment.parse::<usize>().ok())
+                    .filter(|index| *index < items.len()),
+                _ => None,
+            };
+            let Some(index) = index else {
+                break;
+            };
+            i += 1;
+            current_path.push(path[i]);
+            entry = &mut entry[index];
+            if i == path.len() - 1 {
+                if entry.is_object() {
+                    return InsertResult::Conflict(KeyConflict::ObjectIsValue {
+                        key_path: current_path.join("."),
+                    });
+                }
+                return InsertResult::Existed;
+            }
+        }
+
+        match entry {
+            Value::Object(ref mut nested) => {
+                current = nested;
+            }
+            other => {
+                // Key exists but is not an object - conflict!
+                return InsertResult::Conflict(KeyConflict::ValueIsNotObject {
+                    key_path: current_path.join("."),
+                    existing_value: format!("{}", other),
+                });
+            }
+        }
+        i += 1;
+    }
+
+    InsertResult::Existed
+}
+
+/// Sort all keys in a JSON object alphabetically (including nested objects).
+///
+/// Uses a controlled recursion with explicit depth limit to prevent stack overflow
+/// from malicious inputs (DoS protection). Maximum depth is 100 levels.
+pub fn sort_keys_alphabetically(map: &Map<String, Value>) -> Map<String, Value> {
+    const MAX_DEPTH: usize = 100;
+    sort_keys_with_depth(map, 0, MAX_DEPTH)
+}
+
+/// Internal function with depth tracking to prevent stack overflow
+fn sort_keys_with_depth(
+    map: &Map<String, Value>,
+    depth: usize,
+    max_depth: usize,
+) -> Map<String, Value> {
+    let mut sorted = Map::new();
+    let mut keys: Vec<_> = map.keys().collect();
+    keys.sort();
+
+    for key in keys {
+        if let Some(value) = map.get(key) {
+            let sorted_value = match value {
+                Value::Object(nested) if depth < max_depth => {
+                    Value::Object(sort_keys_with_depth(nested, depth + 1, max_depth))
+                }
+                Value::Object(nested) => {
+                    // At max depth, just clone without sorting deeper
+                    Value::Object(nested.clone())
+                }
+                other => other.clone(),
+            };
+            sorted.insert(key.clone(), sorted_value);
+        }
+    }
+
+    sorted
+}
+
+/// Merge extracted keys into an existing translation map.
+/// - New keys are added with default values (explicit or config-level fallback)
+/// - Existing keys are preserved unless removal is requested
+/// - preservePatterns keep dynamic keys even when removal is enabled
+pub(crate) fn merge_keys(
+    existing: &mut Map<String, Value>,
+    keys: &[ExtractedKey],
+    target_namespace: &str,
+    config: &Config,
+    preserve_matcher: &PreserveMatcher,
+) -> SyncResult {
+    let mut result = SyncResult::default();
+    let mut seen_paths: HashSet<String> = HashSet::new();
+    let mut seen_object_roots: Vec<String> = Vec::new();
+    let default_namespace = effective_namespace(&config.default_namespace);
+    let fallback_default = config.default_value.as_deref();
+    let key_separator = config.key_separator.as_str();
+
+    for key in keys {
+        let key_namespace = key.namespace.as_deref().unwrap_or(default_namespace);
+
+        if !config.merge_namespaces && key_namespace != target_namespace {
+            continue;
+        }
+        if !preserve_matcher.in_scope(key_namespace, &key.key) {
+            continue;
+        }
+
+        let effective_key = if config.merge_namespaces {
+            merge_namespace_key(config, key_namespace, &key.key)
+        } else {
+            key.key.clone()
+        };
+
+        if let Some(root) = effective_key.strip_suffix(".*") {
+            seen_paths.insert(root.to_string());
+            seen_object_roots.push(root.to_string());
+            continue;
+        }
+
+        let wanted_value = || match &key.default_value {
+            Some(default_value) => Value::from(default_value.clone()),
+            None => Value::String(fallback_default.unwrap_or("").to_string()),
+        };
+        let added = || AddedKey {
+            namespace: key_namespace.to_string(),
+            key: key.key.clone(),
+        };
+        let skipped = |path: &[&str], existing: &Map<String, Value>, conflict| SkippedKey {
+            namespace: key_namespace.to_string(),
+            key: key.key.clone(),
+            path: path.iter().map(|part| part.to_string()).collect(),
+            value: wanted_value(),
+            existing_value: blocking_value(existing, path).cloned().unwrap_or_default(),
+            conflict,
+        };
+
+        seen_paths.insert(effective_key.clone());
+
+        if key_separator.is_empty() {
+            if let Some(existing_value) = existing.get(&effective_key) {
+                if existing_value.is_object() {
+                    let conflict = KeyConflict::ObjectIsValue {
+                        key_path: effective_key.clone(),
+                    };
+                    result
+                        .conflicts
+                        .push(skipped(&[effective_key.as_str()], existing, conflict));
+                } else {
+                    result.existing_keys += 1;
+                }
+            } else {
+                existing.insert(effective_key.clone(), wanted_value());
+                result.added_keys.push(effective_key.clone());
+                result.added.push(added());
+            }
+        } else {
+            let merged_parts = config
+                .merge_namespaces
+                .then(|| merged_key_parts(config, key_namespace, &key.key));
+            let parts: Vec<&str> = match &merged_parts {
+                Some(parts) => parts.iter().map(String::as_str).collect(),
+                None => effective_key.split(key_separator).collect(),
+            };
+            match insert_nested_key(existing, &parts, wanted_value()) {
+                InsertResult::Added => {
+                    result.added_keys.push(effective_key.clone());
+                    result.added.push(added());
+                }
+                InsertResult::Existed => {
+                    result.existing_keys += 1;
+                }
+                InsertResult::Conflict(conflict) => {
+                    result.conflicts.push(skipped(&parts, existing, conflict));
+                }
+            }
+        }
+    }
+
+    if config.remove_unused_keys {
+        let mut removed = Vec::new();
+        let mut pending = Vec::new();
+        prune_unused_keys(
+            existing,
+            "",
+            key_separator,
+            target_namespace,
+            &seen_paths,
+            &seen_object_roots,
+            preserve_matcher,
+            &[],
+            &mut removed,
+            &mut pending,
+        );
+        result.removed_keys = removed;
+        result.pending_removal = pending;
+    }
+
+    result
+}
+
+fn prune_unused_keys(
+    node: &mut Map<String, Value>,
+    parent_path: &str,
+    key_separator: &str,
+    namespace: &str,
+    seen_paths: &HashSet<String>,
+    seen_object_roots: &[String],
+    preserve_matcher: &PreserveMatcher,
+    inherited_markers: &[Pattern],
+    removed: &mut Vec<String>,
+    pending: &mut Vec<String>,
+) -> bool {
+    let mut keys_to_remove = Vec::new();
+    let mut markers = inherited_markers.to_vec();
+    markers.extend(marker_patterns(node, parent_path, key_separator));
+
+    for (key, value) in node.iter_mut() {
+        let current_path = if parent_path.is_empty() || key_separator.is_empty() {
+            key.clone()
+        } else {
+            format!("{}{}{}", parent_path, key_separator, key)
+        };
+
+        // In-file markers protect the whole subtree they match
+        if is_marker_key(key) || markers.iter().any(|pattern| pattern.matches(&current_path)) {
+            continue;
+        }
+
+        let keep = seen_paths.contains(&current_path)
+            || seen_object_roots.iter().any(|root| {
+                current_path == *root
+                    || (!root.is_empty()
+                        && if key_separator.is_empty() {
+                            current_path.starts_with(root)
+                        } else {
+                            current_path.starts_with(&format!("{}{}", root, key_separator))
+                        })
+            })
+            || preserve_matcher.matches(namespace, &current_path);
+
+        if value.is_array() {
+            // Arrays are one value; keep them while the key or any entry is used
+            let entry_prefix = format!("{}{}", current_path, key_separator);
+            let used = keep
+                || (!key_separator.is_empty()
+                    && seen_paths
+                        .iter()
+                        .any(|path| path.starts_with(&entry_prefix)));
+            if !used && preserve_matcher.in_scope(namespace, &current_path) {
+                if preserve_matcher.in_grace(namespace, &current_path) {
+                    pending.push(current_path);
+                } else {
+                    keys_to_remove.push((key.clone(), current_path));
+                }
+            }
+        } else if let Some(obj) = value.as_object_mut() {
+            // Objects emptied by the run are dropped, untouched empty ones only in scope
+            let keep =
+                keep || (obj.is_empty() && !preserve_matcher.in_scope(namespace, &current_path));
+            let child_empty = prune_unused_keys(
+                obj,
+                &current_path,
+                key_separator,
+                namespace,
+                seen_paths,
+                seen_object_roots,
+                preserve_matcher,
+                &markers,
+                removed,
+                pending,
+            );
+            if child_empty && !keep {
+                keys_to_remove.push((key.clone(), current_path));
+            }
+        } else if !keep && preserve_matcher.in_scope(namespace, &current_path) {
+            if preserve_matcher.in_grace(namespace, &current_path) {
+                pending.push(current_path);
+            } else {
+                keys_to_remove.push((key.clone(), current_path));
+            }
+        }
+    }
+
+    for (key, path) in keys_to_remove {
+        node.remove(&key);
+        removed.push(path);
+    }
+
+    node.is_empty()
+}
+
+pub fn parse_locale_value_str(content: &str, format: OutputFormat, path: &Path) -> Result<Value> {
+    let map = parse_locale_map(content, format, path)?;
+    Ok(Value::Object(map))
+}
+
+fn parse_locale_map(
+    content: &str,
+    format: OutputFormat,
+    path: &Path,
+) -> Result<Map<String, Value>> {
+    if content.trim().is_empty() {
+        return Ok(Map::new());
+    }
+
+    let map: Map<String, Value> = match format {
+        OutputFormat::Json => serde_json::from_str(content)
+            .with_context(|| format!("Failed to parse JSON in: {}", path.display()))?,
+        OutputFormat::Json5 => json5::from_str(content)
+            .with_context(|| format!("Failed to parse JSON5 in: {}", path.display()))?,
+        OutputFormat::JsEsm | OutputFormat::JsCjs | OutputFormat::Ts => {
+            locale_module::module_catalog(content).with_context(|| {
+                format!("Failed to read catalog of JS/TS module: {}", path.display())
+            })?
+        }
+    };
+
+    Ok(map)
+}
+
+fn write_json_locale_with_fs<F: FileSystem>(
+    path: &Path,
+    content: &Map<String, Value>,
+    key_sort: KeySort,
+    style: Option<&JsonStyle>,
+    fs: &F,
+) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs.create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    let style = if let Some(style) = style.cloned() {
+        style
+    } else if fs.exists(path) {
+        let existing = fs
+            .read_to_string(path)
+            .with_context(|| format!("Failed to read existing file: {}", path.display()))?;
+        detect_json_style(&existing)
+    } else {
+        JsonStyle::default()
+    };
+    let mut buffer = Vec::new();
+    serialize_with_style(
+        &mut buffer,
+        &Value::Object(content.clone()),
+        &style,
+        key_sort,
+    )?;
+    if style.trailing_newline {
+        buffer.extend_from_slice(if style.use_crlf { b"\r\n" } else { b"\n" });
+    }
+
+    fs.atomic_write(path, &buffer)
+        .with_context(|| format!("Failed to write locale file: {}", path.display()))
+}
+
+fn write_json5_locale_with_fs<F: FileSystem>(
+    path: &Path,
+    content: &Map<String, Value>,
+    key_sort: KeySort,
+    fs: &F,
+) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs.create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    let existing = if fs.exists(path) {
+        fs.read_to_string(path).ok()
+    } else {
+        None
+    };
+    let (prefix_comments, suffix_comments, prefer_trailing_comma) = existing
+        .as_deref()
+        .map(extract_json5_preservation_hints)
+        .unwrap_or_default();
+    let preserved_numbers = existing
+        .as_deref()
+        .map(|current| build_json5_numeric_preservation_map(current, content))
+        .unwrap_or_default();
+
+    let mut json_body = render_json5_with_preserved_numbers(content, &preserved_numbers, key_sort)?;
+    if prefer_trailing_comma {
+        json_body = add_trailing_commas_to_pretty_json(&json_body);
+    }
+
+    let mut output = String::new();
+    if !prefix_comments.is_empty() {
+        output.push_str(&prefix_comments);
+        if !prefix_comments.ends_with('\n') {
+            output.push('\n');
+        }
+    }
+    output.push_str(&json_body);
+    if !suffix_comments.is_empty() {
+        output.push('\n');
+        output.push_str(&suffix_comments);
+    }
+    output.push('\n');
+    let crlf = existing
+        .as_deref()
+        .is_some_and(|current| current.contains("\r\n"));
+    let buffer = normalize_newlines(&output, crlf).into_bytes();
+    fs.atomic_write(path, &buffer)
+        .with_context(|| format!("Failed to write locale file: {}", path.display()))
+}
+
+fn extract_json5_preservation_hints(content: &str) -> (String, String, bool) {
+    let first_brace = content.find('{');
+    let last_brace = content.rfind('}');
+    let prefix = first_brace
+        .map(|idx| content[..idx].trim_end().to_string())
+        .unwrap_or_default();
+    let suffix = last_brace
+        .map(|idx| content[idx + 1..].trim_start().to_string())
+        .unwrap_or_default();
+    let prefer_trailing_comma = content.contains(",\n}") || content.contains(",\n]");
+    (prefix, suffix, prefer_trailing_comma)
+}
+
+fn add_trailing_commas_to_pretty_json(pretty_json: &str) -> String {
+    let mut lines: Vec<String> = pretty_json.lines().map(|l| l.to_string()).collect();
+    for i in 1..lines.len() {
+        let trimmed = lines[i].trim();
+        if trimmed == "}" || trimmed == "]" {
+            // find previous non-empty line
+            let mut prev_idx = None;
+            for j in (0..i).rev() {
+                if !lines[j].trim().is_empty() {
+                    prev_idx = Some(j);
+                    break;
+                }
+            }
+            if let Some(j) = prev_idx {
+                let prev_trimmed = lines[j].trim();
+                if !(prev_trimmed.ends_with(',')
+                    || prev_trimmed.ends_with('{')
+                    || prev_trimmed.ends_with('['))
+                {
+                    lines[j].push(',');
+                }
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+fn render_json5_with_preserved_numbers(
+    content: &Map<String, Value>,
+    preserved_numbers: &HashMap<String, String>,
+    key_sort: KeySort,
+) -> Result<String> {
+    let root = Value::Object(content.clone());
+    let mut out = String::new();
+    write_json5_value(&root, "", 0, preserved_numbers, key_sort, &mut out)?;
+    Ok(out)
+}
+
+fn write_json5_value(
+    value: &Value,
+    pointer: &str,
+    depth: usize,
+    preserved_numbers: &HashMap<String, String>,
+    key_sort: KeySort,
+    out: &mut String,
+) -> Result<()> {
+    match value {
+        Value::Object(map) => {
+            if map.is_empty() {
+                out.push_str("{}");
+                return Ok(());
+            }
+            out.push('{');
+            out.push('\n');
+            let mut iter = sorted_entries(map, key_sort).into_iter().peekable();
+            while let Some((key, v)) = iter.next() {
+                out.push_str(&"  ".repeat(depth + 1));
+                out.push_str(&serde_json::to_string(key)?);
+                out.push_str(": ");
+                let child_pointer = pointer_child(pointer, key);
+                write_json5_value(
+                    v,
+                    &child_pointer,
+                    depth + 1,
+                    preserved_numbers,
+                    key_sort,
+                    out,
+                )?;
+                if iter.peek().is_some() {
+                    out.push(',');
+                }
+                out.push('\n');
+            }
+            out.push_str(&"  ".repeat(depth));
+            out.push('}');
+        }
+        Value::Array(arr) => {
+            if arr.is_empty() {
+                out.push_str("[]");
+                return Ok(());
+            }
+            out.push('[');
+            out.push('\n');
+            for (idx, item) in arr.iter().enumerate() {
+                out.push_str(&"  ".repeat(depth + 1));
+                let child_pointer = pointer_index(pointer, idx);
+                write_json5_value(
+                    item,
+                    &child_pointer,
+                    depth + 1,
+                    preserved_numbers,
+                    key_sort,
+                    out,
+                )?;
+                if idx + 1 < arr.len() {
+                    out.push(',');
+                }
+                out.push('\n');
+            }
+            out.push_str(&"  ".repeat(depth));
+            out.push(']');
+        }
+        Value::Number(_) => {
+            if let Some(raw) = preserved_numbers.get(pointer) {
+                out.push_str(raw);
+            } else {
+                out.push_str(&serde_json::to_string(value)?);
+            }
+        }
+        _ => out.push_str(&serde_json::to_string(value)?),
+    }
+
+    Ok(())
+}
+
+fn pointer_child(parent: &str, key: &str) -> String {
+    let escaped = key.replace('~', "~0").replace('/', "~1");
+    format!("{}/{}", parent, escaped)
+}
+
+fn pointer_index(parent: &str, idx: usize) -> String {
+    format!("{}/{}", parent, idx)
+}
+
+fn build_json5_numeric_preservation_map(
+    existing_content: &str,
+    new_content: &Map<String, Value>,
+) -> HashMap<String, String> {
+    let Some(existing_value) = json5::from_str::<Value>(existing_content).ok() else {
+        return HashMap::new();
+    };
+    let new_value = Value::Object(new_content.clone());
+    let raw_numbers = collect_json5_number_literals(existing_content);
+    let mut preserved = HashMap::new();
+
+    for (pointer, literal) in raw_numbers {
+        let Some(existing_number) = existing_value.pointer(&pointer) else {
+            continue;
+        };
+        let Some(new_number) = new_value.pointer(&pointer) else {
+            continue;
+        };
+        if numeric_values_equal(existing_number, new_number) {
+            preserved.insert(pointer, literal);
+        }
+    }
+
+    preserved
+}
+
+fn numeric_values_equal(left: &Value, right: &Value) -> bool {
+    let (Value::Number(ln), Value::Number(rn)) = (left, right) else {
+        return false;
+    };
+    if ln == rn {
+        return true;
+    }
+
+    let lf = ln
+        .as_f64()
+        .or_else(|| ln.as_i64().map(|v| v as f64))
+        .or_else(|| ln.as_u64().map(|v| v as f64));
+    let rf = rn
+        .as_f64()
+        .or_else(|| rn.as_i64().map(|v| v as f64))
+        .or_else(|| rn.as_u64().map(|v| v as f64));
+
+    matches!((lf, rf), (Some(a), Some(b)) if a == b)
+}
+
+fn collect_json5_number_literals(content: &str) -> HashMap<String, String> {
+    let mut scanner = Json5NumberScanner::new(content);
+    let mut out = HashMap::new();
+    scanner.scan_root(&mut out);
+    out
+}
+
+struct Json5NumberScanner<'a> {
+    bytes: &'a [u8],
+    idx: usize,
+}
+
+impl<'a> Json5NumberScanner<'a> {
+    fn new(content: &'a str) -> Self {
+        Self {
+            bytes: content.as_bytes(),
+            idx: 0,
+        }
+    }
+
+    fn scan_root(&mut self, out: &mut HashMap<String, String>) {
+        self.skip_ws_and_comments();
+        self.scan_value("", out);
+    }
+
+    fn scan_value(&mut self, pointer: &str, out: &mut HashMap<String, String>) {
+        self.skip_ws_and_comments();
+        let Some(ch) = self.peek() else {
+            return;
+        };
+        match ch {
+            b'{' => self.scan_object(pointer, out),
+            b'[' => self.scan_array(pointer, out),
+            b'"' | b'\'' => {
+                self.scan_string(ch);
+            }
+            b'-' | b'+' | b'.' | b'0'..=b'9' => {
+                let literal = self.scan_token();
+                if !pointer.is_empty() && !literal.is_empty() {
+                    out.insert(pointer.to_string(), literal);
+                }
+            }
+            b't' | b'f' | b'n' => {
+                self.scan_identifier();
+            }
+            b'I' | b'N' => {
+                let literal = self.scan_identifier();
+                if !pointer.is_empty() && !literal.is_empty() {
+                    out.insert(pointer.to_string(), literal);
+                }
+            }
+            _ => {
+                self.idx += 1;
+            }
+        }
+    }
+
+    fn scan_object(&mut self, pointer: &str, out: &mut HashMap<String, String>) {
+        self.idx += 1; // {
+        loop {
+            self.skip_ws_and_comments();
+            match self.peek() {
+                Some(b'}') => {
+                    self.idx += 1;
+                    break;
+                }
+                None => break,
+                _ => {}
+            }
+
+            let Some(key) = self.scan_object_key() else {
+                break;
+            };
+            self.skip_ws_and_comments();
+            if self.peek() == Some(b':') {
+                self.idx += 1;
+            } else {
+                break;
+            }
+
+            let child_pointer = pointer_child(pointer, &key);
+            self.scan_value(&child_pointer, out);
+            self.skip_ws_and_comments();
+
+            match self.peek() {
+                Some(b',') => {
+                    self.idx += 1;
+                }
+                Some(b'}') => {
+                    self.idx += 1;
+                    break;
+                }
+                None => break,
+                _ => {}
+            }
+        }
+    }
+
+    fn scan_array(&mut self, pointer: &str, out: &mut HashMap<String, String>) {
+        self.idx += 1; // [
+        let mut index = 0usize;
+        loop {
+            self.skip_ws_and_comments();
+            match self.peek() {
+                Some(b']') => {
+                    self.idx += 1;
+                    break;
+                }
+                None => break,
+                _ => {}
+            }
+
+            let child_pointer = pointer_index(pointer, index);
+            self.scan_value(&child_pointer, out);
+            index += 1;
+            self.skip_ws_and_comments();
+
+            match self.peek() {
+                Some(b',') => {
+                    self.idx += 1;
+                }
+                Some(b']') => {
+                    self.idx += 1;
+                    break;
+                }
+                None => break,
+                _ => {}
+            }
+        }
+    }
+
+    fn scan_object_key(&mut self) -> Option<String> {
+        self.skip_ws_and_comments();
+        match self.peek()? {
+            b'"' | b'\'' => self.scan_string(self.peek()?).into(),
+            _ => {
+                let ident = self.scan_identifier();
+                if ident.is_empty() {
+                    None
+                } else {
+                    Some(ident)
+                }
+            }
+        }
+    }
+
+    fn scan_string(&mut self, quote: u8) -> String {
+        let mut out = String::new();
+        self.idx += 1; // opening quote
+        while let Some(ch) = self.peek() {
+            self.idx += 1;
+            if ch == quote {
+                break;
+            }
+            if ch == b'\\' {
+                if let Some(next) = self.peek() {
+                    self.idx += 1;
+                    out.push(next as char);
+                }
+                continue;
+            }
+            out.push(ch as char);
+        }
+        out
+    }
+
+    fn scan_identifier(&mut self) -> String {
+        let start = self.idx;
+        while let Some(ch) = self.peek() {
+            if ch.is_ascii_alphanumeric() || ch == b'_' || ch == b'$' {
+                self.idx += 1;
+            } else {
+                break;
+            }
+        }
+        String::from_utf8_lossy(&self.bytes[start..self.idx]).to_string()
+    }
+
+    fn scan_token(&mut self) -> String {
+        let start = self.idx;
+        while let Some(ch) = self.peek() {
+            if ch.is_ascii_whitespace() || matches!(ch, b',' | b'}' | b']') {
+                break;
+            }
+            if ch == b'/' && self.peek_next().is_some_and(|n| n == b'/' || n == b'*') {
+                break;
+            }
+            self.idx += 1;
+        }
+        String::from_utf8_lossy(&self.bytes[start..self.idx]).to_string()
+    }
+
+    fn skip_ws_and_comments(&mut self) {
+        loop {
+            while let Some(ch) = self.peek() {
+                if ch.is_ascii_whitespace() {
+                    self.idx += 1;
+                } else {
+                    break;
+                }
+            }
+
+            let Some(ch) = self.peek() else {
+                return;
+            };
+            if ch != b'/' {
+                return;
+            }
+            let Some(next) = self.peek_next() else {
+                return;
+            };
+            if next == b'/' {
+                self.idx += 2;
+                while let Some(c) = self.peek() {
+                    self.idx += 1;
+                    if c == b'\n' {
+                        break;
+                    }
+                }
+                continue;
+            }
+            if next == b'*' {
+                self.idx += 2;
+                while self.idx + 1 < self.bytes.len() {
+                    if self.bytes[self.idx] == b'*' && self.bytes[self.idx + 1] == b'/' {
+                        self.idx += 2;
+                        break;
+                    }
+                    self.idx += 1;
+                }
+                continue;
+            }
+            return;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.idx).copied()
+    }
+
+    fn peek_next(&self) -> Option<u8> {
+        self.bytes.get(self.idx + 1).copied()
+    }
+}
+
+enum JsVariant {
+    Esm,
+    Cjs,
+}
+
+fn write_js_locale_with_fs<F: FileSystem>(
+    path: &Path,
+    content: &Map<String, Value>,
+    key_sort: KeySort,
+    fs: &F,
+    variant: JsVariant,
+) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs.create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    let json_body = to_string_pretty_sorted(&Value::Object(content.clone()), key_sort)?;
+    let (prefix, suffix) = existing_module_layout(path, fs).unwrap_or_else(|| match variant {
+        JsVariant::Esm => ("export default ".to_string(), ";\n".to_string()),
+        JsVariant::Cjs => ("module.exports = ".to_string(), ";\n".to_string()),
+    });
+    let mut output = String::new();
+    output.push_str(&prefix);
+    output.push_str(&json_body);
+    output.push_str(&suffix);
+
+    let output = normalize_newlines(&output, existing_uses_crlf(path, fs));
+    fs.atomic_write(path, output.as_bytes())
+        .with_context(|| format!("Failed to write locale file: {}", path.display()))
+}
+
+fn write_ts_locale_with_fs<F: FileSystem>(
+    path: &Path,
+    content: &Map<String, Value>,
+    key_sort: KeySort,
+    fs: &F,
+) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs.create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    let json_body = to_string_pretty_sorted(&Value::Object(content.clone()), key_sort)?;
+    let (prefix, suffix) = existing_module_layout(path, fs)
+        .unwrap_or_else(|| ("export default ".to_string(), " as const;\n".to_string()));
+    let output = normalize_newlines(
+        &format!("{}{}{}", prefix, json_body, suffix),
+        existing_uses_crlf(path, fs),
+    );
+    fs.atomic_write(path, output.as_bytes())
+        .with_context(|| format!("Failed to write locale file: {}", path.display()))
+}
+
+/// Text around the catalog object literal of the existing module at `path`,
+/// so rewriting keeps imports, helpers, `satisfies` and the export style
+fn existing_module_layout<F: FileSystem>(path: &Path, fs: &F) -> Option<(String, String)> {
+    if !fs.exists(path) {
+        return None;
+    }
+    let content = fs.read_to_string(path).ok()?;
+    let range = locale_module::catalog_literal_range(&content)?;
+    Some((
+        content[..range.start].to_string(),
+        content[range.end..].to_string(),
+    ))
+}
+
+/// Whether the file at `path` already uses CRLF line endings
+fn existing_uses_crlf<F: FileSystem>(path: &Path, fs: &F) -> bool {
+    fs.exists(path)
+        && fs
+            .read_to_string(path)
+            .is_ok_and(|content| content.contains("\r\n"))
+}
+
+/// `text` with every line ending written as CRLF or LF, so that fragments kept
+/// from an existing file (comments) cannot mix the two
+fn normalize_newlines(text: &str, crlf: bool) -> String {
+    let text = text.replace("\r\n", "\n");
+    if crlf {
+        text.replace('\n', "\r\n")
+    } else {
+        text
+    }
+}
+
+/// `content` of the locale file at `path` rewritten in the configured style,
+/// keeping every key and value: keys ordered by `keySort` and, for JSON,
+/// `indentation`/`minify`, `endOfLine` and a trailing newline unless minified
+pub fn format_locale_content(config: &Config, path: &Path, content: &str) -> Result<String> {
+    let format = config.output_format();
+    let map = parse_locale_map(content, format, path)?;
+    let style = (format == OutputFormat::Json).then(|| {
+        let mut style = configured_json_style(config, content);
+        style.trailing_newline = !style.minify;
+        style
+    });
+
+    // Render with the regular writer into a scratch copy, so the result is
+    // exactly what writing the file in place produces
+    let scratch = tempfile::tempdir().context("Failed to create scratch directory")?;
+    let scratch_path = scratch.path().join(path.file_name().unwrap_or_default());
+    std::fs::write(&scratch_path, content)
+        .with_context(|| format!("Failed to write: {}", scratch_path.display()))?;
+    write_locale_file(&scratch_path, &map, format, config.key_sort, style.as_ref())?;
+    std::fs::read_to_string(&scratch_path)
+        .with_context(|| format!("Failed to read: {}", scratch_path.display()))
+}
+
+/// Write translation data atomically using the configured format.
+pub fn write_locale_file(
+    path: &Path,
+    content: &Map<String, Value>,
+    format: OutputFormat,
+    key_sort: KeySort,
+    style: Option<&JsonStyle>,
+) -> Result<()> {
+    write_locale_file_with_fs(
+        path,
+        content,
+        format,
+        key_sort,
+        style,
+        &crate::fs::RealFileSystem,
+    )
+}
+
+/// Write translation data using the provided FileSystem (for testing)
+pub fn write_locale_file_with_fs<F: FileSystem>(
+    path: &Path,
+    content: &Map<String, Value>,
+    format: OutputFormat,
+    key_sort: KeySort,
+    style: Option<&JsonStyle>,
+    fs: &F,
+) -> Result<()> {
+    match format {
+        OutputFormat::Json => write_json_locale_with_fs(path, content, key_sort, style, fs),
+        OutputFormat::Json5 => write_json5_locale_with_fs(path, content, key_sort, fs),
+        OutputFormat::JsEsm => write_js_locale_with_fs(path, content, key_sort, fs, JsVariant::Esm),
+        OutputFormat::JsCjs => write_js_locale_with_fs(path, content, key_sort, fs, JsVariant::Cjs),
+        OutputFormat::Ts => write_ts_locale_with_fs(path, content, key_sort, fs),
+    }
+}
+
+/// Atomically read, modify, and write a locale file with exclusive file locking.
+/// This prevents data corruption when multiple processes access the same file.
+///
+/// The lock is held for the entire read-modify-write cycle to ensure ACID-like
+/// transaction guarantees.
+///
+/// If `dry_run` is true, the file will not be written but the result will still
+/// indicate what changes would have been made.
+pub(crate) fn sync_locale_file_locked(
+    path: &Path,
+    keys: &[ExtractedKey],
+    target_namespace: &str,
+    config: &Config,
+    preserve_matcher: &PreserveMatcher,
+    dry_run: bool,
+) -> Result<SyncResult> {
+    sync_locale_file_locked_with_fs(
+        path,
+        keys,
+        target_namespace,
+        config,
+        preserve_matcher,
+        dry_run,
+        &crate::fs::RealFileSystem,
+    )
+}
+
+/// Atomically read, modify, and write a locale file using the provided FileSystem.
+/// This version is testable with mock file systems.
+///
+/// If `dry_run` is true, the file will not be written but the result will still
+/// indicate what changes would have been made.
+pub(crate) fn sync_locale_file_locked_with_fs<F: FileSystem>(
+    path: &Path,
+    keys: &[ExtractedKey],
+    target_namespace: &str,
+    config: &Config,
+    preserve_matcher: &PreserveMatcher,
+    dry_run: bool,
+    fs: &F,
+) -> Result<SyncResult> {
+    // Open file with exclusive lock using FileSystem abstraction. The lock on the
+    // namespace file also guards its shards. A dry run only reads: it takes a
+    // shared lock and treats a missing file as empty instead of creating it.
+    let mut locked_file = if !dry_run {
+        if let Some(parent) = path.parent() {
+            fs.create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        Some(fs.open_locked(path)?)
+    } else if fs.exists(path) {
+        Some(fs.open_shared(path)?)
+    } else {
+        None
+    };
+
+    // Read existing content
+    let mut content_str = match locked_file.as_mut() {
+        Some(file) => file
+            .content_string()
+            .with_context(|| format!("Failed to read locale file: {}", path.display()))?,
+        None => String::new(),
+    };
+
+    let format = config.output_format();
+    let mut content = parse_locale_map(&content_str, format, path)?;
+    let shards = if config.sharded_locale_files() {
+        existing_shards(path, fs)
+    } else {
+        Vec::new()
+    };
+    let mut on_disk = vec![content.clone()];
+    for shard in &shards {
+        let shard_str = fs
+            .read_to_string(shard)
+            .with_context(|| format!("Failed to read locale file: {}", shard.display()))?;
+        let shard_content = parse_locale_map(&shard_str, format, shard)?;
+        merge_shard(&mut content, shard_content.clone());
+        on_disk.push(shard_content);
+        if content_str.trim().is_empty() {
+            content_str = shard_str;
+        }
+    }
+    if on_disk[0].is_empty() && !shards.is_empty() {
+        on_disk.remove(0);
+    }
+
+    let style = (format == OutputFormat::Json).then(|| configured_json_style(config, &content_str));
+    // Switching `minify` rewrites files even when no key changed
+    let restyle = !content_str.trim().is_empty()
+        && style
+            .as_ref()
+            .is_some_and(|style| style.minify != detect_json_style(&content_str).minify);
+
+    // Merge new keys
+    let mut sync_result = merge_keys(
+        &mut content,
+        keys,
+        target_namespace,
+        config,
+        preserve_matcher,
+    );
+    sync_result.file_path = paths::display(path, config.path_style);
+
+    let sorted = sort_keys_alphabetically(&content);
+    let planned = match config.shard_max_keys {
+        Some(max_keys) if config.sharded_locale_files() => split_into_shards(&sorted, max_keys),
+        _ => vec![sorted],
+    };
+    // Changing `shardMaxKeys` reshards files even when no key changed
+    let reshard = planned != on_disk;
+
+    // Only write if there were changes and not in dry-run mode
+    if !dry_run
+        && (restyle
+            || reshard
+            || !sync_result.added_keys.is_empty()
+            || !sync_result.removed_keys.is_empty())
+    {
+        let targets: Vec<PathBuf> = if planned.len() == 1 {
+            vec![path.to_path_buf()]
+        } else {
+            (1..=planned.len())
+                .map(|index| shard_path(path, index))
+                .collect()
+        };
+        for (target, shard) in targets.iter().zip(&planned) {
+            write_locale_file_with_fs(target, shard, format, config.key_sort, style.as_ref(), fs)
+                .with_context(|| format!("Failed to write locale file: {}", target.display()))?;
+        }
+        for stale in shards.iter().filter(|shard| !targets.contains(shard)) {
+            fs.remove_file(stale)
+                .with_context(|| format!("Failed to remove shard: {}", stale.display()))?;
+        }
+    }
+
+    // A sharded namespace has no file of its own; the one created to hold the
+    // lock goes once the lock is released
+    let sharded = planned.len() > 1 || (!reshard && !shards.is_empty());
+    drop(locked_file);
+    if !dry_run && sharded && fs.exists(path) {
+        fs.remove_file(path)
+            .with_context(|| format!("Failed to remove locale file: {}", path.display()))?;
+    }
+
+    Ok(sync_result)
+}
+
+/// Collect unique namespaces from a set of extracted keys
+pub fn collect_namespaces(
+    keys: &[ExtractedKey],
+    default_namespace: &str,
+    merge_namespaces: bool,
+) -> BTreeSet<String> {
+    let mut namespaces = BTreeSet::new();
+    let effective_default = effective_namespace(default_namespace).to_string();
+    namespaces.insert(effective_default.clone());
+
+    if merge_namespaces {
+        return namespaces;
+    }
+
+    for key in keys {
+        if let Some(ns) = &key.namespace {
+            namespaces.insert(ns.clone());
+        }
+    }
+
+    namespaces
+}
+
+/// Sync extracted keys to specific namespace files only (for incremental updates)
+/// This is more efficient when only a subset of namespaces have changed.
+///
+/// Uses file locking to prevent data corruption when multiple processes
+/// (e.g., watch mode + manual extract) access the same files.
+///
+/// If `dry_run` is true, files will not be written but results will still
+/// indicate what changes would have been made.
+pub fn sync_namespaces(
+    config: &Config,
+    keys: &[ExtractedKey],
+    output_dir: &str,
+    namespaces: &BTreeSet<String>,
+    dry_run: bool,
+) -> Result<Vec<SyncResult>> {
+    let preserve_matcher =
+        PreserveMatcher::for_config(config)?.with_removable(metadata::expired_dead_keys(config)?);
+    let mut results = Vec::new();
+    let target_namespaces: Vec<String> = if config.merge_namespaces {
+        vec![effective_namespace(&config.default_namespace).to_string()]
+    } else {
+        namespaces.iter().cloned().collect()
+    };
+
+    // Process only the specified namespace files
+    for locale in &config.locales {
+        let keys = plurals::keys_for_locale(config, locale, keys, output_dir)?;
+        for namespace in &target_namespaces {
+            ensure_plain_path_names(locale, namespace)?;
+            let file_path = locale_namespace_file_path(config, output_dir, locale, namespace);
+
+            // Use locked sync for data integrity
+            let sync_result = sync_locale_file_locked(
+                &file_path,
+                &keys,
+                namespace,
+                config,
+                &preserve_matcher,
+                dry_run,
+            )?;
+
+            results.push((namespace.clone(), sync_result));
+        }
+    }
+
+    if !dry_run {
+        metadata::record_extracted_keys(config, keys)?;
+        record_removals(config, &preserve_matcher, &results)?;
+    }
+    Ok(results.into_iter().map(|(_, result)| result).collect())
+}
+
+/// Sync extracted keys to a specific subset of locales.
+pub fn sync_locales(
+    config: &Config,
+    keys: &[ExtractedKey],
+    output_dir: &str,
+    target_locales: &[String],
+    dry_run: bool,
+) -> Result<Vec<SyncResult>> {
+    let preserve_matcher =
+        PreserveMatcher::for_config(config)?.with_removable(metadata::expired_dead_keys(config)?);
+    let mut results = Vec::new();
+    let namespaces = collect_namespaces(keys, &config.default_namespace, config.merge_namespaces);
+
+    for locale in target_locales {
+        let keys = plurals::keys_for_locale(config, locale, keys, output_dir)?;
+        for namespace in &namespaces {
+            ensure_plain_path_names(locale, namespace)?;
+            let file_path = locale_namespace_file_path(config, output_dir, locale, namespace);
+
+            let sync_result = sync_locale_file_locked(
+                &file_path,
+                &keys,
+                namespace,
+                config,
+                &preserve_matcher,
+                dry_run,
+            )?;
+            results.push((namespace.clone(), sync_result));
+        }
+    }
+
+    if !dry_run {
+        metadata::record_extracted_keys(config, keys)?;
+        record_removals(config, &preserve_matcher, &results)?;
+    }
+    Ok(results.into_iter().map(|(_, result)| result).collect())
+}
+
+/// Record the dead keys of a sync, per namespace, for `removalGraceDays`
+fn record_removals(
+    config: &Config,
+    preserve_matcher: &PreserveMatcher,
+    results: &[(String, SyncResult)],
+) -> Result<()> {
+    if config.removal_grace_days.is_none() || !config.remove_unused_keys {
+        return Ok(());
+    }
+    let mut pending = BTreeSet::new();
+    let mut purged = BTreeSet::new();
+    for (namespace, result) in results {
+        pending.extend(
+            result
+                .pending_removal
+                .iter()
+                .map(|key| (namespace.clone(), key.clone())),
+        );
+        purged.extend(
+            result
+                .removed_keys
+                .iter()
+                .map(|key| (namespace.clone(), key.clone())),
+        );
+    }
+    let synced: BTreeSet<&str> = results
+        .iter()
+        .map(|(namespace, _)| namespace.as_str())
+        .collect();
+    metadata::record_removals(
+        config,
+        |namespace, key| synced.contains(namespace) && preserve_matcher.in_scope(namespace, key),
+        &pending,
+        &purged,
+    )
+}
+
+/// Sync extracted keys to all locale files.
+///
+/// If `dry_run` is true, files will not be written but results will still
+/// indicate what changes would have been made.
+pub fn sync_all_locales(
+    config: &Config,
+    keys: &[ExtractedKey],
+    output_dir: &str,
+    dry_run: bool,
+) -> Result<Vec<SyncResult>> {
+    sync_locales(config, keys, output_dir, &config.locales, dry_run)
+}
+
+/// Group extracted keys by the locale root they sync into: the matching `outputs`
+/// entry of their source file, otherwise `default_output`. Roots on disk that no
+/// source maps to any more get an empty group, so their stale keys are pruned
+/// (or reported) like in any other root.
+pub fn group_keys_by_output<'a, P: AsRef<Path>>(
+    config: &Config,
+    files: impl IntoIterator<Item = (P, &'a Vec<ExtractedKey>)>,
+    default_output: &str,
+) -> BTreeMap<String, Vec<ExtractedKey>> {
+    let mut groups: BTreeMap<String, Vec<ExtractedKey>> = std::iter::once(default_output)
+        .chain(config.outputs.iter().map(|target| target.output.as_str()))
+        .filter(|root| Path::new(root).is_dir())
+        .map(|root| (root.to_string(), Vec::new()))
+        .collect();
+    for (file_path, keys) in files {
+        let output = config
+            .output_target_for(file_path.as_ref())
+            .unwrap_or(default_output);
+        groups
+            .entry(output.to_string())
+            .or_default()
+            .extend(keys.iter().cloned());
+    }
+    groups
+}
+
+/// Add messages declared in component `<i18n>` blocks to the default namespace of
+/// their locale. Keys that already exist are left untouched and nothing is pruned.
+/// An existing key whose source default differs from its primary-locale value
+#[derive(Debug, Clone, PartialEq)]
+pub struct DefaultValueUpdate {
+    pub file_path: String,
+    pub namespace: String,
+    pub key: String,
+    pub from: Value,
+    pub to: Value,
+    /// Secondary locales whose translation was cleared as stale
+    pub cleared_locales: Vec<String>,
+}
+
+/// Overwrite primary-locale values of existing keys with their changed source
+/// defaults. With `clear_stale`, the key's translations in the other locales
+/// are emptied so they show up as missing.
+pub fn update_default_values(
+    config: &Config,
+    keys: &[ExtractedKey],
+    output_dir: &str,
+    clear_stale: bool,
+    dry_run: bool,
+) -> Result<Vec<DefaultValueUpdate>> {
+    let preserve_matcher = PreserveMatcher::for_config(config)?;
+    let default_namespace = effective_namespace(&config.default_namespace);
+    let mut defaults: BTreeMap<(&str, &str), Value> = BTreeMap::new();
+    for key in keys {
+        let namespace = key.namespace.as_deref().unwrap_or(default_namespace);
+        if key.key.ends_with(".*") || !preserve_matcher.in_scope(namespace, &key.key) {
+            continue;
+        }
+        if let Some(default_value) = &key.default_value {
+            defaults
+                .entry((namespace, key.key.as_str()))
+                .or_insert_with(|| Value::from(default_value.clone()));
+        }
+    }
+
+    let primary = config.primary_language();
+    let mut files = LocaleFileCache::default();
+    let mut updates = Vec::new();
+    for ((namespace, key), default_value) in defaults {
+        ensure_plain_path_names(primary, namespace)?;
+        let parts = key_parts(config, namespace, key);
+        let path = locale_namespace_file_path(config, output_dir, primary, namespace);
+        let Some(value) = files.leaf_mut(config, &path, &parts)? else {
+            continue;
+        };
+        if *value == default_value {
+            continue;
+        }
+        let from = std::mem::replace(value, default_value.clone());
+
+        let mut cleared_locales = Vec::new();
+        if clear_stale {
+            for locale in config.locales.iter().filter(|l| l.as_str() != primary) {
+                let path = locale_namespace_file_path(config, output_dir, locale, namespace);
+                if let Some(value) = files.leaf_mut(config, &path, &parts)? {
+                    if value.as_str().is_some_and(|text| !text.is_empty()) {
+                        *value = Value::String(String::new());
+                        cleared_locales.push(locale.clone());
+                    }
+                }
+            }
+        }
+        files.changed.insert(path.clone());
+        for locale in &cleared_locales {
+            files.changed.insert(locale_namespace_file_path(
+                config, output_dir, locale, namespace,
+            ));
+        }
+        updates.push(DefaultValueUpdate {
+            file_path: paths::display(&path, config.path_style),
+            namespace: namespace.to_string(),
+            key: key.to_string(),
+            from,
+            to: default_value,
+            cleared_locales,
+        });
+    }
+
+    if !dry_run {
+        files.write_changed(config)?;
+    }
+    Ok(updates)
+}
+
+/// Locale files read once and written back after edits
+#[derive(Default)]
+struct LocaleFileCache {
+    files: BTreeMap<PathBuf, (Map<String, Value>, Option<JsonStyle>)>,
+    changed: BTreeSet<PathBuf>,
+}
+
+impl LocaleFileCache {
+    /// Mutable leaf value at `parts` in `path`; `None` for missing files,
+    /// missing keys and objects
+    fn leaf_mut(
+        &mut self,
+        config: &Config,
+        path: &Path,
+        parts: &[String],
+    ) -> Result<Option<&mut Value>> {
+        if !self.files.contains_key(path) {
+            if !path.is_file() {
+                return Ok(None);
+            }
+            let content = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read locale file: {}", path.display()))?;
+            let format = config.output_format();
+            let style =
+                (format == OutputFormat::Json).then(|| configured_json_style(config, &content));
+            let map = parse_locale_map(&content, format, path)?;
+            self.files.insert(path.to_path_buf(), (map, style));
+        }
+        let (map, _) = self.files.get_mut(path).expect("file was just loaded");
+        let Some((last, parents)) = parts.split_last() else {
+            return Ok(None);
+        };
+        let mut current = map;
+        for part in parents {
+            match current.get_mut(part) {
+                Some(Value::Object(child)) => current = child,
+                _ => return Ok(None),
+            }
+        }
+        Ok(current.get_mut(last).filter(|value| !value.is_object()))
+    }
+
+    fn write_changed(&self, config: &Config) -> Result<()> {
+        for path in &self.changed {
+            let (map, style) = &self.files[path];
+            let sorted = sort_keys_alphabetically(map);
+            write_locale_file(
+                path,
+                &sorted,
+                config.output_format(),
+                config.key_sort,
+                style.as_ref(),
+            )
+            .with_context(|| format!("Failed to write locale file: {}", path.display()))?;
+        }
+        Ok(())
+    }
+}
+
+pub fn sync_component_messages(
+    config: &Config,
+    messages: &[ComponentMessage],
+    output_dir: &str,
+    dry_run: bool,
+) -> Result<Vec<SyncResult>> {
+    let mut by_target: BTreeMap<(&str, &str), Vec<ExtractedKey>> = BTreeMap::new();
+    for message in messages {
+        if !config.locales.contains(&message.locale) {
+            continue;
+        }
+        let output = config
+            .output_target_for(Path::new(&message.file_path))
+            .unwrap_or(output_dir);
+        by_target
+            .entry((output, message.locale.as_str()))
+            .or_default()
+            .push(ExtractedKey {
+                key: message.key.clone(),
+                namespace: None,
+                default_value: Some(message.value.clone().into()),
+            });
+    }
+
+    let mut additive_config = config.clone();
+    additive_config.remove_unused_keys = false;
+
+    let mut results = Vec::new();
+    for ((output, locale), keys) in by_target {
+        results.extend(sync_locales(
+            &additive_config,
+            &keys,
+            output,
+            &[locale.to_string()],
+            dry_run,
+        )?);
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extractor::DefaultValue;
+
+    #[test]
+    fn test_insert_nested_key_simple() {
+        let mut map = Map::new();
+        let result = insert_nested_key(&mut map, &["hello"], "".into());
+
+        assert!(matches!(result, InsertResult::Added));
+        assert_eq!(map.get("hello"), Some(&Value::String("".to_string())));
+    }
+
+    #[test]
+    fn test_insert_nested_key_deep() {
+        let mut map = Map::new();
+        let result = insert_nested_key(&mut map, &["button", "submit"], "".into());
+
+        assert!(matches!(result, InsertResult::Added));
+        let button = map
+            .get("button")
+            .expect("button should exist after insert_nested_key")
+            .as_object()
+            .expect("button should be an object after insert_nested_key");
+        assert_eq!(button.get("submit"), Some(&Value::String("".to_string())));
+    }
+
+    #[test]
+    fn test_insert_nested_key_existing() {
+        let mut map = Map::new();
+        map.insert("hello".to_string(), Value::String("world".to_string()));
+
+        let result = insert_nested_key(&mut map, &["hello"], "".into());
+
+        assert!(matches!(result, InsertResult::Existed));
+        assert_eq!(map.get("hello"), Some(&Value::String("world".to_string())));
+    }
+
+    #[test]
+    fn test_insert_nested_key_conflict() {
+        let mut map = Map::new();
+        // Add a scalar value at "button"
+        map.insert("button".to_string(), Value::String("click me".to_string()));
+
+        // Try to add a nested key "button.submit" - should conflict
+        let result = insert_nested_key(&mut map, &["button", "submit"], "".into());
+
+        assert!(matches!(
+            result,
+            InsertResult::Conflict(KeyConflict::ValueIsNotObject { .. })
+        ));
+        // Original value should be preserved
+        assert_eq!(
+            map.get("button"),
+            Some(&Value::String("click me".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_sort_keys_alphabetically() {
+        let mut map = Map::new();
+        map.insert("zebra".to_string(), Value::String("z".to_string()));
+        map.insert("apple".to_string(), Value::String("a".to_string()));
+        map.insert("mango".to_string(), Value::String("m".to_string()));
+
+        let sorted = sort_keys_alphabetically(&map);
+        let keys: Vec<_> = sorted.keys().collect();
+
+        assert_eq!(keys, vec!["apple", "mango", "zebra"]);
+    }
+
+    #[test]
+    fn test_sort_nested_objects() {
+        let mut inner = Map::new();
+        inner.insert("z".to_string(), Value::String("1".to_string()));
+        inner.insert("a".to_string(), Value::String("2".to_string()));
+
+        let mut map = Map::new();
+        map.insert("nested".to_string(), Value::Object(inner));
+
+        let sorted = sort_keys_alphabetically(&map);
+        let nested = sorted
+            .get("nested")
+            .expect("nested should exist after sort_keys_alphabetically")
+            .as_object()
+            .expect("nested should be an object in sort_keys_alphabetically");
+        let keys: Vec<_> = nested.keys().collect();
+
+        assert_eq!(keys, vec!["a", "z"]);
+    }
+
+    #[test]
+    fn compare_keys_orders_by_folded_letters_and_numbers() {
+        let sorted = |key_sort: KeySort| {
+            let mut keys = vec![
+                "zebra", "Äpfel", "apple", "Banana", "step10", "step2", "a.b",
+            ];
+            keys.sort_by(|a, b| compare_keys(a, b, key_sort));
+            keys
+        };
+        assert_eq!(
+            sorted(KeySort::Byte),
+            vec!["Banana", "a.b", "apple", "step10", "step2", "zebra", "Äpfel"]
+        );
+        assert_eq!(
+            sorted(KeySort::Folded),
+            vec!["a.b", "Äpfel", "apple", "Banana", "step10", "step2", "zebra"]
+        );
+        assert_eq!(
+            sorted(KeySort::Natural),
+            vec!["a.b", "Äpfel", "apple", "Banana", "step2", "step10", "zebra"]
+        );
+        assert_eq!(
+            compare_keys("Item", "item", KeySort::Folded),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_keys("item02", "item2", KeySort::Natural),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn key_sort_applies_to_every_output_format() {
+        use crate::fs::mock::InMemoryFileSystem;
+
+        let fs = InMemoryFileSystem::new();
+        let mut nested = Map::new();
+        nested.insert("step10".to_string(), Value::from("Ten"));
+        nested.insert("step2".to_string(), Value::from("Two"));
+        let mut content = Map::new();
+        content.insert("wizard".to_string(), Value::Object(nested));
+        content.insert("Zoo".to_string(), Value::from("Zoo"));
+        content.insert("ärger".to_string(), Value::from("Ärger"));
+
+        for (path, format) in [
+            ("locales/en/a.json", OutputFormat::Json),
+            ("locales/en/a.json5", OutputFormat::Json5),
+            ("locales/en/a.js", OutputFormat::JsEsm),
+            ("locales/en/a.ts", OutputFormat::Ts),
+        ] {
+            write_locale_file_with_fs(
+                Path::new(path),
+                &content,
+                format,
+                KeySort::Natural,
+                None,
+                &fs,
+            )
+            .unwrap();
+            let written = &fs.get_files()[Path::new(path)];
+            let position = |key: &str| written.find(&format!("{}\"", key)).unwrap();
+            // Top level: ärger, wizard, Zoo; inside wizard: step2, step10
+            assert!(
+                position("ärger") < position("wizard") && position("wizard") < position("Zoo"),
+                "{}:\n{}",
+                path,
+                written
+            );
+            assert!(
+                position("step2") < position("step10"),
+                "{}:\n{}",
+                path,
+                written
+            );
+        }
+    }
+
+    #[test]
+    fn test_merge_keys() {
+        let mut existing = Map::new();
+        existing.insert(
+            "existing".to_string(),
+            Value::String("translated".to_string()),
+        );
+
+        let keys = vec![
+            ExtractedKey {
+                key: "existing".to_string(),
+                namespace: None,
+                default_value: None,
+            },
+            ExtractedKey {
+                key: "new.key".to_string(),
+                namespace: None,
+                default_value: None,
+            },
+        ];
+
+        let config = Config::default();
+        let matcher =
+            PreserveMatcher::new(&config.preserve_patterns, &config.ns_separator).unwrap();
+        let result = merge_keys(&mut existing, &keys, "translation", &config, &matcher);
+
+        assert_eq!(result.added_keys.len(), 1);
+        assert_eq!(result.added_keys[0], "new.key");
+        assert_eq!(
+            result.added,
+            vec![AddedKey {
+                namespace: "translation".to_string(),
+                key: "new.key".to_string(),
+            }]
+        );
+        assert_eq!(result.existing_keys, 1);
+        // Existing translation is preserved
+        assert_eq!(
+            existing.get("existing"),
+            Some(&Value::String("translated".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_merge_keys_with_default_value() {
+        let mut existing = Map::new();
+
+        let keys = vec![
+            ExtractedKey {
+                key: "greeting".to_string(),
+                namespace: None,
+                default_value: Some("Hello World!".into()),
+            },
+            ExtractedKey {
+                key: "no_default".to_string(),
+                namespace: None,
+                default_value: None,
+            },
+        ];
+
+        let config = Config::default();
+        let matcher =
+            PreserveMatcher::new(&config.preserve_patterns, &config.ns_separator).unwrap();
+        let result = merge_keys(&mut existing, &keys, "translation", &config, &matcher);
+
+        assert_eq!(result.added_keys.len(), 2);
+        // Key with default_value should use that value
+        assert_eq!(
+            existing.get("greeting"),
+            Some(&Value::String("Hello World!".to_string()))
+        );
+        // Key without default_value should use empty string
+        assert_eq!(
+            existing.get("no_default"),
+            Some(&Value::String("".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_merge_keys_writes_typed_default_values() {
+        let mut existing = Map::new();
+        let keys = vec![
+            ExtractedKey {
+                key: "limits.retries".to_string(),
+                namespace: None,
+                default_value: Some(DefaultValue::Number(3.into())),
+            },
+            ExtractedKey {
+                key: "flags.beta".to_string(),
+                namespace: None,
+                default_value: Some(DefaultValue::Bool(true)),
+            },
+        ];
+
+        let config = Config::default();
+        let matcher =
+            PreserveMatcher::new(&config.preserve_patterns, &config.ns_separator).unwrap();
+        merge_keys(&mut existing, &keys, "translation", &config, &matcher);
+
+        assert_eq!(
+            Value::Object(existing),
+            serde_json::json!({ "limits": { "retries": 3 }, "flags": { "beta": true } })
+        );
+    }
+
+    #[test]
+    fn test_merge_keys_preserves_array_values() {
+        let mut existing = serde_json::json!({
+            "steps": ["First", "Second"],
+            "faq": [{ "q": "Why?" }],
+            "legacy": ["Old"]
+        })
+        .as_object()
+        .unwrap()
+        .clone();
+        let keys: Vec<ExtractedKey> = ["steps.1", "faq.0.q", "faq.0.a"]
+            .iter()
+            .map(|key| ExtractedKey {
+                key: key.to_string(),
+                namespace: None,
+                default_value: None,
+            })
+            .collect();
+
+        let config = Config {
+            remove_unused_keys: true,
+            ..Config::default()
+        };
+        let matcher =
+            PreserveMatcher::new(&config.preserve_patterns, &config.ns_separator).unwrap();
+        let result = merge_keys(&mut existing, &keys, "translation", &config, &matcher);
+
+        assert_eq!(result.added_keys, vec!["faq.0.a"]);
+        assert_eq!(result.removed_keys, vec!["legacy"]);
+        assert!(result.conflicts.is_empty());
+        assert_eq!(
+            Value::Object(existing),
+            serde_json::json!({
+                "steps": ["First", "Second"],
+                "faq": [{ "q": "Why?", "a": "" }]
+            })
+        );
+    }
+
+    #[test]
+    fn test_merge_keys_flat_mode() {
+        let mut existing = Map::new();
+
+        let keys = vec![
+            ExtractedKey {
+                key: "button.submit".to_string(),
+                namespace: None,
+                default_value: Some("Submit".into()),
+            },
+            ExtractedKey {
+                key: "form.validation.required".to_string(),
+                namespace: None,
+                default_value: None,
+            },
+        ];
+
+        // Empty separator = flat key mode
+        let mut config = Config::default();
+        config.key_separator = String::new();
+        let matcher =
+            PreserveMatcher::new(&config.preserve_patterns, &config.ns_separator).unwrap();
+        let result = merge_keys(&mut existing, &keys, "translation", &config, &matcher);
+
+        assert_eq!(result.added_keys.len(), 2);
+        // Keys should be stored as-is, not nested
+        assert_eq!(
+            existing.get("button.submit"),
+            Some(&Value::String("Submit".to_string()))
+        );
+        assert_eq!(
+            existing.get("form.validation.required"),
+            Some(&Value::String("".to_string()))
+        );
+        // Should NOT have nested structure
+        assert!(existing.get("button").is_none());
+        assert!(existing.get("form").is_none());
+    }
+
+    #[test]
+    fn test_merge_keys_with_merge_namespaces() {
+        let mut existing = Map::new();
+        let keys = vec![
+            ExtractedKey {
+                key: "hello".to_string(),
+                namespace: Some("common".to_string()),
+                default_value: Some("Hello".into()),
+            },
+            ExtractedKey {
+                key: "title".to_string(),
+                namespace: Some("home".to_string()),
+                default_value: Some("Home".into()),
+            },
+        ];
+
+        let mut config = Config::default();
+        config.merge_namespaces = true;
+        let matcher =
+            PreserveMatcher::new(&config.preserve_patterns, &config.ns_separator).unwrap();
+        let result = merge_keys(&mut existing, &keys, "translation", &config, &matcher);
+        assert_eq!(result.added_keys.len(), 2);
+        let common = existing
+            .get("common")
+            .and_then(|v| v.as_object())
+            .expect("common namespace object should exist");
+        assert_eq!(
+            common.get("hello"),
+            Some(&Value::String("Hello".to_string()))
+        );
+        let home = existing
+            .get("home")
+            .and_then(|v| v.as_object())
+            .expect("home namespace object should exist");
+        assert_eq!(home.get("title"), Some(&Value::String("Home".to_string())));
+    }
+
+    #[test]
+    fn test_merge_keys_with_prefixed_layout_and_nesting_depth() {
+        let mut existing = Map::new();
+        let keys = vec![
+            ExtractedKey {
+                key: "button.save".to_string(),
+                namespace: Some("common".to_string()),
+                default_value: Some("Save".into()),
+            },
+            ExtractedKey {
+                key: "title".to_string(),
+                namespace: Some("home".to_string()),
+                default_value: Some("Home".into()),
+            },
+        ];
+
+        let mut config = Config::default();
+        config.merge_namespaces = true;
+        config.merged_namespace_layout = crate::config::MergedNamespaceLayout::Prefixed;
+        config.merged_nesting_depth = Some(1);
+        let matcher =
+            PreserveMatcher::new(&config.preserve_patterns, &config.ns_separator).unwrap();
+        let result = merge_keys(&mut existing, &keys, "translation", &config, &matcher);
+        assert_eq!(result.added_keys.len(), 2);
+        assert_eq!(
+            Value::Object(existing.clone()),
+            serde_json::json!({ "common:button.save": "Save", "home:title": "Home" })
+        );
+
+        let split = split_merged_namespaces(existing, config.merged_namespace_prefix());
+        assert_eq!(
+            Value::Object(split),
+            serde_json::json!({
+                "common": { "button.save": "Save" },
+                "home": { "title": "Home" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_merged_key_parts_limits_nesting_depth() {
+        let mut config = Config::default();
+        config.merge_namespaces = true;
+        config.merged_nesting_depth = Some(2);
+        assert_eq!(
+            merged_key_parts(&config, "common", "form.field.label.short"),
+            vec!["common", "form", "field.label.short"]
+        );
+        assert_eq!(
+            merged_key_parts(&config, "common", "ok"),
+            vec!["common", "ok"]
+        );
+    }
+
+    #[test]
+    fn test_detect_json_style_default() {
+        let style = JsonStyle::default();
+        assert_eq!(style.indent, "  ");
+        assert!(!style.use_crlf);
+        assert!(style.trailing_newline);
+    }
+
+    #[test]
+    fn test_detect_json_style_two_spaces() {
+        let json = r#"{
+  "key": "value"
+}
+"#;
+        let style = detect_json_style(json);
+        assert_eq!(style.indent, "  ");
+        assert!(!style.use_crlf);
+        assert!(style.trailing_newline);
+    }
+
+    #[test]
+    fn test_detect_json_style_four_spaces() {
+        let json = r#"{
+    "key": "value"
+}
+"#;
+        let style = detect_json_style(json);
+        assert_eq!(style.indent, "    ");
+        assert!(!style.use_crlf);
+        assert!(style.trailing_newline);
+    }
+
+    #[test]
+    fn test_detect_json_style_tabs() {
+        let json = "{\n\t\"key\": \"value\"\n}\n";
+        let style = detect_json_style(json);
+        assert_eq!(style.indent, "\t");
+        assert!(!style.use_crlf);
+        assert!(style.trailing_newline);
+    }
+
+    #[test]
+    fn test_detect_json_style_crlf() {
+        let json = "{\r\n  \"key\": \"value\"\r\n}\r\n";
+        let style = detect_json_style(json);
+        assert_eq!(style.indent, "  ");
+        assert!(style.use_crlf);
+        assert!(style.trailing_newline);
+    }
+
+    #[test]
+    fn test_detect_json_style_no_trailing_newline() {
+        let json = r#"{
+  "key": "value"
+}"#;
+        let style = detect_json_style(json);
+        assert_eq!(style.indent, "  ");
+        assert!(!style.use_crlf);
+        assert!(!style.trailing_newline);
+    }
+
+    #[test]
+    fn test_minify_is_detected_and_configured_style_wins() {
+        assert!(detect_json_style(r#"{"key":"value"}"#).minify);
+        assert!(!detect_json_style("{}\n").minify);
+
+        let mut config = Config::default();
+        config.minify = true;
+        assert_eq!(
+            configured_json_style(&config, "{\n  \"key\": \"value\"\n}\n"),
+            JsonStyle::minified()
+        );
+        config.minify = false;
+        config.indentation = Some(crate::config::Indentation::Spaces(4));
+        let style = configured_json_style(&config, r#"{"key":"value"}"#);
+        assert!(!style.minify);
+        assert!(style.trailing_newline);
+        assert_eq!(style.indent, "    ");
+
+        let mut map = Map::new();
+        map.insert("a".to_string(), serde_json::json!({ "b": "c" }));
+        let mut output = Vec::new();
+        serialize_with_style(
+            &mut output,
+            &Value::Object(map),
+            &JsonStyle::minified(),
+            KeySort::Byte,
+        )
+        .unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), r#"{"a":{"b":"c"}}"#);
+    }
+
+    #[test]
+    fn test_format_locale_content_applies_configured_style_only() {
+        let path = Path::new("locales/en/translation.json");
+        let content = "{\r\n  \"b\": \"B\",\r\n  \"a\": { \"y\": \"Y\", \"x\": 1 }\r\n}";
+
+        let mut config = Config::default();
+        assert_eq!(
+            format_locale_content(&config, path, content).unwrap(),
+            "{\r\n  \"a\": {\r\n    \"x\": 1,\r\n    \"y\": \"Y\"\r\n  },\r\n  \"b\": \"B\"\r\n}\r\n"
+        );
+
+        config.end_of_line = EndOfLine::Lf;
+        config.indentation = Some(crate::config::Indentation::Custom("\t".to_string()));
+        let formatted = format_locale_content(&config, path, content).unwrap();
+        assert_eq!(
+            formatted,
+            "{\n\t\"a\": {\n\t\t\"x\": 1,\n\t\t\"y\": \"Y\"\n\t},\n\t\"b\": \"B\"\n}\n"
+        );
+        assert_eq!(
+            format_locale_content(&config, path, &formatted).unwrap(),
+            formatted
+        );
+    }
+
+    #[test]
+    fn test_serialize_with_style_four_spaces() {
+        let mut map = Map::new();
+        map.insert("hello".to_string(), Value::String("world".to_string()));
+
+        let style = JsonStyle {
+            indent: "    ".to_string(),
+            use_crlf: false,
+            trailing_newline: true,
+            minify: false,
+        };
+
+        let mut output = Vec::new();
+        serialize_with_style(&mut output, &Value::Object(map), &style, KeySort::Byte).unwrap();
+        let result = String::from_utf8(output).unwrap();
+
+        assert!(result.contains("    \"hello\""));
+    }
+
+    #[test]
+    fn test_serialize_with_style_tabs() {
+        let mut map = Map::new();
+        map.insert("key".to_string(), Value::String("value".to_string()));
+
+        let style = JsonStyle {
+            indent: "\t".to_string(),
+            use_crlf: false,
+            trailing_newline: true,
+            minify: false,
+        };
+
+        let mut output = Vec::new();
+        serialize_with_style(&mut output, &Value::Object(map), &style, KeySort::Byte).unwrap();
+        let result = String::from_utf8(output).unwrap();
+
+        assert!(result.contains("\t\"key\""));
+    }
+
+    #[test]
+    fn test_namespaces_over_shard_max_keys_are_split_and_merged_back() {
+        use crate::fs::mock::InMemoryFileSystem;
+
+        let fs = InMemoryFileSystem::new();
+        let path = Path::new("locales/en/common.json");
+        let keys: Vec<ExtractedKey> = ["a.x", "a.y", "b", "c.z"]
+            .into_iter()
+            .map(|key| ExtractedKey {
+                key: key.to_string(),
+                namespace: Some("common".to_string()),
+                default_value: None,
+            })
+            .collect();
+        let mut config = Config::default();
+        config.shard_max_keys = Some(2);
+        let matcher =
+            PreserveMatcher::new(&config.preserve_patterns, &config.ns_separator).unwrap();
+        let sync = |config: &Config| {
+            sync_locale_file_locked_with_fs(path, &keys, "common", config, &matcher, false, &fs)
+                .unwrap()
+        };
+
+        assert_eq!(sync(&config).added_keys.len(), 4);
+        let read = |file: &str| -> Value {
+            serde_json::from_str(&fs.get_files()[Path::new(file)]).unwrap()
+        };
+        assert!(!fs.get_files().contains_key(path));
+        assert_eq!(
+            read("locales/en/common.shard-1.json"),
+            serde_json::json!({ "a": { "x": "", "y": "" } })
+        );
+        assert_eq!(
+            read("locales/en/common.shard-2.json"),
+            serde_json::json!({ "b": "", "c": { "z": "" } })
+        );
+        assert_eq!(
+            file_namespace(Path::new("locales/en/common.shard-2.json"), true),
+            Some("common")
+        );
+        assert_eq!(
+            file_namespace(Path::new("locales/en/common.shard-2.json"), false),
+            Some("common.shard-2")
+        );
+        assert_eq!(
+            file_namespace(Path::new("locales/en/v.2.json"), true),
+            Some("v.2")
+        );
+
+        // Nothing changed: the shards stay as they are
+        assert!(sync(&config).added_keys.is_empty());
+        assert!(!fs.get_files().contains_key(path));
+
+        // Raising the limit above the namespace merges the shards back
+        config.shard_max_keys = Some(10);
+        assert!(sync(&config).added_keys.is_empty());
+        assert_eq!(
+            read("locales/en/common.json"),
+            serde_json::json!({ "a": { "x": "", "y": "" }, "b": "", "c": { "z": "" } })
+        );
+        assert_eq!(fs.get_files().len(), 1);
+    }
+
+    #[test]
+    fn test_sync_locale_file_locked_with_mock_fs() {
+        use crate::fs::mock::InMemoryFileSystem;
+        use std::path::Path;
+
+        let fs = InMemoryFileSystem::new();
+
+        // Create an empty file to start with
+        fs.add_file("locales/en/translation.json", "{}");
+
+        let keys = vec![
+            ExtractedKey {
+                key: "hello".to_string(),
+                namespace: None,
+                default_value: Some("Hello World".into()),
+            },
+            ExtractedKey {
+                key: "button.submit".to_string(),
+                namespace: None,
+                default_value: Some("Submit".into()),
+            },
+        ];
+
+        let config = Config::default();
+        let matcher =
+            PreserveMatcher::new(&config.preserve_patterns, &config.ns_separator).unwrap();
+
+        let result = sync_locale_file_locked_with_fs(
+            Path::new("locales/en/translation.json"),
+            &keys,
+            "translation",
+            &config,
+            &matcher,
+            false, // dry_run
+            &fs,
+        )
+        .unwrap();
+
+        assert_eq!(result.added_keys.len(), 2);
+        assert!(result.added_keys.contains(&"hello".to_string()));
+        assert!(result.added_keys.contains(&"button.submit".to_string()));
+
+        // Verify file was written
+        let files = fs.get_files();
+        let content = files
+            .get(Path::new("locales/en/translation.json"))
+            .expect("File should exist");
+
+        // Verify JSON structure
+        let parsed: Map<String, Value> = serde_json::from_str(content).unwrap();
+        assert_eq!(
+            parsed.get("hello"),
+            Some(&Value::String("Hello World".to_string()))
+        );
+
+        // Verify nested key
+        let button = parsed.get("button").unwrap().as_object().unwrap();
+        assert_eq!(
+            button.get("submit"),
+            Some(&Value::String("Submit".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_sync_locale_file_preserves_existing_keys() {
+        use crate::fs::mock::InMemoryFileSystem;
+        use std::path::Path;
+
+        let fs = InMemoryFileSystem::new();
+
+        // Create a file with existing translations
+        fs.add_file(
+            "locales/en/translation.json",
+            r#"{"existing": "Already translated"}"#,
+        );
+
+        let keys = vec![
+            ExtractedKey {
+                key: "existing".to_string(),
+                namespace: None,
+                default_value: Some("New value".into()), // Different value
+            },
+            ExtractedKey {
+                key: "new_key".to_string(),
+                namespace: None,
+                default_value: Some("New key value".into()),
+            },
+        ];
+
+        let mut config = Config::default();
+        config.remove_unused_keys = false;
+        let matcher =
+            PreserveMatcher::new(&config.preserve_patterns, &config.ns_separator).unwrap();
+
+        let result = sync_locale_file_locked_with_fs(
+            Path::new("locales/en/translation.json"),
+            &keys,
+            "translation",
+            &config,
+            &matcher,
+            false, // dry_run
+            &fs,
+        )
+        .unwrap();
+
+        // Only new key should be added
+        assert_eq!(result.added_keys.len(), 1);
+        assert_eq!(result.added_keys[0], "new_key");
+        assert_eq!(result.existing_keys, 1);
+
+        // Verify existing translation was preserved
+        let files = fs.get_files();
+        let content = files
+            .get(Path::new("locales/en/translation.json"))
+            .expect("File should exist");
+        let parsed: Map<String, Value> = serde_json::from_str(content).unwrap();
+
+        // Original value should be preserved, not overwritten
+        assert_eq!(
+            parsed.get("existing"),
+            Some(&Value::String("Already translated".to_string()))
+        );
+        assert_eq!(
+            parsed.get("new_key"),
+            Some(&Value::String("New key value".to_string()))
+        );
+    }
+    #[test]
+    fn test_remove_unused_keys_prunes_stale_entries() {
+        use crate::fs::mock::InMemoryFileSystem;
+        use std::path::Path;
+
+        let fs = InMemoryFileSystem::new();
+        fs.add_file("locales/en/translation.json", r#"{"stale": "keep"}"#);
+
+        let keys: Vec<ExtractedKey> = Vec::new();
+        let config = Config::default();
+        let matcher =
+            PreserveMatcher::new(&config.preserve_patterns, &config.ns_separator).unwrap();
+
+        let result = sync_locale_file_locked_with_fs(
+            Path::new("locales/en/translation.json"),
+            &keys,
+            "translation",
+            &config,
+            &matcher,
+            false, // dry_run
+            &fs,
+        )
+        .unwrap();
+
+        assert_eq!(result.added_keys.len(), 0);
+        assert_eq!(result.removed_keys, vec!["stale".to_string()]);
+
+        let files = fs.get_files();
+        let content = files
+            .get(Path::new("locales/en/translation.json"))
+            .expect("File should exist");
+        let parsed: Map<String, Value> = serde_json::from_str(content).unwrap();
+        assert!(parsed.is_empty());
+    }
+
+    #[test]
+    fn test_key_filter_scopes_additions_and_removals() {
+        let mut existing = serde_json::json!({
+            "auth": { "stale": "Old", "login": "Login" },
+            "home": { "stale": "Keep" },
+            "empty": {}
+        })
+        .as_object()
+        .unwrap()
+        .clone();
+        let keys = vec![
+            ExtractedKey {
+                key: "auth.login".to_string(),
+                namespace: None,
+                default_value: None,
+            },
+            ExtractedKey {
+                key: "auth.logout".to_string(),
+                namespace: None,
+                default_value: Some("Logout".into()),
+            },
+            ExtractedKey {
+                key: "home.title".to_string(),
+                namespace: None,
+                default_value: None,
+            },
+        ];
+
+        let mut config = Config::default();
+        config.key_filter = vec!["auth.*".to_string()];
+        let matcher = PreserveMatcher::for_config(&config).unwrap();
+        let result = merge_keys(&mut existing, &keys, "translation", &config, &matcher);
+
+        assert_eq!(result.added_keys, vec!["auth.logout".to_string()]);
+        assert_eq!(result.removed_keys, vec!["auth.stale".to_string()]);
+        assert_eq!(
+            Value::Object(existing),
+            serde_json::json!({
+                "auth": { "login": "Login", "logout": "Logout" },
+                "home": { "stale": "Keep" },
+                "empty": {}
+            })
+        );
+    }
+
+    #[test]
+    fn test_return_objects_marker_preserves_nested_keys() {
+        use crate::fs::mock::InMemoryFileSystem;
+        use std::path::Path;
+
+        let fs = InMemoryFileSystem::new();
+        fs.add_file(
+            "locales/en/translation.json",
+            r#"{"countries":{"jp":"Japan","us":"United States"},"stale":"remove"}"#,
+        );
+
+        let keys = vec![ExtractedKey {
+            key: "countries.*".to_string(),
+            namespace: None,
+            default_value: None,
+        }];
+        let config = Config::default();
+        let matcher =
+            PreserveMatcher::new(&config.preserve_patterns, &config.ns_separator).unwrap();
+
+        let result = sync_locale_file_locked_with_fs(
+            Path::new("locales/en/translation.json"),
+            &keys,
+            "translation",
+            &config,
+            &matcher,
+            false,
+            &fs,
+        )
+        .unwrap();
+
+        assert_eq!(result.removed_keys, vec!["stale".to_string()]);
+
+        let files = fs.get_files();
+        let content = files
+            .get(Path::new("locales/en/translation.json"))
+            .expect("File should exist");
+        let parsed: Map<String, Value> = serde_json::from_str(content).unwrap();
+        assert!(parsed.get("countries").is_some());
+        let countries = parsed
+            .get("countries")
+            .and_then(|v| v.as_object())
+            .expect("countries should be object");
+        assert_eq!(
+            countries.get("jp"),
+            Some(&Value::String("Japan".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_preserve_patterns_keep_dynamic_keys() {
+        use crate::fs::mock::InMemoryFileSystem;
+        use std::path::Path;
+
+        let fs = InMemoryFileSystem::new();
+        fs.add_file("locales/en/translation.json", r#"{"dynamic": "value"}"#);
+
+        let keys: Vec<ExtractedKey> = Vec::new();
+        let mut config = Config::default();
+        config.preserve_patterns = vec!["translation:dynamic".to_string()];
+        let matcher =
+            PreserveMatcher::new(&config.preserve_patterns, &config.ns_separator).unwrap();
+
+        let result = sync_locale_file_locked_with_fs(
+            Path::new("locales/en/translation.json"),
+            &keys,
+            "translation",
+            &config,
+            &matcher,
+            false, // dry_run
+            &fs,
+        )
+        .unwrap();
+
+        assert!(result.removed_keys.is_empty());
+
+        let files = fs.get_files();
+        let content = files
+            .get(Path::new("locales/en/translation.json"))
+            .expect("File should exist");
+        let parsed: Map<String, Value> = serde_json::from_str(content).unwrap();
+        assert_eq!(
+            parsed.get("dynamic"),
+            Some(&Value::String("value".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_in_file_preserve_markers_protect_keys_from_pruning() {
+        use crate::fs::mock::InMemoryFileSystem;
+        use std::path::Path;
+
+        let fs = InMemoryFileSystem::new();
+        fs.add_file(
+            "locales/en/translation.json",
+            r#"{
+  "//": "Sections below are looked up dynamically",
+  "home": {
+    "__preserve__": ["section.*"],
+    "section": { "hero": "Hero", "footer": "Footer" },
+    "stale": "Old"
+  },
+  "stale": "Old"
+}"#,
+        );
+
+        let keys: Vec<ExtractedKey> = Vec::new();
+        let config = Config::default();
+        let matcher =
+            PreserveMatcher::new(&config.preserve_patterns, &config.ns_separator).unwrap();
+
+        let result = sync_locale_file_locked_with_fs(
+            Path::new("locales/en/translation.json"),
+            &keys,
+            "translation",
+            &config,
+            &matcher,
+            false, // dry_run
+            &fs,
+        )
+        .unwrap();
+
+        let mut removed = result.removed_keys.clone();
+        removed.sort();
+        assert_eq!(removed, vec!["home.stale", "stale"]);
+
+        let files = fs.get_files();
+        let content = files
+            .get(Path::new("locales/en/translation.json"))
+            .expect("File should exist");
+        let parsed: Value = serde_json::from_str(content).unwrap();
+        assert_eq!(
+            parsed,
+            serde_json::json!({
+                "//": "Sections below are looked up dynamically",
+                "home": {
+                    "__preserve__": ["section.*"],
+                    "section": { "hero": "Hero", "footer": "Footer" }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_sync_locale_with_json5_format() {
+        use crate::fs::mock::InMemoryFileSystem;
+        use std::path::Path;
+
+        let fs = InMemoryFileSystem::new();
+        fs.add_file("locales/en/translation.json5", "{ greeting: 'Hello' }");
+
+        let keys = vec![ExtractedKey {
+            key: "farewell".to_string(),
+            namespace: None,
+            default_value: Some("Goodbye".into()),
+        }];
+
+        let mut config = Config::default();
+        config.output_format = OutputFormat::Json5;
+        let matcher =
+            PreserveMatcher::new(&config.preserve_patterns, &config.ns_separator).unwrap();
+
+        let result = sync_locale_file_locked_with_fs(
+            Path::new("locales/en/translation.json5"),
+            &keys,
+            "translation",
+            &config,
+            &matcher,
+            false, // dry_run
+            &fs,
+        )
+        .unwrap();
+
+        assert_eq!(result.added_keys, vec!["farewell".to_string()]);
+
+        let files = fs.get_files();
+        let content = files
+            .get(Path::new("locales/en/translation.json5"))
+            .expect("File should exist");
+        assert!(content.contains("\"farewell\""));
+        assert!(content.ends_with('\n'));
+    }
+
+    #[test]
+    fn test_locale_namespace_file_path_uses_merged_filename_when_enabled() {
+        let mut config = Config::default();
+        config.merge_namespaces = true;
+        config.merged_namespace_filename = Some("all".to_string());
+
+        let path = locale_namespace_file_path(&config, "locales", "en", "translation");
+        assert_eq!(
+            path.to_string_lossy().replace('\\', "/"),
+            "locales/en/all.json"
+        );
+    }
+
+    #[test]
+    fn test_locale_namespace_file_path_expands_locale_in_merged_filename() {
+        let mut config = Config::default();
+        config.merge_namespaces = true;
+        config.merged_namespace_filename = Some("messages.{{lng}}".to_string());
+
+        let path = locale_namespace_file_path(&config, "locales", "de", "common");
+        assert_eq!(
+            path.to_string_lossy().replace('\\', "/"),
+            "locales/de/messages.de.json"
+        );
+    }
+
+    #[test]
+    fn test_locale_namespace_file_path_detects_existing_single_merged_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let locale_dir = tmp.path().join("en");
+        std::fs::create_dir_all(&locale_dir).unwrap();
+        std::fs::write(locale_dir.join("bundle.json"), "{}").unwrap();
+
+        let mut config = Config::default();
+        config.merge_namespaces = true;
+        config.output = tmp.path().to_string_lossy().to_string();
+
+        let path = locale_namespace_file_path(&config, &config.output, "en", "translation");
+        assert_eq!(
+            path.file_name().and_then(|s| s.to_str()),
+            Some("bundle.json")
+        );
+    }
+
+    #[test]
+    fn test_detect_existing_merged_filename_none_for_namespaced_layout() {
+        let tmp = tempfile::tempdir().unwrap();
+        let locale_dir = tmp.path().join("en");
+        std::fs::create_dir_all(&locale_dir).unwrap();
+        std::fs::write(locale_dir.join("common.json"), "{}").unwrap();
+        std::fs::write(locale_dir.join("home.json"), "{}").unwrap();
+
+        let detected = detect_existing_merged_filename(tmp.path().to_str().unwrap(), "en", "json");
+        assert_eq!(detected, None);
+    }
+
+    #[test]
+    fn test_sync_refuses_namespaces_that_leave_the_output_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        let output = tmp.path().join("app/locales");
+        let config = Config {
+            output: output.to_string_lossy().to_string(),
+            locales: vec!["en".to_string()],
+            ..Config::default()
+        };
+        let keys = vec![ExtractedKey {
+            key: "owned".to_string(),
+            namespace: Some("../../../outside".to_string()),
+            default_value: None,
+        }];
+
+        let err = sync_all_locales(&config, &keys, &config.output, false).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("namespace '../../../outside' is not a plain file name"));
+        assert!(!tmp.path().join("outside.json").exists());
+        assert!(ensure_plain_path_names("en", "").is_ok());
+        assert!(ensure_plain_path_names("..", "common").is_err());
+    }
+
+    #[test]
+    fn test_json5_and_ts_writes_keep_one_line_ending_style() {
+        use crate::fs::mock::InMemoryFileSystem;
+        use std::path::Path;
+
+        let fs = InMemoryFileSystem::new();
+        fs.add_file(
+            "locales/en/translation.json5",
+            "// Generated\r\n// Do not edit\r\n{\r\n  greeting: 'Hello',\r\n}\r\n",
+        );
+        fs.add_file(
+            "locales/en/translation.ts",
+            "export default {\r\n  \"greeting\": \"Hello\"\r\n} as const;\r\n",
+        );
+        let mut content = Map::new();
+        content.insert("greeting".to_string(), Value::String("Hello".to_string()));
+        content.insert("farewell".to_string(), Value::String("Bye".to_string()));
+
+        for (path, format) in [
+            ("locales/en/translation.json5", OutputFormat::Json5),
+            ("locales/en/translation.ts", OutputFormat::Ts),
+            ("locales/de/translation.ts", OutputFormat::Ts),
+        ] {
+            write_locale_file_with_fs(Path::new(path), &content, format, KeySort::Byte, None, &fs)
+                .unwrap();
+        }
+
+        let files = fs.get_files();
+        for path in ["locales/en/translation.json5", "locales/en/translation.ts"] {
+            let written = &files[Path::new(path)];
+            assert!(written.contains("farewell"));
+            assert_eq!(
+                written.matches('\n').count(),
+                written.matches("\r\n").count(),
+                "{} mixes line endings:\n{:?}",
+                path,
+                written
+            );
+        }
+        // New files are always written with LF
+        assert!(!files[Path::new("locales/de/translation.ts")].contains('\r'));
+    }
+
+    #[test]
+    fn test_sync_locale_with_json5_preserves_number_literals() {
+        use crate::fs::mock::InMemoryFileSystem;
+        use std::path::Path;
+
+        let fs = InMemoryFileSystem::new();
+        fs.add_file(
+            "locales/en/translation.json5",
+            "{\n  count: 1e3,\n  hex: 0x10,\n  greeting: 'Hello',\n}\n",
+        );
+
+        let keys = vec![
+            ExtractedKey {
+                key: "count".to_string(),
+                namespace: None,
+                default_value: None,
+            },
+            ExtractedKey {
+                key: "hex".to_string(),
+                namespace: None,
+                default_value: None,
+            },
+            ExtractedKey {
+                key: "greeting".to_string(),
+                namespace: None,
+                default_value: Some("Hello".into()),
+            },
+            ExtractedKey {
+                key: "farewell".to_string(),
+                namespace: None,
+                default_value: Some("Goodbye".into()),
+            },
+        ];
+
+        let mut config = Config::default();
+        config.output_format = OutputFormat::Json5;
+        let matcher =
+            PreserveMatcher::new(&config.preserve_patterns, &config.ns_separator).unwrap();
+
+        let _ = sync_locale_file_locked_with_fs(
+            Path::new("locales/en/translation.json5"),
+            &keys,
+            "translation",
+            &config,
+            &matcher,
+            false,
+            &fs,
+        )
+        .unwrap();
+
+        let files = fs.get_files();
+        let content = files
+            .get(Path::new("locales/en/translation.json5"))
+            .expect("File should exist");
+
+        assert!(
+            content.contains("1e3"),
+            "scientific notation should be preserved, got:\n{}",
+            content
+        );
+        assert!(
+            content.contains("0x10"),
+            "hex notation should be preserved, got:\n{}",
+            content
+        );
+        assert!(
+            content.contains("\"farewell\""),
+            "new key should be written"
+        );
+    }
+
+    #[test]
+    fn test_parse_js_module_locale() {
+        let module = "export default { \"hello\": \"world\" };";
+        let value = parse_locale_value_str(module, OutputFormat::JsEsm, Path::new("test.js"))
+            .expect("should parse");
+        assert_eq!(value["hello"], "world");
+    }
+
+    #[test]
+    fn test_write_js_locale_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut map = Map::new();
+        map.insert("foo".to_string(), Value::String("bar".to_string()));
+        let path = tmp.path().join("translation.js");
+        write_js_locale_with_fs(
+            &path,
+            &map,
+            KeySort::Byte,
+            &crate::fs::RealFileSystem,
+            JsVariant::Esm,
+        )
+        .expect("write js file");
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("export default"));
+        assert!(content.contains("foo"));
+    }
+
+    #[test]
+    fn test_write_ts_locale_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut map = Map::new();
+        map.insert("foo".to_string(), Value::String("bar".to_string()));
+        let path = tmp.path().join("translation.ts");
+        write_ts_locale_with_fs(&path, &map, KeySort::Byte, &crate::fs::RealFileSystem)
+            .expect("write ts file");
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("as const"));
+        assert!(content.contains("foo"));
+    }
+
+    #[test]
+    fn test_ts_locale_module_with_satisfies_keeps_layout() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("translation.ts");
+        std::fs::write(
+            &path,
+            "import type { Resource } from './types';\n\nconst resources = {\n  hello: 'Hello',\n} satisfies Resource;\n\nexport default resources;\n",
+        )
+        .unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        let mut map = parse_locale_map(&content, OutputFormat::Ts, &path).unwrap();
+        assert_eq!(map["hello"], "Hello");
+
+        map.insert("bye".to_string(), Value::String("Bye".to_string()));
+        write_ts_locale_with_fs(&path, &map, KeySort::Byte, &crate::fs::RealFileSystem)
+            .expect("write ts file");
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "import type { Resource } from './types';\n\nconst resources = {\n  \"bye\": \"Bye\",\n  \"hello\": \"Hello\"\n} satisfies Resource;\n\nexport default resources;\n"
+        );
+    }
+
+    #[test]
+    fn update_default_values_overwrites_primary_and_clears_stale_translations() {
+        let tmp = tempfile::tempdir().unwrap();
+        let output = tmp.path().join("locales");
+        for (locale, content) in [
+            ("en", r#"{"home":{"title":"Home","save":"Save"}}"#),
+            ("de", r#"{"home":{"title":"Start","save":"Speichern"}}"#),
+            ("fr", r#"{"home":{"title":""}}"#),
+        ] {
+            std::fs::create_dir_all(output.join(locale)).unwrap();
+            std::fs::write(output.join(locale).join("translation.json"), content).unwrap();
+        }
+        let config = Config {
+            locales: vec!["en".to_string(), "de".to_string(), "fr".to_string()],
+            ..Config::default()
+        };
+        let key = |key: &str, default: Option<&str>| ExtractedKey {
+            key: key.to_string(),
+            namespace: None,
+            default_value: default.map(|d| DefaultValue::String(d.to_string())),
+        };
+        let keys = vec![
+            key("home.title", Some("Welcome")),
+            key("home.save", Some("Save")),
+            key("home.new", Some("New")),
+            key("home.plain", None),
+        ];
+        let output_dir = output.to_string_lossy();
+
+        let updates = update_default_values(&config, &keys, &output_dir, true, true).unwrap();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].from, serde_json::json!("Home"));
+        assert_eq!(updates[0].to, serde_json::json!("Welcome"));
+        assert_eq!(updates[0].cleared_locales, vec!["de".to_string()]);
+        let read = |locale: &str| -> Value {
+            serde_json::from_str(
+                &std::fs::read_to_string(output.join(locale).join("translation.json")).unwrap(),
+            )
+            .unwrap()
+        };
+        assert_eq!(read("en")["home"]["title"], "Home");
+
+        update_default_values(&config, &keys, &output_dir, true, false).unwrap();
+        assert_eq!(read("en")["home"]["title"], "Welcome");
+        assert_eq!(read("de")["home"]["title"], "");
+        assert_eq!(read("de")["home"]["save"], "Speichern");
+        assert!(
+            update_default_values(&config, &keys, &output_dir, true, false)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_untracked_locale_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let output = tmp.path().join("locales");
+        for dir in ["en/legacy", "en-US", "de"] {
+            std::fs::create_dir_all(output.join(dir)).unwrap();
+        }
+        for file in [
+            "en/common.json",
+            "en/common.yaml",
+            "en/.gitkeep",
+            "en/legacy/old.json",
+            "en-US/common.json",
+            "de/common.json",
+            "meta.json",
+            "README.md",
+        ] {
+            std::fs::write(output.join(file), "{}").unwrap();
+        }
+        let mut config = Config {
+            output: output.to_string_lossy().to_string(),
+            locales: vec!["en".to_string(), "de".to_string()],
+            metadata_file: Some(output.join("meta.json").to_string_lossy().to_string()),
+            ..Config::default()
+        };
+
+        let found: Vec<(PathBuf, UntrackedReason)> =
+            untracked_locale_files(&config, &config.output)
+                .unwrap()
+                .into_iter()
+                .map(|file| {
+                    (
+                        file.path.strip_prefix(&output).unwrap().to_path_buf(),
+                        file.reason,
+                    )
+                })
+                .collect();
+        assert_eq!(
+            found,
+            vec![
+                (PathBuf::from("README.md"), UntrackedReason::Nesting),
+                (PathBuf::from("en/common.yaml"), UntrackedReason::Extension),
+                (PathBuf::from("en/legacy"), UntrackedReason::Nesting),
+                (
+                    PathBuf::from("en-US"),
+                    UntrackedReason::UnknownLocale("en-US".to_string())
+                ),
+            ]
+        );
+
+        // Flat files are named after their locale
+        config.flat_locale_files = true;
+        let found: Vec<PathBuf> = untracked_locale_files(&config, &config.output)
+            .unwrap()
+            .into_iter()
+            .map(|file| file.path.strip_prefix(&output).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                PathBuf::from("README.md"),
+                PathBuf::from("de"),
+                PathBuf::from("en"),
+                PathBuf::from("en-US"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,232 @@
+//! Key case policy (`keyCase`) and keys that only differ in case, such as
+//! `user.Name` next to `user.name`. Case-insensitive editors and file systems
+//! hide the difference, but i18next looks them up as two keys, so one of them
+//! silently misses its translation.
+
+use anyhow::Result;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::config::{Config, KeyCase};
+use crate::extractor::ExtractedKey;
+use crate::json_sync::{self, PreserveMatcher};
+
+/// A key with a segment that breaks `keyCase`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseViolation {
+    pub namespace: String,
+    pub key: String,
+    pub segment: String,
+}
+
+/// One spelling of a key that has several
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseVariant {
+    pub key: String,
+    /// Source files using this spelling
+    pub files: usize,
+    /// Locales whose files contain this spelling
+    pub locales: Vec<String>,
+}
+
+/// Keys of one namespace that only differ in case
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseConflict {
+    pub namespace: String,
+    /// The most used spelling, which the others are unified to
+    pub dominant: CaseVariant,
+    pub others: Vec<CaseVariant>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyCaseReport {
+    pub violations: Vec<CaseViolation>,
+    pub conflicts: Vec<CaseConflict>,
+}
+
+/// Check the keys of `files` and of every locale file against `keyCase` (when
+/// set) and for spellings that only differ in case
+pub fn check_key_case(
+    config: &Config,
+    files: &[(String, Vec<ExtractedKey>)],
+    scope: &PreserveMatcher,
+) -> Result<KeyCaseReport> {
+    let mut variants: BTreeMap<(String, String), CaseVariant> = BTreeMap::new();
+    for (_file, keys) in files {
+        let used: BTreeSet<(&str, &str)> = keys
+            .iter()
+            .map(|key| {
+                (
+                    key.namespace
+                        .as_deref()
+                        .unwrap_or(config.effective_default_namespace()),
+                    key.key.as_str(),
+                )
+            })
+            .collect();
+        for (namespace, key) in used {
+            variant(&mut variants, namespace, key).files += 1;
+        }
+    }
+    for locale in &config.locales {
+        let values = json_sync::flatten_leaf_values(
+            &json_sync::load_locale_namespaces(config, &config.output, locale)?,
+            &config.key_separator,
+        );
+        for (namespace, key) in values.keys() {
+            variant(&mut variants, namespace, key)
+                .locales
+                .push(locale.clone());
+        }
+    }
+    variants.retain(|(namespace, key), _| scope.in_scope(namespace, key));
+
+    let mut report = KeyCaseReport::default();
+    if let Some(policy) = config.key_case {
+        for (namespace, key) in variants.keys() {
+            if let Some(segment) = violating_segment(config, policy, key) {
+                report.violations.push(CaseViolation {
+                    namespace: namespace.clone(),
+                    key: key.clone(),
+                    segment,
+                });
+            }
+        }
+    }
+
+    let mut groups: BTreeMap<(String, String), Vec<CaseVariant>> = BTreeMap::new();
+    for ((namespace, key), variant) in variants {
+        groups
+            .entry((namespace, key.to_lowercase()))
+            .or_default()
+            .push(variant);
+    }
+    for ((namespace, _), mut spellings) in groups {
+        if spellings.len() < 2 {
+            continue;
+        }
+        spellings.sort_by_key(|variant| dominance(config, variant));
+        let dominant = spellings.remove(0);
+        report.conflicts.push(CaseConflict {
+            namespace,
+            dominant,
+            others: spellings,
+        });
+    }
+    Ok(report)
+}
+
+fn variant<'a>(
+    variants: &'a mut BTreeMap<(String, String), CaseVariant>,
+    namespace: &str,
+    key: &str,
+) -> &'a mut CaseVariant {
+    variants
+        .entry((namespace.to_string(), key.to_string()))
+        .or_insert_with(|| CaseVariant {
+            key: key.to_string(),
+            files: 0,
+            locales: Vec::new(),
+        })
+}
+
+/// Sort key putting the dominant spelling first: most uses, then following
+/// `keyCase`, then fewest capitals, then alphabetical
+fn dominance(config: &Config, variant: &CaseVariant) -> (Reverse<usize>, bool, usize, String) {
+    let uses = variant.files + variant.locales.len();
+    let breaks_policy = config
+        .key_case
+        .is_some_and(|policy| violating_segment(config, policy, &variant.key).is_some());
+    let capitals = variant.key.chars().filter(|c| c.is_uppercase()).count();
+    (Reverse(uses), breaks_policy, capitals, variant.key.clone())
+}
+
+/// First segment of `key` breaking `policy`. The last segment is checked
+/// without its plural suffix and, unless the policy itself uses the context
+/// separator, without a context suffix.
+fn violating_segment(config: &Config, policy: KeyCase, key: &str) -> Option<String> {
+    let segments: Vec<&str> = if config.key_separator.is_empty() {
+        vec![key]
+    } else {
+        key.split(config.key_separator.as_str()).collect()
+    };
+    let plural_config = config.plural_config();
+    let last = segments.len() - 1;
+    segments.iter().enumerate().find_map(|(i, segment)| {
+        let mut checked = segment.to_string();
+        if i == last {
+            if let Some(base) = plural_config.strip_plural_suffix(&checked) {
+                checked = base;
+            }
+            let separator = config.context_separator.as_str();
+            let separator_in_policy = matches!(
+                (policy, separator),
+                (KeyCase::Snake, "_") | (KeyCase::Kebab, "-")
+            );
+            if !separator.is_empty() && !separator_in_policy {
+                if let Some((base, _)) = checked.split_once(separator) {
+                    checked = base.to_string();
+                }
+            }
+        }
+        (!policy.matches(&checked)).then(|| segment.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extractor::test_key;
+
+    #[test]
+    fn key_case_policies_match_segments() {
+        assert!(KeyCase::Camel.matches("userName2"));
+        assert!(!KeyCase::Camel.matches("UserName"));
+        assert!(!KeyCase::Camel.matches("user_name"));
+        assert!(KeyCase::Pascal.matches("UserName"));
+        assert!(KeyCase::Snake.matches("user_name_2"));
+        assert!(!KeyCase::Snake.matches("user__name"));
+        assert!(KeyCase::Kebab.matches("user-name"));
+        assert!(!KeyCase::Kebab.matches("user-Name"));
+        assert!(KeyCase::Lower.matches("user_name-x"));
+    }
+
+    #[test]
+    fn check_key_case_reports_policy_and_case_only_differences() {
+        let tmp = tempfile::tempdir().unwrap();
+        let output = tmp.path().join("locales");
+        std::fs::create_dir_all(output.join("en")).unwrap();
+        std::fs::write(
+            output.join("en/common.json"),
+            r#"{"user":{"name":"Name","Name":"Name"},"item_one":"Item","item_other":"Items","friend_male":"Friend"}"#,
+        )
+        .unwrap();
+        let config = Config {
+            output: output.to_string_lossy().to_string(),
+            locales: vec!["en".to_string()],
+            key_case: Some(KeyCase::Camel),
+            ..Config::default()
+        };
+        let key = |key: &str| test_key(key, Some("common"));
+        let files = vec![
+            ("a.ts".to_string(), vec![key("user.name"), key("userId_x")]),
+            ("b.ts".to_string(), vec![key("user.name")]),
+            ("c.ts".to_string(), vec![key("user.Name")]),
+        ];
+        let scope = PreserveMatcher::for_config(&config).unwrap();
+
+        let report = check_key_case(&config, &files, &scope).unwrap();
+        let violations: Vec<(&str, &str)> = report
+            .violations
+            .iter()
+            .map(|v| (v.key.as_str(), v.segment.as_str()))
+            .collect();
+        assert_eq!(violations, vec![("user.Name", "Name")]);
+        assert_eq!(report.conflicts.len(), 1);
+        let conflict = &report.conflicts[0];
+        assert_eq!(conflict.dominant.key, "user.name");
+        assert_eq!(conflict.dominant.files, 2);
+        assert_eq!(conflict.others[0].key, "user.Name");
+        assert_eq!(conflict.others[0].locales, vec!["en".to_string()]);
+    }
+}
@@ -0,0 +1,352 @@
+//! In-memory index of extracted keys for editor integrations (autocomplete,
+//! go-to-usage), queried by prefix, namespace, file or fuzzy match without
+//! re-reading a report.
+
+use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use crate::config::Config;
+use crate::extractor::{self, ExtractedKey};
+use crate::key_transforms;
+use crate::paths;
+
+/// One key as it would be written to the locale files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedKey {
+    pub namespace: String,
+    pub key: String,
+    /// Source files using the key, sorted
+    pub files: BTreeSet<String>,
+    /// String `defaultValue` from source, the first one found
+    pub default_value: Option<String>,
+}
+
+/// Keys of an extraction run, ordered by namespace and key
+#[derive(Debug, Clone, Default)]
+pub struct KeyIndex {
+    default_namespace: String,
+    keys: BTreeMap<(String, String), IndexedKey>,
+    by_file: BTreeMap<String, BTreeSet<(String, String)>>,
+}
+
+impl KeyIndex {
+    /// Extract the sources of `config` (with key transforms applied) and index them
+    pub fn build(config: &Config) -> Result<Self> {
+        let mut extraction =
+            extractor::extract_with_config(config, &config.input, &config.extraction_ignore())?;
+        key_transforms::apply(config, &mut extraction.files);
+        Ok(Self::from_files(config, &extraction.files))
+    }
+
+    /// Index already extracted keys; keys without a namespace go to the
+    /// default namespace of `config`
+    pub fn from_files(config: &Config, files: &[(String, Vec<ExtractedKey>)]) -> Self {
+        let mut index = Self {
+            default_namespace: config.effective_default_namespace().to_string(),
+            ..Self::default()
+        };
+        for (file_path, keys) in files {
+            index.insert_file(file_path, keys);
+        }
+        index
+    }
+
+    /// Replace the keys of one file, e.g. after it was saved and re-extracted.
+    /// Keys no other file uses are dropped.
+    pub fn replace_file(&mut self, file_path: &str, keys: &[ExtractedKey]) {
+        for id in self.by_file.remove(file_path).unwrap_or_default() {
+            if let Some(entry) = self.keys.get_mut(&id) {
+                entry.files.remove(file_path);
+                if entry.files.is_empty() {
+                    self.keys.remove(&id);
+                }
+            }
+        }
+        self.insert_file(file_path, keys);
+    }
+
+    /// Re-extract one source file with the options of `config` and replace its
+    /// keys; a deleted file drops its keys
+    pub fn refresh_file(&mut self, config: &Config, path: &Path) -> Result<()> {
+        let file_path = paths::display(path, config.path_style);
+        let mut extraction = extractor::ExtractRequest::builder()
+            .config(config)
+            .patterns([glob::Pattern::escape(&path.to_string_lossy())])
+            .build()
+            .run()?;
+        key_transforms::apply(config, &mut extraction.files);
+        let keys: Vec<ExtractedKey> = extraction
+            .files
+            .into_iter()
+            .flat_map(|(_, keys)| keys)
+            .collect();
+        self.replace_file(&file_path, &keys);
+        Ok(())
+    }
+
+    fn insert_file(&mut self, file_path: &str, keys: &[ExtractedKey]) {
+        for key in keys {
+            let namespace = key
+                .namespace
+                .clone()
+                .unwrap_or_else(|| self.default_namespace.clone());
+            let id = (namespace, key.key.clone());
+            let entry = self.keys.entry(id.clone()).or_insert_with(|| IndexedKey {
+                namespace: id.0.clone(),
+                key: id.1.clone(),
+                files: BTreeSet::new(),
+                default_value: None,
+            });
+            entry.files.insert(file_path.to_string());
+            if entry.default_value.is_none() {
+                entry.default_value = key
+                    .default_value
+                    .as_ref()
+                    .and_then(|value| value.as_str())
+                    .map(str::to_string);
+            }
+            self.by_file
+                .entry(file_path.to_string())
+                .or_default()
+                .insert(id);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn get(&self, namespace: &str, key: &str) -> Option<&IndexedKey> {
+        self.keys.get(&(namespace.to_string(), key.to_string()))
+    }
+
+    pub fn namespaces(&self) -> Vec<&str> {
+        let mut namespaces: Vec<&str> = self
+            .keys
+            .keys()
+            .map(|(namespace, _)| namespace.as_str())
+            .collect();
+        namespaces.dedup();
+        namespaces
+    }
+
+    /// Keys starting with `prefix`, in one namespace or all of them
+    pub fn by_prefix(&self, namespace: Option<&str>, prefix: &str) -> Vec<&IndexedKey> {
+        let namespaces = match namespace {
+            Some(namespace) => vec![namespace],
+            None => self.namespaces(),
+        };
+        namespaces
+            .into_iter()
+            .flat_map(|namespace| {
+                self.keys
+                    .range((namespace.to_string(), prefix.to_string())..)
+                    .take_while(move |((ns, key), _)| ns == namespace && key.starts_with(prefix))
+                    .map(|(_, entry)| entry)
+            })
+            .collect()
+    }
+
+    pub fn by_namespace(&self, namespace: &str) -> Vec<&IndexedKey> {
+        self.by_prefix(Some(namespace), "")
+    }
+
+    /// Keys used in `file_path` (as reported by extraction)
+    pub fn by_file(&self, file_path: &str) -> Vec<&IndexedKey> {
+        self.by_file
+            .get(file_path)
+            .into_iter()
+            .flatten()
+            .filter_map(|id| self.keys.get(id))
+            .collect()
+    }
+
+    /// Keys whose `namespace:key` contains the characters of `query` in order,
+    /// best matches first: consecutive characters and ones starting a segment
+    /// (after `.`, `:`, `_` or `-`) rank higher
+    pub fn search(&self, query: &str, limit: usize) -> Vec<&IndexedKey> {
+        let mut matches: Vec<(u32, &IndexedKey)> = self
+            .keys
+            .values()
+            .filter_map(|entry| {
+                let candidate = format!("{}:{}", entry.namespace, entry.key);
+                // A match on the key alone beats one spread into the namespace
+                let score = fuzzy_score(&entry.key, query)
+                    .map(|score| score + 1)
+                    .or_else(|| fuzzy_score(&candidate, query))?;
+                Some((score, entry))
+            })
+            .collect();
+        matches.sort_by(|(a, a_entry), (b, b_entry)| {
+            b.cmp(a)
+                .then_with(|| a_entry.key.len().cmp(&b_entry.key.len()))
+                .then_with(|| {
+                    (&a_entry.namespace, &a_entry.key).cmp(&(&b_entry.namespace, &b_entry.key))
+                })
+        });
+        matches
+            .into_iter()
+            .take(limit)
+            .map(|(_, entry)| entry)
+            .collect()
+    }
+}
+
+/// Case-insensitive subsequence score of `query` in `candidate`, the best of
+/// the matches starting at each occurrence of the first query character
+fn fuzzy_score(candidate: &str, query: &str) -> Option<u32> {
+    let candidate: Vec<char> = candidate.chars().flat_map(char::to_lowercase).collect();
+    let query: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    let Some(first) = query.first() else {
+        return Some(0);
+    };
+    (0..candidate.len())
+        .filter(|start| candidate[*start] == *first)
+        .filter_map(|start| score_from(&candidate, &query, start))
+        .max()
+}
+
+fn score_from(candidate: &[char], query: &[char], start: usize) -> Option<u32> {
+    let mut score = 0;
+    let mut pos = start;
+    let mut previous: Option<usize> = None;
+    for wanted in query {
+        let found = pos + candidate[pos..].iter().position(|c| c == wanted)?;
+        score += 1;
+        if previous.is_some_and(|previous| previous + 1 == found) {
+            score += 2;
+        }
+        if found == 0 || matches!(candidate[found - 1], '.' | ':' | '_' | '-') {
+            score += 3;
+        }
+        previous = Some(found);
+        pos = found + 1;
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extractor::test_key;
+
+    fn key(key: &str, namespace: Option<&str>, default: Option<&str>) -> ExtractedKey {
+        ExtractedKey {
+            default_value: default.map(Into::into),
+            ..test_key(key, namespace)
+        }
+    }
+
+    fn names(keys: Vec<&IndexedKey>) -> Vec<String> {
+        keys.iter()
+            .map(|entry| format!("{}:{}", entry.namespace, entry.key))
+            .collect()
+    }
+
+    fn index() -> KeyIndex {
+        KeyIndex::from_files(
+            &Config::default(),
+            &[
+                (
+                    "src/home.tsx".to_string(),
+                    vec![
+                        key("home.title", None, Some("Home")),
+                        key("home.subtitle", None, None),
+                        key("button.save", Some("common"), Some("Save")),
+                    ],
+                ),
+                (
+                    "src/settings.tsx".to_string(),
+                    vec![
+                        key("settings.title", None, None),
+                        key("button.save", Some("common"), None),
+                    ],
+                ),
+            ],
+        )
+    }
+
+    #[test]
+    fn queries_by_prefix_namespace_and_file() {
+        let index = index();
+        assert_eq!(index.len(), 4);
+        assert_eq!(index.namespaces(), vec!["common", "translation"]);
+        assert_eq!(
+            names(index.by_prefix(Some("translation"), "home.")),
+            vec!["translation:home.subtitle", "translation:home.title"]
+        );
+        assert_eq!(
+            names(index.by_prefix(None, "b")),
+            vec!["common:button.save"]
+        );
+        assert_eq!(
+            names(index.by_namespace("common")),
+            vec!["common:button.save"]
+        );
+        assert_eq!(
+            names(index.by_file("src/settings.tsx")),
+            vec!["common:button.save", "translation:settings.title"]
+        );
+
+        let save = index.get("common", "button.save").unwrap();
+        assert_eq!(save.files.len(), 2);
+        assert_eq!(save.default_value.as_deref(), Some("Save"));
+    }
+
+    #[test]
+    fn search_ranks_segment_and_consecutive_matches_first() {
+        let index = index();
+        assert_eq!(
+            names(index.search("title", 10)),
+            vec![
+                "translation:home.title",
+                "translation:settings.title",
+                "translation:home.subtitle"
+            ]
+        );
+        assert_eq!(
+            names(index.search("hs", 10)),
+            vec!["translation:home.subtitle"]
+        );
+        assert_eq!(index.search("zzz", 10).len(), 0);
+        assert_eq!(index.search("t", 1).len(), 1);
+    }
+
+    #[test]
+    fn refresh_file_reextracts_one_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("app.ts");
+        std::fs::write(&file, "t('old.key')").unwrap();
+        let mut config = Config::default();
+        config.input = vec![format!("{}/*.ts", dir.path().display())];
+
+        let mut index = KeyIndex::build(&config).unwrap();
+        assert!(index.get("translation", "old.key").is_some());
+
+        std::fs::write(&file, "t('new.key', { defaultValue: 'New' })").unwrap();
+        index.refresh_file(&config, &file).unwrap();
+        assert!(index.get("translation", "old.key").is_none());
+        let entry = index.get("translation", "new.key").unwrap();
+        assert_eq!(entry.default_value.as_deref(), Some("New"));
+
+        std::fs::remove_file(&file).unwrap();
+        index.refresh_file(&config, &file).unwrap();
+        assert!(index.is_empty());
+    }
+
+    #[test]
+    fn replace_file_drops_keys_only_that_file_used() {
+        let mut index = index();
+        index.replace_file("src/settings.tsx", &[key("settings.heading", None, None)]);
+
+        assert!(index.get("translation", "settings.title").is_none());
+        assert!(index.get("translation", "settings.heading").is_some());
+        let save = index.get("common", "button.save").unwrap();
+        assert_eq!(save.files.iter().collect::<Vec<_>>(), vec!["src/home.tsx"]);
+    }
+}
@@ -0,0 +1,201 @@
+//! `keyTransforms`: rewrites applied to extracted keys between extraction and
+//! sync, and the reverse mapping (`keyMapFile`) that lets the runtime resolve
+//! the legacy keys still used in source code.
+
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::config::{Config, KeyTransform};
+use crate::extractor::ExtractedKey;
+use crate::fs::{FileSystem, RealFileSystem};
+
+/// Rewritten keys: namespace -> locale key -> source key
+pub type KeyMap = BTreeMap<String, BTreeMap<String, String>>;
+
+/// Apply `transforms` in order to a key (without namespace)
+pub fn transform_key(transforms: &[KeyTransform], key: &str, key_separator: &str) -> String {
+    let mut key = key.to_string();
+    for transform in transforms {
+        match transform {
+            KeyTransform::StripPrefix(prefix) => {
+                if let Some(rest) = key.strip_prefix(prefix.as_str()).filter(|r| !r.is_empty()) {
+                    key = rest.to_string();
+                }
+            }
+            KeyTransform::ReplacePrefix { from, to } => {
+                if let Some(rest) = key.strip_prefix(from.as_str()) {
+                    key = format!("{}{}", to, rest);
+                }
+            }
+            KeyTransform::LowercaseFirstSegment => {
+                let end = if key_separator.is_empty() {
+                    None
+                } else {
+                    key.find(key_separator)
+                };
+                let (first, rest) = key.split_at(end.unwrap_or(key.len()));
+                key = format!("{}{}", first.to_lowercase(), rest);
+            }
+        }
+    }
+    key
+}
+
+/// Rewrite the extracted keys of every file with `keyTransforms`, returning the
+/// reverse mapping of the keys that changed
+pub fn apply(config: &Config, files: &mut [(String, Vec<ExtractedKey>)]) -> KeyMap {
+    let mut key_map = KeyMap::new();
+    for (_, keys) in files.iter_mut() {
+        apply_to_keys(config, keys, &mut key_map);
+    }
+    key_map
+}
+
+/// Rewrite `keys` in place, recording changed keys in `key_map`. When several
+/// source keys converge on one locale key, the first one is kept in the map.
+pub fn apply_to_keys(config: &Config, keys: &mut [ExtractedKey], key_map: &mut KeyMap) {
+    if config.key_transforms.is_empty() {
+        return;
+    }
+    for key in keys {
+        let transformed = transform_key(&config.key_transforms, &key.key, &config.key_separator);
+        if transformed == key.key {
+            continue;
+        }
+        let namespace = key
+            .namespace
+            .as_deref()
+            .unwrap_or(config.effective_default_namespace());
+        key_map
+            .entry(namespace.to_string())
+            .or_default()
+            .entry(transformed.clone())
+            .or_insert_with(|| key.key.clone());
+        key.key = transformed;
+    }
+}
+
+/// Write `key_map` to the configured `keyMapFile`. With `merge`, entries already
+/// in the file are kept (for runs that only see part of the sources).
+/// Does nothing when `keyMapFile` is not set.
+pub fn write_key_map(config: &Config, key_map: &KeyMap, merge: bool) -> Result<()> {
+    let Some(path) = config.key_map_file.as_deref() else {
+        return Ok(());
+    };
+    let path = Path::new(path);
+    let mut merged = if merge {
+        read_key_map(config, path)?
+    } else {
+        KeyMap::new()
+    };
+    for (namespace, keys) in key_map {
+        let entries = merged.entry(namespace.clone()).or_default();
+        for (locale_key, source_key) in keys {
+            entries
+                .entry(locale_key.clone())
+                .or_insert_with(|| source_key.clone());
+        }
+    }
+
+    let value: Map<String, Value> = if config.namespace_less_mode() {
+        // No namespace level: `{ localeKey: sourceKey }`
+        merged
+            .into_values()
+            .flatten()
+            .map(|(locale_key, source_key)| (locale_key, Value::String(source_key)))
+            .collect()
+    } else {
+        merged
+            .into_iter()
+            .map(|(namespace, keys)| (namespace, Value::from_iter(keys)))
+            .collect()
+    };
+    let mut content = serde_json::to_string_pretty(&value)?;
+    content.push('\n');
+    let fs = RealFileSystem;
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs.create_dir_all(parent)?;
+    }
+    if fs.read_to_string(path).ok().as_deref() == Some(content.as_str()) {
+        return Ok(());
+    }
+    fs.atomic_write(path, content.as_bytes())
+        .with_context(|| format!("Failed to write key map: {}", path.display()))
+}
+
+fn read_key_map(config: &Config, path: &Path) -> Result<KeyMap> {
+    if !path.exists() {
+        return Ok(KeyMap::new());
+    }
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read key map: {}", path.display()))?;
+    let parsed = || -> Option<KeyMap> {
+        if config.namespace_less_mode() {
+            let keys = serde_json::from_str(&content).ok()?;
+            Some(KeyMap::from([(String::new(), keys)]))
+        } else {
+            serde_json::from_str(&content).ok()
+        }
+    };
+    parsed().with_context(|| format!("Invalid key map: {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extractor::test_key as key;
+    use tempfile::tempdir;
+
+    #[test]
+    fn transforms_apply_in_order_and_record_reverse_mapping() {
+        let tmp = tempdir().unwrap();
+        let map_path = tmp.path().join("key-map.json");
+        let config = Config {
+            key_transforms: vec![
+                KeyTransform::StripPrefix("app.".to_string()),
+                KeyTransform::ReplacePrefix {
+                    from: "Legacy.".to_string(),
+                    to: "core.".to_string(),
+                },
+                KeyTransform::LowercaseFirstSegment,
+            ],
+            key_map_file: Some(map_path.to_string_lossy().to_string()),
+            ..Config::default()
+        };
+
+        let mut files = vec![(
+            "src/App.tsx".to_string(),
+            vec![
+                key("app.Home.title", None),
+                key("app.Legacy.save", Some("common")),
+                key("home.title", None),
+                key("app.", None),
+            ],
+        )];
+        let key_map = apply(&config, &mut files);
+        let keys: Vec<&str> = files[0].1.iter().map(|k| k.key.as_str()).collect();
+        assert_eq!(keys, vec!["home.title", "core.save", "home.title", "app."]);
+
+        write_key_map(&config, &key_map, false).unwrap();
+        let written: Value =
+            serde_json::from_str(&std::fs::read_to_string(&map_path).unwrap()).unwrap();
+        assert_eq!(
+            written,
+            serde_json::json!({
+                "common": { "core.save": "app.Legacy.save" },
+                "translation": { "home.title": "app.Home.title" }
+            })
+        );
+
+        let mut more = vec![key("app.Footer.text", None)];
+        let mut partial = KeyMap::new();
+        apply_to_keys(&config, &mut more, &mut partial);
+        write_key_map(&config, &partial, true).unwrap();
+        let written: Value =
+            serde_json::from_str(&std::fs::read_to_string(&map_path).unwrap()).unwrap();
+        assert_eq!(written["translation"]["footer.text"], "app.Footer.text");
+        assert_eq!(written["common"]["core.save"], "app.Legacy.save");
+    }
+}
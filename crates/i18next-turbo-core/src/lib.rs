@@ -0,0 +1,63 @@
+//! Extraction, locale sync and configuration of i18next-turbo as a library.
+//!
+//! The `i18next-turbo` CLI and the Node.js bindings are thin layers over this
+//! crate; other Rust tools (build rules, desktop apps, editors) can embed the
+//! same pipeline without pulling in `clap` or Node-API:
+//!
+//! ```no_run
+//! use i18next_turbo_core::config::Config;
+//! use i18next_turbo_core::{extractor, json_sync, key_transforms};
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let config = Config::from_json_string(r#"{ "input": ["src/**/*.tsx"], "locales": ["en", "ja"] }"#)?;
+//! let mut extraction = extractor::extract_with_config(&config, &config.input, &config.extraction_ignore())?;
+//! key_transforms::apply(&config, &mut extraction.files);
+//!
+//! let keys: Vec<_> = extraction.files.into_iter().flat_map(|(_, keys)| keys).collect();
+//! let dry_run = false;
+//! for result in json_sync::sync_all_locales(&config, &keys, &config.output, dry_run)? {
+//!     println!("{}: {} key(s) added", result.file_path, result.added_keys.len());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`config::Config`] is the single options type every entry point takes; it
+//! deserializes from the same camelCase JSON as `i18next-turbo.json`.
+
+#![cfg_attr(test, allow(clippy::field_reassign_with_default))]
+
+pub mod budgets;
+pub mod cancel;
+pub mod cleanup;
+pub mod config;
+pub mod conflicts;
+pub mod eslint_data;
+pub mod extractor;
+pub mod fs;
+pub mod git;
+pub mod handlebars;
+pub mod json_sync;
+pub mod key_case;
+pub mod key_index;
+pub mod key_transforms;
+pub mod lint;
+pub mod locale_module;
+pub mod locale_store;
+pub mod logging;
+pub mod markup;
+pub mod mdx;
+pub mod metadata;
+pub mod metrics;
+pub mod namespaces;
+pub mod nesting;
+pub mod paths;
+pub mod plurals;
+pub mod publish;
+pub mod run_guard;
+pub mod spacing;
+pub mod summary;
+pub mod tms;
+pub mod typegen;
+pub mod vue;
+pub mod walk;
@@ -0,0 +1,386 @@
+//! Catalog object of a JS/TS locale module. Besides the
+//! `export default {...} as const` files the writer produces, hand-written
+//! modules are understood: imports and helper objects above the catalog,
+//! `const resources = {...} satisfies Resource; export default resources;`,
+//! `module.exports = ...` and a single named export.
+
+use anyhow::{anyhow, bail, Result};
+use serde_json::{Map, Number, Value};
+use std::collections::HashMap;
+use std::ops::Range;
+use swc_common::sync::Lrc;
+use swc_common::{FileName, SourceMap, Spanned};
+use swc_ecma_ast::{
+    AssignTarget, Decl, ExportSpecifier, Expr, Lit, MemberProp, Module, ModuleDecl,
+    ModuleExportName, ModuleItem, ObjectLit, Pat, Prop, PropName, PropOrSpread, SimpleAssignTarget,
+    Stmt, UnaryOp,
+};
+use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsSyntax};
+
+/// Identifiers resolve through at most this many `const` declarations
+const MAX_REFERENCE_DEPTH: usize = 16;
+
+/// Translations of the module `content`
+pub fn module_catalog(content: &str) -> Result<Map<String, Value>> {
+    let (module, _) = parse_module(content)?;
+    let module = ModuleCatalog::new(&module);
+    match module.evaluate(module.catalog_expr()?, 0)? {
+        Value::Object(map) => Ok(map),
+        other => bail!("Catalog must be an object, found {}", kind(&other)),
+    }
+}
+
+/// Byte range of the catalog object literal in `content`, when the catalog
+/// is one literal that can be replaced in place
+pub fn catalog_literal_range(content: &str) -> Option<Range<usize>> {
+    let (module, start) = parse_module(content).ok()?;
+    let module = ModuleCatalog::new(&module);
+    let mut expr = unwrap(module.catalog_expr().ok()?);
+    for _ in 0..MAX_REFERENCE_DEPTH {
+        match expr {
+            Expr::Object(object) => {
+                let span = object.span();
+                return Some(span.lo.0 as usize - start..span.hi.0 as usize - start);
+            }
+            Expr::Ident(ident) => expr = unwrap(module.consts.get(ident.sym.as_ref())?),
+            _ => return None,
+        }
+    }
+    None
+}
+
+fn parse_module(content: &str) -> Result<(Module, usize)> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(
+        FileName::Custom("locale module".into()).into(),
+        content.to_string(),
+    );
+    let syntax = Syntax::Typescript(TsSyntax::default());
+    let lexer = Lexer::new(syntax, Default::default(), StringInput::from(&*fm), None);
+    let module = Parser::new_from(lexer).parse_module().map_err(|e| {
+        let loc = cm.lookup_char_pos(e.span().lo);
+        anyhow!(
+            "Invalid module at {}:{}: {}",
+            loc.line,
+            loc.col_display + 1,
+            e.kind().msg()
+        )
+    })?;
+    Ok((module, fm.start_pos.0 as usize))
+}
+
+struct ModuleCatalog<'a> {
+    /// Top-level `const`/`let` initializers by name
+    consts: HashMap<&'a str, &'a Expr>,
+    default_export: Option<&'a Expr>,
+    /// `export { name as default }`
+    default_name: Option<&'a str>,
+    named_exports: Vec<&'a Expr>,
+}
+
+impl<'a> ModuleCatalog<'a> {
+    fn new(module: &'a Module) -> Self {
+        let mut catalog = Self {
+            consts: HashMap::new(),
+            default_export: None,
+            default_name: None,
+            named_exports: Vec::new(),
+        };
+        for item in &module.body {
+            match item {
+                ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => {
+                    catalog.add_declarations(&var.decls, false)
+                }
+                ModuleItem::Stmt(Stmt::Expr(stmt)) => {
+                    // module.exports = {...} / exports.default = {...}
+                    if let Expr::Assign(assign) = &*stmt.expr {
+                        if let AssignTarget::Simple(SimpleAssignTarget::Member(member)) =
+                            &assign.left
+                        {
+                            if is_module_exports(&member.obj, &member.prop) {
+                                catalog.default_export = Some(&assign.right);
+                            }
+                        }
+                    }
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export)) => {
+                    catalog.default_export = Some(&export.expr)
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => {
+                    if let Decl::Var(var) = &export.decl {
+                        catalog.add_declarations(&var.decls, true);
+                    }
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) if export.src.is_none() => {
+                    for specifier in &export.specifiers {
+                        let ExportSpecifier::Named(named) = specifier else {
+                            continue;
+                        };
+                        let exported = named.exported.as_ref().unwrap_or(&named.orig);
+                        if let (ModuleExportName::Ident(orig), true) =
+                            (&named.orig, export_name(exported) == Some("default"))
+                        {
+                            catalog.default_name = Some(orig.sym.as_ref());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        catalog
+    }
+
+    fn add_declarations(&mut self, decls: &'a [swc_ecma_ast::VarDeclarator], exported: bool) {
+        for decl in decls {
+            let (Pat::Ident(name), Some(init)) = (&decl.name, decl.init.as_deref()) else {
+                continue;
+            };
+            self.consts.insert(name.id.sym.as_ref(), init);
+            if exported {
+                self.named_exports.push(init);
+            }
+        }
+    }
+
+    /// The default export, else the only named export holding an object
+    fn catalog_expr(&self) -> Result<&'a Expr> {
+        if let Some(expr) = self.default_export {
+            return Ok(expr);
+        }
+        if let Some(name) = self.default_name {
+            return self
+                .consts
+                .get(name)
+                .copied()
+                .ok_or_else(|| anyhow!("Default export '{}' is not declared in the module", name));
+        }
+        let objects: Vec<&Expr> = self
+            .named_exports
+            .iter()
+            .copied()
+            .filter(|expr| matches!(unwrap(expr), Expr::Object(_)))
+            .collect();
+        match objects.as_slice() {
+            [expr] => Ok(expr),
+            [] => bail!("Module has no default export and no exported object"),
+            _ => bail!(
+                "Module has no default export and {} exported objects; export the catalog as default",
+                objects.len()
+            ),
+        }
+    }
+
+    fn evaluate(&self, expr: &Expr, depth: usize) -> Result<Value> {
+        if depth > MAX_REFERENCE_DEPTH {
+            bail!("References nest too deeply");
+        }
+        Ok(match unwrap(expr) {
+            Expr::Object(object) => Value::Object(self.evaluate_object(object, depth)?),
+            Expr::Array(array) => Value::Array(
+                array
+                    .elems
+                    .iter()
+                    .map(|elem| match elem {
+                        Some(elem) if elem.spread.is_none() => self.evaluate(&elem.expr, depth),
+                        _ => bail!("Unsupported array element in catalog"),
+                    })
+                    .collect::<Result<_>>()?,
+            ),
+            Expr::Lit(Lit::Str(s)) => Value::String(s.value.to_string_lossy().into_owned()),
+            Expr::Lit(Lit::Num(n)) => number(n.value)?,
+            Expr::Lit(Lit::Bool(b)) => Value::Bool(b.value),
+            Expr::Lit(Lit::Null(_)) => Value::Null,
+            Expr::Unary(unary) if unary.op == UnaryOp::Minus => match unwrap(&unary.arg) {
+                Expr::Lit(Lit::Num(n)) => number(-n.value)?,
+                _ => bail!("Unsupported expression in catalog"),
+            },
+            Expr::Tpl(tpl) if tpl.exprs.is_empty() && tpl.quasis.len() == 1 => {
+                let quasi = &tpl.quasis[0];
+                Value::String(match &quasi.cooked {
+                    Some(cooked) => cooked.to_string_lossy().into_owned(),
+                    None => quasi.raw.to_string(),
+                })
+            }
+            Expr::Ident(ident) => {
+                let name = ident.sym.as_ref();
+                let init = self
+                    .consts
+                    .get(name)
+                    .ok_or_else(|| anyhow!("'{}' is not declared in the module", name))?;
+                self.evaluate(init, depth + 1)?
+            }
+            _ => bail!(
+                "Unsupported expression in catalog: only literals, objects, arrays and declared constants are read"
+            ),
+        })
+    }
+
+    fn evaluate_object(&self, object: &ObjectLit, depth: usize) -> Result<Map<String, Value>> {
+        let mut map = Map::new();
+        for prop in &object.props {
+            match prop {
+                PropOrSpread::Spread(spread) => match self.evaluate(&spread.expr, depth + 1)? {
+                    Value::Object(spread) => map.extend(spread),
+                    other => bail!("Cannot spread {} into the catalog", kind(&other)),
+                },
+                PropOrSpread::Prop(prop) => match prop.as_ref() {
+                    Prop::KeyValue(kv) => {
+                        let key = prop_name(&kv.key)
+                            .ok_or_else(|| anyhow!("Unsupported computed key in catalog"))?;
+                        map.insert(key, self.evaluate(&kv.value, depth)?);
+                    }
+                    Prop::Shorthand(ident) => {
+                        let value = self.evaluate(&Expr::Ident(ident.clone()), depth)?;
+                        map.insert(ident.sym.to_string(), value);
+                    }
+                    _ => bail!("Unsupported property in catalog: only key/value pairs are read"),
+                },
+            }
+        }
+        Ok(map)
+    }
+}
+
+/// `expr` without parentheses, `as const`, `as T`, `satisfies T`, `!` and
+/// `Object.freeze(...)`
+fn unwrap(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Paren(paren) => unwrap(&paren.expr),
+        Expr::TsConstAssertion(assertion) => unwrap(&assertion.expr),
+        Expr::TsAs(assertion) => unwrap(&assertion.expr),
+        Expr::TsSatisfies(satisfies) => unwrap(&satisfies.expr),
+        Expr::TsNonNull(non_null) => unwrap(&non_null.expr),
+        Expr::TsTypeAssertion(assertion) => unwrap(&assertion.expr),
+        Expr::Call(call) if call.args.len() == 1 && is_object_freeze(call) => {
+            unwrap(&call.args[0].expr)
+        }
+        _ => expr,
+    }
+}
+
+fn is_object_freeze(call: &swc_ecma_ast::CallExpr) -> bool {
+    let Some(Expr::Member(member)) = call.callee.as_expr().map(|callee| &**callee) else {
+        return false;
+    };
+    matches!(&*member.obj, Expr::Ident(obj) if obj.sym.as_ref() == "Object")
+        && matches!(&member.prop, MemberProp::Ident(prop) if prop.sym.as_ref() == "freeze")
+}
+
+/// `module.exports` or `exports.default`
+fn is_module_exports(obj: &Expr, prop: &MemberProp) -> bool {
+    let (Expr::Ident(obj), MemberProp::Ident(prop)) = (obj, prop) else {
+        return false;
+    };
+    matches!(
+        (obj.sym.as_ref(), prop.sym.as_ref()),
+        ("module", "exports") | ("exports", "default")
+    )
+}
+
+fn export_name(name: &ModuleExportName) -> Option<&str> {
+    match name {
+        ModuleExportName::Ident(ident) => Some(ident.sym.as_ref()),
+        ModuleExportName::Str(s) => s.value.as_str(),
+    }
+}
+
+fn prop_name(name: &PropName) -> Option<String> {
+    match name {
+        PropName::Ident(ident) => Some(ident.sym.to_string()),
+        PropName::Str(s) => Some(s.value.to_string_lossy().into_owned()),
+        PropName::Num(n) => Some(number(n.value).ok()?.to_string()),
+        PropName::Computed(computed) => match unwrap(&computed.expr) {
+            Expr::Lit(Lit::Str(s)) => Some(s.value.to_string_lossy().into_owned()),
+            _ => None,
+        },
+        PropName::BigInt(big) => Some(big.value.to_string()),
+    }
+}
+
+fn number(value: f64) -> Result<Value> {
+    if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+        return Ok(Value::Number(Number::from(value as i64)));
+    }
+    Number::from_f64(value)
+        .map(Value::Number)
+        .ok_or_else(|| anyhow!("Unsupported number in catalog: {}", value))
+}
+
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reads_generated_and_satisfies_modules() {
+        let generated = "export default {\n  \"hello\": \"Hello\"\n} as const;\n";
+        assert_eq!(
+            Value::Object(module_catalog(generated).unwrap()),
+            json!({ "hello": "Hello" })
+        );
+
+        let satisfies = r#"import type { Resource } from 'i18next';
+import { helper } from './helper';
+
+const shared = { ok: 'OK', cancel: `Cancel` };
+
+const resources = {
+  greeting: "Hello {{name}}",
+  'button.label': 'Press',
+  nested: { ...shared, count: 2 },
+  shared,
+  steps: ['One', "Two"],
+} satisfies Resource;
+
+export default resources;
+"#;
+        assert_eq!(
+            Value::Object(module_catalog(satisfies).unwrap()),
+            json!({
+                "greeting": "Hello {{name}}",
+                "button.label": "Press",
+                "nested": { "ok": "OK", "cancel": "Cancel", "count": 2 },
+                "shared": { "ok": "OK", "cancel": "Cancel" },
+                "steps": ["One", "Two"]
+            })
+        );
+        let range = catalog_literal_range(satisfies).unwrap();
+        assert!(satisfies[range].starts_with("{\n  greeting"));
+    }
+
+    #[test]
+    fn reads_named_exports_and_commonjs() {
+        let named =
+            "export const meta = 1;\nexport const en = Object.freeze({ title: 'Title' });\n";
+        assert_eq!(
+            Value::Object(module_catalog(named).unwrap()),
+            json!({ "title": "Title" })
+        );
+        let aliased = "const en = { title: 'Title' };\nexport { en as default };\n";
+        assert_eq!(
+            Value::Object(module_catalog(aliased).unwrap()),
+            json!({ "title": "Title" })
+        );
+        let commonjs = "const base = { a: 'A' };\nmodule.exports = { ...base, b: 'B' };\n";
+        assert_eq!(
+            Value::Object(module_catalog(commonjs).unwrap()),
+            json!({ "a": "A", "b": "B" })
+        );
+        assert!(catalog_literal_range(commonjs).is_some());
+
+        let ambiguous = "export const en = { a: 'A' };\nexport const de = { a: 'A' };\n";
+        assert!(module_catalog(ambiguous).is_err());
+        assert!(module_catalog("export default { label: t('x') };").is_err());
+    }
+}
@@ -0,0 +1,196 @@
+//! Locale files parsed once per command. The analyses of one command
+//! (completeness, dead keys, type generation) read the same files; a
+//! [`LocaleStore`] parses them in parallel up front and hands the parsed
+//! values to each of them. The time spent parsing shows up as the `parse`
+//! phase of `--summary`.
+
+use anyhow::{anyhow, bail, Context, Result};
+use rayon::prelude::*;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Instant;
+
+use crate::config::{Config, OutputFormat};
+use crate::json_sync;
+use crate::summary;
+
+/// Parsed content of a file, `None` when it is blank
+type Parsed = std::result::Result<Option<Arc<Value>>, Arc<anyhow::Error>>;
+
+/// Parsed locale files by path. Files are never re-read, so a store must not
+/// outlive the command that created it when that command writes locale files.
+#[derive(Default)]
+pub struct LocaleStore {
+    files: Mutex<HashMap<PathBuf, Parsed>>,
+}
+
+impl LocaleStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<PathBuf, Parsed>> {
+        self.files
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Read and parse `paths` in parallel, skipping files already loaded.
+    /// Failures are kept and returned by [`LocaleStore::get`].
+    pub fn preload(&self, paths: &[PathBuf]) {
+        let pending: Vec<&PathBuf> = {
+            let files = self.lock();
+            paths
+                .iter()
+                .filter(|path| !files.contains_key(*path))
+                .collect()
+        };
+        if pending.is_empty() {
+            return;
+        }
+        let started = Instant::now();
+        let parsed: Vec<(PathBuf, Parsed)> = pending
+            .into_par_iter()
+            .map(|path| (path.clone(), parse_file(path)))
+            .collect();
+        summary::add_phase("parse", started.elapsed());
+        self.lock().extend(parsed);
+    }
+
+    /// Preload the locale files of `locales` under `output_dir`
+    pub fn preload_locales(
+        &self,
+        config: &Config,
+        output_dir: &str,
+        locales: &[String],
+    ) -> Result<()> {
+        let mut paths = Vec::new();
+        for locale in locales {
+            paths.extend(json_sync::locale_files(config, output_dir, locale)?);
+        }
+        self.preload(&paths);
+        Ok(())
+    }
+
+    /// Parsed content of `path`, read now unless preloaded; `None` for a blank file
+    pub fn get(&self, path: &Path) -> Result<Option<Arc<Value>>> {
+        let cached = self.lock().get(path).cloned();
+        let parsed = match cached {
+            Some(parsed) => parsed,
+            None => {
+                let parsed = parse_file(path);
+                self.lock().insert(path.to_path_buf(), parsed.clone());
+                parsed
+            }
+        };
+        parsed.map_err(|e| anyhow!("{:#}", e))
+    }
+
+    /// Every namespace of `locale`, keyed by namespace name, as
+    /// [`json_sync::load_locale_namespaces`] returns them
+    pub fn namespaces(
+        &self,
+        config: &Config,
+        output_dir: &str,
+        locale: &str,
+    ) -> Result<BTreeMap<String, Map<String, Value>>> {
+        let paths = json_sync::locale_files(config, output_dir, locale)?;
+        self.preload(&paths);
+
+        let mut namespaces = BTreeMap::new();
+        let split_merged = config.merge_namespaces && !config.namespace_less_mode();
+        for path in paths {
+            let map = match self.get(&path)?.as_deref() {
+                Some(Value::Object(map)) => map.clone(),
+                Some(_) => bail!("Locale file is not an object: {}", path.display()),
+                None => Map::new(),
+            };
+            if split_merged {
+                let map = json_sync::split_merged_namespaces(map, config.merged_namespace_prefix());
+                for (namespace, value) in map {
+                    if let Value::Object(nested) = value {
+                        namespaces.insert(namespace, nested);
+                    }
+                }
+            } else if config.flat_locale_files {
+                namespaces.insert(config.effective_default_namespace().to_string(), map);
+            } else {
+                let namespace = json_sync::file_namespace(&path, config.sharded_locale_files())
+                    .unwrap_or("translation")
+                    .to_string();
+                json_sync::merge_shard(namespaces.entry(namespace).or_default(), map);
+            }
+        }
+        Ok(namespaces)
+    }
+
+    /// Number of files loaded so far
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn parse_file(path: &Path) -> Parsed {
+    let parse = || -> Result<Option<Value>> {
+        let content = crate::fs::read_to_string_shared(path)
+            .with_context(|| format!("Failed to read locale file: {}", path.display()))?;
+        if content.trim().is_empty() {
+            return Ok(None);
+        }
+        let value = match path.extension().and_then(|e| e.to_str()) {
+            Some("json5") => {
+                json_sync::parse_locale_value_str(&content, OutputFormat::Json5, path)?
+            }
+            Some("js") => json_sync::parse_locale_value_str(&content, OutputFormat::JsEsm, path)?,
+            Some("ts") => json_sync::parse_locale_value_str(&content, OutputFormat::Ts, path)?,
+            _ => serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse JSON in: {}", path.display()))?,
+        };
+        Ok(Some(value))
+    };
+    parse().map(|value| value.map(Arc::new)).map_err(Arc::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn store_parses_each_file_once_and_keeps_failures() {
+        let tmp = tempdir().unwrap();
+        let output = tmp.path().join("locales");
+        std::fs::create_dir_all(output.join("en")).unwrap();
+        std::fs::create_dir_all(output.join("de")).unwrap();
+        std::fs::write(output.join("en/common.json"), r#"{"ok":"OK"}"#).unwrap();
+        std::fs::write(output.join("en/blank.json"), "\n").unwrap();
+        std::fs::write(output.join("de/common.json"), "{ broken").unwrap();
+        let config = Config {
+            output: output.to_string_lossy().to_string(),
+            locales: vec!["en".to_string(), "de".to_string()],
+            ..Config::default()
+        };
+
+        let store = LocaleStore::new();
+        store
+            .preload_locales(&config, &config.output, &config.locales)
+            .unwrap();
+        assert_eq!(store.len(), 3);
+
+        // Changes on disk are not seen once a file is loaded
+        std::fs::write(output.join("en/common.json"), r#"{"ok":"Changed"}"#).unwrap();
+        let namespaces = store.namespaces(&config, &config.output, "en").unwrap();
+        assert_eq!(namespaces["common"]["ok"], "OK");
+        assert!(namespaces["blank"].is_empty());
+        assert!(store.get(&output.join("en/blank.json")).unwrap().is_none());
+
+        let err = store.namespaces(&config, &config.output, "de").unwrap_err();
+        assert!(format!("{:#}", err).contains("Failed to parse JSON in"));
+    }
+}
@@ -0,0 +1,310 @@
+//! HTML tag and entity safety of translated values: Trans placeholders
+//! (`<1>`, `<strong>`) must be balanced and match the primary locale, and
+//! translations must not bring in markup the source value does not have.
+
+use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::config::Config;
+use crate::json_sync::{self, PreserveMatcher};
+
+/// Elements that never take a closing tag
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// What is wrong with the markup of one value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarkupProblem {
+    /// Open and close tags do not nest properly
+    Unbalanced(String),
+    /// Tag that the primary-locale value does not contain
+    IntroducedTag(String),
+    /// Tag of the primary-locale value that the translation dropped
+    MissingTag(String),
+    /// HTML entity that the primary-locale value does not contain
+    IntroducedEntity(String),
+}
+
+impl fmt::Display for MarkupProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MarkupProblem::Unbalanced(detail) => write!(f, "unbalanced tags: {}", detail),
+            MarkupProblem::IntroducedTag(tag) => {
+                write!(f, "introduces <{}>, which the source does not have", tag)
+            }
+            MarkupProblem::MissingTag(tag) => write!(f, "drops <{}> from the source", tag),
+            MarkupProblem::IntroducedEntity(entity) => {
+                write!(f, "introduces {}, which the source does not have", entity)
+            }
+        }
+    }
+}
+
+/// A markup problem found in a locale file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkupIssue {
+    pub locale: String,
+    pub namespace: String,
+    pub key: String,
+    pub problem: MarkupProblem,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Tag {
+    Open(String),
+    Close(String),
+    SelfClosing(String),
+}
+
+/// Check every string value of `locales` (all configured locales when empty).
+/// Values of the primary locale are only checked for balance; other locales are
+/// also compared with the primary value of the same key.
+pub fn check_markup(
+    config: &Config,
+    locales: &[String],
+    scope: &PreserveMatcher,
+) -> Result<Vec<MarkupIssue>> {
+    let primary = config.primary_language();
+    let reference = json_sync::flatten_string_values(
+        &json_sync::load_locale_namespaces(config, &config.output, primary)?,
+        &config.key_separator,
+    );
+    let locales = if locales.is_empty() {
+        &config.locales[..]
+    } else {
+        locales
+    };
+
+    let mut issues = Vec::new();
+    for locale in locales {
+        let values = if locale == primary {
+            reference.clone()
+        } else {
+            json_sync::flatten_string_values(
+                &json_sync::load_locale_namespaces(config, &config.output, locale)?,
+                &config.key_separator,
+            )
+        };
+        for ((namespace, key), value) in values {
+            if value.is_empty() || !scope.in_scope(&namespace, &key) {
+                continue;
+            }
+            let source = (locale != primary)
+                .then(|| reference.get(&(namespace.clone(), key.clone())))
+                .flatten()
+                .filter(|source| !source.is_empty());
+            for problem in value_problems(&value, source.map(String::as_str)) {
+                issues.push(MarkupIssue {
+                    locale: locale.clone(),
+                    namespace: namespace.clone(),
+                    key: key.clone(),
+                    problem,
+                });
+            }
+        }
+    }
+    Ok(issues)
+}
+
+/// Problems of one value, compared with its `source` (primary-locale) value when given
+pub fn value_problems(value: &str, source: Option<&str>) -> Vec<MarkupProblem> {
+    let tags = parse_tags(value);
+    let mut problems = Vec::new();
+    if let Some(detail) = balance_error(&tags) {
+        problems.push(MarkupProblem::Unbalanced(detail));
+    }
+
+    let Some(source) = source else {
+        return problems;
+    };
+    let expected = element_counts(&parse_tags(source));
+    let actual = element_counts(&tags);
+    for (name, count) in &actual {
+        if expected.get(name).copied().unwrap_or(0) < *count {
+            problems.push(MarkupProblem::IntroducedTag(name.clone()));
+        }
+    }
+    for (name, count) in &expected {
+        if actual.get(name).copied().unwrap_or(0) < *count {
+            problems.push(MarkupProblem::MissingTag(name.clone()));
+        }
+    }
+
+    let source_entities = entities(source);
+    for entity in entities(value).difference(&source_entities) {
+        problems.push(MarkupProblem::IntroducedEntity(entity.clone()));
+    }
+    problems
+}
+
+/// First nesting error of `tags`, if any
+fn balance_error(tags: &[Tag]) -> Option<String> {
+    let mut open: Vec<&str> = Vec::new();
+    for tag in tags {
+        match tag {
+            Tag::Open(name) if !is_void(name) => open.push(name),
+            Tag::Close(name) => match open.pop() {
+                Some(top) if top == name.as_str() => {}
+                Some(top) => return Some(format!("</{}> closes <{}>", name, top)),
+                None => return Some(format!("</{}> has no opening tag", name)),
+            },
+            _ => {}
+        }
+    }
+    open.last()
+        .map(|name| format!("<{}> is never closed", name))
+}
+
+/// Number of elements per tag name (closing tags are not counted)
+fn element_counts(tags: &[Tag]) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for tag in tags {
+        if let Tag::Open(name) | Tag::SelfClosing(name) = tag {
+            *counts.entry(name.clone()).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+fn is_void(name: &str) -> bool {
+    VOID_ELEMENTS.contains(&name)
+}
+
+/// Tags in `value`. A `<` that does not start a well-formed tag (e.g. `a < b`) is text.
+fn parse_tags(value: &str) -> Vec<Tag> {
+    let mut tags = Vec::new();
+    let mut pos = 0;
+    while let Some(offset) = value[pos..].find('<') {
+        let start = pos + offset;
+        match parse_tag(&value[start..]) {
+            Some((tag, len)) => {
+                tags.push(tag);
+                pos = start + len;
+            }
+            None => pos = start + 1,
+        }
+    }
+    tags
+}
+
+/// Parse a tag at the start of `text`; returns the tag and its byte length
+fn parse_tag(text: &str) -> Option<(Tag, usize)> {
+    let rest = &text[1..];
+    let (closing, rest) = match rest.strip_prefix('/') {
+        Some(rest) => (true, rest),
+        None => (false, rest),
+    };
+    let name_len = rest
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+        .unwrap_or(rest.len());
+    let name = &rest[..name_len];
+    let numeric = name.chars().all(|c| c.is_ascii_digit());
+    if name.is_empty() || !(numeric || name.starts_with(|c: char| c.is_ascii_alphabetic())) {
+        return None;
+    }
+
+    let after_name = &rest[name_len..];
+    let end = after_name.find(['>', '<'])?;
+    if after_name.as_bytes()[end] != b'>' {
+        return None;
+    }
+    let inner = after_name[..end].trim_end();
+    let self_closing = inner.ends_with('/');
+    let attributes = inner.trim_end_matches('/');
+    // Attributes need separating whitespace, and indexed placeholders take none
+    if !attributes.is_empty()
+        && (numeric || closing || !attributes.starts_with(char::is_whitespace))
+    {
+        return None;
+    }
+
+    let name = name.to_ascii_lowercase();
+    let len = text.len() - after_name.len() + end + 1;
+    let tag = if closing {
+        Tag::Close(name)
+    } else if self_closing {
+        Tag::SelfClosing(name)
+    } else {
+        Tag::Open(name)
+    };
+    Some((tag, len))
+}
+
+/// Named and numeric character references (`&nbsp;`, `&#169;`, `&#xA9;`) in `value`
+fn entities(value: &str) -> BTreeSet<String> {
+    let mut found = BTreeSet::new();
+    let mut pos = 0;
+    while let Some(offset) = value[pos..].find('&') {
+        let start = pos + offset;
+        pos = start + 1;
+        let Some(len) = value[pos..].find(';') else {
+            break;
+        };
+        let body = &value[pos..pos + len];
+        let valid = match body.strip_prefix('#') {
+            Some(hex) if hex.starts_with(['x', 'X']) => {
+                hex.len() > 1 && hex[1..].chars().all(|c| c.is_ascii_hexdigit())
+            }
+            Some(digits) => !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()),
+            None => {
+                body.starts_with(|c: char| c.is_ascii_alphabetic())
+                    && body.chars().all(|c| c.is_ascii_alphanumeric())
+            }
+        };
+        if valid {
+            found.insert(format!("&{};", body));
+            pos += len + 1;
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_tags_reads_placeholders_and_html() {
+        assert_eq!(
+            parse_tags("Hello <1>{{name}}</1>, <strong class=\"x\">read</strong><br/> 1 < 2"),
+            vec![
+                Tag::Open("1".to_string()),
+                Tag::Close("1".to_string()),
+                Tag::Open("strong".to_string()),
+                Tag::Close("strong".to_string()),
+                Tag::SelfClosing("br".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn value_problems_report_unbalanced_and_changed_markup() {
+        assert_eq!(
+            value_problems("Go <1>home</2>", None),
+            vec![MarkupProblem::Unbalanced("</2> closes <1>".to_string())]
+        );
+        assert_eq!(
+            value_problems("Line<br>break <b>bold</b>", None),
+            Vec::<MarkupProblem>::new()
+        );
+        assert_eq!(
+            value_problems(
+                "Lesen Sie die <script>x</script> Bedingungen&nbsp;",
+                Some("Read the <1>terms</1>")
+            ),
+            vec![
+                MarkupProblem::IntroducedTag("script".to_string()),
+                MarkupProblem::MissingTag("1".to_string()),
+                MarkupProblem::IntroducedEntity("&nbsp;".to_string()),
+            ]
+        );
+        assert!(value_problems(
+            "Lisez les <1>conditions</1> &amp; règles",
+            Some("Read the <1>terms</1> &amp; rules")
+        )
+        .is_empty());
+    }
+}
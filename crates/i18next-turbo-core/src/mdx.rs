@@ -0,0 +1,308 @@
+//! MDX documents: keep the JSX islands, `{expressions}` and ESM `import`/`export`
+//! blocks, blank prose, frontmatter and fenced code, so the rest parses as TSX.
+
+/// Rewrite an MDX document into a TSX module. Every newline is kept, so line
+/// numbers of extracted keys match the document.
+pub fn to_tsx_module(source: &str) -> String {
+    let chars: Vec<char> = source.chars().collect();
+    let mut out = String::with_capacity(source.len() + 16);
+    let mut depth = 0usize;
+    let mut i = skip_frontmatter(&chars, &mut out);
+    let mut line_start = true;
+
+    while i < chars.len() {
+        if line_start {
+            line_start = false;
+            if let Some(end) = fenced_code_end(&chars, i) {
+                blank(&chars[i..end], &mut out);
+                i = end;
+                line_start = true;
+                continue;
+            }
+            if depth == 0
+                && (starts_with(&chars, i, "import ") || starts_with(&chars, i, "export "))
+            {
+                let end = esm_block_end(&chars, i);
+                out.extend(&chars[i..end]);
+                i = end;
+                line_start = true;
+                continue;
+            }
+        }
+
+        let c = chars[i];
+        match c {
+            '\n' => {
+                out.push('\n');
+                line_start = true;
+                i += 1;
+            }
+            '`' => {
+                let end = code_span_end(&chars, i);
+                blank(&chars[i..end], &mut out);
+                i = end;
+            }
+            '<' if is_tag_start(chars.get(i + 1).copied()) => {
+                let end = tag_end(&chars, i);
+                let tag = &chars[i..end];
+                let closing = tag.get(1) == Some(&'/');
+                let self_closing = !closing
+                    && tag
+                        .iter()
+                        .rev()
+                        .skip(1)
+                        .find(|c| !c.is_whitespace())
+                        .is_some_and(|c| *c == '/');
+                if closing && depth == 0 {
+                    blank(tag, &mut out);
+                } else {
+                    if depth == 0 {
+                        out.push_str(";(");
+                    }
+                    out.extend(tag);
+                    if closing {
+                        depth -= 1;
+                    } else if !self_closing {
+                        depth += 1;
+                    }
+                    if depth == 0 {
+                        out.push_str(");");
+                    }
+                }
+                i = end;
+            }
+            '{' => {
+                let end = balanced_end(&chars, i);
+                if depth == 0 {
+                    // A top-level expression becomes an array literal statement, which
+                    // also accepts `{}` and `{/* comments */}`
+                    let inner_end = if chars[end - 1] == '}' && end > i + 1 {
+                        end - 1
+                    } else {
+                        end
+                    };
+                    out.push_str(";[");
+                    out.extend(&chars[i + 1..inner_end]);
+                    out.push_str("];");
+                } else {
+                    out.extend(&chars[i..end]);
+                }
+                i = end;
+            }
+            _ if depth == 0 => {
+                out.push(if c.is_whitespace() { c } else { ' ' });
+                i += 1;
+            }
+            // Markdown inside JSX children stays as JSX text; characters that
+            // are not allowed there are blanked
+            '>' | '}' | '<' => {
+                out.push(' ');
+                i += 1;
+            }
+            _ => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    out
+}
+
+/// Blank a leading `---` YAML frontmatter block, returning where content starts
+fn skip_frontmatter(chars: &[char], out: &mut String) -> usize {
+    if !starts_with(chars, 0, "---") || line_text(chars, 0).trim_end() != "---" {
+        return 0;
+    }
+    let mut pos = next_line(chars, 0);
+    while pos < chars.len() {
+        let next = next_line(chars, pos);
+        if line_text(chars, pos).trim_end() == "---" {
+            blank(&chars[..next], out);
+            return next;
+        }
+        pos = next;
+    }
+    0
+}
+
+/// End (start of the following line) of a fenced code block opening at `start`
+fn fenced_code_end(chars: &[char], start: usize) -> Option<usize> {
+    let line = line_text(chars, start);
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    let fence = line.trim_start_matches(' ');
+    let marker = fence.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = fence.chars().take_while(|c| *c == marker).count();
+    if indent > 3 || len < 3 {
+        return None;
+    }
+
+    let mut pos = next_line(chars, start);
+    while pos < chars.len() {
+        let next = next_line(chars, pos);
+        let closing = line_text(chars, pos);
+        let closing = closing.trim();
+        if closing.chars().take_while(|c| *c == marker).count() >= len
+            && closing.chars().all(|c| c == marker)
+        {
+            return Some(next);
+        }
+        pos = next;
+    }
+    Some(chars.len())
+}
+
+/// ESM blocks run until the next blank line
+fn esm_block_end(chars: &[char], start: usize) -> usize {
+    let mut pos = start;
+    while pos < chars.len() && !line_text(chars, pos).trim().is_empty() {
+        pos = next_line(chars, pos);
+    }
+    pos
+}
+
+/// End of an inline code span opened by the backtick run at `start`; an
+/// unmatched run is just the backticks
+fn code_span_end(chars: &[char], start: usize) -> usize {
+    let len = chars[start..].iter().take_while(|c| **c == '`').count();
+    let mut pos = start + len;
+    while pos < chars.len() {
+        if chars[pos] == '`' {
+            let run = chars[pos..].iter().take_while(|c| **c == '`').count();
+            if run == len {
+                return pos + run;
+            }
+            pos += run;
+        } else {
+            pos += 1;
+        }
+    }
+    start + len
+}
+
+fn is_tag_start(next: Option<char>) -> bool {
+    next.is_some_and(|c| c.is_ascii_alphabetic() || c == '/' || c == '>')
+}
+
+/// End (after `>`) of the tag at `start`, skipping quoted attribute values and
+/// `{...}` attribute expressions
+fn tag_end(chars: &[char], start: usize) -> usize {
+    let mut pos = start + 1;
+    while pos < chars.len() {
+        match chars[pos] {
+            '>' => return pos + 1,
+            '"' | '\'' => pos = string_end(chars, pos),
+            '{' => pos = balanced_end(chars, pos),
+            _ => pos += 1,
+        }
+    }
+    chars.len()
+}
+
+/// End (after the matching `}`) of the expression opening at `start`
+fn balanced_end(chars: &[char], start: usize) -> usize {
+    let mut nesting = 0usize;
+    let mut pos = start;
+    while pos < chars.len() {
+        match chars[pos] {
+            '{' => nesting += 1,
+            '}' => {
+                nesting -= 1;
+                if nesting == 0 {
+                    return pos + 1;
+                }
+            }
+            '"' | '\'' | '`' => {
+                pos = string_end(chars, pos);
+                continue;
+            }
+            _ => {}
+        }
+        pos += 1;
+    }
+    chars.len()
+}
+
+/// End (after the closing quote) of the string literal opening at `start`
+fn string_end(chars: &[char], start: usize) -> usize {
+    let quote = chars[start];
+    let mut pos = start + 1;
+    while pos < chars.len() {
+        match chars[pos] {
+            '\\' => pos += 2,
+            c if c == quote => return pos + 1,
+            _ => pos += 1,
+        }
+    }
+    chars.len()
+}
+
+fn starts_with(chars: &[char], start: usize, prefix: &str) -> bool {
+    let mut rest = chars[start..].iter();
+    prefix
+        .chars()
+        .all(|expected| rest.next() == Some(&expected))
+}
+
+fn line_text(chars: &[char], start: usize) -> String {
+    chars[start..].iter().take_while(|c| **c != '\n').collect()
+}
+
+fn next_line(chars: &[char], start: usize) -> usize {
+    chars[start..]
+        .iter()
+        .position(|c| *c == '\n')
+        .map_or(chars.len(), |offset| start + offset + 1)
+}
+
+/// Replace everything but newlines with spaces
+fn blank(chars: &[char], out: &mut String) {
+    out.extend(chars.iter().map(|c| if *c == '\n' { '\n' } else { ' ' }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_jsx_expressions_and_esm_and_blanks_prose_and_code() {
+        let source = "---\ntitle: t('front.matter')\n---\nimport { Trans } from 'react-i18next'\nexport const title = t('meta.title')\n\n# Heading with t('prose.key') and `t('inline.code')`\n\n<Callout type=\"info\">\n  <Trans i18nKey=\"callout.body\">Read > the `<docs>`</Trans>\n</Callout>\n\n```tsx\nt('fenced.code')\n```\n\nSee {t('inline.expr')} and {/* note */}.\n";
+        let module = to_tsx_module(source);
+
+        assert_eq!(module.lines().count(), source.lines().count());
+        for kept in [
+            "import { Trans } from 'react-i18next'",
+            "export const title = t('meta.title')",
+            ";(<Callout type=\"info\">",
+            "<Trans i18nKey=\"callout.body\">Read   the         </Trans>",
+            "</Callout>);",
+            ";[t('inline.expr')];",
+            ";[/* note */];",
+        ] {
+            assert!(module.contains(kept), "missing {:?} in:\n{}", kept, module);
+        }
+        for dropped in ["front.matter", "prose.key", "inline.code", "fenced.code"] {
+            assert!(
+                !module.contains(dropped),
+                "kept {:?} in:\n{}",
+                dropped,
+                module
+            );
+        }
+    }
+
+    #[test]
+    fn tracks_nesting_across_lines_and_self_closing_tags() {
+        let module = to_tsx_module("<Card\n  title={t('card.title')}\n/>\ntext\n<A><B/></A>\n");
+        assert_eq!(
+            module,
+            ";(<Card\n  title={t('card.title')}\n/>);\n    \n;(<A><B/></A>);\n"
+        );
+    }
+
+    #[test]
+    fn unclosed_fence_blanks_the_rest() {
+        let module = to_tsx_module("~~~\n<Trans i18nKey=\"x\" />\n");
+        assert_eq!(module.trim(), "");
+    }
+}
@@ -0,0 +1,497 @@
+//! Per-key metadata kept in a sidecar JSON file next to the locale files
+//! (`metadataFile`): when each key was first extracted and last seen, and the
+//! review status of each translation.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::config::Config;
+use crate::extractor::ExtractedKey;
+use crate::fs::{FileSystem, RealFileSystem};
+use crate::json_sync;
+
+/// Review state of one translation, set with `mark`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReviewStatus {
+    MachineTranslated,
+    NeedsReview,
+    Approved,
+}
+
+impl ReviewStatus {
+    pub const ALL: [ReviewStatus; 3] = [
+        ReviewStatus::MachineTranslated,
+        ReviewStatus::NeedsReview,
+        ReviewStatus::Approved,
+    ];
+
+    pub fn parse_str(value: &str) -> Result<Self> {
+        match value {
+            "machine-translated" => Ok(ReviewStatus::MachineTranslated),
+            "needs-review" => Ok(ReviewStatus::NeedsReview),
+            "approved" => Ok(ReviewStatus::Approved),
+            _ => bail!(
+                "Unknown review status '{}'. Use machine-translated, needs-review or approved",
+                value
+            ),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReviewStatus::MachineTranslated => "machine-translated",
+            ReviewStatus::NeedsReview => "needs-review",
+            ReviewStatus::Approved => "approved",
+        }
+    }
+}
+
+/// Metadata of one key. Dates are UTC calendar days (`YYYY-MM-DD`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_seen: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_seen: Option<String>,
+    /// Primary-language value when last extracted; a change resets `review`
+    /// to needs-review
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_value: Option<String>,
+    /// Review status per locale
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub review: BTreeMap<String, ReviewStatus>,
+    /// Day the key was first found unused, while `removalGraceDays` keeps it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dead_since: Option<String>,
+}
+
+/// Contents of the metadata file: namespace -> key -> metadata
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetadataFile {
+    #[serde(default)]
+    pub keys: BTreeMap<String, BTreeMap<String, KeyMetadata>>,
+}
+
+impl MetadataFile {
+    /// Read the metadata file; a missing file is empty metadata
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read metadata file: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid metadata file: {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let fs = RealFileSystem;
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs.create_dir_all(parent)?;
+        }
+        let mut content = serde_json::to_string_pretty(self)?;
+        content.push('\n');
+        fs.atomic_write(path, content.as_bytes())
+            .with_context(|| format!("Failed to write metadata file: {}", path.display()))
+    }
+
+    pub fn get(&self, namespace: &str, key: &str) -> Option<&KeyMetadata> {
+        self.keys.get(namespace)?.get(key)
+    }
+
+    /// Mark keys as seen on `today`, setting `firstSeen` for new ones.
+    /// Returns whether anything changed.
+    pub fn record_seen<'a>(
+        &mut self,
+        keys: impl IntoIterator<Item = (&'a str, &'a str)>,
+        today: &str,
+    ) -> bool {
+        let mut changed = false;
+        for (namespace, key) in keys {
+            let entry = self
+                .keys
+                .entry(namespace.to_string())
+                .or_default()
+                .entry(key.to_string())
+                .or_default();
+            if entry.first_seen.is_none() {
+                entry.first_seen = Some(today.to_string());
+                changed = true;
+            }
+            if entry.last_seen.as_deref() != Some(today) {
+                entry.last_seen = Some(today.to_string());
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Store the primary-language value of known keys. When a stored value
+    /// changes, every reviewed locale of that key goes back to needs-review.
+    /// Returns whether anything changed.
+    pub fn record_source_values<'a>(
+        &mut self,
+        values: impl IntoIterator<Item = (&'a str, &'a str, &'a str)>,
+    ) -> bool {
+        let mut changed = false;
+        for (namespace, key, value) in values {
+            let Some(entry) = self.keys.get_mut(namespace).and_then(|k| k.get_mut(key)) else {
+                continue;
+            };
+            if entry.source_value.as_deref() == Some(value) {
+                continue;
+            }
+            if entry.source_value.is_some() {
+                for status in entry.review.values_mut() {
+                    *status = ReviewStatus::NeedsReview;
+                }
+            }
+            entry.source_value = Some(value.to_string());
+            changed = true;
+        }
+        changed
+    }
+
+    /// Set the review status of `key` in `locale`. Returns whether it changed.
+    pub fn set_review(
+        &mut self,
+        namespace: &str,
+        key: &str,
+        locale: &str,
+        status: ReviewStatus,
+    ) -> bool {
+        let review = &mut self
+            .keys
+            .entry(namespace.to_string())
+            .or_default()
+            .entry(key.to_string())
+            .or_default()
+            .review;
+        review.insert(locale.to_string(), status) != Some(status)
+    }
+
+    pub fn review_status(&self, namespace: &str, key: &str, locale: &str) -> Option<ReviewStatus> {
+        self.get(namespace, key)?.review.get(locale).copied()
+    }
+
+    /// Record the dead keys kept for their grace period (`pending`) and the ones
+    /// removed from the locale files (`purged`). Pending keys keep the day they
+    /// were first found dead, purged keys lose their metadata, and other keys
+    /// `checked` by the run are in use again. Returns whether anything changed.
+    pub fn record_removals(
+        &mut self,
+        checked: impl Fn(&str, &str) -> bool,
+        pending: &BTreeSet<(String, String)>,
+        purged: &BTreeSet<(String, String)>,
+        today: &str,
+    ) -> bool {
+        let mut changed = false;
+        for (namespace, key) in purged {
+            if let Some(keys) = self.keys.get_mut(namespace) {
+                changed |= keys.remove(key).is_some();
+            }
+        }
+        for (namespace, keys) in &mut self.keys {
+            for (key, entry) in keys.iter_mut() {
+                if entry.dead_since.is_some()
+                    && checked(namespace, key)
+                    && !pending.contains(&(namespace.clone(), key.clone()))
+                {
+                    entry.dead_since = None;
+                    changed = true;
+                }
+            }
+        }
+        for (namespace, key) in pending {
+            let entry = self
+                .keys
+                .entry(namespace.clone())
+                .or_default()
+                .entry(key.clone())
+                .or_default();
+            if entry.dead_since.is_none() {
+                entry.dead_since = Some(today.to_string());
+                changed = true;
+            }
+        }
+        self.keys.retain(|_, keys| !keys.is_empty());
+        changed
+    }
+
+    /// Keys waiting out the removal grace period: namespace, key and the day
+    /// they were found dead
+    pub fn dead_keys(&self) -> Vec<(&str, &str, &str)> {
+        self.keys
+            .iter()
+            .flat_map(|(namespace, keys)| {
+                keys.iter().filter_map(move |(key, metadata)| {
+                    Some((
+                        namespace.as_str(),
+                        key.as_str(),
+                        metadata.dead_since.as_deref()?,
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    /// Number of keys first seen in each month (`YYYY-MM`)
+    pub fn growth_by_month(&self) -> BTreeMap<String, usize> {
+        let mut months = BTreeMap::new();
+        for metadata in self.keys.values().flat_map(|keys| keys.values()) {
+            if let Some(month) = metadata.first_seen.as_deref().and_then(|d| d.get(..7)) {
+                *months.entry(month.to_string()).or_insert(0) += 1;
+            }
+        }
+        months
+    }
+}
+
+/// Update `lastSeen` (and `firstSeen` for new keys) of extracted keys in the
+/// configured metadata file, and reset the review status of keys whose
+/// primary-language value changed. Does nothing when `metadataFile` is not set.
+pub fn record_extracted_keys(config: &Config, keys: &[ExtractedKey]) -> Result<()> {
+    let Some(path) = config.metadata_file.as_deref() else {
+        return Ok(());
+    };
+    let path = Path::new(path);
+    let mut metadata = MetadataFile::load(path)?;
+    let seen = keys.iter().map(|key| {
+        let namespace = key
+            .namespace
+            .as_deref()
+            .unwrap_or(config.effective_default_namespace());
+        (namespace, key.key.as_str())
+    });
+    let mut changed = metadata.record_seen(seen, &today());
+
+    let primary =
+        json_sync::load_locale_namespaces(config, &config.output, config.primary_language())?;
+    let values = json_sync::flatten_string_values(&primary, &config.key_separator);
+    changed |= metadata.record_source_values(
+        values
+            .iter()
+            .map(|((namespace, key), value)| (namespace.as_str(), key.as_str(), value.as_str())),
+    );
+
+    if changed {
+        metadata.save(path)?;
+    }
+    Ok(())
+}
+
+/// Dead keys whose `removalGraceDays` are over, the only ones extraction may
+/// remove. `None` when no grace period is configured: every dead key goes.
+pub fn expired_dead_keys(config: &Config) -> Result<Option<HashSet<(String, String)>>> {
+    let (Some(days), Some(path)) = (config.removal_grace_days, config.metadata_file.as_deref())
+    else {
+        return Ok(None);
+    };
+    let metadata = MetadataFile::load(Path::new(path))?;
+    let today = today();
+    Ok(Some(
+        metadata
+            .dead_keys()
+            .into_iter()
+            .filter(|(_, _, since)| {
+                days_between(since, &today).is_some_and(|age| age >= i64::from(days))
+            })
+            .map(|(namespace, key, _)| (namespace.to_string(), key.to_string()))
+            .collect(),
+    ))
+}
+
+/// Save the outcome of a sync under `removalGraceDays` to the metadata file
+/// (see [`MetadataFile::record_removals`]). Does nothing without a grace period.
+pub fn record_removals(
+    config: &Config,
+    checked: impl Fn(&str, &str) -> bool,
+    pending: &BTreeSet<(String, String)>,
+    purged: &BTreeSet<(String, String)>,
+) -> Result<()> {
+    let (Some(_), Some(path)) = (config.removal_grace_days, config.metadata_file.as_deref()) else {
+        return Ok(());
+    };
+    let path = Path::new(path);
+    let mut metadata = MetadataFile::load(path)?;
+    if metadata.record_removals(checked, pending, purged, &today()) {
+        metadata.save(path)?;
+    }
+    Ok(())
+}
+
+/// `date` (`YYYY-MM-DD`) moved by `days`
+pub fn add_days(date: &str, days: i64) -> Option<String> {
+    Some(format_date(parse_date(date)? + days))
+}
+
+/// Current UTC date as `YYYY-MM-DD`
+pub fn today() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    format_date((seconds / 86_400) as i64)
+}
+
+/// Whole days from `from` to `to` (both `YYYY-MM-DD`)
+pub fn days_between(from: &str, to: &str) -> Option<i64> {
+    Some(parse_date(to)? - parse_date(from)?)
+}
+
+/// Days since 1970-01-01 of a `YYYY-MM-DD` date
+fn parse_date(date: &str) -> Option<i64> {
+    let mut parts = date.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next()?.parse().ok()?;
+    let day: i64 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    // Civil-from-days inverse (proleptic Gregorian calendar, March-based years)
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    Some(era * 146_097 + day_of_era - 719_468)
+}
+
+fn format_date(days: i64) -> String {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn dates_round_trip_and_count_days() {
+        for date in ["1970-01-01", "2000-02-29", "2024-12-31", "2026-03-01"] {
+            assert_eq!(format_date(parse_date(date).unwrap()), date);
+        }
+        assert_eq!(days_between("2026-01-15", "2026-04-15"), Some(90));
+        assert_eq!(days_between("2024-02-28", "2024-03-01"), Some(2));
+        assert_eq!(days_between("not-a-date", "2024-03-01"), None);
+        assert_eq!(add_days("2026-02-20", 30).as_deref(), Some("2026-03-22"));
+    }
+
+    #[test]
+    fn record_seen_keeps_first_seen_and_round_trips() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("meta/keys.json");
+
+        let mut metadata = MetadataFile::load(&path).unwrap();
+        assert!(metadata.record_seen([("common", "ok"), ("common", "cancel")], "2026-01-10"));
+        assert!(!metadata.record_seen([("common", "ok")], "2026-01-10"));
+        assert!(metadata.record_seen([("common", "ok")], "2026-02-03"));
+        metadata.save(&path).unwrap();
+
+        let loaded = MetadataFile::load(&path).unwrap();
+        assert_eq!(
+            loaded.get("common", "ok"),
+            Some(&KeyMetadata {
+                first_seen: Some("2026-01-10".to_string()),
+                last_seen: Some("2026-02-03".to_string()),
+                ..KeyMetadata::default()
+            })
+        );
+        assert_eq!(
+            loaded.growth_by_month(),
+            BTreeMap::from([("2026-01".to_string(), 2)])
+        );
+    }
+
+    #[test]
+    fn changed_source_value_resets_review_to_needs_review() {
+        let mut metadata = MetadataFile::default();
+        metadata.record_seen([("common", "ok"), ("common", "cancel")], "2026-01-10");
+        assert!(metadata.record_source_values([("common", "ok", "OK"), ("common", "x", "X")]));
+        assert!(metadata.set_review("common", "ok", "de", ReviewStatus::Approved));
+        assert!(metadata.set_review("common", "ok", "fr", ReviewStatus::MachineTranslated));
+        assert!(!metadata.set_review("common", "ok", "de", ReviewStatus::Approved));
+
+        assert!(!metadata.record_source_values([("common", "ok", "OK")]));
+        assert_eq!(
+            metadata.review_status("common", "ok", "de"),
+            Some(ReviewStatus::Approved)
+        );
+
+        assert!(metadata.record_source_values([("common", "ok", "Okay")]));
+        for locale in ["de", "fr"] {
+            assert_eq!(
+                metadata.review_status("common", "ok", locale),
+                Some(ReviewStatus::NeedsReview)
+            );
+        }
+        assert_eq!(metadata.get("common", "x"), None);
+        assert_eq!(metadata.review_status("common", "cancel", "de"), None);
+
+        let json = serde_json::to_value(&metadata).unwrap();
+        assert_eq!(json["keys"]["common"]["ok"]["review"]["de"], "needs-review");
+        assert_eq!(json["keys"]["common"]["ok"]["sourceValue"], "Okay");
+        assert_eq!(
+            ReviewStatus::parse_str("machine-translated").unwrap(),
+            ReviewStatus::MachineTranslated
+        );
+        assert!(ReviewStatus::parse_str("done").is_err());
+    }
+
+    #[test]
+    fn record_removals_tracks_pending_revived_and_purged_keys() {
+        let mut metadata = MetadataFile::default();
+        metadata.record_seen([("common", "seasonal"), ("common", "old")], "2026-01-10");
+        let set = |keys: &[&str]| -> BTreeSet<(String, String)> {
+            keys.iter()
+                .map(|key| ("common".to_string(), key.to_string()))
+                .collect()
+        };
+
+        assert!(metadata.record_removals(
+            |_, _| true,
+            &set(&["seasonal", "old"]),
+            &set(&[]),
+            "2026-02-01"
+        ));
+        assert!(!metadata.record_removals(
+            |_, _| true,
+            &set(&["seasonal", "old"]),
+            &set(&[]),
+            "2026-02-05"
+        ));
+        assert_eq!(
+            metadata.dead_keys(),
+            vec![
+                ("common", "old", "2026-02-01"),
+                ("common", "seasonal", "2026-02-01")
+            ]
+        );
+
+        // `seasonal` is used again, `old` was removed after its grace period
+        assert!(metadata.record_removals(|_, _| true, &set(&[]), &set(&["old"]), "2026-03-10"));
+        assert!(metadata.dead_keys().is_empty());
+        assert_eq!(metadata.get("common", "old"), None);
+        assert_eq!(
+            metadata
+                .get("common", "seasonal")
+                .and_then(|m| m.first_seen.as_deref()),
+            Some("2026-01-10")
+        );
+    }
+}
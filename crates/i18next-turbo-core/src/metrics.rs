@@ -0,0 +1,295 @@
+//! Run metrics of `extract` (phase timings, key and warning counts), written to
+//! `--metrics-file` as JSON or a Prometheus textfile, or pushed to an OTLP/HTTP
+//! collector with `--otlp-endpoint`.
+
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Timings and counters of one `extract` run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExtractMetrics {
+    pub extract_duration: Duration,
+    pub sync_duration: Duration,
+    pub typegen_duration: Duration,
+    pub total_duration: Duration,
+    /// Source files that produced at least one key
+    pub files: usize,
+    /// Unique keys (namespace + key)
+    pub keys: usize,
+    pub keys_by_namespace: BTreeMap<String, usize>,
+    pub keys_added: usize,
+    pub keys_removed: usize,
+    pub dynamic_keys: usize,
+    pub warnings: usize,
+    pub errors: usize,
+    pub conflicts: usize,
+}
+
+/// One exported value: metric name (without prefix), label and value
+struct Sample<'a> {
+    name: &'static str,
+    help: &'static str,
+    label: Option<(&'static str, &'a str)>,
+    value: f64,
+}
+
+impl ExtractMetrics {
+    fn samples(&self) -> Vec<Sample<'_>> {
+        let phases = [
+            ("extract", self.extract_duration),
+            ("sync", self.sync_duration),
+            ("typegen", self.typegen_duration),
+            ("total", self.total_duration),
+        ];
+        let counters = [
+            ("files", "Source files that produced keys", self.files),
+            ("keys", "Unique extracted keys", self.keys),
+            ("keys_added", "Keys added to locale files", self.keys_added),
+            (
+                "keys_removed",
+                "Stale keys removed from locale files",
+                self.keys_removed,
+            ),
+            (
+                "dynamic_keys",
+                "Translation calls with dynamic keys",
+                self.dynamic_keys,
+            ),
+            ("warnings", "Extraction warnings", self.warnings),
+            ("errors", "Extraction errors", self.errors),
+            ("conflicts", "Keys skipped due to conflicts", self.conflicts),
+        ];
+
+        let mut samples: Vec<Sample> = phases
+            .into_iter()
+            .map(|(phase, duration)| Sample {
+                name: "phase_duration_seconds",
+                help: "Wall-clock time of an extract phase",
+                label: Some(("phase", phase)),
+                value: duration.as_secs_f64(),
+            })
+            .collect();
+        samples.extend(counters.into_iter().map(|(name, help, value)| Sample {
+            name,
+            help,
+            label: None,
+            value: value as f64,
+        }));
+        samples.extend(
+            self.keys_by_namespace
+                .iter()
+                .map(|(namespace, count)| Sample {
+                    name: "namespace_keys",
+                    help: "Unique extracted keys per namespace",
+                    label: Some(("namespace", namespace.as_str())),
+                    value: *count as f64,
+                }),
+        );
+        samples
+    }
+
+    /// `{ "durationsMs": {...}, "files": N, "keys": N, ..., "keysByNamespace": {...} }`
+    pub fn to_json(&self) -> Value {
+        let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        json!({
+            "timestamp": httpdate::fmt_http_date(SystemTime::now()),
+            "durationsMs": {
+                "extract": ms(self.extract_duration),
+                "sync": ms(self.sync_duration),
+                "typegen": ms(self.typegen_duration),
+                "total": ms(self.total_duration),
+            },
+            "files": self.files,
+            "keys": self.keys,
+            "keysAdded": self.keys_added,
+            "keysRemoved": self.keys_removed,
+            "dynamicKeys": self.dynamic_keys,
+            "warnings": self.warnings,
+            "errors": self.errors,
+            "conflicts": self.conflicts,
+            "keysByNamespace": self.keys_by_namespace,
+        })
+    }
+
+    /// Prometheus text exposition format (for the node_exporter textfile collector)
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        let mut previous = "";
+        for sample in self.samples() {
+            if sample.name != previous {
+                out.push_str(&format!(
+                    "# HELP i18next_turbo_{0} {1}\n# TYPE i18next_turbo_{0} gauge\n",
+                    sample.name, sample.help
+                ));
+                previous = sample.name;
+            }
+            let label = sample
+                .label
+                .map(|(name, value)| format!("{{{}=\"{}\"}}", name, escape_label(value)))
+                .unwrap_or_default();
+            out.push_str(&format!(
+                "i18next_turbo_{}{} {}\n",
+                sample.name, label, sample.value
+            ));
+        }
+        out
+    }
+
+    /// OTLP/HTTP JSON `ExportMetricsServiceRequest` with one gauge per metric
+    pub fn to_otlp(&self, time_unix_nano: u128) -> Value {
+        let mut metrics: Vec<Value> = Vec::new();
+        let mut previous = "";
+        for sample in self.samples() {
+            let attributes: Vec<Value> = sample
+                .label
+                .map(|(key, value)| json!({ "key": key, "value": { "stringValue": value } }))
+                .into_iter()
+                .collect();
+            let point = json!({
+                "timeUnixNano": time_unix_nano.to_string(),
+                "asDouble": sample.value,
+                "attributes": attributes,
+            });
+            if sample.name == previous {
+                if let Some(points) = metrics
+                    .last_mut()
+                    .and_then(|m| m["gauge"]["dataPoints"].as_array_mut())
+                {
+                    points.push(point);
+                }
+                continue;
+            }
+            previous = sample.name;
+            metrics.push(json!({
+                "name": format!("i18next_turbo.{}", sample.name),
+                "description": sample.help,
+                "gauge": { "dataPoints": [point] },
+            }));
+        }
+
+        json!({
+            "resourceMetrics": [{
+                "resource": {
+                    "attributes": [
+                        { "key": "service.name", "value": { "stringValue": "i18next-turbo" } }
+                    ]
+                },
+                "scopeMetrics": [{
+                    "scope": { "name": "i18next-turbo", "version": env!("CARGO_PKG_VERSION") },
+                    "metrics": metrics,
+                }],
+            }]
+        })
+    }
+
+    /// Write to `path`: Prometheus text for `.prom`/`.txt` files, JSON otherwise
+    pub fn write_file(&self, path: &Path) -> Result<()> {
+        let content = match path.extension().and_then(|e| e.to_str()) {
+            Some("prom" | "txt") => self.to_prometheus(),
+            _ => serde_json::to_string_pretty(&self.to_json())? + "\n",
+        };
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write metrics file: {}", path.display()))
+    }
+
+    /// POST the metrics to an OTLP/HTTP collector. `/v1/metrics` is appended to
+    /// base URLs like `http://localhost:4318`.
+    pub fn push_otlp(&self, endpoint: &str) -> Result<()> {
+        let url = otlp_metrics_url(endpoint);
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let response = reqwest::blocking::Client::new()
+            .post(&url)
+            .json(&self.to_otlp(now))
+            .send()
+            .with_context(|| format!("OTLP export request failed: {}", url))?;
+        if !response.status().is_success() {
+            bail!("OTLP export failed ({}): {}", response.status(), url);
+        }
+        Ok(())
+    }
+}
+
+fn otlp_metrics_url(endpoint: &str) -> String {
+    let endpoint = endpoint.trim_end_matches('/');
+    if endpoint.ends_with("/v1/metrics") {
+        endpoint.to_string()
+    } else {
+        format!("{}/v1/metrics", endpoint)
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metrics_export_as_prometheus_json_and_otlp() {
+        let metrics = ExtractMetrics {
+            extract_duration: Duration::from_millis(250),
+            total_duration: Duration::from_millis(400),
+            files: 3,
+            keys: 5,
+            keys_by_namespace: BTreeMap::from([
+                ("common".to_string(), 2),
+                ("translation".to_string(), 3),
+            ]),
+            keys_added: 1,
+            warnings: 2,
+            ..ExtractMetrics::default()
+        };
+
+        let prometheus = metrics.to_prometheus();
+        assert!(prometheus.contains("# TYPE i18next_turbo_phase_duration_seconds gauge\n"));
+        assert!(
+            prometheus.contains("i18next_turbo_phase_duration_seconds{phase=\"extract\"} 0.25\n")
+        );
+        assert!(prometheus.contains("i18next_turbo_keys 5\n"));
+        assert!(prometheus.contains("i18next_turbo_namespace_keys{namespace=\"common\"} 2\n"));
+        assert_eq!(
+            prometheus
+                .matches("# HELP i18next_turbo_namespace_keys")
+                .count(),
+            1
+        );
+
+        let json = metrics.to_json();
+        assert_eq!(json["durationsMs"]["total"], 400.0);
+        assert_eq!(json["keysByNamespace"]["translation"], 3);
+        assert_eq!(json["warnings"], 2);
+
+        let otlp = metrics.to_otlp(7);
+        let exported = &otlp["resourceMetrics"][0]["scopeMetrics"][0]["metrics"];
+        assert_eq!(exported[0]["name"], "i18next_turbo.phase_duration_seconds");
+        assert_eq!(
+            exported[0]["gauge"]["dataPoints"].as_array().unwrap().len(),
+            4
+        );
+        assert_eq!(exported[0]["gauge"]["dataPoints"][0]["timeUnixNano"], "7");
+
+        assert_eq!(
+            otlp_metrics_url("http://localhost:4318/"),
+            "http://localhost:4318/v1/metrics"
+        );
+        assert_eq!(
+            otlp_metrics_url("https://otel.example.com/v1/metrics"),
+            "https://otel.example.com/v1/metrics"
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use anyhow::{bail, Result};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::config::Config;
+use crate::extractor::ExtractedKey;
+use crate::json_sync;
+
+/// A namespace used in source that is missing from locale files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamespaceIssue {
+    pub namespace: String,
+    /// First source file using the namespace
+    pub file: String,
+    /// Locales without the namespace
+    pub missing_locales: Vec<String>,
+    /// Closest existing namespace when no locale has this one (likely a typo)
+    pub suggestion: Option<String>,
+}
+
+/// Compare namespaces of extracted keys (`useTranslation('x')`, `x:` prefixes,
+/// `<Trans ns>`) against the namespaces present in every configured locale
+pub fn check_namespaces(
+    config: &Config,
+    files: &[(String, Vec<ExtractedKey>)],
+) -> Result<Vec<NamespaceIssue>> {
+    if config.namespace_less_mode() {
+        return Ok(Vec::new());
+    }
+
+    let mut used: BTreeMap<&str, &str> = BTreeMap::new();
+    for (file, keys) in files {
+        for key in keys {
+            let namespace = key
+                .namespace
+                .as_deref()
+                .unwrap_or(config.effective_default_namespace());
+            used.entry(namespace).or_insert(file.as_str());
+        }
+    }
+
+    let mut present: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+    for locale in &config.locales {
+        let namespaces = json_sync::load_locale_namespaces(config, &config.output, locale)?;
+        present.insert(locale.as_str(), namespaces.into_keys().collect());
+    }
+    let known: BTreeSet<&str> = present
+        .values()
+        .flatten()
+        .map(String::as_str)
+        .chain(std::iter::once(config.effective_default_namespace()))
+        .collect();
+
+    Ok(used
+        .into_iter()
+        .filter_map(|(namespace, file)| {
+            let missing_locales: Vec<String> = present
+                .iter()
+                .filter(|(_, namespaces)| !namespaces.contains(namespace))
+                .map(|(locale, _)| locale.to_string())
+                .collect();
+            if missing_locales.is_empty() {
+                return None;
+            }
+            let suggestion = if missing_locales.len() == present.len() {
+                closest_namespace(namespace, &known)
+            } else {
+                None
+            };
+            Some(NamespaceIssue {
+                namespace: namespace.to_string(),
+                file: file.to_string(),
+                missing_locales,
+                suggestion,
+            })
+        })
+        .collect())
+}
+
+/// Known namespace within a small edit distance of `namespace`
+fn closest_namespace(namespace: &str, known: &BTreeSet<&str>) -> Option<String> {
+    let max_distance = (namespace.chars().count() / 3).clamp(1, 3);
+    known
+        .iter()
+        .filter(|candidate| **candidate != namespace)
+        .map(|candidate| (edit_distance(namespace, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min()
+        .map(|(_, candidate)| candidate.to_string())
+}
+
+/// Levenshtein distance, case-insensitive
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Print namespace issues and fail when `strictNamespaces` is enabled
+pub fn report_namespaces(config: &Config, issues: &[NamespaceIssue]) -> Result<()> {
+    if issues.is_empty() {
+        return Ok(());
+    }
+
+    eprintln!();
+    eprintln!(
+        "\x1b[33m⚠ Warning: {} namespace(s) used in source are missing from locale files:\x1b[0m",
+        issues.len()
+    );
+    for issue in issues {
+        match &issue.suggestion {
+            Some(suggestion) => eprintln!(
+                "  {} (used in {}): unknown namespace, did you mean '{}'?",
+                issue.namespace, issue.file, suggestion
+            ),
+            None => eprintln!(
+                "  {} (used in {}): no file for {}",
+                issue.namespace,
+                issue.file,
+                issue.missing_locales.join(", ")
+            ),
+        }
+    }
+
+    if config.strict_namespaces {
+        bail!(
+            "{} namespace(s) missing from locale files (strictNamespaces enabled)",
+            issues.len()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extractor::test_key as key;
+    use tempfile::tempdir;
+
+    #[test]
+    fn check_namespaces_reports_missing_locales_and_typos() {
+        let tmp = tempdir().unwrap();
+        let output = tmp.path().join("locales");
+        for (locale, namespaces) in [
+            ("en", &["translation", "common", "auth"][..]),
+            ("de", &["translation", "common"][..]),
+        ] {
+            std::fs::create_dir_all(output.join(locale)).unwrap();
+            for namespace in namespaces {
+                std::fs::write(
+                    output.join(locale).join(format!("{}.json", namespace)),
+                    "{}",
+                )
+                .unwrap();
+            }
+        }
+        let mut config = Config {
+            output: output.to_string_lossy().to_string(),
+            locales: vec!["en".to_string(), "de".to_string()],
+            ..Config::default()
+        };
+        let files = vec![
+            (
+                "src/a.ts".to_string(),
+                vec![key("title", None), key("save", Some("common"))],
+            ),
+            (
+                "src/b.ts".to_string(),
+                vec![key("login", Some("auth")), key("ok", Some("comon"))],
+            ),
+            ("src/c.ts".to_string(), vec![key("x", Some("billing"))]),
+        ];
+
+        let issues = check_namespaces(&config, &files).unwrap();
+        assert_eq!(
+            issues,
+            vec![
+                NamespaceIssue {
+                    namespace: "auth".to_string(),
+                    file: "src/b.ts".to_string(),
+                    missing_locales: vec!["de".to_string()],
+                    suggestion: None,
+                },
+                NamespaceIssue {
+                    namespace: "billing".to_string(),
+                    file: "src/c.ts".to_string(),
+                    missing_locales: vec!["de".to_string(), "en".to_string()],
+                    suggestion: None,
+                },
+                NamespaceIssue {
+                    namespace: "comon".to_string(),
+                    file: "src/b.ts".to_string(),
+                    missing_locales: vec!["de".to_string(), "en".to_string()],
+                    suggestion: Some("common".to_string()),
+                },
+            ]
+        );
+
+        assert!(report_namespaces(&config, &issues).is_ok());
+        config.strict_namespaces = true;
+        assert!(report_namespaces(&config, &issues).is_err());
+    }
+
+    #[test]
+    fn edit_distance_counts_single_char_edits() {
+        assert_eq!(edit_distance("common", "comon"), 1);
+        assert_eq!(edit_distance("Auth", "auth"), 0);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+}
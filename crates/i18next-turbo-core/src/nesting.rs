@@ -0,0 +1,484 @@
+//! Graph of nested translations (`$t(other.key)`) between the values of a
+//! locale: reference cycles hang i18next at runtime, and references to keys
+//! neither the locale nor its fallbacks have render the raw key. Formatter
+//! names of interpolations (`{{price, currency}}`) are checked against
+//! `allowedFormatters` here too.
+
+use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::config::Config;
+use crate::extractor::{
+    comment_option_exists, extract_comment_string_option, parse_nested_key_token,
+    split_top_level_once,
+};
+use crate::json_sync::{self, PreserveMatcher};
+use crate::paths;
+
+/// `(namespace, key)` of a translation value
+pub type NestingNode = (String, String);
+
+/// A `$t(...)` reference inside a value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NestedReference {
+    /// Key as written, including a namespace prefix
+    pub raw: String,
+    pub namespace: Option<String>,
+    pub key: String,
+    pub context: Option<String>,
+    /// Passes `count`, so any plural form of the key satisfies it
+    pub plural: bool,
+}
+
+/// What is wrong with the nesting of one value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NestingProblem {
+    /// The value takes part in a reference cycle; the path starts and ends
+    /// with the value's own key
+    Cycle(Vec<String>),
+    /// Reference to a key neither the locale nor its fallbacks have
+    MissingReference(String),
+    /// Interpolation formatter that is neither built in nor in `allowedFormatters`
+    UnknownFormatter(String),
+}
+
+impl fmt::Display for NestingProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NestingProblem::Cycle(path) => write!(f, "nesting cycle: {}", path.join(" -> ")),
+            NestingProblem::MissingReference(key) => {
+                write!(f, "references missing key '{}'", key)
+            }
+            NestingProblem::UnknownFormatter(name) => {
+                write!(f, "uses unknown formatter '{}'", name)
+            }
+        }
+    }
+}
+
+/// A nesting problem found in a locale
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NestingIssue {
+    pub locale: String,
+    /// Locale file holding the value
+    pub file: String,
+    pub namespace: String,
+    pub key: String,
+    pub problem: NestingProblem,
+}
+
+/// References between the string values of one locale
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NestingGraph {
+    pub edges: BTreeMap<NestingNode, BTreeSet<NestingNode>>,
+    /// References resolving to no key, by referencing value
+    pub missing: BTreeMap<NestingNode, Vec<NestedReference>>,
+}
+
+impl NestingGraph {
+    /// Build the graph of `values` as returned by
+    /// [`json_sync::flatten_string_values`]. A reference without a namespace
+    /// resolves in the value's own namespace first, then the default one.
+    pub fn build(config: &Config, values: &BTreeMap<NestingNode, String>) -> Self {
+        let mut graph = Self::default();
+        for (node, value) in values {
+            for reference in nested_references(config, value) {
+                match resolve_in(config, values, &node.0, &reference) {
+                    Some(targets) => graph.edges.entry(node.clone()).or_default().extend(targets),
+                    None => graph
+                        .missing
+                        .entry(node.clone())
+                        .or_default()
+                        .push(reference),
+                }
+            }
+        }
+        graph
+    }
+
+    /// Every reference cycle once, each starting at its smallest node and
+    /// ending where it started
+    pub fn cycles(&self) -> Vec<Vec<NestingNode>> {
+        let mut cycles = BTreeSet::new();
+        let mut done = BTreeSet::new();
+        for start in self.edges.keys() {
+            let mut path = Vec::new();
+            self.find_cycles(start, &mut path, &mut done, &mut cycles);
+        }
+        cycles.into_iter().collect()
+    }
+
+    fn find_cycles<'a>(
+        &'a self,
+        node: &'a NestingNode,
+        path: &mut Vec<&'a NestingNode>,
+        done: &mut BTreeSet<&'a NestingNode>,
+        cycles: &mut BTreeSet<Vec<NestingNode>>,
+    ) {
+        if let Some(position) = path.iter().position(|visited| *visited == node) {
+            let cycle = &path[position..];
+            let smallest = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap_or(0);
+            let mut canonical: Vec<NestingNode> = cycle[smallest..]
+                .iter()
+                .chain(&cycle[..smallest])
+                .map(|node| (*node).clone())
+                .collect();
+            canonical.push(canonical[0].clone());
+            cycles.insert(canonical);
+            return;
+        }
+        if done.contains(node) {
+            return;
+        }
+        path.push(node);
+        for next in self.edges.get(node).into_iter().flatten() {
+            self.find_cycles(next, path, done, cycles);
+        }
+        path.pop();
+        done.insert(node);
+    }
+}
+
+/// Built-in i18next formatters, allowed next to `allowedFormatters`
+pub const BUILTIN_FORMATTERS: &[&str] = &["number", "currency", "datetime", "relativetime", "list"];
+
+/// Check the nesting of `locales` (all configured locales when empty), and
+/// their formatter names when `allowedFormatters` is set. A reference missing
+/// in a locale is fine when a fallback has it (see [`fallback_locales`]).
+/// Values outside `scope` are not reported but still resolve references.
+pub fn check_nesting(
+    config: &Config,
+    locales: &[String],
+    scope: &PreserveMatcher,
+) -> Result<Vec<NestingIssue>> {
+    let locales = if locales.is_empty() {
+        &config.locales[..]
+    } else {
+        locales
+    };
+
+    let mut loaded: BTreeMap<String, BTreeMap<NestingNode, String>> = BTreeMap::new();
+    let mut values_of = |locale: &str| -> Result<BTreeMap<NestingNode, String>> {
+        if let Some(values) = loaded.get(locale) {
+            return Ok(values.clone());
+        }
+        let values = json_sync::flatten_string_values(
+            &json_sync::load_locale_namespaces(config, &config.output, locale)?,
+            &config.key_separator,
+        );
+        loaded.insert(locale.to_string(), values.clone());
+        Ok(values)
+    };
+
+    let mut issues = Vec::new();
+    for locale in locales {
+        let values = values_of(locale)?;
+        let issue = |namespace: &str, key: &str, problem: NestingProblem| NestingIssue {
+            locale: locale.clone(),
+            file: paths::display(
+                &json_sync::locale_namespace_file_path(config, &config.output, locale, namespace),
+                config.path_style,
+            ),
+            namespace: namespace.to_string(),
+            key: key.to_string(),
+            problem,
+        };
+
+        let graph = NestingGraph::build(config, &values);
+        for cycle in graph.cycles() {
+            let (namespace, key) = &cycle[0];
+            if !cycle.iter().any(|(ns, key)| scope.in_scope(ns, key)) {
+                continue;
+            }
+            issues.push(issue(
+                namespace,
+                key,
+                NestingProblem::Cycle(
+                    cycle
+                        .iter()
+                        .map(|node| display_node(config, node))
+                        .collect(),
+                ),
+            ));
+        }
+
+        let fallbacks = fallback_locales(config, locale)
+            .into_iter()
+            .map(|fallback| values_of(&fallback))
+            .collect::<Result<Vec<_>>>()?;
+        for ((namespace, key), references) in &graph.missing {
+            if !scope.in_scope(namespace, key) {
+                continue;
+            }
+            for reference in references {
+                let in_fallback = fallbacks.iter().any(|values| {
+                    !resolve_in(config, values, namespace, reference)
+                        .unwrap_or_default()
+                        .is_empty()
+                });
+                if !in_fallback {
+                    issues.push(issue(
+                        namespace,
+                        key,
+                        NestingProblem::MissingReference(reference.raw.clone()),
+                    ));
+                }
+            }
+        }
+
+        if let Some(allowed) = &config.allowed_formatters {
+            for ((namespace, key), value) in &values {
+                if !scope.in_scope(namespace, key) {
+                    continue;
+                }
+                for name in interpolation_formatters(config, value) {
+                    if !BUILTIN_FORMATTERS.contains(&name.as_str()) && !allowed.contains(&name) {
+                        issues.push(issue(
+                            namespace,
+                            key,
+                            NestingProblem::UnknownFormatter(name),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    Ok(issues)
+}
+
+/// Locales i18next falls back to from `locale`: its base language (`de` for
+/// `de-AT`) when configured, then the primary language
+pub fn fallback_locales(config: &Config, locale: &str) -> Vec<String> {
+    let mut fallbacks = Vec::new();
+    if let Some((base, _)) = locale.split_once('-') {
+        if config.locales.iter().any(|configured| configured == base) {
+            fallbacks.push(base.to_string());
+        }
+    }
+    let primary = config.primary_language();
+    if primary != locale && !fallbacks.iter().any(|fallback| fallback == primary) {
+        fallbacks.push(primary.to_string());
+    }
+    fallbacks
+}
+
+/// Formatter names of the interpolations of `value`: `number` and `uppercase`
+/// in `{{price, number(minimumFractionDigits: 2), uppercase}}`
+pub fn interpolation_formatters(config: &Config, value: &str) -> Vec<String> {
+    let (prefix, suffix) = (&config.interpolation_prefix, &config.interpolation_suffix);
+    if prefix.is_empty() || suffix.is_empty() {
+        return Vec::new();
+    }
+    let mut names = Vec::new();
+    let mut cursor = 0usize;
+    while let Some(start) = value[cursor..].find(prefix.as_str()) {
+        let inner_start = cursor + start + prefix.len();
+        let Some(end) = value[inner_start..].find(suffix.as_str()) else {
+            break;
+        };
+        let mut rest = &value[inner_start..inner_start + end];
+        cursor = inner_start + end + suffix.len();
+
+        // The first part is the interpolated value
+        let mut first = true;
+        loop {
+            let (part, tail) = split_top_level_once(rest, ",").unwrap_or((rest, ""));
+            if !first {
+                let name = part.split('(').next().unwrap_or_default().trim();
+                if !name.is_empty() {
+                    names.push(name.to_string());
+                }
+            }
+            first = false;
+            if tail.is_empty() {
+                break;
+            }
+            rest = tail;
+        }
+    }
+    names
+}
+
+/// `$t(...)` references of `value`, using the configured nesting delimiters
+pub fn nested_references(config: &Config, value: &str) -> Vec<NestedReference> {
+    let (prefix, suffix) = (&config.nesting_prefix, &config.nesting_suffix);
+    if prefix.is_empty() || suffix.is_empty() {
+        return Vec::new();
+    }
+    let mut references = Vec::new();
+    let mut cursor = 0usize;
+    while let Some(start) = value[cursor..].find(prefix.as_str()) {
+        let inner_start = cursor + start + prefix.len();
+        let Some(end) = value[inner_start..].find(suffix.as_str()) else {
+            break;
+        };
+        let inner = value[inner_start..inner_start + end].trim();
+        cursor = inner_start + end + suffix.len();
+
+        let (raw_key, options) =
+            split_top_level_once(inner, &config.nesting_options_separator).unwrap_or((inner, ""));
+        let Some(raw) = parse_nested_key_token(raw_key) else {
+            continue;
+        };
+        let (namespace, key) = match raw
+            .split_once(config.ns_separator.as_str())
+            .filter(|_| !config.ns_separator.is_empty())
+        {
+            Some((namespace, key)) => (Some(namespace.to_string()), key),
+            None => (None, raw),
+        };
+        references.push(NestedReference {
+            raw: raw.to_string(),
+            namespace: namespace.or_else(|| extract_comment_string_option(options, "ns")),
+            key: key.to_string(),
+            context: extract_comment_string_option(options, "context"),
+            plural: comment_option_exists(options, "count"),
+        });
+    }
+    references
+}
+
+/// Targets of `reference` made from a value of `namespace`: in the
+/// reference's own namespace, else the value's namespace, then the default one
+fn resolve_in(
+    config: &Config,
+    values: &BTreeMap<NestingNode, String>,
+    namespace: &str,
+    reference: &NestedReference,
+) -> Option<Vec<NestingNode>> {
+    let namespaces: Vec<&str> = match &reference.namespace {
+        Some(namespace) => vec![namespace.as_str()],
+        None => vec![namespace, config.effective_default_namespace()],
+    };
+    namespaces
+        .into_iter()
+        .map(|namespace| resolve(config, values, namespace, reference))
+        .find(|targets| !targets.is_empty())
+}
+
+/// Keys of `namespace` that `reference` renders: the context variant (or the
+/// plain key when the locale has none), and any plural form when counted
+fn resolve(
+    config: &Config,
+    values: &BTreeMap<NestingNode, String>,
+    namespace: &str,
+    reference: &NestedReference,
+) -> Vec<NestingNode> {
+    let mut bases = Vec::new();
+    if let Some(context) = &reference.context {
+        bases.push(format!(
+            "{}{}{}",
+            reference.key, config.context_separator, context
+        ));
+    }
+    bases.push(reference.key.clone());
+
+    for base in bases {
+        let mut targets = Vec::new();
+        let node = (namespace.to_string(), base.clone());
+        if values.contains_key(&node) {
+            targets.push(node);
+        }
+        if reference.plural {
+            let prefix = format!("{}{}", base, config.plural_separator);
+            let start = (namespace.to_string(), prefix.clone());
+            targets.extend(
+                values
+                    .range(start..)
+                    .map(|(node, _)| node)
+                    .take_while(|(ns, key)| ns == namespace && key.starts_with(&prefix))
+                    .cloned(),
+            );
+        }
+        if !targets.is_empty() {
+            return targets;
+        }
+    }
+    Vec::new()
+}
+
+fn display_node(config: &Config, (namespace, key): &NestingNode) -> String {
+    if config.ns_separator.is_empty() {
+        key.clone()
+    } else {
+        format!("{}{}{}", namespace, config.ns_separator, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(entries: &[(&str, &str)]) -> BTreeMap<NestingNode, String> {
+        entries
+            .iter()
+            .map(|(key, value)| {
+                let (namespace, key) = key.split_once(':').unwrap_or(("translation", key));
+                ((namespace.to_string(), key.to_string()), value.to_string())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn nested_references_read_namespace_context_and_count() {
+        let config = Config::default();
+        let references = nested_references(
+            &config,
+            "$t(common:ok) $t('item', { \"count\": {{count}} }) $t(friend, { \"context\": \"male\" })",
+        );
+        assert_eq!(references.len(), 3);
+        assert_eq!(references[0].namespace.as_deref(), Some("common"));
+        assert_eq!(references[0].key, "ok");
+        assert!(references[1].plural);
+        assert_eq!(references[1].key, "item");
+        assert_eq!(references[2].context.as_deref(), Some("male"));
+    }
+
+    #[test]
+    fn graph_reports_cycles_once_and_missing_references() {
+        let config = Config::default();
+        let values = values(&[
+            ("a", "A uses $t(b)"),
+            ("b", "B uses $t(c)"),
+            ("c", "C uses $t(a) and $t(gone)"),
+            ("self", "$t(self)"),
+            ("items_one", "one item"),
+            ("items_other", "{{count}} items"),
+            ("count", "$t(items, { \"count\": 2 }) and $t(common:ok)"),
+            ("common:ok", "OK"),
+        ]);
+        let graph = NestingGraph::build(&config, &values);
+
+        let node = |key: &str| ("translation".to_string(), key.to_string());
+        assert_eq!(
+            graph.cycles(),
+            vec![
+                vec![node("a"), node("b"), node("c"), node("a")],
+                vec![node("self"), node("self")],
+            ]
+        );
+        assert_eq!(graph.missing.len(), 1);
+        assert_eq!(graph.missing[&node("c")].len(), 1);
+        assert_eq!(graph.missing[&node("c")][0].raw, "gone");
+        assert_eq!(graph.edges[&node("count")].len(), 3);
+    }
+
+    #[test]
+    fn formatters_and_fallbacks() {
+        let config = Config {
+            locales: vec!["en".to_string(), "de".to_string(), "de-AT".to_string()],
+            ..Config::default()
+        };
+        assert_eq!(
+            interpolation_formatters(
+                &config,
+                "{{price, currency(USD)}} on {{date, datetime(dateStyle: short), uppercase}} {{name}}"
+            ),
+            vec!["currency", "datetime", "uppercase"]
+        );
+        assert_eq!(fallback_locales(&config, "de-AT"), vec!["de", "en"]);
+        assert_eq!(fallback_locales(&config, "de"), vec!["en"]);
+        assert!(fallback_locales(&config, "en").is_empty());
+    }
+}
@@ -0,0 +1,82 @@
+//! Consistent separators for file paths shown to users and tools, in the
+//! `pathStyle` of the config in use. The style travels with each call rather
+//! than living in the process, so calls with different configs never mix.
+
+use std::path::{Component, Path, MAIN_SEPARATOR};
+
+use crate::config::PathStyle;
+
+/// `path` as a string with the separators of `style`
+pub fn display(path: &Path, style: PathStyle) -> String {
+    normalize(&path.to_string_lossy(), style)
+}
+
+/// Rewrite the separators of a path string (which may mix `\` and `/` when
+/// configured paths are joined with paths from the file system)
+pub fn normalize(path: &str, style: PathStyle) -> String {
+    normalize_with(path, style, MAIN_SEPARATOR)
+}
+
+/// Whether `name` can be joined to a directory without leaving it: one plain
+/// file name, with no separator, `..`, drive or root. Namespaces and locale
+/// codes become file and directory names in the output, and may come from
+/// source code or runtime logs.
+pub fn is_plain_file_name(name: &str) -> bool {
+    if name.contains(['/', '\\', '\0']) {
+        return false;
+    }
+    let mut components = Path::new(name).components();
+    matches!(
+        (components.next(), components.next()),
+        (Some(Component::Normal(_)), None)
+    )
+}
+
+fn normalize_with(path: &str, style: PathStyle, separator: char) -> String {
+    // `\` is a valid file name character where it is not the separator
+    if separator == '/' {
+        return path.to_string();
+    }
+    match style {
+        PathStyle::Native => path.replace('/', &separator.to_string()),
+        PathStyle::Forward => path.replace(separator, "/"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mixed_separators_follow_the_style() {
+        let mixed = r"locales/en\common.json";
+        assert_eq!(
+            normalize_with(mixed, PathStyle::Forward, '\\'),
+            "locales/en/common.json"
+        );
+        assert_eq!(
+            normalize_with(mixed, PathStyle::Native, '\\'),
+            r"locales\en\common.json"
+        );
+        assert_eq!(normalize_with(mixed, PathStyle::Forward, '/'), mixed);
+    }
+
+    #[test]
+    fn plain_file_names_cannot_leave_their_directory() {
+        for name in ["common", "en-US", "zh_Hant", "v1.2", "..hidden"] {
+            assert!(is_plain_file_name(name), "{}", name);
+        }
+        for name in [
+            "",
+            ".",
+            "..",
+            "../common",
+            "a/b",
+            r"..\common",
+            "/etc",
+            "a\0b",
+        ] {
+            assert!(!is_plain_file_name(name), "{:?}", name);
+        }
+    }
+}
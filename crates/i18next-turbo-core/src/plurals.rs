@@ -0,0 +1,666 @@
+//! Legacy (i18next v3/v2 JSON format) plural suffixes and migration to CLDR suffixes,
+//! and validation of CLDR plural families against each locale's plural rules.
+
+use anyhow::Result;
+use icu_locid::Locale;
+use icu_plurals::PluralRules;
+use serde_json::{Map, Value};
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use crate::config::{
+    categories_for_locale, plural_category_to_str, Config, PluralCompat, SingleCategoryPlural,
+};
+use crate::extractor::{DefaultValue, ExtractedKey};
+use crate::json_sync;
+
+/// Plural rule sets of the pre-v4 i18next plural resolver: languages and one
+/// sample number per plural form, in form order.
+const LEGACY_PLURAL_SETS: &[(&[&str], &[u32])] = &[
+    (
+        &[
+            "ach", "ak", "am", "arn", "br", "fil", "gun", "ln", "mfe", "mg", "mi", "oc", "pt",
+            "pt-BR", "tg", "tl", "ti", "tr", "uz", "wa",
+        ],
+        &[1, 2],
+    ),
+    (
+        &[
+            "af", "an", "ast", "az", "bg", "bn", "ca", "da", "de", "dev", "el", "en", "eo", "es",
+            "et", "eu", "fi", "fo", "fur", "fy", "gl", "gu", "ha", "hi", "hu", "hy", "ia", "it",
+            "kk", "kn", "ku", "lb", "mai", "ml", "mn", "mr", "nah", "nap", "nb", "ne", "nl", "nn",
+            "no", "nso", "pa", "pap", "pms", "ps", "pt-PT", "rm", "sco", "se", "si", "so", "son",
+            "sq", "sv", "sw", "ta", "te", "tk", "ur", "yo",
+        ],
+        &[1, 2],
+    ),
+    (
+        &[
+            "ay", "bo", "cgg", "fa", "ht", "id", "ja", "jbo", "ka", "km", "ko", "ky", "lo", "ms",
+            "sah", "su", "th", "tt", "ug", "vi", "wo", "zh",
+        ],
+        &[1],
+    ),
+    (
+        &["be", "bs", "cnr", "dz", "hr", "ru", "sr", "uk"],
+        &[1, 2, 5],
+    ),
+    (&["ar"], &[0, 1, 2, 3, 11, 100]),
+    (&["cs", "sk"], &[1, 2, 5]),
+    (&["csb", "pl"], &[1, 2, 5]),
+    (&["cy"], &[1, 2, 3, 8]),
+    (&["fr"], &[1, 2]),
+    (&["ga"], &[1, 2, 3, 7, 11]),
+    (&["gd"], &[1, 2, 3, 20]),
+    (&["is"], &[1, 2]),
+    (&["jv"], &[0, 1]),
+    (&["kw"], &[1, 2, 3, 4]),
+    (&["lt"], &[1, 2, 10]),
+    (&["lv"], &[1, 2, 0]),
+    (&["mk"], &[1, 2]),
+    (&["mnk"], &[0, 1, 2]),
+    (&["mt"], &[1, 2, 11, 20]),
+    (&["or"], &[2, 1]),
+    (&["ro"], &[1, 2, 20]),
+    (&["sl"], &[5, 1, 2, 3]),
+    (&["he", "iw"], &[1, 2, 20, 21]),
+];
+
+/// Sample numbers for each legacy plural form of `locale` (falls back to the language subtag)
+pub fn legacy_plural_numbers(locale: &str) -> &'static [u32] {
+    let find = |code: &str| {
+        LEGACY_PLURAL_SETS
+            .iter()
+            .find(|(languages, _)| languages.iter().any(|l| l.eq_ignore_ascii_case(code)))
+            .map(|(_, numbers)| *numbers)
+    };
+    let language = locale.split(['-', '_']).next().unwrap_or(locale);
+    find(&locale.replace('_', "-"))
+        .or_else(|| find(language))
+        .unwrap_or(&[1, 2])
+}
+
+/// Key suffixes for each legacy plural form of `locale`; an empty suffix means the bare key.
+pub fn legacy_plural_suffixes(locale: &str, compat: PluralCompat) -> Vec<String> {
+    suffixes_for_numbers(legacy_plural_numbers(locale), compat)
+}
+
+/// Key suffixes when only the number of plural forms is known (no locale rules).
+pub fn legacy_plural_suffixes_for_count(count: usize) -> Vec<String> {
+    if count == 2 {
+        return vec![String::new(), "plural".to_string()];
+    }
+    (0..count).map(|index| index.to_string()).collect()
+}
+
+fn suffixes_for_numbers(numbers: &[u32], compat: PluralCompat) -> Vec<String> {
+    // Two-form sets starting at 1 use `key`/`key_plural` in both v2 and v3.
+    if numbers.len() == 2 && numbers[0] == 1 {
+        return vec![String::new(), "plural".to_string()];
+    }
+    match compat {
+        PluralCompat::V2 => numbers.iter().map(|n| n.to_string()).collect(),
+        _ => (0..numbers.len()).map(|index| index.to_string()).collect(),
+    }
+}
+
+/// How legacy forms of one locale map onto its CLDR categories
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegacyPluralMapping {
+    /// Legacy suffix per form, in form order
+    pub legacy_suffixes: Vec<String>,
+    /// (CLDR category, index of the legacy form providing its value), in CLDR order
+    pub categories: Vec<(String, usize)>,
+}
+
+impl LegacyPluralMapping {
+    /// Map each CLDR category of `locale` to the legacy form used for the same numbers.
+    /// Categories no legacy sample falls into (e.g. Russian "other" for fractions)
+    /// take the value of the last legacy form.
+    pub fn for_locale(locale: &str, compat: PluralCompat) -> Self {
+        let numbers = legacy_plural_numbers(locale);
+        let legacy_suffixes = suffixes_for_numbers(numbers, compat);
+        let cldr = categories_for_locale(locale)
+            .unwrap_or_else(|| vec!["one".to_string(), "other".to_string()]);
+        let rules = locale
+            .trim()
+            .parse::<Locale>()
+            .ok()
+            .and_then(|parsed| PluralRules::try_new_cardinal(&parsed.into()).ok());
+
+        let sample_categories: Vec<Option<&'static str>> = numbers
+            .iter()
+            .map(|n| {
+                rules
+                    .as_ref()
+                    .map(|rules| crate::config::plural_category_to_str(rules.category_for(*n)))
+            })
+            .collect();
+
+        let last = numbers.len().saturating_sub(1);
+        let categories = cldr
+            .into_iter()
+            .map(|category| {
+                let index = sample_categories
+                    .iter()
+                    .position(|c| *c == Some(category.as_str()))
+                    .unwrap_or(last);
+                (category, index)
+            })
+            .collect();
+
+        Self {
+            legacy_suffixes,
+            categories,
+        }
+    }
+}
+
+/// Rewrite legacy plural groups in `map` (recursively) to CLDR suffixes.
+/// Returns the number of plural groups converted. Existing CLDR keys are kept.
+pub fn migrate_legacy_plurals(
+    map: &mut Map<String, Value>,
+    mapping: &LegacyPluralMapping,
+    separator: &str,
+) -> usize {
+    let mut migrated = 0;
+    for value in map.values_mut() {
+        if let Value::Object(nested) = value {
+            migrated += migrate_legacy_plurals(nested, mapping, separator);
+        }
+    }
+
+    let legacy_key = |base: &str, suffix: &str| {
+        if suffix.is_empty() {
+            base.to_string()
+        } else {
+            format!("{}{}{}", base, separator, suffix)
+        }
+    };
+
+    // A group is identified by its first suffixed form (e.g. `key_plural` or `key_0`).
+    let Some(marker) = mapping
+        .legacy_suffixes
+        .iter()
+        .find(|suffix| !suffix.is_empty())
+    else {
+        return migrated;
+    };
+    let marker_suffix = format!("{}{}", separator, marker);
+    let mut bases: Vec<String> = map
+        .iter()
+        .filter(|(_, value)| value.is_string())
+        .filter_map(|(key, _)| key.strip_suffix(&marker_suffix).map(str::to_string))
+        .filter(|base| !base.is_empty())
+        .collect();
+    bases.sort();
+
+    for base in bases {
+        let forms: Vec<Option<Value>> = mapping
+            .legacy_suffixes
+            .iter()
+            .map(|suffix| {
+                map.get(&legacy_key(&base, suffix))
+                    .filter(|value| value.is_string())
+                    .cloned()
+            })
+            .collect();
+
+        // A lone `_0`-style key is only a plural group when the language has a single form.
+        let present = forms.iter().filter(|form| form.is_some()).count();
+        if mapping.legacy_suffixes.len() > 1 && present < 2 {
+            continue;
+        }
+
+        for (index, suffix) in mapping.legacy_suffixes.iter().enumerate() {
+            if forms[index].is_some() {
+                map.remove(&legacy_key(&base, suffix));
+            }
+        }
+        for (category, index) in &mapping.categories {
+            let Some(value) = forms[*index]
+                .clone()
+                .or_else(|| forms.iter().flatten().last().cloned())
+            else {
+                continue;
+            };
+            map.entry(format!("{}{}{}", base, separator, category))
+                .or_insert(value);
+        }
+        migrated += 1;
+    }
+
+    migrated
+}
+
+/// All CLDR plural categories, in suffix order
+const CLDR_CATEGORIES: &[&str] = &["zero", "one", "two", "few", "many", "other"];
+
+/// Plural family whose suffixes do not match the CLDR categories of its locale
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluralFormIssue {
+    /// Key path of the family without the plural suffix (e.g. `cart.item`)
+    pub key: String,
+    /// Categories the locale needs that the family lacks
+    pub missing: Vec<String>,
+    /// Suffixed keys of categories the locale does not have
+    pub unexpected: Vec<String>,
+}
+
+/// CLDR plural categories of `locale` for cardinal or ordinal (`_ordinal_`) families
+pub fn plural_categories(locale: &str, ordinal: bool) -> Option<Vec<String>> {
+    if !ordinal {
+        return categories_for_locale(locale);
+    }
+    let parsed: Locale = locale.trim().parse().ok()?;
+    let rules = PluralRules::try_new_ordinal(&parsed.into()).ok()?;
+    let supported: Vec<_> = rules.categories().collect();
+    Some(
+        CLDR_CATEGORIES
+            .iter()
+            .filter(|name| {
+                supported
+                    .iter()
+                    .any(|category| plural_category_to_str(*category) == **name)
+            })
+            .map(|name| name.to_string())
+            .collect(),
+    )
+}
+
+/// Check the plural families of `map` (recursively) against the plural rules of
+/// `locale`. A family is a set of sibling `base{separator}{category}` string keys
+/// that includes `other`. With `fix`, missing categories are added as copies of
+/// the `other` value. `zero` is never unexpected: i18next uses it for a count of
+/// 0 in every language. Empty forms are not unexpected either, since extraction
+/// writes the categories of every configured locale into each file.
+pub fn check_plural_forms(
+    map: &mut Map<String, Value>,
+    locale: &str,
+    separator: &str,
+    key_separator: &str,
+    fix: bool,
+) -> Vec<PluralFormIssue> {
+    let mut issues = Vec::new();
+    check_plural_forms_at(map, locale, separator, key_separator, "", fix, &mut issues);
+    issues
+}
+
+fn check_plural_forms_at(
+    map: &mut Map<String, Value>,
+    locale: &str,
+    separator: &str,
+    key_separator: &str,
+    prefix: &str,
+    fix: bool,
+    issues: &mut Vec<PluralFormIssue>,
+) {
+    let path = |key: &str| {
+        if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}{}{}", prefix, key_separator, key)
+        }
+    };
+    for (key, value) in map.iter_mut() {
+        if let Value::Object(nested) = value {
+            let nested_prefix = path(key);
+            check_plural_forms_at(
+                nested,
+                locale,
+                separator,
+                key_separator,
+                &nested_prefix,
+                fix,
+                issues,
+            );
+        }
+    }
+
+    let other_suffix = format!("{}other", separator);
+    let bases: Vec<String> = map
+        .iter()
+        .filter(|(_, value)| value.is_string())
+        .filter_map(|(key, _)| key.strip_suffix(&other_suffix).map(str::to_string))
+        .filter(|base| !base.is_empty())
+        .collect();
+
+    for base in bases {
+        let ordinal = base.ends_with(&format!("{}ordinal", separator));
+        let Some(expected) = plural_categories(locale, ordinal) else {
+            continue;
+        };
+        let form = |category: &str| format!("{}{}{}", base, separator, category);
+        let present: Vec<&str> = CLDR_CATEGORIES
+            .iter()
+            .copied()
+            .filter(|category| map.get(&form(category)).is_some_and(Value::is_string))
+            .collect();
+
+        let missing: Vec<String> = expected
+            .iter()
+            .filter(|category| !present.contains(&category.as_str()))
+            .cloned()
+            .collect();
+        let unexpected: Vec<String> = present
+            .iter()
+            .filter(|category| !expected.iter().any(|e| e == *category))
+            .filter(|category| ordinal || **category != "zero")
+            .map(|category| form(category))
+            .filter(|key| map.get(key).and_then(Value::as_str) != Some(""))
+            .collect();
+        if missing.is_empty() && unexpected.is_empty() {
+            continue;
+        }
+
+        if fix {
+            if let Some(other) = map.get(&form("other")).cloned() {
+                for category in &missing {
+                    map.insert(form(category), other.clone());
+                }
+            }
+        }
+        issues.push(PluralFormIssue {
+            key: path(&base),
+            missing,
+            unexpected,
+        });
+    }
+}
+
+/// Extracted keys to sync into `locale`, following its `singleCategoryPlurals`
+/// mode when the locale has a single plural category. `base` drops the `other`
+/// forms generated for other single-category locales; `copyPrimary` fills new
+/// plural keys with the primary locale's translation of the family.
+pub fn keys_for_locale<'a>(
+    config: &Config,
+    locale: &str,
+    keys: &'a [ExtractedKey],
+    output_dir: &str,
+) -> Result<Cow<'a, [ExtractedKey]>> {
+    let plural = config.plural_config();
+    if !plural.single_category_other
+        || categories_for_locale(locale).is_none_or(|categories| categories != ["other"])
+    {
+        return Ok(Cow::Borrowed(keys));
+    }
+
+    let other_suffix = format!("{}other", plural.separator);
+    let default_namespace = config.effective_default_namespace();
+    let id = |key: &'a ExtractedKey| {
+        (
+            key.namespace.as_deref().unwrap_or(default_namespace),
+            key.key.as_str(),
+        )
+    };
+    let extracted: HashSet<(&str, &str)> = keys.iter().map(id).collect();
+    // The family base of a plural key: `item` for `item_other`, and `item` itself
+    // when its `other` form was extracted
+    let family = |key: &'a ExtractedKey| -> Option<&'a str> {
+        let (namespace, name) = id(key);
+        match name.strip_suffix(&other_suffix) {
+            Some(base) if !base.is_empty() => Some(base),
+            _ => extracted
+                .contains(&(namespace, format!("{}{}", name, other_suffix).as_str()))
+                .then_some(name),
+        }
+    };
+
+    match config
+        .single_category_plurals
+        .get(locale)
+        .copied()
+        .unwrap_or_default()
+    {
+        SingleCategoryPlural::Other => Ok(Cow::Borrowed(keys)),
+        SingleCategoryPlural::Base => {
+            if plural.suffixes != ["other"] {
+                return Ok(Cow::Borrowed(keys));
+            }
+            // Only forms whose base key was extracted next to them were generated
+            Ok(Cow::Owned(
+                keys.iter()
+                    .filter(|key| {
+                        let (namespace, name) = id(key);
+                        !name.strip_suffix(&other_suffix).is_some_and(|base| {
+                            !base.is_empty() && extracted.contains(&(namespace, base))
+                        })
+                    })
+                    .cloned()
+                    .collect(),
+            ))
+        }
+        SingleCategoryPlural::CopyPrimary => {
+            let primary = config.primary_language();
+            if primary == locale {
+                return Ok(Cow::Borrowed(keys));
+            }
+            let namespaces = json_sync::load_locale_namespaces(config, output_dir, primary)?;
+            let values = json_sync::flatten_string_values(&namespaces, &config.key_separator);
+            let translation = |namespace: &str, key: &str| {
+                values
+                    .get(&(namespace.to_string(), key.to_string()))
+                    .filter(|value| !value.is_empty())
+            };
+            Ok(Cow::Owned(
+                keys.iter()
+                    .map(|key| {
+                        let Some(base) = family(key) else {
+                            return key.clone();
+                        };
+                        let (namespace, name) = id(key);
+                        let copied = translation(namespace, name)
+                            .or_else(|| {
+                                translation(namespace, &format!("{}{}", base, other_suffix))
+                            })
+                            .or_else(|| translation(namespace, base));
+                        ExtractedKey {
+                            default_value: copied
+                                .map(|value| DefaultValue::from(value.clone()))
+                                .or_else(|| key.default_value.clone()),
+                            ..key.clone()
+                        }
+                    })
+                    .collect(),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn legacy_suffixes_follow_language_sets() {
+        assert_eq!(
+            legacy_plural_suffixes("en-US", PluralCompat::V3),
+            vec!["", "plural"]
+        );
+        assert_eq!(
+            legacy_plural_suffixes("ru", PluralCompat::V3),
+            vec!["0", "1", "2"]
+        );
+        assert_eq!(
+            legacy_plural_suffixes("ru", PluralCompat::V2),
+            vec!["1", "2", "5"]
+        );
+        assert_eq!(legacy_plural_suffixes("ja", PluralCompat::V3), vec!["0"]);
+        assert_eq!(
+            legacy_plural_suffixes("pt_BR", PluralCompat::V3),
+            vec!["", "plural"]
+        );
+    }
+
+    #[test]
+    fn mapping_fills_uncovered_categories_from_last_form() {
+        let mapping = LegacyPluralMapping::for_locale("ru", PluralCompat::V3);
+        assert_eq!(
+            mapping.categories,
+            vec![
+                ("one".to_string(), 0),
+                ("few".to_string(), 1),
+                ("many".to_string(), 2),
+                ("other".to_string(), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn migrate_converts_plural_suffix_groups() {
+        let mapping = LegacyPluralMapping::for_locale("en", PluralCompat::V3);
+        let mut map = json!({
+            "item": "{{count}} item",
+            "item_plural": "{{count}} items",
+            "friend_male": "A boyfriend",
+            "friend_male_plural": "{{count}} boyfriends",
+            "title": "Title",
+            "nested": { "page": "page", "page_plural": "pages" }
+        })
+        .as_object()
+        .unwrap()
+        .clone();
+
+        assert_eq!(migrate_legacy_plurals(&mut map, &mapping, "_"), 3);
+        assert_eq!(
+            Value::Object(map),
+            json!({
+                "item_one": "{{count}} item",
+                "item_other": "{{count}} items",
+                "friend_male_one": "A boyfriend",
+                "friend_male_other": "{{count}} boyfriends",
+                "title": "Title",
+                "nested": { "page_one": "page", "page_other": "pages" }
+            })
+        );
+    }
+
+    #[test]
+    fn migrate_converts_numeric_groups_and_skips_lone_keys() {
+        let mapping = LegacyPluralMapping::for_locale("ru", PluralCompat::V3);
+        let mut map = json!({
+            "apple_0": "яблоко",
+            "apple_1": "яблока",
+            "apple_2": "яблок",
+            "step_0": "Шаг"
+        })
+        .as_object()
+        .unwrap()
+        .clone();
+
+        assert_eq!(migrate_legacy_plurals(&mut map, &mapping, "_"), 1);
+        assert_eq!(
+            Value::Object(map),
+            json!({
+                "apple_one": "яблоко",
+                "apple_few": "яблока",
+                "apple_many": "яблок",
+                "apple_other": "яблок",
+                "step_0": "Шаг"
+            })
+        );
+    }
+
+    #[test]
+    fn check_plural_forms_flags_extra_and_missing_categories() {
+        let mut ja = json!({
+            "item_one": "{{count}} 個",
+            "item_two": "{{count}} 個",
+            "item_few": "",
+            "item_other": "{{count}} 個",
+            "wizard": { "step_one": "最初", "step_two": "次" }
+        })
+        .as_object()
+        .unwrap()
+        .clone();
+        assert_eq!(
+            check_plural_forms(&mut ja, "ja", "_", ".", false),
+            vec![PluralFormIssue {
+                key: "item".to_string(),
+                missing: vec![],
+                unexpected: vec!["item_one".to_string(), "item_two".to_string()],
+            }]
+        );
+
+        let mut ru = json!({
+            "cart": {
+                "item_zero": "пусто",
+                "item_one": "{{count}} товар",
+                "item_other": "{{count}} товара"
+            },
+            "place_ordinal_other": "{{count}}-е место"
+        })
+        .as_object()
+        .unwrap()
+        .clone();
+        let issues = check_plural_forms(&mut ru, "ru", "_", ".", true);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].key, "cart.item");
+        assert_eq!(issues[0].missing, vec!["few", "many"]);
+        assert!(issues[0].unexpected.is_empty());
+        assert_eq!(ru["cart"]["item_few"], json!("{{count}} товара"));
+        assert_eq!(ru["cart"]["item_many"], json!("{{count}} товара"));
+        assert!(check_plural_forms(&mut ru, "ru", "_", ".", false).is_empty());
+    }
+
+    #[test]
+    fn single_category_locales_follow_their_mode() {
+        let tmp = tempfile::tempdir().unwrap();
+        let output = tmp.path().join("locales");
+        std::fs::create_dir_all(output.join("ja")).unwrap();
+        std::fs::write(
+            output.join("ja/translation.json"),
+            r#"{ "item": "{{count}} 個", "item_other": "" }"#,
+        )
+        .unwrap();
+        let mut config = Config {
+            locales: vec!["ja".to_string(), "zh".to_string(), "ko".to_string()],
+            ..Config::default()
+        };
+        config.single_category_plurals = [
+            ("zh".to_string(), SingleCategoryPlural::Other),
+            ("ko".to_string(), SingleCategoryPlural::CopyPrimary),
+        ]
+        .into();
+        assert!(config.plural_config().single_category_other);
+        let keys: Vec<ExtractedKey> = ["item", "item_other", "title"]
+            .into_iter()
+            .map(|key| ExtractedKey {
+                key: key.to_string(),
+                namespace: None,
+                default_value: None,
+            })
+            .collect();
+        let output = output.to_string_lossy();
+        let names = |locale: &str| -> Vec<(String, Option<String>)> {
+            keys_for_locale(&config, locale, &keys, &output)
+                .unwrap()
+                .iter()
+                .map(|key| {
+                    (
+                        key.key.clone(),
+                        key.default_value
+                            .as_ref()
+                            .and_then(|value| value.as_str())
+                            .map(str::to_string),
+                    )
+                })
+                .collect()
+        };
+
+        assert_eq!(
+            names("ja"),
+            vec![("item".to_string(), None), ("title".to_string(), None)]
+        );
+        assert_eq!(names("zh").len(), 3);
+        assert_eq!(
+            names("ko"),
+            vec![
+                ("item".to_string(), Some("{{count}} 個".to_string())),
+                ("item_other".to_string(), Some("{{count}} 個".to_string())),
+                ("title".to_string(), None),
+            ]
+        );
+    }
+}
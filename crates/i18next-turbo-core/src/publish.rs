@@ -0,0 +1,229 @@
+//! `publishDir`: a read-only mirror of the synced catalogs for runtime loading.
+//! Each locale becomes one minified bundle of all its namespaces, named after
+//! a hash of its content (`en.3f2a9c1b07de.json`), and `manifest.json` maps the
+//! locales to their bundles. Bundles never change under a name, so a CDN can
+//! cache them forever and only the manifest needs revalidation.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use crate::config::Config;
+use crate::fs::{FileSystem, RealFileSystem};
+use crate::json_sync;
+
+/// Bumped when the shape of the manifest changes
+pub const FORMAT_VERSION: u64 = 1;
+
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Hex digits of the content hash kept in bundle names
+const HASH_LENGTH: usize = 12;
+
+/// The merged namespaces of one locale, as served
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bundle {
+    pub locale: String,
+    pub hash: String,
+    pub content: Vec<u8>,
+}
+
+impl Bundle {
+    pub fn file_name(&self) -> String {
+        format!("{}.{}.json", self.locale, self.hash)
+    }
+}
+
+/// Files changed by `write`, by name
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PublishReport {
+    pub written: Vec<String>,
+    pub removed: Vec<String>,
+    pub manifest_written: bool,
+}
+
+/// One bundle per configured locale in the i18next resources shape
+/// (`{ "common": {...}, "translation": {...} }`), without marker keys
+pub fn bundles(config: &Config) -> Result<Vec<Bundle>> {
+    let mut bundles = Vec::new();
+    for locale in &config.locales {
+        let mut namespaces = json_sync::load_locale_namespaces(config, &config.output, locale)?;
+        namespaces
+            .values_mut()
+            .for_each(json_sync::strip_marker_keys);
+        let content = serde_json::to_vec(&namespaces)?;
+        let digest = format!("{:x}", Sha256::digest(&content));
+        bundles.push(Bundle {
+            locale: locale.clone(),
+            hash: digest[..HASH_LENGTH].to_string(),
+            content,
+        });
+    }
+    Ok(bundles)
+}
+
+/// The manifest mapping each locale to its bundle. It holds no timestamp, so
+/// it only changes with the bundles.
+pub fn manifest(config: &Config, bundles: &[Bundle]) -> Result<Value> {
+    let mut namespaces = BTreeSet::new();
+    let mut locales = Map::new();
+    for bundle in bundles {
+        let resources: BTreeMap<String, Value> = serde_json::from_slice(&bundle.content)?;
+        namespaces.extend(resources.into_keys());
+        locales.insert(
+            bundle.locale.clone(),
+            json!({
+                "file": bundle.file_name(),
+                "hash": bundle.hash,
+                "bytes": bundle.content.len(),
+            }),
+        );
+    }
+    Ok(json!({
+        "version": FORMAT_VERSION,
+        "defaultLocale": config.primary_language(),
+        "namespaces": namespaces,
+        "locales": locales,
+    }))
+}
+
+/// Publish the bundles and manifest to `dir`. Bundles are written before the
+/// manifest that points to them, and the bundles of the previous manifest are
+/// removed last, so a reader never sees a manifest with missing files. Other
+/// files in `dir` are left alone.
+pub fn write(config: &Config, dir: &Path) -> Result<PublishReport> {
+    let fs = RealFileSystem;
+    let bundles = bundles(config)?;
+    let mut manifest_content = serde_json::to_string_pretty(&manifest(config, &bundles)?)?;
+    manifest_content.push('\n');
+    fs.create_dir_all(dir)
+        .with_context(|| format!("Failed to create publish directory: {}", dir.display()))?;
+
+    let mut report = PublishReport::default();
+    for bundle in &bundles {
+        let path = dir.join(bundle.file_name());
+        if fs.read_to_string(&path).ok().as_deref().map(str::as_bytes) == Some(&bundle.content) {
+            continue;
+        }
+        fs.atomic_write(&path, &bundle.content)
+            .with_context(|| format!("Failed to write bundle: {}", path.display()))?;
+        report.written.push(bundle.file_name());
+    }
+
+    let manifest_path = dir.join(MANIFEST_FILE);
+    let previous = fs.read_to_string(&manifest_path).ok();
+    if previous.as_deref() != Some(manifest_content.as_str()) {
+        fs.atomic_write(&manifest_path, manifest_content.as_bytes())
+            .with_context(|| format!("Failed to write manifest: {}", manifest_path.display()))?;
+        report.manifest_written = true;
+    }
+
+    let current: BTreeSet<String> = bundles.iter().map(Bundle::file_name).collect();
+    for stale in previous
+        .as_deref()
+        .map(listed_files)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|file| !current.contains(file))
+    {
+        let path = dir.join(&stale);
+        if path.is_file() {
+            fs.remove_file(&path)
+                .with_context(|| format!("Failed to remove bundle: {}", path.display()))?;
+            report.removed.push(stale);
+        }
+    }
+    Ok(report)
+}
+
+/// Republish to the configured `publishDir`. Does nothing when it is not set.
+pub fn write_configured(config: &Config) -> Result<()> {
+    if let Some(dir) = config.publish_dir.as_deref() {
+        write(config, Path::new(dir))?;
+    }
+    Ok(())
+}
+
+/// Bundle names listed by a manifest. Only plain file names are trusted, so a
+/// hand-edited manifest cannot remove files outside the directory.
+fn listed_files(manifest: &str) -> Vec<String> {
+    let Ok(Value::Object(root)) = serde_json::from_str::<Value>(manifest) else {
+        return Vec::new();
+    };
+    let Some(Value::Object(locales)) = root.get("locales") else {
+        return Vec::new();
+    };
+    locales
+        .values()
+        .filter_map(|entry| entry.get("file")?.as_str())
+        .filter(|file| !file.is_empty() && !file.starts_with('.') && !file.contains(['/', '\\']))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_publishes_hashed_bundles_and_replaces_stale_ones() {
+        let tmp = tempfile::tempdir().unwrap();
+        let output = tmp.path().join("locales");
+        std::fs::create_dir_all(output.join("en")).unwrap();
+        std::fs::create_dir_all(output.join("ja")).unwrap();
+        std::fs::write(
+            output.join("en/translation.json"),
+            r#"{"title":"Title","__preserve__":["x.*"],"nav":{"// note":"x","home":"Home"}}"#,
+        )
+        .unwrap();
+        std::fs::write(output.join("en/common.json"), r#"{"save":"Save"}"#).unwrap();
+        std::fs::write(output.join("ja/translation.json"), r#"{"title":"題"}"#).unwrap();
+        let config = Config {
+            output: output.to_string_lossy().to_string(),
+            locales: vec!["en".to_string(), "ja".to_string()],
+            ..Config::default()
+        };
+        let dir = tmp.path().join("public/i18n");
+
+        let report = write(&config, &dir).unwrap();
+        assert_eq!(report.written.len(), 2);
+        assert!(report.manifest_written);
+        let manifest: Value =
+            serde_json::from_str(&std::fs::read_to_string(dir.join(MANIFEST_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(manifest["defaultLocale"], "en");
+        assert_eq!(manifest["namespaces"], json!(["common", "translation"]));
+        let en_file = manifest["locales"]["en"]["file"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert!(en_file.starts_with("en.") && en_file.ends_with(".json"));
+        let en: Value =
+            serde_json::from_slice(&std::fs::read(dir.join(&en_file)).unwrap()).unwrap();
+        assert_eq!(
+            en,
+            json!({ "common": { "save": "Save" }, "translation": { "title": "Title", "nav": { "home": "Home" } } })
+        );
+
+        assert_eq!(write(&config, &dir).unwrap(), PublishReport::default());
+
+        std::fs::write(
+            output.join("ja/translation.json"),
+            r#"{"title":"タイトル"}"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("robots.txt"), "").unwrap();
+        let old_ja = manifest["locales"]["ja"]["file"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        let report = write(&config, &dir).unwrap();
+        assert_eq!(report.written.len(), 1);
+        assert_eq!(report.removed, vec![old_ja.clone()]);
+        assert!(!dir.join(&old_ja).exists());
+        assert!(dir.join(&en_file).exists());
+        assert!(dir.join("robots.txt").exists());
+    }
+}
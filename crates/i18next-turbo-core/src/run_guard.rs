@@ -0,0 +1,114 @@
+//! In-process exclusion of runs that write the same locale directory. NAPI
+//! calls from a JS test runner can overlap on worker threads; the per-file
+//! locks keep single writes intact, but not a whole extract or purge.
+
+use anyhow::{bail, Result};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Condvar, Mutex};
+
+use crate::config::{ConcurrentRuns, PathStyle};
+
+static ACTIVE: Mutex<BTreeSet<PathBuf>> = Mutex::new(BTreeSet::new());
+static RELEASED: Condvar = Condvar::new();
+
+/// Held while a run writes its locale directories; released on drop
+#[derive(Debug)]
+pub struct RunGuard {
+    dirs: Vec<PathBuf>,
+}
+
+impl Drop for RunGuard {
+    fn drop(&mut self) {
+        let mut active = ACTIVE.lock().unwrap_or_else(|e| e.into_inner());
+        for dir in &self.dirs {
+            active.remove(dir);
+        }
+        RELEASED.notify_all();
+    }
+}
+
+/// Claim every directory of `dirs` at once, waiting for or rejecting runs that
+/// hold any of them
+pub fn acquire<I, P>(dirs: I, mode: ConcurrentRuns, path_style: PathStyle) -> Result<RunGuard>
+where
+    I: IntoIterator<Item = P>,
+    P: AsRef<Path>,
+{
+    let dirs: BTreeSet<PathBuf> = dirs.into_iter().map(|dir| absolute(dir.as_ref())).collect();
+    let mut active = ACTIVE.lock().unwrap_or_else(|e| e.into_inner());
+    while let Some(busy) = dirs.iter().find(|dir| active.contains(*dir)) {
+        if mode == ConcurrentRuns::Reject {
+            bail!(
+                "Another run is already writing to {}; wait for it to finish or set concurrentRuns to \"queue\"",
+                crate::paths::display(busy, path_style)
+            );
+        }
+        active = RELEASED.wait(active).unwrap_or_else(|e| e.into_inner());
+    }
+    active.extend(dirs.iter().cloned());
+    Ok(RunGuard {
+        dirs: dirs.into_iter().collect(),
+    })
+}
+
+/// `dir` relative to the current directory, without `.` components, so that
+/// `locales` and `./locales` are the same directory
+fn absolute(dir: &Path) -> PathBuf {
+    let dir = std::env::current_dir()
+        .map(|cwd| cwd.join(dir))
+        .unwrap_or_else(|_| dir.to_path_buf());
+    dir.components()
+        .filter(|component| !matches!(component, std::path::Component::CurDir))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    #[test]
+    fn overlapping_runs_queue_or_reject() {
+        let guard = acquire(
+            ["run-guard-test/locales"],
+            ConcurrentRuns::Queue,
+            PathStyle::Native,
+        )
+        .unwrap();
+        let err = acquire(
+            ["./run-guard-test/locales"],
+            ConcurrentRuns::Reject,
+            PathStyle::Native,
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Another run is already writing to"));
+        // Other directories are independent
+        drop(
+            acquire(
+                ["run-guard-test/other"],
+                ConcurrentRuns::Reject,
+                PathStyle::Native,
+            )
+            .unwrap(),
+        );
+
+        let (sender, receiver) = mpsc::channel();
+        let waiter = std::thread::spawn(move || {
+            let _guard = acquire(
+                ["run-guard-test/locales"],
+                ConcurrentRuns::Queue,
+                PathStyle::Native,
+            )
+            .unwrap();
+            sender.send(()).unwrap();
+        });
+        assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+        drop(guard);
+        receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        waiter.join().unwrap();
+    }
+}
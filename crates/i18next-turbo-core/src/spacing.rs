@@ -0,0 +1,235 @@
+//! Invisible differences between a translation and its primary-locale value:
+//! leading or trailing whitespace the source does not have (or lost), runs of
+//! spaces, and final punctuation the translation dropped. They misalign UI
+//! text and send unchanged strings back to translation review.
+
+use anyhow::Result;
+use std::fmt;
+
+use crate::config::Config;
+use crate::json_sync::{self, PreserveMatcher};
+
+/// Marks that end a sentence or label; a translation ending with any of them
+/// keeps the final punctuation of its source
+const FINAL_PUNCTUATION: &[char] = &[
+    '.', '!', '?', ':', ';', '…', '。', '！', '？', '：', '；', '؟', '।',
+];
+
+/// What differs in the spacing of one value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpacingProblem {
+    /// Leading whitespace differs from the primary value
+    LeadingWhitespace,
+    /// Trailing whitespace (including newlines) differs from the primary value
+    TrailingWhitespace,
+    /// Runs of spaces the primary value does not have
+    RepeatedSpaces,
+    /// The primary value ends with this mark, the translation with none
+    MissingPunctuation(char),
+}
+
+impl fmt::Display for SpacingProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpacingProblem::LeadingWhitespace => {
+                write!(f, "leading whitespace differs from the source")
+            }
+            SpacingProblem::TrailingWhitespace => {
+                write!(f, "trailing whitespace differs from the source")
+            }
+            SpacingProblem::RepeatedSpaces => write!(f, "contains repeated spaces"),
+            SpacingProblem::MissingPunctuation(mark) => {
+                write!(f, "drops the final '{}' of the source", mark)
+            }
+        }
+    }
+}
+
+/// A value whose spacing differs from the primary locale
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpacingIssue {
+    pub locale: String,
+    pub namespace: String,
+    pub key: String,
+    pub problems: Vec<SpacingProblem>,
+    /// The value with the problems fixed
+    pub normalized: String,
+}
+
+/// Compare every string value of `locales` (all configured locales when empty)
+/// with the primary value of the same key. The primary locale itself is the
+/// reference and never reported.
+pub fn check_spacing(
+    config: &Config,
+    locales: &[String],
+    scope: &PreserveMatcher,
+) -> Result<Vec<SpacingIssue>> {
+    let primary = config.primary_language();
+    let reference = json_sync::flatten_string_values(
+        &json_sync::load_locale_namespaces(config, &config.output, primary)?,
+        &config.key_separator,
+    );
+    let locales = if locales.is_empty() {
+        &config.locales[..]
+    } else {
+        locales
+    };
+
+    let mut issues = Vec::new();
+    for locale in locales.iter().filter(|locale| *locale != primary) {
+        let values = json_sync::flatten_string_values(
+            &json_sync::load_locale_namespaces(config, &config.output, locale)?,
+            &config.key_separator,
+        );
+        for ((namespace, key), value) in values {
+            if !scope.in_scope(&namespace, &key) {
+                continue;
+            }
+            let Some(source) = reference.get(&(namespace.clone(), key.clone())) else {
+                continue;
+            };
+            let problems = value_problems(&value, source);
+            if problems.is_empty() {
+                continue;
+            }
+            issues.push(SpacingIssue {
+                locale: locale.clone(),
+                normalized: normalize(&value, source),
+                namespace,
+                key,
+                problems,
+            });
+        }
+    }
+    Ok(issues)
+}
+
+/// Spacing problems of `value` compared with its `source` (primary-locale) value.
+/// Blank values on either side are left to completeness checks.
+pub fn value_problems(value: &str, source: &str) -> Vec<SpacingProblem> {
+    let (body, source_body) = (value.trim(), source.trim());
+    if body.is_empty() || source_body.is_empty() {
+        return Vec::new();
+    }
+
+    let mut problems = Vec::new();
+    if leading(value) != leading(source) {
+        problems.push(SpacingProblem::LeadingWhitespace);
+    }
+    if trailing(value) != trailing(source) {
+        problems.push(SpacingProblem::TrailingWhitespace);
+    }
+    if body.contains("  ") && !source_body.contains("  ") {
+        problems.push(SpacingProblem::RepeatedSpaces);
+    }
+    if let Some(mark) = source_body.chars().last() {
+        if FINAL_PUNCTUATION.contains(&mark) && !body.ends_with(FINAL_PUNCTUATION) {
+            problems.push(SpacingProblem::MissingPunctuation(mark));
+        }
+    }
+    problems
+}
+
+/// `value` with the edge whitespace of `source`, runs of spaces collapsed
+/// (unless the source has them) and the source's final punctuation restored.
+/// After CJK text the full-width form of the mark is used.
+pub fn normalize(value: &str, source: &str) -> String {
+    let problems = value_problems(value, source);
+    if problems.is_empty() {
+        return value.to_string();
+    }
+
+    let mut body = value.trim().to_string();
+    if problems.contains(&SpacingProblem::RepeatedSpaces) {
+        body = collapse_spaces(&body);
+    }
+    if let Some(mark) = problems.iter().find_map(|problem| match problem {
+        SpacingProblem::MissingPunctuation(mark) => Some(*mark),
+        _ => None,
+    }) {
+        let mark = match body.chars().last() {
+            Some(last) if is_cjk(last) => full_width(mark),
+            _ => mark,
+        };
+        body.push(mark);
+    }
+    format!("{}{}{}", leading(source), body, trailing(source))
+}
+
+fn leading(value: &str) -> &str {
+    &value[..value.len() - value.trim_start().len()]
+}
+
+fn trailing(value: &str) -> &str {
+    &value[value.trim_end().len()..]
+}
+
+fn collapse_spaces(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        if !(c == ' ' && out.ends_with(' ')) {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Han, kana and CJK punctuation, which take full-width marks
+fn is_cjk(c: char) -> bool {
+    matches!(c, '\u{3000}'..='\u{30ff}' | '\u{3400}'..='\u{9fff}' | '\u{ff00}'..='\u{ffef}')
+}
+
+fn full_width(mark: char) -> char {
+    match mark {
+        '.' => '。',
+        '!' => '！',
+        '?' => '？',
+        ':' => '：',
+        ';' => '；',
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn value_problems_report_invisible_differences() {
+        assert_eq!(
+            value_problems("Speichern ", "Save"),
+            vec![SpacingProblem::TrailingWhitespace]
+        );
+        assert_eq!(
+            value_problems("Name", "Name:\n"),
+            vec![
+                SpacingProblem::TrailingWhitespace,
+                SpacingProblem::MissingPunctuation(':')
+            ]
+        );
+        assert_eq!(
+            value_problems(" Bitte  warten", " Please wait"),
+            vec![SpacingProblem::RepeatedSpaces]
+        );
+        assert_eq!(
+            value_problems("Gespeichert", "Saved."),
+            vec![SpacingProblem::MissingPunctuation('.')]
+        );
+        // Other final marks and blank values are accepted
+        assert!(value_problems("¿Guardar?", "Save.").is_empty());
+        assert!(value_problems("保存しました。", "Saved.").is_empty());
+        assert!(value_problems("  ", "Save").is_empty());
+        assert!(value_problems("A  B", "A  B").is_empty());
+    }
+
+    #[test]
+    fn normalize_restores_source_spacing_and_punctuation() {
+        assert_eq!(
+            normalize("  Bitte  warten ", "Please wait."),
+            "Bitte warten."
+        );
+        assert_eq!(normalize("Name", "Name:\n"), "Name:\n");
+        assert_eq!(normalize("保存しました", " Saved!"), " 保存しました！");
+        assert_eq!(normalize("Speichern", "Save"), "Speichern");
+    }
+}
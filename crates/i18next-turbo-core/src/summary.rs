@@ -0,0 +1,190 @@
+//! End-of-run usage summary for build logs (`--summary`): files scanned, cache
+//! hits, keys added and removed and time per phase, added up over everything
+//! the process ran. Nothing is collected unless it is enabled and nothing
+//! leaves the process.
+
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::time::{Duration, Instant};
+
+/// How the summary block is printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SummaryFormat {
+    Text,
+    Json,
+}
+
+impl SummaryFormat {
+    pub fn parse_str(value: &str) -> Option<Self> {
+        match value {
+            "text" => Some(Self::Text),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Totals of the session so far
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunSummary {
+    /// Commands run, in order (a watch rebuild counts as `watch`)
+    pub commands: Vec<String>,
+    pub files_scanned: usize,
+    /// Files whose keys were reused from the watch cache instead of parsed
+    pub cache_hits: usize,
+    pub keys_added: usize,
+    pub keys_removed: usize,
+    pub phases: BTreeMap<String, Duration>,
+    pub total: Duration,
+}
+
+struct State {
+    format: SummaryFormat,
+    started: Instant,
+    summary: RunSummary,
+}
+
+fn cell() -> &'static Mutex<Option<State>> {
+    static CELL: OnceLock<Mutex<Option<State>>> = OnceLock::new();
+    CELL.get_or_init(|| Mutex::new(None))
+}
+
+fn state() -> MutexGuard<'static, Option<State>> {
+    cell()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Start collecting; the session starts now
+pub fn enable(format: SummaryFormat) {
+    *state() = Some(State {
+        format,
+        started: Instant::now(),
+        summary: RunSummary::default(),
+    });
+}
+
+fn record(update: impl FnOnce(&mut RunSummary)) {
+    if let Some(state) = state().as_mut() {
+        update(&mut state.summary);
+    }
+}
+
+pub fn record_command(name: &str) {
+    record(|summary| summary.commands.push(name.to_string()));
+}
+
+pub fn add_files_scanned(count: usize) {
+    record(|summary| summary.files_scanned += count);
+}
+
+pub fn add_cache_hits(count: usize) {
+    record(|summary| summary.cache_hits += count);
+}
+
+pub fn add_keys(added: usize, removed: usize) {
+    record(|summary| {
+        summary.keys_added += added;
+        summary.keys_removed += removed;
+    });
+}
+
+pub fn add_phase(phase: &str, duration: Duration) {
+    record(|summary| *summary.phases.entry(phase.to_string()).or_default() += duration);
+}
+
+/// Totals so far, or `None` when the summary is not enabled
+pub fn snapshot() -> Option<RunSummary> {
+    state().as_ref().map(|state| RunSummary {
+        total: state.started.elapsed(),
+        ..state.summary.clone()
+    })
+}
+
+/// Print the totals so far to stderr in the enabled format
+pub fn print() {
+    let Some(format) = state().as_ref().map(|state| state.format) else {
+        return;
+    };
+    let Some(summary) = snapshot() else {
+        return;
+    };
+    match format {
+        SummaryFormat::Text => eprint!("{}", summary.render_text()),
+        SummaryFormat::Json => eprintln!("{}", summary.to_json()),
+    }
+}
+
+impl RunSummary {
+    /// `{ "summary": { "commands": [...], "filesScanned": N, ..., "durationsMs": {...} } }`
+    pub fn to_json(&self) -> Value {
+        let ms = |duration: &Duration| duration.as_secs_f64() * 1000.0;
+        let mut durations: BTreeMap<&str, f64> = self
+            .phases
+            .iter()
+            .map(|(phase, duration)| (phase.as_str(), ms(duration)))
+            .collect();
+        durations.insert("total", ms(&self.total));
+        json!({
+            "summary": {
+                "commands": self.commands,
+                "filesScanned": self.files_scanned,
+                "cacheHits": self.cache_hits,
+                "keysAdded": self.keys_added,
+                "keysRemoved": self.keys_removed,
+                "durationsMs": durations,
+            }
+        })
+    }
+
+    pub fn render_text(&self) -> String {
+        let mut out = String::from("\n=== i18next-turbo summary ===\n");
+        out.push_str(&format!("  Commands: {}\n", self.commands.join(", ")));
+        out.push_str(&format!("  Files scanned: {}\n", self.files_scanned));
+        out.push_str(&format!("  Cache hits: {}\n", self.cache_hits));
+        out.push_str(&format!("  Keys added: {}\n", self.keys_added));
+        out.push_str(&format!("  Keys removed: {}\n", self.keys_removed));
+        for (phase, duration) in &self.phases {
+            out.push_str(&format!(
+                "  {} time: {:.2}ms\n",
+                phase,
+                duration.as_secs_f64() * 1000.0
+            ));
+        }
+        out.push_str(&format!(
+            "  Total time: {:.2}ms\n",
+            self.total.as_secs_f64() * 1000.0
+        ));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_renders_totals_as_text_and_json() {
+        let summary = RunSummary {
+            commands: vec!["extract".to_string(), "watch".to_string()],
+            files_scanned: 12,
+            cache_hits: 11,
+            keys_added: 3,
+            keys_removed: 1,
+            phases: BTreeMap::from([("extract".to_string(), Duration::from_millis(40))]),
+            total: Duration::from_millis(50),
+        };
+        let json = summary.to_json();
+        assert_eq!(json["summary"]["filesScanned"], 12);
+        assert_eq!(json["summary"]["cacheHits"], 11);
+        assert_eq!(json["summary"]["commands"][1], "watch");
+        assert_eq!(json["summary"]["durationsMs"]["extract"], 40.0);
+        assert_eq!(json["summary"]["durationsMs"]["total"], 50.0);
+
+        let text = summary.render_text();
+        assert!(text.contains("  Commands: extract, watch\n"));
+        assert!(text.contains("  Keys removed: 1\n"));
+        assert!(text.contains("  extract time: 40.00ms\n"));
+    }
+}
@@ -0,0 +1,208 @@
+//! Catalogs of a translation management system (TMS) against local locale
+//! files: `pull` fills local keys with remote translations, `push` sends keys
+//! the TMS does not know yet. Local files own the keys and their structure;
+//! the TMS only supplies values.
+
+use serde_json::{Map, Value};
+
+use crate::json_sync;
+
+/// Outcome of merging one pulled catalog into one locale file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PullStats {
+    /// Local values replaced by a remote translation
+    pub updated: usize,
+    /// Local values the remote translation already matched, or kept by
+    /// `only_missing`
+    pub unchanged: usize,
+    /// Local keys without a remote translation
+    pub untranslated: usize,
+}
+
+/// Merge the translations of `remote` into the local keys of `local`. Remote
+/// catalogs may be nested or flat (keys joined with `key_separator`); keys only
+/// the TMS has are ignored. With `only_missing`, translated local values are
+/// kept.
+pub fn merge_pulled(
+    local: &mut Map<String, Value>,
+    remote: &Value,
+    key_separator: &str,
+    only_missing: bool,
+) -> PullStats {
+    let mut stats = PullStats::default();
+    merge_into(
+        local,
+        &mut Vec::new(),
+        remote,
+        key_separator,
+        only_missing,
+        &mut stats,
+    );
+    stats
+}
+
+fn merge_into(
+    local: &mut Map<String, Value>,
+    path: &mut Vec<String>,
+    remote: &Value,
+    key_separator: &str,
+    only_missing: bool,
+    stats: &mut PullStats,
+) {
+    for (key, value) in local.iter_mut() {
+        if json_sync::is_marker_key(key) {
+            continue;
+        }
+        path.push(key.clone());
+        match value {
+            Value::Object(nested) => {
+                merge_into(nested, path, remote, key_separator, only_missing, stats)
+            }
+            value => match remote_value(remote, path, key_separator) {
+                Some(translation) if !is_untranslated(translation) && !translation.is_object() => {
+                    if *value == *translation || (only_missing && !is_untranslated(value)) {
+                        stats.unchanged += 1;
+                    } else {
+                        *value = translation.clone();
+                        stats.updated += 1;
+                    }
+                }
+                _ => stats.untranslated += 1,
+            },
+        }
+        path.pop();
+    }
+}
+
+/// Leaf keys of `local` (joined with `key_separator`) that `remote` lacks, with
+/// their local values, in key order
+pub fn new_keys(
+    local: &Map<String, Value>,
+    remote: &Value,
+    key_separator: &str,
+) -> Vec<(String, Value)> {
+    fn collect(
+        local: &Map<String, Value>,
+        path: &mut Vec<String>,
+        remote: &Value,
+        key_separator: &str,
+        out: &mut Vec<(String, Value)>,
+    ) {
+        for (key, value) in local {
+            if json_sync::is_marker_key(key) {
+                continue;
+            }
+            path.push(key.clone());
+            match value {
+                Value::Object(nested) => collect(nested, path, remote, key_separator, out),
+                value if remote_value(remote, path, key_separator).is_none() => {
+                    out.push((path.join(key_separator), value.clone()));
+                }
+                _ => {}
+            }
+            path.pop();
+        }
+    }
+
+    let mut out = Vec::new();
+    collect(local, &mut Vec::new(), remote, key_separator, &mut out);
+    out
+}
+
+/// Value at `path` in a nested, flat or partly flat catalog
+fn remote_value<'a>(remote: &'a Value, path: &[String], key_separator: &str) -> Option<&'a Value> {
+    if path.is_empty() {
+        return Some(remote);
+    }
+    let map = remote.as_object()?;
+    let joins = if key_separator.is_empty() {
+        1
+    } else {
+        path.len()
+    };
+    (1..=joins).find_map(|split| {
+        let value = map.get(&path[..split].join(key_separator))?;
+        remote_value(value, &path[split..], key_separator)
+    })
+}
+
+fn is_untranslated(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(text) => text.is_empty(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn map(value: Value) -> Map<String, Value> {
+        match value {
+            Value::Object(map) => map,
+            _ => panic!("expected an object"),
+        }
+    }
+
+    #[test]
+    fn pull_fills_local_keys_from_nested_and_flat_catalogs() {
+        let mut local = map(json!({
+            "home": { "title": "", "subtitle": "Old" },
+            "button": { "save": "" },
+            "local_only": ""
+        }));
+        let remote = json!({
+            "home": { "title": "Startseite", "subtitle": "Willkommen" },
+            "button.save": "Speichern",
+            "remote_only": "Nur entfernt"
+        });
+
+        let stats = merge_pulled(&mut local, &remote, ".", false);
+        assert_eq!(
+            Value::Object(local),
+            json!({
+                "home": { "title": "Startseite", "subtitle": "Willkommen" },
+                "button": { "save": "Speichern" },
+                "local_only": ""
+            })
+        );
+        assert_eq!(
+            stats,
+            PullStats {
+                updated: 3,
+                unchanged: 0,
+                untranslated: 1
+            }
+        );
+    }
+
+    #[test]
+    fn pull_only_missing_keeps_translated_values() {
+        let mut local = map(json!({ "title": "Local", "body": "" }));
+        let remote = json!({ "title": "Remote", "body": "Text", "empty": "" });
+
+        let stats = merge_pulled(&mut local, &remote, ".", true);
+        assert_eq!(
+            Value::Object(local),
+            json!({ "title": "Local", "body": "Text" })
+        );
+        assert_eq!((stats.updated, stats.unchanged), (1, 1));
+    }
+
+    #[test]
+    fn push_sends_keys_the_remote_lacks() {
+        let local = map(json!({
+            "home": { "title": "Home", "cta": "Start" },
+            "footer": "Footer"
+        }));
+        let remote = json!({ "home.title": "Home", "footer": "" });
+
+        assert_eq!(
+            new_keys(&local, &remote, "."),
+            vec![("home.cta".to_string(), json!("Start"))]
+        );
+        assert_eq!(new_keys(&local, &json!({}), ".").len(), 3);
+    }
+}
@@ -0,0 +1,1234 @@
+#![allow(clippy::too_many_arguments)]
+
+use crate::config::{Config, EnableSelector, OutputTarget, PluralConfig};
+use crate::extractor::ExtractedKey;
+use crate::json_sync::{self, split_merged_namespaces};
+use crate::locale_store::LocaleStore;
+use anyhow::{bail, Context, Result};
+use glob::{glob, Pattern};
+use serde_json::{Map, Value};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Header of every generated file; also marks the files of an `outputDir`
+/// that may be removed once their namespace is gone
+const GENERATED_HEADER: &str = "// This file is auto-generated by i18next-turbo";
+
+/// Where the namespaces of a locale live (`mergeNamespaces`,
+/// `flatLocaleFiles`, `defaultNamespace`, `shardMaxKeys`), and the per-package
+/// roots (`outputs`) read along with the locales directory
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocaleLayout<'a> {
+    pub merge_namespaces: bool,
+    pub merged_namespace_prefix: Option<&'a str>,
+    pub flat_locale_files: bool,
+    pub default_namespace: Option<&'a str>,
+    /// Namespace files may be split into shards (`shardMaxKeys`)
+    pub sharded: bool,
+    pub output_roots: &'a [OutputTarget],
+}
+
+impl<'a> LocaleLayout<'a> {
+    pub fn from_config(config: &'a Config) -> Self {
+        Self {
+            merge_namespaces: config.merge_namespaces && !config.namespace_less_mode(),
+            merged_namespace_prefix: config.merged_namespace_prefix(),
+            flat_locale_files: config.flat_locale_files,
+            default_namespace: Some(config.effective_default_namespace()),
+            sharded: config.sharded_locale_files(),
+            output_roots: &config.outputs,
+        }
+    }
+}
+
+/// Namespaces to declare, and the directory for one declaration file per
+/// namespace (`types.includeNamespaces`, `excludeNamespaces`, `outputDir`)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TypegenNamespaces<'a> {
+    pub include: Option<&'a [String]>,
+    pub exclude: Option<&'a [String]>,
+    pub output_dir: Option<&'a Path>,
+}
+
+impl TypegenNamespaces<'_> {
+    /// Drop the namespaces of `resources` that are not included or are excluded
+    fn filter(&self, resources: &mut Map<String, Value>) -> Result<()> {
+        let patterns = |names: Option<&[String]>| -> Result<Option<Vec<Pattern>>> {
+            names
+                .map(|names| {
+                    names
+                        .iter()
+                        .map(|name| {
+                            Pattern::new(name).with_context(|| {
+                                format!("Invalid typegen namespace pattern: {}", name)
+                            })
+                        })
+                        .collect()
+                })
+                .transpose()
+        };
+        let include = patterns(self.include)?;
+        let exclude = patterns(self.exclude)?.unwrap_or_default();
+        resources.retain(|namespace, _| {
+            include
+                .as_ref()
+                .is_none_or(|include| include.iter().any(|p| p.matches(namespace)))
+                && !exclude.iter().any(|p| p.matches(namespace))
+        });
+        Ok(())
+    }
+}
+
+/// Keys read with `returnObjects` (extracted as `key.*`) and the key separator
+/// splitting them into object paths
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ObjectRoots<'a> {
+    /// `(namespace, key)` pairs, see [`object_root_keys`]
+    pub keys: &'a [(String, String)],
+    pub key_separator: &'a str,
+}
+
+impl ObjectRoots<'_> {
+    /// Type each root as the subtree under it in the primary locale. Flat keys
+    /// (`"countries.jp"`) are gathered into an object that replaces a string
+    /// value of the root. Returns the selector keys of the roots typed as
+    /// objects, which are not leaves and so not listed otherwise.
+    fn apply(&self, resources: &mut Map<String, Value>) -> Vec<String> {
+        let mut selector_keys = Vec::new();
+        for (namespace, key) in self.keys {
+            let Some(Value::Object(obj)) = resources.get_mut(namespace) else {
+                continue;
+            };
+            let path: Vec<&str> = if self.key_separator.is_empty() {
+                vec![key.as_str()]
+            } else {
+                key.split(self.key_separator).collect()
+            };
+            let Some((last, parents)) = path.split_last() else {
+                continue;
+            };
+            let Some(parent) = object_at_mut(obj, parents) else {
+                continue;
+            };
+            if !parent
+                .get(*last)
+                .is_some_and(|value| value.is_object() || value.is_array())
+            {
+                let prefix = format!("{}.", last);
+                let subtree: Map<String, Value> = parent
+                    .iter()
+                    .filter_map(|(key, value)| {
+                        key.strip_prefix(&prefix)
+                            .map(|rest| (rest.to_string(), value.clone()))
+                    })
+                    .collect();
+                if subtree.is_empty() {
+                    continue;
+                }
+                parent.insert(last.to_string(), Value::Object(subtree));
+            }
+            if parent.get(*last).is_some_and(Value::is_object) {
+                selector_keys.push(format!("{}.{}", namespace, path.join(".")));
+            }
+        }
+        selector_keys
+    }
+}
+
+/// The `returnObjects` roots among extracted keys, as `(namespace, key)`
+pub fn object_root_keys<'a>(
+    files: impl IntoIterator<Item = &'a Vec<ExtractedKey>>,
+    default_namespace: &str,
+) -> Vec<(String, String)> {
+    let mut roots: Vec<(String, String)> = files
+        .into_iter()
+        .flatten()
+        .filter_map(|key| {
+            let root = key.key.strip_suffix(".*")?;
+            let namespace = key.namespace.as_deref().unwrap_or(default_namespace);
+            Some((namespace.to_string(), root.to_string()))
+        })
+        .collect();
+    roots.sort();
+    roots.dedup();
+    roots
+}
+
+fn object_at_mut<'a>(
+    obj: &'a mut Map<String, Value>,
+    path: &[&str],
+) -> Option<&'a mut Map<String, Value>> {
+    match path.split_first() {
+        None => Some(obj),
+        Some((first, rest)) => match obj.get_mut(*first) {
+            Some(Value::Object(next)) => object_at_mut(next, rest),
+            _ => None,
+        },
+    }
+}
+
+/// Generate TypeScript type definitions from translation JSON files
+pub fn generate_types(locales_dir: &Path, output_path: &Path, default_locale: &str) -> Result<()> {
+    generate_types_with_options(
+        locales_dir,
+        output_path,
+        default_locale,
+        None,
+        None,
+        None,
+        None,
+        LocaleLayout::default(),
+        None,
+        TypegenNamespaces::default(),
+        ObjectRoots::default(),
+    )
+}
+
+/// Generate TypeScript type definitions with custom indentation
+pub fn generate_types_with_options(
+    locales_dir: &Path,
+    output_path: &Path,
+    default_locale: &str,
+    indentation: Option<&str>,
+    input_patterns: Option<&[String]>,
+    resources_file: Option<&Path>,
+    enable_selector: Option<&EnableSelector>,
+    layout: LocaleLayout,
+    plural_config: Option<&PluralConfig>,
+    namespaces: TypegenNamespaces,
+    object_roots: ObjectRoots,
+) -> Result<()> {
+    let mut resources = load_resources(locales_dir, default_locale, input_patterns, layout)?;
+    if resources.is_empty() {
+        bail!(
+            "No locale files for \"{}\" found in {}",
+            default_locale,
+            locales_dir.display()
+        );
+    }
+    namespaces.filter(&mut resources)?;
+
+    if resources.is_empty() {
+        return Ok(());
+    }
+    let root_selector_keys = object_roots.apply(&mut resources);
+
+    // i18next's own types only resolve the default plural layout; declare the
+    // base keys explicitly for anything else so `t('item', { count })` type-checks.
+    if let Some(plural_config) = plural_config.filter(|p| !p.uses_default_layout()) {
+        for value in resources.values_mut() {
+            if let Value::Object(obj) = value {
+                add_plural_base_keys(obj, plural_config);
+            }
+        }
+    }
+
+    match namespaces.output_dir {
+        Some(output_dir) => write_types_dir(
+            output_dir,
+            &resources,
+            indentation.unwrap_or("  "),
+            enable_selector,
+            &root_selector_keys,
+        )?,
+        None => write_types_file(
+            output_path,
+            &resources,
+            indentation.unwrap_or("  "),
+            true,
+            enable_selector,
+            &root_selector_keys,
+        )?,
+    }
+    if let Some(resources_path) = resources_file {
+        write_types_file(
+            resources_path,
+            &resources,
+            indentation.unwrap_or("  "),
+            false,
+            enable_selector,
+            &root_selector_keys,
+        )?;
+    }
+
+    Ok(())
+}
+
+fn load_resources(
+    locales_dir: &Path,
+    default_locale: &str,
+    input_patterns: Option<&[String]>,
+    layout: LocaleLayout,
+) -> Result<Map<String, Value>> {
+    let mut resources: Map<String, Value> = Map::new();
+    let default_namespace = layout.default_namespace.unwrap_or("translation");
+    let mut roots: Vec<&Path> = vec![locales_dir];
+    for target in layout.output_roots {
+        let root = Path::new(&target.output);
+        if !roots.contains(&root) {
+            roots.push(root);
+        }
+    }
+    let mut files: Vec<PathBuf> = Vec::new();
+    for root in roots {
+        let root_files: Vec<PathBuf> = if layout.flat_locale_files {
+            json_sync::flat_locale_file(root, default_locale, "json")
+                .into_iter()
+                .collect()
+        } else {
+            let locale_dir = root.join(default_locale);
+            if !locale_dir.exists() {
+                continue;
+            }
+            resolve_typegen_files(&locale_dir, input_patterns)?
+        };
+        for path in root_files {
+            if !files.contains(&path) {
+                files.push(path);
+            }
+        }
+    }
+
+    let store = LocaleStore::new();
+    store.preload(&files);
+    // Namespaces spread over shards or locale roots are merged into one
+    let mut insert = |namespace: String, value: Value| match (resources.get_mut(&namespace), value)
+    {
+        (Some(Value::Object(existing)), Value::Object(shard)) => {
+            json_sync::merge_shard(existing, shard)
+        }
+        (_, value) => {
+            resources.insert(namespace, value);
+        }
+    };
+    for path in files {
+        let namespace = if layout.flat_locale_files {
+            default_namespace
+        } else {
+            json_sync::file_namespace(&path, layout.sharded).unwrap_or("translation")
+        };
+        let Some(json) = store.get(&path)? else {
+            bail!("Failed to parse: {}: the file is empty", path.display());
+        };
+        let mut json = Value::clone(&json);
+        // `__preserve__` and `"//"` entries are not translations
+        if let Value::Object(obj) = &mut json {
+            json_sync::strip_marker_keys(obj);
+        }
+        match json {
+            Value::Object(obj) if layout.merge_namespaces => {
+                for (ns, value) in split_merged_namespaces(obj, layout.merged_namespace_prefix) {
+                    insert(ns, value);
+                }
+            }
+            json => insert(namespace.to_string(), json),
+        }
+    }
+
+    Ok(resources)
+}
+
+fn resolve_typegen_files(
+    locale_dir: &Path,
+    input_patterns: Option<&[String]>,
+) -> Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = Vec::new();
+
+    if let Some(patterns) = input_patterns {
+        for pattern in patterns {
+            let candidate = Path::new(pattern);
+            let glob_pattern = if candidate.is_absolute() || pattern.contains('/') {
+                pattern.to_string()
+            } else {
+                locale_dir.join(pattern).to_string_lossy().to_string()
+            };
+
+            let matches = glob(&glob_pattern)
+                .with_context(|| format!("Invalid typegen input pattern: {}", pattern))?;
+            for path in matches.flatten() {
+                if path.is_file() && path.extension().map(|e| e == "json").unwrap_or(false) {
+                    files.push(path);
+                }
+            }
+        }
+    } else {
+        for entry in std::fs::read_dir(locale_dir)
+            .with_context(|| format!("Failed to read locale directory: {}", locale_dir.display()))?
+        {
+            let entry = entry?;
+            let path = entry.path();
+            if path.is_file() && path.extension().map(|e| e == "json").unwrap_or(false) {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// One `<namespace>.d.ts` per namespace plus an `index.d.ts` declaring
+/// `Resources` from them. Generated files of namespaces no longer declared
+/// are removed; other files in the directory are left alone.
+fn write_types_dir(
+    output_dir: &Path,
+    resources: &Map<String, Value>,
+    indentation: &str,
+    enable_selector: Option<&EnableSelector>,
+    root_selector_keys: &[String],
+) -> Result<()> {
+    std::fs::create_dir_all(output_dir)
+        .with_context(|| format!("Failed to create directory: {}", output_dir.display()))?;
+
+    let mut written = vec![output_dir.join("index.d.ts")];
+    for (namespace, value) in resources {
+        let path = output_dir.join(format!("{}.d.ts", namespace));
+        write_atomically(&path, |writer| {
+            write_header(writer)?;
+            write_namespace_interface(writer, namespace, value, indentation)?;
+            writeln!(writer, "export default {};", to_pascal_case(namespace))?;
+            Ok(())
+        })?;
+        written.push(path);
+    }
+    write_atomically(&output_dir.join("index.d.ts"), |writer| {
+        write_header(writer)?;
+        for namespace in resources.keys() {
+            writeln!(
+                writer,
+                "import type {} from \"./{}\";",
+                to_pascal_case(namespace),
+                namespace
+            )?;
+        }
+        writeln!(writer)?;
+        write_resources_interface(
+            writer,
+            resources,
+            indentation,
+            true,
+            enable_selector,
+            root_selector_keys,
+        )
+    })?;
+
+    remove_stale_files(output_dir, &written, |name| name.ends_with(".d.ts"))
+}
+
+/// Remove the generated files in `dir` matching `is_output` that this run did
+/// not write
+fn remove_stale_files(
+    dir: &Path,
+    written: &[PathBuf],
+    is_output: impl Fn(&str) -> bool,
+) -> Result<()> {
+    for entry in std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read directory: {}", dir.display()))?
+    {
+        let path = entry?.path();
+        let stale = is_output(&path.to_string_lossy())
+            && !written.contains(&path)
+            && std::fs::read_to_string(&path)
+                .is_ok_and(|content| content.starts_with(GENERATED_HEADER));
+        if stale {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove: {}", path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Stream a file into a temp file next to `path`, then move it into place
+fn write_atomically(
+    path: &Path,
+    write: impl FnOnce(&mut BufWriter<File>) -> Result<()>,
+) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    let temp_path = PathBuf::from(temp_path);
+    {
+        let file = File::create(&temp_path)
+            .with_context(|| format!("Failed to create temp file: {}", temp_path.display()))?;
+        let mut writer = BufWriter::new(file);
+        write(&mut writer)?;
+        writer.flush()?;
+    }
+    std::fs::rename(&temp_path, path)
+        .with_context(|| format!("Failed to rename temp file to: {}", path.display()))?;
+    Ok(())
+}
+
+/// How the key strings of [`generate_key_constants`] are spelled
+#[derive(Debug, Clone, Copy)]
+pub struct KeyFormat<'a> {
+    pub ns_separator: &'a str,
+    pub key_separator: &'a str,
+    pub plural_config: Option<&'a PluralConfig>,
+}
+
+/// Write runtime key constants into `output_dir`: one `<namespace>.ts` per
+/// namespace exporting a deeply frozen object of full key strings
+/// (`Auth.login.title === "auth:login.title"`), and an `index.ts` combining
+/// them as `Keys`. Importing a namespace file directly keeps the others out of
+/// the bundle. Plural forms collapse into their base key.
+pub fn generate_key_constants(
+    locales_dir: &Path,
+    output_dir: &Path,
+    default_locale: &str,
+    indentation: Option<&str>,
+    input_patterns: Option<&[String]>,
+    layout: LocaleLayout,
+    namespaces: TypegenNamespaces,
+    format: KeyFormat,
+) -> Result<()> {
+    let mut resources = load_resources(locales_dir, default_locale, input_patterns, layout)?;
+    namespaces.filter(&mut resources)?;
+    let indentation = indentation.unwrap_or("  ");
+    std::fs::create_dir_all(output_dir)
+        .with_context(|| format!("Failed to create directory: {}", output_dir.display()))?;
+
+    let mut written = vec![output_dir.join("index.ts")];
+    for (namespace, value) in &resources {
+        let path = output_dir.join(format!("{}.ts", namespace));
+        let prefix = if format.ns_separator.is_empty() {
+            String::new()
+        } else {
+            format!("{}{}", namespace, format.ns_separator)
+        };
+        let name = to_pascal_case(namespace);
+        write_atomically(&path, |writer| {
+            write_header(writer)?;
+            writeln!(writer, "export const {} = Object.freeze({{", name)?;
+            write_constants_body(writer, value, &prefix, 1, indentation, &format)?;
+            writeln!(writer, "}} as const);\n")?;
+            writeln!(writer, "export default {};", name)?;
+            Ok(())
+        })?;
+        written.push(path);
+    }
+    write_atomically(&output_dir.join("index.ts"), |writer| {
+        write_header(writer)?;
+        for namespace in resources.keys() {
+            writeln!(
+                writer,
+                "import {} from \"./{}\";",
+                to_pascal_case(namespace),
+                namespace
+            )?;
+        }
+        if !resources.is_empty() {
+            writeln!(writer)?;
+            let names: Vec<String> = resources.keys().map(|ns| to_pascal_case(ns)).collect();
+            writeln!(writer, "export {{ {} }};\n", names.join(", "))?;
+        }
+        writeln!(writer, "export const Keys = Object.freeze({{")?;
+        for namespace in resources.keys() {
+            writeln!(
+                writer,
+                "{}{}: {},",
+                indentation,
+                constant_property(namespace),
+                to_pascal_case(namespace)
+            )?;
+        }
+        writeln!(writer, "}} as const);\n")?;
+        writeln!(writer, "export default Keys;")?;
+        Ok(())
+    })?;
+
+    remove_stale_files(output_dir, &written, |name| {
+        name.ends_with(".ts") && !name.ends_with(".d.ts")
+    })
+}
+
+/// Properties of one level of a key constants object; `prefix` is the key
+/// string up to this level
+fn write_constants_body<W: Write>(
+    writer: &mut W,
+    value: &Value,
+    prefix: &str,
+    depth: usize,
+    indentation: &str,
+    format: &KeyFormat,
+) -> Result<()> {
+    let Value::Object(obj) = value else {
+        return Ok(());
+    };
+    let indent = indentation.repeat(depth);
+    let mut seen = std::collections::HashSet::new();
+    for (key, val) in obj {
+        if val.is_object() {
+            if !seen.insert(key.clone()) {
+                continue;
+            }
+            writeln!(
+                writer,
+                "{}{}: Object.freeze({{",
+                indent,
+                constant_property(key)
+            )?;
+            let nested = format!("{}{}{}", prefix, key, format.key_separator);
+            write_constants_body(writer, val, &nested, depth + 1, indentation, format)?;
+            writeln!(writer, "{}}} as const),", indent)?;
+            continue;
+        }
+        let name = format
+            .plural_config
+            .and_then(|plural| plural.strip_plural_suffix(key))
+            .filter(|base| !obj.get(base).is_some_and(Value::is_object))
+            .unwrap_or_else(|| key.clone());
+        if !seen.insert(name.clone()) {
+            continue;
+        }
+        writeln!(
+            writer,
+            "{}{}: {},",
+            indent,
+            constant_property(&name),
+            Value::String(format!("{}{}", prefix, name))
+        )?;
+    }
+    Ok(())
+}
+
+/// An object property name, quoted unless it is a plain identifier
+fn constant_property(key: &str) -> String {
+    let mut chars = key.chars();
+    let identifier = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if identifier {
+        key.to_string()
+    } else {
+        Value::String(key.to_string()).to_string()
+    }
+}
+
+fn write_types_file(
+    output_path: &Path,
+    resources: &Map<String, Value>,
+    indentation: &str,
+    include_default_export: bool,
+    enable_selector: Option<&EnableSelector>,
+    root_selector_keys: &[String],
+) -> Result<()> {
+    write_atomically(output_path, |writer| {
+        write_ts_content(
+            writer,
+            resources,
+            indentation,
+            include_default_export,
+            enable_selector,
+            root_selector_keys,
+        )
+    })
+}
+
+/// Add a string entry for the base of every plural variant group that lacks one
+fn add_plural_base_keys(obj: &mut Map<String, Value>, plural_config: &PluralConfig) {
+    let mut bases = Vec::new();
+    for (key, value) in obj.iter_mut() {
+        match value {
+            Value::Object(nested) => add_plural_base_keys(nested, plural_config),
+            Value::String(_) => {
+                if let Some(base) = plural_config.strip_plural_suffix(key) {
+                    bases.push(base);
+                }
+            }
+            _ => {}
+        }
+    }
+    for base in bases {
+        obj.entry(base)
+            .or_insert_with(|| Value::String(String::new()));
+    }
+}
+
+/// Stream TypeScript content directly to a writer (memory-efficient)
+fn write_ts_content<W: Write>(
+    writer: &mut W,
+    resources: &Map<String, Value>,
+    indentation: &str,
+    include_default_export: bool,
+    enable_selector: Option<&EnableSelector>,
+    root_selector_keys: &[String],
+) -> Result<()> {
+    write_header(writer)?;
+
+    // Generate interface for each namespace
+    for (namespace, value) in resources {
+        write_namespace_interface(writer, namespace, value, indentation)?;
+    }
+
+    write_resources_interface(
+        writer,
+        resources,
+        indentation,
+        include_default_export,
+        enable_selector,
+        root_selector_keys,
+    )
+}
+
+fn write_header<W: Write>(writer: &mut W) -> Result<()> {
+    writeln!(writer, "{}", GENERATED_HEADER)?;
+    writeln!(writer, "// Do not edit manually\n")?;
+    Ok(())
+}
+
+fn write_namespace_interface<W: Write>(
+    writer: &mut W,
+    namespace: &str,
+    value: &Value,
+    indentation: &str,
+) -> Result<()> {
+    writeln!(writer, "interface {} {{", to_pascal_case(namespace))?;
+    write_interface_body(writer, value, 1, indentation)?;
+    writeln!(writer, "}}\n")?;
+    Ok(())
+}
+
+/// The `Resources` interface over the namespace interfaces, its exports and
+/// the selector helpers; `SelectorKey` lists the leaves and the
+/// `returnObjects` roots
+fn write_resources_interface<W: Write>(
+    writer: &mut W,
+    resources: &Map<String, Value>,
+    indentation: &str,
+    include_default_export: bool,
+    enable_selector: Option<&EnableSelector>,
+    root_selector_keys: &[String],
+) -> Result<()> {
+    // Generate the Resources interface
+    writeln!(writer, "interface Resources {{")?;
+    for namespace in resources.keys() {
+        let interface_name = to_pascal_case(namespace);
+        writeln!(
+            writer,
+            "{}\"{}\": {};",
+            indentation, namespace, interface_name
+        )?;
+    }
+    writeln!(writer, "}}\n")?;
+
+    // Export declaration
+    writeln!(writer, "export {{ Resources }};")?;
+    if include_default_export {
+        writeln!(writer, "export default Resources;")?;
+    }
+
+    if let Some(selector) = enable_selector {
+        if selector.enabled() {
+            writeln!(writer)?;
+            writeln!(writer, "export type SelectorRoot = Resources;")?;
+            writeln!(
+                writer,
+                "export type SelectorFn<T = unknown> = ($: SelectorRoot) => T;"
+            )?;
+            writeln!(
+                writer,
+                "export declare function keyFromSelector<T>(selector: SelectorFn<T>): string;"
+            )?;
+
+            if selector.optimize() {
+                let mut keys = Vec::new();
+                for (namespace, value) in resources {
+                    collect_selector_keys(namespace, value, "", &mut keys);
+                }
+                keys.extend_from_slice(root_selector_keys);
+                keys.sort();
+                keys.dedup();
+
+                if keys.is_empty() {
+                    writeln!(writer, "export type SelectorKey = never;")?;
+                } else {
+                    writeln!(writer, "export type SelectorKey =")?;
+                    for key in keys {
+                        writeln!(writer, "{}| \"{}\"", indentation, key)?;
+                    }
+                    writeln!(writer, ";")?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn collect_selector_keys(namespace: &str, value: &Value, prefix: &str, out: &mut Vec<String>) {
+    if let Value::Object(obj) = value {
+        for (k, v) in obj {
+            let next = if prefix.is_empty() {
+                k.clone()
+            } else {
+                format!("{}.{}", prefix, k)
+            };
+            if v.is_object() {
+                collect_selector_keys(namespace, v, &next, out);
+            } else {
+                out.push(format!("{}.{}", namespace, next));
+            }
+        }
+    }
+}
+
+/// Stream interface body recursively to a writer
+fn write_interface_body<W: Write>(
+    writer: &mut W,
+    value: &Value,
+    depth: usize,
+    indentation: &str,
+) -> Result<()> {
+    let indent = indentation.repeat(depth);
+
+    if let Value::Object(obj) = value {
+        for (key, val) in obj {
+            let key_safe = property_name(key);
+
+            match val {
+                Value::Object(_) => {
+                    writeln!(writer, "{}{}: {{", indent, key_safe)?;
+                    write_interface_body(writer, val, depth + 1, indentation)?;
+                    writeln!(writer, "{}}};", indent)?;
+                }
+                _ => {
+                    writeln!(writer, "{}{}: {};", indent, key_safe, leaf_type(val))?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn property_name(key: &str) -> String {
+    if key.contains('.') || key.contains('-') {
+        format!("\"{}\"", key)
+    } else {
+        key.to_string()
+    }
+}
+
+/// Inline type of a non-object value; arrays become tuples (`[string, string]`)
+fn leaf_type(value: &Value) -> String {
+    match value {
+        Value::String(_) => "string".to_string(),
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(leaf_type).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Value::Object(obj) => {
+            let fields: Vec<String> = obj
+                .iter()
+                .map(|(key, val)| format!("{}: {};", property_name(key), leaf_type(val)))
+                .collect();
+            format!("{{ {} }}", fields.join(" "))
+        }
+        _ => "unknown".to_string(),
+    }
+}
+
+/// Generate TypeScript interface content from resources (for testing)
+#[cfg(test)]
+fn generate_ts_content(resources: &Map<String, Value>) -> String {
+    let mut output = Vec::new();
+    write_ts_content(&mut output, resources, "  ", true, None, &[])
+        .expect("Failed to write to buffer");
+    String::from_utf8(output).expect("Invalid UTF-8")
+}
+
+/// Convert namespace to PascalCase for interface name
+fn to_pascal_case(s: &str) -> String {
+    s.split(['-', '_', '.'])
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                None => String::new(),
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_to_pascal_case() {
+        assert_eq!(to_pascal_case("translation"), "Translation");
+        assert_eq!(to_pascal_case("common-errors"), "CommonErrors");
+        assert_eq!(to_pascal_case("my_namespace"), "MyNamespace");
+    }
+
+    #[test]
+    fn test_generate_ts_content() {
+        let mut resources = Map::new();
+
+        let mut translation = Map::new();
+        translation.insert("hello".to_string(), Value::String("Hello".to_string()));
+
+        let mut button = Map::new();
+        button.insert("submit".to_string(), Value::String("Submit".to_string()));
+        button.insert("cancel".to_string(), Value::String("Cancel".to_string()));
+        translation.insert("button".to_string(), Value::Object(button));
+
+        resources.insert("translation".to_string(), Value::Object(translation));
+
+        let ts = generate_ts_content(&resources);
+
+        assert!(ts.contains("interface Translation {"));
+        assert!(ts.contains("hello: string;"));
+        assert!(ts.contains("button: {"));
+        assert!(ts.contains("submit: string;"));
+        assert!(ts.contains("interface Resources {"));
+        assert!(ts.contains("\"translation\": Translation;"));
+    }
+
+    #[test]
+    fn test_generate_ts_content_with_array_values() {
+        let resources = serde_json::json!({
+            "translation": {
+                "steps": ["First", "Second"],
+                "faq": [{ "q": "Why?", "a": "Because" }],
+                "empty": []
+            }
+        });
+
+        let ts = generate_ts_content(resources.as_object().unwrap());
+
+        assert!(ts.contains("steps: [string, string];"));
+        assert!(ts.contains("faq: [{ a: string; q: string; }];"));
+        assert!(ts.contains("empty: [];"));
+    }
+
+    #[test]
+    fn test_generate_ts_content_with_custom_indent() {
+        let mut resources = Map::new();
+        resources.insert(
+            "translation".to_string(),
+            Value::Object(
+                [("hello".to_string(), Value::String("Hello".to_string()))]
+                    .into_iter()
+                    .collect(),
+            ),
+        );
+
+        let mut output = Vec::new();
+        write_ts_content(&mut output, &resources, "\t", true, None, &[]).unwrap();
+        let ts = String::from_utf8(output).unwrap();
+        assert!(ts.contains("\t\"translation\": Translation;"));
+        assert!(ts.contains("\thello: string;"));
+    }
+
+    #[test]
+    fn test_generate_types_with_input_patterns_and_resources_file() {
+        let tmp = tempdir().unwrap();
+        let locales_dir = tmp.path().join("locales");
+        let en_dir = locales_dir.join("en");
+        fs::create_dir_all(&en_dir).unwrap();
+        fs::write(
+            en_dir.join("common.json"),
+            r#"{ "hello": "Hello", "nested": { "x": "y" } }"#,
+        )
+        .unwrap();
+        fs::write(en_dir.join("ignore.json"), r#"{ "skip": "me" }"#).unwrap();
+
+        let output = tmp.path().join("types").join("i18next.d.ts");
+        let resources_file = tmp.path().join("types").join("resources.d.ts");
+        let patterns = vec!["common.json".to_string()];
+
+        generate_types_with_options(
+            &locales_dir,
+            &output,
+            "en",
+            Some("  "),
+            Some(&patterns),
+            Some(resources_file.as_path()),
+            None,
+            LocaleLayout::default(),
+            None,
+            TypegenNamespaces::default(),
+            ObjectRoots::default(),
+        )
+        .unwrap();
+
+        let main_content = fs::read_to_string(output).unwrap();
+        let resources_content = fs::read_to_string(resources_file).unwrap();
+        assert!(main_content.contains("interface Common"));
+        assert!(!main_content.contains("interface Ignore"));
+        assert!(main_content.contains("export default Resources;"));
+        assert!(resources_content.contains("export { Resources };"));
+        assert!(!resources_content.contains("export default Resources;"));
+    }
+
+    #[test]
+    fn test_marker_entries_are_not_typed() {
+        let tmp = tempdir().unwrap();
+        let locales_dir = tmp.path().join("locales");
+        let en_dir = locales_dir.join("en");
+        fs::create_dir_all(&en_dir).unwrap();
+        fs::write(
+            en_dir.join("translation.json"),
+            r#"{ "//": "Dynamic keys", "dyn": { "__preserve__": ["*"], "x": "X" } }"#,
+        )
+        .unwrap();
+
+        let output = tmp.path().join("i18next.d.ts");
+        generate_types(&locales_dir, &output, "en").unwrap();
+
+        let content = fs::read_to_string(output).unwrap();
+        assert!(content.contains("x: string;"), "{}", content);
+        assert!(!content.contains("\"//\""), "{}", content);
+        assert!(!content.contains("__preserve__"), "{}", content);
+    }
+
+    #[test]
+    fn test_return_objects_roots_are_typed_as_objects() {
+        let tmp = tempdir().unwrap();
+        let locales_dir = tmp.path().join("locales");
+        fs::create_dir_all(locales_dir.join("en")).unwrap();
+        fs::write(
+            locales_dir.join("en/translation.json"),
+            r#"{ "pages": { "home": { "title": "Home" } }, "steps": ["One", "Two"], "title": "Title" }"#,
+        )
+        .unwrap();
+        fs::write(
+            locales_dir.join("en/flat.json"),
+            r#"{ "countries": "", "countries.jp": "Japan", "countries.us": "USA" }"#,
+        )
+        .unwrap();
+        let extracted = vec![
+            ExtractedKey {
+                key: "pages.home.*".to_string(),
+                namespace: None,
+                default_value: None,
+            },
+            ExtractedKey {
+                key: "countries.*".to_string(),
+                namespace: Some("flat".to_string()),
+                default_value: None,
+            },
+            ExtractedKey {
+                key: "missing.*".to_string(),
+                namespace: None,
+                default_value: None,
+            },
+            ExtractedKey {
+                key: "title".to_string(),
+                namespace: None,
+                default_value: None,
+            },
+        ];
+        let keys = object_root_keys([&extracted], "translation");
+        assert_eq!(
+            keys,
+            vec![
+                ("flat".to_string(), "countries".to_string()),
+                ("translation".to_string(), "missing".to_string()),
+                ("translation".to_string(), "pages.home".to_string()),
+            ]
+        );
+
+        let output = tmp.path().join("i18next.d.ts");
+        generate_types_with_options(
+            &locales_dir,
+            &output,
+            "en",
+            None,
+            None,
+            None,
+            Some(&EnableSelector::Mode("optimize".to_string())),
+            LocaleLayout::default(),
+            None,
+            TypegenNamespaces::default(),
+            ObjectRoots {
+                keys: &keys,
+                key_separator: ".",
+            },
+        )
+        .unwrap();
+        let ts = fs::read_to_string(output).unwrap();
+        // Flat keys under a root are gathered into an object in place of its string value
+        assert!(ts.contains("  countries: {\n    jp: string;\n    us: string;\n  };"));
+        assert!(!ts.contains("countries: string;"));
+        assert!(ts.contains("  | \"flat.countries\""));
+        assert!(ts.contains("  | \"translation.pages.home\""));
+        assert!(ts.contains("  | \"translation.pages.home.title\""));
+        assert!(!ts.contains("missing"));
+    }
+
+    #[test]
+    fn test_generate_ts_content_with_enable_selector_optimize() {
+        let mut resources = Map::new();
+        resources.insert(
+            "common".to_string(),
+            Value::Object(
+                [("hello".to_string(), Value::String("Hello".to_string()))]
+                    .into_iter()
+                    .collect(),
+            ),
+        );
+
+        let mut output = Vec::new();
+        write_ts_content(
+            &mut output,
+            &resources,
+            "  ",
+            true,
+            Some(&EnableSelector::Mode("optimize".to_string())),
+            &[],
+        )
+        .unwrap();
+        let ts = String::from_utf8(output).unwrap();
+        assert!(ts.contains("export type SelectorRoot = Resources;"));
+        assert!(ts.contains("export type SelectorKey ="));
+        assert!(ts.contains("\"common.hello\""));
+    }
+
+    #[test]
+    fn test_plural_base_keys_are_declared_for_custom_layout() {
+        let tmp = tempdir().unwrap();
+        let locales_dir = tmp.path().join("locales");
+        let en_dir = locales_dir.join("en");
+        fs::create_dir_all(&en_dir).unwrap();
+        fs::write(
+            en_dir.join("common.json"),
+            r#"{ "item_one_male": "a", "item_other_male": "b", "page_one": "p", "title": "T" }"#,
+        )
+        .unwrap();
+
+        let plural_config = PluralConfig {
+            order: crate::config::ContextPluralOrder::PluralContext,
+            ..PluralConfig::default()
+        };
+        let output = tmp.path().join("i18next.d.ts");
+        generate_types_with_options(
+            &locales_dir,
+            &output,
+            "en",
+            None,
+            None,
+            None,
+            None,
+            LocaleLayout::default(),
+            Some(&plural_config),
+            TypegenNamespaces::default(),
+            ObjectRoots::default(),
+        )
+        .unwrap();
+
+        let content = fs::read_to_string(output).unwrap();
+        assert!(content.contains("item_male: string;"));
+        assert!(content.contains("page: string;"));
+        assert!(content.contains("item_one_male: string;"));
+        assert!(!content.contains("title_"));
+    }
+
+    #[test]
+    fn namespaces_are_filtered_and_split_into_one_file_each() {
+        let tmp = tempdir().unwrap();
+        let locales_dir = tmp.path().join("locales");
+        let en_dir = locales_dir.join("en");
+        fs::create_dir_all(&en_dir).unwrap();
+        fs::write(en_dir.join("common.json"), r#"{ "save": "Save" }"#).unwrap();
+        fs::write(en_dir.join("admin-users.json"), r#"{ "title": "Users" }"#).unwrap();
+        fs::write(en_dir.join("admin-audit.json"), r#"{ "title": "Audit" }"#).unwrap();
+        fs::write(en_dir.join("legacy.json"), r#"{ "old": "Old" }"#).unwrap();
+
+        let types_dir = tmp.path().join("types");
+        fs::create_dir_all(&types_dir).unwrap();
+        fs::write(
+            types_dir.join("removed.d.ts"),
+            format!("{}\n", GENERATED_HEADER),
+        )
+        .unwrap();
+        fs::write(types_dir.join("custom.d.ts"), "declare const x: string;\n").unwrap();
+
+        let include = vec!["common".to_string(), "admin-*".to_string()];
+        let exclude = vec!["admin-audit".to_string()];
+        generate_types_with_options(
+            &locales_dir,
+            &tmp.path().join("unused.d.ts"),
+            "en",
+            None,
+            None,
+            None,
+            None,
+            LocaleLayout::default(),
+            None,
+            TypegenNamespaces {
+                include: Some(&include),
+                exclude: Some(&exclude),
+                output_dir: Some(&types_dir),
+            },
+            ObjectRoots::default(),
+        )
+        .unwrap();
+
+        let common = fs::read_to_string(types_dir.join("common.d.ts")).unwrap();
+        assert!(common.contains("interface Common {\n  save: string;\n}"));
+        assert!(common.contains("export default Common;"));
+        let index = fs::read_to_string(types_dir.join("index.d.ts")).unwrap();
+        assert!(index.contains("import type AdminUsers from \"./admin-users\";"));
+        assert!(index.contains("  \"common\": Common;"));
+        assert!(index.contains("export default Resources;"));
+        assert!(!index.contains("Legacy") && !index.contains("AdminAudit"));
+        assert!(!types_dir.join("admin-audit.d.ts").exists());
+        assert!(!types_dir.join("removed.d.ts").exists());
+        assert!(types_dir.join("custom.d.ts").exists());
+        assert!(!tmp.path().join("unused.d.ts").exists());
+    }
+
+    #[test]
+    fn key_constants_are_frozen_objects_per_namespace() {
+        let tmp = tempdir().unwrap();
+        let locales_dir = tmp.path().join("locales");
+        let en_dir = locales_dir.join("en");
+        fs::create_dir_all(&en_dir).unwrap();
+        fs::write(
+            en_dir.join("auth.json"),
+            r#"{ "login": { "title": "Log in", "item_one": "1 item", "item_other": "{{count}} items" }, "sign-up": "Sign up" }"#,
+        )
+        .unwrap();
+        fs::write(en_dir.join("common.json"), r#"{ "save": "Save" }"#).unwrap();
+
+        let constants_dir = tmp.path().join("keys");
+        let plural_config = PluralConfig::default();
+        generate_key_constants(
+            &locales_dir,
+            &constants_dir,
+            "en",
+            None,
+            None,
+            LocaleLayout::default(),
+            TypegenNamespaces::default(),
+            KeyFormat {
+                ns_separator: ":",
+                key_separator: ".",
+                plural_config: Some(&plural_config),
+            },
+        )
+        .unwrap();
+
+        let auth = fs::read_to_string(constants_dir.join("auth.ts")).unwrap();
+        assert!(auth.contains("export const Auth = Object.freeze({"));
+        assert!(auth.contains("  login: Object.freeze({\n    item: \"auth:login.item\",\n    title: \"auth:login.title\",\n  } as const),"));
+        assert!(!auth.contains("item_one"));
+        assert!(auth.contains("  \"sign-up\": \"auth:sign-up\","));
+        assert!(auth.contains("export default Auth;"));
+        let index = fs::read_to_string(constants_dir.join("index.ts")).unwrap();
+        assert!(index.contains("import Common from \"./common\";"));
+        assert!(index.contains("export { Auth, Common };"));
+        assert!(index.contains("  common: Common,"));
+    }
+}
@@ -0,0 +1,511 @@
+//! Vue single-file component parsing: top-level blocks, template expressions
+//! and `<i18n>` custom blocks.
+
+use anyhow::{bail, Context, Result};
+use serde_json::{Map, Value};
+
+/// A top-level block of a single-file component (`<template>`, `<script>`, `<i18n>`, ...)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SfcBlock {
+    pub tag: String,
+    pub attrs: Vec<(String, Option<String>)>,
+    pub content: String,
+}
+
+impl SfcBlock {
+    /// Value of an attribute; `Some("")` for attributes without a value
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(attr, _)| attr.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_deref().unwrap_or(""))
+    }
+}
+
+/// Expression found in a template that may contain translation calls
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateExpression {
+    /// `{{ ... }}` interpolation or a directive / bound attribute value
+    Code(String),
+    /// Value of a `v-t` directive (`'key'` or `{ path: 'key', ... }`)
+    TranslateDirective(String),
+}
+
+/// Split a component into its top-level blocks. Nested `<template>` elements stay
+/// inside their parent block and the content of other blocks is taken verbatim.
+pub fn parse_sfc(source: &str) -> Vec<SfcBlock> {
+    let mut blocks = Vec::new();
+    let mut pos = 0;
+
+    while let Some(offset) = source[pos..].find('<') {
+        let start = pos + offset;
+        let rest = &source[start..];
+        if rest.starts_with("<!--") {
+            pos = skip_comment(source, start);
+            continue;
+        }
+        let Some(tag) = parse_open_tag(source, start) else {
+            pos = skip_to_tag_end(source, start);
+            continue;
+        };
+        if tag.self_closing {
+            blocks.push(SfcBlock {
+                tag: tag.name,
+                attrs: tag.attrs,
+                content: String::new(),
+            });
+            pos = tag.end;
+            continue;
+        }
+
+        let close = if tag.name.eq_ignore_ascii_case("template") {
+            find_template_close(source, tag.end)
+        } else {
+            find_raw_close(source, tag.end, &tag.name)
+        };
+        let (content_end, next) = close.unwrap_or((source.len(), source.len()));
+        blocks.push(SfcBlock {
+            tag: tag.name,
+            attrs: tag.attrs,
+            content: source[tag.end..content_end].to_string(),
+        });
+        pos = next;
+    }
+
+    blocks
+}
+
+/// Collect interpolations, bound attributes, directive values and `v-t` values
+/// from template markup. Static attribute values and plain text are skipped.
+pub fn template_expressions(template: &str) -> Vec<TemplateExpression> {
+    let mut expressions = Vec::new();
+    let mut pos = 0;
+
+    while pos < template.len() {
+        let rest = &template[pos..];
+        let next_tag = rest.find('<');
+        let next_mustache = rest.find("{{");
+
+        match (next_tag, next_mustache) {
+            (_, Some(m)) if next_tag.is_none_or(|t| m < t) => {
+                let start = pos + m + 2;
+                let Some(len) = template[start..].find("}}") else {
+                    break;
+                };
+                let expr = template[start..start + len].trim();
+                if !expr.is_empty() {
+                    expressions.push(TemplateExpression::Code(expr.to_string()));
+                }
+                pos = start + len + 2;
+            }
+            (Some(t), _) => {
+                let start = pos + t;
+                if template[start..].starts_with("<!--") {
+                    pos = skip_comment(template, start);
+                    continue;
+                }
+                let Some(tag) = parse_open_tag(template, start) else {
+                    pos = skip_to_tag_end(template, start);
+                    continue;
+                };
+                for (name, value) in &tag.attrs {
+                    let Some(value) = value.as_deref().map(str::trim) else {
+                        continue;
+                    };
+                    if value.is_empty() {
+                        continue;
+                    }
+                    if name == "v-t" {
+                        expressions.push(TemplateExpression::TranslateDirective(value.to_string()));
+                    } else if is_dynamic_attribute(name) {
+                        expressions.push(TemplateExpression::Code(value.to_string()));
+                    }
+                }
+                pos = tag.end;
+                // Raw text elements never contain template syntax
+                let raw = ["script", "style"]
+                    .iter()
+                    .any(|raw| tag.name.eq_ignore_ascii_case(raw));
+                if raw && !tag.self_closing {
+                    pos = find_raw_close(template, pos, &tag.name)
+                        .map(|(_, next)| next)
+                        .unwrap_or(template.len());
+                }
+            }
+            _ => break,
+        }
+    }
+
+    expressions
+}
+
+/// Translation call equivalent to a `v-t` directive value, e.g. `$t('key')`
+pub fn translate_directive_call(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.starts_with(['\'', '"', '`']) {
+        return Some(format!("$t({})", value));
+    }
+    if value.starts_with('{') {
+        let path = object_property_literal(value, "path")?;
+        return Some(format!("$t({})", path));
+    }
+    None
+}
+
+/// Messages of an `<i18n>` block as (locale, dotted key, message) triples.
+/// Blocks with a `locale` attribute hold messages for that locale only;
+/// otherwise the top-level keys are locales.
+pub fn parse_i18n_block(block: &SfcBlock) -> Result<Vec<(String, String, String)>> {
+    if block.attr("src").is_some() {
+        bail!("<i18n src=\"...\"> blocks are not supported");
+    }
+    let content = block.content.trim();
+    if content.is_empty() {
+        return Ok(Vec::new());
+    }
+    let value: Value = match block.attr("lang").unwrap_or("json") {
+        "json" => serde_json::from_str(content).context("Invalid JSON in <i18n> block")?,
+        "json5" => json5::from_str(content).context("Invalid JSON5 in <i18n> block")?,
+        other => bail!("Unsupported <i18n> block language: {}", other),
+    };
+    let Value::Object(map) = value else {
+        bail!("<i18n> block must contain an object");
+    };
+
+    let mut messages = Vec::new();
+    match block.attr("locale").filter(|locale| !locale.is_empty()) {
+        Some(locale) => flatten_messages(&map, locale, "", &mut messages),
+        None => {
+            for (locale, locale_messages) in &map {
+                if let Value::Object(locale_map) = locale_messages {
+                    flatten_messages(locale_map, locale, "", &mut messages);
+                }
+            }
+        }
+    }
+    Ok(messages)
+}
+
+fn flatten_messages(
+    map: &Map<String, Value>,
+    locale: &str,
+    prefix: &str,
+    out: &mut Vec<(String, String, String)>,
+) {
+    for (key, value) in map {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value {
+            Value::Object(nested) => flatten_messages(nested, locale, &path, out),
+            Value::String(message) => out.push((locale.to_string(), path, message.clone())),
+            _ => {}
+        }
+    }
+}
+
+/// `:prop`, `@event`, `#slot` and `v-*` attributes hold JavaScript expressions
+fn is_dynamic_attribute(name: &str) -> bool {
+    name.starts_with(':') || name.starts_with('@') || name.starts_with('#') || {
+        name.starts_with("v-") && name != "v-pre" && name != "v-cloak" && name != "v-once"
+    }
+}
+
+/// Source text of a string literal property (`path: 'key'`) in an object expression
+fn object_property_literal(object: &str, property: &str) -> Option<String> {
+    let mut search = 0;
+    while let Some(offset) = object[search..].find(property) {
+        let start = search + offset;
+        search = start + property.len();
+        let boundary = object[..start]
+            .chars()
+            .next_back()
+            .is_none_or(|c| matches!(c, '{' | ',' | '\'' | '"') || c.is_whitespace());
+        if !boundary {
+            continue;
+        }
+        let after = object[search..]
+            .trim_start_matches(['\'', '"'])
+            .trim_start();
+        let Some(after_colon) = after.strip_prefix(':') else {
+            continue;
+        };
+        let literal = after_colon.trim_start();
+        let quote = literal.chars().next()?;
+        if !matches!(quote, '\'' | '"' | '`') {
+            return None;
+        }
+        let end = literal[1..].find(quote)?;
+        return Some(literal[..end + 2].to_string());
+    }
+    None
+}
+
+struct OpenTag {
+    name: String,
+    attrs: Vec<(String, Option<String>)>,
+    /// Byte index just past the closing `>`
+    end: usize,
+    self_closing: bool,
+}
+
+/// Parse `<name attr="value" ...>` starting at `start` (the `<`).
+/// Quoted values may contain `>` and the other quote character.
+fn parse_open_tag(source: &str, start: usize) -> Option<OpenTag> {
+    let bytes = source.as_bytes();
+    let mut pos = start + 1;
+    if !bytes.get(pos)?.is_ascii_alphabetic() {
+        return None;
+    }
+    let name_start = pos;
+    while pos < bytes.len()
+        && (bytes[pos].is_ascii_alphanumeric() || matches!(bytes[pos], b'-' | b'_' | b'.' | b':'))
+    {
+        pos += 1;
+    }
+    let name = source[name_start..pos].to_string();
+    let mut attrs = Vec::new();
+
+    loop {
+        while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        match bytes.get(pos)? {
+            b'>' => {
+                return Some(OpenTag {
+                    name,
+                    attrs,
+                    end: pos + 1,
+                    self_closing: false,
+                })
+            }
+            b'/' if bytes.get(pos + 1) == Some(&b'>') => {
+                return Some(OpenTag {
+                    name,
+                    attrs,
+                    end: pos + 2,
+                    self_closing: true,
+                })
+            }
+            b'/' => {
+                pos += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        let attr_start = pos;
+        let ends_name = |pos: usize| {
+            bytes[pos].is_ascii_whitespace()
+                || matches!(bytes[pos], b'=' | b'>')
+                || (bytes[pos] == b'/' && bytes.get(pos + 1) == Some(&b'>'))
+        };
+        while pos < bytes.len() && !ends_name(pos) {
+            pos += 1;
+        }
+        let attr_name = source[attr_start..pos].to_string();
+
+        let mut lookahead = pos;
+        while lookahead < bytes.len() && bytes[lookahead].is_ascii_whitespace() {
+            lookahead += 1;
+        }
+        if bytes.get(lookahead) != Some(&b'=') {
+            attrs.push((attr_name, None));
+            continue;
+        }
+        pos = lookahead + 1;
+        while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+
+        let value = match bytes.get(pos)? {
+            quote @ (b'"' | b'\'') => {
+                let value_start = pos + 1;
+                let len = source[value_start..].find(*quote as char)?;
+                pos = value_start + len + 1;
+                &source[value_start..value_start + len]
+            }
+            _ => {
+                let value_start = pos;
+                while pos < bytes.len() && !bytes[pos].is_ascii_whitespace() && bytes[pos] != b'>' {
+                    pos += 1;
+                }
+                &source[value_start..pos]
+            }
+        };
+        attrs.push((attr_name, Some(decode_entities(value))));
+    }
+}
+
+fn decode_entities(value: &str) -> String {
+    if !value.contains('&') {
+        return value.to_string();
+    }
+    value
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+fn skip_comment(source: &str, start: usize) -> usize {
+    source[start..]
+        .find("-->")
+        .map(|end| start + end + 3)
+        .unwrap_or(source.len())
+}
+
+fn skip_to_tag_end(source: &str, start: usize) -> usize {
+    source[start + 1..]
+        .find('>')
+        .map(|end| start + end + 2)
+        .unwrap_or(source.len())
+}
+
+/// Find `</tag>` for a raw text block; returns (content end, index after the close tag)
+fn find_raw_close(source: &str, from: usize, tag: &str) -> Option<(usize, usize)> {
+    let needle = format!("</{}", tag.to_ascii_lowercase());
+    let lower = source[from..].to_ascii_lowercase();
+    let mut search = 0;
+    while let Some(offset) = lower[search..].find(&needle) {
+        let close = search + offset;
+        let after = close + needle.len();
+        if lower[after..].starts_with(|c: char| c == '>' || c.is_ascii_whitespace()) {
+            let end = lower[after..].find('>')? + after + 1;
+            return Some((from + close, from + end));
+        }
+        search = after;
+    }
+    None
+}
+
+/// Find the `</template>` matching an open template, skipping nested ones
+fn find_template_close(source: &str, from: usize) -> Option<(usize, usize)> {
+    let mut depth = 1usize;
+    let mut pos = from;
+
+    while let Some(offset) = source[pos..].find('<') {
+        let start = pos + offset;
+        let rest = &source[start..];
+        if rest.starts_with("<!--") {
+            pos = skip_comment(source, start);
+        } else if rest.len() >= 11 && rest[..11].eq_ignore_ascii_case("</template>") {
+            depth -= 1;
+            if depth == 0 {
+                return Some((start, start + 11));
+            }
+            pos = start + 11;
+        } else if let Some(tag) = parse_open_tag(source, start) {
+            if tag.name.eq_ignore_ascii_case("template") && !tag.self_closing {
+                depth += 1;
+            }
+            pos = tag.end;
+        } else {
+            pos = start + 1;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_sfc_keeps_nested_templates_in_the_root_block() {
+        let source = r#"
+<template>
+  <div>
+    <template v-if="ok"><span>{{ $t('nested') }}</span></template>
+  </div>
+</template>
+<script setup>
+const a = "</template>";
+</script>
+<i18n locale="en">{ "hello": "Hello" }</i18n>
+"#;
+        let blocks = parse_sfc(source);
+        let tags: Vec<&str> = blocks.iter().map(|b| b.tag.as_str()).collect();
+        assert_eq!(tags, vec!["template", "script", "i18n"]);
+        assert!(blocks[0].content.contains("$t('nested')"));
+        assert!(blocks[1].content.contains("\"</template>\""));
+        assert_eq!(blocks[2].attr("locale"), Some("en"));
+    }
+
+    #[test]
+    fn template_expressions_handle_nested_quotes_and_multiline_bindings() {
+        let template = r#"
+<button
+  :title="
+    $t('a.title', { name: 'x > y' })
+  "
+  title="t('static')"
+  @click='notify($t("b.clicked"))'
+  v-t="{ path: 'c.label', args: { n } }"
+>{{ $t('d.text') }}</button>
+"#;
+        let expressions = template_expressions(template);
+        assert_eq!(
+            expressions,
+            vec![
+                TemplateExpression::Code("$t('a.title', { name: 'x > y' })".to_string()),
+                TemplateExpression::Code("notify($t(\"b.clicked\"))".to_string()),
+                TemplateExpression::TranslateDirective(
+                    "{ path: 'c.label', args: { n } }".to_string()
+                ),
+                TemplateExpression::Code("$t('d.text')".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn translate_directive_call_supports_string_and_object_forms() {
+        assert_eq!(
+            translate_directive_call("'hello'"),
+            Some("$t('hello')".to_string())
+        );
+        assert_eq!(
+            translate_directive_call("{ path: \"greeting\", locale: 'en' }"),
+            Some("$t(\"greeting\")".to_string())
+        );
+        assert_eq!(translate_directive_call("dynamicKey"), None);
+    }
+
+    #[test]
+    fn parse_i18n_block_flattens_messages_per_locale() {
+        let block = SfcBlock {
+            tag: "i18n".to_string(),
+            attrs: vec![],
+            content: r#"{ "en": { "title": "Title", "menu": { "open": "Open" } }, "de": { "title": "Titel" } }"#
+                .to_string(),
+        };
+        let messages = parse_i18n_block(&block).unwrap();
+        assert_eq!(
+            messages,
+            vec![
+                ("de".to_string(), "title".to_string(), "Titel".to_string()),
+                (
+                    "en".to_string(),
+                    "menu.open".to_string(),
+                    "Open".to_string()
+                ),
+                ("en".to_string(), "title".to_string(), "Title".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_i18n_block_rejects_unsupported_languages() {
+        let block = SfcBlock {
+            tag: "i18n".to_string(),
+            attrs: vec![("lang".to_string(), Some("yaml".to_string()))],
+            content: "en:\n  hello: Hello".to_string(),
+        };
+        let err = parse_i18n_block(&block).unwrap_err();
+        assert!(err.to_string().contains("yaml"));
+    }
+}
@@ -0,0 +1,221 @@
+//! Safe traversal of glob matches and locale directories: symlink handling and
+//! file names that only differ in case.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+
+/// Why a path was not used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Skipped {
+    /// Reached through a symlink while `followSymlinks` is off
+    NotFollowed,
+    /// The symlink resolves outside the project root
+    OutsideRoot(PathBuf),
+    /// The symlink is dangling or part of a cycle
+    Unresolvable,
+    /// The same file was already reached through another path
+    Duplicate,
+}
+
+impl std::fmt::Display for Skipped {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Skipped::NotFollowed => write!(f, "symlink not followed (followSymlinks is off)"),
+            Skipped::OutsideRoot(target) => {
+                write!(
+                    f,
+                    "symlink escapes the project root to {}",
+                    target.display()
+                )
+            }
+            Skipped::Unresolvable => write!(f, "dangling or cyclic symlink"),
+            Skipped::Duplicate => write!(f, "same file already reached through another path"),
+        }
+    }
+}
+
+/// Admits glob matches one at a time. A path through a symlink is used only
+/// when `follow` is set and it resolves inside the root (the current directory,
+/// or the pattern's base directory when that lies elsewhere); every real file
+/// is used once, so symlink cycles cannot repeat files.
+#[derive(Debug)]
+pub struct SymlinkGuard {
+    follow: bool,
+    cwd: Option<PathBuf>,
+    seen: HashSet<PathBuf>,
+}
+
+impl SymlinkGuard {
+    pub fn new(follow: bool) -> Self {
+        Self {
+            follow,
+            cwd: std::env::current_dir()
+                .ok()
+                .and_then(|dir| dir.canonicalize().ok()),
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Check `path`, matched by a pattern whose literal directory is `base`
+    pub fn admit(&mut self, base: &Path, path: &Path) -> Result<(), Skipped> {
+        let Ok(real) = path.canonicalize() else {
+            return Err(Skipped::Unresolvable);
+        };
+        if through_symlink(base, path) {
+            if !self.follow {
+                return Err(Skipped::NotFollowed);
+            }
+            let base = base.canonicalize().unwrap_or_else(|_| base.to_path_buf());
+            let root = match &self.cwd {
+                Some(cwd) if base.starts_with(cwd) => cwd.as_path(),
+                _ => base.as_path(),
+            };
+            if !real.starts_with(root) {
+                return Err(Skipped::OutsideRoot(real));
+            }
+        }
+        if !self.seen.insert(real) {
+            return Err(Skipped::Duplicate);
+        }
+        Ok(())
+    }
+}
+
+/// Literal directory of a glob pattern (`src/**/*.ts` -> `src`)
+pub fn glob_base(pattern: &str) -> PathBuf {
+    let mut base = PathBuf::new();
+    for part in pattern.split('/') {
+        if part.contains(['*', '?', '[', '{']) {
+            break;
+        }
+        base.push(if part.is_empty() && base.as_os_str().is_empty() {
+            "/"
+        } else {
+            part
+        });
+    }
+    // The last literal part of a pattern without wildcards is the file itself
+    if base == Path::new(pattern) {
+        base.pop();
+    }
+    if base.as_os_str().is_empty() {
+        PathBuf::from(".")
+    } else {
+        base
+    }
+}
+
+/// Whether `path` or one of its directories below `base` is a symlink
+fn through_symlink(base: &Path, path: &Path) -> bool {
+    let relative = path.strip_prefix(base).unwrap_or(path);
+    let mut current = if path.starts_with(base) {
+        base.to_path_buf()
+    } else {
+        PathBuf::new()
+    };
+    for component in relative.components() {
+        if matches!(component, Component::CurDir) {
+            continue;
+        }
+        current.push(component);
+        if std::fs::symlink_metadata(&current).is_ok_and(|meta| meta.file_type().is_symlink()) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Groups of entries of `dir` whose names only differ in case (`Common.json`
+/// and `common.json`), which collide on case-insensitive file systems
+pub fn case_collisions(dir: &Path) -> Vec<Vec<String>> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut by_folded: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        by_folded.entry(name.to_lowercase()).or_default().push(name);
+    }
+    by_folded
+        .into_values()
+        .filter(|names| names.len() > 1)
+        .map(|mut names| {
+            names.sort();
+            names
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_base_is_the_literal_directory() {
+        assert_eq!(glob_base("src/**/*.ts"), PathBuf::from("src"));
+        assert_eq!(
+            glob_base("./src/app/*.{ts,tsx}"),
+            PathBuf::from("./src/app")
+        );
+        assert_eq!(glob_base("**/*.ts"), PathBuf::from("."));
+        assert_eq!(glob_base("src/app.ts"), PathBuf::from("src"));
+        assert_eq!(glob_base("/abs/dir/*.ts"), PathBuf::from("/abs/dir"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn guard_skips_escaping_cyclic_and_unfollowed_symlinks() {
+        use std::os::unix::fs::symlink;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join("project");
+        let src = root.join("src");
+        let outside = tmp.path().join("outside");
+        std::fs::create_dir_all(src.join("real")).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::write(src.join("real/a.ts"), "").unwrap();
+        std::fs::write(outside.join("b.ts"), "").unwrap();
+        symlink(src.join("real"), src.join("linked")).unwrap();
+        symlink(&outside, src.join("escape")).unwrap();
+        symlink(src.join("loop"), src.join("loop")).unwrap();
+
+        let mut guard = SymlinkGuard::new(true);
+        assert_eq!(guard.admit(&src, &src.join("real/a.ts")), Ok(()));
+        assert_eq!(
+            guard.admit(&src, &src.join("linked/a.ts")),
+            Err(Skipped::Duplicate)
+        );
+        assert!(matches!(
+            guard.admit(&src, &src.join("escape/b.ts")),
+            Err(Skipped::OutsideRoot(_))
+        ));
+        assert_eq!(
+            guard.admit(&src, &src.join("loop")),
+            Err(Skipped::Unresolvable)
+        );
+
+        let mut guard = SymlinkGuard::new(false);
+        assert_eq!(
+            guard.admit(&src, &src.join("linked/a.ts")),
+            Err(Skipped::NotFollowed)
+        );
+        assert_eq!(guard.admit(&src, &src.join("real/a.ts")), Ok(()));
+    }
+
+    #[test]
+    fn case_collisions_group_names_differing_in_case() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("common.json"), "{}").unwrap();
+        std::fs::write(tmp.path().join("translation.json"), "{}").unwrap();
+        if std::fs::write(tmp.path().join("Common.json"), "{}").is_err()
+            || std::fs::read_dir(tmp.path()).unwrap().count() < 3
+        {
+            // Case-insensitive file system: the collision cannot be created
+            return;
+        }
+        assert_eq!(
+            case_collisions(tmp.path()),
+            vec![vec!["Common.json".to_string(), "common.json".to_string()]]
+        );
+    }
+}
@@ -70,6 +70,11 @@ try {
  * @param {boolean} [options.fail_on_warnings] - Fail on warnings
  * @param {boolean} [options.generate_types] - Generate TypeScript types
  * @param {string} [options.types_output] - TypeScript output path
+ * @param {AbortSignal} [options.signal] - Cancels the extraction; the result then has
+ *   `cancelled: true` and no locale file is written
+ * @param {number} [options.timeoutMs] - Cancel the extraction after this many milliseconds
+ * @param {string[]} [options.diagnosticKinds] - Only report diagnostics of these kinds,
+ *   e.g. `['dynamic-key', 'parse-error']`
  * @returns {Promise<object>} Extraction results
  */
 async function extract(config, options = {}) {
@@ -80,7 +85,26 @@ async function extract(config, options = {}) {
       message: 'Executed via CLI fallback (native addon unavailable)'
     };
   }
-  
+
+  const { signal, ...nativeOptions } = options;
+  if (signal || nativeOptions.timeoutMs !== undefined) {
+    // Runs off the main thread so the abort event can be delivered mid-run
+    const token = new nativeAddon.CancellationToken();
+    const onAbort = () => token.cancel();
+    if (signal) {
+      if (signal.aborted) {
+        token.cancel();
+      } else {
+        signal.addEventListener('abort', onAbort, { once: true });
+      }
+    }
+    try {
+      return await nativeAddon.extractAsync(config, nativeOptions, token);
+    } finally {
+      if (signal) signal.removeEventListener('abort', onAbort);
+    }
+  }
+
   // Call native function
   const resultJson = nativeAddon.extract(config, options);
   
@@ -126,6 +150,49 @@ async function check(config, options = {}) {
   return JSON.parse(resultJson);
 }
 
+/**
+ * List dead (unused) translation keys without modifying any file
+ *
+ * @param {object} config - Configuration object
+ * @param {object} [options] - Optional options
+ * @param {string[]} [options.locales] - Locales to scan (defaults to the first configured locale)
+ * @returns {Promise<object>} `{ deadKeys, suggestedPreservePatterns }`
+ */
+async function findDeadKeys(config, options = {}) {
+  requireNativeAddon('findDeadKeys');
+  return nativeAddon.findDeadKeys(config, options);
+}
+
+/**
+ * Remove a chosen subset of dead keys from locale files
+ *
+ * Keys are re-checked before removal; keys that are in use again are returned
+ * in `skippedKeys` instead of being removed.
+ *
+ * @param {object} config - Configuration object
+ * @param {object[]} keys - Entries of `findDeadKeys().deadKeys`
+ * @returns {Promise<object>} `{ removedCount, skippedKeys }`
+ */
+async function purgeDeadKeys(config, keys) {
+  requireNativeAddon('purgeDeadKeys');
+  return nativeAddon.purgeDeadKeys(config, keys);
+}
+
+/**
+ * Extract the sources once and keep the keys in memory for fast lookups
+ *
+ * The returned index has `byPrefix(prefix, namespace?)`, `byNamespace(ns)`,
+ * `byFile(path)`, `search(query, limit?)`, `namespaces()`, `size` and
+ * `refreshFile(path)` to re-extract one file after it is saved.
+ *
+ * @param {object} config - Configuration object
+ * @returns {Promise<object>} KeyIndex
+ */
+async function buildKeyIndex(config) {
+  requireNativeAddon('buildKeyIndex');
+  return nativeAddon.buildKeyIndex(config);
+}
+
 /**
  * Watch for file changes and extract keys automatically
  * 
@@ -151,9 +218,18 @@ module.exports = {
   extract,
   lint,
   check,
+  findDeadKeys,
+  purgeDeadKeys,
+  buildKeyIndex,
   watch
 };
 
+function requireNativeAddon(name) {
+  if (!nativeAddon) {
+    throw new Error(`${name}() requires the native addon. Build it with: cargo build --release`);
+  }
+}
+
 async function runCliFallback(config, command, options = {}) {
   const tmpRoot = fs.mkdtempSync(path.join(os.tmpdir(), 'i18next-turbo-node-fallback-'));
   const configPath = path.join(tmpRoot, 'i18next-turbo.json');
@@ -166,6 +242,7 @@ async function runCliFallback(config, command, options = {}) {
     if (options.generate_types || options.generateTypes) args.push('--generate-types');
     const typesOutput = options.types_output || options.typesOutput;
     if (typesOutput) args.push('--types-output', String(typesOutput));
+    for (const kind of options.diagnosticKinds || []) args.push('--diagnostic', String(kind));
   }
   if (command === 'lint' && (options.fail_on_error || options.failOnError)) {
     args.push('--fail-on-error');
@@ -180,7 +257,8 @@ async function runCliFallback(config, command, options = {}) {
     const child = spawn(process.execPath, args, {
       cwd: process.cwd(),
       stdio: 'inherit',
-      env: process.env
+      env: process.env,
+      signal: options.signal
     });
     child.on('error', reject);
     child.on('exit', (code) => {
@@ -0,0 +1,872 @@
+use anyhow::Result;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::Path;
+
+use crate::config::Config;
+use crate::extractor;
+use crate::json_sync;
+use crate::key_transforms;
+use crate::plan::{self, Change, ChangePlan};
+
+/// Most search results listed at once
+const MAX_RESULTS: usize = 20;
+
+/// Rows `PageUp`/`PageDown` move the selection in the terminal UI
+const PAGE: usize = 10;
+
+/// A key known from the sources or the locale files
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BrowseEntry {
+    pub namespace: String,
+    pub key: String,
+    /// Value per locale; missing locales have no entry
+    pub values: BTreeMap<String, Value>,
+    /// `file:line:column` of each usage (`file` when the literal isn't found)
+    pub usages: BTreeSet<String>,
+}
+
+/// What the prompt loop does after a command
+#[derive(Debug, PartialEq, Eq)]
+enum Flow {
+    Continue,
+    /// Leave and apply the plan
+    Apply,
+    /// Leave without applying
+    Discard,
+}
+
+/// Interactive key browser state
+struct Browser<'a> {
+    config: &'a Config,
+    entries: Vec<BrowseEntry>,
+    /// Entry indexes of the last search, numbered from 1 in the listing
+    results: Vec<usize>,
+    plan: Vec<Change>,
+}
+
+/// Search keys, show values in every locale and usage sites, and queue deletes
+/// and renames that are applied when leaving with `quit` (or written to
+/// `emit_plan` instead). A terminal gets the full-screen UI; piped input the
+/// line-based prompt, so sessions can be scripted.
+pub fn run(config: &Config, emit_plan: Option<&Path>) -> Result<()> {
+    println!("=== i18next-turbo browse ===\n");
+    let entries = load_entries(config)?;
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+    let changes = if stdin.is_terminal() && stdout.is_terminal() {
+        let mut terminal = ratatui::try_init()?;
+        let changes = App::new(config, entries).run(&mut terminal);
+        ratatui::restore();
+        changes?
+    } else {
+        browse(config, entries, &mut stdin.lock(), &mut stdout)?
+    };
+    if changes.is_empty() {
+        println!("No changes applied.");
+        return Ok(());
+    }
+    match emit_plan {
+        Some(path) => ChangePlan::new(changes).emit(path, config.path_style),
+        None => {
+            println!("\nApplying {} change(s)...", changes.len());
+            plan::apply(config, &changes, false)
+        }
+    }
+}
+
+/// Run the prompt loop over `input`, returning the plan to apply (empty when
+/// the session was discarded)
+fn browse(
+    config: &Config,
+    entries: Vec<BrowseEntry>,
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+) -> Result<Vec<Change>> {
+    let mut browser = Browser {
+        config,
+        entries,
+        results: Vec::new(),
+        plan: Vec::new(),
+    };
+    writeln!(
+        out,
+        "{} keys in {} locale(s). Type `help` for commands.",
+        browser.entries.len(),
+        config.locales.len()
+    )?;
+
+    let mut line = String::new();
+    loop {
+        write!(out, "browse> ")?;
+        out.flush()?;
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(out)?;
+            break;
+        }
+        match browser.handle(line.trim(), out)? {
+            Flow::Continue => {}
+            Flow::Apply => break,
+            Flow::Discard => return Ok(Vec::new()),
+        }
+    }
+    Ok(browser.plan)
+}
+
+impl Browser<'_> {
+    fn handle(&mut self, line: &str, out: &mut impl Write) -> Result<Flow> {
+        let (command, rest) = line.split_once(' ').unwrap_or((line, ""));
+        let rest = rest.trim();
+        match command {
+            "" => {}
+            "help" | "?" => print_help(out)?,
+            "search" | "s" | "/" => self.search(rest, out)?,
+            "show" => self.with_entry(rest, out, |browser, index, out| browser.show(index, out))?,
+            "delete" | "d" => self.with_entry(rest, out, |browser, index, out| {
+                let change = browser.delete_change(index);
+                writeln!(out, "Queued: {}", plan::describe(&change))?;
+                let usages = browser.entries[index].usages.len();
+                if usages > 0 {
+                    writeln!(out, "  Note: still used in {} place(s)", usages)?;
+                }
+                browser.queue(change);
+                Ok(())
+            })?,
+            "rename" | "r" => {
+                let (target, new_key) = rest.rsplit_once(' ').unwrap_or((rest, ""));
+                if new_key.is_empty() {
+                    writeln!(out, "Usage: rename <key|#> <new key>")?;
+                    return Ok(Flow::Continue);
+                }
+                self.with_entry(target.trim(), out, |browser, index, out| {
+                    let change = browser.rename_change(index, new_key);
+                    writeln!(out, "Queued: {}", plan::describe(&change))?;
+                    browser.queue(change);
+                    Ok(())
+                })?
+            }
+            "undo" | "u" => match self.plan.pop() {
+                Some(change) => writeln!(out, "Removed: {}", plan::describe(&change))?,
+                None => writeln!(out, "Nothing queued.")?,
+            },
+            "plan" | "p" => {
+                if self.plan.is_empty() {
+                    writeln!(out, "Nothing queued.")?;
+                }
+                for (i, change) in self.plan.iter().enumerate() {
+                    writeln!(out, "  {}. {}", i + 1, plan::describe(change))?;
+                }
+            }
+            "quit" | "q" | "exit" => return Ok(Flow::Apply),
+            "quit!" | "q!" => {
+                if !self.plan.is_empty() {
+                    writeln!(out, "Discarded {} queued change(s).", self.plan.len())?;
+                }
+                return Ok(Flow::Discard);
+            }
+            // A bare number shows that result of the last search
+            _ if command.parse::<usize>().is_ok() && rest.is_empty() => {
+                self.with_entry(command, out, |browser, index, out| browser.show(index, out))?
+            }
+            _ => writeln!(out, "Unknown command `{}`. Type `help`.", command)?,
+        }
+        Ok(Flow::Continue)
+    }
+
+    /// Indexes of the keys whose id or any value contains `query`
+    /// (case-insensitive)
+    fn matching(&self, query: &str) -> Vec<usize> {
+        let query = query.to_lowercase();
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| {
+                self.display_key(entry).to_lowercase().contains(&query)
+                    || entry
+                        .values
+                        .values()
+                        .any(|value| value_text(value).to_lowercase().contains(&query))
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// List keys whose id or any value contains `query` (case-insensitive)
+    fn search(&mut self, query: &str, out: &mut impl Write) -> Result<()> {
+        self.results = self.matching(query);
+
+        if self.results.is_empty() {
+            writeln!(out, "No keys match \"{}\".", query)?;
+            return Ok(());
+        }
+        for (number, &index) in self.results.iter().enumerate().take(MAX_RESULTS) {
+            let entry = &self.entries[index];
+            writeln!(
+                out,
+                "  {:>3}. {}  [{}/{} locales, {} usage(s)]",
+                number + 1,
+                self.display_key(entry),
+                entry.values.len(),
+                self.config.locales.len(),
+                entry.usages.len()
+            )?;
+        }
+        if self.results.len() > MAX_RESULTS {
+            writeln!(
+                out,
+                "  ... {} more, refine the search",
+                self.results.len() - MAX_RESULTS
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Values of every locale side by side, then usage sites
+    fn show(&self, index: usize, out: &mut impl Write) -> Result<()> {
+        let entry = &self.entries[index];
+        writeln!(out, "\n{}", self.display_key(entry))?;
+        let width = self
+            .config
+            .locales
+            .iter()
+            .map(|l| l.chars().count())
+            .max()
+            .unwrap_or(0);
+        for locale in &self.config.locales {
+            let value = entry
+                .values
+                .get(locale)
+                .map(value_text)
+                .unwrap_or_else(|| "(missing)".to_string());
+            writeln!(out, "  {:<width$}  {}", locale, value, width = width)?;
+        }
+        if entry.usages.is_empty() {
+            writeln!(out, "  Not used in source files")?;
+        } else {
+            writeln!(out, "  Used in:")?;
+            for usage in &entry.usages {
+                writeln!(out, "    {}", usage)?;
+            }
+        }
+        writeln!(out)?;
+        Ok(())
+    }
+
+    /// Resolve `target` (a search result number or a key) and run `action` on it
+    fn with_entry<W: Write>(
+        &mut self,
+        target: &str,
+        out: &mut W,
+        action: impl FnOnce(&mut Self, usize, &mut W) -> Result<()>,
+    ) -> Result<()> {
+        match self.resolve(target) {
+            Some(index) => action(self, index, out),
+            None => {
+                writeln!(out, "No key `{}`. Search first or give a full key.", target)?;
+                Ok(())
+            }
+        }
+    }
+
+    fn resolve(&self, target: &str) -> Option<usize> {
+        if target.is_empty() {
+            return None;
+        }
+        if let Ok(number) = target.parse::<usize>() {
+            return number
+                .checked_sub(1)
+                .and_then(|i| self.results.get(i).copied());
+        }
+        let default_namespace = self.config.effective_default_namespace();
+        let (namespace, key) = match target.split_once(self.config.ns_separator.as_str()) {
+            Some((namespace, key)) if !self.config.ns_separator.is_empty() => (namespace, key),
+            _ => (default_namespace, target),
+        };
+        self.entries
+            .iter()
+            .position(|entry| entry.namespace == namespace && entry.key == key)
+    }
+
+    fn delete_change(&self, index: usize) -> Change {
+        let entry = &self.entries[index];
+        Change::DeleteKey {
+            key: format!("{}:{}", entry.namespace, entry.key),
+            locales: Vec::new(),
+        }
+    }
+
+    /// Rename to `new_key`, in the entry's namespace unless it names one
+    fn rename_change(&self, index: usize, new_key: &str) -> Change {
+        let entry = &self.entries[index];
+        let to = if new_key.contains(':') {
+            new_key.to_string()
+        } else {
+            format!("{}:{}", entry.namespace, new_key)
+        };
+        Change::RenameKey {
+            from: format!("{}:{}", entry.namespace, entry.key),
+            to,
+            locales_only: false,
+        }
+    }
+
+    fn queue(&mut self, change: Change) {
+        if !self.plan.contains(&change) {
+            self.plan.push(change);
+        }
+    }
+
+    fn display_key(&self, entry: &BrowseEntry) -> String {
+        if self.config.namespace_less_mode() {
+            entry.key.clone()
+        } else {
+            format!(
+                "{}{}{}",
+                entry.namespace, self.config.ns_separator, entry.key
+            )
+        }
+    }
+}
+
+/// What the terminal UI's keys edit
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Input {
+    /// Keys move the selection and queue changes
+    Browse,
+    /// Keys edit the search query
+    Search,
+    /// Keys edit the new name of the selected key
+    Rename(String),
+}
+
+/// Full-screen key browser: the matching keys on the left, the selected key's
+/// value in every locale side by side on the right, usages and the queued plan
+/// below
+struct App<'a> {
+    browser: Browser<'a>,
+    query: String,
+    /// Position of the selected key in `browser.results`
+    selected: usize,
+    input: Input,
+    /// Outcome of the last action, shown above the key help
+    status: String,
+}
+
+impl<'a> App<'a> {
+    fn new(config: &'a Config, entries: Vec<BrowseEntry>) -> Self {
+        let mut app = App {
+            browser: Browser {
+                config,
+                entries,
+                results: Vec::new(),
+                plan: Vec::new(),
+            },
+            query: String::new(),
+            selected: 0,
+            input: Input::Browse,
+            status: String::new(),
+        };
+        app.filter();
+        app.status = format!(
+            "{} keys in {} locale(s)",
+            app.browser.entries.len(),
+            config.locales.len()
+        );
+        app
+    }
+
+    /// Draw and handle keys until the user leaves, returning the plan to apply
+    /// (empty when the session was discarded)
+    fn run(mut self, terminal: &mut DefaultTerminal) -> Result<Vec<Change>> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match self.on_key(key) {
+                Flow::Continue => {}
+                Flow::Apply => return Ok(self.browser.plan),
+                Flow::Discard => return Ok(Vec::new()),
+            }
+        }
+    }
+
+    fn filter(&mut self) {
+        self.browser.results = self.browser.matching(&self.query);
+        self.selected = 0;
+    }
+
+    fn current(&self) -> Option<usize> {
+        self.browser.results.get(self.selected).copied()
+    }
+
+    fn on_key(&mut self, key: KeyEvent) -> Flow {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Flow::Discard;
+        }
+        match &mut self.input {
+            Input::Search => match key.code {
+                KeyCode::Char(c) => {
+                    self.query.push(c);
+                    self.filter();
+                }
+                KeyCode::Backspace => {
+                    self.query.pop();
+                    self.filter();
+                }
+                KeyCode::Enter | KeyCode::Esc => self.input = Input::Browse,
+                _ => {}
+            },
+            Input::Rename(new_key) => match key.code {
+                KeyCode::Char(c) => new_key.push(c),
+                KeyCode::Backspace => {
+                    new_key.pop();
+                }
+                KeyCode::Enter => {
+                    let new_key = new_key.trim().to_string();
+                    self.input = Input::Browse;
+                    if let (Some(index), false) = (self.current(), new_key.is_empty()) {
+                        let change = self.browser.rename_change(index, &new_key);
+                        self.status = format!("Queued: {}", plan::describe(&change));
+                        self.browser.queue(change);
+                    }
+                }
+                KeyCode::Esc => self.input = Input::Browse,
+                _ => {}
+            },
+            Input::Browse => match key.code {
+                KeyCode::Char('/') => self.input = Input::Search,
+                KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => self.move_down(1),
+                KeyCode::PageUp => self.selected = self.selected.saturating_sub(PAGE),
+                KeyCode::PageDown => self.move_down(PAGE),
+                KeyCode::Home => self.selected = 0,
+                KeyCode::End => self.move_down(usize::MAX),
+                KeyCode::Char('d') => {
+                    if let Some(index) = self.current() {
+                        let change = self.browser.delete_change(index);
+                        self.status = format!("Queued: {}", plan::describe(&change));
+                        let usages = self.browser.entries[index].usages.len();
+                        if usages > 0 {
+                            self.status += &format!(" (still used in {} place(s))", usages);
+                        }
+                        self.browser.queue(change);
+                    }
+                }
+                KeyCode::Char('r') => {
+                    if let Some(index) = self.current() {
+                        self.input = Input::Rename(self.browser.entries[index].key.clone());
+                    }
+                }
+                KeyCode::Char('u') => {
+                    self.status = match self.browser.plan.pop() {
+                        Some(change) => format!("Removed: {}", plan::describe(&change)),
+                        None => "Nothing queued.".to_string(),
+                    }
+                }
+                KeyCode::Char('q') => return Flow::Apply,
+                KeyCode::Char('Q') | KeyCode::Esc => return Flow::Discard,
+                _ => {}
+            },
+        }
+        Flow::Continue
+    }
+
+    fn move_down(&mut self, rows: usize) {
+        let last = self.browser.results.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add(rows).min(last);
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let plan_height = self.browser.plan.len().clamp(1, 5) as u16 + 2;
+        let [search_area, body, plan_area, status_area, help_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(6),
+            Constraint::Length(plan_height),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [list_area, detail_area] =
+            Layout::horizontal([Constraint::Percentage(35), Constraint::Percentage(65)])
+                .areas(body);
+
+        let (title, text) = match &self.input {
+            Input::Rename(new_key) => (" Rename to ", new_key.as_str()),
+            _ => (" Search ", self.query.as_str()),
+        };
+        let editing = self.input != Input::Browse;
+        frame.render_widget(
+            Paragraph::new(text).block(Block::bordered().title(title).border_style(if editing {
+                Style::new().fg(Color::Yellow)
+            } else {
+                Style::new()
+            })),
+            search_area,
+        );
+        if editing {
+            let column = search_area.x + 1 + text.chars().count() as u16;
+            frame.set_cursor_position((column.min(search_area.right() - 2), search_area.y + 1));
+        }
+
+        let locales = self.browser.config.locales.len();
+        let items: Vec<ListItem> = self
+            .browser
+            .results
+            .iter()
+            .map(|&index| {
+                let entry = &self.browser.entries[index];
+                ListItem::new(format!(
+                    "{} [{}/{}]",
+                    self.browser.display_key(entry),
+                    entry.values.len(),
+                    locales
+                ))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(Block::bordered().title(format!(" Keys ({}) ", self.browser.results.len())))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        let mut state = ListState::default().with_selected(self.current().map(|_| self.selected));
+        frame.render_stateful_widget(list, list_area, &mut state);
+
+        match self.current() {
+            Some(index) => self.draw_entry(frame, detail_area, &self.browser.entries[index]),
+            None => frame.render_widget(
+                Paragraph::new("No keys match the search.").block(Block::bordered()),
+                detail_area,
+            ),
+        }
+
+        let plan: Vec<Line> = if self.browser.plan.is_empty() {
+            vec![Line::styled(
+                "Nothing queued.",
+                Style::new().fg(Color::DarkGray),
+            )]
+        } else {
+            self.browser
+                .plan
+                .iter()
+                .enumerate()
+                .map(|(i, change)| Line::from(format!("{}. {}", i + 1, plan::describe(change))))
+                .collect()
+        };
+        let scroll = self.browser.plan.len().saturating_sub(5) as u16;
+        frame.render_widget(
+            Paragraph::new(plan)
+                .scroll((scroll, 0))
+                .block(Block::bordered().title(" Plan (applied on q) ")),
+            plan_area,
+        );
+        frame.render_widget(Paragraph::new(self.status.as_str()), status_area);
+        frame.render_widget(
+            Paragraph::new(
+                "/ search  \u{2191}\u{2193} move  d delete  r rename  u undo  q apply and quit  Q discard and quit",
+            )
+            .style(Style::new().fg(Color::DarkGray)),
+            help_area,
+        );
+    }
+
+    /// One column per locale with the key's value, usage sites below
+    fn draw_entry(&self, frame: &mut Frame, area: ratatui::layout::Rect, entry: &BrowseEntry) {
+        let usage_height = entry.usages.len().clamp(1, 6) as u16 + 2;
+        let [values_area, usages_area] =
+            Layout::vertical([Constraint::Min(3), Constraint::Length(usage_height)]).areas(area);
+        let locales = &self.browser.config.locales;
+        let columns = Layout::horizontal(vec![
+            Constraint::Ratio(1, locales.len().max(1) as u32);
+            locales.len()
+        ])
+        .split(values_area);
+        for (locale, column) in locales.iter().zip(columns.iter()) {
+            let value = match entry.values.get(locale) {
+                Some(value) => Text::from(value_text(value)),
+                None => Text::styled("(missing)", Style::new().fg(Color::Red)),
+            };
+            frame.render_widget(
+                Paragraph::new(value)
+                    .wrap(Wrap { trim: false })
+                    .block(Block::bordered().title(format!(" {} ", locale))),
+                *column,
+            );
+        }
+
+        let usages: Vec<Line> = if entry.usages.is_empty() {
+            vec![Line::styled(
+                "Not used in source files",
+                Style::new().fg(Color::DarkGray),
+            )]
+        } else {
+            entry
+                .usages
+                .iter()
+                .map(|usage| Line::from(usage.as_str()))
+                .collect()
+        };
+        frame.render_widget(
+            Paragraph::new(usages).block(Block::bordered().title(vec![
+                Span::raw(" "),
+                Span::styled(
+                    self.browser.display_key(entry),
+                    Style::new().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(" used in "),
+            ])),
+            usages_area,
+        );
+    }
+}
+
+fn print_help(out: &mut impl Write) -> Result<()> {
+    writeln!(
+        out,
+        "Commands:
+  search <text>        keys whose name or any value contains <text> (also `s`, `/`)
+  <#> | show <key|#>   values in every locale and usage sites
+  delete <key|#>       queue deleting the key from all locales (also `d`)
+  rename <key|#> <new> queue renaming the key in sources and locales (also `r`)
+  undo                 drop the last queued change (also `u`)
+  plan                 list queued changes (also `p`)
+  quit                 leave and apply the plan (also `q`, end of input)
+  quit!                leave without applying (also `q!`)"
+    )?;
+    Ok(())
+}
+
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Every extracted or translated key with its values and usage sites
+fn load_entries(config: &Config) -> Result<Vec<BrowseEntry>> {
+    let extraction =
+        extractor::extract_with_config(config, &config.input, &config.extraction_ignore())?;
+
+    let mut entries: BTreeMap<(String, String), BrowseEntry> = BTreeMap::new();
+    let default_namespace = config.effective_default_namespace();
+    for (file_path, keys) in &extraction.files {
+        for key in keys {
+            let namespace = key.namespace.as_deref().unwrap_or(default_namespace);
+            let locale_key = key_transforms::transform_key(
+                &config.key_transforms,
+                &key.key,
+                &config.key_separator,
+            );
+            let mut usages: BTreeSet<String> = extraction
+                .key_sources
+                .iter()
+                .filter(|found| {
+                    found.file_path == *file_path
+                        && found.namespace == key.namespace
+                        && found.key == key.key
+                })
+                .map(|found| format!("{}:{}:{}", file_path, found.line, found.column))
+                .collect();
+            // Keys from component blocks are only known by file
+            if usages.is_empty() {
+                usages.insert(file_path.clone());
+            }
+            entry_for(&mut entries, namespace, &locale_key)
+                .usages
+                .extend(usages);
+        }
+    }
+    for locale in &config.locales {
+        let catalog = json_sync::load_locale_namespaces(config, &config.output, locale)?;
+        for ((namespace, key), value) in
+            json_sync::flatten_leaf_values(&catalog, &config.key_separator)
+        {
+            entry_for(&mut entries, &namespace, &key)
+                .values
+                .insert(locale.clone(), value);
+        }
+    }
+    Ok(entries.into_values().collect())
+}
+
+fn entry_for<'a>(
+    entries: &'a mut BTreeMap<(String, String), BrowseEntry>,
+    namespace: &str,
+    key: &str,
+) -> &'a mut BrowseEntry {
+    entries
+        .entry((namespace.to_string(), key.to_string()))
+        .or_insert_with(|| BrowseEntry {
+            namespace: namespace.to_string(),
+            key: key.to_string(),
+            ..BrowseEntry::default()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(namespace: &str, key: &str, values: &[(&str, &str)], usages: &[&str]) -> BrowseEntry {
+        BrowseEntry {
+            namespace: namespace.to_string(),
+            key: key.to_string(),
+            values: values
+                .iter()
+                .map(|(locale, value)| (locale.to_string(), json!(value)))
+                .collect(),
+            usages: usages.iter().map(|u| u.to_string()).collect(),
+        }
+    }
+
+    fn config() -> Config {
+        Config {
+            locales: vec!["en".to_string(), "de".to_string()],
+            ..Config::default()
+        }
+    }
+
+    fn entries() -> Vec<BrowseEntry> {
+        vec![
+            entry(
+                "translation",
+                "home.title",
+                &[("en", "Home"), ("de", "Startseite")],
+                &["src/App.tsx:3:4"],
+            ),
+            entry("translation", "home.old", &[("en", "Old home")], &[]),
+            entry("common", "save", &[("en", "Save")], &["src/Form.tsx:9:6"]),
+        ]
+    }
+
+    #[test]
+    fn browse_searches_shows_and_queues_changes() {
+        let config = config();
+        let entries = entries();
+        let mut input = "search home\n1\ndelete 2\nrename home.title home.heading\nr common:save actions.save\nplan\nundo\nquit\n".as_bytes();
+        let mut out = Vec::new();
+        let plan = browse(&config, entries.clone(), &mut input, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(out.contains("    1. translation:home.title  [2/2 locales, 1 usage(s)]"));
+        assert!(out.contains("    2. translation:home.old  [1/2 locales, 0 usage(s)]"));
+        assert!(out.contains("  en  Home\n  de  Startseite\n  Used in:\n    src/App.tsx:3:4\n"));
+        assert!(out.contains("  3. rename key common:save -> common:actions.save"));
+        assert_eq!(
+            plan,
+            vec![
+                Change::DeleteKey {
+                    key: "translation:home.old".to_string(),
+                    locales: Vec::new(),
+                },
+                Change::RenameKey {
+                    from: "translation:home.title".to_string(),
+                    to: "translation:home.heading".to_string(),
+                    locales_only: false,
+                },
+            ]
+        );
+
+        let mut input = "d common:save\nq!\n".as_bytes();
+        let plan = browse(&config, entries, &mut input, &mut Vec::new()).unwrap();
+        assert!(plan.is_empty());
+    }
+
+    fn press(app: &mut App, keys: &str) -> Flow {
+        let mut flow = Flow::Continue;
+        for c in keys.chars() {
+            let code = match c {
+                '\n' => KeyCode::Enter,
+                '\x08' => KeyCode::Backspace,
+                c => KeyCode::Char(c),
+            };
+            flow = app.on_key(KeyEvent::from(code));
+        }
+        flow
+    }
+
+    fn screen(app: &App) -> Vec<String> {
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(100, 24)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn app_shows_locales_side_by_side_and_queues_changes() {
+        let config = config();
+        let mut app = App::new(&config, entries());
+        assert_eq!(press(&mut app, "/home\n"), Flow::Continue);
+        assert_eq!(app.browser.results, vec![0, 1]);
+
+        let lines = screen(&app);
+        assert!(
+            lines[4].contains("translation:home.title [2/2]"),
+            "{:#?}",
+            lines
+        );
+        let row = lines
+            .iter()
+            .find(|line| line.contains("Startseite"))
+            .expect("de column");
+        let en = row.find("Home").expect("en column on the same row");
+        assert!(en < row.find("Startseite").unwrap(), "{}", row);
+        assert!(lines
+            .iter()
+            .any(|line| line.contains(" en ") && line.contains(" de ")));
+        assert!(lines.iter().any(|line| line.contains("src/App.tsx:3:4")));
+
+        press(&mut app, "jd");
+        assert!(app
+            .status
+            .starts_with("Queued: delete key translation:home.old"));
+        press(&mut app, "kr\x08\x08\x08\x08\x08heading\n");
+        press(&mut app, "/\x08\x08\x08\x08save\nd");
+        assert!(
+            app.status.ends_with("(still used in 1 place(s))"),
+            "{}",
+            app.status
+        );
+        press(&mut app, "u");
+        assert!(screen(&app).iter().any(|line| line
+            .contains("2. rename key translation:home.title -> translation:home.heading")));
+
+        assert_eq!(press(&mut app, "q"), Flow::Apply);
+        assert_eq!(
+            app.browser.plan,
+            vec![
+                Change::DeleteKey {
+                    key: "translation:home.old".to_string(),
+                    locales: Vec::new(),
+                },
+                Change::RenameKey {
+                    from: "translation:home.title".to_string(),
+                    to: "translation:home.heading".to_string(),
+                    locales_only: false,
+                },
+            ]
+        );
+
+        let mut app = App::new(&config, entries());
+        press(&mut app, "d");
+        assert_eq!(press(&mut app, "Q"), Flow::Discard);
+    }
+}
@@ -1,45 +1,123 @@
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::io::{self, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use crate::budgets;
 use crate::cleanup;
-use crate::config::Config;
+use crate::commands::rename_key;
+use crate::config::{Config, PathStyle};
+use crate::config_edit::ConfigFile;
 use crate::extractor::{self, ExtractedKey};
+use crate::json_sync;
+use crate::key_case;
+use crate::key_transforms;
+use crate::locale_store::LocaleStore;
+use crate::markup;
+use crate::metadata::{self, MetadataFile};
+use crate::namespaces;
+use crate::nesting;
+use crate::paths;
+use crate::plan::{self, Change, ChangePlan};
+use crate::plurals;
+use crate::spacing;
+use crate::summary;
 
-pub fn run(config: &Config, remove: bool, dry_run: bool, locale: Option<String>) -> Result<()> {
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    config: &Config,
+    remove: bool,
+    dry_run: bool,
+    locale: Option<String>,
+    write_config: bool,
+    config_path: Option<&Path>,
+    filter_files: &[String],
+    filter_keys: &[String],
+    values: bool,
+    nesting: bool,
+    spacing: bool,
+    key_case: bool,
+    fix: bool,
+    dead_for: Option<u32>,
+    fix_plurals: bool,
+    emit_plan: Option<&Path>,
+    all_locales: bool,
+) -> Result<()> {
     println!("=== i18next-turbo check ===\n");
 
-    // Determine locale to check
-    let check_locale = locale
-        .as_ref()
-        .or(config.locales.first())
-        .map(|s| s.as_str())
-        .unwrap_or("en");
+    if !filter_files.is_empty() && filter_keys.is_empty() {
+        bail!("--filter-file needs --filter-key: only keys owned by the filtered files can be judged dead");
+    }
+    if all_locales && locale.is_some() {
+        bail!("--all-locales and --locale cannot be combined");
+    }
+    let mut scoped = config.clone();
+    scoped.key_filter = filter_keys.to_vec();
+    let scope = json_sync::PreserveMatcher::for_config(&scoped)?;
+    if values {
+        return check_values(config, locale, &scope);
+    }
+    if nesting {
+        return check_nesting(config, locale, &scope);
+    }
+    if spacing {
+        return check_spacing(config, locale, &scope, fix, dry_run);
+    }
+    if key_case {
+        return check_key_case(config, &scope, fix, dry_run);
+    }
+    let lifecycle = config
+        .metadata_file
+        .as_deref()
+        .map(|path| MetadataFile::load(Path::new(path)))
+        .transpose()?;
+    if dead_for.is_some() && lifecycle.is_none() {
+        bail!("--dead-for needs 'metadataFile' in the config to know when keys were last seen");
+    }
+
+    // Determine locales to check
+    let check_locales: Vec<String> = if all_locales {
+        config.locales.clone()
+    } else {
+        let check_locale = locale
+            .as_ref()
+            .or(config.locales.first())
+            .map(|s| s.as_str())
+            .unwrap_or("en");
+        vec![check_locale.to_string()]
+    };
 
     println!("Configuration:");
     println!("  Locales directory: {}", config.output);
-    println!("  Checking locale: {}", check_locale);
+    if all_locales {
+        println!("  Checking locales: {}", check_locales.join(", "));
+    } else {
+        println!("  Checking locale: {}", check_locales[0]);
+    }
     println!("  Default namespace: {}", config.default_namespace);
+    if !filter_files.is_empty() {
+        println!("  Filter files: {:?}", filter_files);
+    }
+    if !filter_keys.is_empty() {
+        println!("  Filter keys: {:?}", filter_keys);
+    }
     println!();
 
     // First, extract keys from source
     println!("Extracting keys from source files...");
-    let plural_config = config.plural_config();
-    let extraction = extractor::extract_from_glob_with_options(
-        &config.input,
-        &config.ignore,
-        &config.functions,
-        config.extract_from_comments,
-        &plural_config,
-        &config.trans_components,
-        &config.trans_keep_basic_html_nodes_for,
-        &config.use_translation_names,
-        &config.nesting_prefix,
-        &config.nesting_suffix,
-        &config.nesting_options_separator,
-        &config.interpolation_prefix,
-        &config.interpolation_suffix,
-    )?;
+    let input = if filter_files.is_empty() {
+        config.input.clone()
+    } else {
+        extractor::filter_input_files(&config.input, filter_files)?
+    };
+    if input.is_empty() {
+        println!("No source files match --filter-file.");
+        return Ok(());
+    }
+    let mut extraction =
+        extractor::extract_with_config(config, &input, &config.extraction_ignore())?;
+    key_transforms::apply(config, &mut extraction.files);
 
     let mut all_keys: Vec<ExtractedKey> = Vec::new();
     for (_file_path, keys) in &extraction.files {
@@ -47,21 +125,395 @@ pub fn run(config: &Config, remove: bool, dry_run: bool, locale: Option<String>)
     }
 
     println!("  Found {} keys in source code", all_keys.len());
+    let budget_violations = budgets::check_budgets(config, &all_keys);
+    let namespace_issues = namespaces::check_namespaces(config, &extraction.files)?;
 
-    // Find dead keys
+    // Find dead keys in each locale root against the keys synced into it
     println!("\nScanning for dead keys...");
-    let locales_path = Path::new(&config.output);
-    let dead_keys = cleanup::find_dead_keys(
-        locales_path,
-        &all_keys,
-        config.effective_default_namespace(),
-        config.namespace_less_mode(),
-        config.merge_namespaces,
-        config.preserve_context_variants,
-        &config.context_separator,
-        check_locale,
+    let groups = json_sync::group_keys_by_output(
+        config,
+        extraction.files.iter().map(|(path, keys)| (path, keys)),
+        &config.output,
+    );
+    let roots: Vec<&str> = groups.keys().map(String::as_str).collect();
+    let store = LocaleStore::new();
+    for root in &roots {
+        store.preload_locales(config, root, &check_locales)?;
+    }
+    let mut dead_keys = Vec::new();
+    let mut locale_of_file: HashMap<String, String> = HashMap::new();
+    for ((root, keys), check_locale) in groups
+        .iter()
+        .flat_map(|group| check_locales.iter().map(move |locale| (group, locale)))
+    {
+        for dk in cleanup::find_dead_keys_with_store(
+            &store,
+            Path::new(root),
+            keys,
+            config.effective_default_namespace(),
+            config.namespace_less_mode(),
+            config.merge_namespaces,
+            config.merged_namespace_prefix(),
+            config.sharded_locale_files(),
+            config.preserve_context_variants,
+            &config.context_separator,
+            &config.plural_separator,
+            check_locale,
+            config.path_style,
+        )? {
+            if scope.in_scope(&dk.namespace, &dk.key_path) {
+                locale_of_file.insert(dk.file_path.clone(), check_locale.clone());
+                dead_keys.push(dk);
+            }
+        }
+    }
+    let (dead_keys, suggestions) = cleanup::triage_dead_keys(
+        dead_keys,
+        &config.preserve_patterns,
+        &extraction.dynamic_keys,
+        &config.ns_separator,
     )?;
 
+    let today = metadata::today();
+    let last_seen_days = |dk: &cleanup::DeadKey| {
+        lifecycle
+            .as_ref()?
+            .get(&dk.namespace, &dk.key_path)?
+            .last_seen
+            .as_deref()
+            .and_then(|last_seen| metadata::days_between(last_seen, &today))
+    };
+    let dead_keys = match dead_for {
+        Some(days) => {
+            let (old, recent): (Vec<_>, Vec<_>) = dead_keys
+                .into_iter()
+                .partition(|dk| last_seen_days(dk).is_some_and(|age| age >= i64::from(days)));
+            if !recent.is_empty() {
+                println!(
+                    "\nSkipping {} dead key(s) seen within the last {} day(s) or never recorded",
+                    recent.len(),
+                    days
+                );
+            }
+            old
+        }
+        None => dead_keys,
+    };
+    let ages: Vec<Option<i64>> = dead_keys.iter().map(last_seen_days).collect();
+
+    let locale_of = |dk: &cleanup::DeadKey| locale_of_file[&dk.file_path].as_str();
+
+    report_preserve_suggestions(
+        &suggestions,
+        write_config,
+        dry_run,
+        config_path,
+        config.path_style,
+    )?;
+    if all_locales {
+        report_structure_drift(&check_locales, &dead_keys, locale_of);
+    }
+    match emit_plan {
+        Some(path) => emit_dead_key_plan(&dead_keys, locale_of, path, config.path_style)?,
+        None => report_dead_keys(config, &roots, &dead_keys, &ages, remove, dry_run)?,
+    }
+    report_plural_forms(config, locale.as_deref(), fix_plurals, dry_run)?;
+    report_untracked_files(config)?;
+    namespaces::report_namespaces(config, &namespace_issues)?;
+    budgets::report_budgets(config, &budget_violations)
+}
+
+/// Validate tags and entities of translated values against the primary locale
+fn check_values(
+    config: &Config,
+    locale: Option<String>,
+    scope: &json_sync::PreserveMatcher,
+) -> Result<()> {
+    let locales: Vec<String> = locale.into_iter().collect();
+    println!(
+        "Checking translation markup against primary locale '{}'...",
+        config.primary_language()
+    );
+    let issues = markup::check_markup(config, &locales, scope)?;
+
+    let checked = if locales.is_empty() {
+        &config.locales
+    } else {
+        &locales
+    };
+    for locale in checked {
+        let locale_issues: Vec<&markup::MarkupIssue> = issues
+            .iter()
+            .filter(|issue| &issue.locale == locale)
+            .collect();
+        if locale_issues.is_empty() {
+            println!("\n[{}] OK", locale);
+            continue;
+        }
+        println!("\n[{}] {} issue(s):", locale, locale_issues.len());
+        for issue in locale_issues {
+            println!("  {}:{} {}", issue.namespace, issue.key, issue.problem);
+        }
+    }
+
+    if !issues.is_empty() {
+        bail!("{} translation value issue(s) found", issues.len());
+    }
+    println!("\nAll translation values have safe markup.");
+    Ok(())
+}
+
+/// Validate `$t(...)` references between translated values and their
+/// interpolation formatters
+fn check_nesting(
+    config: &Config,
+    locale: Option<String>,
+    scope: &json_sync::PreserveMatcher,
+) -> Result<()> {
+    let locales: Vec<String> = locale.into_iter().collect();
+    println!("Checking nested translations...");
+    let issues = nesting::check_nesting(config, &locales, scope)?;
+
+    let checked = if locales.is_empty() {
+        &config.locales
+    } else {
+        &locales
+    };
+    for locale in checked {
+        let locale_issues: Vec<&nesting::NestingIssue> = issues
+            .iter()
+            .filter(|issue| &issue.locale == locale)
+            .collect();
+        if locale_issues.is_empty() {
+            println!("\n[{}] OK", locale);
+            continue;
+        }
+        println!("\n[{}] {} issue(s):", locale, locale_issues.len());
+        for issue in locale_issues {
+            println!(
+                "  {}:{} {} ({})",
+                issue.namespace, issue.key, issue.problem, issue.file
+            );
+        }
+    }
+
+    if !issues.is_empty() {
+        bail!("{} nesting issue(s) found", issues.len());
+    }
+    println!("\nAll nested translations resolve without cycles.");
+    Ok(())
+}
+
+/// Report values whose whitespace or final punctuation differs from the
+/// primary locale; with `fix`, rewrite them to the normalized value
+fn check_spacing(
+    config: &Config,
+    locale: Option<String>,
+    scope: &json_sync::PreserveMatcher,
+    fix: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let locales: Vec<String> = locale.into_iter().collect();
+    println!(
+        "Checking whitespace and punctuation against primary locale '{}'...",
+        config.primary_language()
+    );
+    let issues = spacing::check_spacing(config, &locales, scope)?;
+
+    let checked = if locales.is_empty() {
+        &config.locales
+    } else {
+        &locales
+    };
+    for locale in checked {
+        if locale == config.primary_language() {
+            continue;
+        }
+        let locale_issues: Vec<&spacing::SpacingIssue> = issues
+            .iter()
+            .filter(|issue| &issue.locale == locale)
+            .collect();
+        if locale_issues.is_empty() {
+            println!("\n[{}] OK", locale);
+            continue;
+        }
+        println!("\n[{}] {} issue(s):", locale, locale_issues.len());
+        for issue in locale_issues {
+            let problems: Vec<String> = issue.problems.iter().map(|p| p.to_string()).collect();
+            println!(
+                "  {}:{} {}",
+                issue.namespace,
+                issue.key,
+                problems.join(", ")
+            );
+        }
+    }
+
+    if issues.is_empty() {
+        println!("\nAll translation values match the spacing of the primary locale.");
+        return Ok(());
+    }
+    if !fix {
+        bail!("{} spacing issue(s) found", issues.len());
+    }
+    for issue in &issues {
+        plan::set_value(
+            config,
+            &issue.locale,
+            &issue.namespace,
+            &issue.key,
+            &Value::String(issue.normalized.clone()),
+            dry_run,
+        )?;
+    }
+    if dry_run {
+        println!("\n[Dry run] Would normalize {} value(s)", issues.len());
+    } else {
+        println!("\nNormalized {} value(s)", issues.len());
+    }
+    Ok(())
+}
+
+/// Report keys breaking `keyCase` and spellings of one key that only differ in
+/// case; with `fix`, unify each key to its most used spelling in the locale
+/// files and the sources
+fn check_key_case(
+    config: &Config,
+    scope: &json_sync::PreserveMatcher,
+    fix: bool,
+    dry_run: bool,
+) -> Result<()> {
+    match config.key_case {
+        Some(policy) => println!("Checking key case (keyCase: {})...", policy.as_str()),
+        None => println!("Checking keys that only differ in case..."),
+    }
+    let mut extraction =
+        extractor::extract_with_config(config, &config.input, &config.extraction_ignore())?;
+    key_transforms::apply(config, &mut extraction.files);
+    let report = key_case::check_key_case(config, &extraction.files, scope)?;
+
+    let describe = |variant: &key_case::CaseVariant| {
+        format!(
+            "{} ({} file(s), {} locale(s))",
+            variant.key,
+            variant.files,
+            variant.locales.len()
+        )
+    };
+    if !report.conflicts.is_empty() {
+        println!("\n{} key(s) only differ in case:", report.conflicts.len());
+        for conflict in &report.conflicts {
+            let others: Vec<String> = conflict.others.iter().map(describe).collect();
+            println!(
+                "  {}:{} <- {}",
+                conflict.namespace,
+                describe(&conflict.dominant),
+                others.join(", ")
+            );
+        }
+    }
+    if let (Some(policy), false) = (config.key_case, report.violations.is_empty()) {
+        println!(
+            "\n{} key(s) break keyCase '{}':",
+            report.violations.len(),
+            policy.as_str()
+        );
+        for violation in &report.violations {
+            println!(
+                "  {}:{} (segment '{}')",
+                violation.namespace, violation.key, violation.segment
+            );
+        }
+    }
+    if report.conflicts.is_empty() && report.violations.is_empty() {
+        println!("\nNo key case issues found.");
+        return Ok(());
+    }
+
+    let mut remaining = report.violations.len();
+    if fix {
+        unify_key_case(config, &report.conflicts, dry_run)?;
+    } else {
+        remaining += report.conflicts.len();
+    }
+    if remaining > 0 {
+        bail!("{} key case issue(s) found", remaining);
+    }
+    Ok(())
+}
+
+/// Move the values of the other spellings to the dominant one (keeping its own
+/// translation where it has one), delete them and rename them in the sources
+fn unify_key_case(
+    config: &Config,
+    conflicts: &[key_case::CaseConflict],
+    dry_run: bool,
+) -> Result<()> {
+    println!("\nUnifying spellings...");
+    let mut values = HashMap::new();
+    for locale in &config.locales {
+        let namespaces = json_sync::load_locale_namespaces(config, &config.output, locale)?;
+        values.insert(
+            locale.as_str(),
+            json_sync::flatten_leaf_values(&namespaces, &config.key_separator),
+        );
+    }
+    let qualified = |namespace: &str, key: &str| {
+        if namespace == config.effective_default_namespace() {
+            key.to_string()
+        } else {
+            format!("{}{}{}", namespace, config.ns_separator, key)
+        }
+    };
+
+    let mut unified = 0;
+    for conflict in conflicts {
+        let namespace = conflict.namespace.as_str();
+        let target = conflict.dominant.key.as_str();
+        for other in &conflict.others {
+            println!("  {}:{} -> {}", namespace, other.key, target);
+            for locale in &other.locales {
+                let locale_values = &values[locale.as_str()];
+                let kept = locale_values.get(&(namespace.to_string(), target.to_string()));
+                let moved = locale_values.get(&(namespace.to_string(), other.key.clone()));
+                if let Some(moved) = moved {
+                    if kept.is_none_or(|kept| kept.as_str() == Some("")) {
+                        plan::set_value(config, locale, namespace, target, moved, dry_run)?;
+                    }
+                }
+                plan::delete_key(
+                    config,
+                    std::slice::from_ref(locale),
+                    namespace,
+                    &other.key,
+                    dry_run,
+                )?;
+            }
+            rename_key::rename_in_sources(
+                config,
+                &qualified(namespace, &other.key),
+                &qualified(namespace, target),
+                dry_run,
+            )?;
+            unified += 1;
+        }
+    }
+    if dry_run {
+        println!("\n[Dry run] Would unify {} spelling(s)", unified);
+    } else {
+        println!("\nUnified {} spelling(s)", unified);
+    }
+    Ok(())
+}
+
+fn report_dead_keys(
+    config: &Config,
+    roots: &[&str],
+    dead_keys: &[cleanup::DeadKey],
+    ages: &[Option<i64>],
+    remove: bool,
+    dry_run: bool,
+) -> Result<()> {
     if dead_keys.is_empty() {
         println!("\nNo dead keys found. All translation keys are in use!");
         return Ok(());
@@ -70,8 +522,14 @@ pub fn run(config: &Config, remove: bool, dry_run: bool, locale: Option<String>)
     println!("\nFound {} dead key(s):", dead_keys.len());
     println!("{}", "-".repeat(60));
 
-    for dk in &dead_keys {
-        println!("  [{}] {} -> {}", dk.namespace, dk.key_path, dk.file_path);
+    for (dk, age) in dead_keys.iter().zip(ages) {
+        match age {
+            Some(days) => println!(
+                "  [{}] {} -> {} (last seen {} day(s) ago)",
+                dk.namespace, dk.key_path, dk.file_path, days
+            ),
+            None => println!("  [{}] {} -> {}", dk.namespace, dk.key_path, dk.file_path),
+        }
     }
 
     println!("{}", "-".repeat(60));
@@ -83,8 +541,35 @@ pub fn run(config: &Config, remove: bool, dry_run: bool, locale: Option<String>)
             return Ok(());
         }
         println!("\nRemoving dead keys...");
-        let removed = cleanup::purge_dead_keys(locales_path, &dead_keys)?;
+        let (removed, kept) = cleanup::purge_expired_dead_keys(config, roots, dead_keys)?;
         println!("  Removed {} key(s)", removed);
+        if !kept.is_empty() {
+            println!("  Kept {} key(s) within removalGraceDays", kept.len());
+        }
+        summary::add_keys(0, removed);
+        if config.remove_empty_files {
+            let files: BTreeSet<PathBuf> = dead_keys
+                .iter()
+                .map(|dk| PathBuf::from(&dk.file_path))
+                .collect();
+            let files: Vec<PathBuf> = files.into_iter().collect();
+            let empty = cleanup::empty_locale_files(config, &files)?;
+            // Each file belongs to the innermost root holding it
+            let mut deleted = Vec::new();
+            for root in roots {
+                let in_root: Vec<PathBuf> = empty
+                    .iter()
+                    .filter(|path| cleanup::root_of(roots, path) == Some(*root))
+                    .cloned()
+                    .collect();
+                deleted.extend(cleanup::delete_empty_files(
+                    Path::new(root),
+                    &in_root,
+                    false,
+                )?);
+            }
+            report_deleted_files(&deleted, false, config.path_style);
+        }
     } else if dry_run {
         println!("\n[Dry run] Would remove {} key(s)", dead_keys.len());
         println!("Run with --remove (without --dry-run) to actually remove them.");
@@ -96,6 +581,294 @@ pub fn run(config: &Config, remove: bool, dry_run: bool, locale: Option<String>)
     Ok(())
 }
 
+/// List locale files and directories deleted by `removeEmptyFiles`
+pub(crate) fn report_deleted_files(deleted: &[PathBuf], dry_run: bool, path_style: PathStyle) {
+    if deleted.is_empty() {
+        return;
+    }
+    println!("\nEmpty locale files (removeEmptyFiles):");
+    for path in deleted {
+        println!(
+            "  {} {}",
+            if dry_run { "Would delete" } else { "Deleted" },
+            paths::display(path, path_style)
+        );
+    }
+}
+
+/// Dead keys of each `(namespace, key)` grouped with the locales they were found in
+fn dead_key_locales<'a>(
+    dead_keys: &'a [cleanup::DeadKey],
+    locale_of: impl Fn(&cleanup::DeadKey) -> &'a str,
+) -> BTreeMap<(&'a str, &'a str), BTreeSet<&'a str>> {
+    let mut grouped: BTreeMap<(&str, &str), BTreeSet<&str>> = BTreeMap::new();
+    for dk in dead_keys {
+        grouped
+            .entry((dk.namespace.as_str(), dk.key_path.as_str()))
+            .or_default()
+            .insert(locale_of(dk));
+    }
+    grouped
+}
+
+/// List dead keys present in the locale files of some checked locales only;
+/// the files of the other locales have drifted in structure
+fn report_structure_drift<'a>(
+    locales: &[String],
+    dead_keys: &'a [cleanup::DeadKey],
+    locale_of: impl Fn(&cleanup::DeadKey) -> &'a str,
+) {
+    let drifted: Vec<_> = dead_key_locales(dead_keys, locale_of)
+        .into_iter()
+        .filter(|(_, found)| found.len() < locales.len())
+        .collect();
+    if drifted.is_empty() {
+        return;
+    }
+
+    println!(
+        "\n{} dead key(s) exist in some locales only (structure drift):",
+        drifted.len()
+    );
+    println!("{}", "-".repeat(60));
+    for ((namespace, key), found) in drifted {
+        let missing: Vec<&str> = locales
+            .iter()
+            .map(String::as_str)
+            .filter(|locale| !found.contains(locale))
+            .collect();
+        println!(
+            "  [{}] {} -> in {}; missing in {}",
+            namespace,
+            key,
+            found.into_iter().collect::<Vec<_>>().join(", "),
+            missing.join(", ")
+        );
+    }
+    println!("{}", "-".repeat(60));
+}
+
+/// Write a plan deleting `dead_keys` from the locales they were found in
+/// instead of removing them
+fn emit_dead_key_plan<'a>(
+    dead_keys: &'a [cleanup::DeadKey],
+    locale_of: impl Fn(&cleanup::DeadKey) -> &'a str,
+    path: &Path,
+    path_style: PathStyle,
+) -> Result<()> {
+    println!("\nFound {} dead key(s)", dead_keys.len());
+    let changes = dead_key_locales(dead_keys, locale_of)
+        .into_iter()
+        .map(|((namespace, key), locales)| Change::DeleteKey {
+            key: format!("{}:{}", namespace, key),
+            locales: locales.into_iter().map(str::to_string).collect(),
+        })
+        .collect();
+    ChangePlan::new(changes).emit(path, path_style)
+}
+
+/// Check plural families of `locale` (all locales when `None`) against their CLDR
+/// categories and, with `fix`, add missing forms copied from `_other`.
+/// Warn about files in the locales directory that the config never reads, such
+/// as a leftover `en-US/` after a locale was renamed
+fn report_untracked_files(config: &Config) -> Result<()> {
+    let untracked = json_sync::untracked_locale_files(config, &config.output)?;
+    if untracked.is_empty() {
+        return Ok(());
+    }
+    println!(
+        "\n{} untracked file(s) in {} (never read or written):",
+        untracked.len(),
+        config.output
+    );
+    for file in &untracked {
+        println!(
+            "  {}: {}",
+            paths::display(&file.path, config.path_style),
+            file.reason
+        );
+    }
+    Ok(())
+}
+
+fn report_plural_forms(
+    config: &Config,
+    locale: Option<&str>,
+    fix: bool,
+    dry_run: bool,
+) -> Result<()> {
+    if config.disable_plurals || config.plural_compat.is_legacy() {
+        return Ok(());
+    }
+    let locales: Vec<&str> = match locale {
+        Some(locale) => vec![locale],
+        None => config.locales.iter().map(String::as_str).collect(),
+    };
+    let key_separator = if config.key_separator.is_empty() {
+        "."
+    } else {
+        config.key_separator.as_str()
+    };
+    let format = config.output_format();
+
+    println!("\nChecking plural forms against CLDR rules...");
+    let (mut missing, mut unexpected) = (0, 0);
+    for locale in locales {
+        for path in json_sync::locale_files(config, &config.output, locale)? {
+            let content = crate::fs::read_to_string_shared(&path)
+                .with_context(|| format!("Failed to read locale file: {}", path.display()))?;
+            if content.trim().is_empty() {
+                continue;
+            }
+            let Value::Object(mut map) =
+                json_sync::parse_locale_value_str(&content, format, &path)?
+            else {
+                continue;
+            };
+            let issues = plurals::check_plural_forms(
+                &mut map,
+                locale,
+                &config.plural_separator,
+                key_separator,
+                fix,
+            );
+            let mut file_missing = 0;
+            for issue in &issues {
+                if !issue.missing.is_empty() {
+                    println!(
+                        "  [{}] {}: missing {} ({})",
+                        locale,
+                        issue.key,
+                        issue.missing.join(", "),
+                        paths::display(&path, config.path_style)
+                    );
+                }
+                if !issue.unexpected.is_empty() {
+                    println!(
+                        "  [{}] {}: {} not a plural form of '{}' ({})",
+                        locale,
+                        issue.key,
+                        issue.unexpected.join(", "),
+                        locale,
+                        paths::display(&path, config.path_style)
+                    );
+                }
+                file_missing += issue.missing.len();
+                unexpected += issue.unexpected.len();
+            }
+            if fix && !dry_run && file_missing > 0 {
+                let style = json_sync::detect_json_style(&content);
+                json_sync::write_locale_file(&path, &map, format, config.key_sort, Some(&style))?;
+            }
+            missing += file_missing;
+        }
+    }
+
+    if missing == 0 && unexpected == 0 {
+        println!("  All plural families match the CLDR categories of their locale.");
+        return Ok(());
+    }
+    if missing > 0 {
+        if fix && dry_run {
+            println!(
+                "[Dry run] Would add {} missing plural form(s) copied from _other",
+                missing
+            );
+        } else if fix {
+            println!(
+                "Added {} missing plural form(s) copied from _other",
+                missing
+            );
+        } else {
+            println!("Run with --fix-plurals to add missing forms copied from _other.");
+        }
+    }
+    if unexpected > 0 {
+        println!(
+            "{} form(s) are never used at runtime for their locale; remove them manually.",
+            unexpected
+        );
+    }
+    Ok(())
+}
+
+/// List keys kept alive by dynamic template literal keys and, with `--write-config`,
+/// add the covering patterns to the config's `preservePatterns`.
+fn report_preserve_suggestions(
+    suggestions: &[cleanup::PreserveSuggestion],
+    write_config: bool,
+    dry_run: bool,
+    config_path: Option<&Path>,
+    path_style: PathStyle,
+) -> Result<()> {
+    if suggestions.is_empty() {
+        return Ok(());
+    }
+
+    let covered: usize = suggestions.iter().map(|s| s.keys.len()).sum();
+    println!(
+        "\n{} unused-looking key(s) may be produced by dynamic keys:",
+        covered
+    );
+    println!("{}", "-".repeat(60));
+    for suggestion in suggestions {
+        println!(
+            "  {} ({}) covers {} key(s)",
+            suggestion.pattern,
+            suggestion.sites.join(", "),
+            suggestion.keys.len()
+        );
+        for dk in &suggestion.keys {
+            println!("    [{}] {}", dk.namespace, dk.key_path);
+        }
+    }
+    println!("{}", "-".repeat(60));
+
+    let patterns: Vec<&str> = suggestions.iter().map(|s| s.pattern.as_str()).collect();
+    if !write_config {
+        println!("Suggested \"preservePatterns\": {:?}", patterns);
+        println!("Run with --write-config to add them to your config file.");
+        return Ok(());
+    }
+
+    let Some(path) = config_path.filter(|path| {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+    }) else {
+        bail!(
+            "--write-config needs a JSON config file; add {:?} to \"preservePatterns\" manually",
+            patterns
+        );
+    };
+    if dry_run {
+        println!(
+            "[Dry run] Would add {} pattern(s) to \"preservePatterns\" in {}",
+            patterns.len(),
+            paths::display(path, path_style)
+        );
+        return Ok(());
+    }
+    let added = add_preserve_patterns(path, &patterns)?;
+    println!(
+        "Added {} pattern(s) to \"preservePatterns\" in {}",
+        added,
+        paths::display(path, path_style)
+    );
+    Ok(())
+}
+
+/// Append `patterns` missing from the config file's `preservePatterns`,
+/// keeping the rest of the file as written
+fn add_preserve_patterns(path: &Path, patterns: &[&str]) -> Result<usize> {
+    let mut file = ConfigFile::read(path)?;
+    let added = file.push_strings("preservePatterns", patterns)?;
+    if added > 0 {
+        file.write()?;
+    }
+    Ok(added)
+}
+
 fn confirm_removal(count: usize) -> bool {
     println!(
         "\nThis will permanently remove {} key(s) from your locale files.",
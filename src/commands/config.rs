@@ -0,0 +1,138 @@
+use anyhow::Result;
+use serde_json::Value;
+use std::path::Path;
+
+use crate::config::{Config, ConfigSource, Preset, ENV_PREFIX};
+use crate::paths;
+
+/// Print the config file layer, or with `resolved` the final config after
+/// environment and `--set` overrides, annotated with the layer of each value.
+pub fn show(
+    config: &Config,
+    file_config: &Config,
+    source_path: Option<&Path>,
+    file_keys: &[String],
+    overrides: &[(String, ConfigSource)],
+    resolved: bool,
+) -> Result<()> {
+    if !resolved {
+        let mut value = serde_json::to_value(file_config)?;
+        mask_secrets(&mut value);
+        println!("{}", serde_json::to_string_pretty(&value)?);
+        return Ok(());
+    }
+
+    println!("=== i18next-turbo config ===\n");
+    match source_path {
+        Some(path) => println!("Config file: {}", paths::display(path, config.path_style)),
+        None => println!("Config file: none"),
+    }
+    println!(
+        "Precedence: default < preset < config file < environment ({}*) < --set < command flags\n",
+        ENV_PREFIX
+    );
+
+    let Value::Object(mut values) = serde_json::to_value(config)? else {
+        return Ok(());
+    };
+    let Value::Object(defaults) = serde_json::to_value(Config::default())? else {
+        return Ok(());
+    };
+    let Value::Object(file_values) = serde_json::to_value(file_config)? else {
+        return Ok(());
+    };
+    for value in values.values_mut() {
+        mask_secrets(value);
+    }
+
+    let preset = file_config.preset.map(|preset| (preset, preset.defaults()));
+    for (key, value) in &values {
+        let source = value_source(
+            key,
+            overrides,
+            file_keys,
+            preset.as_ref(),
+            &file_values,
+            &defaults,
+        );
+        println!("  {}: {}  ({})", key, value, source);
+    }
+
+    Ok(())
+}
+
+/// Highest-precedence layer that set `key`
+fn value_source(
+    key: &str,
+    overrides: &[(String, ConfigSource)],
+    file_keys: &[String],
+    preset: Option<&(Preset, serde_json::Map<String, Value>)>,
+    file_values: &serde_json::Map<String, Value>,
+    defaults: &serde_json::Map<String, Value>,
+) -> ConfigSource {
+    if let Some((_, source)) = overrides.iter().rev().find(|(name, _)| name == key) {
+        return source.clone();
+    }
+    if file_keys.iter().any(|name| name == key) {
+        return ConfigSource::File;
+    }
+    if let Some((preset, _)) = preset.filter(|(_, keys)| keys.contains_key(key)) {
+        return ConfigSource::Preset(*preset);
+    }
+    if file_values.get(key) != defaults.get(key) {
+        return ConfigSource::Detected;
+    }
+    ConfigSource::Default
+}
+
+/// Hide credentials (e.g. `locize.apiKey`) from printed output
+pub(crate) fn mask_secrets(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, nested) in map.iter_mut() {
+                if key == "apiKey" && nested.is_string() {
+                    *nested = Value::String("***".to_string());
+                } else {
+                    mask_secrets(nested);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(mask_secrets),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn value_source_prefers_latest_override() {
+        let overrides = vec![
+            (
+                "output".to_string(),
+                ConfigSource::Env("I18NEXT_TURBO_OUTPUT".to_string()),
+            ),
+            ("output".to_string(), ConfigSource::Cli),
+        ];
+        let file_keys = vec!["locales".to_string()];
+        let file_values = json!({ "output": "locales", "locales": ["en"], "input": ["app/**"] });
+        let defaults = json!({ "output": "locales", "locales": ["en"], "input": ["src/**"] });
+        let (Value::Object(file_values), Value::Object(defaults)) = (file_values, defaults) else {
+            unreachable!()
+        };
+
+        let preset = (Preset::Nextjs, Preset::Nextjs.defaults());
+        let source = |key, preset| {
+            value_source(key, &overrides, &file_keys, preset, &file_values, &defaults)
+        };
+        assert_eq!(source("output", None), ConfigSource::Cli);
+        assert_eq!(source("locales", None), ConfigSource::File);
+        assert_eq!(source("input", None), ConfigSource::Detected);
+        assert_eq!(
+            source("input", Some(&preset)),
+            ConfigSource::Preset(Preset::Nextjs)
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use anyhow::{bail, Result};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::config::Config;
+use crate::daemon::{self, Daemon, DaemonRequest, DaemonResponse};
+use crate::paths;
+
+fn resolve_socket(socket: Option<PathBuf>) -> PathBuf {
+    socket.unwrap_or_else(|| PathBuf::from(daemon::DEFAULT_SOCKET_PATH))
+}
+
+pub fn start(config: &Config, socket: Option<PathBuf>) -> Result<()> {
+    println!("=== i18next-turbo daemon ===\n");
+
+    let socket_path = resolve_socket(socket);
+    let started = Instant::now();
+    let mut daemon = Daemon::new(config.clone());
+    daemon.build_index()?;
+
+    println!("Index ready in {:?}", started.elapsed());
+    println!("  Files: {}", daemon.file_count());
+    println!("  Keys: {}", daemon.key_count());
+    println!(
+        "Listening on {} (Ctrl+C or `daemon stop` to exit)\n",
+        paths::display(&socket_path, config.path_style)
+    );
+
+    daemon::serve(&mut daemon, &socket_path)?;
+    println!("Daemon stopped.");
+    Ok(())
+}
+
+pub fn stop(socket: Option<PathBuf>) -> Result<()> {
+    let socket_path = resolve_socket(socket);
+    request(&socket_path, DaemonRequest::Shutdown)?;
+    println!("Daemon stopped.");
+    Ok(())
+}
+
+pub fn ping(socket: Option<PathBuf>) -> Result<()> {
+    let socket_path = resolve_socket(socket);
+    if let DaemonResponse::Pong { files, keys } = request(&socket_path, DaemonRequest::Ping)? {
+        println!(
+            "Daemon is running: {} file(s), {} key(s) indexed",
+            files, keys
+        );
+    }
+    Ok(())
+}
+
+pub fn extract_changed(socket: Option<PathBuf>, files: Vec<String>, dry_run: bool) -> Result<()> {
+    let socket_path = resolve_socket(socket);
+    let response = request(
+        &socket_path,
+        DaemonRequest::ExtractChanged { files, dry_run },
+    )?;
+    if let DaemonResponse::Extracted {
+        files,
+        added_keys,
+        removed_keys,
+    } = response
+    {
+        let (added, removed) = if dry_run {
+            ("would add", "would remove")
+        } else {
+            ("added", "removed")
+        };
+        println!(
+            "{} file(s) re-extracted: {} {} key(s), {} {} key(s)",
+            files, added, added_keys, removed, removed_keys
+        );
+    }
+    Ok(())
+}
+
+pub fn query(socket: Option<PathBuf>, key: String) -> Result<()> {
+    let socket_path = resolve_socket(socket);
+    if let DaemonResponse::KeyLocations {
+        key,
+        namespace,
+        files,
+    } = request(&socket_path, DaemonRequest::QueryKey { key })?
+    {
+        if files.is_empty() {
+            println!("{}:{} is not used in any source file", namespace, key);
+        } else {
+            println!("{}:{} is used in:", namespace, key);
+            for file in files {
+                println!("  {}", file);
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn lint(socket: Option<PathBuf>, path: String, fail_on_error: bool) -> Result<()> {
+    let socket_path = resolve_socket(socket);
+    let response = request(&socket_path, DaemonRequest::LintFile { path: path.clone() })?;
+    if let DaemonResponse::LintIssues { issues } = response {
+        for issue in &issues {
+            println!("{}:{}:{} {}", path, issue.line, issue.column, issue.message);
+        }
+        if fail_on_error && !issues.is_empty() {
+            bail!(
+                "{} lint issue(s) found (--fail-on-error enabled)",
+                issues.len()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn request(socket_path: &Path, request: DaemonRequest) -> Result<DaemonResponse> {
+    match daemon::send_request(socket_path, &request)? {
+        DaemonResponse::Error { message } => bail!("Daemon error: {}", message),
+        response => Ok(response),
+    }
+}
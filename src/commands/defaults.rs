@@ -0,0 +1,377 @@
+use anyhow::{bail, Context, Result};
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::extractor::{self, DefaultValue, ExtractedKey, KeySource};
+use crate::json_sync;
+use crate::key_transforms;
+
+/// A `defaultValue` in source that differs from the primary-locale value
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Drift {
+    namespace: String,
+    key: String,
+    file_path: String,
+    code: String,
+    catalog: String,
+}
+
+/// List keys whose string `defaultValue` in source differs from the primary
+/// locale. With `prefer_code` the catalog takes the source defaults (like
+/// `extract --update-defaults`); with `prefer_catalog` the default literals of
+/// the drifting calls are rewritten to the catalog values.
+pub fn run(config: &Config, prefer_code: bool, prefer_catalog: bool, dry_run: bool) -> Result<()> {
+    println!("=== i18next-turbo defaults ===\n");
+    if prefer_code && prefer_catalog {
+        bail!("--prefer-code and --prefer-catalog cannot be combined");
+    }
+    let primary = config.primary_language();
+    println!("  Primary locale: {}", primary);
+
+    let mut extraction =
+        extractor::extract_with_config(config, &config.input, &config.extraction_ignore())?;
+    key_transforms::apply(config, &mut extraction.files);
+
+    // Files are grouped by the locale root they sync into
+    let mut by_output: BTreeMap<String, Vec<(String, Vec<ExtractedKey>)>> = BTreeMap::new();
+    for (file_path, keys) in extraction.files {
+        let output = config
+            .output_target_for(Path::new(&file_path))
+            .unwrap_or(&config.output)
+            .to_string();
+        by_output.entry(output).or_default().push((file_path, keys));
+    }
+
+    let mut drifts: Vec<(String, Drift)> = Vec::new();
+    for (output, files) in &by_output {
+        let namespaces = json_sync::load_locale_namespaces(config, output, primary)?;
+        let catalog = json_sync::flatten_string_values(&namespaces, &config.key_separator);
+        drifts.extend(
+            find_drift(config, files, &catalog)
+                .into_iter()
+                .map(|drift| (output.clone(), drift)),
+        );
+    }
+
+    if drifts.is_empty() {
+        println!("\nAll source defaults match the primary locale.");
+        return Ok(());
+    }
+    println!();
+    for (_, drift) in &drifts {
+        println!("  [{}] {}", drift.namespace, drift.key);
+        println!("    code:    {:?} ({})", drift.code, drift.file_path);
+        println!("    catalog: {:?}", drift.catalog);
+    }
+
+    if prefer_code {
+        let mut updated = 0;
+        for (output, drifts) in group_by_output(&drifts) {
+            let keys: Vec<ExtractedKey> = drifts
+                .iter()
+                .map(|drift| ExtractedKey {
+                    key: drift.key.clone(),
+                    namespace: Some(drift.namespace.clone()),
+                    default_value: Some(drift.code.clone().into()),
+                })
+                .collect();
+            updated +=
+                json_sync::update_default_values(config, &keys, output, false, dry_run)?.len();
+        }
+        println!(
+            "\n{} {} primary-locale value(s) from source defaults",
+            if dry_run { "Would update" } else { "Updated" },
+            updated
+        );
+        return Ok(());
+    }
+    if prefer_catalog {
+        return rewrite_sources(config, &drifts, &extraction.key_sources, dry_run);
+    }
+    bail!(
+        "{} default value mismatch(es); reconcile with --prefer-code or --prefer-catalog",
+        drifts.len()
+    );
+}
+
+/// Source defaults that differ from non-empty catalog values. Plural forms are
+/// skipped: one `defaultValue` feeds every form, which translators then refine.
+fn find_drift(
+    config: &Config,
+    files: &[(String, Vec<ExtractedKey>)],
+    catalog: &BTreeMap<(String, String), String>,
+) -> Vec<Drift> {
+    let plural = config.plural_config();
+    let plural_suffixes: Vec<String> = plural
+        .suffixes
+        .iter()
+        .filter(|suffix| !suffix.is_empty())
+        .map(|suffix| format!("{}{}", plural.separator, suffix))
+        .collect();
+    let default_namespace = config.effective_default_namespace();
+
+    let mut drifts = BTreeSet::new();
+    for (file_path, keys) in files {
+        for key in keys {
+            let Some(code) = key.default_value.as_ref().and_then(|value| value.as_str()) else {
+                continue;
+            };
+            if plural_suffixes
+                .iter()
+                .any(|suffix| key.key.ends_with(suffix.as_str()))
+            {
+                continue;
+            }
+            let namespace = key.namespace.as_deref().unwrap_or(default_namespace);
+            let Some(value) = catalog.get(&(namespace.to_string(), key.key.clone())) else {
+                continue;
+            };
+            if value.is_empty() || value == code {
+                continue;
+            }
+            drifts.insert(Drift {
+                namespace: namespace.to_string(),
+                key: key.key.clone(),
+                file_path: file_path.clone(),
+                code: code.to_string(),
+                catalog: value.clone(),
+            });
+        }
+    }
+    drifts.into_iter().collect()
+}
+
+fn group_by_output(drifts: &[(String, Drift)]) -> BTreeMap<&str, Vec<&Drift>> {
+    let mut groups: BTreeMap<&str, Vec<&Drift>> = BTreeMap::new();
+    for (output, drift) in drifts {
+        groups.entry(output.as_str()).or_default().push(drift);
+    }
+    groups
+}
+
+/// Rewrite the default literals of `drifts` in their source files, only inside
+/// the calls the extractor found each drifting key in
+fn rewrite_sources(
+    config: &Config,
+    drifts: &[(String, Drift)],
+    sources: &[KeySource],
+    dry_run: bool,
+) -> Result<()> {
+    let default_namespace = config.effective_default_namespace();
+    let mut by_file: BTreeMap<&str, Vec<(&Drift, Vec<&KeySource>)>> = BTreeMap::new();
+    for (_, drift) in drifts {
+        let calls = sources
+            .iter()
+            .filter(|source| {
+                !source.from_comment
+                    && source.file_path == drift.file_path
+                    && source.namespace.as_deref().unwrap_or(default_namespace) == drift.namespace
+                    && source.default_value.as_ref().and_then(DefaultValue::as_str)
+                        == Some(drift.code.as_str())
+                    && key_transforms::transform_key(
+                        &config.key_transforms,
+                        &source.key,
+                        &config.key_separator,
+                    ) == drift.key
+            })
+            .collect();
+        by_file
+            .entry(drift.file_path.as_str())
+            .or_default()
+            .push((drift, calls));
+    }
+
+    let (mut rewritten, mut skipped) = (0, Vec::new());
+    for (file_path, drifts) in by_file {
+        let path = Path::new(file_path);
+        let mut source = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read source file: {}", path.display()))?;
+        let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+        for (drift, calls) in drifts {
+            let mut found = false;
+            for call in calls {
+                // A call nested in one already rewritten is left alone
+                if edits
+                    .iter()
+                    .any(|(span, _)| span.start < call.span.end && call.span.start < span.end)
+                {
+                    continue;
+                }
+                let Some(text) = source.get(call.span.clone()) else {
+                    continue;
+                };
+                let key = call.generated_from.as_deref().unwrap_or(&call.key);
+                if let Some(updated) = rewrite_default(text, key, &drift.code, &drift.catalog) {
+                    edits.push((call.span.clone(), updated));
+                    found = true;
+                }
+            }
+            if !found {
+                skipped.push(drift);
+            }
+        }
+        if edits.is_empty() {
+            continue;
+        }
+        edits.sort_by_key(|(span, _)| std::cmp::Reverse(span.start));
+        for (span, text) in edits {
+            source.replace_range(span, &text);
+        }
+        rewritten += 1;
+        if !dry_run {
+            std::fs::write(path, &source)
+                .with_context(|| format!("Failed to write source file: {}", path.display()))?;
+        }
+    }
+
+    println!(
+        "\n{} defaults in {} source file(s) to the catalog values",
+        if dry_run { "Would rewrite" } else { "Rewrote" },
+        rewritten
+    );
+    if !skipped.is_empty() {
+        println!("Left {} default(s) to edit by hand:", skipped.len());
+        for drift in skipped {
+            println!(
+                "  [{}] {} ({})",
+                drift.namespace, drift.key, drift.file_path
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Replace the string literal `code` where it is the default of `key` in the
+/// text of a call: a `defaultValue:` option, a `defaults=` prop, or the
+/// argument after the key
+fn rewrite_default(source: &str, key: &str, code: &str, catalog: &str) -> Option<String> {
+    let mut output = String::with_capacity(source.len());
+    let mut rest = source;
+    let mut replaced = false;
+    'scan: while !rest.is_empty() {
+        for quote in ['\'', '"'] {
+            let literal = quoted(code, quote);
+            if !rest.starts_with(&literal) {
+                continue;
+            }
+            let before = output.trim_end();
+            let is_default = before
+                .strip_suffix(':')
+                .is_some_and(|before| before.trim_end().ends_with("defaultValue"))
+                || before
+                    .strip_suffix('{')
+                    .unwrap_or(before)
+                    .trim_end()
+                    .strip_suffix('=')
+                    .is_some_and(|before| before.trim_end().ends_with("defaults"))
+                || before.strip_suffix(',').is_some_and(|before| {
+                    let before = before.trim_end();
+                    ['\'', '"', '`'].iter().any(|key_quote| {
+                        before
+                            .strip_suffix(*key_quote)
+                            .is_some_and(|before| before.ends_with(key))
+                    })
+                });
+            if is_default {
+                output.push_str(&quoted(catalog, quote));
+                rest = &rest[literal.len()..];
+                replaced = true;
+                continue 'scan;
+            }
+        }
+        let next = rest.chars().next().map_or(1, char::len_utf8);
+        output.push_str(&rest[..next]);
+        rest = &rest[next..];
+    }
+    replaced.then_some(output)
+}
+
+/// `value` as a JS string literal in `quote`
+fn quoted(value: &str, quote: char) -> String {
+    let mut literal = String::with_capacity(value.len() + 2);
+    literal.push(quote);
+    for c in value.chars() {
+        match c {
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            c if c == quote => {
+                literal.push('\\');
+                literal.push(c);
+            }
+            c => literal.push(c),
+        }
+    }
+    literal.push(quote);
+    literal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drift_skips_matching_empty_and_plural_values() {
+        let key = |key: &str, default: &str| ExtractedKey {
+            key: key.to_string(),
+            namespace: None,
+            default_value: Some(default.into()),
+        };
+        let files = vec![(
+            "src/app.tsx".to_string(),
+            vec![
+                key("save", "Save changes"),
+                key("cancel", "Cancel"),
+                key("title", "Home"),
+                key("item_other", "item"),
+            ],
+        )];
+        let catalog: BTreeMap<(String, String), String> = [
+            ("save", "Save"),
+            ("cancel", "Cancel"),
+            ("title", ""),
+            ("item_other", "{{count}} items"),
+        ]
+        .into_iter()
+        .map(|(key, value)| {
+            (
+                ("translation".to_string(), key.to_string()),
+                value.to_string(),
+            )
+        })
+        .collect();
+
+        assert_eq!(
+            find_drift(&Config::default(), &files, &catalog),
+            vec![Drift {
+                namespace: "translation".to_string(),
+                key: "save".to_string(),
+                file_path: "src/app.tsx".to_string(),
+                code: "Save changes".to_string(),
+                catalog: "Save".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn rewrite_default_only_touches_default_positions() {
+        let source = r#"t('save', { defaultValue: 'Save changes' });
+t("save", "Save changes");
+<Trans i18nKey="save" defaults="Save changes" />
+label('Save changes');
+"#;
+        assert_eq!(
+            rewrite_default(source, "save", "Save changes", "Don't save").unwrap(),
+            r#"t('save', { defaultValue: 'Don\'t save' });
+t("save", "Don't save");
+<Trans i18nKey="save" defaults="Don't save" />
+label('Save changes');
+"#
+        );
+        assert_eq!(
+            rewrite_default("label('Save changes')", "save", "Save changes", "Save"),
+            None
+        );
+    }
+}
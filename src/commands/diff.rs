@@ -0,0 +1,289 @@
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use crate::config::{Config, PathStyle};
+use crate::json_sync;
+use crate::paths;
+
+/// Output of `diff --format`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffFormat {
+    Text,
+    Json,
+    Lines,
+}
+
+impl DiffFormat {
+    pub fn parse_str(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "text" => Ok(DiffFormat::Text),
+            "json" => Ok(DiffFormat::Json),
+            "lines" => Ok(DiffFormat::Lines),
+            _ => bail!(
+                "Unsupported diff format '{}'. Use text, json or lines",
+                value
+            ),
+        }
+    }
+}
+
+/// How a key differs from the first tree to the second
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyChange {
+    Added(Value),
+    Removed(Value),
+    Changed { from: Value, to: Value },
+}
+
+/// A key that differs between two locale trees
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyDiff {
+    pub locale: String,
+    pub namespace: String,
+    pub key: String,
+    pub change: KeyChange,
+}
+
+/// Compare two locale roots laid out like the configured `output`
+pub fn run(config: &Config, dir_a: &Path, dir_b: &Path, format: &str) -> Result<()> {
+    let format = DiffFormat::parse_str(format)?;
+    let diffs = diff_trees(config, dir_a, dir_b)?;
+
+    match format {
+        DiffFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&diff_json(&diffs))?);
+        }
+        DiffFormat::Lines => print!("{}", diff_lines(&diffs)),
+        DiffFormat::Text => print_text(&diffs, dir_a, dir_b, config.path_style),
+    }
+    Ok(())
+}
+
+/// Keys added, removed or changed from `dir_a` to `dir_b`, sorted by locale,
+/// namespace and key
+pub fn diff_trees(config: &Config, dir_a: &Path, dir_b: &Path) -> Result<Vec<KeyDiff>> {
+    let locales: BTreeSet<String> = tree_locales(config, dir_a)?
+        .into_iter()
+        .chain(tree_locales(config, dir_b)?)
+        .collect();
+
+    let mut diffs = Vec::new();
+    for locale in locales {
+        let a = tree_values(config, dir_a, &locale)?;
+        let b = tree_values(config, dir_b, &locale)?;
+        let ids: BTreeSet<&(String, String)> = a.keys().chain(b.keys()).collect();
+        for id in ids {
+            let change = match (a.get(id), b.get(id)) {
+                (None, Some(to)) => KeyChange::Added(to.clone()),
+                (Some(from), None) => KeyChange::Removed(from.clone()),
+                (Some(from), Some(to)) if from != to => KeyChange::Changed {
+                    from: from.clone(),
+                    to: to.clone(),
+                },
+                _ => continue,
+            };
+            diffs.push(KeyDiff {
+                locale: locale.clone(),
+                namespace: id.0.clone(),
+                key: id.1.clone(),
+                change,
+            });
+        }
+    }
+    Ok(diffs)
+}
+
+/// Locales present in a locale root: sub-directories, or `<locale>.<ext>`
+/// files with `flatLocaleFiles`
+fn tree_locales(config: &Config, dir: &Path) -> Result<BTreeSet<String>> {
+    if !dir.is_dir() {
+        bail!("Locale directory not found: {}", dir.display());
+    }
+    let extension = config.output_extension();
+    let mut locales = BTreeSet::new();
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("Failed to read: {}", dir.display()))?
+    {
+        let path = entry?.path();
+        let is_locale = if config.flat_locale_files {
+            path.is_file() && path.extension().and_then(|e| e.to_str()) == Some(extension)
+        } else {
+            path.is_dir()
+        };
+        let name = if config.flat_locale_files {
+            path.file_stem()
+        } else {
+            path.file_name()
+        };
+        if let Some(name) = name.and_then(|n| n.to_str()).filter(|_| is_locale) {
+            if !name.starts_with('.') {
+                locales.insert(name.to_string());
+            }
+        }
+    }
+    Ok(locales)
+}
+
+fn tree_values(
+    config: &Config,
+    dir: &Path,
+    locale: &str,
+) -> Result<BTreeMap<(String, String), Value>> {
+    let namespaces = json_sync::load_locale_namespaces(config, &dir.to_string_lossy(), locale)?;
+    Ok(json_sync::flatten_leaf_values(
+        &namespaces,
+        &config.key_separator,
+    ))
+}
+
+/// `{ "summary": {...}, "locales": { locale: { namespace: { added, removed, changed } } } }`
+fn diff_json(diffs: &[KeyDiff]) -> Value {
+    let mut locales = json!({});
+    for diff in diffs {
+        let groups = &mut locales[&diff.locale][&diff.namespace];
+        if groups.is_null() {
+            *groups = json!({ "added": {}, "removed": {}, "changed": {} });
+        }
+        let (kind, value) = match &diff.change {
+            KeyChange::Added(value) => ("added", value.clone()),
+            KeyChange::Removed(value) => ("removed", value.clone()),
+            KeyChange::Changed { from, to } => ("changed", json!({ "from": from, "to": to })),
+        };
+        groups[kind][&diff.key] = value;
+    }
+
+    let (added, removed, changed) = count_changes(diffs);
+    json!({
+        "summary": { "added": added, "removed": removed, "changed": changed },
+        "locales": locales,
+    })
+}
+
+/// `-key: value` / `+key: value` lines under a `locale/namespace` heading per
+/// group. Keys are compared, not file lines, so this is no patch to apply.
+fn diff_lines(diffs: &[KeyDiff]) -> String {
+    let mut out = String::new();
+    let mut current: Option<(&str, &str)> = None;
+    for diff in diffs {
+        let group = (diff.locale.as_str(), diff.namespace.as_str());
+        if current != Some(group) {
+            current = Some(group);
+            out.push_str(&format!("{}/{}\n", diff.locale, diff.namespace));
+        }
+        match &diff.change {
+            KeyChange::Added(value) => out.push_str(&format!("+{}: {}\n", diff.key, value)),
+            KeyChange::Removed(value) => out.push_str(&format!("-{}: {}\n", diff.key, value)),
+            KeyChange::Changed { from, to } => {
+                out.push_str(&format!("-{}: {}\n+{}: {}\n", diff.key, from, diff.key, to))
+            }
+        }
+    }
+    out
+}
+
+fn print_text(diffs: &[KeyDiff], dir_a: &Path, dir_b: &Path, path_style: PathStyle) {
+    println!("=== i18next-turbo diff ===\n");
+    println!("  A: {}", paths::display(dir_a, path_style));
+    println!("  B: {}", paths::display(dir_b, path_style));
+
+    for group in diffs.chunk_by(|a, b| a.locale == b.locale && a.namespace == b.namespace) {
+        let (added, removed, changed) = count_changes(group);
+        println!(
+            "\n[{}] {}: {} added, {} removed, {} changed",
+            group[0].locale, group[0].namespace, added, removed, changed
+        );
+        for diff in group {
+            match &diff.change {
+                KeyChange::Added(value) => println!("  + {}: {}", diff.key, value),
+                KeyChange::Removed(value) => println!("  - {}: {}", diff.key, value),
+                KeyChange::Changed { from, to } => {
+                    println!("  ~ {}: {} -> {}", diff.key, from, to)
+                }
+            }
+        }
+    }
+
+    if diffs.is_empty() {
+        println!("\nNo differences.");
+    } else {
+        let (added, removed, changed) = count_changes(diffs);
+        println!(
+            "\nTotal: {} added, {} removed, {} changed",
+            added, removed, changed
+        );
+    }
+}
+
+fn count_changes(diffs: &[KeyDiff]) -> (usize, usize, usize) {
+    diffs
+        .iter()
+        .fold((0, 0, 0), |(added, removed, changed), diff| {
+            match diff.change {
+                KeyChange::Added(_) => (added + 1, removed, changed),
+                KeyChange::Removed(_) => (added, removed + 1, changed),
+                KeyChange::Changed { .. } => (added, removed, changed + 1),
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write(root: &Path, file: &str, content: &str) {
+        let path = root.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn diff_trees_reports_added_removed_and_changed_keys() {
+        let tmp = tempdir().unwrap();
+        let (a, b) = (tmp.path().join("a"), tmp.path().join("b"));
+        write(
+            &a,
+            "de/common.json",
+            r#"{"nav":{"home":"Start","old":"Alt"},"ok":"OK"}"#,
+        );
+        write(
+            &b,
+            "de/common.json",
+            r#"{"nav":{"home":"Startseite"},"ok":"OK","new":"Neu"}"#,
+        );
+        write(&b, "fr/common.json", r#"{"ok":"OK"}"#);
+
+        let diffs = diff_trees(&Config::default(), &a, &b).unwrap();
+        let summary: Vec<(&str, &str, &KeyChange)> = diffs
+            .iter()
+            .map(|d| (d.locale.as_str(), d.key.as_str(), &d.change))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "de",
+                    "nav.home",
+                    &KeyChange::Changed {
+                        from: json!("Start"),
+                        to: json!("Startseite")
+                    }
+                ),
+                ("de", "nav.old", &KeyChange::Removed(json!("Alt"))),
+                ("de", "new", &KeyChange::Added(json!("Neu"))),
+                ("fr", "ok", &KeyChange::Added(json!("OK"))),
+            ]
+        );
+
+        assert_eq!(
+            diff_lines(&diffs),
+            "de/common\n-nav.home: \"Start\"\n+nav.home: \"Startseite\"\n-nav.old: \"Alt\"\n+new: \"Neu\"\nfr/common\n+ok: \"OK\"\n"
+        );
+        assert_eq!(
+            diff_json(&diffs)["summary"],
+            json!({ "added": 2, "removed": 1, "changed": 1 })
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use anyhow::{bail, Result};
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::extractor;
+use crate::json_sync;
+use crate::paths;
+use crate::walk::{self, Skipped, SymlinkGuard};
+
+/// A problem with how source or locale files are laid out on disk
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Problem {
+    /// A symlink that is skipped because it is unsafe
+    Symlink { path: PathBuf, reason: Skipped },
+    /// Entries of one directory whose names only differ in case
+    CaseCollision { dir: PathBuf, names: Vec<String> },
+    /// A file under the locales directory that no command reads or writes
+    Untracked(json_sync::UntrackedFile),
+}
+
+/// Report file-system problems: symlinks under `input` or the locale directories
+/// that escape the project root, dangle or loop, file names that collide on
+/// case-insensitive file systems (macOS, Windows), and files in the locales
+/// directories that the config never reads
+pub fn run(config: &Config) -> Result<()> {
+    println!("=== i18next-turbo doctor ===\n");
+    println!(
+        "  Follow symlinks: {}",
+        if config.follow_symlinks { "yes" } else { "no" }
+    );
+
+    let mut problems = symlink_problems(config)?;
+    problems.extend(case_collision_problems(config));
+    problems.extend(untracked_problems(config)?);
+
+    if problems.is_empty() {
+        println!("\nNo problems found.");
+        return Ok(());
+    }
+
+    println!();
+    for problem in &problems {
+        match problem {
+            Problem::Symlink { path, reason } => {
+                println!(
+                    "  ✗ {}: {}",
+                    paths::display(path, config.path_style),
+                    reason
+                )
+            }
+            Problem::CaseCollision { dir, names } => println!(
+                "  ✗ {}: {} only differ in case and collide on macOS/Windows",
+                paths::display(dir, config.path_style),
+                names.join(", ")
+            ),
+            Problem::Untracked(file) => println!(
+                "  ✗ {}: never read or written ({})",
+                paths::display(&file.path, config.path_style),
+                file.reason
+            ),
+        }
+    }
+    bail!("{} problem(s) found", problems.len());
+}
+
+fn symlink_problems(config: &Config) -> Result<Vec<Problem>> {
+    let mut problems = Vec::new();
+    let mut guard = SymlinkGuard::new(config.follow_symlinks);
+    let mut check = |base: &Path, path: PathBuf| match guard.admit(base, &path) {
+        // Unfollowed links are skipped on purpose and duplicates are harmless
+        Ok(()) | Err(Skipped::NotFollowed) | Err(Skipped::Duplicate) => {}
+        Err(reason) => problems.push(Problem::Symlink { path, reason }),
+    };
+
+    for pattern in config
+        .input
+        .iter()
+        .flat_map(|pattern| extractor::expand_brace_patterns(pattern))
+    {
+        let Ok(paths) = glob::glob(&pattern) else {
+            continue;
+        };
+        let base = walk::glob_base(&pattern);
+        for path in paths.flatten() {
+            if path.is_file() || is_symlink(&path) {
+                check(&base, path);
+            }
+        }
+    }
+
+    for locale_dir in locale_dirs(config) {
+        let Ok(entries) = std::fs::read_dir(&locale_dir) else {
+            continue;
+        };
+        let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+        paths.sort();
+        for path in paths {
+            if path.is_file() || is_symlink(&path) {
+                check(&locale_dir, path);
+            }
+        }
+    }
+    Ok(problems)
+}
+
+fn case_collision_problems(config: &Config) -> Vec<Problem> {
+    std::iter::once(PathBuf::from(&config.output))
+        .chain(locale_dirs(config))
+        .flat_map(|dir| {
+            walk::case_collisions(&dir)
+                .into_iter()
+                .map(move |names| Problem::CaseCollision {
+                    dir: dir.clone(),
+                    names,
+                })
+        })
+        .collect()
+}
+
+fn untracked_problems(config: &Config) -> Result<Vec<Problem>> {
+    let mut problems = Vec::new();
+    let outputs = std::iter::once(config.output.as_str())
+        .chain(config.outputs.iter().map(|target| target.output.as_str()));
+    for output in outputs {
+        problems.extend(
+            json_sync::untracked_locale_files(config, output)?
+                .into_iter()
+                .map(Problem::Untracked),
+        );
+    }
+    Ok(problems)
+}
+
+fn locale_dirs(config: &Config) -> Vec<PathBuf> {
+    if config.flat_locale_files {
+        return Vec::new();
+    }
+    config
+        .locales
+        .iter()
+        .map(|locale| Path::new(&config.output).join(locale))
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+fn is_symlink(path: &Path) -> bool {
+    std::fs::symlink_metadata(path).is_ok_and(|meta| meta.file_type().is_symlink())
+}
@@ -0,0 +1,120 @@
+use anyhow::{bail, Context, Result};
+use serde_json::{Map, Value};
+use std::path::Path;
+use std::process::Command;
+
+use crate::commands::set_value::split_key;
+use crate::config::Config;
+use crate::json_sync;
+use crate::paths;
+use crate::plan;
+
+/// Open `$VISUAL` (or `$EDITOR`) on the values of `key` in every locale, as a
+/// JSON object of locale to value, and write back the locales whose value
+/// changed. Locales left `null` or removed from the object are not touched.
+pub fn run(config: &Config, key: &str, dry_run: bool) -> Result<()> {
+    println!("=== i18next-turbo edit ===\n");
+    let (namespace, key) = split_key(config, key);
+
+    let current = current_values(config, &namespace, &key)?;
+    let file = tempfile::Builder::new()
+        .prefix("i18next-turbo-edit-")
+        .suffix(".json")
+        .tempfile()
+        .context("Failed to create a temporary file for the editor")?;
+    std::fs::write(
+        file.path(),
+        serde_json::to_string_pretty(&Value::Object(current.clone()))? + "\n",
+    )
+    .with_context(|| format!("Failed to write: {}", file.path().display()))?;
+
+    launch_editor(file.path())?;
+
+    let content = std::fs::read_to_string(file.path())
+        .with_context(|| format!("Failed to read: {}", file.path().display()))?;
+    let edited = match serde_json::from_str::<Value>(&content) {
+        Ok(Value::Object(edited)) => edited,
+        Ok(_) => bail!("The edited values must be a JSON object of locale to value"),
+        Err(e) => bail!("The edited values are not valid JSON: {}", e),
+    };
+    if let Some(locale) = edited.keys().find(|locale| !current.contains_key(*locale)) {
+        bail!(
+            "Unknown locale '{}'; configured locales: {}",
+            locale,
+            config.locales.join(", ")
+        );
+    }
+
+    let mut changed = 0;
+    for (locale, value) in &edited {
+        if value.is_null() || current.get(locale) == Some(value) {
+            continue;
+        }
+        if plan::set_value(config, locale, &namespace, &key, value, dry_run)? {
+            let path =
+                json_sync::locale_namespace_file_path(config, &config.output, locale, &namespace);
+            println!(
+                "  {} {}",
+                if dry_run { "Would update" } else { "Updated" },
+                paths::display(&path, config.path_style)
+            );
+            changed += 1;
+        }
+    }
+
+    if changed == 0 {
+        println!("No changes.");
+    } else if dry_run {
+        println!("\n[Dry run] Would update {} locale file(s).", changed);
+    } else {
+        println!("\nUpdated {} locale file(s).", changed);
+    }
+    Ok(())
+}
+
+/// Value of `key` in every configured locale, `null` where it is missing
+fn current_values(config: &Config, namespace: &str, key: &str) -> Result<Map<String, Value>> {
+    let node = (namespace.to_string(), key.to_string());
+    let mut values = Map::new();
+    for locale in &config.locales {
+        let namespaces = json_sync::load_locale_namespaces(config, &config.output, locale)?;
+        let value = json_sync::flatten_leaf_values(&namespaces, &config.key_separator)
+            .remove(&node)
+            .unwrap_or(Value::Null);
+        values.insert(locale.clone(), value);
+    }
+    Ok(values)
+}
+
+/// `$VISUAL`, then `$EDITOR`, then `vi`; run through the shell so editors with
+/// arguments such as `code --wait` work
+fn launch_editor(path: &Path) -> Result<()> {
+    let editor = ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+
+    #[cfg(windows)]
+    let status = Command::new("cmd")
+        .arg("/C")
+        .arg(format!("{} \"{}\"", editor, path.display()))
+        .status();
+    #[cfg(not(windows))]
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$1\"", editor))
+        .arg("sh")
+        .arg(path)
+        .status();
+
+    let status = status.with_context(|| format!("Failed to start editor: {}", editor))?;
+    if !status.success() {
+        bail!(
+            "Editor '{}' exited with {}; nothing was changed",
+            editor,
+            status
+        );
+    }
+    Ok(())
+}
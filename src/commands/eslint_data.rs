@@ -0,0 +1,46 @@
+use anyhow::{bail, Result};
+use std::path::Path;
+
+use crate::config::Config;
+use crate::eslint_data;
+use crate::extractor;
+use crate::key_transforms;
+use crate::paths;
+
+/// Write the ESLint rule data to `output`, falling back to `eslintDataFile`
+pub fn run(config: &Config, output: Option<&str>) -> Result<()> {
+    println!("=== i18next-turbo export-eslint-data ===\n");
+    let Some(output) = output.or(config.eslint_data_file.as_deref()) else {
+        bail!("No output path: pass --output or set \"eslintDataFile\" in the config");
+    };
+
+    println!("Extracting keys from source files...");
+    let mut extraction =
+        extractor::extract_with_config(config, &config.input, &config.extraction_ignore())?;
+    key_transforms::apply(config, &mut extraction.files);
+
+    let path = Path::new(output);
+    let written = eslint_data::write(
+        config,
+        path,
+        extraction.files.iter().map(|(file, keys)| (file, keys)),
+    )?;
+    let files = extraction
+        .files
+        .iter()
+        .filter(|(_, keys)| !keys.is_empty())
+        .count();
+    if written {
+        println!(
+            "  Wrote {} ({} source file(s))",
+            paths::display(path, config.path_style),
+            files
+        );
+    } else {
+        println!(
+            "  {} is already up to date.",
+            paths::display(path, config.path_style)
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,440 @@
+use anyhow::Result;
+use glob::Pattern;
+
+use crate::commands::extract_file;
+use crate::config::Config;
+use crate::extractor::{self, KeySource, SkipReason, SkippedCall};
+use crate::key_transforms;
+use crate::paths;
+
+/// Trace why `query` (`key` or `ns:key`) is or is not extracted: the config
+/// options that shape it, then every input file mentioning the key with the
+/// outcome of each mention. Nothing is written.
+pub fn run(config: &Config, query: &str) -> Result<()> {
+    let (namespace, key) = split_query(config, query);
+    let plural = config.plural_config();
+    // Plural forms come from a call with the base key and a `count` option
+    let base = plural.strip_plural_suffix(key);
+    let literal = base.as_deref().unwrap_or(key);
+
+    println!("=== i18next-turbo extract --explain ===\n");
+    println!("  Key: {}{}{}", namespace, config.ns_separator, key);
+    println!();
+    for line in config_notes(config, namespace, key, base.as_deref()) {
+        println!("  {}", line);
+    }
+
+    let ignore: Vec<Pattern> = config
+        .extraction_ignore()
+        .iter()
+        .filter_map(|pattern| Pattern::new(pattern).ok())
+        .collect();
+    let (mut extracted, mut mentioned) = (0, 0);
+    for path in extractor::input_files(&config.input, &[], config.follow_symlinks)? {
+        let Ok(source) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let offsets = mentions(&source, literal);
+        if let Some(pattern) = ignore.iter().find(|pattern| pattern.matches_path(&path)) {
+            if !offsets.is_empty() {
+                mentioned += 1;
+                println!("\n  {}", paths::display(&path, config.path_style));
+                println!(
+                    "    ✗ skipped: the file matches ignore/generatedFiles pattern '{}'",
+                    pattern
+                );
+            }
+            continue;
+        }
+
+        let file_path = paths::display(&path, config.path_style);
+        let mut extraction = extract_file::extract_one(config, &path)?;
+        let dynamic: Vec<(&SkippedCall, &str)> = extraction
+            .skipped_calls
+            .iter()
+            .filter_map(|skip| match &skip.reason {
+                SkipReason::DynamicKey(pattern) => Some((skip, pattern.as_str())),
+                _ => None,
+            })
+            .filter(|(_, pattern)| Pattern::new(pattern).is_ok_and(|p| p.matches(key)))
+            .collect();
+        if offsets.is_empty() && dynamic.is_empty() {
+            continue;
+        }
+        mentioned += 1;
+        println!("\n  {}", file_path);
+
+        let default_namespace = config.effective_default_namespace();
+        // Spans of component blocks are relative to the block, not the file
+        let sources: Vec<(&KeySource, String, String)> = extraction
+            .key_sources
+            .iter()
+            .filter(|source| source.file_path == file_path)
+            .map(|source| {
+                let found = source.namespace.as_deref().unwrap_or(default_namespace);
+                let locale_key = key_transforms::transform_key(
+                    &config.key_transforms,
+                    &source.key,
+                    &config.key_separator,
+                );
+                (source, found.to_string(), locale_key)
+            })
+            .collect();
+        let skipped: Vec<&SkippedCall> = extraction
+            .skipped_calls
+            .iter()
+            .filter(|skip| skip.file_path == file_path)
+            .collect();
+
+        let mut outcomes: Vec<String> = Vec::new();
+        for &offset in &offsets {
+            let covering: Vec<&(&KeySource, String, String)> = sources
+                .iter()
+                .filter(|(source, _, _)| source.span.contains(&offset))
+                .collect();
+            let outcome = if let Some((source, _, _)) = covering
+                .iter()
+                .find(|(_, found, locale_key)| locale_key == key && found == namespace)
+            {
+                format!("✓ {}:{}: extracted", source.line, source.column)
+            } else if let Some(skip) = skipped
+                .iter()
+                .filter(|skip| skip.span.contains(&offset))
+                .min_by_key(|skip| skip.span.len())
+            {
+                format!(
+                    "✗ {}:{}: {}",
+                    skip.line,
+                    skip.column,
+                    skip_note(config, &skip.reason)
+                )
+            } else if let Some((source, found, locale_key)) = covering
+                .iter()
+                .min_by_key(|(source, _, _)| source.span.len())
+            {
+                format!(
+                    "✗ {}:{}: {}",
+                    source.line,
+                    source.column,
+                    extracted_note(config, found, locale_key, namespace, key, base.as_deref())
+                )
+            } else {
+                format!(
+                    "✗ {}: {}",
+                    line_column(&source, offset),
+                    diagnose(config, &source, offset)
+                )
+            };
+            if !outcomes.contains(&outcome) {
+                outcomes.push(outcome);
+            }
+        }
+
+        key_transforms::apply(config, &mut extraction.files);
+        if extraction.files.iter().flat_map(|(_, keys)| keys).any(|k| {
+            k.key == key && k.namespace.as_deref().unwrap_or(default_namespace) == namespace
+        }) {
+            extracted += 1;
+            if offsets.is_empty() {
+                outcomes.push("✓ -: extracted".to_string());
+            }
+        }
+        for outcome in outcomes {
+            println!("    {}", outcome);
+        }
+        for (skip, pattern) in dynamic {
+            println!(
+                "    ~ {}:{}: dynamic template literal ({}) can produce this key but is never extracted; list it in preservePatterns to keep it",
+                skip.line, skip.column, pattern
+            );
+        }
+    }
+
+    println!();
+    if mentioned == 0 {
+        println!(
+            "No file matched by input ({}) mentions '{}'.",
+            config.input.join(", "),
+            literal
+        );
+    } else {
+        println!(
+            "Extracted from {} of {} file(s) mentioning '{}'.",
+            extracted, mentioned, literal
+        );
+    }
+    Ok(())
+}
+
+/// `ns:key` into its namespace (the default one when absent) and key
+fn split_query<'a>(config: &'a Config, query: &'a str) -> (&'a str, &'a str) {
+    if !config.ns_separator.is_empty() {
+        if let Some((namespace, key)) = query.split_once(config.ns_separator.as_str()) {
+            return (namespace, key);
+        }
+    }
+    (config.effective_default_namespace(), query)
+}
+
+/// Config options that decide how the key is found and written
+fn config_notes(config: &Config, namespace: &str, key: &str, base: Option<&str>) -> Vec<String> {
+    let mut notes = vec![
+        format!("functions: {}", config.functions.join(", ")),
+        format!(
+            "transComponents: {}",
+            config
+                .trans_components
+                .iter()
+                .map(|component| component.name())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        format!(
+            "nsSeparator: {:?}, keySeparator: {:?}, defaultNS: {}",
+            config.ns_separator,
+            config.key_separator,
+            config.effective_default_namespace()
+        ),
+        format!(
+            "extractFromComments: {}",
+            if config.extract_from_comments {
+                "on"
+            } else {
+                "off"
+            }
+        ),
+    ];
+    let ignore = config.extraction_ignore();
+    if !ignore.is_empty() {
+        notes.push(format!("ignore/generatedFiles: {}", ignore.join(", ")));
+    }
+    if let Some(base) = base {
+        notes.push(format!(
+            "plural form of '{}' (pluralSeparator {:?})",
+            base, config.plural_separator
+        ));
+    }
+    if !config.key_transforms.is_empty() {
+        notes.push(format!(
+            "keyTransforms: {} rule(s); source keys are matched after they are applied",
+            config.key_transforms.len()
+        ));
+    }
+    if crate::json_sync::PreserveMatcher::new(&config.preserve_patterns, &config.ns_separator)
+        .is_ok_and(|matcher| matcher.matches(namespace, key))
+    {
+        notes.push("preservePatterns: matched; the key is kept even when unused".to_string());
+    }
+    if !config.remove_unused_keys {
+        notes.push("removeUnusedKeys: off; unused keys stay in locale files".to_string());
+    }
+    notes
+}
+
+/// Byte offsets where `needle` appears on its own, not inside a longer word
+fn mentions(source: &str, needle: &str) -> Vec<usize> {
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    source
+        .match_indices(needle)
+        .map(|(offset, _)| offset)
+        .filter(|&offset| {
+            !source[..offset].chars().next_back().is_some_and(is_word)
+                && !source[offset + needle.len()..]
+                    .chars()
+                    .next()
+                    .is_some_and(is_word)
+        })
+        .collect()
+}
+
+fn line_column(source: &str, offset: usize) -> String {
+    let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+    format!(
+        "{}:{}",
+        source[..offset].matches('\n').count() + 1,
+        source[line_start..offset].chars().count() + 1
+    )
+}
+
+/// What the extractor recorded about a skipped call
+fn skip_note(config: &Config, reason: &SkipReason) -> String {
+    match reason {
+        SkipReason::DisabledRegion => {
+            "inside an i18next-extract-disable region without a matching i18next-extract-enable"
+                .to_string()
+        }
+        SkipReason::DisabledComment(comment) => format!("disabled by a `{}` comment", comment),
+        SkipReason::DynamicKey(pattern) => format!(
+            "the key is a dynamic template literal ({}); its interpolations cannot be resolved",
+            pattern
+        ),
+        SkipReason::UnresolvedScope => format!(
+            "the ns option or the useTranslation/getFixedT namespace is not a string literal, so the key went to namespace '{}'",
+            config.effective_default_namespace()
+        ),
+    }
+}
+
+/// How a call mentioning the key was extracted, when not as the key asked for
+fn extracted_note(
+    config: &Config,
+    found: &str,
+    locale_key: &str,
+    namespace: &str,
+    key: &str,
+    base: Option<&str>,
+) -> String {
+    if locale_key == key {
+        format!(
+            "extracted into namespace '{}' (from the ns option, the useTranslation/getFixedT scope or a '{}' prefix)",
+            found, config.ns_separator
+        )
+    } else if found == namespace && base == Some(locale_key) {
+        format!(
+            "extracted as '{}' without plural forms: the call has no `count` option",
+            locale_key
+        )
+    } else if locale_key.ends_with(&format!("{}{}", config.key_separator, key)) {
+        format!(
+            "extracted as '{}{}{}' (keyPrefix of the useTranslation/getFixedT scope)",
+            found, config.ns_separator, locale_key
+        )
+    } else {
+        format!(
+            "the call is extracted as '{}{}{}'",
+            found, config.ns_separator, locale_key
+        )
+    }
+}
+
+/// Why a mention of the key outside any call the extractor read cannot
+/// produce the key
+fn diagnose(config: &Config, source: &str, offset: usize) -> String {
+    const NO_KEY: &str =
+        "looks like a translation call but produced no key; the key must be a plain string literal";
+    let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+    if is_comment(&source[line_start..offset]) {
+        return if config.extract_from_comments {
+            "in a comment that is not a `t('key')` call".to_string()
+        } else {
+            "in a comment, and extractFromComments is off".to_string()
+        };
+    }
+
+    let before = &source[..offset];
+    let prefix = before
+        .strip_suffix(['\'', '"', '`'])
+        .unwrap_or(before)
+        .trim_end();
+    if let Some(call) = prefix.strip_suffix('(') {
+        let callee: String = call
+            .trim_end()
+            .chars()
+            .rev()
+            .take_while(|c| c.is_alphanumeric() || matches!(c, '_' | '$' | '.'))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .collect();
+        let callee = callee.trim_start_matches("this.");
+        if callee.is_empty() {
+            return "not the first argument of a function call".to_string();
+        }
+        if config.functions.iter().any(|name| name == callee) {
+            return NO_KEY.to_string();
+        }
+        return format!(
+            "`{}(...)` is not in functions ({})",
+            callee,
+            config.functions.join(", ")
+        );
+    }
+    if let Some(attr) = prefix.strip_suffix('=') {
+        let attr: String = attr
+            .chars()
+            .rev()
+            .take_while(|c| c.is_alphanumeric() || *c == '-')
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .collect();
+        let tag = before.rfind('<').map(|start| {
+            before[start + 1..]
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || matches!(c, '_' | '.'))
+                .collect::<String>()
+        });
+        if let Some(tag) = tag.filter(|tag| !tag.is_empty()) {
+            return match config
+                .trans_components
+                .iter()
+                .find(|component| component.name() == tag)
+            {
+                Some(component) if component.key_attr() != attr => format!(
+                    "`{}` is not the key attribute of <{}> (keyAttr is `{}`)",
+                    attr,
+                    tag,
+                    component.key_attr()
+                ),
+                Some(_) => NO_KEY.to_string(),
+                None => format!("<{}> is not in transComponents", tag),
+            };
+        }
+    }
+    "not a translation call, <Trans> key or comment".to_string()
+}
+
+fn is_comment(text: &str) -> bool {
+    let text = text.trim_start();
+    text.starts_with("//")
+        || text.starts_with("/*")
+        || text.starts_with('*')
+        || text.contains(" //")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnose_key(source: &str, key: &str) -> String {
+        let mut config = Config::default();
+        config.extract_from_comments = false;
+        let offset = mentions(source, key)[0];
+        diagnose(&config, source, offset)
+    }
+
+    #[test]
+    fn mentions_skip_longer_words() {
+        assert_eq!(
+            mentions("t('some.key'); t('awesome.key'); x.some.key2", "some.key"),
+            vec![3]
+        );
+    }
+
+    #[test]
+    fn diagnose_names_the_reason_a_mention_is_skipped() {
+        assert_eq!(
+            diagnose_key("translate('some.key');", "some.key"),
+            "`translate(...)` is not in functions (t)"
+        );
+        assert!(
+            diagnose_key("t(some.key);", "some.key").starts_with("looks like a translation call")
+        );
+        assert!(diagnose_key("t(x, 'some.key');", "some.key").starts_with("not a translation call"));
+        assert_eq!(
+            diagnose_key("// t('some.key')", "some.key"),
+            "in a comment, and extractFromComments is off"
+        );
+        assert_eq!(
+            diagnose_key("<Message i18nKey=\"some.key\" />", "some.key"),
+            "<Message> is not in transComponents"
+        );
+        assert_eq!(
+            diagnose_key("<Trans key=\"some.key\" />", "some.key"),
+            "`key` is not the key attribute of <Trans> (keyAttr is `i18nKey`)"
+        );
+    }
+}
@@ -1,11 +1,28 @@
-use anyhow::{bail, Result};
-use std::collections::HashSet;
+use anyhow::{bail, Context, Result};
+use serde_json::json;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::Path;
+use std::time::Instant;
 
-use crate::config::Config;
-use crate::extractor::{self, ExtractedKey};
+use crate::budgets;
+use crate::commands::lint;
+use crate::config::{Config, PathStyle};
+use crate::conflicts::ConflictReport;
+use crate::eslint_data;
+use crate::extractor::{
+    self, ComponentMessage, DiagnosticKind, DuplicateKey, ExtractedKey, ExtractionError,
+    ExtractionResult, KeyCollision, KeySource, DEV_NAMESPACE,
+};
 use crate::json_sync::{self, KeyConflict};
+use crate::key_transforms;
+use crate::metrics::ExtractMetrics;
+use crate::paths;
+use crate::publish;
+use crate::summary;
 use crate::typegen;
 
+/// Extract keys and sync the locale files. With `emailTemplates` configured,
+/// the templates run through a second pass into their own locale root.
 #[allow(clippy::too_many_arguments)]
 pub fn run(
     config: &Config,
@@ -18,17 +35,120 @@ pub fn run(
     sync_primary: bool,
     sync_all: bool,
     verbose: bool,
+    error_report: Option<&Path>,
+    conflict_report: Option<&Path>,
+    json_report: Option<&Path>,
+    filter_files: &[String],
+    filter_keys: &[String],
+    metrics_file: Option<&Path>,
+    otlp_endpoint: Option<&str>,
+    strict_duplicates: bool,
+    update_defaults: bool,
+    clear_stale: bool,
+    production: bool,
+    run_lint: bool,
+    diagnostic_kinds: &[String],
 ) -> Result<()> {
+    let diagnostic_kinds = diagnostic_kinds
+        .iter()
+        .map(|kind| DiagnosticKind::parse_str(kind))
+        .collect::<Result<Vec<_>>>()?;
     if sync_primary && sync_all {
         bail!("--sync-primary and --sync-all cannot be used together");
     }
-
+    if clear_stale && !update_defaults {
+        bail!("--clear-stale needs --update-defaults");
+    }
     if dry_run {
         println!("=== i18next-turbo extract (dry-run) ===\n");
     } else {
         println!("=== i18next-turbo extract ===\n");
     }
 
+    run_pass(
+        config,
+        output,
+        fail_on_warnings,
+        generate_types,
+        types_output,
+        dry_run,
+        ci,
+        sync_primary,
+        verbose,
+        error_report,
+        conflict_report,
+        json_report,
+        filter_files,
+        filter_keys,
+        metrics_file,
+        otlp_endpoint,
+        strict_duplicates,
+        update_defaults,
+        clear_stale,
+        production,
+        run_lint,
+        &diagnostic_kinds,
+    )?;
+
+    let Some(email_config) = config.email_templates_config() else {
+        return Ok(());
+    };
+    // Reports, metrics and types describe the web app sources only
+    println!("\n=== Email templates ===\n");
+    run_pass(
+        &email_config,
+        None,
+        fail_on_warnings,
+        false,
+        types_output,
+        dry_run,
+        ci,
+        sync_primary,
+        verbose,
+        None,
+        None,
+        None,
+        filter_files,
+        filter_keys,
+        None,
+        None,
+        strict_duplicates,
+        update_defaults,
+        clear_stale,
+        production,
+        false,
+        &diagnostic_kinds,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_pass(
+    config: &Config,
+    output: Option<String>,
+    fail_on_warnings: bool,
+    generate_types: bool,
+    types_output: &str,
+    dry_run: bool,
+    ci: bool,
+    sync_primary: bool,
+    verbose: bool,
+    error_report: Option<&Path>,
+    conflict_report: Option<&Path>,
+    json_report: Option<&Path>,
+    filter_files: &[String],
+    filter_keys: &[String],
+    metrics_file: Option<&Path>,
+    otlp_endpoint: Option<&str>,
+    strict_duplicates: bool,
+    update_defaults: bool,
+    clear_stale: bool,
+    production: bool,
+    run_lint: bool,
+    diagnostic_kinds: &[DiagnosticKind],
+) -> Result<()> {
+    let started = Instant::now();
+    let config = &scoped_config(config, filter_files, filter_keys, production);
+
     // Determine output directory
     let output_dir = output.as_ref().unwrap_or(&config.output);
 
@@ -37,6 +157,12 @@ pub fn run(
     println!("  Output: {}", output_dir);
     println!("  Locales: {:?}", config.locales);
     println!("  Functions: {:?}", config.functions);
+    if !filter_files.is_empty() {
+        println!("  Filter files: {:?}", filter_files);
+    }
+    if !filter_keys.is_empty() {
+        println!("  Filter keys: {:?}", filter_keys);
+    }
     if verbose {
         println!("  Key separator: {:?}", config.key_separator);
         println!("  NS separator: {:?}", config.ns_separator);
@@ -47,46 +173,99 @@ pub fn run(
         if !config.ignore.is_empty() {
             println!("  Ignore patterns: {:?}", config.ignore);
         }
+        if !config.generated_files.is_empty() {
+            println!("  Generated files: {:?}", config.generated_files);
+        }
         if !config.preserve_patterns.is_empty() {
             println!("  Preserve patterns: {:?}", config.preserve_patterns);
         }
     }
     println!();
 
-    let plural_config = config.plural_config();
-
-    // Extract keys from files
-    let extraction = extractor::extract_from_glob_with_options(
-        &config.input,
-        &config.ignore,
-        &config.functions,
-        config.extract_from_comments,
-        &plural_config,
-        &config.trans_components,
-        &config.trans_keep_basic_html_nodes_for,
-        &config.use_translation_names,
-        &config.nesting_prefix,
-        &config.nesting_suffix,
-        &config.nesting_options_separator,
-        &config.interpolation_prefix,
-        &config.interpolation_suffix,
-    )?;
+    let input = if filter_files.is_empty() {
+        config.input.clone()
+    } else {
+        extractor::filter_input_files(&config.input, filter_files)?
+    };
+    if input.is_empty() {
+        println!("No source files match --filter-file.");
+        return Ok(());
+    }
+
+    // Extract keys from files; `--lint` checks the same parse for hardcoded strings
+    let mut request = extractor::ExtractRequest::builder()
+        .config(config)
+        .patterns(input.iter().cloned())
+        .ignore(config.extraction_ignore())
+        .diagnostic_kinds(diagnostic_kinds.iter().copied());
+    if run_lint {
+        request = request.lint(lint::lint_options(config));
+    }
+    let mut extraction = request.build().run()?;
+    let mut metrics = ExtractMetrics {
+        extract_duration: started.elapsed(),
+        dynamic_keys: extraction.dynamic_keys.len(),
+        warnings: extraction.total_warnings(),
+        errors: extraction.errors.len(),
+        ..ExtractMetrics::default()
+    };
 
     // Report any errors encountered during extraction
     if !extraction.errors.is_empty() {
         eprintln!("\nExtraction errors:");
         for error in &extraction.errors {
-            eprintln!("  {}: {}", error.file_path, error.message);
+            eprintln!("  {}", error);
         }
         eprintln!();
     }
+    if let Some(report_path) = error_report {
+        write_error_report(report_path, &extraction.errors)?;
+    }
+    if extraction.dynamic_key_errors() > 0 {
+        bail!(
+            "{} dynamic template literal key(s) at error severity (dynamicKeySeverity / dynamicKeyRules)",
+            extraction.dynamic_key_errors()
+        );
+    }
+
+    let key_map = key_transforms::apply(config, &mut extraction.files);
+
+    if production {
+        for (_, keys) in extraction.files.iter_mut() {
+            keys.retain(|key| key.namespace.as_deref() != Some(DEV_NAMESPACE));
+        }
+        extraction.files.retain(|(_, keys)| !keys.is_empty());
+    }
+
+    if !config.key_filter.is_empty() {
+        let scope = json_sync::PreserveMatcher::for_config(config)?;
+        let default_namespace = config.effective_default_namespace();
+        for (_, keys) in extraction.files.iter_mut() {
+            keys.retain(|key| {
+                scope.in_scope(
+                    key.namespace.as_deref().unwrap_or(default_namespace),
+                    &key.key,
+                )
+            });
+        }
+        extraction.files.retain(|(_, keys)| !keys.is_empty());
+    }
 
     if extraction.files.is_empty() {
         println!("No translation keys found.");
-        if fail_on_warnings && extraction.warning_count > 0 {
+        if let Some(report_path) = conflict_report {
+            ConflictReport::from_sync_results(config, &[], &[]).write(report_path)?;
+        }
+        if let Some(report_path) = json_report {
+            write_json_report(report_path, &[], &[], dry_run)?;
+        }
+        print_error_summary(&extraction.errors, error_report, config.path_style);
+        metrics.total_duration = started.elapsed();
+        export_metrics(&metrics, metrics_file, otlp_endpoint, config.path_style)?;
+        if fail_on_warnings && extraction.total_warnings() > 0 {
             bail!(
                 "{} warning(s) encountered (--fail-on-warnings enabled)",
-                extraction.warning_count
+                extraction.total_warnings()
             );
         }
         return Ok(());
@@ -116,29 +295,121 @@ pub fn run(
     println!("\nExtraction Summary:");
     println!("  Files processed: {}", extraction.files.len());
     println!("  Unique keys found: {}", unique_keys.len());
-    if extraction.warning_count > 0 {
-        println!("  Warnings: {}", extraction.warning_count);
+    if extraction.total_warnings() > 0 {
+        println!("  Warnings: {}", extraction.total_warnings());
+        for (kind, count) in extraction.diagnostic_counts() {
+            println!("    {}: {}", kind, count);
+        }
+    }
+
+    report_duplicate_keys(config, &extraction.duplicate_keys);
+    if strict_duplicates && !extraction.duplicate_keys.is_empty() {
+        bail!(
+            "{} key(s) extracted from code and comments with different default values (--strict-duplicates enabled)",
+            extraction.duplicate_keys.len()
+        );
     }
 
+    metrics.files = extraction.files.len();
+    metrics.keys = unique_keys.len();
+    let mut namespace_keys: BTreeMap<&str, HashSet<&str>> = BTreeMap::new();
+    for key in &all_keys {
+        let namespace = key
+            .namespace
+            .as_deref()
+            .unwrap_or(config.effective_default_namespace());
+        namespace_keys
+            .entry(namespace)
+            .or_default()
+            .insert(key.key.as_str());
+    }
+    metrics.keys_by_namespace = namespace_keys
+        .into_iter()
+        .map(|(namespace, keys)| (namespace.to_string(), keys.len()))
+        .collect();
+
     // Sync to JSON files
+    let sync_started = Instant::now();
     if dry_run {
         println!("\nPreviewing changes (dry-run mode)...");
     } else {
         println!("\nSyncing to locale files...");
     }
-    let sync_results = if sync_primary {
-        let locales = vec![config.primary_language().to_string()];
-        json_sync::sync_locales(config, &all_keys, output_dir, &locales, dry_run)?
-    } else if sync_all {
-        json_sync::sync_all_locales(config, &all_keys, output_dir, dry_run)?
+    let messages: Vec<ComponentMessage> = extraction
+        .messages
+        .iter()
+        .filter(|message| !sync_primary || message.locale == config.primary_language())
+        .cloned()
+        .collect();
+    let message_results =
+        json_sync::sync_component_messages(config, &messages, output_dir, dry_run)?;
+    for result in &message_results {
+        if !result.added_keys.is_empty() {
+            println!(
+                "  {} - {} {} message(s) from <i18n> blocks",
+                result.file_path,
+                if dry_run { "would add" } else { "added" },
+                result.added_keys.len()
+            );
+        }
+    }
+    // Default is sync-all behavior; --sync-primary limits writes to the primary locale
+    let target_locales = if sync_primary {
+        vec![config.primary_language().to_string()]
     } else {
-        // default is sync-all behavior
-        json_sync::sync_all_locales(config, &all_keys, output_dir, dry_run)?
+        config.locales.clone()
     };
+    let mut default_updates = Vec::new();
+    if update_defaults {
+        for (target_output, keys) in json_sync::group_keys_by_output(
+            config,
+            extraction.files.iter().map(|(path, keys)| (path, keys)),
+            output_dir,
+        ) {
+            default_updates.extend(json_sync::update_default_values(
+                config,
+                &keys,
+                &target_output,
+                clear_stale,
+                dry_run,
+            )?);
+        }
+        report_default_updates(&default_updates, dry_run);
+    }
+    let mut sync_results = Vec::new();
+    for (target_output, keys) in json_sync::group_keys_by_output(
+        config,
+        extraction.files.iter().map(|(path, keys)| (path, keys)),
+        output_dir,
+    ) {
+        sync_results.extend(json_sync::sync_locales(
+            config,
+            &keys,
+            &target_output,
+            &target_locales,
+            dry_run,
+        )?);
+    }
+
+    if !dry_run {
+        // A filtered run only sees part of the keys, so keep the other mappings
+        let partial = !filter_files.is_empty() || !filter_keys.is_empty();
+        key_transforms::write_key_map(config, &key_map, partial)?;
+        if !partial {
+            eslint_data::write_configured(
+                config,
+                extraction.files.iter().map(|(path, keys)| (path, keys)),
+            )?;
+        }
+        publish::write_configured(config)?;
+    }
+
+    metrics.sync_duration = sync_started.elapsed();
 
     // Report sync results
     let mut total_added = 0;
     let mut total_removed = 0;
+    let mut total_pending = 0;
     let mut total_conflicts = 0;
     let mut all_conflicts: Vec<(String, KeyConflict)> = Vec::new();
 
@@ -174,12 +445,13 @@ pub fn run(
             }
             total_removed += result.removed_keys.len();
         }
+        total_pending += result.pending_removal.len();
 
         // Collect conflicts for reporting
         if !result.conflicts.is_empty() {
             total_conflicts += result.conflicts.len();
-            for conflict in &result.conflicts {
-                all_conflicts.push((result.file_path.clone(), conflict.clone()));
+            for skipped in &result.conflicts {
+                all_conflicts.push((result.file_path.clone(), skipped.conflict.clone()));
             }
         }
     }
@@ -197,6 +469,12 @@ pub fn run(
             total_removed
         );
     }
+    if total_pending > 0 {
+        println!(
+            "  Dead keys kept for removalGraceDays: {} (see 'i18next-turbo status')",
+            total_pending
+        );
+    }
 
     // Report conflicts with user-friendly messages
     if !all_conflicts.is_empty() {
@@ -232,9 +510,33 @@ pub fn run(
             "  \x1b[90mTo fix: manually update the conflicting keys in your locale files,\x1b[0m"
         );
         eprintln!("  \x1b[90mor rename the keys in your source code to avoid collision.\x1b[0m");
+        if let Some(report_path) = conflict_report {
+            eprintln!(
+                "  \x1b[90mConflict report: {} (apply with `resolve-conflicts`)\x1b[0m",
+                paths::display(report_path, config.path_style)
+            );
+        }
+    }
+    if let Some(report_path) = conflict_report {
+        ConflictReport::from_sync_results(config, &sync_results, &extraction.files)
+            .write(report_path)?;
     }
+    if let Some(report_path) = json_report {
+        let results: Vec<&json_sync::SyncResult> =
+            message_results.iter().chain(&sync_results).collect();
+        let sources = added_key_sources(
+            config,
+            &extraction.files,
+            &extraction.key_sources,
+            &extraction.messages,
+            &results,
+        );
+        write_json_report(report_path, &results, &sources, dry_run)?;
+    }
+    report_key_collisions(config, &extraction.key_collisions);
 
     // Generate TypeScript types if requested (skip in dry-run mode)
+    let typegen_started = Instant::now();
     if generate_types && !dry_run {
         println!("\nGenerating TypeScript types...");
         let locales_dir_override = config
@@ -250,6 +552,10 @@ pub fn run(
         let input_patterns = config.types_input_patterns();
         let resources_file = config.types_resources_file();
         let enable_selector = config.types_enable_selector();
+        let root_keys = typegen::object_root_keys(
+            extraction.files.iter().map(|(_, keys)| keys),
+            config.effective_default_namespace(),
+        );
         typegen::generate_types_with_options(
             locales_dir_path,
             types_path,
@@ -258,40 +564,535 @@ pub fn run(
             input_patterns.as_deref(),
             resources_file.as_deref().map(std::path::Path::new),
             enable_selector.as_ref(),
-            config.merge_namespaces,
+            typegen::LocaleLayout::from_config(config),
+            Some(&config.plural_config()),
+            config.types_namespaces(),
+            typegen::ObjectRoots {
+                keys: &root_keys,
+                key_separator: &config.key_separator,
+            },
         )?;
-        println!("  Generated: {}", types_output);
+        println!(
+            "  Generated: {}",
+            config
+                .types_output_dir()
+                .unwrap_or_else(|| types_output.to_string())
+        );
     } else if generate_types && dry_run {
         println!("\n(Skipping type generation in dry-run mode)");
     }
 
+    metrics.typegen_duration = typegen_started.elapsed();
+
+    print_error_summary(&extraction.errors, error_report, config.path_style);
+
+    metrics.keys_added = total_added;
+    metrics.keys_removed = total_removed;
+    metrics.conflicts = total_conflicts;
+    metrics.total_duration = started.elapsed();
+    export_metrics(&metrics, metrics_file, otlp_endpoint, config.path_style)?;
+    summary::add_phase("sync", metrics.sync_duration);
+    summary::add_phase("typegen", metrics.typegen_duration);
+    summary::add_keys(total_added, total_removed);
+
     if dry_run {
         println!("\nDry-run complete. No files were modified.");
     } else {
         println!("\nDone!");
     }
 
+    let budget_violations = budgets::check_budgets(config, &all_keys);
+    budgets::report_budgets(config, &budget_violations)?;
+
+    if let Some(lint_result) = &extraction.lint {
+        println!("\nScanning for hardcoded strings (same parse)...");
+        lint::report(config, lint_result, false)?;
+    }
+
     // Check fail-on-warnings (includes extraction warnings and key conflicts)
-    let total_warnings = extraction.warning_count + total_conflicts;
+    let total_warnings = extraction.total_warnings() + total_conflicts;
     if fail_on_warnings && total_warnings > 0 {
         bail!(
-            "{} warning(s) encountered (--fail-on-warnings enabled): {} extraction warnings, {} key conflicts",
+            "{} warning(s) encountered (--fail-on-warnings enabled): {}, {} key conflicts",
             total_warnings,
-            extraction.warning_count,
+            format_diagnostic_counts(&extraction),
             total_conflicts
         );
     }
 
     // Check CI mode: fail if locale files would be/were updated
-    let has_changes = total_added > 0 || total_removed > 0;
+    let has_changes = total_added > 0 || total_removed > 0 || !default_updates.is_empty();
     if ci && has_changes {
         bail!(
-            "Locale files {} out of sync (--ci enabled): {} keys added, {} keys removed",
+            "Locale files {} out of sync (--ci enabled): {} keys added, {} keys removed, {} default values changed",
             if dry_run { "are" } else { "were" },
             total_added,
-            total_removed
+            total_removed,
+            default_updates.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Run extraction and sync twice into scratch copies of the locale roots, on
+/// one thread and then on all of them, and fail unless every written file is
+/// byte-identical. The real locale files are not touched.
+pub fn verify_determinism(config: &Config, output: Option<String>) -> Result<()> {
+    println!("=== i18next-turbo extract (determinism check) ===\n");
+    let output_dir = output.as_ref().unwrap_or(&config.output);
+    let single = scratch_run(config, output_dir, 1)?;
+    let parallel = scratch_run(config, output_dir, 0)?;
+    println!("  Runs: 1 thread, {} threads", rayon::current_num_threads());
+
+    let paths: BTreeSet<&String> = single.keys().chain(parallel.keys()).collect();
+    let differing: Vec<&String> = paths
+        .into_iter()
+        .filter(|path| single.get(*path) != parallel.get(*path))
+        .collect();
+    if differing.is_empty() {
+        println!(
+            "\n✓ {} locale file(s) are byte-identical across both runs",
+            single.len()
+        );
+        return Ok(());
+    }
+    println!();
+    for path in &differing {
+        println!("  ✗ {}", path);
+    }
+    bail!(
+        "{} locale file(s) differ between identical runs",
+        differing.len()
+    );
+}
+
+/// Files written by one extract run (keyed by their real path) when the locale
+/// roots are copied to a scratch directory first
+fn scratch_run(
+    config: &Config,
+    output_dir: &str,
+    threads: usize,
+) -> Result<BTreeMap<String, Vec<u8>>> {
+    let scratch = tempfile::tempdir().context("Failed to create a scratch directory")?;
+    let mut roots: Vec<String> = vec![output_dir.to_string()];
+    for target in &config.outputs {
+        if !roots.contains(&target.output) {
+            roots.push(target.output.clone());
+        }
+    }
+    let scratch_root = |root: &str| {
+        let index = roots.iter().position(|r| r == root).unwrap_or(0);
+        scratch.path().join(index.to_string())
+    };
+
+    let mut scratch_config = config.clone();
+    scratch_config.metadata_file = None;
+    scratch_config.output = paths::display(&scratch_root(&config.output), config.path_style);
+    for target in &mut scratch_config.outputs {
+        target.output = paths::display(&scratch_root(&target.output), config.path_style);
+    }
+    for root in &roots {
+        copy_dir(Path::new(root), &scratch_root(root))?;
+    }
+    let scratch_output = paths::display(&scratch_root(output_dir), config.path_style);
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .context("Failed to start the extraction threads")?;
+    pool.install(|| -> Result<()> {
+        let config = &scratch_config;
+        let mut extraction =
+            extractor::extract_with_config(config, &config.input, &config.extraction_ignore())?;
+        key_transforms::apply(config, &mut extraction.files);
+        json_sync::sync_component_messages(config, &extraction.messages, &scratch_output, false)?;
+        for (target_output, keys) in json_sync::group_keys_by_output(
+            config,
+            extraction.files.iter().map(|(path, keys)| (path, keys)),
+            &scratch_output,
+        ) {
+            json_sync::sync_all_locales(config, &keys, &target_output, false)?;
+        }
+        Ok(())
+    })?;
+
+    let mut files = BTreeMap::new();
+    for (index, root) in roots.iter().enumerate() {
+        let dir = scratch.path().join(index.to_string());
+        for entry in walkdir::WalkDir::new(&dir).sort_by_file_name() {
+            let entry = entry?;
+            if entry.file_type().is_file() {
+                let relative = entry.path().strip_prefix(&dir).unwrap_or(entry.path());
+                files.insert(
+                    paths::display(&Path::new(root).join(relative), config.path_style),
+                    std::fs::read(entry.path())?,
+                );
+            }
+        }
+    }
+    Ok(files)
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    std::fs::create_dir_all(to)?;
+    if !from.is_dir() {
+        return Ok(());
+    }
+    for entry in walkdir::WalkDir::new(from) {
+        let entry = entry?;
+        let target = to.join(entry.path().strip_prefix(from).unwrap_or(entry.path()));
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&target)?;
+        } else if entry.file_type().is_file() {
+            std::fs::copy(entry.path(), &target)
+                .with_context(|| format!("Failed to copy {}", entry.path().display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Warn about plural/context variants that are also used as literal keys
+fn report_key_collisions(config: &Config, collisions: &[KeyCollision]) {
+    if collisions.is_empty() {
+        return;
+    }
+    eprintln!();
+    eprintln!(
+        "\x1b[33m⚠ Warning: {} key(s) are both generated by plural/context expansion and written literally:\x1b[0m",
+        collisions.len()
+    );
+    for collision in collisions {
+        let key = match &collision.namespace {
+            Some(namespace) => format!("{}{}{}", namespace, config.ns_separator, collision.key),
+            None => collision.key.clone(),
+        };
+        eprintln!("  {}", key);
+        eprintln!(
+            "    generated from '{}' at {}:{}",
+            collision
+                .generated
+                .generated_from
+                .as_deref()
+                .unwrap_or_default(),
+            collision.generated.file_path,
+            collision.generated.line
+        );
+        eprintln!(
+            "    literal at {}:{}",
+            collision.literal.file_path, collision.literal.line
         );
     }
+}
 
+fn report_duplicate_keys(config: &Config, duplicates: &[DuplicateKey]) {
+    if duplicates.is_empty() {
+        return;
+    }
+    eprintln!();
+    eprintln!(
+        "\x1b[33m⚠ Warning: {} key(s) are extracted from code and comments with different default values:\x1b[0m",
+        duplicates.len()
+    );
+    let describe = |source: &KeySource| {
+        let default = source
+            .default_value
+            .clone()
+            .map(serde_json::Value::from)
+            .unwrap_or_default();
+        format!("{} at {}:{}", default, source.file_path, source.line)
+    };
+    for duplicate in duplicates {
+        let key = match &duplicate.namespace {
+            Some(namespace) => format!("{}{}{}", namespace, config.ns_separator, duplicate.key),
+            None => duplicate.key.clone(),
+        };
+        eprintln!("  {}", key);
+        eprintln!("    code:    {}", describe(&duplicate.code));
+        eprintln!("    comment: {}", describe(&duplicate.comment));
+    }
+}
+
+fn report_default_updates(updates: &[json_sync::DefaultValueUpdate], dry_run: bool) {
+    if updates.is_empty() {
+        return;
+    }
+    println!(
+        "  {} {} changed default value(s):",
+        if dry_run { "Would update" } else { "Updated" },
+        updates.len()
+    );
+    for update in updates {
+        let cleared = if update.cleared_locales.is_empty() {
+            String::new()
+        } else {
+            format!(
+                " ({} stale: {})",
+                if dry_run { "would clear" } else { "cleared" },
+                update.cleared_locales.join(", ")
+            )
+        };
+        println!(
+            "    ~ [{}] {}: {} -> {}{}",
+            update.namespace, update.key, update.from, update.to, cleared
+        );
+    }
+}
+
+/// Number of failing files listed in the end-of-run summary
+const TOP_FAILING_FILES: usize = 10;
+
+/// Config of a targeted run: `--filter-key` scopes additions and removals, and a
+/// `--filter-file` run without one never prunes keys owned by unfiltered files.
+fn scoped_config(
+    config: &Config,
+    filter_files: &[String],
+    filter_keys: &[String],
+    production: bool,
+) -> Config {
+    let mut scoped = config.clone();
+    scoped.key_filter = filter_keys.to_vec();
+    if !filter_files.is_empty() && filter_keys.is_empty() {
+        scoped.remove_unused_keys = false;
+    }
+    if production {
+        // Developer-only keys are left out, not stale
+        scoped
+            .preserve_patterns
+            .push(format!("{}{}*", DEV_NAMESPACE, config.ns_separator));
+    }
+    scoped
+}
+
+/// Write `--metrics-file` and push to `--otlp-endpoint`. A collector that is
+/// down only produces a warning, so it never fails the extraction itself.
+fn export_metrics(
+    metrics: &ExtractMetrics,
+    metrics_file: Option<&Path>,
+    otlp_endpoint: Option<&str>,
+    path_style: PathStyle,
+) -> Result<()> {
+    if let Some(path) = metrics_file {
+        metrics.write_file(path)?;
+        println!("  Metrics: {}", paths::display(path, path_style));
+    }
+    if let Some(endpoint) = otlp_endpoint {
+        if let Err(err) = metrics.push_otlp(endpoint) {
+            eprintln!("\x1b[33m⚠ Warning: {:#}\x1b[0m", err);
+        }
+    }
     Ok(())
 }
+
+/// A key added to a locale file and the first source location that introduced it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddedKeySource {
+    /// Locale file the key was added to
+    pub file: String,
+    pub namespace: String,
+    pub key: String,
+    /// First source file that extracted the key; `None` for keys no source file
+    /// produced (e.g. plural forms only one locale needs)
+    pub source: Option<String>,
+    /// 1-based line and column of the first call (or comment) in `source` that
+    /// produced the key, as recorded by the extractor
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+/// Trace every added key of `results` back to the first file of `files` (then
+/// of the `<i18n>` blocks in `messages`) that extracted it, and to its first
+/// call there in `key_sources`
+pub fn added_key_sources(
+    config: &Config,
+    files: &[(String, Vec<ExtractedKey>)],
+    key_sources: &[KeySource],
+    messages: &[ComponentMessage],
+    results: &[&json_sync::SyncResult],
+) -> Vec<AddedKeySource> {
+    let default_namespace = config.effective_default_namespace();
+    // Sources hold keys as written; locale files get them after keyTransforms
+    let mut first_call: HashMap<(&str, &str, String), &KeySource> = HashMap::new();
+    for source in key_sources {
+        let namespace = source.namespace.as_deref().unwrap_or(default_namespace);
+        let key = key_transforms::transform_key(
+            &config.key_transforms,
+            &source.key,
+            &config.key_separator,
+        );
+        first_call
+            .entry((source.file_path.as_str(), namespace, key))
+            .and_modify(|first| {
+                if (source.line, source.column) < (first.line, first.column) {
+                    *first = source;
+                }
+            })
+            .or_insert(source);
+    }
+    let mut first_file: HashMap<(&str, &str), &str> = HashMap::new();
+    for (file_path, keys) in files {
+        for key in keys {
+            let namespace = key.namespace.as_deref().unwrap_or(default_namespace);
+            first_file
+                .entry((namespace, key.key.as_str()))
+                .or_insert(file_path);
+        }
+    }
+    for message in messages {
+        first_file
+            .entry((default_namespace, message.key.as_str()))
+            .or_insert(&message.file_path);
+    }
+
+    let mut added = Vec::new();
+    for result in results {
+        for key in &result.added {
+            let source = first_file
+                .get(&(key.namespace.as_str(), key.key.as_str()))
+                .copied();
+            let call = source
+                .and_then(|path| first_call.get(&(path, key.namespace.as_str(), key.key.clone())));
+            added.push(AddedKeySource {
+                file: result.file_path.clone(),
+                namespace: key.namespace.clone(),
+                key: key.key.clone(),
+                source: source.map(str::to_string),
+                line: call.map(|call| call.line),
+                column: call.map(|call| call.column),
+            });
+        }
+    }
+    added
+}
+
+/// Write the locale files a run changed, with the added keys and where they
+/// came from, as a JSON artifact for review
+fn write_json_report(
+    path: &Path,
+    results: &[&json_sync::SyncResult],
+    sources: &[AddedKeySource],
+    dry_run: bool,
+) -> Result<()> {
+    let mut files: BTreeMap<&str, (Vec<serde_json::Value>, Vec<&str>)> = BTreeMap::new();
+    for added in sources {
+        files.entry(&added.file).or_default().0.push(json!({
+            "namespace": added.namespace,
+            "key": added.key,
+            "source": added.source,
+            "line": added.line,
+            "column": added.column,
+        }));
+    }
+    for result in results {
+        for key in &result.removed_keys {
+            files.entry(&result.file_path).or_default().1.push(key);
+        }
+    }
+    let updated_files: Vec<serde_json::Value> = files
+        .into_iter()
+        .map(|(file, (added, removed))| {
+            json!({ "file": file, "addedKeys": added, "removedKeys": removed })
+        })
+        .collect();
+    let report = json!({
+        "dryRun": dry_run,
+        "keysAdded": sources.len(),
+        "updatedFiles": updated_files,
+    });
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(&report)? + "\n")
+        .with_context(|| format!("Failed to write JSON report: {}", path.display()))?;
+    Ok(())
+}
+
+/// Write every extraction error (with parse positions) as a JSON artifact
+fn write_error_report(path: &Path, errors: &[ExtractionError]) -> Result<()> {
+    let report = json!({
+        "errorCount": errors.len(),
+        "fileCount": group_errors_by_file(errors).len(),
+        "errors": errors,
+    });
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(&report)? + "\n")
+        .with_context(|| format!("Failed to write error report: {}", path.display()))?;
+    Ok(())
+}
+
+/// (file, error count), most errors first
+fn group_errors_by_file(errors: &[ExtractionError]) -> Vec<(&str, usize)> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for error in errors {
+        *counts.entry(error.file_path.as_str()).or_default() += 1;
+    }
+    let mut grouped: Vec<(&str, usize)> = counts.into_iter().collect();
+    grouped.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    grouped
+}
+
+/// `dynamic-key: 2, parse-error: 1`, or `no extraction warnings`
+fn format_diagnostic_counts(extraction: &ExtractionResult) -> String {
+    let counts = extraction.diagnostic_counts();
+    if counts.is_empty() {
+        return "no extraction warnings".to_string();
+    }
+    counts
+        .iter()
+        .map(|(kind, count)| format!("{}: {}", kind, count))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn print_error_summary(
+    errors: &[ExtractionError],
+    error_report: Option<&Path>,
+    path_style: PathStyle,
+) {
+    if errors.is_empty() {
+        return;
+    }
+
+    let grouped = group_errors_by_file(errors);
+    eprintln!();
+    eprintln!(
+        "\x1b[31m✗ {} extraction error(s) in {} file(s). Top failing files:\x1b[0m",
+        errors.len(),
+        grouped.len()
+    );
+    for (file_path, count) in grouped.iter().take(TOP_FAILING_FILES) {
+        eprintln!("  {} ({} error(s))", file_path, count);
+    }
+    if grouped.len() > TOP_FAILING_FILES {
+        eprintln!(
+            "  ... and {} more file(s)",
+            grouped.len() - TOP_FAILING_FILES
+        );
+    }
+    match error_report {
+        Some(path) => eprintln!("  Full report: {}", paths::display(path, path_style)),
+        None => eprintln!("  Use --error-report <path.json> to save the full list."),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(file_path: &str) -> ExtractionError {
+        ExtractionError::glob(file_path.to_string(), "boom".to_string())
+    }
+
+    #[test]
+    fn group_errors_by_file_sorts_by_count() {
+        let errors = vec![error("b.ts"), error("a.ts"), error("b.ts"), error("c.ts")];
+        assert_eq!(
+            group_errors_by_file(&errors),
+            vec![("b.ts", 2), ("a.ts", 1), ("c.ts", 1)]
+        );
+    }
+}
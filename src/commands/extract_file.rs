@@ -0,0 +1,278 @@
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::config::Config;
+use crate::extractor::{self, ExtractedKey, ExtractionResult, KeySource};
+use crate::key_transforms;
+use crate::paths;
+
+/// An extracted key, as `keyTransforms` write it, and where it comes from
+struct LocatedKey {
+    key: ExtractedKey,
+    /// The key as written in the source, before `keyTransforms`
+    source_key: String,
+    /// 1-based line and column where the first call (or comment) producing the
+    /// key starts, then the line and column just past its end
+    span: Option<(usize, usize, usize, usize)>,
+}
+
+/// Extract exactly one file with the configured extraction options, ignoring
+/// `input` and `ignore`, and print its keys with locations and timing
+pub fn run(config: &Config, path: &Path, json_output: bool) -> Result<()> {
+    if !path.is_file() {
+        bail!("File not found: {}", path.display());
+    }
+    let source = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read: {}", path.display()))?;
+
+    let started = Instant::now();
+    let extraction = extract_one(config, path)?;
+    let elapsed = started.elapsed();
+
+    print_report(config, path, &source, &extraction, elapsed, json_output)
+}
+
+/// Extract source read from stdin as if it were the file at `path` and print
+/// the same JSON as `extract-file --json`; nothing is written
+pub fn run_stdin(config: &Config, path: &Path, fail_on_warnings: bool) -> Result<()> {
+    let source = std::io::read_to_string(std::io::stdin()).context("Failed to read stdin")?;
+
+    let started = Instant::now();
+    let extraction = extractor::ExtractRequest::builder()
+        .config(config)
+        .build()
+        .run_source(source.as_str(), path)?;
+    let elapsed = started.elapsed();
+
+    print_report(config, path, &source, &extraction, elapsed, true)?;
+    if extraction.dynamic_key_errors() > 0 {
+        bail!(
+            "{} dynamic template literal key(s) at error severity (dynamicKeySeverity / dynamicKeyRules)",
+            extraction.dynamic_key_errors()
+        );
+    }
+    if fail_on_warnings && extraction.total_warnings() > 0 {
+        bail!(
+            "{} warning(s) encountered (--fail-on-warnings enabled)",
+            extraction.total_warnings()
+        );
+    }
+    Ok(())
+}
+
+fn print_report(
+    config: &Config,
+    path: &Path,
+    source: &str,
+    extraction: &ExtractionResult,
+    elapsed: Duration,
+    json_output: bool,
+) -> Result<()> {
+    let mut keys: Vec<LocatedKey> = extraction
+        .files
+        .iter()
+        .flat_map(|(file_path, keys)| keys.iter().map(move |key| (file_path, key)))
+        .map(|(file_path, key)| {
+            let first = extraction
+                .key_sources
+                .iter()
+                .filter(|found| {
+                    found.file_path == *file_path
+                        && found.namespace == key.namespace
+                        && found.key == key.key
+                })
+                .min_by_key(|found| (found.line, found.column));
+            let mut transformed = key.clone();
+            transformed.key = key_transforms::transform_key(
+                &config.key_transforms,
+                &key.key,
+                &config.key_separator,
+            );
+            LocatedKey {
+                key: transformed,
+                source_key: key.key.clone(),
+                span: first.map(|found| span(source, found)),
+            }
+        })
+        .collect();
+    // Unlocated keys (e.g. from component blocks) go last
+    keys.sort_by_key(|k| {
+        k.span
+            .map_or((usize::MAX, usize::MAX), |(line, column, _, _)| {
+                (line, column)
+            })
+    });
+
+    if json_output {
+        let report = json_report(config, path, &keys, extraction, elapsed);
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_text(config, path, &keys, extraction, elapsed);
+    }
+    Ok(())
+}
+
+pub(crate) fn extract_one(config: &Config, path: &Path) -> Result<ExtractionResult> {
+    extractor::extract_with_config(
+        config,
+        &[glob::Pattern::escape(&path.to_string_lossy())],
+        &[],
+    )
+}
+
+/// Start and end (exclusive) line and column of the call recorded in `found`
+fn span(source: &str, found: &KeySource) -> (usize, usize, usize, usize) {
+    let (end_line, end_column) = source
+        .get(..found.span.end)
+        .map(|before| {
+            let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+            (
+                before.matches('\n').count() + 1,
+                before[line_start..].chars().count() + 1,
+            )
+        })
+        .unwrap_or((found.line, found.column));
+    (found.line, found.column, end_line, end_column)
+}
+
+fn display_key(config: &Config, key: &ExtractedKey) -> String {
+    match key.namespace.as_deref() {
+        Some(namespace) => format!("{}{}{}", namespace, config.ns_separator, key.key),
+        None => key.key.clone(),
+    }
+}
+
+fn print_text(
+    config: &Config,
+    path: &Path,
+    keys: &[LocatedKey],
+    extraction: &ExtractionResult,
+    elapsed: Duration,
+) {
+    println!("=== i18next-turbo extract-file ===\n");
+    println!("  File: {}", paths::display(path, config.path_style));
+    println!();
+
+    for located in keys {
+        let location = match located.span {
+            Some((line, column, _, _)) => format!("{}:{}", line, column),
+            None => "-".to_string(),
+        };
+        let mut notes = Vec::new();
+        if located.source_key != located.key.key {
+            notes.push(format!("from {}", located.source_key));
+        }
+        if let Some(default) = &located.key.default_value {
+            notes.push(format!("default: {}", Value::from(default.clone())));
+        }
+        let notes = if notes.is_empty() {
+            String::new()
+        } else {
+            format!("  ({})", notes.join(", "))
+        };
+        println!(
+            "  {:>8}  {}{}",
+            location,
+            display_key(config, &located.key),
+            notes
+        );
+    }
+    for dynamic in &extraction.dynamic_keys {
+        println!(
+            "  {:>8}  {}  (dynamic)",
+            format!("{}:-", dynamic.line),
+            dynamic.pattern
+        );
+    }
+    for error in &extraction.errors {
+        eprintln!("  {}", error);
+    }
+
+    println!(
+        "\nExtracted {} keys in {:.2}ms ({} warnings, {} errors)",
+        keys.len(),
+        elapsed.as_secs_f64() * 1000.0,
+        extraction.total_warnings(),
+        extraction.errors.len()
+    );
+}
+
+fn json_report(
+    config: &Config,
+    path: &Path,
+    keys: &[LocatedKey],
+    extraction: &ExtractionResult,
+    elapsed: Duration,
+) -> Value {
+    let keys: Vec<Value> = keys
+        .iter()
+        .map(|located| {
+            let span = located.span.map(|(line, column, end_line, end_column)| {
+                json!({
+                    "line": line,
+                    "column": column,
+                    "endLine": end_line,
+                    "endColumn": end_column,
+                })
+            });
+            json!({
+                "key": located.key.key,
+                "sourceKey": located.source_key,
+                "namespace": located
+                    .key
+                    .namespace
+                    .as_deref()
+                    .unwrap_or(config.effective_default_namespace()),
+                "defaultValue": located.key.default_value.clone().map(Value::from),
+                "span": span,
+            })
+        })
+        .collect();
+    let dynamic_keys: Vec<Value> = extraction
+        .dynamic_keys
+        .iter()
+        .map(|dynamic| json!({ "pattern": dynamic.pattern, "line": dynamic.line }))
+        .collect();
+
+    json!({
+        "file": path.to_string_lossy(),
+        "keys": keys,
+        "dynamicKeys": dynamic_keys,
+        "errors": extraction.errors,
+        "diagnostics": extraction.diagnostics,
+        "warnings": extraction.total_warnings(),
+        "elapsedMs": elapsed.as_secs_f64() * 1000.0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn span_ends_where_the_recorded_call_ends() {
+        let source = "const a = 1;\n  t('home.title', {\n    defaultValue: 'Home' });\n";
+        let start = source.find("t(").unwrap();
+        let end = source.find(");").unwrap() + 1;
+        let found = KeySource {
+            file_path: "a.ts".to_string(),
+            line: 2,
+            column: 3,
+            span: start..end,
+            namespace: None,
+            key: "home.title".to_string(),
+            generated_from: None,
+            default_value: None,
+            from_comment: false,
+        };
+        assert_eq!(span(source, &found), (2, 3, 3, 28));
+        // A span outside the source only keeps its start
+        let outside = KeySource {
+            span: 0..source.len() + 10,
+            ..found
+        };
+        assert_eq!(span(source, &outside), (2, 3, 2, 3));
+    }
+}
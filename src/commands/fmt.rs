@@ -0,0 +1,53 @@
+use anyhow::{bail, Context, Result};
+
+use crate::config::Config;
+use crate::fs::{FileSystem, RealFileSystem};
+use crate::json_sync;
+use crate::paths;
+
+/// Rewrite every locale file in the configured style (`indentation`, `minify`,
+/// `keySort`, `endOfLine`) without adding or removing keys. With `check`,
+/// only list the files that would change and fail if there are any.
+pub fn run(config: &Config, check: bool) -> Result<()> {
+    println!("=== i18next-turbo fmt ===\n");
+
+    let (mut checked, mut unformatted) = (0, 0);
+    for locale in &config.locales {
+        for path in json_sync::locale_files(config, &config.output, locale)? {
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read: {}", path.display()))?;
+            let formatted = json_sync::format_locale_content(config, &path, &content)?;
+            checked += 1;
+            if formatted == content {
+                continue;
+            }
+            unformatted += 1;
+            if check {
+                println!(
+                    "  {} - not formatted",
+                    paths::display(&path, config.path_style)
+                );
+            } else {
+                RealFileSystem.atomic_write(&path, formatted.as_bytes())?;
+                println!("  {} - formatted", paths::display(&path, config.path_style));
+            }
+        }
+    }
+
+    if unformatted > 0 {
+        println!();
+    }
+    if check && unformatted > 0 {
+        bail!(
+            "{} of {} locale file(s) not formatted (run `i18next-turbo fmt`)",
+            unformatted,
+            checked
+        );
+    }
+    if check {
+        println!("All {} locale file(s) are formatted.", checked);
+    } else {
+        println!("Formatted {} of {} locale file(s).", unformatted, checked);
+    }
+    Ok(())
+}
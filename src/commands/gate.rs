@@ -0,0 +1,330 @@
+use anyhow::{bail, Result};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+use crate::config::Config;
+use crate::json_sync;
+use crate::plurals;
+
+/// Number of missing namespaces listed for a failing locale
+const TOP_MISSING_NAMESPACES: usize = 5;
+
+/// Completeness of one locale measured against the primary locale
+#[derive(Debug, Clone, PartialEq)]
+struct LocaleCompleteness {
+    translated: usize,
+    total: usize,
+    /// (namespace, missing key count), most missing first
+    missing_by_namespace: Vec<(String, usize)>,
+}
+
+impl LocaleCompleteness {
+    fn ratio(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.translated as f64 / self.total as f64
+        }
+    }
+}
+
+pub fn run(config: &Config, locale: Option<String>) -> Result<()> {
+    println!("=== i18next-turbo gate ===\n");
+
+    let gates: Vec<(&String, &f64)> = config
+        .release_gates
+        .iter()
+        .filter(|(gate_locale, _)| locale.as_ref().is_none_or(|l| l == *gate_locale))
+        .collect();
+    if gates.is_empty() {
+        println!(
+            "No release gates configured. Add \"releaseGates\": {{ \"de\": 0.95 }} to your config."
+        );
+        return Ok(());
+    }
+
+    let primary_locale = config.primary_language();
+    let reference = json_sync::load_locale_namespaces(config, &config.output, primary_locale)?;
+
+    println!("Reference locale: {}", primary_locale);
+    println!();
+
+    let mut failed = Vec::new();
+    for (gate_locale, threshold) in gates {
+        let catalog = json_sync::load_locale_namespaces(config, &config.output, gate_locale)?;
+        let completeness =
+            compute_completeness(&reference, &catalog, gate_locale, plural_separator(config));
+        let ratio = completeness.ratio();
+        let passed = ratio >= *threshold;
+
+        println!(
+            "  {} {}: {:.1}% ({}/{}) required {:.1}%",
+            if passed {
+                "\x1b[32m✓\x1b[0m"
+            } else {
+                "\x1b[31m✗\x1b[0m"
+            },
+            gate_locale,
+            ratio * 100.0,
+            completeness.translated,
+            completeness.total,
+            threshold * 100.0
+        );
+
+        if !passed {
+            for (namespace, missing) in completeness
+                .missing_by_namespace
+                .iter()
+                .take(TOP_MISSING_NAMESPACES)
+            {
+                println!("      {}: {} missing", namespace, missing);
+            }
+            failed.push(gate_locale.clone());
+        }
+    }
+
+    println!();
+    if !failed.is_empty() {
+        bail!(
+            "Release gate failed for {} locale(s): {}",
+            failed.len(),
+            failed.join(", ")
+        );
+    }
+
+    println!("All release gates passed!");
+    Ok(())
+}
+
+/// Separator of CLDR plural families, `None` when plurals are disabled or use
+/// legacy suffixes that do not name categories
+fn plural_separator(config: &Config) -> Option<&str> {
+    (!config.disable_plurals && !config.plural_compat.is_legacy())
+        .then_some(config.plural_separator.as_str())
+}
+
+/// Completeness of `locale`'s `catalog` against the `reference` keys, with
+/// plural families expanded to the categories of `locale`
+fn compute_completeness(
+    reference: &BTreeMap<String, Map<String, Value>>,
+    catalog: &BTreeMap<String, Map<String, Value>>,
+    locale: &str,
+    plural_separator: Option<&str>,
+) -> LocaleCompleteness {
+    let mut translated = 0;
+    let mut total = 0;
+    let mut missing_by_namespace = Vec::new();
+    let empty = Map::new();
+
+    for (namespace, reference_map) in reference {
+        let mut leaves = Vec::new();
+        collect_leaf_paths(
+            reference_map,
+            locale,
+            plural_separator,
+            &mut Vec::new(),
+            &mut leaves,
+        );
+        let target = catalog.get(namespace).unwrap_or(&empty);
+
+        let namespace_translated = leaves
+            .iter()
+            .filter(|path| is_translated(lookup_path(target, path)))
+            .count();
+        total += leaves.len();
+        translated += namespace_translated;
+
+        let missing = leaves.len() - namespace_translated;
+        if missing > 0 {
+            missing_by_namespace.push((namespace.clone(), missing));
+        }
+    }
+
+    missing_by_namespace.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    LocaleCompleteness {
+        translated,
+        total,
+        missing_by_namespace,
+    }
+}
+
+/// Leaf paths of `map` that `locale` must translate. A plural family (sibling
+/// `base{separator}{category}` string keys including `other`, as in `check
+/// --plurals`) needs the categories of `locale`, not those of the reference
+/// locale, plus `zero` where the reference has it.
+fn collect_leaf_paths(
+    map: &Map<String, Value>,
+    locale: &str,
+    plural_separator: Option<&str>,
+    prefix: &mut Vec<String>,
+    out: &mut Vec<Vec<String>>,
+) {
+    let family_of = |key: &str| -> Option<(String, String)> {
+        let separator = plural_separator.filter(|separator| !separator.is_empty())?;
+        let (base, category) = key.rsplit_once(separator)?;
+        let is_family = !base.is_empty()
+            && matches!(category, "zero" | "one" | "two" | "few" | "many" | "other")
+            && map
+                .get(&format!("{}{}other", base, separator))
+                .is_some_and(Value::is_string);
+        is_family.then(|| (base.to_string(), category.to_string()))
+    };
+
+    let mut families: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (key, value) in map {
+        if json_sync::is_marker_key(key) {
+            continue;
+        }
+        if let Some((base, category)) = value.is_string().then(|| family_of(key)).flatten() {
+            families.entry(base).or_default().push(category);
+            continue;
+        }
+        prefix.push(key.clone());
+        match value {
+            Value::Object(nested) => {
+                collect_leaf_paths(nested, locale, plural_separator, prefix, out)
+            }
+            _ => out.push(prefix.clone()),
+        }
+        prefix.pop();
+    }
+
+    let separator = plural_separator.unwrap_or_default();
+    for (base, reference_categories) in families {
+        let ordinal = base.ends_with(&format!("{}ordinal", separator));
+        let mut categories = plurals::plural_categories(locale, ordinal)
+            .unwrap_or_else(|| reference_categories.clone());
+        if reference_categories
+            .iter()
+            .any(|category| category == "zero")
+            && !categories.iter().any(|category| category == "zero")
+        {
+            categories.insert(0, "zero".to_string());
+        }
+        for category in categories {
+            prefix.push(format!("{}{}{}", base, separator, category));
+            out.push(prefix.clone());
+            prefix.pop();
+        }
+    }
+}
+
+fn lookup_path<'a>(map: &'a Map<String, Value>, path: &[String]) -> Option<&'a Value> {
+    let (last, parents) = path.split_last()?;
+    let mut current = map;
+    for part in parents {
+        current = current.get(part)?.as_object()?;
+    }
+    current.get(last)
+}
+
+fn is_translated(value: Option<&Value>) -> bool {
+    match value {
+        Some(Value::String(s)) => !s.trim().is_empty(),
+        Some(Value::Null) | Some(Value::Object(_)) | None => false,
+        Some(_) => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn catalog(value: Value) -> BTreeMap<String, Map<String, Value>> {
+        value
+            .as_object()
+            .unwrap()
+            .iter()
+            .map(|(ns, v)| (ns.clone(), v.as_object().unwrap().clone()))
+            .collect()
+    }
+
+    #[test]
+    fn compute_completeness_counts_non_empty_values() {
+        let reference = catalog(json!({
+            "common": { "a": "A", "b": "B", "nested": { "c": "C" } },
+            "home": { "title": "Title" }
+        }));
+        let target = catalog(json!({
+            "common": { "a": "A!", "b": "", "nested": { "c": "C!" } }
+        }));
+
+        let result = compute_completeness(&reference, &target, "de", Some("_"));
+        assert_eq!(result.total, 4);
+        assert_eq!(result.translated, 2);
+        assert_eq!(
+            result.missing_by_namespace,
+            vec![("common".to_string(), 1), ("home".to_string(), 1)]
+        );
+        assert!((result.ratio() - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn marker_entries_are_not_counted() {
+        let reference = catalog(json!({
+            "translation": {
+                "//": "Dynamic keys",
+                "dyn": { "__preserve__": ["*"], "x": "X" },
+                "title": "Title"
+            }
+        }));
+        let target = catalog(json!({ "translation": { "title": "Titel" } }));
+
+        let result = compute_completeness(&reference, &target, "de", Some("_"));
+        assert_eq!(result.total, 2);
+        assert_eq!(result.translated, 1);
+    }
+
+    #[test]
+    fn empty_reference_is_complete() {
+        let result = compute_completeness(&BTreeMap::new(), &BTreeMap::new(), "de", Some("_"));
+        assert_eq!(result.ratio(), 1.0);
+    }
+
+    #[test]
+    fn plural_families_need_the_categories_of_the_target_locale() {
+        let reference = catalog(json!({
+            "cart": {
+                "item_one": "{{count}} item",
+                "item_other": "{{count}} items",
+                "place_ordinal_one": "{{count}}st",
+                "place_ordinal_two": "{{count}}nd",
+                "place_ordinal_few": "{{count}}rd",
+                "place_ordinal_other": "{{count}}th",
+                "title": "Cart"
+            }
+        }));
+
+        // Polish needs one, few, many and other; its ordinals only other
+        let target = catalog(json!({
+            "cart": {
+                "item_one": "{{count}} produkt",
+                "item_few": "{{count}} produkty",
+                "item_other": "{{count}} produktu",
+                "place_ordinal_other": "{{count}}.",
+                "title": "Koszyk"
+            }
+        }));
+        let result = compute_completeness(&reference, &target, "pl", Some("_"));
+        assert_eq!(result.total, 6);
+        assert_eq!(result.translated, 5);
+        assert_eq!(result.missing_by_namespace, vec![("cart".to_string(), 1)]);
+
+        // Japanese has a single category, so the English `one` is not missing
+        let target = catalog(json!({
+            "cart": {
+                "item_other": "{{count}} 個",
+                "place_ordinal_other": "{{count}} 番目",
+                "title": "カート"
+            }
+        }));
+        let result = compute_completeness(&reference, &target, "ja", Some("_"));
+        assert_eq!((result.translated, result.total), (3, 3));
+
+        // Without plurals, suffixed keys are plain keys
+        let result = compute_completeness(&reference, &target, "ja", None);
+        assert_eq!((result.translated, result.total), (3, 7));
+    }
+}
@@ -0,0 +1,189 @@
+use anyhow::{bail, Context, Result};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::extractor::{self, ExtractionResult};
+use crate::git;
+use crate::json_sync;
+use crate::key_transforms;
+
+/// Pre-commit entry point: extract from staged (or given) files only and stage locale updates.
+pub fn run(config: &Config, staged: bool, files: Vec<String>, dry_run: bool) -> Result<()> {
+    let candidates = if staged {
+        git::lines(&[
+            "diff",
+            "--cached",
+            "--name-only",
+            "--relative",
+            "--diff-filter=ACMR",
+        ])?
+    } else {
+        files
+    };
+    if candidates.is_empty() && !staged {
+        bail!("No files given. Pass file paths or use --staged to read them from git");
+    }
+
+    verify_staged_locale_files(config, &candidates)?;
+
+    let source_files: Vec<String> = candidates
+        .iter()
+        // Staged files are read from the index, even when deleted from the working copy
+        .filter(|file| staged || Path::new(file).is_file())
+        .filter(|file| extractor::matches_input_patterns(Path::new(file), &config.input))
+        .cloned()
+        .collect();
+    if source_files.is_empty() {
+        return Ok(());
+    }
+
+    let mut extraction = if staged {
+        extract_staged(config, &source_files)?
+    } else {
+        let patterns: Vec<String> = source_files
+            .iter()
+            .map(|file| glob::Pattern::escape(file))
+            .collect();
+        extractor::extract_with_config(config, &patterns, &config.extraction_ignore())?
+    };
+    key_transforms::apply(config, &mut extraction.files);
+    for error in &extraction.errors {
+        eprintln!("  {}", error);
+    }
+
+    if extraction.files.iter().all(|(_, keys)| keys.is_empty()) {
+        return Ok(());
+    }
+    // Keys of a package with its own `outputs` root go there, as in `extract`
+    let groups = json_sync::group_keys_by_output(
+        config,
+        extraction.files.iter().map(|(path, keys)| (path, keys)),
+        &config.output,
+    );
+
+    // Only a subset of the sources is visible here, so never prune keys owned by other files.
+    let mut hook_config = config.clone();
+    hook_config.remove_unused_keys = false;
+    let sync = |dry_run: bool| -> Result<Vec<json_sync::SyncResult>> {
+        let mut results = Vec::new();
+        for (output, keys) in &groups {
+            results.extend(json_sync::sync_all_locales(
+                &hook_config,
+                keys,
+                output,
+                dry_run,
+            )?);
+        }
+        Ok(results)
+    };
+
+    let preview = sync(true)?;
+    let targets: BTreeSet<String> = preview
+        .iter()
+        .filter(|result| !result.added_keys.is_empty())
+        .map(|result| result.file_path.clone())
+        .collect();
+    if targets.is_empty() {
+        return Ok(());
+    }
+
+    if dry_run {
+        for target in &targets {
+            println!("Would update and stage {}", target);
+        }
+        return Ok(());
+    }
+
+    if staged {
+        let unstaged: BTreeSet<String> = git::lines(&["diff", "--name-only", "--relative"])?
+            .into_iter()
+            .collect();
+        let partially_staged: Vec<&String> = targets
+            .iter()
+            .filter(|target| unstaged.contains(normalize_path(target)))
+            .collect();
+        if !partially_staged.is_empty() {
+            bail!(
+                "Locale file(s) have unstaged changes and cannot be staged automatically: {}",
+                partially_staged
+                    .iter()
+                    .map(|s| s.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+    }
+
+    let results = sync(false)?;
+    let mut to_stage = Vec::new();
+    for result in &results {
+        if !result.added_keys.is_empty() {
+            println!(
+                "  {} - added {} new key(s)",
+                result.file_path,
+                result.added_keys.len()
+            );
+            to_stage.push(result.file_path.clone());
+        }
+    }
+
+    if staged && !to_stage.is_empty() {
+        let mut args = vec!["add", "--"];
+        args.extend(to_stage.iter().map(|s| s.as_str()));
+        git::lines(&args)?;
+    }
+
+    Ok(())
+}
+
+/// Extract each file as staged, so unstaged edits never reach the locale files.
+fn extract_staged(config: &Config, files: &[String]) -> Result<ExtractionResult> {
+    let request = extractor::ExtractRequest::builder().config(config).build();
+    let ignore = config.extraction_ignore();
+    let mut extraction = ExtractionResult::default();
+    for file in files {
+        let path = Path::new(normalize_path(file));
+        if extractor::matches_input_patterns(path, &ignore) {
+            continue;
+        }
+        let source = git::output(&["show", &format!(":./{}", path.display())])?;
+        let result = request.run_source(source, path)?;
+        extraction.files.extend(result.files);
+        extraction.errors.extend(result.errors);
+    }
+    Ok(extraction)
+}
+
+/// Make sure every staged locale file parses, so a broken merge never reaches a commit.
+fn verify_staged_locale_files(config: &Config, files: &[String]) -> Result<()> {
+    let output_root = Path::new(normalize_path(&config.output));
+    let extension = config.output_extension();
+
+    for file in files {
+        let path = Path::new(normalize_path(file));
+        let is_locale_file = path.starts_with(output_root)
+            && path.extension().and_then(|e| e.to_str()) == Some(extension);
+        if !is_locale_file {
+            continue;
+        }
+
+        let content = match git::output(&["show", &format!(":./{}", path.display())]) {
+            Ok(content) => content,
+            // Not in the index (e.g. the hook was given plain paths); check the working copy.
+            Err(_) => std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read: {}", path.display()))?,
+        };
+        if content.trim().is_empty() {
+            continue;
+        }
+        json_sync::parse_locale_value_str(&content, config.output_format(), path)
+            .with_context(|| format!("Staged locale file is invalid: {}", path.display()))?;
+    }
+
+    Ok(())
+}
+
+fn normalize_path(path: &str) -> &str {
+    path.strip_prefix("./").unwrap_or(path)
+}
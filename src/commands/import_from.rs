@@ -0,0 +1,273 @@
+use anyhow::{bail, Context, Result};
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::git;
+use crate::json_sync;
+use crate::paths;
+
+/// Where translations are imported from
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ImportSource {
+    /// A locales root on disk (another checkout or an export)
+    Directory(PathBuf),
+    /// A git ref whose tree contains the configured output directory
+    GitRef { reference: String, output: String },
+}
+
+impl ImportSource {
+    fn resolve(config: &Config, source: &str) -> Result<Self> {
+        let path = Path::new(source);
+        if path.is_dir() {
+            // Accept either a checkout root or the locales directory itself.
+            let nested = path.join(&config.output);
+            if nested.is_dir() {
+                return Ok(ImportSource::Directory(nested));
+            }
+            return Ok(ImportSource::Directory(path.to_path_buf()));
+        }
+
+        if git::output(&[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{}^{{tree}}", source),
+        ])
+        .is_err()
+        {
+            bail!(
+                "Import source not found: '{}' is neither a directory nor a git ref",
+                source
+            );
+        }
+        Ok(ImportSource::GitRef {
+            reference: source.to_string(),
+            output: config.output.trim_start_matches("./").to_string(),
+        })
+    }
+
+    /// Where the source keeps the locale file at `relative` (its path below the
+    /// output directory), for messages
+    fn describe(&self, relative: &Path) -> String {
+        match self {
+            ImportSource::Directory(root) => root.join(relative).display().to_string(),
+            ImportSource::GitRef { reference, output } => {
+                format!("{}:./{}", reference, git_path(output, relative))
+            }
+        }
+    }
+
+    /// Read the locale file at `relative` (its path below the output directory,
+    /// so flat and merged layouts resolve like the local files), `None` when it
+    /// does not exist in the source
+    fn read(&self, relative: &Path) -> Option<String> {
+        match self {
+            ImportSource::Directory(root) => std::fs::read_to_string(root.join(relative)).ok(),
+            ImportSource::GitRef { reference, output } => git::output(&[
+                "show",
+                &format!("{}:./{}", reference, git_path(output, relative)),
+            ])
+            .ok(),
+        }
+    }
+}
+
+/// `relative` below `output` as a git tree path, which always uses `/`
+fn git_path(output: &str, relative: &Path) -> String {
+    let relative = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    if output.is_empty() || output == "." {
+        relative
+    } else {
+        format!("{}/{}", output.trim_end_matches('/'), relative)
+    }
+}
+
+/// Copy translations for keys that are empty locally but filled in `source`.
+/// Keys missing locally and values that differ are never touched.
+pub fn run(config: &Config, source: &str, locale: Option<String>, dry_run: bool) -> Result<()> {
+    println!("=== i18next-turbo import-from ===\n");
+
+    let import_source = ImportSource::resolve(config, source)?;
+    let locales: Vec<&String> = config
+        .locales
+        .iter()
+        .filter(|l| locale.as_ref().is_none_or(|wanted| wanted == *l))
+        .collect();
+    if locales.is_empty() {
+        bail!(
+            "Locale '{}' is not configured",
+            locale.as_deref().unwrap_or_default()
+        );
+    }
+
+    let format = config.output_format();
+    let mut total_imported = 0;
+
+    let explicit_locale = locale.is_some();
+    let mut missing = Vec::new();
+    let mut found_any = false;
+    for locale in locales {
+        let paths = json_sync::locale_files(config, &config.output, locale)?;
+        let mut found = false;
+        for path in &paths {
+            let Ok(relative) = path.strip_prefix(&config.output) else {
+                continue;
+            };
+            let file_name = paths::display(relative, config.path_style);
+            let Some(source_content) = import_source.read(relative) else {
+                continue;
+            };
+            found = true;
+            let source_value = json_sync::parse_locale_value_str(&source_content, format, relative)
+                .with_context(|| format!("Failed to parse import source for {}", file_name))?;
+            let Value::Object(source_map) = source_value else {
+                continue;
+            };
+
+            let content = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read: {}", path.display()))?;
+            let Value::Object(mut local_map) =
+                json_sync::parse_locale_value_str(&content, format, path)?
+            else {
+                continue;
+            };
+
+            let imported = fill_empty_values(&mut local_map, &source_map);
+            if imported == 0 {
+                continue;
+            }
+            total_imported += imported;
+
+            if dry_run {
+                println!("  {} - would import {} value(s)", file_name, imported);
+            } else {
+                let style = json_sync::detect_json_style(&content);
+                json_sync::write_locale_file(
+                    path,
+                    &local_map,
+                    format,
+                    config.key_sort,
+                    Some(&style),
+                )?;
+                println!("  {} - imported {} value(s)", file_name, imported);
+            }
+        }
+        found_any |= found;
+        if let (false, Some(first)) = (found, paths.first()) {
+            let expected = first
+                .strip_prefix(&config.output)
+                .map(|relative| import_source.describe(relative))
+                .unwrap_or_default();
+            missing.push(format!("{} (expected {})", locale, expected));
+        }
+    }
+
+    // A source laid out differently would otherwise import nothing silently
+    if !missing.is_empty() && (explicit_locale || !found_any) {
+        bail!(
+            "No locale files found in import source '{}' for: {}",
+            source,
+            missing.join(", ")
+        );
+    }
+    for locale in &missing {
+        eprintln!("Warning: no locale files in import source for {}", locale);
+    }
+
+    println!();
+    if total_imported == 0 {
+        println!("No missing translations found in {}", source);
+    } else if dry_run {
+        println!(
+            "Would import {} translation(s) (dry run, no files written)",
+            total_imported
+        );
+    } else {
+        println!("Imported {} translation(s) from {}", total_imported, source);
+    }
+
+    Ok(())
+}
+
+/// Fill empty string leaves of `target` from non-empty strings at the same path in `source`
+fn fill_empty_values(target: &mut Map<String, Value>, source: &Map<String, Value>) -> usize {
+    let mut imported = 0;
+    for (key, value) in target.iter_mut() {
+        match (value, source.get(key)) {
+            (Value::Object(nested), Some(Value::Object(source_nested))) => {
+                imported += fill_empty_values(nested, source_nested);
+            }
+            (Value::String(current), Some(Value::String(translated)))
+                if current.trim().is_empty() && !translated.trim().is_empty() =>
+            {
+                *current = translated.clone();
+                imported += 1;
+            }
+            _ => {}
+        }
+    }
+    imported
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn map(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn fill_empty_values_only_touches_empty_leaves() {
+        let mut target = map(json!({
+            "empty": "",
+            "differs": "Local",
+            "nested": { "empty": "", "kept": "" },
+            "local_only": ""
+        }));
+        let source = map(json!({
+            "empty": "Imported",
+            "differs": "Remote",
+            "nested": { "empty": "Nested", "kept": "" },
+            "source_only": "Ignored"
+        }));
+
+        assert_eq!(fill_empty_values(&mut target, &source), 2);
+        assert_eq!(
+            Value::Object(target),
+            json!({
+                "empty": "Imported",
+                "differs": "Local",
+                "nested": { "empty": "Nested", "kept": "" },
+                "local_only": ""
+            })
+        );
+    }
+
+    #[test]
+    fn git_paths_keep_the_local_layout_below_the_output() {
+        assert_eq!(
+            git_path("messages", Path::new("de.json")),
+            "messages/de.json"
+        );
+        assert_eq!(
+            git_path("locales/", &Path::new("de").join("common.json")),
+            "locales/de/common.json"
+        );
+        assert_eq!(git_path(".", Path::new("de.json")), "de.json");
+    }
+
+    #[test]
+    fn fill_empty_values_ignores_shape_mismatches() {
+        let mut target = map(json!({ "a": "", "b": { "c": "" } }));
+        let source = map(json!({ "a": { "x": "X" }, "b": "B" }));
+
+        assert_eq!(fill_empty_values(&mut target, &source), 0);
+    }
+}
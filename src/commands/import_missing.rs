@@ -0,0 +1,316 @@
+use anyhow::{bail, Context, Result};
+use glob::Pattern;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use crate::commands::set_value;
+use crate::config::{Config, OutputFormat};
+use crate::extractor::ExtractedKey;
+use crate::json_sync::{self, PRESERVE_MARKER_KEY};
+use crate::paths;
+
+/// Merge the keys of an i18next `saveMissing` log (one JSON object per line)
+/// into the catalogs. Each key is added to every locale and listed in a
+/// `__preserve__` marker, which flags it as found at runtime and keeps later
+/// extractions from pruning it as unused.
+pub fn run(config: &Config, log: &Path, dry_run: bool) -> Result<()> {
+    println!("=== i18next-turbo import-missing ===\n");
+
+    let content = std::fs::read_to_string(log)
+        .with_context(|| format!("Failed to read: {}", log.display()))?;
+    let (mut entries, skipped) = parse_log(config, &content);
+    for line in &skipped {
+        eprintln!(
+            "  Warning: skipped line {} (expected a JSON object with a \"key\")",
+            line
+        );
+    }
+    // Logs are written by clients, so a namespace may try to leave the output directory
+    entries.retain(|(namespace, key), _| {
+        let plain = paths::is_plain_file_name(namespace);
+        if !plain {
+            eprintln!(
+                "  Warning: skipped '{}' in namespace '{}' (not a plain file name)",
+                key, namespace
+            );
+        }
+        plain
+    });
+    if entries.is_empty() {
+        println!(
+            "No missing keys in {}.",
+            paths::display(log, config.path_style)
+        );
+        return Ok(());
+    }
+    println!(
+        "Importing {} runtime key(s) from {}...",
+        entries.len(),
+        paths::display(log, config.path_style)
+    );
+
+    let outputs = NamespaceOutputs::load(config)?;
+    let mut groups: BTreeMap<&str, Vec<ExtractedKey>> = BTreeMap::new();
+    for ((namespace, key), fallback) in &entries {
+        groups
+            .entry(outputs.output_of(namespace))
+            .or_default()
+            .push(ExtractedKey {
+                key: key.clone(),
+                namespace: Some(namespace.clone()),
+                default_value: fallback.clone().map(Into::into),
+            });
+    }
+    // The log only names the missing keys, so nothing else may be pruned
+    let mut merge_config = config.clone();
+    merge_config.remove_unused_keys = false;
+    let mut results = Vec::new();
+    for (output, keys) in &groups {
+        results.extend(json_sync::sync_all_locales(
+            &merge_config,
+            keys,
+            output,
+            dry_run,
+        )?);
+    }
+
+    let mut total_added = 0;
+    for result in &results {
+        if !result.added_keys.is_empty() {
+            let action = if dry_run { "would add" } else { "added" };
+            println!(
+                "  {} - {} {} key(s)",
+                result.file_path,
+                action,
+                result.added_keys.len()
+            );
+            total_added += result.added_keys.len();
+        }
+        for conflict in &result.conflicts {
+            eprintln!(
+                "  Warning: skipped '{}' in {}: {}",
+                conflict.key, result.file_path, conflict.conflict
+            );
+        }
+    }
+
+    let mut marked = 0;
+    if !dry_run {
+        for locale in &config.locales {
+            for (namespace, key) in entries.keys() {
+                let path = json_sync::locale_namespace_file_path(
+                    config,
+                    outputs.output_of(namespace),
+                    locale,
+                    namespace,
+                );
+                marked += usize::from(mark_preserved(config, &path, namespace, key)?);
+            }
+        }
+    }
+
+    println!();
+    if dry_run {
+        println!(
+            "Would import {} key(s) (dry run, no files written)",
+            total_added
+        );
+    } else {
+        println!(
+            "Imported {} key(s); {} preserve marker(s) added",
+            total_added, marked
+        );
+    }
+    Ok(())
+}
+
+/// Output roots of the namespaces in `outputs`. A log names no source files, so
+/// a key of a namespace that only an `outputs` root has (in its primary locale)
+/// goes there, where extraction put the other keys of the namespace. Every other
+/// key goes to the global output.
+struct NamespaceOutputs<'a> {
+    default_output: &'a str,
+    roots: Vec<(&'a str, BTreeSet<String>)>,
+}
+
+impl<'a> NamespaceOutputs<'a> {
+    fn load(config: &'a Config) -> Result<Self> {
+        let primary = config.primary_language();
+        let global: BTreeSet<String> =
+            json_sync::load_locale_namespaces(config, &config.output, primary)?
+                .into_keys()
+                .collect();
+        let mut roots = Vec::new();
+        for target in &config.outputs {
+            let namespaces = json_sync::load_locale_namespaces(config, &target.output, primary)?;
+            let own = namespaces
+                .into_keys()
+                .filter(|namespace| !global.contains(namespace))
+                .collect();
+            roots.push((target.output.as_str(), own));
+        }
+        Ok(Self {
+            default_output: &config.output,
+            roots,
+        })
+    }
+
+    fn output_of(&self, namespace: &str) -> &'a str {
+        self.roots
+            .iter()
+            .find(|(_, namespaces)| namespaces.contains(namespace))
+            .map_or(self.default_output, |(output, _)| output)
+    }
+}
+
+/// Fallback value of each missing (namespace, key)
+type MissingKeys = BTreeMap<(String, String), Option<String>>;
+
+/// Missing keys of a `saveMissing` log by (namespace, key), with the first
+/// fallback value logged for each, and the numbers of unreadable lines.
+///
+/// Each line is an object such as `{"lng":["ja"],"ns":"common","key":"save",
+/// "fallbackValue":"Save"}`. Without `ns`, a `namespace:key` prefix or the
+/// default namespace applies. Language fields are ignored: a key missing in one
+/// locale is added to all of them.
+fn parse_log(config: &Config, content: &str) -> (MissingKeys, Vec<usize>) {
+    let mut entries = MissingKeys::new();
+    let mut skipped = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Ok(Value::Object(record)) = serde_json::from_str::<Value>(line) else {
+            skipped.push(i + 1);
+            continue;
+        };
+        let Some(key) = record
+            .get("key")
+            .and_then(Value::as_str)
+            .filter(|key| !key.is_empty())
+        else {
+            skipped.push(i + 1);
+            continue;
+        };
+        let namespace = record
+            .get("ns")
+            .or_else(|| record.get("namespace"))
+            .and_then(|ns| match ns {
+                Value::Array(namespaces) => namespaces.first().and_then(Value::as_str),
+                ns => ns.as_str(),
+            });
+        let (namespace, key) = match namespace {
+            Some(namespace) => (namespace.to_string(), key.to_string()),
+            None => set_value::split_key(config, key),
+        };
+        let fallback = record
+            .get("fallbackValue")
+            .or_else(|| record.get("defaultValue"))
+            .and_then(Value::as_str)
+            .filter(|value| !value.is_empty() && *value != key)
+            .map(str::to_string);
+
+        let entry = entries.entry((namespace, key)).or_default();
+        if entry.is_none() {
+            *entry = fallback;
+        }
+    }
+    (entries, skipped)
+}
+
+/// List `key` in the `__preserve__` marker of its parent object in `path`.
+/// Returns whether the file changed.
+fn mark_preserved(config: &Config, path: &Path, namespace: &str, key: &str) -> Result<bool> {
+    if !path.exists() {
+        return Ok(false);
+    }
+    let format = config.output_format();
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read: {}", path.display()))?;
+    let Value::Object(mut map) = json_sync::parse_locale_value_str(&content, format, path)? else {
+        bail!("Locale file root must be an object: {}", path.display());
+    };
+    let parts = json_sync::key_parts(config, namespace, key);
+    let Some((last, parents)) = parts.split_last() else {
+        return Ok(false);
+    };
+    if !add_marker(&mut map, parents, &Pattern::escape(last)) {
+        return Ok(false);
+    }
+    let style =
+        (format == OutputFormat::Json).then(|| json_sync::configured_json_style(config, &content));
+    json_sync::write_locale_file(path, &map, format, config.key_sort, style.as_ref())?;
+    Ok(true)
+}
+
+/// Add `pattern` to the marker of the object at `parents`. Returns false when
+/// the object is missing or already lists the pattern.
+fn add_marker(map: &mut Map<String, Value>, parents: &[String], pattern: &str) -> bool {
+    let mut node = map;
+    for part in parents {
+        match node.get_mut(part) {
+            Some(Value::Object(nested)) => node = nested,
+            _ => return false,
+        }
+    }
+    let Value::Array(patterns) = node
+        .entry(PRESERVE_MARKER_KEY)
+        .or_insert_with(|| Value::Array(Vec::new()))
+    else {
+        return false;
+    };
+    if patterns.iter().any(|entry| entry.as_str() == Some(pattern)) {
+        return false;
+    }
+    patterns.push(Value::String(pattern.to_string()));
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_log_dedupes_keys_per_namespace() {
+        let config = Config::default();
+        let log = concat!(
+            "{\"lng\":[\"ja\"],\"ns\":\"common\",\"key\":\"save\",\"fallbackValue\":\"save\"}\n",
+            "{\"lng\":[\"de\"],\"ns\":\"common\",\"key\":\"save\",\"fallbackValue\":\"Save\"}\n",
+            "{\"lng\":\"en\",\"key\":\"common:cancel\"}\n",
+            "{\"key\":\"errors.404\",\"defaultValue\":\"Not found\"}\n",
+            "\n",
+            "not json\n",
+            "{\"ns\":\"common\"}\n",
+        );
+
+        let (entries, skipped) = parse_log(&config, log);
+        let entries: Vec<_> = entries
+            .iter()
+            .map(|((ns, key), value)| (ns.as_str(), key.as_str(), value.as_deref()))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("common", "cancel", None),
+                ("common", "save", Some("Save")),
+                ("translation", "errors.404", Some("Not found")),
+            ]
+        );
+        assert_eq!(skipped, vec![6, 7]);
+    }
+
+    #[test]
+    fn add_marker_lists_the_key_once_in_its_parent() {
+        let mut map = json!({ "errors": { "404": "" } })
+            .as_object()
+            .unwrap()
+            .clone();
+        let parents = vec!["errors".to_string()];
+        assert!(add_marker(&mut map, &parents, "404"));
+        assert!(!add_marker(&mut map, &parents, "404"));
+        assert!(!add_marker(&mut map, &["missing".to_string()], "x"));
+        assert_eq!(map["errors"]["__preserve__"], json!(["404"]));
+    }
+}
@@ -0,0 +1,512 @@
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{Map, Number, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use swc_common::sync::Lrc;
+use swc_common::{FileName, SourceMap};
+use swc_ecma_ast::{
+    Decl, Expr, Lit, Module, ModuleDecl, ModuleItem, Pat, Prop, PropName, PropOrSpread, Stmt,
+};
+use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsSyntax};
+
+use crate::config::Config;
+use crate::json_sync;
+use crate::paths;
+
+/// Split a bundled i18next resources object (`{ en: { translation: {...} } }`) exported
+/// from a JS/TS file into per-locale, per-namespace locale files. Values already present
+/// in the locale files are kept.
+pub fn run(config: &Config, source: &Path, dry_run: bool) -> Result<()> {
+    println!("=== i18next-turbo ingest ===\n");
+
+    let content = std::fs::read_to_string(source)
+        .with_context(|| format!("Failed to read: {}", source.display()))?;
+    let (resources, skipped) = parse_resources(&content, source)?;
+    for path in &skipped {
+        eprintln!(
+            "  Warning: skipped non-literal value at {} (only literals can be ingested)",
+            path
+        );
+    }
+
+    // Group namespaces by target file; with mergeNamespaces one file holds them all.
+    let mut targets: BTreeMap<PathBuf, Map<String, Value>> = BTreeMap::new();
+    for (locale, namespaces) in resources {
+        let Value::Object(namespaces) = namespaces else {
+            bail!(
+                "Resources for locale '{}' must be an object of namespaces",
+                locale
+            );
+        };
+        if !config.locales.contains(&locale) {
+            println!(
+                "  Note: locale '{}' is not listed in \"locales\" of your config",
+                locale
+            );
+        }
+        for (namespace, values) in namespaces {
+            let Value::Object(values) = values else {
+                bail!(
+                    "Namespace '{}' of locale '{}' must be an object",
+                    namespace,
+                    locale
+                );
+            };
+            json_sync::ensure_plain_path_names(&locale, &namespace)?;
+            let path =
+                json_sync::locale_namespace_file_path(config, &config.output, &locale, &namespace);
+            let target = targets.entry(path).or_default();
+            if config.merge_namespaces && !config.namespace_less_mode() {
+                target.extend(merged_namespace_entries(config, &namespace, values));
+            } else {
+                target.extend(values);
+            }
+        }
+    }
+
+    let format = config.output_format();
+    let mut total_added = 0;
+    let mut total_kept = 0;
+    for (path, values) in targets {
+        let (mut map, style) = if path.exists() {
+            let existing = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read: {}", path.display()))?;
+            let Value::Object(map) = json_sync::parse_locale_value_str(&existing, format, &path)?
+            else {
+                bail!("Locale file root must be an object: {}", path.display());
+            };
+            (map, Some(json_sync::detect_json_style(&existing)))
+        } else {
+            (Map::new(), None)
+        };
+
+        let (added, kept) = merge_missing(&mut map, values);
+        total_added += added;
+        total_kept += kept;
+        if added == 0 {
+            continue;
+        }
+
+        if dry_run {
+            println!(
+                "  {} - would add {} value(s)",
+                paths::display(&path, config.path_style),
+                added
+            );
+        } else {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create: {}", parent.display()))?;
+            }
+            json_sync::write_locale_file(&path, &map, format, config.key_sort, style.as_ref())?;
+            println!(
+                "  {} - added {} value(s)",
+                paths::display(&path, config.path_style),
+                added
+            );
+        }
+    }
+
+    println!();
+    if total_kept > 0 {
+        println!(
+            "Kept {} existing value(s) that differ from {}",
+            total_kept,
+            paths::display(source, config.path_style)
+        );
+    }
+    if dry_run {
+        println!(
+            "Would ingest {} value(s) (dry run, no files written)",
+            total_added
+        );
+    } else {
+        println!(
+            "Ingested {} value(s) from {}",
+            total_added,
+            paths::display(source, config.path_style)
+        );
+    }
+
+    Ok(())
+}
+
+/// Evaluate the resources object exported by `source`.
+/// Returns the locale map and the dotted paths of values that are not literals.
+fn parse_resources(source: &str, path: &Path) -> Result<(Map<String, Value>, Vec<String>)> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(
+        FileName::Real(path.to_path_buf()).into(),
+        source.to_string(),
+    );
+    let is_tsx = path
+        .extension()
+        .is_some_and(|ext| ext == "tsx" || ext == "jsx");
+    let lexer = Lexer::new(
+        Syntax::Typescript(TsSyntax {
+            tsx: is_tsx,
+            ..Default::default()
+        }),
+        Default::default(),
+        StringInput::from(&*fm),
+        None,
+    );
+    let module = Parser::new_from(lexer)
+        .parse_module()
+        .map_err(|e| anyhow!("Failed to parse {}: {:?}", path.display(), e.kind()))?;
+
+    let bindings = top_level_bindings(&module);
+    let root = exported_resources(&module, &bindings).ok_or_else(|| {
+        anyhow!(
+            "No resources object found in {} (expected `export default`, `module.exports` or a `resources` export)",
+            path.display()
+        )
+    })?;
+
+    let mut skipped = Vec::new();
+    match evaluate(root, &bindings, "", &mut skipped, 0) {
+        Some(Value::Object(map)) => Ok((map, skipped)),
+        _ => bail!(
+            "The resources export of {} is not an object",
+            path.display()
+        ),
+    }
+}
+
+/// `const`/`let` declarations at module level, including exported ones
+fn top_level_bindings(module: &Module) -> HashMap<String, &Expr> {
+    let mut bindings = HashMap::new();
+    for item in &module.body {
+        let decl = match item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => var,
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => match &export.decl {
+                Decl::Var(var) => var,
+                _ => continue,
+            },
+            _ => continue,
+        };
+        for declarator in &decl.decls {
+            if let (Pat::Ident(ident), Some(init)) = (&declarator.name, &declarator.init) {
+                bindings.insert(ident.id.sym.to_string(), init.as_ref());
+            }
+        }
+    }
+    bindings
+}
+
+/// The default export, `module.exports = ...`, or a binding named `resources`
+fn exported_resources<'a>(
+    module: &'a Module,
+    bindings: &HashMap<String, &'a Expr>,
+) -> Option<&'a Expr> {
+    for item in &module.body {
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export)) => {
+                return Some(export.expr.as_ref());
+            }
+            ModuleItem::Stmt(Stmt::Expr(stmt)) => {
+                if let Expr::Assign(assign) = stmt.expr.as_ref() {
+                    let is_module_exports = assign
+                        .left
+                        .as_simple()
+                        .and_then(|target| target.as_member())
+                        .is_some_and(|member| {
+                            member
+                                .obj
+                                .as_ident()
+                                .is_some_and(|obj| &*obj.sym == "module")
+                                && member
+                                    .prop
+                                    .as_ident()
+                                    .is_some_and(|prop| &*prop.sym == "exports")
+                        });
+                    if is_module_exports {
+                        return Some(assign.right.as_ref());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    bindings.get("resources").copied()
+}
+
+/// Convert a literal expression to JSON, following references to top-level bindings.
+/// Non-literal values are recorded in `skipped` and left out.
+fn evaluate(
+    expr: &Expr,
+    bindings: &HashMap<String, &Expr>,
+    path: &str,
+    skipped: &mut Vec<String>,
+    depth: usize,
+) -> Option<Value> {
+    // Guards against self-referencing bindings.
+    if depth > 64 {
+        skipped.push(path.to_string());
+        return None;
+    }
+    let value = match expr {
+        Expr::Paren(paren) => return evaluate(&paren.expr, bindings, path, skipped, depth + 1),
+        Expr::TsAs(ts_as) => return evaluate(&ts_as.expr, bindings, path, skipped, depth + 1),
+        Expr::TsConstAssertion(assertion) => {
+            return evaluate(&assertion.expr, bindings, path, skipped, depth + 1)
+        }
+        Expr::TsSatisfies(satisfies) => {
+            return evaluate(&satisfies.expr, bindings, path, skipped, depth + 1)
+        }
+        Expr::Ident(ident) => match bindings.get(&*ident.sym) {
+            Some(bound) => return evaluate(bound, bindings, path, skipped, depth + 1),
+            None => None,
+        },
+        Expr::Lit(Lit::Str(s)) => s.value.as_str().map(|s| Value::String(s.to_string())),
+        Expr::Lit(Lit::Num(n)) if n.value.fract() == 0.0 && n.value.abs() < 1e15 => {
+            Some(Value::from(n.value as i64))
+        }
+        Expr::Lit(Lit::Num(n)) => Number::from_f64(n.value).map(Value::Number),
+        Expr::Lit(Lit::Bool(b)) => Some(Value::Bool(b.value)),
+        Expr::Lit(Lit::Null(_)) => Some(Value::Null),
+        Expr::Tpl(tpl) if tpl.exprs.is_empty() => tpl.quasis.first().map(|quasi| {
+            let text = quasi
+                .cooked
+                .as_ref()
+                .and_then(|cooked| cooked.as_str())
+                .unwrap_or(quasi.raw.as_ref());
+            Value::String(text.to_string())
+        }),
+        Expr::Array(array) => {
+            let mut items = Vec::new();
+            for (index, element) in array.elems.iter().enumerate() {
+                let item_path = format!("{}[{}]", path, index);
+                match element {
+                    Some(element) if element.spread.is_none() => {
+                        if let Some(value) =
+                            evaluate(&element.expr, bindings, &item_path, skipped, depth + 1)
+                        {
+                            items.push(value);
+                        }
+                    }
+                    _ => skipped.push(item_path),
+                }
+            }
+            Some(Value::Array(items))
+        }
+        Expr::Object(object) => {
+            let mut map = Map::new();
+            for prop in &object.props {
+                let PropOrSpread::Prop(prop) = prop else {
+                    skipped.push(join_path(path, "..."));
+                    continue;
+                };
+                let (key, value_expr): (String, Option<&Expr>) = match prop.as_ref() {
+                    Prop::KeyValue(kv) => match prop_name(&kv.key) {
+                        Some(key) => (key, Some(kv.value.as_ref())),
+                        None => {
+                            skipped.push(join_path(path, "[computed]"));
+                            continue;
+                        }
+                    },
+                    Prop::Shorthand(ident) => (ident.sym.to_string(), None),
+                    _ => {
+                        skipped.push(join_path(path, "[method]"));
+                        continue;
+                    }
+                };
+                let key_path = join_path(path, &key);
+                let value = match value_expr {
+                    Some(value_expr) => {
+                        evaluate(value_expr, bindings, &key_path, skipped, depth + 1)
+                    }
+                    None => bindings
+                        .get(&key)
+                        .and_then(|bound| evaluate(bound, bindings, &key_path, skipped, depth + 1)),
+                };
+                match value {
+                    Some(value) => {
+                        map.insert(key, value);
+                    }
+                    None if value_expr.is_none() => skipped.push(key_path),
+                    None => {}
+                }
+            }
+            Some(Value::Object(map))
+        }
+        _ => None,
+    };
+    if value.is_none() && !matches!(expr, Expr::Object(_) | Expr::Array(_)) {
+        skipped.push(path.to_string());
+    }
+    value
+}
+
+fn prop_name(name: &PropName) -> Option<String> {
+    match name {
+        PropName::Ident(ident) => Some(ident.sym.to_string()),
+        PropName::Str(s) => s.value.as_str().map(|s| s.to_string()),
+        PropName::Num(n) => Some(n.value.to_string()),
+        _ => None,
+    }
+}
+
+fn join_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", parent, key)
+    }
+}
+
+/// Lay out one namespace's values the way `mergedNamespaceLayout` and
+/// `mergedNestingDepth` shape a merged file
+fn merged_namespace_entries(
+    config: &Config,
+    namespace: &str,
+    values: Map<String, Value>,
+) -> Map<String, Value> {
+    let mut leaves = Vec::new();
+    collect_leaves(values, &[], &config.key_separator, &mut leaves);
+
+    let mut merged = Map::new();
+    'leaves: for (key, value) in leaves {
+        let parts = json_sync::merged_key_parts(config, namespace, &key);
+        let Some((last, parents)) = parts.split_last() else {
+            continue;
+        };
+        let mut current = &mut merged;
+        for part in parents {
+            let Value::Object(nested) = current
+                .entry(part.clone())
+                .or_insert_with(|| Value::Object(Map::new()))
+            else {
+                continue 'leaves;
+            };
+            current = nested;
+        }
+        current.entry(last.clone()).or_insert(value);
+    }
+    merged
+}
+
+/// Flatten `values` to `(key, leaf)` pairs; nothing is flattened without a key separator
+fn collect_leaves(
+    values: Map<String, Value>,
+    prefix: &[String],
+    key_separator: &str,
+    leaves: &mut Vec<(String, Value)>,
+) {
+    for (key, value) in values {
+        let mut path = prefix.to_vec();
+        path.push(key);
+        match value {
+            Value::Object(nested) if !key_separator.is_empty() && !nested.is_empty() => {
+                collect_leaves(nested, &path, key_separator, leaves)
+            }
+            value => leaves.push((path.join(key_separator), value)),
+        }
+    }
+}
+
+/// Copy values from `source` whose paths are missing in `target`.
+/// Returns (values added, existing values kept because they differ).
+fn merge_missing(target: &mut Map<String, Value>, source: Map<String, Value>) -> (usize, usize) {
+    let mut added = 0;
+    let mut kept = 0;
+    for (key, value) in source {
+        match (target.get_mut(&key), value) {
+            (None, value) => {
+                added += count_leaves(&value);
+                target.insert(key, value);
+            }
+            (Some(Value::Object(existing)), Value::Object(nested)) => {
+                let (a, k) = merge_missing(existing, nested);
+                added += a;
+                kept += k;
+            }
+            (Some(existing), value) => {
+                if *existing != value {
+                    kept += count_leaves(&value);
+                }
+            }
+        }
+    }
+    (added, kept)
+}
+
+fn count_leaves(value: &Value) -> usize {
+    match value {
+        Value::Object(map) => map.values().map(count_leaves).sum(),
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_resources_follows_bindings_and_unwraps_const_assertions() {
+        let source = r#"
+            const enCommon = { greeting: 'Hello', nested: { count: 3 } };
+            const resources = {
+              en: { translation: { title: `Title` }, common: enCommon },
+              ja: { translation: { title: "タイトル", dynamic: getLabel() } },
+            } as const;
+            export default resources;
+        "#;
+        let (resources, skipped) = parse_resources(source, Path::new("i18n.ts")).unwrap();
+
+        assert_eq!(
+            Value::Object(resources),
+            json!({
+                "en": {
+                    "translation": { "title": "Title" },
+                    "common": { "greeting": "Hello", "nested": { "count": 3 } }
+                },
+                "ja": { "translation": { "title": "タイトル" } }
+            })
+        );
+        assert_eq!(skipped, vec!["ja.translation.dynamic"]);
+    }
+
+    #[test]
+    fn parse_resources_reads_module_exports() {
+        let source = "module.exports = { en: { translation: { a: 'A' } } };";
+        let (resources, _) = parse_resources(source, Path::new("i18n.js")).unwrap();
+        assert_eq!(resources["en"]["translation"]["a"], json!("A"));
+    }
+
+    #[test]
+    fn merged_namespace_entries_follow_layout_and_depth() {
+        let mut config = Config::default();
+        config.merge_namespaces = true;
+        config.merged_namespace_layout = crate::config::MergedNamespaceLayout::Prefixed;
+        config.merged_nesting_depth = Some(1);
+        let values = json!({ "button": { "save": "Save" }, "title": "Title" })
+            .as_object()
+            .unwrap()
+            .clone();
+
+        assert_eq!(
+            Value::Object(merged_namespace_entries(&config, "common", values)),
+            json!({ "common:button.save": "Save", "common:title": "Title" })
+        );
+    }
+
+    #[test]
+    fn merge_missing_keeps_existing_values() {
+        let mut target = json!({ "a": "Local", "nested": { "b": "" } })
+            .as_object()
+            .unwrap()
+            .clone();
+        let source = json!({ "a": "Bundled", "nested": { "b": "", "c": "C" }, "d": "D" })
+            .as_object()
+            .unwrap()
+            .clone();
+
+        assert_eq!(merge_missing(&mut target, source), (2, 1));
+        assert_eq!(
+            Value::Object(target),
+            json!({ "a": "Local", "nested": { "b": "", "c": "C" }, "d": "D" })
+        );
+    }
+}
@@ -1,12 +1,13 @@
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use notify_debouncer_mini::{new_debouncer, notify::RecursiveMode, DebounceEventResult};
+use serde_json::{json, Value};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc::channel;
 use std::time::Duration;
 
 use crate::config::Config;
-use crate::lint::{self, LintOptions};
+use crate::lint::{self, LintOptions, LintResult};
 
 pub fn run(config: &Config, fail_on_error: bool, watch: bool) -> Result<()> {
     if watch {
@@ -20,47 +21,127 @@ pub fn run(config: &Config, fail_on_error: bool, watch: bool) -> Result<()> {
     println!();
 
     println!("Scanning for hardcoded strings...");
-    let lint_options = LintOptions {
-        ignored_attributes: config.lint.ignored_attributes.clone(),
-        ignored_tags: config.lint.ignored_tags.clone(),
-        accepted_attributes: config.lint.accepted_attributes.clone(),
-        accepted_tags: config.lint.accepted_tags.clone(),
-        ignore_patterns: config.lint.ignore.clone(),
-    };
+    let lint_options = lint_options(config);
     let result = lint::lint_from_glob_with_options(&config.input, &lint_options)?;
+    report(config, &result, fail_on_error)
+}
 
+/// Print the hardcoded strings of `result` and check the translation values
+/// (lengths, secrets); fails on any issue with `fail_on_error`
+pub(crate) fn report(config: &Config, result: &LintResult, fail_on_error: bool) -> Result<()> {
     println!("  Files checked: {}", result.files_checked);
     println!("  Issues found: {}", result.issues.len());
     println!();
 
+    let length_issues = lint::check_value_lengths(config)?;
+    let secret_issues = lint::check_secret_values(config)?;
+
     if result.issues.is_empty() {
         println!("No hardcoded strings found. All text appears to be translated!");
-        return Ok(());
+    } else {
+        println!("{}", "=".repeat(60));
+        println!("Issues:");
+        println!("{}", "=".repeat(60));
+
+        for issue in &result.issues {
+            println!("\n{}:{}:{}", issue.file_path, issue.line, issue.column);
+            println!("  {}", issue.message);
+            println!("  Text: \"{}\"", issue.text);
+        }
+
+        println!("\n{}", "=".repeat(60));
+        println!("Total: {} issue(s)", result.issues.len());
     }
 
-    println!("{}", "=".repeat(60));
-    println!("Issues:");
-    println!("{}", "=".repeat(60));
+    if !length_issues.is_empty() {
+        println!("\nTranslation length issues:");
+        for issue in &length_issues {
+            println!(
+                "  [{}] {}: {}",
+                issue.locale,
+                format_key(config, &issue.namespace, &issue.key),
+                issue.message()
+            );
+        }
+        println!("Total: {} length issue(s)", length_issues.len());
+    }
 
-    for issue in &result.issues {
-        println!("\n{}:{}:{}", issue.file_path, issue.line, issue.column);
-        println!("  {}", issue.message);
-        println!("  Text: \"{}\"", issue.text);
+    if !secret_issues.is_empty() {
+        println!("\nSensitive values in translations:");
+        for issue in &secret_issues {
+            println!(
+                "  [{}] {}: {}",
+                issue.locale,
+                format_key(config, &issue.namespace, &issue.key),
+                issue.message()
+            );
+        }
+        println!("Total: {} sensitive value(s)", secret_issues.len());
+    }
+
+    let total = result.issues.len() + length_issues.len() + secret_issues.len();
+    if fail_on_error && total > 0 {
+        bail!("{} lint issue(s) found (--fail-on-error enabled)", total);
     }
 
-    println!("\n{}", "=".repeat(60));
-    println!("Total: {} issue(s)", result.issues.len());
+    Ok(())
+}
+
+/// Lint source read from stdin as if it were the file at `path` and print the
+/// issues as JSON
+pub fn run_stdin(config: &Config, path: &Path, fail_on_error: bool) -> Result<()> {
+    let source = std::io::read_to_string(std::io::stdin()).context("Failed to read stdin")?;
+    let lint_options = lint_options(config);
+    let ignored = lint_options
+        .ignore_patterns
+        .iter()
+        .filter_map(|pattern| glob::Pattern::new(pattern).ok())
+        .any(|pattern| pattern.matches_path(path));
+    let issues = if ignored {
+        Vec::new()
+    } else {
+        lint::lint_source_with_options(&source, path, &lint_options)?
+    };
+
+    let report: Vec<Value> = issues
+        .iter()
+        .map(|issue| {
+            json!({
+                "line": issue.line,
+                "column": issue.column,
+                "message": issue.message,
+                "text": issue.text,
+            })
+        })
+        .collect();
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&json!({
+            "file": path.to_string_lossy(),
+            "issues": report,
+        }))?
+    );
 
-    if fail_on_error {
+    if fail_on_error && !issues.is_empty() {
         bail!(
             "{} lint issue(s) found (--fail-on-error enabled)",
-            result.issues.len()
+            issues.len()
         );
     }
-
     Ok(())
 }
 
+pub(crate) fn lint_options(config: &Config) -> LintOptions {
+    LintOptions {
+        ignored_attributes: config.lint.ignored_attributes.clone(),
+        ignored_tags: config.lint.ignored_tags.clone(),
+        accepted_attributes: config.lint.accepted_attributes.clone(),
+        accepted_tags: config.lint.accepted_tags.clone(),
+        ignore_patterns: config.lint.ignore.clone(),
+        path_style: config.path_style,
+    }
+}
+
 fn run_watch(config: &Config, fail_on_error: bool) -> Result<()> {
     println!("=== i18next-turbo lint (watch) ===\n");
     run_once(config, fail_on_error)?;
@@ -101,20 +182,13 @@ fn handle_watch_events(
 }
 
 fn run_once(config: &Config, fail_on_error: bool) -> Result<()> {
-    let lint_options = LintOptions {
-        ignored_attributes: config.lint.ignored_attributes.clone(),
-        ignored_tags: config.lint.ignored_tags.clone(),
-        accepted_attributes: config.lint.accepted_attributes.clone(),
-        accepted_tags: config.lint.accepted_tags.clone(),
-        ignore_patterns: config.lint.ignore.clone(),
-    };
+    let lint_options = lint_options(config);
     let result = lint::lint_from_glob_with_options(&config.input, &lint_options)?;
 
     println!("  Files checked: {}", result.files_checked);
     println!("  Issues found: {}", result.issues.len());
     if result.issues.is_empty() {
-        println!("No hardcoded strings found. All text appears to be translated!\n");
-        return Ok(());
+        println!("No hardcoded strings found. All text appears to be translated!");
     }
 
     for issue in &result.issues {
@@ -123,17 +197,41 @@ fn run_once(config: &Config, fail_on_error: bool) -> Result<()> {
             issue.file_path, issue.line, issue.column, issue.message
         );
     }
+    let length_issues = lint::check_value_lengths(config)?;
+    for issue in &length_issues {
+        println!(
+            "[{}] {}: {}",
+            issue.locale,
+            format_key(config, &issue.namespace, &issue.key),
+            issue.message()
+        );
+    }
+    let secret_issues = lint::check_secret_values(config)?;
+    for issue in &secret_issues {
+        println!(
+            "[{}] {}: {}",
+            issue.locale,
+            format_key(config, &issue.namespace, &issue.key),
+            issue.message()
+        );
+    }
     println!();
 
-    if fail_on_error {
-        bail!(
-            "{} lint issue(s) found (--fail-on-error enabled)",
-            result.issues.len()
-        );
+    let total = result.issues.len() + length_issues.len() + secret_issues.len();
+    if fail_on_error && total > 0 {
+        bail!("{} lint issue(s) found (--fail-on-error enabled)", total);
     }
     Ok(())
 }
 
+fn format_key(config: &Config, namespace: &str, key: &str) -> String {
+    if config.namespace_less_mode() {
+        key.to_string()
+    } else {
+        format!("{}{}{}", namespace, config.ns_separator, key)
+    }
+}
+
 fn compute_watch_dirs(patterns: &[String]) -> Vec<PathBuf> {
     let mut dirs = HashSet::new();
     for pattern in patterns {
@@ -0,0 +1,304 @@
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use crate::config::{Config, OutputFormat, PathStyle};
+use crate::config_edit::ConfigFile;
+use crate::extractor;
+use crate::json_sync;
+use crate::paths;
+use crate::plurals;
+
+/// Scaffold the files of `locale` from the primary locale, with empty values or
+/// (`copy_values`) the primary values as fallbacks, and add it to the config
+pub fn add(
+    config: &Config,
+    config_path: Option<&Path>,
+    locale: &str,
+    copy_values: bool,
+    dry_run: bool,
+) -> Result<()> {
+    println!("=== i18next-turbo add-locale ===\n");
+    validate_code(locale)?;
+    if config.locales.iter().any(|configured| configured == locale) {
+        bail!("Locale '{}' is already configured", locale);
+    }
+    let primary = config.primary_language();
+    println!(
+        "Scaffolding '{}' from '{}' with {} values...",
+        locale,
+        primary,
+        if copy_values { "copied" } else { "empty" }
+    );
+
+    let format = config.output_format();
+    let key_separator = if config.key_separator.is_empty() {
+        "."
+    } else {
+        config.key_separator.as_str()
+    };
+    let mut written = 0;
+    for source in json_sync::locale_files(config, &config.output, primary)? {
+        let target = if config.flat_locale_files {
+            json_sync::locale_namespace_file_path(config, &config.output, locale, "")
+        } else {
+            Path::new(&config.output)
+                .join(locale)
+                .join(source.file_name().unwrap_or_default())
+        };
+        if target.exists() {
+            println!(
+                "  {} already exists, kept",
+                paths::display(&target, config.path_style)
+            );
+            continue;
+        }
+        let content = std::fs::read_to_string(&source)
+            .with_context(|| format!("Failed to read: {}", source.display()))?;
+        let mut map = if content.trim().is_empty() {
+            Map::new()
+        } else {
+            match json_sync::parse_locale_value_str(&content, format, &source)? {
+                Value::Object(map) => map,
+                _ => bail!("Locale file is not an object: {}", source.display()),
+            }
+        };
+        if !copy_values {
+            clear_values(&mut map);
+        }
+        if !config.disable_plurals && !config.plural_compat.is_legacy() {
+            plurals::check_plural_forms(
+                &mut map,
+                locale,
+                &config.plural_separator,
+                key_separator,
+                true,
+            );
+        }
+
+        if dry_run {
+            println!(
+                "  [Dry run] Would create {}",
+                paths::display(&target, config.path_style)
+            );
+        } else {
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+            }
+            let style = (format == OutputFormat::Json)
+                .then(|| json_sync::configured_json_style(config, &content));
+            json_sync::write_locale_file(&target, &map, format, config.key_sort, style.as_ref())?;
+            println!("  Created {}", paths::display(&target, config.path_style));
+        }
+        written += 1;
+    }
+    if written == 0 {
+        println!("  '{}' has no locale files to copy.", primary);
+    }
+
+    update_config(
+        config_path,
+        config.path_style,
+        dry_run,
+        &format!("add '{}' to", locale),
+        |file| Ok(file.push_strings("locales", &[locale])? > 0),
+    )
+}
+
+/// Delete the files of `locale` in every output root, remove it from the config
+/// and warn about source code that still refers to it
+pub fn remove(
+    config: &Config,
+    config_path: Option<&Path>,
+    locale: &str,
+    dry_run: bool,
+) -> Result<()> {
+    println!("=== i18next-turbo remove-locale ===\n");
+    validate_code(locale)?;
+    if locale == config.primary_language() {
+        bail!("Cannot remove the primary locale '{}'", locale);
+    }
+    if !config.locales.iter().any(|configured| configured == locale) {
+        println!(
+            "'{}' is not a configured locale; removing leftover files only.",
+            locale
+        );
+    }
+
+    println!("Removing locale files...");
+    let outputs = std::iter::once(config.output.as_str())
+        .chain(config.outputs.iter().map(|target| target.output.as_str()));
+    let mut removed = 0;
+    for output in outputs {
+        let target = if config.flat_locale_files {
+            json_sync::locale_namespace_file_path(config, output, locale, "")
+        } else {
+            Path::new(output).join(locale)
+        };
+        if !target.exists() {
+            continue;
+        }
+        if dry_run {
+            println!(
+                "  [Dry run] Would remove {}",
+                paths::display(&target, config.path_style)
+            );
+        } else {
+            if target.is_dir() {
+                std::fs::remove_dir_all(&target)
+            } else {
+                std::fs::remove_file(&target)
+            }
+            .with_context(|| format!("Failed to remove: {}", target.display()))?;
+            println!("  Removed {}", paths::display(&target, config.path_style));
+        }
+        removed += 1;
+    }
+    if removed == 0 {
+        println!("  No files found for '{}'.", locale);
+    }
+
+    update_config(
+        config_path,
+        config.path_style,
+        dry_run,
+        &format!("remove '{}' from", locale),
+        |file| {
+            let mut changed = false;
+            for field in ["locales", "secondaryLanguages"] {
+                changed |= file.remove_string(field, locale)?;
+            }
+            changed |= file.remove_member("releaseGates", locale)?;
+            Ok(changed)
+        },
+    )?;
+
+    let references = code_references(config, locale)?;
+    if !references.is_empty() {
+        println!(
+            "\n⚠ Warning: {} line(s) of source code still refer to '{}':",
+            references.len(),
+            locale
+        );
+        for (path, line, text) in &references {
+            println!(
+                "  {}:{}: {}",
+                paths::display(path, config.path_style),
+                line,
+                text
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Locale codes name directories and files, so they must be a single segment
+fn validate_code(locale: &str) -> Result<()> {
+    if locale.is_empty()
+        || locale.starts_with('.')
+        || locale.contains(|c: char| c == '/' || c == '\\' || c.is_whitespace())
+    {
+        bail!("Invalid locale code '{}'", locale);
+    }
+    Ok(())
+}
+
+/// Replace every string value with `""`, keeping marker keys
+fn clear_values(map: &mut Map<String, Value>) {
+    for (key, value) in map.iter_mut() {
+        if json_sync::is_marker_key(key) {
+            continue;
+        }
+        match value {
+            Value::Object(nested) => clear_values(nested),
+            Value::String(text) => text.clear(),
+            _ => {}
+        }
+    }
+}
+
+/// Apply `edit` to a JSON config file, keeping its layout; other config files
+/// are left to the user
+fn update_config(
+    config_path: Option<&Path>,
+    path_style: PathStyle,
+    dry_run: bool,
+    action: &str,
+    edit: impl FnOnce(&mut ConfigFile) -> Result<bool>,
+) -> Result<()> {
+    println!("\nUpdating config...");
+    let Some(path) = config_path.filter(|path| {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+    }) else {
+        println!("  Not a JSON config file: {} \"locales\" manually.", action);
+        return Ok(());
+    };
+    let mut file = ConfigFile::read(path)?;
+    if !edit(&mut file)? {
+        println!(
+            "  {} is already up to date.",
+            paths::display(path, path_style)
+        );
+        return Ok(());
+    }
+    if dry_run {
+        println!(
+            "  [Dry run] Would update {}",
+            paths::display(path, path_style)
+        );
+        return Ok(());
+    }
+    file.write()?;
+    println!("  Updated {}", paths::display(path, path_style));
+    Ok(())
+}
+
+/// Source lines with a string literal naming `locale` (`'de'`) or a path
+/// through it (`'./locales/de/common.json'`), such as imports and switch cases
+fn code_references(config: &Config, locale: &str) -> Result<Vec<(PathBuf, usize, String)>> {
+    let literal = Regex::new(r#"'([^'\n]*)'|"([^"\n]*)"|`([^`\n]*)`"#)?;
+    let refers = |text: &str| {
+        text == locale
+            || (text.contains('/')
+                && text
+                    .split('/')
+                    .any(|segment| segment.split('.').next() == Some(locale)))
+    };
+
+    let mut files = BTreeSet::new();
+    for pattern in config
+        .input
+        .iter()
+        .flat_map(|pattern| extractor::expand_brace_patterns(pattern))
+    {
+        let Ok(paths) = glob::glob(&pattern) else {
+            continue;
+        };
+        files.extend(paths.flatten().filter(|path| path.is_file()));
+    }
+
+    let mut references = Vec::new();
+    for path in files {
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        for (i, line) in content.lines().enumerate() {
+            let found = literal.captures_iter(line).any(|captures| {
+                captures
+                    .iter()
+                    .skip(1)
+                    .flatten()
+                    .any(|text| refers(text.as_str()))
+            });
+            if found {
+                references.push((path.clone(), i + 1, line.trim().to_string()));
+            }
+        }
+    }
+    Ok(references)
+}
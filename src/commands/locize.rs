@@ -1,5 +1,7 @@
 use crate::config::{Config, LocizeConfig, OutputFormat};
+use crate::json_sync;
 use crate::logging;
+use crate::paths;
 use anyhow::{anyhow, bail, Context, Result};
 use reqwest::blocking::{Client, Response};
 use reqwest::header::LAST_MODIFIED;
@@ -28,10 +30,7 @@ pub fn upload(
     let namespaces = resolve_namespaces(config, locize, namespace.as_deref(), &extension)?;
     let api_key = resolve_api_key(locize)?;
     let dry_run = dry_run || locize.dry_run.unwrap_or(false);
-    let version = locize
-        .version
-        .clone()
-        .unwrap_or_else(|| "latest".to_string());
+    let version = resolve_version(locize);
     let client = Client::new();
 
     for locale in locales {
@@ -40,7 +39,7 @@ pub fn upload(
             if !file_path.exists() {
                 println!(
                     "⚠︎ {} をスキップ (ファイルが見つかりません)",
-                    file_path.display()
+                    paths::display(&file_path, config.path_style)
                 );
                 continue;
             }
@@ -116,10 +115,7 @@ pub fn download(
     let namespaces = resolve_namespaces(config, locize, namespace.as_deref(), &extension)?;
     let api_key = resolve_api_key(locize)?;
     let dry_run = dry_run || locize.dry_run.unwrap_or(false);
-    let version = locize
-        .version
-        .clone()
-        .unwrap_or_else(|| "latest".to_string());
+    let version = resolve_version(locize);
     let client = Client::new();
 
     for locale in locales {
@@ -160,7 +156,7 @@ pub fn download(
                 fs::create_dir_all(parent)
                     .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
             }
-            let formatted = serde_json::to_string_pretty(&payload)?;
+            let formatted = json_sync::to_string_pretty_sorted(&payload, config.key_sort)?;
             fs::write(&file_path, format!("{}\n", formatted))
                 .with_context(|| format!("Failed to write {}", file_path.display()))?;
             println!("✓ Downloaded {} / {}", locale, ns);
@@ -239,7 +235,10 @@ pub fn setup(
     fs::write(&save_path, format!("{}\n", serialized))
         .with_context(|| format!("設定ファイルの保存に失敗しました: {}", save_path.display()))?;
 
-    println!("✓ Locize 設定を保存しました: {}", save_path.display());
+    println!(
+        "✓ Locize 設定を保存しました: {}",
+        paths::display(&save_path, config.path_style)
+    );
     Ok(())
 }
 
@@ -355,7 +354,7 @@ fn ensure_supported_format(config: &Config) -> Result<()> {
     }
 }
 
-fn resolve_api_key(locize: &LocizeConfig) -> Result<String> {
+pub(crate) fn resolve_api_key(locize: &LocizeConfig) -> Result<String> {
     if let Some(key) = &locize.api_key {
         if !key.trim().is_empty() {
             return Ok(key.clone());
@@ -368,7 +367,14 @@ fn resolve_api_key(locize: &LocizeConfig) -> Result<String> {
         }
     }
 
-    bail!("Locize API キーが設定されていません。config.locize.apiKey か LOCIZE_API_KEY を指定してください。");
+    bail!("Locize API キーが設定されていません。config.locize.apiKey (pull/push では tms.apiKey) か LOCIZE_API_KEY を指定してください。");
+}
+
+pub(crate) fn resolve_version(locize: &LocizeConfig) -> String {
+    locize
+        .version
+        .clone()
+        .unwrap_or_else(|| "latest".to_string())
 }
 
 fn resolve_locales(config: &Config, override_locale: Option<String>) -> Result<Vec<String>> {
@@ -472,7 +478,7 @@ fn ensure_success(response: Response, url: &str) -> Result<Response> {
     }
 }
 
-fn download_base_host(locize: &LocizeConfig) -> &'static str {
+pub(crate) fn download_base_host(locize: &LocizeConfig) -> &'static str {
     match locize.cdn_type.as_deref() {
         Some("pro") => "api.locize.pro",
         _ => "api.locize.app",
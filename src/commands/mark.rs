@@ -0,0 +1,101 @@
+use anyhow::{bail, Context, Result};
+use std::path::Path;
+
+use crate::config::Config;
+use crate::json_sync;
+use crate::metadata::{MetadataFile, ReviewStatus};
+use crate::paths;
+
+/// Set the review status of keys matching `patterns` (`namespace:glob`, or a
+/// glob in the default namespace) in `locales` (every non-primary locale when
+/// empty)
+pub fn run(
+    config: &Config,
+    status: &str,
+    patterns: &[String],
+    locales: &[String],
+    dry_run: bool,
+) -> Result<()> {
+    println!("=== i18next-turbo mark ===\n");
+
+    let status = ReviewStatus::parse_str(status)?;
+    let Some(path) = config.metadata_file.as_deref() else {
+        bail!("mark stores review status in the metadata file; set `metadataFile` in the config");
+    };
+    let path = Path::new(path);
+
+    let primary = config.primary_language();
+    let locales: Vec<String> = if locales.is_empty() {
+        config
+            .locales
+            .iter()
+            .filter(|locale| locale.as_str() != primary)
+            .cloned()
+            .collect()
+    } else {
+        locales.to_vec()
+    };
+    if locales.is_empty() {
+        bail!(
+            "No locales to mark: pass --locale or configure locales besides the primary language"
+        );
+    }
+
+    let matchers = patterns
+        .iter()
+        .map(|pattern| {
+            let (namespace, key) = match pattern.split_once(config.ns_separator.as_str()) {
+                Some((namespace, key)) if !config.ns_separator.is_empty() => (namespace, key),
+                _ => (config.effective_default_namespace(), pattern.as_str()),
+            };
+            let key = glob::Pattern::new(key)
+                .with_context(|| format!("Invalid key pattern: {}", pattern))?;
+            Ok((namespace.to_string(), key))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let namespaces = json_sync::load_locale_namespaces(config, &config.output, primary)?;
+    let keys: Vec<(String, String)> =
+        json_sync::flatten_leaf_values(&namespaces, &config.key_separator)
+            .into_keys()
+            .filter(|(namespace, key)| {
+                matchers
+                    .iter()
+                    .any(|(ns, pattern)| ns == namespace && pattern.matches(key))
+            })
+            .collect();
+    if keys.is_empty() {
+        bail!(
+            "No keys of the primary language ({}) match: {}",
+            primary,
+            patterns.join(", ")
+        );
+    }
+
+    let mut metadata = MetadataFile::load(path)?;
+    let mut changed = 0;
+    for (namespace, key) in &keys {
+        for locale in &locales {
+            if metadata.set_review(namespace, key, locale, status) {
+                changed += 1;
+            }
+        }
+    }
+
+    println!("  Keys: {}", keys.len());
+    println!("  Locales: {}", locales.join(", "));
+    println!("  Status: {}", status.as_str());
+    if dry_run {
+        println!("\n[Dry run] Would update {} review status(es)", changed);
+    } else {
+        if changed > 0 {
+            metadata.save(path)?;
+        }
+        println!(
+            "\nUpdated {} review status(es) in {}",
+            changed,
+            paths::display(path, config.path_style)
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::path::Path;
+
+use crate::config::{Config, PluralCompat};
+use crate::json_sync;
+use crate::paths;
+use crate::plan::{self, ChangePlan};
+use crate::plurals::{self, LegacyPluralMapping};
+
+/// Convert legacy (v3/v2) plural keys to CLDR (v4) suffixes in every locale file,
+/// or write the conversion as a change plan with `emit_plan`.
+pub fn run(
+    config: &Config,
+    from: Option<String>,
+    dry_run: bool,
+    emit_plan: Option<&Path>,
+) -> Result<()> {
+    println!("=== i18next-turbo migrate-plurals ===\n");
+
+    let from = match from.as_deref() {
+        Some(value) => PluralCompat::parse_str(value)?,
+        None if config.plural_compat.is_legacy() => config.plural_compat,
+        None => PluralCompat::V3,
+    };
+    if !from.is_legacy() {
+        println!("Catalog is already in the v4 plural format, nothing to migrate.");
+        return Ok(());
+    }
+
+    if let Some(path) = emit_plan {
+        return emit_migration_plan(config, from, path);
+    }
+
+    let format = config.output_format();
+    let mut total_groups = 0;
+
+    for locale in &config.locales {
+        let mapping = LegacyPluralMapping::for_locale(locale, from);
+        for path in json_sync::locale_files(config, &config.output, locale)? {
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read: {}", path.display()))?;
+            let Value::Object(mut map) =
+                json_sync::parse_locale_value_str(&content, format, &path)?
+            else {
+                continue;
+            };
+
+            let migrated =
+                plurals::migrate_legacy_plurals(&mut map, &mapping, &config.plural_separator);
+            if migrated == 0 {
+                continue;
+            }
+            total_groups += migrated;
+
+            if dry_run {
+                println!(
+                    "  {} - would migrate {} plural key group(s)",
+                    paths::display(&path, config.path_style),
+                    migrated
+                );
+            } else {
+                let style = json_sync::detect_json_style(&content);
+                json_sync::write_locale_file(&path, &map, format, config.key_sort, Some(&style))?;
+                println!(
+                    "  {} - migrated {} plural key group(s)",
+                    paths::display(&path, config.path_style),
+                    migrated
+                );
+            }
+        }
+    }
+
+    println!();
+    if total_groups == 0 {
+        println!("No legacy plural keys found.");
+    } else if dry_run {
+        println!(
+            "Would migrate {} plural key group(s) (dry run, no files written)",
+            total_groups
+        );
+    } else {
+        println!("Migrated {} plural key group(s).", total_groups);
+        if config.plural_compat.is_legacy() {
+            println!("Remove \"pluralCompat\" from your config to extract v4 plural keys.");
+        }
+    }
+
+    Ok(())
+}
+
+/// Write the migration of each locale as `setValue` changes for the CLDR keys
+/// followed by `deleteKey` changes for the legacy ones
+fn emit_migration_plan(config: &Config, from: PluralCompat, path: &Path) -> Result<()> {
+    let mut changes = Vec::new();
+    for locale in &config.locales {
+        let mapping = LegacyPluralMapping::for_locale(locale, from);
+        let before = json_sync::load_locale_namespaces(config, &config.output, locale)?;
+        let mut after = before.clone();
+        let migrated: usize = after
+            .values_mut()
+            .map(|map| plurals::migrate_legacy_plurals(map, &mapping, &config.plural_separator))
+            .sum();
+        if migrated > 0 {
+            println!("  {} - {} plural key group(s)", locale, migrated);
+        }
+        changes.extend(plan::value_changes(
+            config,
+            locale,
+            &json_sync::flatten_leaf_values(&before, &config.key_separator),
+            &json_sync::flatten_leaf_values(&after, &config.key_separator),
+        ));
+    }
+    ChangePlan::new(changes).emit(path, config.path_style)
+}
@@ -1,10 +1,38 @@
+pub mod browse;
 pub mod check;
+pub mod config;
+pub mod daemon;
+pub mod defaults;
+pub mod diff;
+pub mod doctor;
+pub mod edit;
+pub mod eslint_data;
+pub mod explain;
 pub mod extract;
+pub mod extract_file;
+pub mod fmt;
+pub mod gate;
+pub mod hook;
+pub mod import_from;
+pub mod import_missing;
+pub mod ingest;
 pub mod init;
 pub mod lint;
+pub mod locale;
 pub mod locize;
+pub mod mark;
 pub mod migrate;
+pub mod migrate_plurals;
+pub mod plan;
+pub mod publish;
+pub mod pull;
+pub mod push;
 pub mod rename_key;
+pub mod rename_namespace;
+pub mod resolve_conflicts;
+pub mod set_value;
+pub mod size;
 pub mod status;
 pub mod sync;
+pub mod tms_client;
 pub mod typegen;
@@ -0,0 +1,41 @@
+use anyhow::Result;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::paths;
+use crate::plan::{self, ChangePlan};
+
+/// Apply every change of the plan at `path` in order
+pub fn apply(config: &Config, path: &Path, dry_run: bool) -> Result<()> {
+    println!("=== i18next-turbo plan apply ===\n");
+    let plan = ChangePlan::read(path)?;
+    println!(
+        "  Plan: {} ({} change(s))",
+        paths::display(path, config.path_style),
+        plan.changes.len()
+    );
+    if dry_run {
+        println!("  [Dry run] No files will be modified");
+    }
+
+    plan::apply(config, &plan.changes, dry_run)?;
+
+    if dry_run {
+        println!("\n[Dry run] Run without --dry-run to apply the plan.");
+    } else {
+        println!("\nApplied {} change(s).", plan.changes.len());
+    }
+    Ok(())
+}
+
+/// List the changes of the plan at `path`
+pub fn show(config: &Config, path: &Path) -> Result<()> {
+    let plan = ChangePlan::read(path)?;
+    println!("=== i18next-turbo plan ===\n");
+    println!("  Plan: {}\n", paths::display(path, config.path_style));
+    for (i, change) in plan.changes.iter().enumerate() {
+        println!("  {}. {}", i + 1, plan::describe(change));
+    }
+    println!("\nTotal: {} change(s)", plan.changes.len());
+    Ok(())
+}
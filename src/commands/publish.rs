@@ -0,0 +1,39 @@
+use anyhow::{bail, Result};
+use std::path::Path;
+
+use crate::config::Config;
+use crate::paths;
+use crate::publish;
+
+/// Publish the synced catalogs as hashed per-locale bundles and a manifest to
+/// `output`, falling back to `publishDir`
+pub fn run(config: &Config, output: Option<&str>) -> Result<()> {
+    println!("=== i18next-turbo publish-dir ===\n");
+    let Some(output) = output.or(config.publish_dir.as_deref()) else {
+        bail!("No output directory: pass --output or set \"publishDir\" in the config");
+    };
+
+    let dir = Path::new(output);
+    println!(
+        "Publishing {} locale(s) to {}...",
+        config.locales.len(),
+        paths::display(dir, config.path_style)
+    );
+    let report = publish::write(config, dir)?;
+    for file in &report.written {
+        println!("  Wrote {}", file);
+    }
+    if report.manifest_written {
+        println!("  Wrote {}", publish::MANIFEST_FILE);
+    }
+    for file in &report.removed {
+        println!("  Removed {}", file);
+    }
+    if report == publish::PublishReport::default() {
+        println!(
+            "  {} is already up to date.",
+            paths::display(dir, config.path_style)
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+
+use crate::commands::tms_client;
+use crate::config::Config;
+use crate::json_sync;
+use crate::tms;
+
+/// Download translations from the configured TMS and merge them into the
+/// existing local locale files
+pub fn run(
+    config: &Config,
+    locale: Option<String>,
+    namespace: Option<String>,
+    only_missing: bool,
+    dry_run: bool,
+) -> Result<()> {
+    println!("=== i18next-turbo pull ===\n");
+    let adapter = tms_client::adapter(config)?;
+    let provider = adapter.provider().name();
+    let locales = match locale {
+        Some(locale) => vec![locale],
+        None => config.locales.clone(),
+    };
+    let format = config.output_format();
+
+    let mut total = tms::PullStats::default();
+    for locale in &locales {
+        for (ns, path) in tms_client::namespace_files(config, locale, namespace.as_deref())? {
+            let Some(remote) = adapter.download(locale, &ns)? else {
+                println!("  {}/{}: not found in {}", locale, ns, provider);
+                continue;
+            };
+            let existing = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read locale file: {}", path.display()))?;
+            let Value::Object(mut map) =
+                json_sync::parse_locale_value_str(&existing, format, &path)?
+            else {
+                bail!("Locale file root must be an object: {}", path.display());
+            };
+
+            let stats = tms::merge_pulled(&mut map, &remote, &config.key_separator, only_missing);
+            if stats.updated > 0 && !dry_run {
+                let style = json_sync::detect_json_style(&existing);
+                json_sync::write_locale_file(&path, &map, format, config.key_sort, Some(&style))?;
+            }
+            println!(
+                "  {}/{}: {} updated, {} untranslated",
+                locale, ns, stats.updated, stats.untranslated
+            );
+            total.updated += stats.updated;
+            total.unchanged += stats.unchanged;
+            total.untranslated += stats.untranslated;
+        }
+    }
+
+    println!(
+        "\n{} {} value(s) from {} ({} unchanged, {} still untranslated)",
+        if dry_run { "Would update" } else { "Updated" },
+        total.updated,
+        provider,
+        total.unchanged,
+        total.untranslated
+    );
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+
+use crate::commands::tms_client;
+use crate::config::Config;
+use crate::json_sync;
+use crate::tms;
+
+/// Send the keys of the local locale files that the configured TMS does not
+/// have yet. Values already in the TMS are never overwritten.
+pub fn run(
+    config: &Config,
+    locale: Option<String>,
+    namespace: Option<String>,
+    dry_run: bool,
+) -> Result<()> {
+    println!("=== i18next-turbo push ===\n");
+    let adapter = tms_client::adapter(config)?;
+    let provider = adapter.provider().name();
+    let locale = locale.unwrap_or_else(|| config.primary_language().to_string());
+    let format = config.output_format();
+
+    let mut pushed = 0;
+    for (ns, path) in tms_client::namespace_files(config, &locale, namespace.as_deref())? {
+        let existing = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read locale file: {}", path.display()))?;
+        let Value::Object(map) = json_sync::parse_locale_value_str(&existing, format, &path)?
+        else {
+            bail!("Locale file root must be an object: {}", path.display());
+        };
+        let remote = adapter.download(&locale, &ns)?.unwrap_or_else(|| json!({}));
+        let keys = tms::new_keys(&map, &remote, &config.key_separator);
+        if keys.is_empty() {
+            continue;
+        }
+        for (key, _) in &keys {
+            println!("  {}/{}: {}", locale, ns, key);
+        }
+        if !dry_run {
+            adapter.add_keys(&locale, &ns, &keys)?;
+        }
+        pushed += keys.len();
+    }
+
+    println!(
+        "\n{} {} new key(s) to {}",
+        if dry_run { "Would push" } else { "Pushed" },
+        pushed,
+        provider
+    );
+    Ok(())
+}
@@ -5,6 +5,7 @@ use serde_json::{Map, Value};
 
 use crate::config::Config;
 use crate::json_sync;
+use crate::paths;
 
 pub fn run(
     config: &Config,
@@ -33,46 +34,17 @@ pub fn run(
     // Step 1: Rename in source files (unless locales_only)
     if !locales_only {
         println!("Scanning source files...");
-
-        for pattern in &config.input {
-            for path in glob::glob(pattern)?.flatten().filter(|p| p.is_file()) {
-                let content = std::fs::read_to_string(&path)?;
-
-                let search_key = if old_ns == config.default_namespace {
-                    old_key_path.clone()
-                } else {
-                    format!("{}:{}", old_ns, old_key_path)
-                };
-
-                let replace_key = if new_ns == config.default_namespace {
-                    new_key_path.clone()
-                } else {
-                    format!("{}:{}", new_ns, new_key_path)
-                };
-
-                if content.contains(&format!("'{}'", search_key))
-                    || content.contains(&format!("\"{}\"", search_key))
-                    || content.contains(&format!("`{}`", search_key))
-                {
-                    let new_content = content
-                        .replace(&format!("'{}'", search_key), &format!("'{}'", replace_key))
-                        .replace(
-                            &format!("\"{}\"", search_key),
-                            &format!("\"{}\"", replace_key),
-                        )
-                        .replace(&format!("`{}`", search_key), &format!("`{}`", replace_key));
-
-                    if new_content != content {
-                        println!("  {}", path.display());
-                        source_changes += 1;
-
-                        if !dry_run {
-                            std::fs::write(&path, new_content)?;
-                        }
-                    }
-                }
-            }
-        }
+        let search_key = if old_ns == config.default_namespace {
+            old_key_path.clone()
+        } else {
+            format!("{}:{}", old_ns, old_key_path)
+        };
+        let replace_key = if new_ns == config.default_namespace {
+            new_key_path.clone()
+        } else {
+            format!("{}:{}", new_ns, new_key_path)
+        };
+        source_changes = rename_in_sources(config, &search_key, &replace_key, dry_run)?;
 
         if source_changes == 0 {
             println!("  No source files contain the key.");
@@ -115,7 +87,13 @@ pub fn run(
                 if !dry_run {
                     if let Some(obj) = json.as_object() {
                         let sorted = json_sync::sort_keys_alphabetically(obj);
-                        json_sync::write_locale_file(&ns_file, &sorted, format, None)?;
+                        json_sync::write_locale_file(
+                            &ns_file,
+                            &sorted,
+                            format,
+                            config.key_sort,
+                            None,
+                        )?;
                     }
                 }
 
@@ -139,7 +117,13 @@ pub fn run(
                 if !dry_run {
                     if let Some(obj) = new_json.as_object() {
                         let sorted = json_sync::sort_keys_alphabetically(obj);
-                        json_sync::write_locale_file(&new_ns_file, &sorted, format, None)?;
+                        json_sync::write_locale_file(
+                            &new_ns_file,
+                            &sorted,
+                            format,
+                            config.key_sort,
+                            None,
+                        )?;
                     }
                 }
 
@@ -154,7 +138,13 @@ pub fn run(
                 if !dry_run {
                     if let Some(obj) = json.as_object() {
                         let sorted = json_sync::sort_keys_alphabetically(obj);
-                        json_sync::write_locale_file(&ns_file, &sorted, format, None)?;
+                        json_sync::write_locale_file(
+                            &ns_file,
+                            &sorted,
+                            format,
+                            config.key_sort,
+                            None,
+                        )?;
                     }
                 }
 
@@ -249,6 +239,44 @@ mod tests {
     }
 }
 
+/// Replace quoted occurrences (`'key'`, `"key"`, `` `key` ``) of `search_key`
+/// in the input files, printing each changed file. Returns how many changed.
+pub(crate) fn rename_in_sources(
+    config: &Config,
+    search_key: &str,
+    replace_key: &str,
+    dry_run: bool,
+) -> Result<usize> {
+    let mut changed = 0;
+    for pattern in &config.input {
+        for path in glob::glob(pattern)?.flatten().filter(|p| p.is_file()) {
+            let content = std::fs::read_to_string(&path)?;
+            if content.contains(&format!("'{}'", search_key))
+                || content.contains(&format!("\"{}\"", search_key))
+                || content.contains(&format!("`{}`", search_key))
+            {
+                let new_content = content
+                    .replace(&format!("'{}'", search_key), &format!("'{}'", replace_key))
+                    .replace(
+                        &format!("\"{}\"", search_key),
+                        &format!("\"{}\"", replace_key),
+                    )
+                    .replace(&format!("`{}`", search_key), &format!("`{}`", replace_key));
+
+                if new_content != content {
+                    println!("  {}", paths::display(&path, config.path_style));
+                    changed += 1;
+
+                    if !dry_run {
+                        std::fs::write(&path, new_content)?;
+                    }
+                }
+            }
+        }
+    }
+    Ok(changed)
+}
+
 /// Parse a key that may contain namespace (ns:key.path)
 fn parse_key_with_ns(key: &str, default_ns: &str) -> (String, String) {
     if key.contains(':') {
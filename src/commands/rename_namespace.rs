@@ -0,0 +1,458 @@
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::extractor;
+use crate::json_sync;
+use crate::paths;
+
+/// Namespace argument of calls that bind a namespace, besides `useTranslationNames`
+const NAMESPACE_CALLS: &[(&str, usize)] = &[("withTranslation", 0), ("getFixedT", 1)];
+
+/// How the namespace is stored in one locale
+enum LocaleChange {
+    /// `<locale>/<old>.<ext>` becomes `<locale>/<new>.<ext>`
+    MoveFile { from: PathBuf, to: PathBuf },
+    /// Top-level namespace entries renamed inside a merged file
+    RewriteMerged {
+        path: PathBuf,
+        content: Map<String, Value>,
+    },
+}
+
+pub fn run(
+    config: &Config,
+    old_ns: &str,
+    new_ns: &str,
+    dry_run: bool,
+    locales_only: bool,
+) -> Result<()> {
+    println!("=== i18next-turbo rename-namespace ===\n");
+
+    if config.namespace_less_mode() {
+        bail!("rename-namespace needs namespaces, but this config is namespace-less (defaultNamespace: false)");
+    }
+    validate_namespace(config, new_ns)?;
+    if old_ns == new_ns {
+        bail!("The old and new namespace are the same: {}", old_ns);
+    }
+
+    println!("Renaming namespace:");
+    println!("  From: {}", old_ns);
+    println!("  To:   {}", new_ns);
+    if dry_run {
+        println!("  Mode: Dry run (no files will be modified)");
+    }
+    println!();
+
+    // Plan every locale first so a conflict leaves all files untouched
+    let mut locale_changes = Vec::new();
+    for locale in &config.locales {
+        if let Some(change) = plan_locale(config, locale, old_ns, new_ns)? {
+            locale_changes.push(change);
+        }
+    }
+
+    let mut source_changes = 0;
+    if !locales_only {
+        println!("Scanning source files...");
+        let calls = namespace_calls(config);
+        let ns_attrs: Vec<&str> = std::iter::once("ns")
+            .chain(config.trans_components.iter().map(|c| c.ns_attr()))
+            .collect();
+        for path in extractor::input_files(
+            &config.input,
+            &config.extraction_ignore(),
+            config.follow_symlinks,
+        )? {
+            let Ok(content) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            let new_content = rewrite_source(
+                &content,
+                old_ns,
+                new_ns,
+                &config.ns_separator,
+                &calls,
+                &ns_attrs,
+            );
+            if new_content != content {
+                println!("  {}", paths::display(&path, config.path_style));
+                source_changes += 1;
+                if !dry_run {
+                    std::fs::write(&path, new_content)
+                        .with_context(|| format!("Failed to write: {}", path.display()))?;
+                }
+            }
+        }
+        if source_changes == 0 {
+            println!("  No source files reference the namespace.");
+        }
+    }
+
+    println!("\nUpdating locale files...");
+    for change in &locale_changes {
+        match change {
+            LocaleChange::MoveFile { from, to } => {
+                println!(
+                    "  {} -> {}",
+                    paths::display(from, config.path_style),
+                    paths::display(to, config.path_style)
+                );
+                if !dry_run {
+                    std::fs::rename(from, to).with_context(|| {
+                        format!("Failed to move {} to {}", from.display(), to.display())
+                    })?;
+                }
+            }
+            LocaleChange::RewriteMerged { path, content } => {
+                println!("  {}", paths::display(path, config.path_style));
+                if !dry_run {
+                    json_sync::write_locale_file(
+                        path,
+                        content,
+                        config.output_format(),
+                        config.key_sort,
+                        None,
+                    )?;
+                }
+            }
+        }
+    }
+    if locale_changes.is_empty() {
+        println!("  Namespace not found in any locale files.");
+    }
+
+    let types_output = config.types_output_path();
+    if !dry_run && !locale_changes.is_empty() && Path::new(&types_output).exists() {
+        println!();
+        super::typegen::run(
+            config,
+            &types_output,
+            config.types_default_locale(),
+            config.types_locales_dir(),
+        )?;
+    }
+
+    println!("\n{}", "=".repeat(40));
+    println!("Summary:");
+    if !locales_only {
+        println!("  Source files updated: {}", source_changes);
+    }
+    println!("  Locale files updated: {}", locale_changes.len());
+    if old_ns == config.default_namespace {
+        println!(
+            "\nNote: '{}' is the defaultNamespace; set \"defaultNamespace\": \"{}\" in your config.",
+            old_ns, new_ns
+        );
+    }
+
+    if dry_run {
+        println!("\n[Dry run] No files were modified.");
+    } else if source_changes > 0 || !locale_changes.is_empty() {
+        println!("\nDone!");
+    }
+
+    Ok(())
+}
+
+fn validate_namespace(config: &Config, namespace: &str) -> Result<()> {
+    let invalid = namespace.trim().is_empty()
+        || !paths::is_plain_file_name(namespace)
+        || (!config.ns_separator.is_empty() && namespace.contains(config.ns_separator.as_str()));
+    if invalid {
+        bail!("Invalid namespace name: '{}'", namespace);
+    }
+    Ok(())
+}
+
+/// Locale file change renaming `old_ns` in `locale`, or `None` when the locale
+/// does not have the namespace
+fn plan_locale(
+    config: &Config,
+    locale: &str,
+    old_ns: &str,
+    new_ns: &str,
+) -> Result<Option<LocaleChange>> {
+    let from = json_sync::locale_namespace_file_path(config, &config.output, locale, old_ns);
+    if !from.is_file() {
+        return Ok(None);
+    }
+
+    if !config.merge_namespaces {
+        let to = json_sync::locale_namespace_file_path(config, &config.output, locale, new_ns);
+        if to.exists() {
+            bail!(
+                "Cannot rename namespace: {} already exists (rename-key can move individual keys)",
+                to.display()
+            );
+        }
+        return Ok(Some(LocaleChange::MoveFile { from, to }));
+    }
+
+    let content = std::fs::read_to_string(&from)
+        .with_context(|| format!("Failed to read locale file: {}", from.display()))?;
+    let Value::Object(root) =
+        json_sync::parse_locale_value_str(&content, config.output_format(), &from)?
+    else {
+        return Ok(None);
+    };
+    let prefix = config.merged_namespace_prefix();
+    let rename = |key: &str| -> Option<String> {
+        match prefix {
+            Some(separator) => key
+                .strip_prefix(old_ns)
+                .and_then(|rest| rest.strip_prefix(separator))
+                .map(|rest| format!("{}{}{}", new_ns, separator, rest)),
+            None => (key == old_ns).then(|| new_ns.to_string()),
+        }
+    };
+    let in_new_namespace = |key: &str| match prefix {
+        Some(separator) => key
+            .strip_prefix(new_ns)
+            .is_some_and(|rest| rest.starts_with(separator)),
+        None => key == new_ns,
+    };
+
+    if !root.keys().any(|key| rename(key).is_some()) {
+        return Ok(None);
+    }
+    if root.keys().any(|key| in_new_namespace(key)) {
+        bail!(
+            "Cannot rename namespace: '{}' already exists in {}",
+            new_ns,
+            from.display()
+        );
+    }
+    let content = root
+        .into_iter()
+        .map(|(key, value)| (rename(&key).unwrap_or(key), value))
+        .collect();
+    Ok(Some(LocaleChange::RewriteMerged {
+        path: from,
+        content: json_sync::sort_keys_alphabetically(&content),
+    }))
+}
+
+/// Calls and the index of their namespace argument
+fn namespace_calls(config: &Config) -> Vec<(String, usize)> {
+    config
+        .use_translation_names
+        .iter()
+        .map(|name| (name.name().to_string(), name.ns_arg()))
+        .chain(
+            NAMESPACE_CALLS
+                .iter()
+                .map(|(name, index)| (name.to_string(), *index)),
+        )
+        .collect()
+}
+
+/// Rewrite references to `old` in source code: `old:` key prefixes, namespace
+/// arguments of `calls` (`useTranslation('old')`, `useTranslation(['old'])`),
+/// and `ns` options or attributes (`{ ns: 'old' }`, `<Trans ns="old">`).
+fn rewrite_source(
+    source: &str,
+    old: &str,
+    new: &str,
+    ns_separator: &str,
+    calls: &[(String, usize)],
+    ns_attrs: &[&str],
+) -> String {
+    let mut edits: Vec<Range<usize>> = Vec::new();
+
+    if !ns_separator.is_empty() {
+        let prefix = Regex::new(&format!(
+            r#"['"`]{}{}"#,
+            regex::escape(old),
+            regex::escape(ns_separator)
+        ))
+        .expect("escaped namespace is a valid regex");
+        for found in prefix.find_iter(source) {
+            edits.push(found.start() + 1..found.start() + 1 + old.len());
+        }
+    }
+
+    for (name, ns_arg) in calls {
+        let call = Regex::new(&format!(r"\b{}\s*\(", regex::escape(name)))
+            .expect("escaped call name is a valid regex");
+        for found in call.find_iter(source) {
+            if let Some(arg) = call_arguments(source, found.end()).get(*ns_arg) {
+                namespace_literals(source, arg.clone(), old, &mut edits);
+            }
+        }
+    }
+
+    let attrs = ns_attrs
+        .iter()
+        .chain(&["namespace"])
+        .map(|attr| regex::escape(attr))
+        .collect::<Vec<_>>()
+        .join("|");
+    let option = Regex::new(&format!(r"\b(?:{})\s*[:=]\s*", attrs))
+        .expect("escaped attribute names are a valid regex");
+    for found in option.find_iter(source) {
+        let end = value_end(source.as_bytes(), found.end());
+        namespace_literals(source, found.end()..end, old, &mut edits);
+    }
+
+    edits.sort_by_key(|range| range.start);
+    edits.dedup_by(|next, previous| next.start < previous.end);
+    let mut out = String::with_capacity(source.len());
+    let mut pos = 0;
+    for range in edits {
+        out.push_str(&source[pos..range.start]);
+        out.push_str(new);
+        pos = range.end;
+    }
+    out.push_str(&source[pos..]);
+    out
+}
+
+/// Ranges of the string literals in `range` whose content is exactly `old`
+fn namespace_literals(source: &str, range: Range<usize>, old: &str, edits: &mut Vec<Range<usize>>) {
+    let bytes = source.as_bytes();
+    let mut pos = range.start;
+    while pos < range.end {
+        if matches!(bytes[pos], b'\'' | b'"' | b'`') {
+            let end = value_end(bytes, pos);
+            if end >= pos + 2 && &source[pos + 1..end - 1] == old {
+                edits.push(pos + 1..end - 1);
+            }
+            pos = end;
+        } else {
+            pos += 1;
+        }
+    }
+}
+
+/// Ranges of the arguments of a call whose `(` ends at `start`
+fn call_arguments(source: &str, start: usize) -> Vec<Range<usize>> {
+    let bytes = source.as_bytes();
+    let mut args = Vec::new();
+    let mut pos = start;
+    let mut arg_start = start;
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b',' => {
+                args.push(arg_start..pos);
+                pos += 1;
+                arg_start = pos;
+            }
+            b')' => {
+                if !source[arg_start..pos].trim().is_empty() {
+                    args.push(arg_start..pos);
+                }
+                break;
+            }
+            b'}' | b']' => break,
+            _ => pos = value_end(bytes, pos).max(pos + 1),
+        }
+    }
+    args
+}
+
+/// End of the string literal or bracketed expression at `start`; other
+/// values (identifiers, numbers) are left alone and end where they start
+fn value_end(bytes: &[u8], start: usize) -> usize {
+    let Some(&first) = bytes.get(start) else {
+        return start;
+    };
+    match first {
+        b'\'' | b'"' | b'`' => {
+            let mut pos = start + 1;
+            while pos < bytes.len() {
+                match bytes[pos] {
+                    b'\\' => pos += 2,
+                    c if c == first => return pos + 1,
+                    _ => pos += 1,
+                }
+            }
+            bytes.len()
+        }
+        b'(' | b'[' | b'{' => {
+            let close = match first {
+                b'(' => b')',
+                b'[' => b']',
+                _ => b'}',
+            };
+            let mut pos = start + 1;
+            while pos < bytes.len() {
+                match bytes[pos] {
+                    c if c == close => return pos + 1,
+                    b'\'' | b'"' | b'`' | b'(' | b'[' | b'{' => pos = value_end(bytes, pos),
+                    _ => pos += 1,
+                }
+            }
+            bytes.len()
+        }
+        _ => start,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    #[test]
+    fn rewrite_source_updates_prefixes_hooks_and_ns_options() {
+        let calls = vec![
+            ("useTranslation".to_string(), 0),
+            ("getFixedT".to_string(), 1),
+        ];
+        let source = r#"const { t } = useTranslation(['billing', 'common']);
+const fixed = i18n.getFixedT('de', "billing");
+t('billing:invoice.title');
+t('invoice.total', { ns: 'billing' });
+<Trans i18nKey="invoice.due" ns={'billing'} />;
+<Trans i18nKey="invoice.paid" ns="billing" />;
+const label = 'billing';
+t('billingInfo:title');
+"#;
+        let expected = r#"const { t } = useTranslation(['payments', 'common']);
+const fixed = i18n.getFixedT('de', "payments");
+t('payments:invoice.title');
+t('invoice.total', { ns: 'payments' });
+<Trans i18nKey="invoice.due" ns={'payments'} />;
+<Trans i18nKey="invoice.paid" ns="payments" />;
+const label = 'billing';
+t('billingInfo:title');
+"#;
+        assert_eq!(
+            rewrite_source(source, "billing", "payments", ":", &calls, &["ns"]),
+            expected
+        );
+    }
+
+    #[test]
+    fn rename_namespace_moves_files_and_rejects_conflicts() {
+        let tmp = tempdir().unwrap();
+        let mut config = Config::default();
+        config.output = tmp.path().join("locales").to_string_lossy().to_string();
+        config.locales = vec!["en".to_string(), "de".to_string()];
+        config.input = vec![];
+        for locale in ["en", "de"] {
+            let dir = Path::new(&config.output).join(locale);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("billing.json"), r#"{"title":"Billing"}"#).unwrap();
+        }
+        std::fs::write(Path::new(&config.output).join("de/payments.json"), "{}").unwrap();
+
+        let err = run(&config, "billing", "payments", false, true).unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+        assert!(Path::new(&config.output).join("en/billing.json").exists());
+
+        std::fs::remove_file(Path::new(&config.output).join("de/payments.json")).unwrap();
+        run(&config, "billing", "payments", false, true).unwrap();
+        for locale in ["en", "de"] {
+            let dir = Path::new(&config.output).join(locale);
+            assert!(!dir.join("billing.json").exists());
+            assert!(dir.join("payments.json").exists());
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::conflicts::{self, ConflictEntry, ConflictReport, ResolveStrategy};
+use crate::json_sync;
+use crate::paths;
+
+/// Apply `strategy` to every conflict of the report at `report_path`, one
+/// locale file at a time
+pub fn run(
+    config: &Config,
+    report_path: &Path,
+    strategy: ResolveStrategy,
+    dry_run: bool,
+) -> Result<()> {
+    println!("=== i18next-turbo resolve-conflicts ===\n");
+    let report = ConflictReport::load(report_path)?;
+    if report.conflicts.is_empty() {
+        println!(
+            "No conflicts in {}.",
+            paths::display(report_path, config.path_style)
+        );
+        return Ok(());
+    }
+
+    let mut by_file: BTreeMap<&str, Vec<&ConflictEntry>> = BTreeMap::new();
+    for entry in &report.conflicts {
+        by_file.entry(entry.file.as_str()).or_default().push(entry);
+    }
+
+    let format = config.output_format();
+    let (mut resolved, mut unchanged) = (0, 0);
+    for (file, entries) in by_file {
+        let path = Path::new(file);
+        let existing = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read locale file: {}", path.display()))?;
+        let Value::Object(mut map) = json_sync::parse_locale_value_str(&existing, format, path)?
+        else {
+            bail!("Locale file root must be an object: {}", path.display());
+        };
+
+        let mut changed = 0;
+        for entry in entries {
+            if conflicts::resolve_in_map(&mut map, entry, strategy) {
+                changed += 1;
+                println!("  {} - {}:{}", file, entry.namespace, entry.key);
+            } else {
+                unchanged += 1;
+            }
+        }
+        if changed > 0 && !dry_run {
+            let style = json_sync::detect_json_style(&existing);
+            json_sync::write_locale_file(path, &map, format, config.key_sort, Some(&style))?;
+        }
+        resolved += changed;
+    }
+
+    println!(
+        "\n{} {} conflict(s)",
+        if dry_run { "Would resolve" } else { "Resolved" },
+        resolved
+    );
+    if unchanged > 0 {
+        println!(
+            "Left {} conflict(s) unchanged (kept, already fixed, or not resolvable with this strategy)",
+            unchanged
+        );
+    }
+    Ok(())
+}
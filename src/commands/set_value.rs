@@ -0,0 +1,56 @@
+use anyhow::{bail, Result};
+use serde_json::Value;
+
+use crate::config::Config;
+use crate::json_sync;
+use crate::paths;
+use crate::plan;
+
+/// Set `key` (`namespace:key.path`, or a key of the default namespace) to
+/// `value` in `locale`, keeping the locale file's formatting
+pub fn run(config: &Config, locale: &str, key: &str, value: &str, dry_run: bool) -> Result<()> {
+    println!("=== i18next-turbo set-value ===\n");
+    ensure_locale(config, locale)?;
+    let (namespace, key) = split_key(config, key);
+    let path = json_sync::locale_namespace_file_path(config, &config.output, locale, &namespace);
+
+    let value = Value::String(value.to_string());
+    if !plan::set_value(config, locale, &namespace, &key, &value, dry_run)? {
+        println!(
+            "  {} already has this value.",
+            paths::display(&path, config.path_style)
+        );
+    } else if dry_run {
+        println!(
+            "  [Dry run] Would update {}",
+            paths::display(&path, config.path_style)
+        );
+    } else {
+        println!("  Updated {}", paths::display(&path, config.path_style));
+    }
+    Ok(())
+}
+
+/// `namespace:key` -> (namespace, key), defaulting the namespace
+pub(crate) fn split_key(config: &Config, key: &str) -> (String, String) {
+    match key.split_once(config.ns_separator.as_str()) {
+        Some((namespace, key)) if !config.ns_separator.is_empty() => {
+            (namespace.to_string(), key.to_string())
+        }
+        _ => (
+            config.effective_default_namespace().to_string(),
+            key.to_string(),
+        ),
+    }
+}
+
+pub(crate) fn ensure_locale(config: &Config, locale: &str) -> Result<()> {
+    if !config.locales.iter().any(|configured| configured == locale) {
+        bail!(
+            "Unknown locale '{}'; configured locales: {}",
+            locale,
+            config.locales.join(", ")
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use anyhow::{Context, Result};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use crate::config::Config;
+use crate::json_sync;
+
+/// Byte sizes of one locale file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct FileSize {
+    raw: usize,
+    gzip: usize,
+}
+
+impl FileSize {
+    fn of(content: &[u8]) -> Result<Self> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(content)?;
+        Ok(Self {
+            raw: content.len(),
+            gzip: encoder.finish()?.len(),
+        })
+    }
+
+    fn add(&mut self, other: FileSize) {
+        self.raw += other.raw;
+        self.gzip += other.gzip;
+    }
+}
+
+/// Print the raw and gzip size of each namespace file of each locale, as
+/// served to the browser
+pub fn run(config: &Config, locale: Option<String>) -> Result<()> {
+    println!("=== i18next-turbo size ===\n");
+
+    let locales: Vec<String> = match locale {
+        Some(locale) => vec![locale],
+        None => config.locales.clone(),
+    };
+    println!("  Locales directory: {}", config.output);
+    println!("  Minify: {}", if config.minify { "on" } else { "off" });
+
+    let mut total = FileSize::default();
+    let mut file_count = 0;
+    for locale in &locales {
+        let mut rows: BTreeMap<String, FileSize> = BTreeMap::new();
+        for path in json_sync::locale_files(config, &config.output, locale)? {
+            let content = std::fs::read(&path)
+                .with_context(|| format!("Failed to read locale file: {}", path.display()))?;
+            let name = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default()
+                .to_string();
+            rows.insert(name, FileSize::of(&content)?);
+        }
+        if rows.is_empty() {
+            println!("\n[{}] no locale files", locale);
+            continue;
+        }
+
+        let mut locale_total = FileSize::default();
+        for size in rows.values() {
+            locale_total.add(*size);
+        }
+        println!("\n[{}]", locale);
+        for line in format_size_table(&rows, &locale_total) {
+            println!("  {}", line);
+        }
+        total.add(locale_total);
+        file_count += rows.len();
+    }
+
+    println!(
+        "\nTotal: {} file(s), {} raw, {} gzip",
+        file_count,
+        format_bytes(total.raw),
+        format_bytes(total.gzip)
+    );
+    Ok(())
+}
+
+fn format_size_table(rows: &BTreeMap<String, FileSize>, total: &FileSize) -> Vec<String> {
+    let width = rows
+        .keys()
+        .map(|ns| ns.chars().count())
+        .chain(["Namespace".len()])
+        .max()
+        .unwrap_or(0);
+    let line = |name: &str, size: &FileSize| {
+        format!(
+            "{:<width$}  {:>10}  {:>10}",
+            name,
+            format_bytes(size.raw),
+            format_bytes(size.gzip),
+            width = width
+        )
+    };
+
+    let mut lines = vec![format!(
+        "{:<width$}  {:>10}  {:>10}",
+        "Namespace",
+        "Raw",
+        "Gzip",
+        width = width
+    )];
+    lines.extend(rows.iter().map(|(namespace, size)| line(namespace, size)));
+    if rows.len() > 1 {
+        lines.push(line("Total", total));
+    }
+    lines
+}
+
+fn format_bytes(bytes: usize) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gzip_size_shrinks_repetitive_content() {
+        let content = r#"{"a":"Save changes","b":"Save changes","c":"Save changes"}"#.repeat(20);
+        let size = FileSize::of(content.as_bytes()).unwrap();
+        assert_eq!(size.raw, content.len());
+        assert!(size.gzip < size.raw / 4);
+    }
+
+    #[test]
+    fn size_table_lists_namespaces_and_total() {
+        let rows = BTreeMap::from([
+            (
+                "common".to_string(),
+                FileSize {
+                    raw: 2048,
+                    gzip: 700,
+                },
+            ),
+            ("translation".to_string(), FileSize { raw: 100, gzip: 90 }),
+        ]);
+        let lines = format_size_table(
+            &rows,
+            &FileSize {
+                raw: 2148,
+                gzip: 790,
+            },
+        );
+        assert_eq!(
+            lines,
+            vec![
+                "Namespace           Raw        Gzip",
+                "common          2.0 KiB       700 B",
+                "translation       100 B        90 B",
+                "Total           2.1 KiB       790 B",
+            ]
+        );
+    }
+}
@@ -1,32 +1,79 @@
 use anyhow::{bail, Result};
 use serde_json::Value;
-use std::collections::HashSet;
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 use crate::cleanup;
 use crate::config::Config;
 use crate::extractor::{self, ExtractedKey};
+use crate::json_sync;
+use crate::key_transforms;
+use crate::locale_store::LocaleStore;
+use crate::metadata::{self, MetadataFile, ReviewStatus};
+
+/// Key counts of one namespace in one locale
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct NamespaceStatus {
+    /// Keys extracted from source
+    source: usize,
+    /// Keys present in the locale files
+    locale: usize,
+    /// Source keys absent from the locale files
+    missing: usize,
+    /// Source keys with a non-empty value in the locale files
+    translated: usize,
+    /// Locale keys no longer used in source
+    dead: usize,
+}
+
+impl NamespaceStatus {
+    fn completeness(&self) -> f64 {
+        if self.source == 0 {
+            1.0
+        } else {
+            self.translated as f64 / self.source as f64
+        }
+    }
+
+    fn add(&mut self, other: &NamespaceStatus) {
+        self.source += other.source;
+        self.locale += other.locale;
+        self.missing += other.missing;
+        self.translated += other.translated;
+        self.dead += other.dead;
+    }
+}
+
+/// (namespace, key); the namespace is the default one in namespace-less mode
+type KeyId = (String, String);
 
 pub fn run(
     config: &Config,
     locale: Option<String>,
     fail_on_incomplete: bool,
     namespace: Option<String>,
+    min_completeness: Option<f64>,
 ) -> Result<()> {
     println!("=== i18next-turbo status ===\n");
 
-    // Determine locale to check
-    let check_locale = locale
-        .as_ref()
-        .or(config.locales.first())
-        .map(|s| s.as_str())
-        .unwrap_or("en");
+    if let Some(min) = min_completeness {
+        if !(0.0..=100.0).contains(&min) {
+            bail!("--min-completeness must be a percentage between 0 and 100");
+        }
+    }
+
+    // Determine locales to check (all configured locales by default)
+    let check_locales: Vec<String> = match locale {
+        Some(locale) => vec![locale],
+        None if config.locales.is_empty() => vec!["en".to_string()],
+        None => config.locales.clone(),
+    };
 
     let namespace_filter = namespace.as_deref();
 
     println!("Configuration:");
     println!("  Locales directory: {}", config.output);
-    println!("  Checking locale: {}", check_locale);
+    println!("  Checking locales: {}", check_locales.join(", "));
     println!(
         "  Default namespace: {}",
         config.effective_default_namespace()
@@ -38,196 +85,445 @@ pub fn run(
 
     // Extract keys from source
     println!("Scanning source files...");
-    let plural_config = config.plural_config();
-    let extraction = extractor::extract_from_glob_with_options(
-        &config.input,
-        &config.ignore,
-        &config.functions,
-        config.extract_from_comments,
-        &plural_config,
-        &config.trans_components,
-        &config.trans_keep_basic_html_nodes_for,
-        &config.use_translation_names,
-        &config.nesting_prefix,
-        &config.nesting_suffix,
-        &config.nesting_options_separator,
-        &config.interpolation_prefix,
-        &config.interpolation_suffix,
-    )?;
-
-    let mut source_keys: HashSet<String> = HashSet::new();
-    let mut all_keys: Vec<ExtractedKey> = Vec::new();
-    let namespace_less_mode = config.namespace_less_mode();
+    let mut extraction =
+        extractor::extract_with_config(config, &config.input, &config.extraction_ignore())?;
+    key_transforms::apply(config, &mut extraction.files);
 
-    for (_file_path, keys) in &extraction.files {
-        for key in keys {
-            let namespace = key
-                .namespace
-                .as_deref()
-                .unwrap_or(config.effective_default_namespace());
-            if namespace_filter.is_none_or(|filter| filter == namespace) {
-                let full_key = if namespace_less_mode {
-                    key.key.clone()
-                } else {
-                    format!("{}:{}", namespace, key.key)
-                };
-                source_keys.insert(full_key);
-            }
-            all_keys.push(key.clone());
-        }
-    }
+    // Each locale root is compared with the keys synced into it
+    let groups: Vec<(String, Vec<ExtractedKey>, HashSet<KeyId>)> = json_sync::group_keys_by_output(
+        config,
+        extraction.files.iter().map(|(path, keys)| (path, keys)),
+        &config.output,
+    )
+    .into_iter()
+    .map(|(root, keys)| {
+        let ids = source_key_ids(config, &keys, namespace_filter);
+        (root, keys, ids)
+    })
+    .collect();
+    let namespace_less_mode = config.namespace_less_mode();
+    let source_keys: HashSet<KeyId> = groups
+        .iter()
+        .flat_map(|(_, _, ids)| ids.iter().cloned())
+        .collect();
 
     println!("  Source files: {}", extraction.files.len());
     println!("  Keys in source: {}", source_keys.len());
 
-    // Count keys in locale files
-    let locales_path = Path::new(&config.output);
-    let locale_dir = locales_path.join(check_locale);
-
-    let mut locale_keys: HashSet<String> = HashSet::new();
-
-    if locale_dir.exists() {
-        for entry in std::fs::read_dir(&locale_dir)? {
-            let entry = entry?;
-            let path = entry.path();
+    let metadata = match config.metadata_file.as_deref() {
+        Some(path) => Some(MetadataFile::load(Path::new(path))?),
+        None => None,
+    };
 
-            if path.extension().map(|e| e == "json").unwrap_or(false) {
-                let namespace = path
-                    .file_stem()
-                    .and_then(|s| s.to_str())
-                    .unwrap_or("translation");
-
-                if let Some(filter) = namespace_filter {
-                    if namespace != filter {
-                        continue;
+    let store = LocaleStore::new();
+    for (root, _, _) in &groups {
+        store.preload_locales(config, root, &check_locales)?;
+    }
+    let mut incomplete_locales = Vec::new();
+    let mut below_threshold = Vec::new();
+    for check_locale in &check_locales {
+        let mut rows: BTreeMap<String, NamespaceStatus> = BTreeMap::new();
+        for (root, keys, ids) in &groups {
+            let locale_keys =
+                load_locale_keys(config, &store, root, check_locale, namespace_filter)?;
+            let dead_keys = cleanup::find_dead_keys_with_store(
+                &store,
+                Path::new(root),
+                keys,
+                config.effective_default_namespace(),
+                namespace_less_mode,
+                config.merge_namespaces,
+                config.merged_namespace_prefix(),
+                config.sharded_locale_files(),
+                config.preserve_context_variants,
+                &config.context_separator,
+                &config.plural_separator,
+                check_locale,
+                config.path_style,
+            )?;
+            let dead_namespaces: Vec<&str> = dead_keys
+                .iter()
+                .map(|dk| {
+                    if namespace_less_mode {
+                        config.effective_default_namespace()
+                    } else {
+                        dk.namespace.as_str()
                     }
-                }
+                })
+                .filter(|ns| namespace_filter.is_none_or(|filter| filter == *ns))
+                .collect();
+            for (namespace, row) in namespace_rows(ids, &locale_keys, &dead_namespaces) {
+                rows.entry(namespace).or_default().add(&row);
+            }
+        }
+        let mut total = NamespaceStatus::default();
+        rows.values().for_each(|row| total.add(row));
 
-                let content = std::fs::read_to_string(&path)?;
-                if content.trim().is_empty() {
-                    continue;
-                }
+        println!("\nLocale: {}", check_locale);
+        for line in format_namespace_table(&rows, &total) {
+            println!("  {}", line);
+        }
+        println!(
+            "  Progress: {}",
+            format_progress_bar(total.translated, total.source)
+        );
+        if let Some(line) = metadata
+            .as_ref()
+            .and_then(|metadata| format_review_summary(metadata, &source_keys, check_locale))
+        {
+            println!("  Review: {}", line);
+        }
 
-                if let Ok(json) = serde_json::from_str::<Value>(&content) {
-                    count_json_keys(
-                        &json,
-                        namespace,
-                        "",
-                        namespace_less_mode,
-                        config.merge_namespaces,
-                        &mut locale_keys,
-                    );
+        if total.missing > 0 || total.dead > 0 {
+            incomplete_locales.push((check_locale.clone(), total.missing, total.dead));
+        }
+        if let Some(min) = min_completeness {
+            for (namespace, row) in &rows {
+                let percent = row.completeness() * 100.0;
+                if row.source > 0 && percent < min {
+                    below_threshold.push(format!("{}/{} {:.1}%", check_locale, namespace, percent));
                 }
             }
         }
     }
 
-    println!("  Keys in locale: {}", locale_keys.len());
-
-    // Find dead keys
-    let dead_keys = cleanup::find_dead_keys(
-        locales_path,
-        &all_keys,
-        config.effective_default_namespace(),
-        namespace_less_mode,
-        config.merge_namespaces,
-        config.preserve_context_variants,
-        &config.context_separator,
-        check_locale,
-    )?;
-    let dead_keys: Vec<_> = dead_keys
-        .into_iter()
-        .filter(|dk| namespace_filter.is_none_or(|ns| dk.namespace == ns))
-        .collect();
-
-    // Find missing keys (in source but not in locale)
-    let missing_count = source_keys
-        .iter()
-        .filter(|k| !locale_keys.contains(*k))
-        .count();
-
-    let total_keys = source_keys.len();
-    let completed = total_keys.saturating_sub(missing_count);
-    println!("  Progress: {}", format_progress_bar(completed, total_keys));
+    if let Some(metadata) = &metadata {
+        let growth = metadata.growth_by_month();
+        if !growth.is_empty() {
+            println!("\nKey growth (new keys per month):");
+            for line in format_growth_chart(&growth) {
+                println!("  {}", line);
+            }
+        }
+        if let Some(days) = config.removal_grace_days {
+            let lines = format_pending_removals(metadata, days, &metadata::today());
+            if !lines.is_empty() {
+                println!(
+                    "\nPending removal ({} dead key(s), removalGraceDays: {}):",
+                    lines.len(),
+                    days
+                );
+                for line in lines {
+                    println!("  {}", line);
+                }
+            }
+        }
+    }
 
     // Summary
     println!("\n{}", "=".repeat(40));
     println!("Summary:");
     println!("{}", "=".repeat(40));
 
-    let is_incomplete = missing_count > 0 || !dead_keys.is_empty();
-
-    if !is_incomplete {
+    if incomplete_locales.is_empty() {
         println!("  \x1b[32m✓\x1b[0m All keys are synchronized!");
     } else {
-        if missing_count > 0 {
-            println!(
-                "  \x1b[33m!\x1b[0m Missing keys (in source, not in locale): {}",
-                missing_count
-            );
-        }
-        if !dead_keys.is_empty() {
+        for (locale, missing, dead) in &incomplete_locales {
             println!(
-                "  \x1b[33m!\x1b[0m Dead keys (in locale, not in source): {}",
-                dead_keys.len()
+                "  \x1b[33m!\x1b[0m {}: {} missing (in source, not in locale), {} dead (in locale, not in source)",
+                locale, missing, dead
             );
         }
         println!();
         println!("Run 'i18next-turbo extract' to add missing keys.");
-        if !dead_keys.is_empty() {
+        if incomplete_locales.iter().any(|(_, _, dead)| *dead > 0) {
             println!("Run 'i18next-turbo check --remove' to remove dead keys.");
         }
     }
 
     // Fail if incomplete and --fail-on-incomplete is set
-    if fail_on_incomplete && is_incomplete {
+    if fail_on_incomplete && !incomplete_locales.is_empty() {
+        let missing: usize = incomplete_locales.iter().map(|(_, m, _)| m).sum();
+        let dead: usize = incomplete_locales.iter().map(|(_, _, d)| d).sum();
         bail!(
             "Translations are incomplete: {} missing, {} dead (--fail-on-incomplete enabled)",
-            missing_count,
-            dead_keys.len()
+            missing,
+            dead
         );
     }
+    if let Some(min) = min_completeness {
+        if !below_threshold.is_empty() {
+            bail!(
+                "Completeness below {:.1}% (--min-completeness): {}",
+                min,
+                below_threshold.join(", ")
+            );
+        }
+    }
 
     Ok(())
 }
 
-/// Count all leaf keys in a JSON structure
+/// Namespaced ids of extracted `keys`, limited to `namespace_filter`
+fn source_key_ids(
+    config: &Config,
+    keys: &[ExtractedKey],
+    namespace_filter: Option<&str>,
+) -> HashSet<KeyId> {
+    let default_namespace = config.effective_default_namespace();
+    keys.iter()
+        .filter_map(|key| {
+            let namespace = if config.namespace_less_mode() {
+                default_namespace
+            } else {
+                key.namespace.as_deref().unwrap_or(default_namespace)
+            };
+            namespace_filter
+                .is_none_or(|filter| filter == namespace)
+                .then(|| (namespace.to_string(), key.key.clone()))
+        })
+        .collect()
+}
+
+/// Keys of one locale's JSON files under `root`, with whether each has a
+/// non-empty value
+fn load_locale_keys(
+    config: &Config,
+    store: &LocaleStore,
+    root: &str,
+    locale: &str,
+    namespace_filter: Option<&str>,
+) -> Result<HashMap<KeyId, bool>> {
+    let locales_path = Path::new(root);
+    let locale_dir = locales_path.join(locale);
+    let namespace_less_mode = config.namespace_less_mode();
+
+    let mut locale_keys = HashMap::new();
+
+    let flat_file = json_sync::flat_locale_file(locales_path, locale, "json");
+    let paths: Vec<PathBuf> = if let Some(path) = &flat_file {
+        vec![path.clone()]
+    } else if locale_dir.exists() {
+        std::fs::read_dir(&locale_dir)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<_>>()?
+    } else {
+        Vec::new()
+    };
+
+    for path in paths {
+        if path.extension().map(|e| e == "json").unwrap_or(false) {
+            let namespace = if flat_file.is_some() || namespace_less_mode {
+                config.effective_default_namespace()
+            } else {
+                json_sync::file_namespace(&path, config.sharded_locale_files())
+                    .unwrap_or("translation")
+            };
+
+            if let Ok(Some(json)) = store.get(&path) {
+                let mut json = Value::clone(&json);
+                if config.merge_namespaces && !namespace_less_mode {
+                    if let Value::Object(root) = json {
+                        json = Value::Object(json_sync::split_merged_namespaces(
+                            root,
+                            config.merged_namespace_prefix(),
+                        ));
+                    }
+                }
+                count_json_keys(
+                    &json,
+                    namespace,
+                    "",
+                    config.merge_namespaces && !namespace_less_mode,
+                    &mut locale_keys,
+                );
+            }
+        }
+    }
+
+    if let Some(filter) = namespace_filter {
+        locale_keys.retain(|(namespace, _), _| namespace == filter);
+    }
+    Ok(locale_keys)
+}
+
+/// Per-namespace counts for one locale, sorted by namespace
+fn namespace_rows(
+    source_keys: &HashSet<KeyId>,
+    locale_keys: &HashMap<KeyId, bool>,
+    dead_namespaces: &[&str],
+) -> BTreeMap<String, NamespaceStatus> {
+    let mut rows: BTreeMap<String, NamespaceStatus> = BTreeMap::new();
+    for id in source_keys {
+        let row = rows.entry(id.0.clone()).or_default();
+        row.source += 1;
+        match locale_keys.get(id) {
+            Some(true) => row.translated += 1,
+            Some(false) => {}
+            None => row.missing += 1,
+        }
+    }
+    for (namespace, _) in locale_keys.keys() {
+        rows.entry(namespace.clone()).or_default().locale += 1;
+    }
+    for namespace in dead_namespaces {
+        rows.entry(namespace.to_string()).or_default().dead += 1;
+    }
+    rows
+}
+
+fn format_namespace_table(
+    rows: &BTreeMap<String, NamespaceStatus>,
+    total: &NamespaceStatus,
+) -> Vec<String> {
+    let width = rows
+        .keys()
+        .map(|ns| ns.chars().count())
+        .chain(["Namespace".len()])
+        .max()
+        .unwrap_or(0);
+    let line = |name: &str, row: &NamespaceStatus| {
+        format!(
+            "{:<width$}  {:>6}  {:>6}  {:>7}  {:>4}  {:>8}",
+            name,
+            row.source,
+            row.locale,
+            row.missing,
+            row.dead,
+            format!("{:.1}%", row.completeness() * 100.0),
+            width = width
+        )
+    };
+
+    let mut lines = vec![format!(
+        "{:<width$}  {:>6}  {:>6}  {:>7}  {:>4}  {:>8}",
+        "Namespace",
+        "Source",
+        "Locale",
+        "Missing",
+        "Dead",
+        "Complete",
+        width = width
+    )];
+    lines.extend(rows.iter().map(|(namespace, row)| line(namespace, row)));
+    if rows.len() > 1 {
+        lines.push(line("Total", total));
+    }
+    lines
+}
+
+/// Collect all leaf keys in a JSON structure, recording whether each has a
+/// non-empty value
 fn count_json_keys(
     value: &Value,
     namespace: &str,
     prefix: &str,
-    namespace_less_mode: bool,
     merge_namespaces: bool,
-    keys: &mut HashSet<String>,
+    keys: &mut HashMap<KeyId, bool>,
 ) {
     match value {
         Value::Object(obj) => {
-            if merge_namespaces && !namespace_less_mode && prefix.is_empty() {
+            if merge_namespaces && prefix.is_empty() {
                 for (root_ns, nested) in obj {
-                    count_json_keys(nested, root_ns, "", namespace_less_mode, false, keys);
+                    if !json_sync::is_marker_key(root_ns) {
+                        count_json_keys(nested, root_ns, "", false, keys);
+                    }
                 }
                 return;
             }
-            for (k, v) in obj {
+            for (k, v) in obj.iter().filter(|(k, _)| !json_sync::is_marker_key(k)) {
                 let path = if prefix.is_empty() {
                     k.clone()
                 } else {
                     format!("{}.{}", prefix, k)
                 };
-                count_json_keys(v, namespace, &path, namespace_less_mode, false, keys);
+                count_json_keys(v, namespace, &path, false, keys);
             }
         }
-        Value::String(_) => {
-            if namespace_less_mode {
-                keys.insert(prefix.to_string());
-            } else {
-                keys.insert(format!("{}:{}", namespace, prefix));
+        Value::String(_) | Value::Array(_) => {
+            let translated = match value {
+                Value::String(s) => !s.trim().is_empty(),
+                _ => true,
+            };
+            keys.insert((namespace.to_string(), prefix.to_string()), translated);
+            // Array entries are keys of their own as well (`steps.0`)
+            if let Value::Array(items) = value {
+                for (index, item) in items.iter().enumerate() {
+                    let path = format!("{}.{}", prefix, index);
+                    count_json_keys(item, namespace, &path, false, keys);
+                }
             }
         }
         _ => {}
     }
 }
 
+/// One bar per month, scaled to the busiest month, with the running key total
+/// "2 approved, 1 needs-review, 0 machine-translated, 5 unmarked" for the
+/// source keys of `locale`; `None` when no key of the locale has been marked
+fn format_review_summary(
+    metadata: &MetadataFile,
+    source_keys: &HashSet<KeyId>,
+    locale: &str,
+) -> Option<String> {
+    let mut counts: BTreeMap<ReviewStatus, usize> = BTreeMap::new();
+    for (namespace, key) in source_keys {
+        if let Some(status) = metadata.review_status(namespace, key, locale) {
+            *counts.entry(status).or_insert(0) += 1;
+        }
+    }
+    if counts.is_empty() {
+        return None;
+    }
+    let marked: usize = counts.values().sum();
+    let mut parts: Vec<String> = ReviewStatus::ALL
+        .iter()
+        .rev()
+        .map(|status| {
+            format!(
+                "{} {}",
+                counts.get(status).copied().unwrap_or(0),
+                status.as_str()
+            )
+        })
+        .collect();
+    parts.push(format!("{} unmarked", source_keys.len() - marked));
+    Some(parts.join(", "))
+}
+
+/// One line per key waiting out `removalGraceDays`, with the day extraction
+/// removes it
+fn format_pending_removals(metadata: &MetadataFile, days: u32, today: &str) -> Vec<String> {
+    metadata
+        .dead_keys()
+        .into_iter()
+        .map(|(namespace, key, since)| {
+            let purge = metadata::add_days(since, i64::from(days)).unwrap_or_default();
+            let when = if purge.as_str() <= today {
+                "by the next extract".to_string()
+            } else {
+                format!("on {}", purge)
+            };
+            format!(
+                "{}:{} (dead since {}, removed {})",
+                namespace, key, since, when
+            )
+        })
+        .collect()
+}
+
+fn format_growth_chart(growth: &BTreeMap<String, usize>) -> Vec<String> {
+    const BAR_WIDTH: usize = 30;
+
+    let busiest = growth.values().copied().max().unwrap_or(0).max(1);
+    let mut total = 0;
+    growth
+        .iter()
+        .map(|(month, &added)| {
+            total += added;
+            let filled = (added * BAR_WIDTH).div_ceil(busiest);
+            format!(
+                "{} {:<width$} +{} ({} total)",
+                month,
+                "#".repeat(filled),
+                added,
+                total,
+                width = BAR_WIDTH
+            )
+        })
+        .collect()
+}
+
 fn format_progress_bar(completed: usize, total: usize) -> String {
     const BAR_WIDTH: usize = 30;
 
@@ -245,17 +541,157 @@ fn format_progress_bar(completed: usize, total: usize) -> String {
 mod tests {
     use super::*;
     use serde_json::json;
+    use std::collections::BTreeSet;
+
+    fn key_ids(keys: &HashMap<KeyId, bool>) -> Vec<String> {
+        let mut ids: Vec<String> = keys
+            .keys()
+            .map(|(ns, key)| format!("{}:{}", ns, key))
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn count_json_keys_counts_array_entries() {
+        let value = json!({ "steps": ["First", "Second"], "faq": [{ "q": "Why?" }] });
+        let mut keys = HashMap::new();
+        count_json_keys(&value, "translation", "", false, &mut keys);
+        assert_eq!(
+            key_ids(&keys),
+            vec![
+                "translation:faq",
+                "translation:faq.0.q",
+                "translation:steps",
+                "translation:steps.0",
+                "translation:steps.1",
+            ]
+        );
+    }
+
+    #[test]
+    fn count_json_keys_skips_marker_entries() {
+        let value = json!({
+            "//": "Dynamic keys",
+            "dyn": { "__preserve__": ["*"], "x": "X" }
+        });
+        let mut keys = HashMap::new();
+        count_json_keys(&value, "translation", "", false, &mut keys);
+        assert_eq!(key_ids(&keys), vec!["translation:dyn.x"]);
+    }
 
     #[test]
     fn count_json_keys_supports_merged_namespace_object_shape() {
         let value = json!({
             "common": { "hello": "x" },
-            "home": { "title": "y" }
+            "home": { "title": "" }
         });
-        let mut keys = HashSet::new();
-        count_json_keys(&value, "translation", "", false, true, &mut keys);
-        assert!(keys.contains("common:hello"));
-        assert!(keys.contains("home:title"));
-        assert_eq!(keys.len(), 2);
+        let mut keys = HashMap::new();
+        count_json_keys(&value, "translation", "", true, &mut keys);
+        assert_eq!(key_ids(&keys), vec!["common:hello", "home:title"]);
+        assert!(keys[&("common".to_string(), "hello".to_string())]);
+        assert!(!keys[&("home".to_string(), "title".to_string())]);
+    }
+
+    #[test]
+    fn namespace_rows_break_down_missing_translated_and_dead() {
+        let id = |ns: &str, key: &str| (ns.to_string(), key.to_string());
+        let source = HashSet::from([
+            id("common", "ok"),
+            id("common", "cancel"),
+            id("home", "title"),
+            id("home", "intro"),
+        ]);
+        let locale = HashMap::from([
+            (id("common", "ok"), true),
+            (id("common", "cancel"), false),
+            (id("home", "title"), true),
+            (id("home", "old"), true),
+        ]);
+
+        let rows = namespace_rows(&source, &locale, &["home"]);
+        assert_eq!(
+            rows["common"],
+            NamespaceStatus {
+                source: 2,
+                locale: 2,
+                missing: 0,
+                translated: 1,
+                dead: 0,
+            }
+        );
+        assert_eq!(rows["home"].missing, 1);
+        assert_eq!(rows["home"].dead, 1);
+
+        let mut total = NamespaceStatus::default();
+        rows.values().for_each(|row| total.add(row));
+        let table = format_namespace_table(&rows, &total);
+        assert_eq!(
+            table[0],
+            "Namespace  Source  Locale  Missing  Dead  Complete"
+        );
+        assert_eq!(
+            table[1],
+            "common          2       2        0     0     50.0%"
+        );
+        assert_eq!(
+            table[3],
+            "Total           4       4        1     1     50.0%"
+        );
+    }
+
+    #[test]
+    fn review_summary_counts_marked_source_keys_of_locale() {
+        let mut metadata = MetadataFile::default();
+        metadata.set_review("common", "ok", "de", ReviewStatus::Approved);
+        metadata.set_review("common", "cancel", "de", ReviewStatus::NeedsReview);
+        metadata.set_review("common", "gone", "de", ReviewStatus::Approved);
+        metadata.set_review("common", "ok", "fr", ReviewStatus::MachineTranslated);
+        let source_keys: HashSet<KeyId> = ["ok", "cancel", "save"]
+            .into_iter()
+            .map(|key| ("common".to_string(), key.to_string()))
+            .collect();
+
+        assert_eq!(
+            format_review_summary(&metadata, &source_keys, "de").as_deref(),
+            Some("1 approved, 1 needs-review, 0 machine-translated, 1 unmarked")
+        );
+        assert_eq!(format_review_summary(&metadata, &source_keys, "ja"), None);
+    }
+
+    #[test]
+    fn pending_removals_show_the_day_keys_go() {
+        let mut metadata = MetadataFile::default();
+        let pending: BTreeSet<(String, String)> = [("common", "sale"), ("common", "xmas")]
+            .iter()
+            .map(|(ns, key)| (ns.to_string(), key.to_string()))
+            .collect();
+        metadata.record_removals(|_, _| true, &pending, &BTreeSet::new(), "2026-09-01");
+
+        assert_eq!(
+            format_pending_removals(&metadata, 30, "2026-09-15"),
+            vec![
+                "common:sale (dead since 2026-09-01, removed on 2026-10-01)",
+                "common:xmas (dead since 2026-09-01, removed on 2026-10-01)",
+            ]
+        );
+        assert_eq!(
+            format_pending_removals(&metadata, 7, "2026-09-15")[0],
+            "common:sale (dead since 2026-09-01, removed by the next extract)"
+        );
+    }
+
+    #[test]
+    fn growth_chart_scales_to_busiest_month_and_accumulates() {
+        let growth = BTreeMap::from([("2026-01".to_string(), 10), ("2026-02".to_string(), 5)]);
+        let lines = format_growth_chart(&growth);
+        assert_eq!(
+            lines[0],
+            format!("2026-01 {} +10 (10 total)", "#".repeat(30))
+        );
+        assert_eq!(
+            lines[1],
+            format!("2026-02 {}{} +5 (15 total)", "#".repeat(15), " ".repeat(15))
+        );
     }
 }
@@ -1,11 +1,27 @@
 use anyhow::{Context, Result};
 use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::path::Path;
 
+use crate::cleanup;
+use crate::commands::check;
 use crate::config::Config;
 use crate::json_sync;
+use crate::metadata;
+use crate::nesting;
+use crate::paths;
+use crate::plan::{self, ChangePlan};
+use crate::summary;
 
-pub fn run(config: &Config, remove_unused: bool, dry_run: bool) -> Result<()> {
+/// Copy missing keys from the primary locale to the others and, with
+/// `remove_unused`, drop keys the primary locale lacks. With `emit_plan` the
+/// additions and removals are written as a change plan instead.
+pub fn run(
+    config: &Config,
+    remove_unused: bool,
+    dry_run: bool,
+    emit_plan: Option<&Path>,
+) -> Result<()> {
     println!("=== i18next-turbo sync ===\n");
 
     if config.locales.len() < 2 {
@@ -38,13 +54,18 @@ pub fn run(config: &Config, remove_unused: bool, dry_run: bool) -> Result<()> {
     if !primary_dir.exists() {
         println!(
             "Primary locale directory does not exist: {}",
-            primary_dir.display()
+            paths::display(&primary_dir, config.path_style)
         );
         return Ok(());
     }
 
     let mut total_added = 0;
     let mut total_removed = 0;
+    let mut emptied = Vec::new();
+    let expired = metadata::expired_dead_keys(config)?;
+    let mut pending = BTreeSet::new();
+    let mut purged = BTreeSet::new();
+    let mut changes = Vec::new();
 
     // Process each namespace file in primary locale
     for entry in std::fs::read_dir(&primary_dir)? {
@@ -91,16 +112,45 @@ pub fn run(config: &Config, remove_unused: bool, dry_run: bool) -> Result<()> {
                 };
 
                 // Sync keys
-                let (added, removed) =
-                    sync_json_keys(&primary_json, &mut secondary_json, remove_unused);
+                let mut prune = Prune {
+                    key_separator: &config.key_separator,
+                    namespace,
+                    expired: expired.as_ref(),
+                    pending: &mut pending,
+                    purged: &mut purged,
+                };
+                let before = secondary_json.clone();
+                let (added, removed) = sync_json_keys(
+                    &primary_json,
+                    &mut secondary_json,
+                    "",
+                    remove_unused.then_some(&mut prune),
+                );
 
-                if added > 0 || removed > 0 {
-                    println!(
-                        "  {}/{}.{}: +{} added, -{} removed",
-                        secondary_locale, namespace, extension, added, removed
-                    );
+                // A copied marker is a change even though it adds no key
+                if secondary_json != before {
+                    if added > 0 || removed > 0 {
+                        println!(
+                            "  {}/{}.{}: +{} added, -{} removed",
+                            secondary_locale, namespace, extension, added, removed
+                        );
+                    }
 
-                    if !dry_run {
+                    if emit_plan.is_some() {
+                        let leaves = |json: &Value| {
+                            let map = json.as_object().cloned().unwrap_or_default();
+                            json_sync::flatten_leaf_values(
+                                &BTreeMap::from([(namespace.to_string(), map)]),
+                                &config.key_separator,
+                            )
+                        };
+                        changes.extend(plan::value_changes(
+                            config,
+                            secondary_locale,
+                            &leaves(&before),
+                            &leaves(&secondary_json),
+                        ));
+                    } else if !dry_run {
                         // Ensure directory exists
                         if let Some(parent) = secondary_path.parent() {
                             std::fs::create_dir_all(parent)?;
@@ -112,11 +162,15 @@ pub fn run(config: &Config, remove_unused: bool, dry_run: bool) -> Result<()> {
                                 &secondary_path,
                                 &sorted,
                                 output_format,
+                                config.key_sort,
                                 None,
                             )?;
                         }
                     }
 
+                    if config.remove_empty_files && cleanup::is_empty_locale(&secondary_json) {
+                        emptied.push(secondary_path.clone());
+                    }
                     total_added += added;
                     total_removed += removed;
                 }
@@ -124,6 +178,25 @@ pub fn run(config: &Config, remove_unused: bool, dry_run: bool) -> Result<()> {
         }
     }
 
+    if !pending.is_empty() {
+        println!("  Kept {} key(s) within removalGraceDays", pending.len());
+    }
+    if let Some(path) = emit_plan {
+        return ChangePlan::new(changes).emit(path, config.path_style);
+    }
+    if remove_unused && !dry_run {
+        // Only extraction knows which keys are in use again
+        metadata::record_removals(config, |_, _| false, &pending, &purged)?;
+    }
+
+    summary::add_keys(total_added, if remove_unused { total_removed } else { 0 });
+    check::report_deleted_files(
+        &cleanup::delete_empty_files(locales_path, &emptied, dry_run)?,
+        dry_run,
+        config.path_style,
+    );
+    report_nesting(config, &primary_locale)?;
+
     println!();
     if total_added == 0 && total_removed == 0 {
         println!("All locales are already in sync!");
@@ -143,15 +216,99 @@ pub fn run(config: &Config, remove_unused: bool, dry_run: bool) -> Result<()> {
     Ok(())
 }
 
-/// Sync JSON keys from primary to secondary, returning (added, removed) counts
-fn sync_json_keys(primary: &Value, secondary: &mut Value, remove_unused: bool) -> (usize, usize) {
+/// Warn about `$t(...)` cycles and missing references in the primary locale,
+/// which every secondary locale inherits through sync
+fn report_nesting(config: &Config, primary_locale: &str) -> Result<()> {
+    let scope = json_sync::PreserveMatcher::for_config(config)?;
+    let issues = nesting::check_nesting(config, &[primary_locale.to_string()], &scope)?;
+    if issues.is_empty() {
+        return Ok(());
+    }
+    println!("\nNesting warnings ({}):", primary_locale);
+    for issue in &issues {
+        println!(
+            "  {}:{} {} ({})",
+            issue.namespace, issue.key, issue.problem, issue.file
+        );
+    }
+    println!("  Run `i18next-turbo check --nesting` to validate every locale.");
+    Ok(())
+}
+
+/// Removal of the secondary keys the primary locale lacks, holding back those
+/// still within `removalGraceDays`
+struct Prune<'a> {
+    key_separator: &'a str,
+    namespace: &'a str,
+    /// Keys whose grace period is over; `None` without a grace period
+    expired: Option<&'a HashSet<(String, String)>>,
+    pending: &'a mut BTreeSet<(String, String)>,
+    purged: &'a mut BTreeSet<(String, String)>,
+}
+
+impl Prune<'_> {
+    fn path(&self, prefix: &str, key: &str) -> String {
+        if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}{}{}", prefix, self.key_separator, key)
+        }
+    }
+
+    /// Remove the removable keys of `value` at `path`, returning the number of
+    /// keys removed and whether nothing of `value` is left
+    fn remove(&mut self, path: &str, value: &mut Value) -> (usize, bool) {
+        if let Value::Object(obj) = value {
+            let mut removed = 0;
+            let keys: Vec<String> = obj
+                .keys()
+                .filter(|key| !json_sync::is_marker_key(key))
+                .cloned()
+                .collect();
+            for key in keys {
+                let child_path = self.path(path, &key);
+                if let Some(child) = obj.get_mut(&key) {
+                    let (count, gone) = self.remove(&child_path, child);
+                    removed += count;
+                    if gone {
+                        obj.remove(&key);
+                    }
+                }
+            }
+            return (removed, obj.keys().all(|key| json_sync::is_marker_key(key)));
+        }
+        let id = (self.namespace.to_string(), path.to_string());
+        if self.expired.is_none_or(|expired| expired.contains(&id)) {
+            self.purged.insert(id);
+            (count_leaf_keys(value), true)
+        } else {
+            self.pending.insert(id);
+            (0, false)
+        }
+    }
+}
+
+/// Sync JSON keys from primary to secondary, returning (added, removed) counts.
+/// Keys missing from primary are removed only with a `prune`. Marker entries
+/// (`__preserve__`, `"//"`) are copied as they are and never counted or pruned,
+/// so they protect the same keys in every locale.
+fn sync_json_keys(
+    primary: &Value,
+    secondary: &mut Value,
+    prefix: &str,
+    mut prune: Option<&mut Prune>,
+) -> (usize, usize) {
     let mut added = 0;
     let mut removed = 0;
 
     if let (Value::Object(primary_obj), Value::Object(secondary_obj)) = (primary, secondary) {
         // Add missing keys from primary
         for (key, primary_value) in primary_obj {
-            if !secondary_obj.contains_key(key) {
+            if json_sync::is_marker_key(key) {
+                if !secondary_obj.contains_key(key) {
+                    secondary_obj.insert(key.clone(), primary_value.clone());
+                }
+            } else if !secondary_obj.contains_key(key) {
                 // Add key with empty string or nested object
                 let new_value = create_empty_structure(primary_value);
                 secondary_obj.insert(key.clone(), new_value);
@@ -159,7 +316,12 @@ fn sync_json_keys(primary: &Value, secondary: &mut Value, remove_unused: bool) -
             } else if let Value::Object(_) = primary_value {
                 // Recursively sync nested objects
                 if let Some(secondary_value) = secondary_obj.get_mut(key) {
-                    let (a, r) = sync_json_keys(primary_value, secondary_value, remove_unused);
+                    let path = match prune.as_deref() {
+                        Some(prune) => prune.path(prefix, key),
+                        None => String::new(),
+                    };
+                    let (a, r) =
+                        sync_json_keys(primary_value, secondary_value, &path, prune.as_deref_mut());
                     added += a;
                     removed += r;
                 }
@@ -167,16 +329,21 @@ fn sync_json_keys(primary: &Value, secondary: &mut Value, remove_unused: bool) -
         }
 
         // Remove keys that don't exist in primary
-        if remove_unused {
+        if let Some(prune) = prune {
             let keys_to_remove: Vec<String> = secondary_obj
                 .keys()
-                .filter(|k| !primary_obj.contains_key(*k))
+                .filter(|k| !primary_obj.contains_key(*k) && !json_sync::is_marker_key(k))
                 .cloned()
                 .collect();
 
             for key in keys_to_remove {
-                if let Some(value) = secondary_obj.remove(&key) {
-                    removed += count_leaf_keys(&value);
+                let path = prune.path(prefix, &key);
+                if let Some(value) = secondary_obj.get_mut(&key) {
+                    let (count, gone) = prune.remove(&path, value);
+                    removed += count;
+                    if gone {
+                        secondary_obj.remove(&key);
+                    }
                 }
             }
         }
@@ -185,24 +352,37 @@ fn sync_json_keys(primary: &Value, secondary: &mut Value, remove_unused: bool) -
     (added, removed)
 }
 
-/// Create an empty structure matching the primary's structure
+/// Create an empty structure matching the primary's structure, keeping marker
+/// entries as they are
 fn create_empty_structure(value: &Value) -> Value {
     match value {
         Value::Object(obj) => {
             let mut new_obj = Map::new();
             for (k, v) in obj {
-                new_obj.insert(k.clone(), create_empty_structure(v));
+                let v = if json_sync::is_marker_key(k) {
+                    v.clone()
+                } else {
+                    create_empty_structure(v)
+                };
+                new_obj.insert(k.clone(), v);
             }
             Value::Object(new_obj)
         }
+        Value::Array(items) => Value::Array(items.iter().map(create_empty_structure).collect()),
         _ => Value::String(String::new()),
     }
 }
 
-/// Count the number of leaf keys in a JSON structure
+/// Count the number of leaf keys in a JSON structure (each array entry counts),
+/// skipping marker entries
 fn count_leaf_keys(value: &Value) -> usize {
     match value {
-        Value::Object(obj) => obj.values().map(count_leaf_keys).sum(),
+        Value::Object(obj) => obj
+            .iter()
+            .filter(|(key, _)| !json_sync::is_marker_key(key))
+            .map(|(_, value)| count_leaf_keys(value))
+            .sum(),
+        Value::Array(items) => items.iter().map(count_leaf_keys).sum(),
         Value::String(_) => 1,
         _ => 0,
     }
@@ -0,0 +1,358 @@
+//! Provider adapters shared by `pull` and `push`: each one downloads the
+//! catalog of one locale and namespace and adds new keys through the TMS API.
+
+use anyhow::{anyhow, bail, Context, Result};
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::StatusCode;
+use serde_json::{json, Map, Value};
+use std::env;
+use std::path::PathBuf;
+
+use crate::commands::locize;
+use crate::config::{Config, LocizeConfig, TmsConfig, TmsProvider};
+use crate::json_sync;
+
+pub trait TmsAdapter {
+    fn provider(&self) -> TmsProvider;
+
+    /// Catalog of `namespace` in `locale`, `None` when the TMS does not have it
+    fn download(&self, locale: &str, namespace: &str) -> Result<Option<Value>>;
+
+    /// Add `keys` (flat key, value) to `namespace` in `locale`
+    fn add_keys(&self, locale: &str, namespace: &str, keys: &[(String, Value)]) -> Result<()>;
+}
+
+/// Adapter for the `tms` section of `config`
+pub fn adapter(config: &Config) -> Result<Box<dyn TmsAdapter>> {
+    let tms = config.tms.as_ref().ok_or_else(|| {
+        anyhow!(
+            "No TMS configured. Add a \"tms\" section with provider and projectId to the config."
+        )
+    })?;
+    let client = Client::new();
+    let base_url = |default: &str| {
+        tms.base_url
+            .as_deref()
+            .unwrap_or(default)
+            .trim_end_matches('/')
+            .to_string()
+    };
+    Ok(match tms.provider {
+        TmsProvider::Locize => {
+            // Same settings, API key and host as the `locize` command
+            let locize = LocizeConfig {
+                project_id: tms.project_id.clone(),
+                api_key: tms.api_key.clone(),
+                version: tms.version.clone(),
+                namespaces: tms.namespaces.clone(),
+                ..LocizeConfig::default()
+            };
+            Box::new(Locize {
+                client,
+                base_url: base_url(&format!("https://{}", locize::download_base_host(&locize))),
+                api_key: locize::resolve_api_key(&locize)?,
+                locize,
+            })
+        }
+        TmsProvider::Crowdin => Box::new(Crowdin {
+            client,
+            base_url: base_url("https://api.crowdin.com/api/v2"),
+            project_id: tms.project_id.clone(),
+            api_key: resolve_api_key(tms, "CROWDIN_PERSONAL_TOKEN")?,
+            source_language: config.primary_language().to_string(),
+        }),
+        TmsProvider::Phrase => Box::new(Phrase {
+            client,
+            base_url: base_url("https://api.phrase.com/v2"),
+            project_id: tms.project_id.clone(),
+            api_key: resolve_api_key(tms, "PHRASE_ACCESS_TOKEN")?,
+        }),
+    })
+}
+
+fn resolve_api_key(tms: &TmsConfig, variable: &str) -> Result<String> {
+    tms.api_key
+        .clone()
+        .or_else(|| env::var(variable).ok())
+        .filter(|key| !key.trim().is_empty())
+        .ok_or_else(|| {
+            anyhow!(
+                "No {} API key. Set tms.apiKey or {}.",
+                tms.provider.name(),
+                variable
+            )
+        })
+}
+
+/// Local locale files of `locale` with their namespace, limited to
+/// `namespace` or `tms.namespaces` when given
+pub fn namespace_files(
+    config: &Config,
+    locale: &str,
+    namespace: Option<&str>,
+) -> Result<Vec<(String, PathBuf)>> {
+    if config.merge_namespaces {
+        bail!("pull and push do not support mergeNamespaces; use one file per namespace");
+    }
+    let wanted: Option<Vec<String>> = match namespace {
+        Some(namespace) => Some(vec![namespace.to_string()]),
+        None => config.tms.as_ref().and_then(|tms| tms.namespaces.clone()),
+    };
+    let mut files = Vec::new();
+    for path in json_sync::locale_files(config, &config.output, locale)? {
+        let namespace = if config.flat_locale_files {
+            config.effective_default_namespace()
+        } else {
+            json_sync::file_namespace(&path, config.sharded_locale_files()).unwrap_or("translation")
+        }
+        .to_string();
+        if wanted
+            .as_ref()
+            .is_none_or(|wanted| wanted.contains(&namespace))
+        {
+            files.push((namespace, path));
+        }
+    }
+    Ok(files)
+}
+
+fn send(request: RequestBuilder, provider: TmsProvider, what: &str) -> Result<Response> {
+    let response = request
+        .send()
+        .with_context(|| format!("{} request failed: {}", provider.name(), what))?;
+    ensure_success(response, provider, what)
+}
+
+/// JSON body of a response, `None` for 404
+fn json_or_missing(
+    request: RequestBuilder,
+    provider: TmsProvider,
+    what: &str,
+) -> Result<Option<Value>> {
+    let response = request
+        .send()
+        .with_context(|| format!("{} request failed: {}", provider.name(), what))?;
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    ensure_success(response, provider, what)?
+        .json()
+        .map(Some)
+        .with_context(|| format!("Failed to parse {} response: {}", provider.name(), what))
+}
+
+fn ensure_success(response: Response, provider: TmsProvider, what: &str) -> Result<Response> {
+    if response.status().is_success() {
+        return Ok(response);
+    }
+    let status = response.status();
+    let body = response.text().unwrap_or_default();
+    bail!(
+        "{} API error ({} {}): {}",
+        provider.name(),
+        what,
+        status,
+        body
+    );
+}
+
+/// String content for providers that store plain text per key
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+struct Locize {
+    client: Client,
+    base_url: String,
+    locize: LocizeConfig,
+    api_key: String,
+}
+
+impl Locize {
+    fn url(&self, endpoint: Option<&str>, locale: &str, namespace: &str) -> String {
+        let mut url = self.base_url.clone();
+        if let Some(endpoint) = endpoint {
+            url = format!("{}/{}", url, endpoint);
+        }
+        format!(
+            "{}/{}/{}/{}/{}",
+            url,
+            self.locize.project_id,
+            locize::resolve_version(&self.locize),
+            locale,
+            namespace
+        )
+    }
+}
+
+impl TmsAdapter for Locize {
+    fn provider(&self) -> TmsProvider {
+        TmsProvider::Locize
+    }
+
+    fn download(&self, locale: &str, namespace: &str) -> Result<Option<Value>> {
+        let url = self.url(None, locale, namespace);
+        let request = self.client.get(&url).bearer_auth(&self.api_key);
+        json_or_missing(request, self.provider(), &url)
+    }
+
+    fn add_keys(&self, locale: &str, namespace: &str, keys: &[(String, Value)]) -> Result<()> {
+        // The `missing` endpoint only adds keys, it never overwrites values
+        let url = self.url(Some("missing"), locale, namespace);
+        let payload: Map<String, Value> = keys.iter().cloned().collect();
+        let request = self
+            .client
+            .post(&url)
+            .bearer_auth(&self.api_key)
+            .json(&payload);
+        send(request, self.provider(), &url)?;
+        Ok(())
+    }
+}
+
+struct Crowdin {
+    client: Client,
+    base_url: String,
+    project_id: String,
+    api_key: String,
+    source_language: String,
+}
+
+impl Crowdin {
+    /// Id of the source file `<namespace>.json`
+    fn file_id(&self, namespace: &str) -> Result<Option<u64>> {
+        let url = format!(
+            "{}/projects/{}/files?limit=500",
+            self.base_url, self.project_id
+        );
+        let request = self.client.get(&url).bearer_auth(&self.api_key);
+        let files = send(request, self.provider(), &url)?
+            .json::<Value>()
+            .with_context(|| format!("Failed to parse Crowdin response: {}", url))?;
+        let file_name = format!("{}.json", namespace);
+        Ok(files["data"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|file| &file["data"])
+            .find(|file| file["name"].as_str() == Some(file_name.as_str()))
+            .and_then(|file| file["id"].as_u64()))
+    }
+}
+
+impl TmsAdapter for Crowdin {
+    fn provider(&self) -> TmsProvider {
+        TmsProvider::Crowdin
+    }
+
+    fn download(&self, locale: &str, namespace: &str) -> Result<Option<Value>> {
+        let Some(file_id) = self.file_id(namespace)? else {
+            return Ok(None);
+        };
+        let url = format!(
+            "{}/projects/{}/translations/exports",
+            self.base_url, self.project_id
+        );
+        let request = self
+            .client
+            .post(&url)
+            .bearer_auth(&self.api_key)
+            .json(&json!({ "targetLanguageId": locale, "fileIds": [file_id] }));
+        let export = send(request, self.provider(), &url)?
+            .json::<Value>()
+            .with_context(|| format!("Failed to parse Crowdin response: {}", url))?;
+        let download_url = export["data"]["url"]
+            .as_str()
+            .ok_or_else(|| anyhow!("Crowdin export returned no download url: {}", url))?;
+        // The export url is pre-signed and must not carry the API token
+        json_or_missing(self.client.get(download_url), self.provider(), download_url)
+    }
+
+    fn add_keys(&self, locale: &str, namespace: &str, keys: &[(String, Value)]) -> Result<()> {
+        if locale != self.source_language {
+            bail!(
+                "Crowdin only accepts new strings in the source language ({}), not {}",
+                self.source_language,
+                locale
+            );
+        }
+        let file_id = self.file_id(namespace)?.ok_or_else(|| {
+            anyhow!(
+                "Crowdin project has no source file {}.json; upload it once before pushing keys",
+                namespace
+            )
+        })?;
+        let url = format!("{}/projects/{}/strings", self.base_url, self.project_id);
+        for (key, value) in keys {
+            let request = self
+                .client
+                .post(&url)
+                .bearer_auth(&self.api_key)
+                .json(&json!({ "text": text(value), "identifier": key, "fileId": file_id }));
+            send(request, self.provider(), &url)?;
+        }
+        Ok(())
+    }
+}
+
+struct Phrase {
+    client: Client,
+    base_url: String,
+    project_id: String,
+    api_key: String,
+}
+
+impl Phrase {
+    fn authorized(&self, request: RequestBuilder) -> RequestBuilder {
+        request.header("Authorization", format!("token {}", self.api_key))
+    }
+}
+
+impl TmsAdapter for Phrase {
+    fn provider(&self) -> TmsProvider {
+        TmsProvider::Phrase
+    }
+
+    fn download(&self, locale: &str, namespace: &str) -> Result<Option<Value>> {
+        let url = format!(
+            "{}/projects/{}/locales/{}/download",
+            self.base_url, self.project_id, locale
+        );
+        let request = self
+            .authorized(self.client.get(&url))
+            .query(&[("file_format", "i18next"), ("tags", namespace)]);
+        json_or_missing(request, self.provider(), &url)
+    }
+
+    fn add_keys(&self, locale: &str, namespace: &str, keys: &[(String, Value)]) -> Result<()> {
+        let keys_url = format!("{}/projects/{}/keys", self.base_url, self.project_id);
+        let translations_url = format!(
+            "{}/projects/{}/translations",
+            self.base_url, self.project_id
+        );
+        for (key, value) in keys {
+            let request = self
+                .authorized(self.client.post(&keys_url))
+                .json(&json!({ "name": key, "tags": namespace }));
+            let created = send(request, self.provider(), &keys_url)?
+                .json::<Value>()
+                .with_context(|| format!("Failed to parse Phrase response: {}", keys_url))?;
+            let content = text(value);
+            if content.is_empty() {
+                continue;
+            }
+            let key_id = created["id"]
+                .as_str()
+                .ok_or_else(|| anyhow!("Phrase returned no id for key {}", key))?;
+            let request = self
+                .authorized(self.client.post(&translations_url))
+                .json(&json!({ "locale_id": locale, "key_id": key_id, "content": content }));
+            send(request, self.provider(), &translations_url)?;
+        }
+        Ok(())
+    }
+}
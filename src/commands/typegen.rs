@@ -2,6 +2,8 @@ use anyhow::Result;
 use std::path::Path;
 
 use crate::config::Config;
+use crate::extractor;
+use crate::key_transforms;
 use crate::typegen;
 
 pub fn run(
@@ -22,7 +24,9 @@ pub fn run(
     println!("Configuration:");
     println!("  Locales directory: {}", locales_dir_path);
     println!("  Default locale: {}", default_locale);
-    println!("  Output: {}", output);
+    let output_dir = config.types_output_dir();
+    let written = output_dir.as_deref().unwrap_or(output);
+    println!("  Output: {}", written);
     println!();
 
     let locales_path = Path::new(locales_dir_path);
@@ -32,6 +36,14 @@ pub fn run(
     let input_patterns = config.types_input_patterns();
     let resources_file = config.types_resources_file();
     let enable_selector = config.types_enable_selector();
+    // `returnObjects` keys are only known from the source code
+    let mut extraction =
+        extractor::extract_with_config(config, &config.input, &config.extraction_ignore())?;
+    key_transforms::apply(config, &mut extraction.files);
+    let root_keys = typegen::object_root_keys(
+        extraction.files.iter().map(|(_, keys)| keys),
+        config.effective_default_namespace(),
+    );
     typegen::generate_types_with_options(
         locales_path,
         output_path,
@@ -40,11 +52,72 @@ pub fn run(
         input_patterns.as_deref(),
         resources_file.as_deref().map(Path::new),
         enable_selector.as_ref(),
-        config.merge_namespaces,
+        locale_layout(config, locales_dir.is_some()),
+        Some(&config.plural_config()),
+        config.types_namespaces(),
+        typegen::ObjectRoots {
+            keys: &root_keys,
+            key_separator: &config.key_separator,
+        },
     )?;
 
     println!("TypeScript types generated successfully!");
-    println!("  Output: {}", output);
+    println!("  Output: {}", written);
 
     Ok(())
 }
+
+/// Write the runtime key constant modules into `output_dir`
+pub fn run_constants(
+    config: &Config,
+    output_dir: &str,
+    default_locale: Option<String>,
+    locales_dir: Option<String>,
+) -> Result<()> {
+    println!("=== i18next-turbo typegen (constants) ===\n");
+
+    let locales_dir_path = locales_dir.as_ref().unwrap_or(&config.output);
+    let default_locale = default_locale
+        .as_ref()
+        .or(config.locales.first())
+        .map(|s| s.as_str())
+        .unwrap_or("en");
+
+    println!("Configuration:");
+    println!("  Locales directory: {}", locales_dir_path);
+    println!("  Default locale: {}", default_locale);
+    println!("  Output: {}", output_dir);
+    println!();
+
+    let indentation = config.types_indentation_string();
+    let input_patterns = config.types_input_patterns();
+    let plural_config = config.plural_config();
+    typegen::generate_key_constants(
+        Path::new(locales_dir_path),
+        Path::new(output_dir),
+        default_locale,
+        indentation.as_deref(),
+        input_patterns.as_deref(),
+        locale_layout(config, locales_dir.is_some()),
+        config.types_namespaces(),
+        typegen::KeyFormat {
+            ns_separator: &config.ns_separator,
+            key_separator: &config.key_separator,
+            plural_config: Some(&plural_config),
+        },
+    )?;
+
+    println!("Key constants generated successfully!");
+    println!("  Output: {}", output_dir);
+
+    Ok(())
+}
+
+/// Layout of the locale files; an explicit `--locales-dir` replaces the
+/// per-package roots of `outputs`
+fn locale_layout(config: &Config, explicit_dir: bool) -> typegen::LocaleLayout<'_> {
+    typegen::LocaleLayout {
+        output_roots: if explicit_dir { &[] } else { &config.outputs },
+        ..typegen::LocaleLayout::from_config(config)
+    }
+}
@@ -0,0 +1,346 @@
+//! Edits to a JSON config file that keep the user's key order and formatting.
+//! Values are spliced into the original text instead of re-serializing the
+//! whole file, so only the edited array or object changes.
+
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// A JSON config file being edited in place
+pub struct ConfigFile {
+    path: PathBuf,
+    content: String,
+}
+
+/// One array element or object member: `span` runs from the start of the key
+/// (or the value) to the end of the value
+struct Item {
+    key: Option<String>,
+    span: Range<usize>,
+    value: Range<usize>,
+}
+
+/// The items of an array or object and the position of its closing bracket
+struct Container {
+    open: usize,
+    close: usize,
+    items: Vec<Item>,
+}
+
+impl ConfigFile {
+    pub fn read(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+        Self::from_content(path, content)
+    }
+
+    fn from_content(path: &Path, content: String) -> Result<Self> {
+        let root: Value = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+        if !root.is_object() {
+            bail!("Config file root must be a JSON object: {}", path.display());
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            content,
+        })
+    }
+
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    pub fn write(&self) -> Result<()> {
+        std::fs::write(&self.path, &self.content)
+            .with_context(|| format!("Failed to write config file: {}", self.path.display()))
+    }
+
+    /// Append the `values` missing from the top-level string array `field`,
+    /// creating the field after the last one when it does not exist. Returns
+    /// how many values were added.
+    pub fn push_strings(&mut self, field: &str, values: &[&str]) -> Result<usize> {
+        let root = self.root()?;
+        let Some(member) = find_member(&root, field) else {
+            let values: Vec<&str> = dedup(values);
+            if values.is_empty() {
+                return Ok(0);
+            }
+            let array = values
+                .iter()
+                .map(serde_json::to_string)
+                .collect::<serde_json::Result<Vec<_>>>()?
+                .join(", ");
+            let member = format!("{}: [{}]", serde_json::to_string(field)?, array);
+            self.insert_item(&root, &member, true);
+            return Ok(values.len());
+        };
+
+        let mut added = 0;
+        for value in dedup(values) {
+            let array = self.array(field, member.value.start)?;
+            if self.position(&array, value).is_some() {
+                continue;
+            }
+            self.insert_item(&array, &serde_json::to_string(value)?, false);
+            added += 1;
+        }
+        Ok(added)
+    }
+
+    /// Remove every `value` from the top-level string array `field`. Returns
+    /// whether anything was removed.
+    pub fn remove_string(&mut self, field: &str, value: &str) -> Result<bool> {
+        let mut removed = false;
+        loop {
+            let root = self.root()?;
+            let Some(member) = find_member(&root, field) else {
+                return Ok(removed);
+            };
+            let array = self.array(field, member.value.start)?;
+            let Some(index) = self.position(&array, value) else {
+                return Ok(removed);
+            };
+            self.remove_item(&array, index);
+            removed = true;
+        }
+    }
+
+    /// Remove the member `key` from the top-level object `field`. Returns
+    /// whether it existed.
+    pub fn remove_member(&mut self, field: &str, key: &str) -> Result<bool> {
+        let root = self.root()?;
+        let Some(member) = find_member(&root, field) else {
+            return Ok(false);
+        };
+        if !self.content[member.value.start..].starts_with('{') {
+            return Ok(false);
+        }
+        let object = scan_container(&self.content, member.value.start)?;
+        let Some(index) = object
+            .items
+            .iter()
+            .position(|item| item.key.as_deref() == Some(key))
+        else {
+            return Ok(false);
+        };
+        self.remove_item(&object, index);
+        Ok(true)
+    }
+
+    fn root(&self) -> Result<Container> {
+        scan_container(&self.content, skip_whitespace(&self.content, 0))
+    }
+
+    fn array(&self, field: &str, start: usize) -> Result<Container> {
+        if !self.content[start..].starts_with('[') {
+            bail!("\"{}\" must be an array in {}", field, self.path.display());
+        }
+        scan_container(&self.content, start)
+    }
+
+    fn position(&self, array: &Container, value: &str) -> Option<usize> {
+        array.items.iter().position(|item| {
+            serde_json::from_str::<Value>(&self.content[item.value.clone()])
+                .is_ok_and(|item| item.as_str() == Some(value))
+        })
+    }
+
+    /// Insert `text` as the last item of `container`, separated and indented
+    /// like its existing items
+    fn insert_item(&mut self, container: &Container, text: &str, is_root: bool) {
+        let content = &self.content;
+        let (at, insertion) = match container.items.as_slice() {
+            [] => {
+                let inner = container.open + 1..container.close;
+                let text = if is_root {
+                    format!("\n  {}\n", text)
+                } else {
+                    text.to_string()
+                };
+                self.content.replace_range(inner, &text);
+                return;
+            }
+            [only] => {
+                let leading = &content[container.open + 1..only.span.start];
+                let separator = if leading.contains('\n') {
+                    format!(",{}", leading)
+                } else {
+                    ", ".to_string()
+                };
+                (only.span.end, format!("{}{}", separator, text))
+            }
+            [.., previous, last] => (
+                last.span.end,
+                format!("{}{}", &content[previous.span.end..last.span.start], text),
+            ),
+        };
+        self.content.insert_str(at, &insertion);
+    }
+
+    /// Remove item `index` of `container` with the separator next to it
+    fn remove_item(&mut self, container: &Container, index: usize) {
+        let items = &container.items;
+        let range = if items.len() == 1 {
+            container.open + 1..container.close
+        } else if index + 1 == items.len() {
+            items[index - 1].span.end..items[index].span.end
+        } else {
+            items[index].span.start..items[index + 1].span.start
+        };
+        self.content.replace_range(range, "");
+    }
+}
+
+fn dedup<'a>(values: &[&'a str]) -> Vec<&'a str> {
+    let mut unique: Vec<&str> = Vec::new();
+    for value in values {
+        if !unique.contains(value) {
+            unique.push(value);
+        }
+    }
+    unique
+}
+
+fn find_member<'a>(container: &'a Container, key: &str) -> Option<&'a Item> {
+    container
+        .items
+        .iter()
+        .find(|item| item.key.as_deref() == Some(key))
+}
+
+fn skip_whitespace(content: &str, mut at: usize) -> usize {
+    let bytes = content.as_bytes();
+    while at < bytes.len() && bytes[at].is_ascii_whitespace() {
+        at += 1;
+    }
+    at
+}
+
+/// End of the string literal starting at `start`
+fn string_end(content: &str, start: usize) -> Result<usize> {
+    let bytes = content.as_bytes();
+    let mut at = start + 1;
+    while at < bytes.len() {
+        match bytes[at] {
+            b'\\' => at += 2,
+            b'"' => return Ok(at + 1),
+            _ => at += 1,
+        }
+    }
+    bail!("Unterminated string in config file")
+}
+
+/// End of the JSON value starting at `start`
+fn value_end(content: &str, start: usize) -> Result<usize> {
+    match content.as_bytes().get(start) {
+        Some(b'"') => string_end(content, start),
+        Some(b'{' | b'[') => Ok(scan_container(content, start)?.close + 1),
+        Some(_) => Ok(content[start..]
+            .find(|c: char| c == ',' || c == ']' || c == '}' || c.is_ascii_whitespace())
+            .map_or(content.len(), |len| start + len)),
+        None => bail!("Unexpected end of config file"),
+    }
+}
+
+/// Items of the array or object opening at `open`
+fn scan_container(content: &str, open: usize) -> Result<Container> {
+    let bytes = content.as_bytes();
+    let (is_object, closing) = match bytes.get(open) {
+        Some(b'{') => (true, b'}'),
+        Some(b'[') => (false, b']'),
+        _ => bail!("Expected an array or object in config file"),
+    };
+    let mut items = Vec::new();
+    let mut at = skip_whitespace(content, open + 1);
+    loop {
+        match bytes.get(at) {
+            Some(&byte) if byte == closing => {
+                return Ok(Container {
+                    open,
+                    close: at,
+                    items,
+                })
+            }
+            None => bail!("Unexpected end of config file"),
+            _ => {}
+        }
+        let start = at;
+        let key = if is_object {
+            let end = string_end(content, at)?;
+            let key: String = serde_json::from_str(&content[at..end])?;
+            at = skip_whitespace(content, end);
+            if bytes.get(at) != Some(&b':') {
+                bail!("Expected ':' in config file");
+            }
+            at = skip_whitespace(content, at + 1);
+            Some(key)
+        } else {
+            None
+        };
+        let end = value_end(content, at)?;
+        items.push(Item {
+            key,
+            span: start..end,
+            value: at..end,
+        });
+        at = skip_whitespace(content, end);
+        if bytes.get(at) == Some(&b',') {
+            at = skip_whitespace(content, at + 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(content: &str) -> ConfigFile {
+        ConfigFile::from_content(Path::new("i18next-turbo.json"), content.to_string()).unwrap()
+    }
+
+    #[test]
+    fn push_strings_keeps_order_and_layout() {
+        let mut config = file(
+            "{\n    \"output\": \"locales\",\n    \"locales\": [\"en\", \"ja\"],\n    \"input\": [\n        \"src/**/*.ts\"\n    ]\n}\n",
+        );
+        assert_eq!(config.push_strings("locales", &["de", "en"]).unwrap(), 1);
+        assert_eq!(config.push_strings("input", &["lib/**/*.ts"]).unwrap(), 1);
+        assert_eq!(
+            config.push_strings("preservePatterns", &["a.*"]).unwrap(),
+            1
+        );
+        assert_eq!(
+            config.content(),
+            "{\n    \"output\": \"locales\",\n    \"locales\": [\"en\", \"ja\", \"de\"],\n    \"input\": [\n        \"src/**/*.ts\",\n        \"lib/**/*.ts\"\n    ],\n    \"preservePatterns\": [\"a.*\"]\n}\n"
+        );
+
+        let mut config = file("{\"locales\":[],\"output\":\"x\"}");
+        config.push_strings("locales", &["en"]).unwrap();
+        config.push_strings("input", &["src/**"]).unwrap();
+        assert_eq!(
+            config.content(),
+            "{\"locales\":[\"en\"],\"output\":\"x\",\"input\": [\"src/**\"]}"
+        );
+        assert!(file("{\"locales\": \"en\"}")
+            .push_strings("locales", &["de"])
+            .is_err());
+    }
+
+    #[test]
+    fn removals_take_their_separator_along() {
+        let mut config = file(
+            "{\n  \"locales\": [\"en\", \"de\", \"ja\", \"de\"],\n  \"releaseGates\": {\n    \"de\": 90,\n    \"ja\": 80\n  },\n  \"secondaryLanguages\": [\"de\"]\n}",
+        );
+        assert!(config.remove_string("locales", "de").unwrap());
+        assert!(config.remove_string("secondaryLanguages", "de").unwrap());
+        assert!(config.remove_member("releaseGates", "de").unwrap());
+        assert!(!config.remove_member("releaseGates", "fr").unwrap());
+        assert!(!config.remove_string("missing", "de").unwrap());
+        assert_eq!(
+            config.content(),
+            "{\n  \"locales\": [\"en\", \"ja\"],\n  \"releaseGates\": {\n    \"ja\": 80\n  },\n  \"secondaryLanguages\": []\n}"
+        );
+        serde_json::from_str::<Value>(config.content()).unwrap();
+    }
+}
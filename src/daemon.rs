@@ -0,0 +1,721 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use crate::config::Config;
+use crate::eslint_data;
+use crate::extractor::{self, ExtractedKey};
+use crate::json_sync;
+use crate::key_transforms::{self, KeyMap};
+use crate::lint::{self, LintOptions};
+use crate::publish;
+
+/// Default socket path, relative to the project root. On Windows it names the
+/// daemon's named pipe instead (see [`pipe_name`]).
+pub const DEFAULT_SOCKET_PATH: &str = ".i18next-turbo.sock";
+
+/// A request sent to the daemon (one JSON object per line)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum DaemonRequest {
+    /// Health check
+    Ping,
+    /// Re-extract the given files and sync affected namespaces
+    ExtractChanged {
+        files: Vec<String>,
+        #[serde(default)]
+        dry_run: bool,
+    },
+    /// Look up which source files use a key (`ns:key` or `key`)
+    QueryKey { key: String },
+    /// Lint a single file for hardcoded strings
+    LintFile { path: String },
+    /// Stop the daemon
+    Shutdown,
+}
+
+/// A response sent back by the daemon (one JSON object per line)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum DaemonResponse {
+    Pong {
+        files: usize,
+        keys: usize,
+    },
+    Extracted {
+        files: usize,
+        added_keys: usize,
+        removed_keys: usize,
+    },
+    KeyLocations {
+        key: String,
+        namespace: String,
+        files: Vec<String>,
+    },
+    LintIssues {
+        issues: Vec<DaemonLintIssue>,
+    },
+    ShuttingDown,
+    Error {
+        message: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaemonLintIssue {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    pub text: String,
+}
+
+/// In-memory key index served by the daemon
+pub struct Daemon {
+    config: Config,
+    /// Cache of extracted keys per file, same shape as the watcher's cache
+    file_cache: HashMap<PathBuf, Vec<ExtractedKey>>,
+}
+
+impl Daemon {
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            file_cache: HashMap::new(),
+        }
+    }
+
+    /// Build the initial index from all configured input files
+    pub fn build_index(&mut self) -> Result<()> {
+        let (extraction, _) = self.extract_patterns(&self.config.input)?;
+        self.file_cache.clear();
+        for (file_path, keys) in extraction.files {
+            self.file_cache
+                .insert(cache_path(Path::new(&file_path)), keys);
+        }
+        Ok(())
+    }
+
+    pub fn file_count(&self) -> usize {
+        self.file_cache.len()
+    }
+
+    pub fn key_count(&self) -> usize {
+        self.file_cache.values().map(|keys| keys.len()).sum()
+    }
+
+    /// Handle a single request against the in-memory index
+    pub fn handle(&mut self, request: DaemonRequest) -> DaemonResponse {
+        let result = match request {
+            DaemonRequest::Ping => Ok(DaemonResponse::Pong {
+                files: self.file_count(),
+                keys: self.key_count(),
+            }),
+            DaemonRequest::ExtractChanged { files, dry_run } => {
+                self.extract_changed(&files, dry_run)
+            }
+            DaemonRequest::QueryKey { key } => Ok(self.query_key(&key)),
+            DaemonRequest::LintFile { path } => self.lint_file(&path),
+            DaemonRequest::Shutdown => Ok(DaemonResponse::ShuttingDown),
+        };
+        result.unwrap_or_else(|e| DaemonResponse::Error {
+            message: format!("{:#}", e),
+        })
+    }
+
+    /// Extract `patterns` with `keyTransforms` applied, along with the reverse key mapping
+    fn extract_patterns(
+        &self,
+        patterns: &[String],
+    ) -> Result<(extractor::ExtractionResult, KeyMap)> {
+        let mut extraction = extractor::extract_with_config(
+            &self.config,
+            patterns,
+            &self.config.extraction_ignore(),
+        )?;
+        let key_map = key_transforms::apply(&self.config, &mut extraction.files);
+        Ok((extraction, key_map))
+    }
+
+    /// Whether `path` is a source file of the configured `input`, given as
+    /// written or relative to the current directory
+    fn is_input(&self, path: &Path) -> bool {
+        let input = &self.config.input;
+        extractor::matches_input_patterns(path, input)
+            || std::path::absolute(path)
+                .is_ok_and(|absolute| extractor::matches_input_patterns(&absolute, input))
+    }
+
+    fn extract_changed(&mut self, files: &[String], dry_run: bool) -> Result<DaemonResponse> {
+        let mut affected_namespaces = BTreeSet::new();
+        let mut existing = Vec::new();
+
+        // Editors and hooks report every saved file; only sources of `input` count
+        let paths: BTreeSet<PathBuf> = files
+            .iter()
+            .map(|file| cache_path(Path::new(file)))
+            .filter(|path| self.is_input(path))
+            .collect();
+        for path in &paths {
+            if let Some(keys) = self.file_cache.remove(path) {
+                self.collect_namespaces(&keys, &mut affected_namespaces);
+            }
+            if path.is_file() {
+                existing.push(glob::Pattern::escape(&path.to_string_lossy()));
+            }
+        }
+
+        let key_map = if existing.is_empty() {
+            KeyMap::new()
+        } else {
+            let (extraction, key_map) = self.extract_patterns(&existing)?;
+            for (file_path, keys) in extraction.files {
+                self.collect_namespaces(&keys, &mut affected_namespaces);
+                self.file_cache
+                    .insert(cache_path(Path::new(&file_path)), keys);
+            }
+            key_map
+        };
+
+        let mut sync_results = Vec::new();
+        for (output, keys) in
+            json_sync::group_keys_by_output(&self.config, &self.file_cache, &self.config.output)
+        {
+            sync_results.extend(json_sync::sync_namespaces(
+                &self.config,
+                &keys,
+                &output,
+                &affected_namespaces,
+                dry_run,
+            )?);
+        }
+        if !dry_run {
+            key_transforms::write_key_map(&self.config, &key_map, true)?;
+            eslint_data::write_configured(&self.config, &self.file_cache)?;
+            publish::write_configured(&self.config)?;
+        }
+
+        Ok(DaemonResponse::Extracted {
+            files: paths.len(),
+            added_keys: sync_results.iter().map(|r| r.added_keys.len()).sum(),
+            removed_keys: sync_results.iter().map(|r| r.removed_keys.len()).sum(),
+        })
+    }
+
+    fn collect_namespaces(&self, keys: &[ExtractedKey], namespaces: &mut BTreeSet<String>) {
+        for key in keys {
+            let ns = key
+                .namespace
+                .clone()
+                .unwrap_or_else(|| self.config.default_namespace.clone());
+            namespaces.insert(ns);
+        }
+    }
+
+    fn query_key(&self, raw_key: &str) -> DaemonResponse {
+        let default_ns = self.config.effective_default_namespace();
+        let separator = self.config.ns_separator.as_str();
+        let (namespace, key) = match raw_key.split_once(separator) {
+            Some((ns, key)) if !separator.is_empty() => (ns, key),
+            _ => (default_ns, raw_key),
+        };
+
+        let mut files: Vec<String> = self
+            .file_cache
+            .iter()
+            .filter(|(_, keys)| {
+                keys.iter().any(|k| {
+                    k.key == key && k.namespace.as_deref().unwrap_or(default_ns) == namespace
+                })
+            })
+            .map(|(path, _)| path.display().to_string())
+            .collect();
+        files.sort();
+
+        DaemonResponse::KeyLocations {
+            key: key.to_string(),
+            namespace: namespace.to_string(),
+            files,
+        }
+    }
+
+    fn lint_file(&self, path: &str) -> Result<DaemonResponse> {
+        let options = LintOptions {
+            ignored_attributes: self.config.lint.ignored_attributes.clone(),
+            ignored_tags: self.config.lint.ignored_tags.clone(),
+            accepted_attributes: self.config.lint.accepted_attributes.clone(),
+            accepted_tags: self.config.lint.accepted_tags.clone(),
+            ignore_patterns: self.config.lint.ignore.clone(),
+            path_style: self.config.path_style,
+        };
+        let issues = lint::lint_file_with_options(path, &options)?
+            .into_iter()
+            .map(|issue| DaemonLintIssue {
+                line: issue.line,
+                column: issue.column,
+                message: issue.message,
+                text: issue.text,
+            })
+            .collect();
+        Ok(DaemonResponse::LintIssues { issues })
+    }
+}
+
+/// `path` as the file cache stores it: without `.` components, and relative
+/// to the current directory when below it, so `./src/a.ts`, `src/a.ts` and
+/// its absolute path name the same file
+fn cache_path(path: &Path) -> PathBuf {
+    let path = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| path.strip_prefix(cwd).ok())
+        .unwrap_or(path);
+    path.components()
+        .filter(|component| *component != Component::CurDir)
+        .collect()
+}
+
+/// Answer the requests of one connection, one JSON line each. Returns whether
+/// a `Shutdown` request arrived.
+fn serve_connection(
+    daemon: &mut Daemon,
+    reader: impl Read,
+    mut writer: impl Write,
+) -> Result<bool> {
+    for line in BufReader::new(reader).lines() {
+        let Ok(line) = line else { break };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (response, shutdown) = match serde_json::from_str::<DaemonRequest>(&line) {
+            Ok(request) => {
+                let shutdown = request == DaemonRequest::Shutdown;
+                (daemon.handle(request), shutdown)
+            }
+            Err(e) => (
+                DaemonResponse::Error {
+                    message: format!("Invalid request: {}", e),
+                },
+                false,
+            ),
+        };
+        let payload = serde_json::to_string(&response)?;
+        if writeln!(writer, "{}", payload).is_err() {
+            break;
+        }
+        if shutdown {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Write `request` to a daemon connection and read its response
+fn exchange(mut stream: impl Read + Write, request: &DaemonRequest) -> Result<DaemonResponse> {
+    writeln!(stream, "{}", serde_json::to_string(request)?)?;
+    let mut line = String::new();
+    BufReader::new(stream)
+        .read_line(&mut line)
+        .context("Failed to read daemon response")?;
+    serde_json::from_str(&line).context("Invalid daemon response")
+}
+
+fn connect_error(socket_path: &Path) -> String {
+    format!(
+        "Failed to connect to daemon at {} (is `i18next-turbo daemon start` running?)",
+        socket_path.display()
+    )
+}
+
+/// Serve requests on a Unix domain socket until a `Shutdown` request arrives
+#[cfg(unix)]
+pub fn serve(daemon: &mut Daemon, socket_path: &Path) -> Result<()> {
+    use std::os::unix::net::UnixListener;
+
+    if socket_path.exists() {
+        if send_request(socket_path, &DaemonRequest::Ping).is_ok() {
+            anyhow::bail!("A daemon is already listening on {}", socket_path.display());
+        }
+        std::fs::remove_file(socket_path)
+            .with_context(|| format!("Failed to remove stale socket: {}", socket_path.display()))?;
+    }
+
+    let listener = UnixListener::bind(socket_path)
+        .with_context(|| format!("Failed to bind socket: {}", socket_path.display()))?;
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("Daemon connection error: {}", e);
+                continue;
+            }
+        };
+        let writer = stream.try_clone()?;
+        if serve_connection(daemon, stream, writer)? {
+            break;
+        }
+    }
+
+    let _ = std::fs::remove_file(socket_path);
+    Ok(())
+}
+
+/// Serve requests on the named pipe of `socket_path` until a `Shutdown`
+/// request arrives
+#[cfg(windows)]
+pub fn serve(daemon: &mut Daemon, socket_path: &Path) -> Result<()> {
+    let name = pipe_name(socket_path);
+    let mut first = true;
+    loop {
+        let pipe = match pipe::accept(&name, first) {
+            Ok(pipe) => pipe,
+            Err(e) if first => {
+                return Err(e).with_context(|| {
+                    format!(
+                        "Failed to listen on {} (is a daemon already running?)",
+                        name
+                    )
+                })
+            }
+            Err(e) => {
+                eprintln!("Daemon connection error: {:#}", e);
+                continue;
+            }
+        };
+        first = false;
+        let writer = pipe.try_clone()?;
+        let shutdown = serve_connection(daemon, &pipe, writer)?;
+        pipe::disconnect(&pipe);
+        if shutdown {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn serve(_daemon: &mut Daemon, _socket_path: &Path) -> Result<()> {
+    anyhow::bail!("Daemon mode requires Unix domain sockets or named pipes and is not supported on this platform")
+}
+
+/// Send a single request to a running daemon and wait for its response
+#[cfg(unix)]
+pub fn send_request(socket_path: &Path, request: &DaemonRequest) -> Result<DaemonResponse> {
+    let stream = std::os::unix::net::UnixStream::connect(socket_path)
+        .with_context(|| connect_error(socket_path))?;
+    exchange(stream, request)
+}
+
+/// Send a single request to a running daemon and wait for its response
+#[cfg(windows)]
+pub fn send_request(socket_path: &Path, request: &DaemonRequest) -> Result<DaemonResponse> {
+    let pipe =
+        pipe::connect(&pipe_name(socket_path)).with_context(|| connect_error(socket_path))?;
+    exchange(pipe, request)
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn send_request(_socket_path: &Path, _request: &DaemonRequest) -> Result<DaemonResponse> {
+    anyhow::bail!("Daemon mode requires Unix domain sockets or named pipes and is not supported on this platform")
+}
+
+/// Named pipe serving the daemon of `socket_path` on Windows: a `\\.\pipe\`
+/// path is used as is, any other path names a pipe after its absolute form,
+/// so each project gets its own daemon
+pub fn pipe_name(socket_path: &Path) -> String {
+    const PREFIX: &str = r"\\.\pipe\";
+    let raw = socket_path.to_string_lossy();
+    if raw.starts_with(PREFIX) {
+        return raw.into_owned();
+    }
+    let absolute = std::path::absolute(socket_path).unwrap_or_else(|_| socket_path.to_path_buf());
+    let name: String = absolute
+        .to_string_lossy()
+        .chars()
+        .map(|c| {
+            if matches!(c, '\\' | '/' | ':') {
+                '-'
+            } else {
+                c
+            }
+        })
+        .collect();
+    format!("{}i18next-turbo{}", PREFIX, name)
+}
+
+/// Win32 named pipe endpoints, as `File`s so they read and write like sockets
+#[cfg(windows)]
+mod pipe {
+    use anyhow::{Context, Result};
+    use std::ffi::OsStr;
+    use std::fs::{File, OpenOptions};
+    use std::io;
+    use std::os::windows::ffi::OsStrExt;
+    use std::os::windows::io::{AsRawHandle, FromRawHandle};
+    use windows_sys::Win32::Foundation::{
+        CloseHandle, GetLastError, ERROR_PIPE_BUSY, ERROR_PIPE_CONNECTED, INVALID_HANDLE_VALUE,
+    };
+    use windows_sys::Win32::Storage::FileSystem::{
+        FlushFileBuffers, FILE_FLAG_FIRST_PIPE_INSTANCE, PIPE_ACCESS_DUPLEX,
+    };
+    use windows_sys::Win32::System::Pipes::{
+        ConnectNamedPipe, CreateNamedPipeW, DisconnectNamedPipe, WaitNamedPipeW,
+        PIPE_READMODE_BYTE, PIPE_REJECT_REMOTE_CLIENTS, PIPE_TYPE_BYTE, PIPE_UNLIMITED_INSTANCES,
+        PIPE_WAIT,
+    };
+
+    const BUFFER_SIZE: u32 = 64 * 1024;
+    /// Milliseconds a client waits for a busy daemon
+    const BUSY_TIMEOUT: u32 = 5_000;
+
+    fn wide(name: &str) -> Vec<u16> {
+        OsStr::new(name).encode_wide().chain(Some(0)).collect()
+    }
+
+    /// Create an instance of pipe `name` and wait for a client. The `first`
+    /// instance fails when another daemon already owns the pipe.
+    pub fn accept(name: &str, first: bool) -> Result<File> {
+        let wide_name = wide(name);
+        let mut open_mode = PIPE_ACCESS_DUPLEX;
+        if first {
+            open_mode |= FILE_FLAG_FIRST_PIPE_INSTANCE;
+        }
+        // SAFETY: `wide_name` is NUL-terminated and outlives the call; no
+        // security attributes are passed
+        let handle = unsafe {
+            CreateNamedPipeW(
+                wide_name.as_ptr(),
+                open_mode,
+                PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+                PIPE_UNLIMITED_INSTANCES,
+                BUFFER_SIZE,
+                BUFFER_SIZE,
+                0,
+                std::ptr::null(),
+            )
+        };
+        if handle == INVALID_HANDLE_VALUE {
+            return Err(io::Error::last_os_error())
+                .with_context(|| format!("Failed to create named pipe: {}", name));
+        }
+        // SAFETY: `handle` is the pipe created above; without OVERLAPPED the
+        // call blocks until a client connects
+        let connected = unsafe { ConnectNamedPipe(handle, std::ptr::null_mut()) } != 0
+            || unsafe { GetLastError() } == ERROR_PIPE_CONNECTED;
+        if !connected {
+            let error = io::Error::last_os_error();
+            // SAFETY: the handle is not used after closing it
+            unsafe { CloseHandle(handle) };
+            return Err(error).context("Failed to accept a daemon connection");
+        }
+        // SAFETY: the file takes sole ownership of the handle and closes it
+        Ok(unsafe { File::from_raw_handle(handle) })
+    }
+
+    /// Let the client read the last response, then end the connection
+    pub fn disconnect(pipe: &File) {
+        // SAFETY: the handle stays owned by `pipe`, which outlives both calls
+        unsafe {
+            FlushFileBuffers(pipe.as_raw_handle());
+            DisconnectNamedPipe(pipe.as_raw_handle());
+        }
+    }
+
+    /// Open pipe `name`, waiting while the daemon serves another client
+    pub fn connect(name: &str) -> io::Result<File> {
+        loop {
+            match OpenOptions::new().read(true).write(true).open(name) {
+                Err(e) if e.raw_os_error() == Some(ERROR_PIPE_BUSY as i32) => {
+                    // SAFETY: the name buffer is NUL-terminated and outlives the call
+                    if unsafe { WaitNamedPipeW(wide(name).as_ptr(), BUSY_TIMEOUT) } == 0 {
+                        return Err(io::Error::last_os_error());
+                    }
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn make_daemon(root: &Path) -> Daemon {
+        let config = Config {
+            input: vec![format!("{}/src/**/*.ts", root.display())],
+            output: root.join("locales").display().to_string(),
+            locales: vec!["en".to_string()],
+            functions: vec!["t".to_string()],
+            ..Config::default()
+        };
+        Daemon::new(config)
+    }
+
+    #[test]
+    fn request_protocol_uses_tagged_json() {
+        let request: DaemonRequest =
+            serde_json::from_str(r#"{"type":"queryKey","key":"common:hello"}"#).unwrap();
+        assert_eq!(
+            request,
+            DaemonRequest::QueryKey {
+                key: "common:hello".to_string()
+            }
+        );
+        let request: DaemonRequest =
+            serde_json::from_str(r#"{"type":"extractChanged","files":["a.ts"]}"#).unwrap();
+        assert_eq!(
+            request,
+            DaemonRequest::ExtractChanged {
+                files: vec!["a.ts".to_string()],
+                dry_run: false
+            }
+        );
+    }
+
+    #[test]
+    fn extract_changed_updates_index_and_query() {
+        let tmp = tempdir().unwrap();
+        let src = tmp.path().join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        let file = src.join("app.ts");
+        std::fs::write(&file, "t('first.key'); t('common:shared');").unwrap();
+
+        let mut daemon = make_daemon(tmp.path());
+        daemon.build_index().unwrap();
+        assert_eq!(daemon.file_count(), 1);
+
+        let response = daemon.handle(DaemonRequest::QueryKey {
+            key: "common:shared".to_string(),
+        });
+        match response {
+            DaemonResponse::KeyLocations {
+                namespace, files, ..
+            } => {
+                assert_eq!(namespace, "common");
+                assert_eq!(files.len(), 1);
+            }
+            other => panic!("unexpected response: {:?}", other),
+        }
+
+        std::fs::write(&file, "t('second.key');").unwrap();
+        let response = daemon.handle(DaemonRequest::ExtractChanged {
+            files: vec![file.display().to_string()],
+            dry_run: false,
+        });
+        assert!(matches!(response, DaemonResponse::Extracted { .. }));
+
+        let response = daemon.handle(DaemonRequest::QueryKey {
+            key: "first.key".to_string(),
+        });
+        assert!(matches!(
+            response,
+            DaemonResponse::KeyLocations { ref files, .. } if files.is_empty()
+        ));
+        let locale =
+            std::fs::read_to_string(tmp.path().join("locales/en/translation.json")).unwrap();
+        assert!(locale.contains("second"));
+    }
+
+    #[test]
+    fn extract_changed_drops_deleted_files() {
+        let tmp = tempdir().unwrap();
+        let src = tmp.path().join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        let file = src.join("gone.ts");
+        std::fs::write(&file, "t('gone.key');").unwrap();
+
+        let mut daemon = make_daemon(tmp.path());
+        daemon.build_index().unwrap();
+        std::fs::remove_file(&file).unwrap();
+
+        daemon.handle(DaemonRequest::ExtractChanged {
+            files: vec![file.display().to_string()],
+            dry_run: true,
+        });
+        assert_eq!(daemon.file_count(), 0);
+    }
+
+    #[test]
+    fn extract_changed_normalizes_paths_and_skips_other_files() {
+        let tmp = tempdir().unwrap();
+        let src = tmp.path().join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(src.join("app.ts"), "t('first.key');").unwrap();
+
+        let mut daemon = make_daemon(tmp.path());
+        daemon.build_index().unwrap();
+        std::fs::write(src.join("app.ts"), "t('second.key');").unwrap();
+        std::fs::write(src.join("notes.md"), "t('notes.key');").unwrap();
+
+        let root = tmp.path().display();
+        let response = daemon.handle(DaemonRequest::ExtractChanged {
+            files: vec![
+                format!("{}/./src/app.ts", root),
+                format!("{}/src/notes.md", root),
+            ],
+            dry_run: false,
+        });
+        assert!(
+            matches!(response, DaemonResponse::Extracted { files: 1, .. }),
+            "{:?}",
+            response
+        );
+        // The cached entry was replaced, not joined by a `./` duplicate
+        assert_eq!(daemon.file_count(), 1);
+        for (key, used) in [
+            ("first.key", false),
+            ("second.key", true),
+            ("notes.key", false),
+        ] {
+            let response = daemon.handle(DaemonRequest::QueryKey {
+                key: key.to_string(),
+            });
+            assert!(
+                matches!(response, DaemonResponse::KeyLocations { ref files, .. } if files.is_empty() != used),
+                "{}: {:?}",
+                key,
+                response
+            );
+        }
+    }
+
+    #[test]
+    fn pipe_names_follow_the_socket_path() {
+        assert_eq!(pipe_name(Path::new(r"\\.\pipe\custom")), r"\\.\pipe\custom");
+        let name = pipe_name(Path::new(".i18next-turbo.sock"));
+        let rest = name.strip_prefix(r"\\.\pipe\i18next-turbo").unwrap();
+        assert!(rest.ends_with("-.i18next-turbo.sock"), "{}", name);
+        assert!(!rest.contains(['/', '\\', ':']), "{}", name);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn serve_answers_ping_and_shutdown() {
+        let tmp = tempdir().unwrap();
+        let socket = tmp.path().join("daemon.sock");
+        let mut daemon = make_daemon(tmp.path());
+
+        let server_socket = socket.clone();
+        let handle = std::thread::spawn(move || serve(&mut daemon, &server_socket));
+
+        let mut attempts = 0;
+        let response = loop {
+            match send_request(&socket, &DaemonRequest::Ping) {
+                Ok(response) => break response,
+                Err(_) if attempts < 100 => {
+                    attempts += 1;
+                    std::thread::sleep(std::time::Duration::from_millis(20));
+                }
+                Err(e) => panic!("daemon did not start: {}", e),
+            }
+        };
+        assert_eq!(response, DaemonResponse::Pong { files: 0, keys: 0 });
+
+        let response = send_request(&socket, &DaemonRequest::Shutdown).unwrap();
+        assert_eq!(response, DaemonResponse::ShuttingDown);
+        handle.join().unwrap().unwrap();
+        assert!(!socket.exists());
+    }
+}
@@ -1,14 +1,20 @@
 #![cfg_attr(test, allow(clippy::field_reassign_with_default))]
 
-pub mod cleanup;
+// The extraction/sync library; re-exported so `i18next_turbo::config` and the
+// other module paths keep working for the CLI and existing embedders
+pub use i18next_turbo_core::{
+    budgets, cancel, cleanup, config, conflicts, eslint_data, extractor, fs, git, handlebars,
+    json_sync, key_case, key_index, key_transforms, lint, locale_module, locale_store, logging,
+    markup, mdx, metadata, metrics, namespaces, nesting, paths, plurals, publish, run_guard,
+    spacing, summary, tms, typegen, vue, walk,
+};
+
 pub mod commands;
-pub mod config;
-pub mod extractor;
-pub mod fs;
-pub mod json_sync;
-pub mod lint;
-pub mod logging;
-pub mod typegen;
+pub mod config_edit;
+pub mod daemon;
+#[cfg(feature = "napi")]
+pub mod napi_config;
+pub mod plan;
 pub mod watcher;
 
 #[cfg(feature = "napi")]
@@ -17,13 +23,15 @@ use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
 #[cfg(feature = "napi")]
-use crate::cleanup as cleanup_mod;
+use crate::cancel::CancellationToken;
 #[cfg(feature = "napi")]
-use crate::config::{Config, NapiConfig};
+use crate::cleanup as cleanup_mod;
 #[cfg(feature = "napi")]
-use crate::extractor::ExtractedKey;
+use crate::config::Config;
 #[cfg(feature = "napi")]
 use crate::lint as lint_mod;
+#[cfg(feature = "napi")]
+use crate::napi_config::NapiConfig;
 
 // ============================================
 // NAPI Result Types (zero-copy JS interop)
@@ -43,10 +51,57 @@ pub struct ExtractResult {
     pub keys_added: u32,
     /// List of updated locale files
     pub updated_files: Vec<String>,
+    /// Every key added to a locale file, with the source location that introduced it
+    pub added_keys: Vec<AddedKeyInfo>,
     /// Number of warnings encountered
     pub warnings: u32,
+    /// Warnings and errors of the extraction, e.g. dynamic keys and parse errors
+    pub diagnostics: Vec<DiagnosticInfo>,
+    /// Number of diagnostics of each kind that occurred
+    pub diagnostic_counts: Vec<DiagnosticCount>,
     /// Optional message (e.g., "No translation keys found.")
     pub message: Option<String>,
+    /// The run was cancelled or timed out: counts cover the files processed
+    /// until then and no locale file was written
+    pub cancelled: bool,
+}
+
+/// A key added to a locale file and where it was first extracted from
+#[cfg(feature = "napi")]
+#[napi(object)]
+pub struct AddedKeyInfo {
+    /// Locale file the key was added to
+    pub file: String,
+    pub namespace: String,
+    pub key: String,
+    /// First source file that extracted the key
+    pub source: Option<String>,
+    /// 1-based line of the first call in `source` that produced the key
+    pub line: Option<u32>,
+    /// 1-based column where that call starts
+    pub column: Option<u32>,
+}
+
+/// A problem found while extracting
+#[cfg(feature = "napi")]
+#[napi(object)]
+pub struct DiagnosticInfo {
+    /// e.g. "dynamic-key", "unresolved-namespace", "parse-error"
+    pub kind: String,
+    /// "warn" or "error"
+    pub severity: String,
+    pub file_path: String,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+    pub message: String,
+}
+
+/// How many diagnostics of one kind occurred
+#[cfg(feature = "napi")]
+#[napi(object)]
+pub struct DiagnosticCount {
+    pub kind: String,
+    pub count: u32,
 }
 
 /// Result of lint operation
@@ -83,6 +138,8 @@ pub struct CheckResult {
     pub dead_keys: Vec<DeadKeyInfo>,
     /// Number of keys removed (if remove option was used)
     pub removed_count: u32,
+    /// `preservePatterns` entries covering unused keys that dynamic keys may produce
+    pub suggested_preserve_patterns: Vec<String>,
 }
 
 /// Information about a dead key
@@ -97,6 +154,49 @@ pub struct DeadKeyInfo {
     pub namespace: String,
 }
 
+/// Result of `findDeadKeys`
+#[cfg(feature = "napi")]
+#[napi(object)]
+pub struct DeadKeysResult {
+    /// List of dead (unused) keys
+    pub dead_keys: Vec<DeadKeyInfo>,
+    /// `preservePatterns` entries covering unused keys that dynamic keys may produce
+    pub suggested_preserve_patterns: Vec<String>,
+}
+
+/// Result of `purgeDeadKeys`
+#[cfg(feature = "napi")]
+#[napi(object)]
+pub struct PurgeResult {
+    /// Number of keys removed from locale files
+    pub removed_count: u32,
+    /// Requested keys that were left alone because they are no longer dead or
+    /// are still within `removalGraceDays`
+    pub skipped_keys: Vec<DeadKeyInfo>,
+}
+
+#[cfg(feature = "napi")]
+impl From<&cleanup_mod::DeadKey> for DeadKeyInfo {
+    fn from(key: &cleanup_mod::DeadKey) -> Self {
+        DeadKeyInfo {
+            file_path: key.file_path.clone(),
+            key_path: key.key_path.clone(),
+            namespace: key.namespace.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "napi")]
+impl From<DeadKeyInfo> for cleanup_mod::DeadKey {
+    fn from(key: DeadKeyInfo) -> Self {
+        cleanup_mod::DeadKey {
+            file_path: key.file_path,
+            key_path: key.key_path,
+            namespace: key.namespace,
+        }
+    }
+}
+
 /// Extract translation keys from source files
 ///
 /// # Arguments
@@ -108,8 +208,92 @@ pub struct DeadKeyInfo {
 #[napi]
 #[cfg(feature = "napi")]
 pub fn extract(config: NapiConfig, options: Option<ExtractOptions>) -> Result<ExtractResult> {
-    let config: Config = Config::from_napi(config)
+    let config: Config = Config::try_from(config)
         .map_err(|e| napi::Error::from_reason(format!("Config validation failed: {}", e)))?;
+    run_extract(&config, options, CancellationToken::new())
+}
+
+/// Cancellation handle for `extractAsync`; call `cancel()` from any callback
+/// (e.g. an `AbortSignal` listener) while the extraction runs
+#[cfg(feature = "napi")]
+#[napi(js_name = "CancellationToken")]
+#[derive(Default)]
+pub struct NapiCancellationToken {
+    token: CancellationToken,
+}
+
+#[cfg(feature = "napi")]
+#[napi]
+impl NapiCancellationToken {
+    #[napi(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[napi]
+    pub fn cancel(&self) {
+        self.token.cancel();
+    }
+
+    #[napi(getter)]
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+}
+
+/// `extract` on the libuv thread pool, so the event loop stays free to cancel it
+#[cfg(feature = "napi")]
+pub struct ExtractTask {
+    config: Config,
+    options: Option<ExtractOptions>,
+    token: CancellationToken,
+}
+
+#[cfg(feature = "napi")]
+impl Task for ExtractTask {
+    type Output = ExtractResult;
+    type JsValue = ExtractResult;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        run_extract(&self.config, self.options.take(), self.token.clone())
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+/// Extract translation keys without blocking the event loop
+///
+/// Cancelling `token` (or `options.timeoutMs` elapsing) stops globbing and
+/// parsing promptly. The promise then resolves with `cancelled: true`, counts
+/// of the files processed so far, and no locale file written.
+#[napi(ts_return_type = "Promise<ExtractResult>")]
+#[cfg(feature = "napi")]
+pub fn extract_async(
+    config: NapiConfig,
+    options: Option<ExtractOptions>,
+    token: Option<&NapiCancellationToken>,
+) -> Result<AsyncTask<ExtractTask>> {
+    let config: Config = Config::try_from(config)
+        .map_err(|e| napi::Error::from_reason(format!("Config validation failed: {}", e)))?;
+    Ok(AsyncTask::new(ExtractTask {
+        config,
+        options,
+        token: token.map(|token| token.token.clone()).unwrap_or_default(),
+    }))
+}
+
+#[cfg(feature = "napi")]
+fn run_extract(
+    config: &Config,
+    options: Option<ExtractOptions>,
+    token: CancellationToken,
+) -> Result<ExtractResult> {
+    let config = config.clone();
+    if let Some(timeout_ms) = options.as_ref().and_then(|o| o.timeout_ms) {
+        token.cancel_after(std::time::Duration::from_millis(timeout_ms.into()));
+    }
 
     // Extract options
     let output = options.as_ref().and_then(|o| o.output.as_ref());
@@ -130,32 +314,72 @@ pub fn extract(config: NapiConfig, options: Option<ExtractOptions>) -> Result<Ex
 
     // Determine output directory
     let output_dir = output.unwrap_or(&config.output);
+    let _guard = guard_outputs(&config, output_dir)?;
 
-    let plural_config = config.plural_config();
+    let diagnostic_kinds = options
+        .as_ref()
+        .and_then(|o| o.diagnostic_kinds.as_ref())
+        .into_iter()
+        .flatten()
+        .map(|kind| crate::extractor::DiagnosticKind::parse_str(kind))
+        .collect::<anyhow::Result<Vec<_>>>()
+        .map_err(|e| napi::Error::from_reason(e.to_string()))?;
 
     // Extract keys from files
-    let extraction = crate::extractor::extract_from_glob_with_options(
-        &config.input,
-        &config.ignore,
-        &config.functions,
-        config.extract_from_comments,
-        &plural_config,
-        &config.trans_components,
-        &config.trans_keep_basic_html_nodes_for,
-        &config.use_translation_names,
-        &config.nesting_prefix,
-        &config.nesting_suffix,
-        &config.nesting_options_separator,
-        &config.interpolation_prefix,
-        &config.interpolation_suffix,
-    )
-    .map_err(|e| napi::Error::from_reason(format!("Extraction failed: {}", e)))?;
+    let mut extraction = crate::extractor::ExtractRequest::builder()
+        .config(&config)
+        .patterns(config.input.iter().cloned())
+        .ignore(config.extraction_ignore())
+        .diagnostic_kinds(diagnostic_kinds)
+        .cancellation(token)
+        .build()
+        .run()
+        .map_err(|e| napi::Error::from_reason(format!("Extraction failed: {}", e)))?;
+    let (diagnostics, diagnostic_counts) = diagnostic_report(&extraction);
+    let key_map = crate::key_transforms::apply(&config, &mut extraction.files);
+
+    // Collect all keys
+    let mut unique_keys: std::collections::HashSet<String> = std::collections::HashSet::new();
+
+    for (_file_path, keys) in &extraction.files {
+        for key in keys {
+            let full_key = match &key.namespace {
+                Some(ns) => format!("{}:{}", ns, key.key),
+                None => key.key.clone(),
+            };
+            unique_keys.insert(full_key);
+        }
+    }
+
+    // Partial results would drop the keys of unvisited files from the locale files
+    if extraction.cancelled {
+        return Ok(ExtractResult {
+            success: false,
+            files_processed: extraction.files.len() as u32,
+            unique_keys: unique_keys.len() as u32,
+            keys_added: 0,
+            updated_files: vec![],
+            added_keys: vec![],
+            warnings: extraction.total_warnings() as u32,
+            diagnostics,
+            diagnostic_counts,
+            message: Some("Extraction cancelled; no locale files were written.".to_string()),
+            cancelled: true,
+        });
+    }
+
+    if extraction.dynamic_key_errors() > 0 {
+        return Err(napi::Error::from_reason(format!(
+            "Failed: {} dynamic template literal key(s) at error severity",
+            extraction.dynamic_key_errors()
+        )));
+    }
 
     if extraction.files.is_empty() {
-        if fail_on_warnings && extraction.warning_count > 0 {
+        if fail_on_warnings && extraction.total_warnings() > 0 {
             return Err(napi::Error::from_reason(format!(
                 "Failed: {} warning(s) encountered (fail_on_warnings enabled)",
-                extraction.warning_count
+                extraction.total_warnings()
             )));
         }
         return Ok(ExtractResult {
@@ -164,28 +388,39 @@ pub fn extract(config: NapiConfig, options: Option<ExtractOptions>) -> Result<Ex
             unique_keys: 0,
             keys_added: 0,
             updated_files: vec![],
-            warnings: extraction.warning_count as u32,
+            added_keys: vec![],
+            warnings: extraction.total_warnings() as u32,
+            diagnostics,
+            diagnostic_counts,
             message: Some("No translation keys found.".to_string()),
+            cancelled: false,
         });
     }
 
-    // Collect all keys
-    let mut unique_keys: std::collections::HashSet<String> = std::collections::HashSet::new();
-    let mut all_keys: Vec<ExtractedKey> = Vec::new();
-
-    for (_file_path, keys) in &extraction.files {
-        for key in keys {
-            let full_key = match &key.namespace {
-                Some(ns) => format!("{}:{}", ns, key.key),
-                None => key.key.clone(),
-            };
-            unique_keys.insert(full_key);
-            all_keys.push(key.clone());
-        }
+    // Sync to JSON files (component <i18n> messages first so they win over empty defaults)
+    let message_results =
+        crate::json_sync::sync_component_messages(&config, &extraction.messages, output_dir, false)
+            .map_err(|e| napi::Error::from_reason(format!("Sync failed: {}", e)))?;
+    let mut sync_results = Vec::new();
+    for (target_output, keys) in crate::json_sync::group_keys_by_output(
+        &config,
+        extraction.files.iter().map(|(path, keys)| (path, keys)),
+        output_dir,
+    ) {
+        sync_results.extend(
+            crate::json_sync::sync_all_locales(&config, &keys, &target_output, false)
+                .map_err(|e| napi::Error::from_reason(format!("Sync failed: {}", e)))?,
+        );
     }
-
-    // Sync to JSON files
-    let sync_results = crate::json_sync::sync_all_locales(&config, &all_keys, output_dir, false)
+    sync_results.extend(message_results);
+    crate::key_transforms::write_key_map(&config, &key_map, false)
+        .map_err(|e| napi::Error::from_reason(format!("Sync failed: {}", e)))?;
+    crate::eslint_data::write_configured(
+        &config,
+        extraction.files.iter().map(|(path, keys)| (path, keys)),
+    )
+    .map_err(|e| napi::Error::from_reason(format!("Sync failed: {}", e)))?;
+    crate::publish::write_configured(&config)
         .map_err(|e| napi::Error::from_reason(format!("Sync failed: {}", e)))?;
 
     // Report sync results
@@ -194,10 +429,31 @@ pub fn extract(config: NapiConfig, options: Option<ExtractOptions>) -> Result<Ex
     for result in &sync_results {
         if !result.added_keys.is_empty() {
             total_added += result.added_keys.len();
-            updated_files.push(result.file_path.clone());
+            if !updated_files.contains(&result.file_path) {
+                updated_files.push(result.file_path.clone());
+            }
         }
     }
 
+    let results: Vec<&crate::json_sync::SyncResult> = sync_results.iter().collect();
+    let added_keys = crate::commands::extract::added_key_sources(
+        &config,
+        &extraction.files,
+        &extraction.key_sources,
+        &extraction.messages,
+        &results,
+    )
+    .into_iter()
+    .map(|added| AddedKeyInfo {
+        file: added.file,
+        namespace: added.namespace,
+        key: added.key,
+        source: added.source,
+        line: added.line.map(|line| line as u32),
+        column: added.column.map(|column| column as u32),
+    })
+    .collect();
+
     // Generate TypeScript types if requested
     if generate_types {
         let locales_dir = config.types.locales_dir.as_deref().unwrap_or(output_dir);
@@ -213,6 +469,10 @@ pub fn extract(config: NapiConfig, options: Option<ExtractOptions>) -> Result<Ex
         let input_patterns = config.types_input_patterns();
         let resources_file = config.types_resources_file();
         let enable_selector = config.types_enable_selector();
+        let root_keys = crate::typegen::object_root_keys(
+            extraction.files.iter().map(|(_, keys)| keys),
+            config.effective_default_namespace(),
+        );
         crate::typegen::generate_types_with_options(
             locales_dir,
             types_path,
@@ -221,16 +481,22 @@ pub fn extract(config: NapiConfig, options: Option<ExtractOptions>) -> Result<Ex
             input_patterns.as_deref(),
             resources_file.as_deref().map(std::path::Path::new),
             enable_selector.as_ref(),
-            config.merge_namespaces,
+            crate::typegen::LocaleLayout::from_config(&config),
+            Some(&config.plural_config()),
+            config.types_namespaces(),
+            crate::typegen::ObjectRoots {
+                keys: &root_keys,
+                key_separator: &config.key_separator,
+            },
         )
         .map_err(|e| napi::Error::from_reason(format!("Type generation failed: {}", e)))?;
     }
 
     // Check fail-on-warnings
-    if fail_on_warnings && extraction.warning_count > 0 {
+    if fail_on_warnings && extraction.total_warnings() > 0 {
         return Err(napi::Error::from_reason(format!(
             "Failed: {} warning(s) encountered (fail_on_warnings enabled)",
-            extraction.warning_count
+            extraction.total_warnings()
         )));
     }
 
@@ -240,11 +506,46 @@ pub fn extract(config: NapiConfig, options: Option<ExtractOptions>) -> Result<Ex
         unique_keys: unique_keys.len() as u32,
         keys_added: total_added as u32,
         updated_files,
-        warnings: extraction.warning_count as u32,
+        added_keys,
+        warnings: extraction.total_warnings() as u32,
+        diagnostics,
+        diagnostic_counts,
         message: None,
+        cancelled: false,
     })
 }
 
+#[cfg(feature = "napi")]
+fn diagnostic_report(
+    extraction: &crate::extractor::ExtractionResult,
+) -> (Vec<DiagnosticInfo>, Vec<DiagnosticCount>) {
+    let diagnostics = extraction
+        .diagnostics
+        .iter()
+        .map(|diagnostic| DiagnosticInfo {
+            kind: diagnostic.kind.to_string(),
+            severity: match diagnostic.severity {
+                crate::config::DiagnosticSeverity::Error => "error",
+                _ => "warn",
+            }
+            .to_string(),
+            file_path: diagnostic.file_path.clone(),
+            line: diagnostic.line.map(|line| line as u32),
+            column: diagnostic.column.map(|column| column as u32),
+            message: diagnostic.message.clone(),
+        })
+        .collect();
+    let counts = extraction
+        .diagnostic_counts()
+        .into_iter()
+        .map(|(kind, count)| DiagnosticCount {
+            kind: kind.to_string(),
+            count: count as u32,
+        })
+        .collect();
+    (diagnostics, counts)
+}
+
 /// Watch for file changes and extract keys automatically
 ///
 /// # Arguments
@@ -257,14 +558,22 @@ pub fn extract(config: NapiConfig, options: Option<ExtractOptions>) -> Result<Ex
 #[napi]
 #[cfg(feature = "napi")]
 pub fn watch(config: NapiConfig, options: Option<WatchOptions>) -> Result<()> {
-    let config: Config = Config::from_napi(config)
+    let config: Config = Config::try_from(config)
         .map_err(|e| napi::Error::from_reason(format!("Config validation failed: {}", e)))?;
 
     // Extract options
     let output = options.as_ref().and_then(|o| o.output.as_ref());
+    let batch_window = options
+        .as_ref()
+        .and_then(|o| o.batch_window_ms)
+        .map(u64::from)
+        .unwrap_or(crate::watcher::DEFAULT_MAX_BATCH_WINDOW_MS);
+    let dry_run = options.as_ref().and_then(|o| o.dry_run).unwrap_or(false);
 
     // Create watcher
-    let mut watcher = crate::watcher::FileWatcher::new(config, output.cloned());
+    let mut watcher = crate::watcher::FileWatcher::new(config, output.cloned())
+        .with_max_batch_window(std::time::Duration::from_millis(batch_window))
+        .with_dry_run(dry_run);
 
     // Run watcher (this blocks)
     watcher
@@ -286,6 +595,10 @@ pub struct ExtractOptions {
     pub generate_types: Option<bool>,
     /// TypeScript output path (only used with generate_types)
     pub types_output: Option<String>,
+    /// Cancel the extraction after this many milliseconds
+    pub timeout_ms: Option<u32>,
+    /// Only report diagnostics of these kinds, e.g. ["dynamic-key"]
+    pub diagnostic_kinds: Option<Vec<String>>,
 }
 
 /// Watch options
@@ -294,6 +607,10 @@ pub struct ExtractOptions {
 pub struct WatchOptions {
     /// Output directory (overrides config)
     pub output: Option<String>,
+    /// Longest time (ms) bursts of file events are batched into one write
+    pub batch_window_ms: Option<u32>,
+    /// Report what each save would change without writing locale files
+    pub dry_run: Option<bool>,
 }
 
 /// Lint options
@@ -316,18 +633,30 @@ pub struct CheckOptions {
     pub locale: Option<String>,
 }
 
+/// Find dead keys options
+#[cfg(feature = "napi")]
+#[napi(object)]
+pub struct FindDeadKeysOptions {
+    /// Locales to scan (defaults to first locale in config)
+    pub locales: Option<Vec<String>>,
+}
+
 /// Lint source files for hardcoded strings
 #[cfg(feature = "napi")]
 #[napi]
 pub fn lint(config: NapiConfig, options: Option<LintOptions>) -> Result<LintResult> {
-    let config: Config = Config::from_napi(config)
+    let config: Config = Config::try_from(config)
         .map_err(|e| napi::Error::from_reason(format!("Config validation failed: {}", e)))?;
     let fail_on_error = options
         .as_ref()
         .and_then(|o| o.fail_on_error)
         .unwrap_or(false);
 
-    let result = lint_mod::lint_from_glob(&config.input)
+    let lint_options = lint_mod::LintOptions {
+        path_style: config.path_style,
+        ..lint_mod::LintOptions::default()
+    };
+    let result = lint_mod::lint_from_glob_with_options(&config.input, &lint_options)
         .map_err(|e| napi::Error::from_reason(format!("Lint failed: {}", e)))?;
 
     if fail_on_error && !result.issues.is_empty() {
@@ -357,7 +686,7 @@ pub fn lint(config: NapiConfig, options: Option<LintOptions>) -> Result<LintResu
 #[cfg(feature = "napi")]
 #[napi]
 pub fn check(config: NapiConfig, options: Option<CheckOptions>) -> Result<CheckResult> {
-    let config: Config = Config::from_napi(config)
+    let config: Config = Config::try_from(config)
         .map_err(|e| napi::Error::from_reason(format!("Config validation failed: {}", e)))?;
     let remove = options.as_ref().and_then(|o| o.remove).unwrap_or(false);
     let dry_run = options.as_ref().and_then(|o| o.dry_run).unwrap_or(false);
@@ -368,57 +697,242 @@ pub fn check(config: NapiConfig, options: Option<CheckOptions>) -> Result<CheckR
         .or(config.locales.first().map(|s| s.as_str()))
         .unwrap_or("en");
 
-    let plural_config = config.plural_config();
-    let extraction = crate::extractor::extract_from_glob_with_options(
-        &config.input,
-        &config.ignore,
-        &config.functions,
-        config.extract_from_comments,
-        &plural_config,
-        &config.trans_components,
-        &config.trans_keep_basic_html_nodes_for,
-        &config.use_translation_names,
-        &config.nesting_prefix,
-        &config.nesting_suffix,
-        &config.nesting_options_separator,
-        &config.interpolation_prefix,
-        &config.interpolation_suffix,
-    )
-    .map_err(|e| napi::Error::from_reason(format!("Extraction failed: {}", e)))?;
-
-    let mut all_keys: Vec<ExtractedKey> = Vec::new();
-    for (_file_path, keys) in &extraction.files {
-        all_keys.extend(keys.iter().cloned());
-    }
-
-    let locales_path = std::path::Path::new(&config.output);
-    let dead_keys = cleanup_mod::find_dead_keys(
-        locales_path,
-        &all_keys,
-        config.effective_default_namespace(),
-        config.namespace_less_mode(),
-        config.merge_namespaces,
-        config.preserve_context_variants,
-        &config.context_separator,
-        locale,
-    )
-    .map_err(|e| napi::Error::from_reason(format!("Check failed: {}", e)))?;
+    let _guard = guard_outputs(&config, &config.output)?;
+    let (dead_keys, suggestions) = scan_dead_keys(&config, &[locale.to_string()])?;
 
     let mut removed_count = 0usize;
     if remove && !dry_run && !dead_keys.is_empty() {
-        removed_count = cleanup_mod::purge_dead_keys(locales_path, &dead_keys)
-            .map_err(|e| napi::Error::from_reason(format!("Cleanup failed: {}", e)))?;
+        removed_count =
+            cleanup_mod::purge_expired_dead_keys(&config, &locale_roots(&config), &dead_keys)
+                .map_err(|e| napi::Error::from_reason(format!("Cleanup failed: {}", e)))?
+                .0;
     }
 
     Ok(CheckResult {
-        dead_keys: dead_keys
-            .iter()
-            .map(|dk| DeadKeyInfo {
-                file_path: dk.file_path.clone(),
-                key_path: dk.key_path.clone(),
-                namespace: dk.namespace.clone(),
-            })
-            .collect(),
+        dead_keys: dead_keys.iter().map(DeadKeyInfo::from).collect(),
+        removed_count: removed_count as u32,
+        suggested_preserve_patterns: suggestions,
+    })
+}
+
+/// List dead (unused) keys without modifying any file, so callers can pick
+/// which ones to pass to `purgeDeadKeys`
+#[cfg(feature = "napi")]
+#[napi]
+pub fn find_dead_keys(
+    config: NapiConfig,
+    options: Option<FindDeadKeysOptions>,
+) -> Result<DeadKeysResult> {
+    let config: Config = Config::try_from(config)
+        .map_err(|e| napi::Error::from_reason(format!("Config validation failed: {}", e)))?;
+    let locales = options
+        .and_then(|o| o.locales)
+        .filter(|locales| !locales.is_empty())
+        .unwrap_or_else(|| {
+            vec![config
+                .locales
+                .first()
+                .map_or("en", |s| s.as_str())
+                .to_string()]
+        });
+
+    let (dead_keys, suggestions) = scan_dead_keys(&config, &locales)?;
+    Ok(DeadKeysResult {
+        dead_keys: dead_keys.iter().map(DeadKeyInfo::from).collect(),
+        suggested_preserve_patterns: suggestions,
+    })
+}
+
+/// A key of a `KeyIndex`
+#[cfg(feature = "napi")]
+#[napi(object)]
+pub struct KeyIndexEntry {
+    pub namespace: String,
+    pub key: String,
+    /// Source files using the key
+    pub files: Vec<String>,
+    /// String `defaultValue` from source
+    pub default_value: Option<String>,
+}
+
+#[cfg(feature = "napi")]
+impl From<&crate::key_index::IndexedKey> for KeyIndexEntry {
+    fn from(entry: &crate::key_index::IndexedKey) -> Self {
+        KeyIndexEntry {
+            namespace: entry.namespace.clone(),
+            key: entry.key.clone(),
+            files: entry.files.iter().cloned().collect(),
+            default_value: entry.default_value.clone(),
+        }
+    }
+}
+
+/// Extracted keys kept in memory for autocomplete and similar lookups; build
+/// it once with `buildKeyIndex` and `refreshFile` on save
+#[cfg(feature = "napi")]
+#[napi(js_name = "KeyIndex")]
+pub struct NapiKeyIndex {
+    config: Config,
+    index: crate::key_index::KeyIndex,
+}
+
+#[cfg(feature = "napi")]
+fn key_index_entries(entries: Vec<&crate::key_index::IndexedKey>) -> Vec<KeyIndexEntry> {
+    entries.into_iter().map(KeyIndexEntry::from).collect()
+}
+
+#[cfg(feature = "napi")]
+#[napi]
+impl NapiKeyIndex {
+    #[napi(getter)]
+    pub fn size(&self) -> u32 {
+        self.index.len() as u32
+    }
+
+    #[napi]
+    pub fn namespaces(&self) -> Vec<String> {
+        self.index
+            .namespaces()
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Keys starting with `prefix`, in `namespace` or every namespace
+    #[napi]
+    pub fn by_prefix(&self, prefix: String, namespace: Option<String>) -> Vec<KeyIndexEntry> {
+        key_index_entries(self.index.by_prefix(namespace.as_deref(), &prefix))
+    }
+
+    #[napi]
+    pub fn by_namespace(&self, namespace: String) -> Vec<KeyIndexEntry> {
+        key_index_entries(self.index.by_namespace(&namespace))
+    }
+
+    #[napi]
+    pub fn by_file(&self, file_path: String) -> Vec<KeyIndexEntry> {
+        key_index_entries(self.index.by_file(&file_path))
+    }
+
+    /// Fuzzy search over `namespace:key`, best matches first (default limit: 20)
+    #[napi]
+    pub fn search(&self, query: String, limit: Option<u32>) -> Vec<KeyIndexEntry> {
+        key_index_entries(self.index.search(&query, limit.unwrap_or(20) as usize))
+    }
+
+    /// Re-extract one source file after it changed or was deleted
+    #[napi]
+    pub fn refresh_file(&mut self, file_path: String) -> Result<()> {
+        self.index
+            .refresh_file(&self.config, std::path::Path::new(&file_path))
+            .map_err(|e| napi::Error::from_reason(format!("Extraction failed: {}", e)))
+    }
+}
+
+/// Extract the sources once and keep the keys in memory for queries
+#[cfg(feature = "napi")]
+#[napi]
+pub fn build_key_index(config: NapiConfig) -> Result<NapiKeyIndex> {
+    let config: Config = Config::try_from(config)
+        .map_err(|e| napi::Error::from_reason(format!("Config validation failed: {}", e)))?;
+    let index = crate::key_index::KeyIndex::build(&config)
+        .map_err(|e| napi::Error::from_reason(format!("Extraction failed: {}", e)))?;
+    Ok(NapiKeyIndex { config, index })
+}
+
+/// Remove the given keys (as returned by `findDeadKeys`) from locale files.
+/// Keys are re-checked first; any that are no longer dead, or still within
+/// `removalGraceDays`, are skipped.
+#[cfg(feature = "napi")]
+#[napi]
+pub fn purge_dead_keys(config: NapiConfig, keys: Vec<DeadKeyInfo>) -> Result<PurgeResult> {
+    let config: Config = Config::try_from(config)
+        .map_err(|e| napi::Error::from_reason(format!("Config validation failed: {}", e)))?;
+    let requested: Vec<cleanup_mod::DeadKey> = keys.into_iter().map(Into::into).collect();
+    let _guard = guard_outputs(&config, &config.output)?;
+
+    let (dead_keys, _) = scan_dead_keys(&config, &config.locales)?;
+    let (selected, mut skipped) = cleanup_mod::select_dead_keys(&dead_keys, &requested);
+    let (removed_count, kept) =
+        cleanup_mod::purge_expired_dead_keys(&config, &locale_roots(&config), &selected)
+            .map_err(|e| napi::Error::from_reason(format!("Cleanup failed: {}", e)))?;
+    skipped.extend(kept);
+
+    Ok(PurgeResult {
         removed_count: removed_count as u32,
+        skipped_keys: skipped.iter().map(DeadKeyInfo::from).collect(),
     })
 }
+
+/// `output` and the `outputs` locale roots
+#[cfg(feature = "napi")]
+fn locale_roots(config: &Config) -> Vec<&str> {
+    std::iter::once(config.output.as_str())
+        .chain(config.outputs.iter().map(|target| target.output.as_str()))
+        .collect()
+}
+
+/// Hold `output_dir` and the `outputs` locale roots for the rest of the call, so
+/// overlapping calls from one JS process do not interleave their writes
+#[cfg(feature = "napi")]
+fn guard_outputs(config: &Config, output_dir: &str) -> Result<crate::run_guard::RunGuard> {
+    let dirs = std::iter::once(output_dir)
+        .chain(config.outputs.iter().map(|target| target.output.as_str()));
+    crate::run_guard::acquire(dirs, config.concurrent_runs, config.path_style)
+        .map_err(|e| napi::Error::from_reason(e.to_string()))
+}
+
+/// Extract the sources once and collect the dead keys of `locales`, along with
+/// suggested `preservePatterns`
+#[cfg(feature = "napi")]
+fn scan_dead_keys(
+    config: &Config,
+    locales: &[String],
+) -> Result<(Vec<cleanup_mod::DeadKey>, Vec<String>)> {
+    let mut extraction =
+        crate::extractor::extract_with_config(config, &config.input, &config.extraction_ignore())
+            .map_err(|e| napi::Error::from_reason(format!("Extraction failed: {}", e)))?;
+    crate::key_transforms::apply(config, &mut extraction.files);
+
+    // Each locale root is checked against the keys synced into it
+    let groups = crate::json_sync::group_keys_by_output(
+        config,
+        extraction.files.iter().map(|(path, keys)| (path, keys)),
+        &config.output,
+    );
+    let mut dead_keys = Vec::new();
+    for ((root, keys), locale) in groups
+        .iter()
+        .flat_map(|group| locales.iter().map(move |locale| (group, locale)))
+    {
+        dead_keys.extend(
+            cleanup_mod::find_dead_keys(
+                std::path::Path::new(root),
+                keys,
+                config.effective_default_namespace(),
+                config.namespace_less_mode(),
+                config.merge_namespaces,
+                config.merged_namespace_prefix(),
+                config.sharded_locale_files(),
+                config.preserve_context_variants,
+                &config.context_separator,
+                &config.plural_separator,
+                locale,
+                config.path_style,
+            )
+            .map_err(|e| napi::Error::from_reason(format!("Check failed: {}", e)))?,
+        );
+    }
+    let (dead_keys, suggestions) = cleanup_mod::triage_dead_keys(
+        dead_keys,
+        &config.preserve_patterns,
+        &extraction.dynamic_keys,
+        &config.ns_separator,
+    )
+    .map_err(|e| napi::Error::from_reason(format!("Check failed: {}", e)))?;
+
+    Ok((
+        dead_keys,
+        suggestions.into_iter().map(|s| s.pattern).collect(),
+    ))
+}
@@ -1,13 +1,17 @@
 #![cfg_attr(test, allow(clippy::field_reassign_with_default))]
 
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
 use i18next_turbo::commands;
 use i18next_turbo::config::Config;
+use i18next_turbo::conflicts;
 use i18next_turbo::logging::{self, LogLevel};
-use i18next_turbo::watcher::FileWatcher;
+use i18next_turbo::plan::{Change, ChangePlan};
+use i18next_turbo::summary::{self, SummaryFormat};
+use i18next_turbo::watcher::{self, ConfigReload, FileWatcher};
 use std::io::Read;
 use std::path::PathBuf;
+use std::time::Duration;
 use std::{fs, path::Path};
 
 #[derive(Parser)]
@@ -38,6 +42,27 @@ struct Cli {
     #[arg(long, global = true)]
     log_level: Option<String>,
 
+    /// Override a config value for this run (repeatable), e.g. --set output=public/locales.
+    /// Wins over the config file and I18NEXT_TURBO_* environment variables
+    #[arg(long = "set", global = true, value_name = "KEY=VALUE")]
+    set: Vec<String>,
+
+    /// Write JSON locale files minified (same as --set minify=true)
+    #[arg(long, global = true)]
+    minify: bool,
+
+    /// Print a usage summary (files scanned, cache hits, keys added/removed,
+    /// time per phase) to stderr at the end of the run: text (default) or json
+    #[arg(
+        long,
+        global = true,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        default_missing_value = "text",
+        value_parser = ["text", "json"]
+    )]
+    summary: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -77,6 +102,83 @@ enum Commands {
         /// Sync all configured locale files (default behavior)
         #[arg(long)]
         sync_all: bool,
+
+        /// Write all extraction errors (parse and glob errors with positions) to a JSON file
+        #[arg(long, value_name = "PATH")]
+        error_report: Option<PathBuf>,
+
+        /// Write keys skipped for conflicts (with existing and wanted values) to a
+        /// JSON file for `resolve-conflicts`
+        #[arg(long, value_name = "PATH")]
+        conflict_report: Option<PathBuf>,
+
+        /// Write the updated locale files to a JSON file, with the source file,
+        /// line and column that first introduced each added key
+        #[arg(long, value_name = "PATH")]
+        json_report: Option<PathBuf>,
+
+        /// Only extract from source files matching this glob (repeatable).
+        /// Stale keys are kept unless --filter-key scopes the removal
+        #[arg(long, value_name = "GLOB")]
+        filter_file: Vec<String>,
+
+        /// Only add or remove keys matching this glob, e.g. "auth.*" (repeatable)
+        #[arg(long, value_name = "GLOB")]
+        filter_key: Vec<String>,
+
+        /// Write run metrics (timings, key/warning counts) to a file: Prometheus
+        /// text for .prom, JSON otherwise
+        #[arg(long, value_name = "PATH")]
+        metrics_file: Option<PathBuf>,
+
+        /// Push run metrics to an OTLP/HTTP collector, e.g. http://localhost:4318
+        #[arg(long, value_name = "URL")]
+        otlp_endpoint: Option<String>,
+
+        /// Fail when a key is extracted from code and from a comment with different
+        /// default values
+        #[arg(long)]
+        strict_duplicates: bool,
+
+        /// Overwrite primary-locale values of existing keys whose source default changed
+        #[arg(long)]
+        update_defaults: bool,
+
+        /// With --update-defaults, empty the changed keys in the other locales
+        #[arg(long)]
+        clear_stale: bool,
+
+        /// Trace why a key ("key" or "ns:key") is or is not extracted; writes nothing
+        #[arg(long, value_name = "KEY")]
+        explain: Option<String>,
+
+        /// Run extraction twice into scratch copies of the locale files (one
+        /// thread, then all) and fail unless the output is byte-identical
+        #[arg(long)]
+        verify_determinism: bool,
+
+        /// Leave out developer-only keys (`devOnly: true`, `i18next-dev-only`),
+        /// which otherwise go to the `dev` namespace; existing ones are kept
+        #[arg(long)]
+        production: bool,
+
+        /// Also lint for hardcoded strings, reusing the parse of each file
+        #[arg(long)]
+        lint: bool,
+
+        /// Only report extraction diagnostics of this kind (repeatable): dynamic-key,
+        /// dynamic-context, unresolved-namespace, conflicting-options, unsafe-namespace,
+        /// parse-error, file-error
+        #[arg(long, value_name = "KIND")]
+        diagnostic: Vec<String>,
+
+        /// Read one source file from stdin and print its keys as JSON; writes nothing
+        #[arg(long, requires = "stdin_filepath")]
+        stdin: bool,
+
+        /// Path the stdin source stands for; its extension picks the parser
+        #[arg(long, value_name = "PATH", requires = "stdin")]
+        stdin_filepath: Option<PathBuf>,
     },
 
     /// Watch for file changes and extract keys automatically
@@ -84,6 +186,23 @@ enum Commands {
         /// Output directory (overrides config)
         #[arg(short, long)]
         output: Option<String>,
+
+        /// Longest time (ms) bursts of file events are batched into one write
+        #[arg(long, default_value_t = watcher::DEFAULT_MAX_BATCH_WINDOW_MS)]
+        batch_window: u64,
+
+        /// Watch several config files concurrently; paths in each config are
+        /// relative to its directory
+        #[arg(long, num_args = 1..)]
+        configs: Vec<PathBuf>,
+
+        /// Watch every i18next-turbo.json found under the current directory
+        #[arg(long)]
+        workspace: bool,
+
+        /// Print what each save would change without writing locale files
+        #[arg(long, visible_alias = "report-only")]
+        dry_run: bool,
     },
 
     /// Generate TypeScript type definitions from existing locale files
@@ -99,6 +218,25 @@ enum Commands {
         /// Locales directory (overrides config)
         #[arg(short, long)]
         locales_dir: Option<String>,
+
+        /// Write runtime key constant modules (one per namespace) instead of
+        /// type definitions; --output sets their directory
+        #[arg(long)]
+        constants: bool,
+    },
+
+    /// Export the valid keys, namespaces and per-file namespaces as JSON for ESLint rules
+    ExportEslintData {
+        /// Output path (defaults to eslintDataFile)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Publish the synced catalogs as content-hashed per-locale bundles with a manifest.json
+    PublishDir {
+        /// Output directory (defaults to publishDir)
+        #[arg(short, long)]
+        output: Option<String>,
     },
 
     /// Check for dead (unused) translation keys
@@ -114,6 +252,59 @@ enum Commands {
         /// Locale to check (defaults to first locale in config)
         #[arg(short, long)]
         locale: Option<String>,
+
+        /// Add suggested preservePatterns for keys produced by dynamic keys to the config file
+        #[arg(long)]
+        write_config: bool,
+
+        /// Only extract from source files matching this glob (repeatable; needs --filter-key)
+        #[arg(long, value_name = "GLOB")]
+        filter_file: Vec<String>,
+
+        /// Only report or remove dead keys matching this glob, e.g. "auth.*" (repeatable)
+        #[arg(long, value_name = "GLOB")]
+        filter_key: Vec<String>,
+
+        /// Validate HTML tags, Trans placeholders and entities of translated values instead of dead keys
+        #[arg(long)]
+        values: bool,
+
+        /// Validate references inside translated values: `$t(...)` cycles, `$t(...)` keys
+        /// missing from the locale and its fallbacks, and formatters outside `allowedFormatters`
+        #[arg(long, visible_alias = "references", conflicts_with = "values")]
+        nesting: bool,
+
+        /// Report translated values whose leading/trailing whitespace, repeated spaces
+        /// or final punctuation differ from the primary locale
+        #[arg(long, group = "fixable", conflicts_with_all = ["values", "nesting"])]
+        spacing: bool,
+
+        /// Report keys breaking `keyCase` and keys that only differ in case
+        /// (`user.Name` vs `user.name`) across sources and locale files
+        #[arg(long, group = "fixable", conflicts_with_all = ["values", "nesting"])]
+        key_case: bool,
+
+        /// With --spacing, rewrite the reported values to match the primary locale;
+        /// with --key-case, unify each key to its most used spelling, sources included
+        #[arg(long, requires = "fixable")]
+        fix: bool,
+
+        /// Only report or remove dead keys not seen for at least this many days (needs `metadataFile`)
+        #[arg(long, value_name = "DAYS")]
+        dead_for: Option<u32>,
+
+        /// Add plural forms required by each locale's CLDR rules, copied from `_other`
+        #[arg(long)]
+        fix_plurals: bool,
+
+        /// Write a change plan deleting the dead keys instead of removing them
+        #[arg(long, value_name = "PATH")]
+        emit_plan: Option<PathBuf>,
+
+        /// Check every configured locale, report dead keys present in some
+        /// locales only, and remove dead keys from all of them
+        #[arg(long)]
+        all_locales: bool,
     },
 
     /// Show translation status summary
@@ -129,6 +320,112 @@ enum Commands {
         /// Only include keys from the specified namespace
         #[arg(long)]
         namespace: Option<String>,
+
+        /// Exit with non-zero code if any namespace of a checked locale is less
+        /// than this percentage translated
+        #[arg(long, value_name = "PERCENT")]
+        min_completeness: Option<f64>,
+    },
+
+    /// Fail when a locale's translation completeness is below its release gate
+    Gate {
+        /// Only evaluate the gate for this locale
+        #[arg(short, long)]
+        locale: Option<String>,
+    },
+
+    /// Check for unsafe symlinks and file names that collide on case-insensitive file systems
+    Doctor,
+
+    /// Compare `defaultValue` strings in source with the primary locale
+    Defaults {
+        /// Overwrite the primary-locale values with the source defaults
+        #[arg(long)]
+        prefer_code: bool,
+
+        /// Rewrite the source defaults to the primary-locale values
+        #[arg(long)]
+        prefer_catalog: bool,
+
+        /// Show what would change without writing files
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Resolve the conflicts of an `extract --conflict-report` file
+    ResolveConflicts {
+        /// Conflict report written by `extract --conflict-report`
+        #[arg(long, value_name = "PATH", default_value = "conflicts.json")]
+        report: PathBuf,
+
+        /// How to resolve: keep-existing, overwrite or nest (also `strategy=<name>`)
+        #[arg(long, value_name = "STRATEGY")]
+        apply: String,
+
+        /// Show what would change without writing files
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Report the raw and gzip byte size of every locale file
+    Size {
+        /// Only report this locale
+        #[arg(short, long)]
+        locale: Option<String>,
+    },
+
+    /// Set the review status of translations (kept in `metadataFile`)
+    Mark {
+        /// machine-translated, needs-review or approved
+        status: String,
+
+        /// Keys to mark: `namespace:key` or glob patterns like "checkout.*"
+        #[arg(required = true)]
+        keys: Vec<String>,
+
+        /// Locale to mark (repeatable; defaults to every locale but the primary one)
+        #[arg(short, long)]
+        locale: Vec<String>,
+
+        /// Show what would change without writing the metadata file
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Copy translations for keys that are empty locally from another checkout, export, or git ref
+    ImportFrom {
+        /// Directory (checkout root or locales directory) or git ref to import from,
+        /// laid out like the configured output. Locales without files there are
+        /// reported; it is an error when no locale (or the `--locale` one) has any.
+        source: String,
+
+        /// Only import into this locale
+        #[arg(short, long)]
+        locale: Option<String>,
+
+        /// Preview changes without writing files
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Split a bundled i18next resources object from a JS/TS file into locale files
+    Ingest {
+        /// JS/TS file exporting `{ en: { translation: {...} } }`
+        source: PathBuf,
+
+        /// Preview changes without writing files
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Merge the keys of an i18next saveMissing log (JSON lines) into the catalogs
+    ImportMissing {
+        /// Log file with one `{"ns": ..., "key": ..., "fallbackValue": ...}` object per line
+        log: PathBuf,
+
+        /// Preview changes without writing files
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Sync translation keys across locales
@@ -140,6 +437,18 @@ enum Commands {
         /// Preview changes without writing files
         #[arg(long)]
         dry_run: bool,
+
+        /// Write a change plan with the added and removed keys instead of changing files
+        #[arg(long, value_name = "PATH")]
+        emit_plan: Option<PathBuf>,
+    },
+
+    /// Rewrite locale files in the configured style (indentation, key order,
+    /// line endings) without adding or removing keys
+    Fmt {
+        /// List unformatted files and fail instead of writing (useful for CI)
+        #[arg(long)]
+        check: bool,
     },
 
     /// Lint source files for hardcoded strings that should be translated
@@ -151,6 +460,51 @@ enum Commands {
         /// Watch mode: re-run lint when files change
         #[arg(long)]
         watch: bool,
+
+        /// Read one source file from stdin and print its issues as JSON
+        #[arg(long, requires = "stdin_filepath", conflicts_with = "watch")]
+        stdin: bool,
+
+        /// Path the stdin source stands for; its extension picks the parser
+        #[arg(long, value_name = "PATH", requires = "stdin")]
+        stdin_filepath: Option<PathBuf>,
+    },
+
+    /// Extract a single file (ignoring input globs) and print its keys with locations and timing
+    ExtractFile {
+        /// Source file to extract
+        path: PathBuf,
+
+        /// Print the result as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Interactively search keys, compare their values across locales, see
+    /// where they are used, and queue deletes/renames applied on exit
+    Browse {
+        /// Write the queued changes as a change plan instead of applying them
+        #[arg(long, value_name = "PATH")]
+        emit_plan: Option<PathBuf>,
+    },
+
+    /// Review or apply a change plan written by --emit-plan
+    Plan {
+        #[command(subcommand)]
+        command: PlanCommands,
+    },
+
+    /// Compare two locale roots (e.g. the repo and a TMS export) key by key
+    Diff {
+        /// Locale root to compare from
+        dir_a: PathBuf,
+
+        /// Locale root to compare to
+        dir_b: PathBuf,
+
+        /// Output format: text, json or lines (`-`/`+` key lines per locale and namespace)
+        #[arg(long, default_value = "text")]
+        format: String,
     },
 
     /// Rename a translation key in source files and locale files
@@ -168,6 +522,85 @@ enum Commands {
         /// Only rename in locale files (skip source files)
         #[arg(long)]
         locales_only: bool,
+
+        /// Write a change plan with the rename instead of changing files
+        #[arg(long, value_name = "PATH")]
+        emit_plan: Option<PathBuf>,
+    },
+
+    /// Rename a namespace in locale files and source code
+    RenameNamespace {
+        /// The namespace to rename
+        old_namespace: String,
+
+        /// The new namespace name
+        new_namespace: String,
+
+        /// Preview changes without modifying files
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Only rename locale files (skip source files)
+        #[arg(long)]
+        locales_only: bool,
+
+        /// Write a change plan with the rename instead of changing files
+        #[arg(long, value_name = "PATH")]
+        emit_plan: Option<PathBuf>,
+    },
+
+    /// Scaffold a new locale's files from the primary locale and add it to the config
+    AddLocale {
+        /// Locale code to add, e.g. "fr"
+        locale: String,
+
+        /// Copy the primary locale's values as fallbacks instead of leaving them empty
+        #[arg(long)]
+        copy_values: bool,
+
+        /// Preview changes without writing files
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Delete a retired locale's files, remove it from the config and list
+    /// source code that still refers to it
+    ///
+    /// There is no --emit-plan: change plans edit keys, not locale directories
+    /// or the config file. Use --dry-run to review the removal.
+    RemoveLocale {
+        /// Locale code to remove, e.g. "en-US"
+        locale: String,
+
+        /// Preview changes without deleting files
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Set one translation value, keeping the locale file's formatting
+    SetValue {
+        /// Locale to change
+        locale: String,
+
+        /// Key to set: `namespace:key.path`, or a key of the default namespace
+        key: String,
+
+        /// New value
+        value: String,
+
+        /// Preview changes without writing files
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Edit the values of a key in every locale in $VISUAL or $EDITOR
+    Edit {
+        /// Key to edit: `namespace:key.path`, or a key of the default namespace
+        key: String,
+
+        /// Preview changes without writing files
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Initialize a new i18next-turbo configuration file
@@ -216,11 +649,159 @@ enum Commands {
         dry_run: bool,
     },
 
+    /// Convert legacy v3/v2 plural keys (`key_plural`, `key_0`) to v4 suffixes in all locales
+    MigratePlurals {
+        /// Legacy format of the catalog: v3 or v2 (defaults to `pluralCompat`, then v3)
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Preview changes without writing files
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Write a change plan with the migrated keys instead of changing files
+        #[arg(long, value_name = "PATH")]
+        emit_plan: Option<PathBuf>,
+    },
+
+    /// Merge translations from the configured TMS (`tms` in the config) into local locale files
+    Pull {
+        /// Only pull this locale (default: all locales)
+        #[arg(short, long)]
+        locale: Option<String>,
+
+        /// Only pull this namespace
+        #[arg(short, long)]
+        namespace: Option<String>,
+
+        /// Only fill untranslated local values; keep translated ones
+        #[arg(long)]
+        only_missing: bool,
+
+        /// Show what would change without writing files
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Send local keys the configured TMS does not have yet
+    Push {
+        /// Locale to push (default: the primary language)
+        #[arg(short, long)]
+        locale: Option<String>,
+
+        /// Only push this namespace
+        #[arg(short, long)]
+        namespace: Option<String>,
+
+        /// List the new keys without sending them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
     /// Locize integration commands
     Locize {
         #[command(subcommand)]
         command: LocizeCommands,
     },
+
+    /// Pre-commit hook: extract from staged files and stage updated locale files
+    Hook {
+        /// Extract the files staged in the git index, as staged
+        #[arg(long)]
+        staged: bool,
+
+        /// Files to extract from (ignored with --staged)
+        files: Vec<String>,
+
+        /// Show which locale files would be updated without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Keep the key index in memory and serve requests over a local socket
+    Daemon {
+        /// Socket path (defaults to .i18next-turbo.sock in the current directory).
+        /// On Windows the daemon listens on a named pipe derived from it.
+        #[arg(long, global = true)]
+        socket: Option<PathBuf>,
+
+        #[command(subcommand)]
+        command: DaemonCommands,
+    },
+
+    /// Inspect the configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Print the configuration as JSON
+    Show {
+        /// Print the final config after environment and --set overrides, with the source of each value
+        #[arg(long)]
+        resolved: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum DaemonCommands {
+    /// Start the daemon in the foreground
+    Start,
+
+    /// Stop a running daemon
+    Stop,
+
+    /// Check whether a daemon is running
+    Ping,
+
+    /// Re-extract the given files through the daemon and sync affected namespaces
+    ExtractChanged {
+        /// Changed (or deleted) source files
+        #[arg(required = true)]
+        files: Vec<String>,
+
+        /// Preview changes without writing files
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Show which source files use a key (`ns:key` or `key`)
+    Query {
+        /// The key to look up
+        key: String,
+    },
+
+    /// Lint a single file through the daemon
+    Lint {
+        /// File to lint
+        file: String,
+
+        /// Fail on lint errors
+        #[arg(long)]
+        fail_on_error: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum PlanCommands {
+    /// Apply the changes of a plan in order
+    Apply {
+        /// Plan file (JSON)
+        file: PathBuf,
+
+        /// Show what would change without modifying files
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// List the changes of a plan
+    Show {
+        /// Plan file (JSON)
+        file: PathBuf,
+    },
 }
 
 #[derive(Subcommand)]
@@ -318,7 +899,27 @@ enum LocizeCommands {
 }
 
 fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    if let Some(format) = cli.summary.as_deref().and_then(SummaryFormat::parse_str) {
+        summary::enable(format);
+        summary::record_command(matches.subcommand_name().unwrap_or_default());
+    }
+    let result = run(cli);
+    summary::print();
+    if let Err(error) = &result {
+        if error.is::<watcher::ConfigChanged>() {
+            // The Node wrapper evaluates the JS/TS config again and restarts `watch`
+            std::process::exit(watcher::RELOAD_EXIT_CODE);
+        }
+    }
+    result
+}
+
+fn run(mut cli: Cli) -> Result<()> {
+    if cli.minify {
+        cli.set.push("minify=true".to_string());
+    }
 
     let loaded_config = load_config(&cli)?;
     let mut config = loaded_config.config;
@@ -326,6 +927,8 @@ fn main() -> Result<()> {
     if matches!(loaded_config.source_kind, ConfigSourceKind::Default) {
         auto_detect_config_for_command(&mut config, &cli.command);
     }
+    let file_config = config.clone();
+    let overrides = config.apply_overrides(|name| std::env::var(name).ok(), &cli.set)?;
 
     let requested_level = cli
         .log_level
@@ -350,7 +953,37 @@ fn main() -> Result<()> {
             ci,
             sync_primary,
             sync_all,
+            error_report,
+            conflict_report,
+            json_report,
+            filter_file,
+            filter_key,
+            metrics_file,
+            otlp_endpoint,
+            strict_duplicates,
+            update_defaults,
+            clear_stale,
+            explain,
+            verify_determinism,
+            production,
+            lint,
+            diagnostic,
+            stdin,
+            stdin_filepath,
         } => {
+            if let Some(path) = stdin_filepath.filter(|_| stdin) {
+                ensure_stdin_is_free(cli.config_stdin)?;
+                commands::extract_file::run_stdin(&config, &path, fail_on_warnings)?;
+                return Ok(());
+            }
+            if let Some(key) = explain {
+                commands::explain::run(&config, &key)?;
+                return Ok(());
+            }
+            if verify_determinism {
+                commands::extract::verify_determinism(&config, output)?;
+                return Ok(());
+            }
             let resolved_types_output = types_output.unwrap_or_else(|| config.types_output_path());
             commands::extract::run(
                 &config,
@@ -363,21 +996,89 @@ fn main() -> Result<()> {
                 sync_primary,
                 sync_all,
                 cli.verbose,
+                error_report.as_deref(),
+                conflict_report.as_deref(),
+                json_report.as_deref(),
+                &filter_file,
+                &filter_key,
+                metrics_file.as_deref(),
+                otlp_endpoint.as_deref(),
+                strict_duplicates,
+                update_defaults,
+                clear_stale,
+                production,
+                lint,
+                &diagnostic,
             )?;
         }
-        Commands::Watch { output } => {
-            println!("=== i18next-turbo watch ===\n");
-            let mut watcher = FileWatcher::new(config.clone(), output);
-            watcher.run()?;
+        Commands::Watch {
+            output,
+            batch_window,
+            mut configs,
+            workspace,
+            dry_run,
+        } => {
+            if dry_run {
+                println!("=== i18next-turbo watch (dry-run) ===\n");
+            } else {
+                println!("=== i18next-turbo watch ===\n");
+            }
+            if workspace {
+                configs.extend(watcher::discover_configs(Path::new(".")));
+            }
+            if configs.is_empty() {
+                let mut watcher = FileWatcher::new(config.clone(), output)
+                    .with_max_batch_window(Duration::from_millis(batch_window))
+                    .with_dry_run(dry_run);
+                let reload = match (loaded_config.source_kind, loaded_config.source_path) {
+                    (ConfigSourceKind::File, Some(path)) => Some(ConfigReload::File {
+                        path,
+                        overrides: cli.set.clone(),
+                        base_dir: None,
+                    }),
+                    (ConfigSourceKind::InlineJson, Some(path)) => {
+                        Some(ConfigReload::Wrapper { path })
+                    }
+                    _ => None,
+                };
+                if let Some(reload) = reload {
+                    watcher = watcher.with_config_reload(reload);
+                }
+                watcher.run()?;
+            } else {
+                if output.is_some() {
+                    anyhow::bail!("--output cannot be combined with --configs or --workspace");
+                }
+                let watchers = workspace_watchers(&configs, &cli.set)?
+                    .into_iter()
+                    .map(|watcher| {
+                        watcher
+                            .with_max_batch_window(Duration::from_millis(batch_window))
+                            .with_dry_run(dry_run)
+                    })
+                    .collect();
+                watcher::run_concurrently(watchers)?;
+            }
         }
         Commands::Typegen {
             output,
             default_locale,
             locales_dir,
+            constants,
         } => {
-            let resolved_output = output.unwrap_or_else(|| config.types_output_path());
             let resolved_default_locale = default_locale.or_else(|| config.types_default_locale());
             let resolved_locales_dir = locales_dir.or_else(|| config.types_locales_dir());
+            if constants {
+                let resolved_output = output.unwrap_or_else(|| config.types_constants_dir());
+                commands::typegen::run_constants(
+                    &config,
+                    &resolved_output,
+                    resolved_default_locale,
+                    resolved_locales_dir,
+                )?;
+                return Ok(());
+            }
+            let resolved_output = output.unwrap_or_else(|| config.types_output_path());
             commands::typegen::run(
                 &config,
                 &resolved_output,
@@ -385,40 +1086,236 @@ fn main() -> Result<()> {
                 resolved_locales_dir,
             )?;
         }
+        Commands::ExportEslintData { output } => {
+            commands::eslint_data::run(&config, output.as_deref())?;
+        }
+        Commands::PublishDir { output } => {
+            commands::publish::run(&config, output.as_deref())?;
+        }
         Commands::Check {
             remove,
             dry_run,
             locale,
+            write_config,
+            filter_file,
+            filter_key,
+            values,
+            nesting,
+            spacing,
+            key_case,
+            fix,
+            dead_for,
+            fix_plurals,
+            emit_plan,
+            all_locales,
         } => {
-            commands::check::run(&config, remove, dry_run, locale)?;
+            commands::check::run(
+                &config,
+                remove,
+                dry_run,
+                locale,
+                write_config,
+                loaded_config.source_path.as_deref(),
+                &filter_file,
+                &filter_key,
+                values,
+                nesting,
+                spacing,
+                key_case,
+                fix,
+                dead_for,
+                fix_plurals,
+                emit_plan.as_deref(),
+                all_locales,
+            )?;
         }
         Commands::Status {
             locale,
             fail_on_incomplete,
             namespace,
+            min_completeness,
+        } => {
+            commands::status::run(
+                &config,
+                locale,
+                fail_on_incomplete,
+                namespace,
+                min_completeness,
+            )?;
+        }
+        Commands::Gate { locale } => {
+            commands::gate::run(&config, locale)?;
+        }
+        Commands::Doctor => {
+            commands::doctor::run(&config)?;
+        }
+        Commands::Defaults {
+            prefer_code,
+            prefer_catalog,
+            dry_run,
+        } => {
+            commands::defaults::run(&config, prefer_code, prefer_catalog, dry_run)?;
+        }
+        Commands::ResolveConflicts {
+            report,
+            apply,
+            dry_run,
+        } => {
+            let strategy = conflicts::ResolveStrategy::parse_str(&apply)?;
+            commands::resolve_conflicts::run(&config, &report, strategy, dry_run)?;
+        }
+        Commands::Size { locale } => {
+            commands::size::run(&config, locale)?;
+        }
+        Commands::Mark {
+            status,
+            keys,
+            locale,
+            dry_run,
+        } => {
+            commands::mark::run(&config, &status, &keys, &locale, dry_run)?;
+        }
+        Commands::ImportFrom {
+            source,
+            locale,
+            dry_run,
         } => {
-            commands::status::run(&config, locale, fail_on_incomplete, namespace)?;
+            commands::import_from::run(&config, &source, locale, dry_run)?;
+        }
+        Commands::Ingest { source, dry_run } => {
+            commands::ingest::run(&config, &source, dry_run)?;
+        }
+        Commands::ImportMissing { log, dry_run } => {
+            commands::import_missing::run(&config, &log, dry_run)?;
         }
         Commands::Sync {
             remove_unused,
             dry_run,
+            emit_plan,
         } => {
-            commands::sync::run(&config, remove_unused, dry_run)?;
+            commands::sync::run(&config, remove_unused, dry_run, emit_plan.as_deref())?;
+        }
+        Commands::Fmt { check } => {
+            commands::fmt::run(&config, check)?;
         }
         Commands::Lint {
             fail_on_error,
             watch,
+            stdin,
+            stdin_filepath,
+        } => {
+            if let Some(path) = stdin_filepath.filter(|_| stdin) {
+                ensure_stdin_is_free(cli.config_stdin)?;
+                commands::lint::run_stdin(&config, &path, fail_on_error)?;
+            } else {
+                commands::lint::run(&config, fail_on_error, watch)?;
+            }
+        }
+        Commands::ExtractFile { path, json } => {
+            commands::extract_file::run(&config, &path, json)?;
+        }
+        Commands::AddLocale {
+            locale,
+            copy_values,
+            dry_run,
+        } => {
+            commands::locale::add(
+                &config,
+                loaded_config.source_path.as_deref(),
+                &locale,
+                copy_values,
+                dry_run,
+            )?;
+        }
+        Commands::RemoveLocale { locale, dry_run } => {
+            commands::locale::remove(
+                &config,
+                loaded_config.source_path.as_deref(),
+                &locale,
+                dry_run,
+            )?;
+        }
+        Commands::SetValue {
+            locale,
+            key,
+            value,
+            dry_run,
+        } => {
+            commands::set_value::run(&config, &locale, &key, &value, dry_run)?;
+        }
+        Commands::Edit { key, dry_run } => {
+            commands::edit::run(&config, &key, dry_run)?;
+        }
+        Commands::Browse { emit_plan } => {
+            commands::browse::run(&config, emit_plan.as_deref())?;
+        }
+        Commands::Plan { command } => match command {
+            PlanCommands::Apply { file, dry_run } => {
+                commands::plan::apply(&config, &file, dry_run)?;
+            }
+            PlanCommands::Show { file } => {
+                commands::plan::show(&config, &file)?;
+            }
+        },
+        Commands::Diff {
+            dir_a,
+            dir_b,
+            format,
+        } => {
+            commands::diff::run(&config, &dir_a, &dir_b, &format)?;
+        }
+        Commands::RenameKey {
+            old_key,
+            new_key,
+            locales_only,
+            emit_plan: Some(path),
+            ..
         } => {
-            commands::lint::run(&config, fail_on_error, watch)?;
+            let change = Change::RenameKey {
+                from: old_key,
+                to: new_key,
+                locales_only,
+            };
+            ChangePlan::new(vec![change]).emit(&path, config.path_style)?;
         }
         Commands::RenameKey {
             old_key,
             new_key,
             dry_run,
             locales_only,
+            emit_plan: None,
         } => {
             commands::rename_key::run(&config, &old_key, &new_key, dry_run, locales_only)?;
         }
+        Commands::RenameNamespace {
+            old_namespace,
+            new_namespace,
+            locales_only,
+            emit_plan: Some(path),
+            ..
+        } => {
+            let change = Change::MoveNamespace {
+                from: old_namespace,
+                to: new_namespace,
+                locales_only,
+            };
+            ChangePlan::new(vec![change]).emit(&path, config.path_style)?;
+        }
+        Commands::RenameNamespace {
+            old_namespace,
+            new_namespace,
+            dry_run,
+            locales_only,
+            emit_plan: None,
+        } => {
+            commands::rename_namespace::run(
+                &config,
+                &old_namespace,
+                &new_namespace,
+                dry_run,
+                locales_only,
+            )?;
+        }
         Commands::Init {
             force,
             interactive,
@@ -452,6 +1349,28 @@ fn main() -> Result<()> {
                 matches!(loaded_config.source_kind, ConfigSourceKind::InlineJson),
             )?;
         }
+        Commands::MigratePlurals {
+            from,
+            dry_run,
+            emit_plan,
+        } => {
+            commands::migrate_plurals::run(&config, from, dry_run, emit_plan.as_deref())?;
+        }
+        Commands::Pull {
+            locale,
+            namespace,
+            only_missing,
+            dry_run,
+        } => {
+            commands::pull::run(&config, locale, namespace, only_missing, dry_run)?;
+        }
+        Commands::Push {
+            locale,
+            namespace,
+            dry_run,
+        } => {
+            commands::push::run(&config, locale, namespace, dry_run)?;
+        }
         Commands::Locize { command } => match command {
             LocizeCommands::Upload {
                 locale,
@@ -503,6 +1422,38 @@ fn main() -> Result<()> {
                 )?;
             }
         },
+        Commands::Hook {
+            staged,
+            files,
+            dry_run,
+        } => {
+            commands::hook::run(&config, staged, files, dry_run)?;
+        }
+        Commands::Config { command } => match command {
+            ConfigCommands::Show { resolved } => commands::config::show(
+                &config,
+                &file_config,
+                loaded_config.source_path.as_deref(),
+                &loaded_config.file_keys,
+                &overrides,
+                resolved,
+            )?,
+        },
+        Commands::Daemon { socket, command } => match command {
+            DaemonCommands::Start => commands::daemon::start(&config, socket)?,
+            DaemonCommands::Stop => commands::daemon::stop(socket)?,
+            DaemonCommands::Ping => commands::daemon::ping(socket)?,
+            DaemonCommands::ExtractChanged { files, dry_run } => {
+                commands::daemon::extract_changed(socket, files, dry_run)?;
+            }
+            DaemonCommands::Query { key } => commands::daemon::query(socket, key)?,
+            DaemonCommands::Lint {
+                file,
+                fail_on_error,
+            } => {
+                commands::daemon::lint(socket, file, fail_on_error)?;
+            }
+        },
     }
 
     Ok(())
@@ -511,7 +1462,10 @@ fn main() -> Result<()> {
 fn auto_detect_config_for_command(config: &mut Config, command: &Commands) {
     let should_detect = matches!(
         command,
-        Commands::Status { .. } | Commands::Lint { .. } | Commands::Check { .. }
+        Commands::Status { .. }
+            | Commands::Lint { .. }
+            | Commands::Check { .. }
+            | Commands::Fmt { .. }
     );
     if !should_detect {
         return;
@@ -708,6 +1662,53 @@ struct LoadedConfig {
     config: Config,
     source_kind: ConfigSourceKind,
     source_path: Option<PathBuf>,
+    /// Top-level keys written in the config file or inline JSON
+    file_keys: Vec<String>,
+}
+
+fn top_level_keys(json: &str) -> Vec<String> {
+    serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(json)
+        .map(|map| map.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// One watcher per config file, labelled with the config's directory. Each
+/// config's paths are resolved against that directory.
+fn workspace_watchers(
+    config_paths: &[PathBuf],
+    cli_overrides: &[String],
+) -> Result<Vec<FileWatcher>> {
+    let mut seen = std::collections::HashSet::new();
+    let mut watchers = Vec::new();
+    for path in config_paths {
+        if !seen.insert(path.canonicalize().unwrap_or_else(|_| path.clone())) {
+            continue;
+        }
+        let mut config = Config::load(path)?;
+        config.apply_overrides(|name| std::env::var(name).ok(), cli_overrides)?;
+        let dir = normalize_relative_path(path.parent().unwrap_or(Path::new(".")));
+        config.resolve_paths_from(Path::new(&dir));
+        let reload = ConfigReload::File {
+            path: path.clone(),
+            overrides: cli_overrides.to_vec(),
+            base_dir: Some(PathBuf::from(&dir)),
+        };
+        let label = if dir.is_empty() { ".".to_string() } else { dir };
+        watchers.push(
+            FileWatcher::new(config, None)
+                .with_label(label)
+                .with_config_reload(reload),
+        );
+    }
+    Ok(watchers)
+}
+
+/// `--stdin` source and `--config-stdin` cannot share stdin
+fn ensure_stdin_is_free(config_stdin: bool) -> Result<()> {
+    if config_stdin {
+        anyhow::bail!("--stdin cannot be combined with --config-stdin; pass the config with --config-json instead");
+    }
+    Ok(())
 }
 
 fn load_config(cli: &Cli) -> Result<LoadedConfig> {
@@ -721,6 +1722,7 @@ fn load_config(cli: &Cli) -> Result<LoadedConfig> {
             config,
             source_kind: ConfigSourceKind::InlineJson,
             source_path: cli.config_path_hint.clone(),
+            file_keys: top_level_keys(&stdin_content),
         });
     }
 
@@ -730,6 +1732,7 @@ fn load_config(cli: &Cli) -> Result<LoadedConfig> {
             config,
             source_kind: ConfigSourceKind::InlineJson,
             source_path: cli.config_path_hint.clone(),
+            file_keys: top_level_keys(config_json),
         });
     }
 
@@ -739,6 +1742,9 @@ fn load_config(cli: &Cli) -> Result<LoadedConfig> {
             config,
             source_kind: ConfigSourceKind::File,
             source_path: Some(config_path.clone()),
+            file_keys: fs::read_to_string(config_path)
+                .map(|content| top_level_keys(&content))
+                .unwrap_or_default(),
         });
     }
 
@@ -749,6 +1755,9 @@ fn load_config(cli: &Cli) -> Result<LoadedConfig> {
             config,
             source_kind: ConfigSourceKind::File,
             source_path: Some(default_path.to_path_buf()),
+            file_keys: fs::read_to_string(default_path)
+                .map(|content| top_level_keys(&content))
+                .unwrap_or_default(),
         });
     }
 
@@ -756,6 +1765,7 @@ fn load_config(cli: &Cli) -> Result<LoadedConfig> {
         config: Config::default(),
         source_kind: ConfigSourceKind::Default,
         source_path: None,
+        file_keys: Vec::new(),
     })
 }
 
@@ -818,6 +1828,7 @@ mod tests {
             locale: None,
             fail_on_incomplete: false,
             namespace: None,
+            min_completeness: None,
         };
         auto_detect_config_for_command(&mut config, &cmd);
 
@@ -848,6 +1859,23 @@ mod tests {
             ci: false,
             sync_primary: false,
             sync_all: false,
+            error_report: None,
+            conflict_report: None,
+            json_report: None,
+            filter_file: Vec::new(),
+            filter_key: Vec::new(),
+            metrics_file: None,
+            otlp_endpoint: None,
+            strict_duplicates: false,
+            update_defaults: false,
+            clear_stale: false,
+            explain: None,
+            verify_determinism: false,
+            production: false,
+            lint: false,
+            diagnostic: vec![],
+            stdin: false,
+            stdin_filepath: None,
         };
         auto_detect_config_for_command(&mut config, &cmd);
 
@@ -882,6 +1910,18 @@ mod tests {
             remove: false,
             dry_run: true,
             locale: None,
+            write_config: false,
+            filter_file: Vec::new(),
+            filter_key: Vec::new(),
+            values: false,
+            nesting: false,
+            spacing: false,
+            key_case: false,
+            fix: false,
+            dead_for: None,
+            fix_plurals: false,
+            emit_plan: None,
+            all_locales: false,
         };
         auto_detect_config_for_command(&mut config, &cmd);
         assert_eq!(config.output, "public/locales");
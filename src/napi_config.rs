@@ -0,0 +1,610 @@
+//! Node-API mirrors of the config types. JavaScript objects cannot carry the
+//! serde enums of [`Config`], so options arrive as plain strings and numbers
+//! and are converted (and validated) here.
+
+#![allow(non_snake_case)]
+
+use anyhow::{bail, Result};
+use napi::bindgen_prelude::Either;
+use napi_derive::napi;
+use std::collections::BTreeMap;
+
+use crate::config::{
+    ConcurrentRuns, Config, ContextPluralOrder, DiagnosticSeverity, EmailTemplatesConfig,
+    EnableSelector, EndOfLine, Indentation, KeyCase, KeySort, KeyTransform, LocizeConfig,
+    MergedNamespaceLayout, OutputFormat, OutputTarget, PathStyle, PluralCompat, SeverityRule,
+    SingleCategoryPlural, TmsConfig, TmsProvider, TransComponent, TransComponentDetails,
+    TypesConfig, UseTranslationName,
+};
+
+#[napi(object)]
+#[allow(non_snake_case)]
+pub struct NapiConfig {
+    pub preset: Option<String>,
+    pub input: Option<Vec<String>>,
+    pub output: Option<String>,
+    pub outputFormat: Option<String>,
+    pub flatLocaleFiles: Option<bool>,
+    pub locales: Option<Vec<String>>,
+    pub defaultNamespace: Option<String>,
+    pub functions: Option<Vec<String>>,
+    pub useTranslationNames: Option<Vec<String>>,
+    pub messageFactoryFunctions: Option<Vec<String>>,
+    pub keyProducingFunctions: Option<Vec<String>>,
+    pub keySeparator: Option<String>,
+    pub nsSeparator: Option<String>,
+    pub contextSeparator: Option<String>,
+    pub pluralSeparator: Option<String>,
+    pub contextPluralOrder: Option<String>,
+    pub pluralSuffixes: Option<Vec<String>>,
+    pub disablePlurals: Option<bool>,
+    pub generateBasePluralForms: Option<bool>,
+    pub singleCategoryPlurals: Option<std::collections::HashMap<String, String>>,
+    pub extractFromComments: Option<bool>,
+    pub dynamicKeySeverity: Option<String>,
+    pub dynamicKeyRules: Option<Vec<NapiSeverityRule>>,
+    pub useLocalePluralRules: Option<bool>,
+    pub pluralCompat: Option<String>,
+    pub ignore: Option<Vec<String>>,
+    pub generatedFiles: Option<Vec<String>>,
+    pub preservePatterns: Option<Vec<String>>,
+    pub preserveContextVariants: Option<bool>,
+    pub removeUnusedKeys: Option<bool>,
+    pub mergeNamespaces: Option<bool>,
+    pub mergedNamespaceFilename: Option<String>,
+    pub mergedNamespaceLayout: Option<String>,
+    pub mergedNestingDepth: Option<u32>,
+    pub defaultValue: Option<String>,
+    /// Component names, or `{ name, keyAttr, nsAttr, defaultAttr }` objects
+    pub transComponents: Option<Vec<Either<String, NapiTransComponent>>>,
+    pub transKeepBasicHtmlNodesFor: Option<Vec<String>>,
+    pub transUnescape: Option<bool>,
+    pub transUnescapeKeys: Option<bool>,
+    pub nestingPrefix: Option<String>,
+    pub nestingSuffix: Option<String>,
+    pub nestingOptionsSeparator: Option<String>,
+    pub interpolationPrefix: Option<String>,
+    pub interpolationSuffix: Option<String>,
+    pub allowedFormatters: Option<Vec<String>>,
+    pub types: Option<NapiTypesConfig>,
+    pub locize: Option<NapiLocizeConfig>,
+    pub tms: Option<NapiTmsConfig>,
+    pub primaryLanguage: Option<String>,
+    pub secondaryLanguages: Option<Vec<String>>,
+    /// Indentation: number (spaces) or string (e.g., "\t")
+    pub indentation: Option<NapiIndentation>,
+    pub minify: Option<bool>,
+    pub keySort: Option<String>,
+    pub endOfLine: Option<String>,
+    pub followSymlinks: Option<bool>,
+    pub pathStyle: Option<String>,
+    pub shardMaxKeys: Option<u32>,
+    pub removeEmptyFiles: Option<bool>,
+    pub concurrentRuns: Option<String>,
+    pub logLevel: Option<String>,
+    pub budgets: Option<std::collections::HashMap<String, u32>>,
+    pub failOnBudgetExceeded: Option<bool>,
+    pub strictNamespaces: Option<bool>,
+    pub releaseGates: Option<std::collections::HashMap<String, f64>>,
+    pub metadataFile: Option<String>,
+    pub removalGraceDays: Option<u32>,
+    pub outputs: Option<Vec<NapiOutputTarget>>,
+    pub keyTransforms: Option<Vec<NapiKeyTransform>>,
+    pub keyMapFile: Option<String>,
+    pub eslintDataFile: Option<String>,
+    pub publishDir: Option<String>,
+    pub keyCase: Option<String>,
+    pub emailTemplates: Option<NapiEmailTemplatesConfig>,
+}
+
+/// NAPI-compatible `keyTransforms` entry; exactly one field must be set
+#[napi(object)]
+pub struct NapiKeyTransform {
+    pub stripPrefix: Option<String>,
+    pub replacePrefix: Option<NapiReplacePrefix>,
+    pub lowercaseFirstSegment: Option<bool>,
+}
+
+#[napi(object)]
+pub struct NapiReplacePrefix {
+    pub from: String,
+    pub to: String,
+}
+
+impl TryFrom<NapiKeyTransform> for KeyTransform {
+    type Error = anyhow::Error;
+
+    fn try_from(value: NapiKeyTransform) -> Result<Self> {
+        match (
+            value.stripPrefix,
+            value.replacePrefix,
+            value.lowercaseFirstSegment.unwrap_or(false),
+        ) {
+            (Some(prefix), None, false) => Ok(KeyTransform::StripPrefix(prefix)),
+            (None, Some(replace), false) => Ok(KeyTransform::ReplacePrefix {
+                from: replace.from,
+                to: replace.to,
+            }),
+            (None, None, true) => Ok(KeyTransform::LowercaseFirstSegment),
+            _ => bail!(
+                "Configuration error: each 'keyTransforms' entry must set exactly one of stripPrefix, replacePrefix or lowercaseFirstSegment."
+            ),
+        }
+    }
+}
+
+/// NAPI-compatible `transComponents` entry; unset props keep the `Trans` names
+#[napi(object)]
+pub struct NapiTransComponent {
+    pub name: String,
+    pub keyAttr: Option<String>,
+    pub nsAttr: Option<String>,
+    pub defaultAttr: Option<String>,
+}
+
+impl From<NapiTransComponent> for TransComponent {
+    fn from(value: NapiTransComponent) -> Self {
+        let mut details = TransComponentDetails::new(value.name);
+        if let Some(key_attr) = value.keyAttr {
+            details.key_attr = key_attr;
+        }
+        if let Some(ns_attr) = value.nsAttr {
+            details.ns_attr = ns_attr;
+        }
+        if let Some(default_attr) = value.defaultAttr {
+            details.default_attr = default_attr;
+        }
+        TransComponent::Detailed(details)
+    }
+}
+
+/// NAPI-compatible `emailTemplates`; `keySeparator: ""` keeps keys flat
+#[napi(object)]
+pub struct NapiEmailTemplatesConfig {
+    pub input: Vec<String>,
+    pub ignore: Option<Vec<String>>,
+    pub functions: Option<Vec<String>>,
+    pub output: String,
+    pub outputFormat: Option<String>,
+    pub keySeparator: Option<String>,
+    pub defaultNamespace: Option<String>,
+    pub locales: Option<Vec<String>>,
+}
+
+impl TryFrom<NapiEmailTemplatesConfig> for EmailTemplatesConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(value: NapiEmailTemplatesConfig) -> Result<Self> {
+        Ok(Self {
+            input: value.input,
+            ignore: value.ignore.unwrap_or_default(),
+            functions: value
+                .functions
+                .unwrap_or_else(|| vec!["tEmail".to_string()]),
+            output: value.output,
+            output_format: value
+                .outputFormat
+                .as_deref()
+                .map(OutputFormat::parse_str)
+                .transpose()?
+                .unwrap_or_default(),
+            key_separator: value.keySeparator.unwrap_or_default(),
+            default_namespace: value
+                .defaultNamespace
+                .unwrap_or_else(|| "email".to_string()),
+            locales: value.locales,
+        })
+    }
+}
+
+/// NAPI-compatible `tms`; `provider` is `locize`, `crowdin` or `phrase`
+#[napi(object)]
+pub struct NapiTmsConfig {
+    pub provider: String,
+    pub projectId: String,
+    pub apiKey: Option<String>,
+    pub baseUrl: Option<String>,
+    pub version: Option<String>,
+    pub namespaces: Option<Vec<String>>,
+}
+
+impl TryFrom<NapiTmsConfig> for TmsConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(value: NapiTmsConfig) -> Result<Self> {
+        Ok(Self {
+            provider: TmsProvider::parse_str(&value.provider)?,
+            project_id: value.projectId,
+            api_key: value.apiKey,
+            base_url: value.baseUrl,
+            version: value.version,
+            namespaces: value.namespaces,
+        })
+    }
+}
+
+/// `severity` is `off`, `warn` or `error`
+#[napi(object)]
+pub struct NapiSeverityRule {
+    #[napi(js_name = "match")]
+    pub pattern: String,
+    pub severity: String,
+}
+
+#[napi(object)]
+pub struct NapiOutputTarget {
+    #[napi(js_name = "match")]
+    pub pattern: String,
+    pub output: String,
+}
+
+/// NAPI-compatible indentation type
+/// Can be either a number (spaces) or a string (custom indentation)
+#[napi(object)]
+pub struct NapiIndentation {
+    /// Number of spaces (mutually exclusive with `custom`)
+    pub spaces: Option<u32>,
+    /// Custom indentation string (mutually exclusive with `spaces`)
+    pub custom: Option<String>,
+}
+
+impl From<NapiIndentation> for Indentation {
+    fn from(value: NapiIndentation) -> Self {
+        if let Some(spaces) = value.spaces {
+            Indentation::Spaces(spaces as usize)
+        } else if let Some(custom) = value.custom {
+            Indentation::Custom(custom)
+        } else {
+            Indentation::Spaces(2) // default
+        }
+    }
+}
+
+/// Options from JavaScript, with unset fields taken from the `preset` or the
+/// defaults of `i18next-turbo.json`
+impl TryFrom<NapiConfig> for Config {
+    type Error = anyhow::Error;
+
+    fn try_from(config: NapiConfig) -> Result<Self> {
+        let defaults = match &config.preset {
+            Some(preset) => {
+                Config::from_json_string(&serde_json::json!({ "preset": preset }).to_string())?
+            }
+            None => Config::default(),
+        };
+        let config = Config {
+            preset: defaults.preset,
+            input: config.input.unwrap_or_else(|| defaults.input.clone()),
+            output: config.output.unwrap_or_else(|| defaults.output.clone()),
+            outputs: config
+                .outputs
+                .map(|targets| {
+                    targets
+                        .into_iter()
+                        .map(|target| OutputTarget {
+                            pattern: target.pattern,
+                            output: target.output,
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            output_format: config
+                .outputFormat
+                .as_deref()
+                .map(OutputFormat::parse_str)
+                .transpose()?
+                .unwrap_or(defaults.output_format),
+            flat_locale_files: config.flatLocaleFiles.unwrap_or(defaults.flat_locale_files),
+            locales: config.locales.unwrap_or_else(|| defaults.locales.clone()),
+            default_namespace: config
+                .defaultNamespace
+                .unwrap_or_else(|| defaults.default_namespace.clone()),
+            functions: config
+                .functions
+                .unwrap_or_else(|| defaults.functions.clone()),
+            use_translation_names: config
+                .useTranslationNames
+                .map(|names| names.into_iter().map(UseTranslationName::Name).collect())
+                .unwrap_or_else(|| defaults.use_translation_names.clone()),
+            message_factory_functions: config
+                .messageFactoryFunctions
+                .unwrap_or_else(|| defaults.message_factory_functions.clone()),
+            key_producing_functions: config
+                .keyProducingFunctions
+                .unwrap_or_else(|| defaults.key_producing_functions.clone()),
+            key_separator: config
+                .keySeparator
+                .unwrap_or_else(|| defaults.key_separator.clone()),
+            ns_separator: config
+                .nsSeparator
+                .unwrap_or_else(|| defaults.ns_separator.clone()),
+            context_separator: config
+                .contextSeparator
+                .unwrap_or_else(|| defaults.context_separator.clone()),
+            plural_separator: config
+                .pluralSeparator
+                .unwrap_or_else(|| defaults.plural_separator.clone()),
+            context_plural_order: config
+                .contextPluralOrder
+                .as_deref()
+                .map(ContextPluralOrder::parse_str)
+                .transpose()?
+                .unwrap_or(defaults.context_plural_order),
+            plural_suffixes: config
+                .pluralSuffixes
+                .unwrap_or_else(|| defaults.plural_suffixes.clone()),
+            disable_plurals: config.disablePlurals.unwrap_or(false),
+            generate_base_plural_forms: config.generateBasePluralForms.unwrap_or(false),
+            single_category_plurals: config
+                .singleCategoryPlurals
+                .map(|modes| {
+                    modes
+                        .into_iter()
+                        .map(|(locale, mode)| Ok((locale, SingleCategoryPlural::parse_str(&mode)?)))
+                        .collect::<Result<BTreeMap<_, _>>>()
+                })
+                .transpose()?
+                .unwrap_or_default(),
+            extract_from_comments: config
+                .extractFromComments
+                .unwrap_or(defaults.extract_from_comments),
+            dynamic_key_severity: config
+                .dynamicKeySeverity
+                .as_deref()
+                .map(DiagnosticSeverity::parse_str)
+                .transpose()?
+                .unwrap_or(defaults.dynamic_key_severity),
+            dynamic_key_rules: config
+                .dynamicKeyRules
+                .map(|rules| {
+                    rules
+                        .into_iter()
+                        .map(|rule| {
+                            Ok(SeverityRule {
+                                pattern: rule.pattern,
+                                severity: DiagnosticSeverity::parse_str(&rule.severity)?,
+                            })
+                        })
+                        .collect::<Result<Vec<_>>>()
+                })
+                .transpose()?
+                .unwrap_or_default(),
+            use_locale_plural_rules: config
+                .useLocalePluralRules
+                .unwrap_or(defaults.use_locale_plural_rules),
+            plural_compat: config
+                .pluralCompat
+                .as_deref()
+                .map(PluralCompat::parse_str)
+                .transpose()?
+                .unwrap_or(defaults.plural_compat),
+            ignore: config.ignore.unwrap_or_else(|| defaults.ignore.clone()),
+            generated_files: config
+                .generatedFiles
+                .unwrap_or_else(|| defaults.generated_files.clone()),
+            preserve_patterns: config
+                .preservePatterns
+                .unwrap_or_else(|| defaults.preserve_patterns.clone()),
+            key_filter: defaults.key_filter.clone(),
+            preserve_context_variants: config
+                .preserveContextVariants
+                .unwrap_or(defaults.preserve_context_variants),
+            remove_unused_keys: config
+                .removeUnusedKeys
+                .unwrap_or(defaults.remove_unused_keys),
+            merge_namespaces: config.mergeNamespaces.unwrap_or(defaults.merge_namespaces),
+            merged_namespace_filename: config
+                .mergedNamespaceFilename
+                .or_else(|| defaults.merged_namespace_filename.clone()),
+            merged_namespace_layout: config
+                .mergedNamespaceLayout
+                .as_deref()
+                .map(MergedNamespaceLayout::parse_str)
+                .transpose()?
+                .unwrap_or(defaults.merged_namespace_layout),
+            merged_nesting_depth: config
+                .mergedNestingDepth
+                .map(|depth| depth as usize)
+                .or(defaults.merged_nesting_depth),
+            default_value: config
+                .defaultValue
+                .or_else(|| defaults.default_value.clone()),
+            trans_components: config
+                .transComponents
+                .map(|components| {
+                    components
+                        .into_iter()
+                        .map(|component| match component {
+                            Either::A(name) => TransComponent::Name(name),
+                            Either::B(details) => details.into(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_else(|| defaults.trans_components.clone()),
+            trans_keep_basic_html_nodes_for: config
+                .transKeepBasicHtmlNodesFor
+                .unwrap_or_else(|| defaults.trans_keep_basic_html_nodes_for.clone()),
+            trans_unescape: config.transUnescape.unwrap_or(defaults.trans_unescape),
+            trans_unescape_keys: config
+                .transUnescapeKeys
+                .unwrap_or(defaults.trans_unescape_keys),
+            nesting_prefix: config
+                .nestingPrefix
+                .unwrap_or_else(|| defaults.nesting_prefix.clone()),
+            nesting_suffix: config
+                .nestingSuffix
+                .unwrap_or_else(|| defaults.nesting_suffix.clone()),
+            nesting_options_separator: config
+                .nestingOptionsSeparator
+                .unwrap_or_else(|| defaults.nesting_options_separator.clone()),
+            interpolation_prefix: config
+                .interpolationPrefix
+                .unwrap_or_else(|| defaults.interpolation_prefix.clone()),
+            interpolation_suffix: config
+                .interpolationSuffix
+                .unwrap_or_else(|| defaults.interpolation_suffix.clone()),
+            allowed_formatters: config.allowedFormatters,
+            types: config.types.map(TypesConfig::from).unwrap_or_default(),
+            locize: config.locize.and_then(|locize_cfg| {
+                locize_cfg.projectId.map(|project_id| LocizeConfig {
+                    project_id,
+                    api_key: locize_cfg.apiKey,
+                    version: locize_cfg.version,
+                    source_language: locize_cfg.sourceLanguage,
+                    namespaces: locize_cfg.namespaces,
+                    update_values: locize_cfg.updateValues,
+                    source_language_only: locize_cfg.sourceLanguageOnly,
+                    compare_modification_time: locize_cfg.compareModificationTime,
+                    cdn_type: locize_cfg.cdnType,
+                    dry_run: locize_cfg.dryRun,
+                })
+            }),
+            tms: config.tms.map(TmsConfig::try_from).transpose()?,
+            primary_language: config.primaryLanguage,
+            secondary_languages: config.secondaryLanguages,
+            indentation: config.indentation.map(Indentation::from),
+            minify: config.minify.unwrap_or(defaults.minify),
+            key_sort: config
+                .keySort
+                .as_deref()
+                .map(KeySort::parse_str)
+                .transpose()?
+                .unwrap_or(defaults.key_sort),
+            end_of_line: config
+                .endOfLine
+                .as_deref()
+                .map(EndOfLine::parse_str)
+                .transpose()?
+                .unwrap_or(defaults.end_of_line),
+            follow_symlinks: config.followSymlinks.unwrap_or(defaults.follow_symlinks),
+            path_style: config
+                .pathStyle
+                .as_deref()
+                .map(PathStyle::parse_str)
+                .transpose()?
+                .unwrap_or(defaults.path_style),
+            shard_max_keys: config
+                .shardMaxKeys
+                .map(|max| max as usize)
+                .or(defaults.shard_max_keys),
+            remove_empty_files: config
+                .removeEmptyFiles
+                .unwrap_or(defaults.remove_empty_files),
+            concurrent_runs: config
+                .concurrentRuns
+                .as_deref()
+                .map(ConcurrentRuns::parse_str)
+                .transpose()?
+                .unwrap_or(defaults.concurrent_runs),
+            lint: defaults.lint.clone(),
+            log_level: config
+                .logLevel
+                .unwrap_or_else(|| defaults.log_level.clone()),
+            budgets: config
+                .budgets
+                .map(|budgets| {
+                    budgets
+                        .into_iter()
+                        .map(|(ns, budget)| (ns, budget as usize))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            fail_on_budget_exceeded: config
+                .failOnBudgetExceeded
+                .unwrap_or(defaults.fail_on_budget_exceeded),
+            strict_namespaces: config
+                .strictNamespaces
+                .unwrap_or(defaults.strict_namespaces),
+            release_gates: config
+                .releaseGates
+                .map(|gates| gates.into_iter().collect())
+                .unwrap_or_default(),
+            metadata_file: config
+                .metadataFile
+                .or_else(|| defaults.metadata_file.clone()),
+            removal_grace_days: config.removalGraceDays.or(defaults.removal_grace_days),
+            key_transforms: config
+                .keyTransforms
+                .map(|transforms| {
+                    transforms
+                        .into_iter()
+                        .map(KeyTransform::try_from)
+                        .collect::<Result<Vec<_>>>()
+                })
+                .transpose()?
+                .unwrap_or_else(|| defaults.key_transforms.clone()),
+            key_map_file: config.keyMapFile.or_else(|| defaults.key_map_file.clone()),
+            eslint_data_file: config
+                .eslintDataFile
+                .or_else(|| defaults.eslint_data_file.clone()),
+            publish_dir: config.publishDir.or_else(|| defaults.publish_dir.clone()),
+            key_case: config
+                .keyCase
+                .as_deref()
+                .map(KeyCase::parse_str)
+                .transpose()?
+                .or(defaults.key_case),
+            email_templates: config
+                .emailTemplates
+                .map(EmailTemplatesConfig::try_from)
+                .transpose()?,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+#[napi(object)]
+pub struct NapiTypesConfig {
+    pub input: Option<Vec<String>>,
+    pub output: Option<String>,
+    pub resourcesFile: Option<String>,
+    pub enableSelector: Option<String>,
+    pub defaultLocale: Option<String>,
+    pub localesDir: Option<String>,
+    pub indentation: Option<NapiIndentation>,
+    pub includeNamespaces: Option<Vec<String>>,
+    pub excludeNamespaces: Option<Vec<String>>,
+    pub outputDir: Option<String>,
+    pub constantsDir: Option<String>,
+}
+
+#[napi(object)]
+pub struct NapiLocizeConfig {
+    pub projectId: Option<String>,
+    pub apiKey: Option<String>,
+    pub version: Option<String>,
+    pub sourceLanguage: Option<String>,
+    pub namespaces: Option<Vec<String>>,
+    pub updateValues: Option<bool>,
+    pub sourceLanguageOnly: Option<bool>,
+    pub compareModificationTime: Option<bool>,
+    pub cdnType: Option<String>,
+    pub dryRun: Option<bool>,
+}
+
+impl From<NapiTypesConfig> for TypesConfig {
+    fn from(value: NapiTypesConfig) -> Self {
+        Self {
+            input: value.input,
+            output: value.output,
+            resources_file: value.resourcesFile,
+            enable_selector: value.enableSelector.and_then(|raw| {
+                let normalized = raw.trim().to_lowercase();
+                match normalized.as_str() {
+                    "true" => Some(EnableSelector::Bool(true)),
+                    "false" => Some(EnableSelector::Bool(false)),
+                    "optimize" => Some(EnableSelector::Mode("optimize".to_string())),
+                    _ => None,
+                }
+            }),
+            default_locale: value.defaultLocale,
+            locales_dir: value.localesDir,
+            indentation: value.indentation.map(Indentation::from),
+            include_namespaces: value.includeNamespaces,
+            exclude_namespaces: value.excludeNamespaces,
+            output_dir: value.outputDir,
+            constants_dir: value.constantsDir,
+        }
+    }
+}
@@ -0,0 +1,447 @@
+//! Declarative change plans: bulk locale edits written as JSON by destructive
+//! commands (`--emit-plan`) so they can be reviewed, then run with `plan apply`.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::commands::set_value::split_key;
+use crate::commands::{rename_key, rename_namespace};
+use crate::config::{Config, OutputFormat, PathStyle};
+use crate::json_sync;
+use crate::paths;
+
+/// Plan format version written to `version`
+pub const PLAN_VERSION: u32 = 1;
+
+/// One change of a plan. Keys are written as `namespace:key` (with the
+/// configured `nsSeparator`); a key without a namespace uses the default
+/// namespace.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum Change {
+    /// Rename a key in locale files and (unless `localesOnly`) source files
+    RenameKey {
+        from: String,
+        to: String,
+        #[serde(default, skip_serializing_if = "is_false")]
+        locales_only: bool,
+    },
+    /// Remove a key from the locale files of `locales` (every locale when empty)
+    DeleteKey {
+        key: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        locales: Vec<String>,
+    },
+    /// Move every key of a namespace to another namespace
+    MoveNamespace {
+        from: String,
+        to: String,
+        #[serde(default, skip_serializing_if = "is_false")]
+        locales_only: bool,
+    },
+    /// Set the value of a key in one locale
+    SetValue {
+        locale: String,
+        key: String,
+        value: Value,
+    },
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+/// `{ "version": 1, "changes": [...] }`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangePlan {
+    pub version: u32,
+    pub changes: Vec<Change>,
+}
+
+impl ChangePlan {
+    pub fn new(changes: Vec<Change>) -> Self {
+        Self {
+            version: PLAN_VERSION,
+            changes,
+        }
+    }
+
+    pub fn read(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read plan: {}", path.display()))?;
+        let plan: Self = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid plan: {}", path.display()))?;
+        if plan.version != PLAN_VERSION {
+            bail!(
+                "Unsupported plan version {} in {} (expected {})",
+                plan.version,
+                path.display(),
+                PLAN_VERSION
+            );
+        }
+        Ok(plan)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)? + "\n")
+            .with_context(|| format!("Failed to write plan: {}", path.display()))
+    }
+
+    /// Write the plan instead of changing files, as done by `--emit-plan`
+    pub fn emit(&self, path: &Path, path_style: PathStyle) -> Result<()> {
+        self.write(path)?;
+        println!(
+            "\nWrote plan with {} change(s) to {}. Review it, then run: i18next-turbo plan apply {}",
+            self.changes.len(),
+            paths::display(path, path_style),
+            paths::display(path, path_style)
+        );
+        Ok(())
+    }
+}
+
+/// One-line description of a change
+pub fn describe(change: &Change) -> String {
+    match change {
+        Change::RenameKey { from, to, .. } => format!("rename key {} -> {}", from, to),
+        Change::DeleteKey { key, locales } if locales.is_empty() => format!("delete key {}", key),
+        Change::DeleteKey { key, locales } => {
+            format!("delete key {} ({})", key, locales.join(", "))
+        }
+        Change::MoveNamespace { from, to, .. } => format!("move namespace {} -> {}", from, to),
+        Change::SetValue { locale, key, value } => {
+            format!("set {} [{}] = {}", key, locale, value)
+        }
+    }
+}
+
+/// Changes turning the leaf values `before` into `after` in one locale:
+/// `setValue` for added and changed keys, then `deleteKey` for dropped ones.
+/// Both maps are keyed by `(namespace, key)`, as from
+/// [`json_sync::flatten_leaf_values`].
+pub fn value_changes(
+    config: &Config,
+    locale: &str,
+    before: &BTreeMap<(String, String), Value>,
+    after: &BTreeMap<(String, String), Value>,
+) -> Vec<Change> {
+    let plan_key = |(namespace, key): &(String, String)| {
+        format!("{}{}{}", namespace, config.ns_separator, key)
+    };
+    let set = after
+        .iter()
+        .filter(|(id, value)| before.get(*id) != Some(*value))
+        .map(|(id, value)| Change::SetValue {
+            locale: locale.to_string(),
+            key: plan_key(id),
+            value: value.clone(),
+        });
+    let delete = before
+        .keys()
+        .filter(|id| !after.contains_key(*id))
+        .map(|id| Change::DeleteKey {
+            key: plan_key(id),
+            locales: vec![locale.to_string()],
+        });
+    set.chain(delete).collect()
+}
+
+/// Apply `changes` in order
+pub fn apply(config: &Config, changes: &[Change], dry_run: bool) -> Result<()> {
+    for (i, change) in changes.iter().enumerate() {
+        println!("\n[{}/{}] {}", i + 1, changes.len(), describe(change));
+        match change {
+            Change::RenameKey {
+                from,
+                to,
+                locales_only,
+            } => rename_key::run(config, from, to, dry_run, *locales_only)?,
+            Change::MoveNamespace {
+                from,
+                to,
+                locales_only,
+            } => rename_namespace::run(config, from, to, dry_run, *locales_only)?,
+            Change::DeleteKey { key, locales } => {
+                let (namespace, key) = split_key(config, key);
+                let locales = if locales.is_empty() {
+                    &config.locales
+                } else {
+                    locales
+                };
+                let changed = delete_key(config, locales, &namespace, &key, dry_run)?;
+                println!(
+                    "  {} {} locale file(s)",
+                    if dry_run { "Would update" } else { "Updated" },
+                    changed
+                );
+            }
+            Change::SetValue { locale, key, value } => {
+                let (namespace, key) = split_key(config, key);
+                edit_locale_file(config, locale, &namespace, &key, Some(value), dry_run)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Set `key` of `namespace` to `value` in `locale`, keeping the file's
+/// formatting. Returns whether the file changed.
+pub(crate) fn set_value(
+    config: &Config,
+    locale: &str,
+    namespace: &str,
+    key: &str,
+    value: &Value,
+    dry_run: bool,
+) -> Result<bool> {
+    edit_locale_file(config, locale, namespace, key, Some(value), dry_run)
+}
+
+/// Remove `key` from the `namespace` file of each of `locales`, returning how
+/// many files changed
+pub(crate) fn delete_key(
+    config: &Config,
+    locales: &[String],
+    namespace: &str,
+    key: &str,
+    dry_run: bool,
+) -> Result<usize> {
+    let mut changed = 0;
+    for locale in locales {
+        if edit_locale_file(config, locale, namespace, key, None, dry_run)? {
+            changed += 1;
+        }
+    }
+    Ok(changed)
+}
+
+/// Set (`Some`) or remove (`None`) `key` in the locale file holding `namespace`.
+/// Returns whether the file changed.
+fn edit_locale_file(
+    config: &Config,
+    locale: &str,
+    namespace: &str,
+    key: &str,
+    value: Option<&Value>,
+    dry_run: bool,
+) -> Result<bool> {
+    json_sync::ensure_plain_path_names(locale, namespace)?;
+    let parts = json_sync::key_parts(config, namespace, key);
+    let path = json_sync::locale_namespace_file_path(config, &config.output, locale, namespace);
+    let format = config.output_format();
+    let (mut root, content) = if path.is_file() {
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read: {}", path.display()))?;
+        match json_sync::parse_locale_value_str(&content, format, &path)? {
+            Value::Object(map) => (map, content),
+            _ => bail!("Locale file is not an object: {}", path.display()),
+        }
+    } else if value.is_some() {
+        (Map::new(), String::new())
+    } else {
+        return Ok(false);
+    };
+
+    let changed = match value {
+        Some(value) => set_path(&mut root, &parts, value.clone())?,
+        None => remove_path(&mut root, &parts),
+    };
+    if changed && !dry_run {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        let sorted = json_sync::sort_keys_alphabetically(&root);
+        let style = (format == OutputFormat::Json)
+            .then(|| json_sync::configured_json_style(config, &content));
+        json_sync::write_locale_file(&path, &sorted, format, config.key_sort, style.as_ref())?;
+    }
+    Ok(changed)
+}
+
+/// Set the value at `parts`, creating parent objects. Fails when a parent is
+/// already a value.
+fn set_path(object: &mut Map<String, Value>, parts: &[String], value: Value) -> Result<bool> {
+    match parts {
+        [] => Ok(false),
+        [last] => Ok(object.insert(last.clone(), value.clone()).as_ref() != Some(&value)),
+        [first, rest @ ..] => {
+            let child = object
+                .entry(first.clone())
+                .or_insert_with(|| Value::Object(Map::new()));
+            match child {
+                Value::Object(child) => set_path(child, rest, value),
+                _ => bail!(
+                    "Cannot set a nested key under '{}': it holds a value",
+                    first
+                ),
+            }
+        }
+    }
+}
+
+/// Remove the value at `parts`, dropping parent objects left empty
+fn remove_path(object: &mut Map<String, Value>, parts: &[String]) -> bool {
+    match parts {
+        [] => false,
+        [last] => object.remove(last).is_some(),
+        [first, rest @ ..] => {
+            let Some(Value::Object(child)) = object.get_mut(first) else {
+                return false;
+            };
+            let removed = remove_path(child, rest);
+            if removed && child.is_empty() {
+                object.remove(first);
+            }
+            removed
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    #[test]
+    fn plan_round_trips_and_applies_deletes_and_value_edits() {
+        let plan: ChangePlan = serde_json::from_value(json!({
+            "version": 1,
+            "changes": [
+                { "op": "deleteKey", "key": "home.old" },
+                { "op": "deleteKey", "key": "common:legacy", "locales": ["de"] },
+                { "op": "setValue", "locale": "de", "key": "home.title", "value": "Startseite" },
+                { "op": "renameKey", "from": "a", "to": "b", "localesOnly": true },
+                { "op": "moveNamespace", "from": "billing", "to": "payments" }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(
+            plan.changes[3],
+            Change::RenameKey {
+                from: "a".to_string(),
+                to: "b".to_string(),
+                locales_only: true
+            }
+        );
+        let written = serde_json::to_value(&plan).unwrap();
+        assert_eq!(
+            written["changes"][0],
+            json!({ "op": "deleteKey", "key": "home.old" })
+        );
+        assert_eq!(describe(&plan.changes[1]), "delete key common:legacy (de)");
+
+        let tmp = tempdir().unwrap();
+        let output = tmp.path().join("locales");
+        for locale in ["en", "de"] {
+            std::fs::create_dir_all(output.join(locale)).unwrap();
+            std::fs::write(
+                output.join(locale).join("translation.json"),
+                r#"{"home":{"old":"x","title":"Home"}}"#,
+            )
+            .unwrap();
+            std::fs::write(
+                output.join(locale).join("common.json"),
+                r#"{"legacy":"y","save":"Save"}"#,
+            )
+            .unwrap();
+        }
+        let config = Config {
+            output: output.to_string_lossy().to_string(),
+            locales: vec!["en".to_string(), "de".to_string()],
+            ..Config::default()
+        };
+
+        apply(&config, &plan.changes[..3], true).unwrap();
+        let read = |file: &str| -> Value {
+            serde_json::from_str(&std::fs::read_to_string(output.join(file)).unwrap()).unwrap()
+        };
+        assert_eq!(read("de/translation.json")["home"]["old"], "x");
+
+        apply(&config, &plan.changes[..3], false).unwrap();
+        assert_eq!(
+            read("en/translation.json"),
+            json!({ "home": { "title": "Home" } })
+        );
+        assert_eq!(
+            read("de/translation.json"),
+            json!({ "home": { "title": "Startseite" } })
+        );
+        assert_eq!(
+            read("en/common.json"),
+            json!({ "legacy": "y", "save": "Save" })
+        );
+        assert_eq!(read("de/common.json"), json!({ "save": "Save" }));
+    }
+
+    #[test]
+    fn delete_key_removes_key_and_empty_parents_in_every_locale() {
+        let tmp = tempdir().unwrap();
+        let output = tmp.path().join("locales");
+        for locale in ["en", "de"] {
+            std::fs::create_dir_all(output.join(locale)).unwrap();
+            std::fs::write(
+                output.join(locale).join("translation.json"),
+                r#"{"home":{"old":"x"},"title":"y"}"#,
+            )
+            .unwrap();
+        }
+        let config = Config {
+            output: output.to_string_lossy().to_string(),
+            locales: vec!["en".to_string(), "de".to_string(), "fr".to_string()],
+            ..Config::default()
+        };
+
+        assert_eq!(
+            delete_key(&config, &config.locales, "translation", "home.old", false).unwrap(),
+            2
+        );
+        let written: Value = serde_json::from_str(
+            &std::fs::read_to_string(output.join("de/translation.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(written, json!({ "title": "y" }));
+    }
+
+    #[test]
+    fn keys_follow_the_configured_ns_separator() {
+        let tmp = tempdir().unwrap();
+        let output = tmp.path().join("locales");
+        std::fs::create_dir_all(output.join("en")).unwrap();
+        std::fs::write(output.join("en/common.json"), r#"{"a:b":"x"}"#).unwrap();
+        let config = Config {
+            output: output.to_string_lossy().to_string(),
+            locales: vec!["en".to_string()],
+            ns_separator: "::".to_string(),
+            ..Config::default()
+        };
+        let changes = [Change::SetValue {
+            locale: "en".to_string(),
+            key: "common::a:b".to_string(),
+            value: json!("y"),
+        }];
+
+        apply(&config, &changes, false).unwrap();
+        let written: Value =
+            serde_json::from_str(&std::fs::read_to_string(output.join("en/common.json")).unwrap())
+                .unwrap();
+        assert_eq!(written, json!({ "a:b": "y" }));
+    }
+
+    #[test]
+    fn read_rejects_unknown_plan_version() {
+        let tmp = tempdir().unwrap();
+        let path = tmp.path().join("plan.json");
+        std::fs::write(&path, r#"{ "version": 2, "changes": [] }"#).unwrap();
+        let err = ChangePlan::read(&path).unwrap_err();
+        assert!(err.to_string().contains("Unsupported plan version 2"));
+    }
+}
@@ -1,42 +1,130 @@
 use anyhow::{Context, Result};
 use glob::Pattern;
-use notify_debouncer_mini::{new_debouncer, notify::RecursiveMode, DebounceEventResult};
-use std::collections::{HashMap, HashSet};
+use notify_debouncer_mini::{
+    new_debouncer, notify::RecursiveMode, DebounceEventResult, DebouncedEvent,
+};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::mpsc::channel;
-use std::time::Duration;
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
 
 use crate::config::Config;
-use crate::extractor::{self, ExtractedKey};
+use crate::eslint_data;
+use crate::extractor::{self, ExtractRequest, ExtractedKey};
 use crate::json_sync;
+use crate::key_transforms;
+use crate::paths;
+use crate::publish;
+use crate::summary;
+
+/// Default upper bound for coalescing event bursts, in milliseconds
+pub const DEFAULT_MAX_BATCH_WINDOW_MS: u64 = 2000;
+
+/// Changed files listed individually per batch; larger batches are summarized
+const MAX_LISTED_CHANGES: usize = 20;
+
+/// Exit code of `watch` when a config evaluated by the Node wrapper changed;
+/// the wrapper evaluates the config again and restarts the watcher
+pub const RELOAD_EXIT_CODE: i32 = 75;
+
+/// Where a watcher re-reads its config from when the config file changes
+#[derive(Debug, Clone)]
+pub enum ConfigReload {
+    /// Load the config file again, apply these `--set` overrides (and the
+    /// environment) and resolve its paths from `base_dir` when set
+    File {
+        path: PathBuf,
+        overrides: Vec<String>,
+        base_dir: Option<PathBuf>,
+    },
+    /// A config the Node wrapper evaluated (JS/TS configs): stop with
+    /// [`ConfigChanged`] so the wrapper can evaluate it again
+    Wrapper { path: PathBuf },
+}
+
+impl ConfigReload {
+    fn path(&self) -> &Path {
+        match self {
+            ConfigReload::File { path, .. } | ConfigReload::Wrapper { path } => path,
+        }
+    }
+}
+
+/// Returned by [`FileWatcher::run`] when a [`ConfigReload::Wrapper`] config changed
+#[derive(Debug)]
+pub struct ConfigChanged(pub PathBuf);
+
+impl std::fmt::Display for ConfigChanged {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Config file changed: {}", self.0.display())
+    }
+}
+
+impl std::error::Error for ConfigChanged {}
 
 /// File watcher with incremental extraction support
 pub struct FileWatcher {
     config: Config,
     output_dir: String,
     debounce_duration: Duration,
+    /// Longest time event bursts are coalesced into one extraction and write
+    max_batch_window: Duration,
     /// Cache of extracted keys per file for incremental updates
     file_cache: HashMap<PathBuf, Vec<ExtractedKey>>,
     ignore_patterns: Vec<Pattern>,
+    /// Prefix of output lines when several watchers share one terminal
+    label: Option<String>,
+    /// Report what each save would change without writing locale files
+    dry_run: bool,
+    /// `output_dir` came from `--output` and survives config reloads
+    output_overridden: bool,
+    /// Config file to follow for changes
+    config_reload: Option<ConfigReload>,
 }
 
 impl FileWatcher {
     pub fn new(config: Config, output_dir: Option<String>) -> Self {
+        let output_overridden = output_dir.is_some();
         let output = output_dir.unwrap_or_else(|| config.output.clone());
-        let ignore_patterns = config
-            .ignore
-            .iter()
-            .filter_map(|pattern| Pattern::new(pattern).ok())
-            .collect();
+        let ignore_patterns = ignore_patterns(&config);
         Self {
             config,
             output_dir: output,
             debounce_duration: Duration::from_millis(300),
+            max_batch_window: Duration::from_millis(DEFAULT_MAX_BATCH_WINDOW_MS),
             file_cache: HashMap::new(),
             ignore_patterns,
+            label: None,
+            dry_run: false,
+            output_overridden,
+            config_reload: None,
         }
     }
 
+    /// Prefix output lines with `[label]` (used when running several watchers)
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Only report the changes each save would make to the locale files
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Re-read the config when its file changes and re-extract with the new one
+    pub fn with_config_reload(mut self, reload: ConfigReload) -> Self {
+        self.config_reload = Some(reload);
+        self
+    }
+
+    /// Set how long bursts of file events may be coalesced before locale files are written
+    pub fn with_max_batch_window(mut self, window: Duration) -> Self {
+        self.max_batch_window = window;
+        self
+    }
+
     /// Run the file watcher, blocking until interrupted
     pub fn run(&mut self) -> Result<()> {
         let (tx, rx) = channel();
@@ -46,7 +134,7 @@ impl FileWatcher {
             new_debouncer(self.debounce_duration, tx).context("Failed to create file watcher")?;
 
         // Compute directories to watch from glob patterns
-        let watch_dirs = self.compute_watch_dirs();
+        let mut watch_dirs = self.compute_watch_dirs();
 
         if watch_dirs.is_empty() {
             anyhow::bail!("No valid directories found to watch from input patterns");
@@ -54,26 +142,168 @@ impl FileWatcher {
 
         // Watch all computed directories
         for dir in &watch_dirs {
-            println!("Watching: {}", dir.display());
+            self.say(format_args!(
+                "Watching: {}",
+                paths::display(dir, self.config.path_style)
+            ));
             debouncer
                 .watcher()
                 .watch(dir, RecursiveMode::Recursive)
                 .with_context(|| format!("Failed to watch directory: {}", dir.display()))?;
         }
-
-        println!("\nWatching for changes... (Ctrl+C to stop)\n");
+        let config_file = self.watch_config_file(debouncer.watcher(), &watch_dirs)?;
+
+        if self.dry_run {
+            self.say(format_args!(
+                "\nWatching for changes in dry-run mode; locale files are not written (Ctrl+C to stop)\n"
+            ));
+        } else {
+            self.say(format_args!("\nWatching for changes... (Ctrl+C to stop)\n"));
+        }
 
         // Initial full extraction
         self.full_extract()?;
 
-        // Process events in a loop
+        // Process events in a loop, one sync per coalesced batch
         while let Ok(result) = rx.recv() {
-            self.handle_events(result)?;
+            let mut events = self.collect_batch(result, &rx);
+            let config_events = config_file.as_ref().map_or(0, |config_file| {
+                let before = events.len();
+                events.retain(|event| !same_file(&event.path, config_file));
+                before - events.len()
+            });
+            if config_events > 0 && self.reload_config()? {
+                let dirs = self.compute_watch_dirs();
+                for dir in watch_dirs.iter().filter(|dir| !dirs.contains(dir)) {
+                    let _ = debouncer.watcher().unwatch(dir);
+                }
+                for dir in dirs.iter().filter(|dir| !watch_dirs.contains(dir)) {
+                    self.say(format_args!(
+                        "Watching: {}",
+                        paths::display(dir, self.config.path_style)
+                    ));
+                    debouncer
+                        .watcher()
+                        .watch(dir, RecursiveMode::Recursive)
+                        .with_context(|| format!("Failed to watch directory: {}", dir.display()))?;
+                }
+                watch_dirs = dirs;
+                self.full_extract()?;
+                continue;
+            }
+            self.handle_events(events)?;
         }
 
         Ok(())
     }
 
+    /// Watch the directory of the config file unless an input directory covers
+    /// it already; returns the config file to match events against
+    fn watch_config_file(
+        &self,
+        watcher: &mut dyn notify_debouncer_mini::notify::Watcher,
+        watch_dirs: &[PathBuf],
+    ) -> Result<Option<PathBuf>> {
+        let Some(reload) = &self.config_reload else {
+            return Ok(None);
+        };
+        let Ok(config_file) = reload.path().canonicalize() else {
+            self.warn(format_args!(
+                "Config file not found, changes to it are not followed: {}",
+                reload.path().display()
+            ));
+            return Ok(None);
+        };
+        let dir = config_file.parent().unwrap_or(Path::new("/"));
+        if !watch_dirs.iter().any(|watched| dir.starts_with(watched)) {
+            watcher
+                .watch(dir, RecursiveMode::NonRecursive)
+                .with_context(|| format!("Failed to watch directory: {}", dir.display()))?;
+        }
+        Ok(Some(config_file))
+    }
+
+    /// Load the changed config and, when it is valid and differs, apply it and
+    /// log the changed options. Returns whether the config was replaced.
+    fn reload_config(&mut self) -> Result<bool> {
+        let Some(reload) = self.config_reload.clone() else {
+            return Ok(false);
+        };
+        let (path, overrides, base_dir) = match reload {
+            ConfigReload::Wrapper { path } => {
+                self.say(format_args!(
+                    "\n--- Config changed: {} (restarting) ---",
+                    paths::display(&path, self.config.path_style)
+                ));
+                return Err(ConfigChanged(path).into());
+            }
+            ConfigReload::File {
+                path,
+                overrides,
+                base_dir,
+            } => (path, overrides, base_dir),
+        };
+        let loaded = Config::load(&path).and_then(|mut config| {
+            config.apply_overrides(|name| std::env::var(name).ok(), &overrides)?;
+            if let Some(dir) = &base_dir {
+                config.resolve_paths_from(dir);
+            }
+            Ok(config)
+        });
+        let config = match loaded {
+            Ok(config) => config,
+            Err(error) => {
+                self.warn(format_args!(
+                    "Config reload failed, keeping the previous config: {:#}",
+                    error
+                ));
+                return Ok(false);
+            }
+        };
+
+        let changed = self.config.changed_options(&config)?;
+        if changed.is_empty() {
+            self.say(format_args!(
+                "Config saved without changes: {}",
+                paths::display(&path, self.config.path_style)
+            ));
+            return Ok(false);
+        }
+        self.say(format_args!(
+            "\n--- Config reloaded: {} ---",
+            paths::display(&path, config.path_style)
+        ));
+        for (key, mut old, mut new) in changed {
+            crate::commands::config::mask_secrets(&mut old);
+            crate::commands::config::mask_secrets(&mut new);
+            self.say(format_args!("  {}: {} -> {}", key, old, new));
+        }
+
+        if !self.output_overridden {
+            self.output_dir = config.output.clone();
+        }
+        self.ignore_patterns = ignore_patterns(&config);
+        self.config = config;
+        self.file_cache.clear();
+        Ok(true)
+    }
+
+    fn say(&self, message: std::fmt::Arguments) {
+        for line in message.to_string().split('\n') {
+            match &self.label {
+                Some(label) if !line.is_empty() => println!("[{}] {}", label, line),
+                _ => println!("{}", line),
+            }
+        }
+    }
+
+    fn warn(&self, message: std::fmt::Arguments) {
+        match &self.label {
+            Some(label) => eprintln!("[{}] {}", label, message),
+            None => eprintln!("{}", message),
+        }
+    }
+
     /// Compute directories to watch from glob patterns
     fn compute_watch_dirs(&self) -> Vec<PathBuf> {
         let mut dirs = HashSet::new();
@@ -125,24 +355,14 @@ impl FileWatcher {
 
     /// Perform initial full extraction of all files
     fn full_extract(&mut self) -> Result<()> {
-        println!("--- Initial extraction ---");
+        self.say(format_args!("--- Initial extraction ---"));
 
-        let plural_config = self.config.plural_config();
-        let extraction = extractor::extract_from_glob_with_options(
+        let mut extraction = extractor::extract_with_config(
+            &self.config,
             &self.config.input,
-            &self.config.ignore,
-            &self.config.functions,
-            self.config.extract_from_comments,
-            &plural_config,
-            &self.config.trans_components,
-            &self.config.trans_keep_basic_html_nodes_for,
-            &self.config.use_translation_names,
-            &self.config.nesting_prefix,
-            &self.config.nesting_suffix,
-            &self.config.nesting_options_separator,
-            &self.config.interpolation_prefix,
-            &self.config.interpolation_suffix,
+            &self.config.extraction_ignore(),
         )?;
+        let key_map = key_transforms::apply(&self.config, &mut extraction.files);
 
         // Populate cache
         for (file_path, keys) in &extraction.files {
@@ -151,41 +371,81 @@ impl FileWatcher {
         }
 
         // Sync to JSON
-        let all_keys: Vec<ExtractedKey> = self.file_cache.values().flatten().cloned().collect();
-        let sync_results =
-            json_sync::sync_all_locales(&self.config, &all_keys, &self.output_dir, false)?;
+        let mut sync_results = Vec::new();
+        for (output, keys) in
+            json_sync::group_keys_by_output(&self.config, &self.file_cache, &self.output_dir)
+        {
+            sync_results.extend(json_sync::sync_all_locales(
+                &self.config,
+                &keys,
+                &output,
+                self.dry_run,
+            )?);
+        }
+        if !self.dry_run {
+            key_transforms::write_key_map(&self.config, &key_map, false)?;
+            eslint_data::write_configured(&self.config, &self.file_cache)?;
+            publish::write_configured(&self.config)?;
+        }
 
         // Report
         let total_keys: usize = self.file_cache.values().map(|v| v.len()).sum();
         let total_added: usize = sync_results.iter().map(|r| r.added_keys.len()).sum();
         let total_removed: usize = sync_results.iter().map(|r| r.removed_keys.len()).sum();
 
-        println!("  Files: {}", self.file_cache.len());
-        println!("  Keys: {}", total_keys);
-        if total_added > 0 {
-            println!("  New keys added: {}", total_added);
-        }
-        if total_removed > 0 {
-            println!("  Keys removed: {}", total_removed);
+        self.say(format_args!("  Files: {}", self.file_cache.len()));
+        self.say(format_args!("  Keys: {}", total_keys));
+        if self.dry_run {
+            self.report_dry_run(&sync_results);
+        } else {
+            summary::add_keys(total_added, total_removed);
+            if total_added > 0 {
+                self.say(format_args!("  New keys added: {}", total_added));
+            }
+            if total_removed > 0 {
+                self.say(format_args!("  Keys removed: {}", total_removed));
+            }
         }
-        if extraction.warning_count > 0 {
-            println!("  Warnings: {}", extraction.warning_count);
+        if extraction.total_warnings() > 0 {
+            self.say(format_args!("  Warnings: {}", extraction.total_warnings()));
         }
-        println!("--- Ready ---\n");
+        self.say(format_args!("--- Ready ---\n"));
+        summary::print();
 
         Ok(())
     }
 
-    /// Handle debounced file events
-    fn handle_events(&mut self, result: DebounceEventResult) -> Result<()> {
-        let events = match result {
-            Ok(events) => events,
-            Err(error) => {
-                eprintln!("Watch error: {:?}", error);
-                return Ok(());
-            }
+    /// Coalesce debounced batches arriving back to back (e.g. a branch switch touching
+    /// thousands of files) so they are extracted and written once. Stops when no batch
+    /// arrives within the debounce duration or `max_batch_window` has elapsed.
+    fn collect_batch(
+        &self,
+        first: DebounceEventResult,
+        rx: &Receiver<DebounceEventResult>,
+    ) -> Vec<DebouncedEvent> {
+        let deadline = Instant::now() + self.max_batch_window;
+        let mut events = Vec::new();
+        let mut push = |result: DebounceEventResult| match result {
+            Ok(mut batch) => events.append(&mut batch),
+            Err(error) => self.warn(format_args!("Watch error: {:?}", error)),
         };
+        push(first);
 
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+            match rx.recv_timeout(self.debounce_duration.min(remaining)) {
+                Ok(result) => push(result),
+                Err(_) => break,
+            }
+        }
+        events
+    }
+
+    /// Handle a batch of debounced file events
+    fn handle_events(&mut self, events: Vec<DebouncedEvent>) -> Result<()> {
         let mut changed_files = Vec::new();
         let mut removed_files = Vec::new();
 
@@ -211,7 +471,7 @@ impl FileWatcher {
         removed_files.dedup();
 
         // Collect namespaces from removed files before removing from cache
-        let mut affected_namespaces = HashSet::new();
+        let mut affected_namespaces = BTreeSet::new();
         for path in &removed_files {
             if let Some(keys) = self.file_cache.get(path) {
                 for key in keys {
@@ -229,15 +489,28 @@ impl FileWatcher {
             return Ok(());
         }
 
-        println!("--- Change detected ---");
-        for f in &changed_files {
-            println!("  Modified: {}", f.display());
+        self.say(format_args!("--- Change detected ---"));
+        let listed = changed_files
+            .iter()
+            .map(|f| ("Modified", f))
+            .chain(removed_files.iter().map(|f| ("Removed", f)));
+        for (label, f) in listed.take(MAX_LISTED_CHANGES) {
+            self.say(format_args!(
+                "  {}: {}",
+                label,
+                paths::display(f, self.config.path_style)
+            ));
         }
-        for f in &removed_files {
-            println!("  Removed: {}", f.display());
+        let total_changes = changed_files.len() + removed_files.len();
+        if total_changes > MAX_LISTED_CHANGES {
+            self.say(format_args!(
+                "  ... and {} more file(s)",
+                total_changes - MAX_LISTED_CHANGES
+            ));
         }
 
         // Re-extract only changed files and collect their namespaces
+        let started = Instant::now();
         let changed_keys = self.incremental_extract(&changed_files)?;
         for key in &changed_keys {
             if let Some(ns) = &key.namespace {
@@ -247,28 +520,53 @@ impl FileWatcher {
             }
         }
 
-        // Merge all cached keys but only sync affected namespaces
-        let all_keys: Vec<ExtractedKey> = self.file_cache.values().flatten().cloned().collect();
+        // Merge all cached keys per locale root but only sync affected namespaces
+        let mut sync_results = Vec::new();
+        for (output, keys) in
+            json_sync::group_keys_by_output(&self.config, &self.file_cache, &self.output_dir)
+        {
+            // Only sync the affected namespaces (IO optimization)
+            sync_results.extend(json_sync::sync_namespaces(
+                &self.config,
+                &keys,
+                &output,
+                &affected_namespaces,
+                self.dry_run,
+            )?);
+        }
 
-        // Only sync the affected namespaces (IO optimization)
-        let sync_results = json_sync::sync_namespaces(
-            &self.config,
-            &all_keys,
-            &self.output_dir,
-            &affected_namespaces,
-            false, // dry_run (watch mode always writes)
-        )?;
+        summary::record_command("watch");
+        summary::add_files_scanned(changed_files.len());
+        summary::add_cache_hits(
+            self.file_cache
+                .keys()
+                .filter(|path| changed_files.binary_search(path).is_err())
+                .count(),
+        );
+        summary::add_phase("watch", started.elapsed());
+
+        if self.dry_run {
+            self.report_dry_run(&sync_results);
+            self.say(format_args!("--- Dry run complete ---\n"));
+            summary::print();
+            return Ok(());
+        }
+
+        eslint_data::write_configured(&self.config, &self.file_cache)?;
+        publish::write_configured(&self.config)?;
 
         let total_added: usize = sync_results.iter().map(|r| r.added_keys.len()).sum();
         let total_removed: usize = sync_results.iter().map(|r| r.removed_keys.len()).sum();
+        summary::add_keys(total_added, total_removed);
         if total_added > 0 {
-            println!("  Added {} new key(s)", total_added);
+            self.say(format_args!("  Added {} new key(s)", total_added));
         }
         if total_removed > 0 {
-            println!("  Removed {} stale key(s)", total_removed);
+            self.say(format_args!("  Removed {} stale key(s)", total_removed));
         }
 
-        println!("--- Sync complete ---\n");
+        self.say(format_args!("--- Sync complete ---\n"));
+        summary::print();
 
         Ok(())
     }
@@ -278,20 +576,30 @@ impl FileWatcher {
     fn incremental_extract(&mut self, changed_files: &[PathBuf]) -> Result<Vec<ExtractedKey>> {
         use rayon::prelude::*;
 
-        let plural_config = self.config.plural_config();
-        let results: Vec<_> = changed_files
+        let request = ExtractRequest::builder()
+            .functions(self.config.functions.iter().cloned())
+            .extract_from_comments(self.config.extract_from_comments)
+            .plural_config(self.config.plural_config())
+            .path_style(self.config.path_style)
+            .build();
+        let results: Vec<(PathBuf, Vec<ExtractedKey>)> = changed_files
             .par_iter()
             .filter(|path| !self.is_ignored(path))
             .filter_map(|path| {
-                match extractor::extract_from_file_with_options(
-                    path,
-                    &self.config.functions,
-                    self.config.extract_from_comments,
-                    &plural_config,
-                ) {
-                    Ok(keys) => Some((path.clone(), keys)),
+                let extracted = std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read: {}", path.display()))
+                    .and_then(|source| request.run_source(source, path));
+                match extracted {
+                    Ok(result) => Some((
+                        path.clone(),
+                        result
+                            .files
+                            .into_iter()
+                            .flat_map(|(_, keys)| keys)
+                            .collect(),
+                    )),
                     Err(e) => {
-                        eprintln!("  Warning: {}", e);
+                        self.warn(format_args!("  Warning: {}", e));
                         None
                     }
                 }
@@ -300,7 +608,9 @@ impl FileWatcher {
 
         // Update cache and collect all extracted keys
         let mut all_extracted_keys = Vec::new();
-        for (path, keys) in results {
+        let mut key_map = key_transforms::KeyMap::new();
+        for (path, mut keys) in results {
+            key_transforms::apply_to_keys(&self.config, &mut keys, &mut key_map);
             if keys.is_empty() {
                 self.file_cache.remove(&path);
             } else {
@@ -308,9 +618,113 @@ impl FileWatcher {
                 self.file_cache.insert(path, keys);
             }
         }
+        if !self.dry_run {
+            key_transforms::write_key_map(&self.config, &key_map, true)?;
+        }
 
         Ok(all_extracted_keys)
     }
+
+    /// List the keys each locale file would gain or lose. Nothing is written, so
+    /// every report compares against the files on disk, not the previous report.
+    fn report_dry_run(&self, sync_results: &[json_sync::SyncResult]) {
+        let mut changed = false;
+        for result in sync_results {
+            if result.added_keys.is_empty() && result.removed_keys.is_empty() {
+                continue;
+            }
+            changed = true;
+            self.say(format_args!("  {}", result.file_path));
+            for key in &result.added_keys {
+                self.say(format_args!("    + {}", key));
+            }
+            for key in &result.removed_keys {
+                self.say(format_args!("    - {}", key));
+            }
+        }
+        if !changed {
+            self.say(format_args!("  Locale files are up to date"));
+        }
+    }
+}
+
+fn ignore_patterns(config: &Config) -> Vec<Pattern> {
+    config
+        .extraction_ignore()
+        .iter()
+        .filter_map(|pattern| Pattern::new(pattern).ok())
+        .collect()
+}
+
+/// `path` from a file event names `file` (canonical), also after the file was
+/// replaced or removed
+fn same_file(path: &Path, file: &Path) -> bool {
+    if path == file {
+        return true;
+    }
+    match (path.parent().map(Path::canonicalize), path.file_name()) {
+        (Some(Ok(dir)), Some(name)) => dir.join(name) == file,
+        _ => false,
+    }
+}
+
+/// Run several watchers concurrently in this process, sharing one extraction
+/// thread pool. A watcher that fails is reported while the others keep running.
+pub fn run_concurrently(watchers: Vec<FileWatcher>) -> Result<()> {
+    println!("Watchers:");
+    for watcher in &watchers {
+        println!(
+            "  [{}] {} -> {}",
+            watcher.label.as_deref().unwrap_or("-"),
+            watcher.config.input.join(", "),
+            watcher.output_dir
+        );
+    }
+    println!();
+
+    let total = watchers.len();
+    let failed = std::thread::scope(|scope| {
+        let handles: Vec<_> = watchers
+            .into_iter()
+            .map(|mut watcher| {
+                scope.spawn(move || {
+                    let result = watcher.run();
+                    if let Err(error) = &result {
+                        watcher.warn(format_args!("Watcher stopped: {:#}", error));
+                    }
+                    result.is_err()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap_or(true))
+            .filter(|failed| *failed)
+            .count()
+    });
+    if failed > 0 {
+        anyhow::bail!("{} of {} watcher(s) stopped with errors", failed, total);
+    }
+    Ok(())
+}
+
+/// Config files (`i18next-turbo.json`) under `root`, skipping dependency, build
+/// and hidden directories
+pub fn discover_configs(root: &Path) -> Vec<PathBuf> {
+    let mut configs: Vec<PathBuf> = walkdir::WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_str().unwrap_or_default();
+            entry.depth() == 0
+                || !(name.starts_with('.')
+                    || matches!(name, "node_modules" | "target" | "dist" | "build"))
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file() && entry.file_name() == "i18next-turbo.json")
+        .map(|entry| entry.into_path())
+        .collect();
+    configs.sort();
+    configs
 }
 
 #[cfg(test)]
@@ -352,6 +766,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn reload_config_applies_valid_changes_and_keeps_invalid_ones_out() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config_path = tmp.path().join("i18next-turbo.json");
+        let write = |json: &str| std::fs::write(&config_path, json).unwrap();
+        write(r#"{ "input": ["src/**/*.ts"], "output": "locales", "locales": ["en"] }"#);
+        let config = Config::load(&config_path).unwrap();
+        let mut watcher = FileWatcher::new(config, None).with_config_reload(ConfigReload::File {
+            path: config_path.clone(),
+            overrides: vec!["defaultNamespace=app".to_string()],
+            base_dir: Some(PathBuf::from("web")),
+        });
+        watcher
+            .file_cache
+            .insert(PathBuf::from("web/src/a.ts"), Vec::new());
+
+        write(
+            r#"{ "input": ["src/**/*.ts"], "output": "public/locales", "locales": ["en", "de"] }"#,
+        );
+        assert!(watcher.reload_config().unwrap());
+        assert_eq!(watcher.config.locales, vec!["en", "de"]);
+        assert_eq!(watcher.config.default_namespace, "app");
+        assert_eq!(watcher.output_dir, "web/public/locales");
+        assert!(watcher.file_cache.is_empty());
+
+        // Saved again unchanged, then broken: the loaded config stays
+        assert!(!watcher.reload_config().unwrap());
+        write(r#"{ "input": ["src/**/*.ts"], "locales": [] }"#);
+        assert!(!watcher.reload_config().unwrap());
+        assert_eq!(watcher.config.locales, vec!["en", "de"]);
+
+        let mut wrapped = FileWatcher::new(Config::default(), Some("out".to_string()))
+            .with_config_reload(ConfigReload::Wrapper {
+                path: config_path.clone(),
+            });
+        let error = wrapped.reload_config().unwrap_err();
+        assert!(error.is::<ConfigChanged>());
+    }
+
     #[test]
     fn should_process_file_respects_extension_and_ignore_patterns() {
         let config = make_test_config(
@@ -391,4 +844,101 @@ mod tests {
         let cached = watcher.file_cache.get(&file).unwrap();
         assert!(cached.iter().any(|k| k.key == "watch.key"));
     }
+
+    #[test]
+    fn dry_run_extracts_without_writing_locale_files() {
+        let cwd = std::env::current_dir().unwrap();
+        let tmp = tempdir_in(&cwd).unwrap();
+        let src_dir = tmp.path().join("src");
+        std::fs::create_dir_all(&src_dir).unwrap();
+        let file = src_dir.join("app.ts");
+        std::fs::write(&file, "t('watch.key');").unwrap();
+
+        let relative = src_dir
+            .strip_prefix(&cwd)
+            .unwrap()
+            .to_string_lossy()
+            .to_string();
+        let mut config = make_test_config(vec![format!("{}/**/*.ts", relative)], vec![]);
+        let locales = tmp.path().join("locales");
+        config.output = locales.to_string_lossy().to_string();
+        config.key_map_file = Some(
+            tmp.path()
+                .join("key-map.json")
+                .to_string_lossy()
+                .to_string(),
+        );
+        let mut watcher = FileWatcher::new(config, None).with_dry_run(true);
+
+        watcher.full_extract().unwrap();
+        std::fs::write(&file, "t('watch.key'); t('watch.other');").unwrap();
+        watcher
+            .handle_events(vec![DebouncedEvent {
+                path: file.clone(),
+                kind: notify_debouncer_mini::DebouncedEventKind::Any,
+            }])
+            .unwrap();
+
+        assert!(watcher
+            .file_cache
+            .get(&file)
+            .unwrap()
+            .iter()
+            .any(|k| k.key == "watch.other"));
+        assert!(!locales.exists());
+        assert!(!tmp.path().join("key-map.json").exists());
+    }
+
+    #[test]
+    fn collect_batch_coalesces_back_to_back_batches() {
+        let config = make_test_config(vec!["src/**/*.ts".to_string()], vec![]);
+        let watcher = FileWatcher::new(config, None);
+        let event = |path: &str| DebouncedEvent {
+            path: PathBuf::from(path),
+            kind: notify_debouncer_mini::DebouncedEventKind::Any,
+        };
+
+        let (tx, rx) = channel();
+        tx.send(Ok(vec![event("src/b.ts")])).unwrap();
+        tx.send(Ok(vec![event("src/c.ts"), event("src/d.ts")]))
+            .unwrap();
+        let events = watcher.collect_batch(Ok(vec![event("src/a.ts")]), &rx);
+        assert_eq!(events.len(), 4);
+
+        // The window caps how long a continuous burst is held back.
+        let watcher = watcher.with_max_batch_window(Duration::ZERO);
+        tx.send(Ok(vec![event("src/e.ts")])).unwrap();
+        let events = watcher.collect_batch(Ok(vec![event("src/a.ts")]), &rx);
+        assert_eq!(events.len(), 1);
+    }
+
+    #[test]
+    fn discover_configs_skips_dependency_and_hidden_dirs() {
+        let cwd = std::env::current_dir().unwrap();
+        let tmp = tempdir_in(&cwd).unwrap();
+        for dir in [
+            "",
+            "packages/ui",
+            "packages/web",
+            "node_modules/pkg",
+            ".cache",
+        ] {
+            let dir = tmp.path().join(dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("i18next-turbo.json"), "{}").unwrap();
+        }
+
+        let found: Vec<PathBuf> = discover_configs(tmp.path())
+            .into_iter()
+            .map(|path| path.strip_prefix(tmp.path()).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                PathBuf::from("i18next-turbo.json"),
+                PathBuf::from("packages/ui/i18next-turbo.json"),
+                PathBuf::from("packages/web/i18next-turbo.json"),
+            ]
+        );
+    }
 }
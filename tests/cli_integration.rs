@@ -91,6 +91,114 @@ fn extract_creates_locale_file() {
     assert_eq!(json["hello"]["world"], "");
 }
 
+#[test]
+fn summary_reports_run_totals_as_text_or_json() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(project.join("src/app.ts"), "t('a'); t('b');").unwrap();
+    fs::write(project.join("src/empty.ts"), "export {};").unwrap();
+    let config_path = write_config(project);
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "extract",
+            "--summary",
+            "json",
+        ],
+    );
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let line = stderr
+        .lines()
+        .find(|line| line.starts_with("{\"summary\""))
+        .unwrap_or_else(|| panic!("no summary in stderr: {}", stderr));
+    let summary: Value = serde_json::from_str(line).unwrap();
+    assert_eq!(summary["summary"]["commands"], json!(["extract"]));
+    assert_eq!(summary["summary"]["filesScanned"], 2);
+    assert_eq!(summary["summary"]["keysAdded"], 2);
+    assert!(summary["summary"]["durationsMs"]["extract"].is_number());
+
+    let output = run_cli(
+        project,
+        &[
+            "--summary",
+            "--config",
+            config_path.to_str().unwrap(),
+            "check",
+        ],
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("=== i18next-turbo summary ==="),
+        "stderr: {}",
+        stderr
+    );
+    assert!(stderr.contains("  Commands: check\n"));
+    assert!(stderr.contains("  Files scanned: 2\n"));
+
+    // Opt-in only
+    let output = run_cli(
+        project,
+        &["--config", config_path.to_str().unwrap(), "extract"],
+    );
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("summary"));
+}
+
+#[test]
+fn extract_writes_dev_only_keys_to_dev_namespace_unless_production() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/app.ts"),
+        "t('app.title');\nt('debug.panel.title', { devOnly: true });\n// i18next-dev-only\nt('debug.flag');\n",
+    )
+    .unwrap();
+    let config_path = write_config(project);
+
+    let output = run_cli(
+        project,
+        &["--config", config_path.to_str().unwrap(), "extract"],
+    );
+    assert!(output.status.success());
+    let translation = read_json(&project.join("locales/en/translation.json"));
+    assert_eq!(translation, json!({ "app": { "title": "" } }));
+    let dev = read_json(&project.join("locales/en/dev.json"));
+    assert_eq!(
+        dev,
+        json!({ "debug": { "flag": "", "panel": { "title": "" } } })
+    );
+
+    // Production runs leave developer-only keys out without pruning them
+    fs::write(
+        project.join("src/app.ts"),
+        "t('app.title');\nt('app.save');\nt('debug.extra', { devOnly: true });\n",
+    )
+    .unwrap();
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "extract",
+            "--production",
+        ],
+    );
+    assert!(
+        output.status.success(),
+        "stdout: {}\nstderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let translation = read_json(&project.join("locales/en/translation.json"));
+    assert_eq!(translation["app"]["save"], "");
+    assert_eq!(read_json(&project.join("locales/en/dev.json")), dev);
+}
+
 #[test]
 fn extract_generates_types_file() {
     let tmp = tempdir().unwrap();
@@ -268,6 +376,65 @@ fn sync_adds_missing_keys_to_secondary_locale() {
     assert_eq!(ja["hello"], "");
 }
 
+#[test]
+fn sync_copies_array_translations_entry_by_entry() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(project.join("src/app.ts"), "console.log('sync');").unwrap();
+    let config_path = write_config_with_locales(project, &["en", "ja"]);
+
+    write_locale_json(
+        &project.join("locales/en/translation.json"),
+        json!({"steps": ["First", "Second"], "hello": "Hello"}),
+    );
+    write_locale_json(&project.join("locales/ja/translation.json"), json!({}));
+
+    let output = run_cli(
+        project,
+        &["--config", config_path.to_str().unwrap(), "sync"],
+    );
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("+3 added"), "stdout: {}", stdout);
+
+    let ja = read_json(&project.join("locales/ja/translation.json"));
+    assert_eq!(ja, json!({"hello": "", "steps": ["", ""]}));
+}
+
+#[test]
+fn sync_copies_marker_entries_as_they_are() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(project.join("src/app.ts"), "console.log('sync');").unwrap();
+    let config_path = write_config_with_locales(project, &["en", "de"]);
+    let config_arg = config_path.to_str().unwrap();
+
+    write_locale_json(
+        &project.join("locales/en/translation.json"),
+        json!({"//": "Dynamic keys", "dyn": {"__preserve__": ["*"], "x": "X"}}),
+    );
+    write_locale_json(&project.join("locales/de/translation.json"), json!({}));
+
+    let output = run_cli(project, &["--config", config_arg, "sync"]);
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("+1 added"), "stdout: {}", stdout);
+
+    let de = read_json(&project.join("locales/de/translation.json"));
+    assert_eq!(
+        de,
+        json!({"//": "Dynamic keys", "dyn": {"__preserve__": ["*"], "x": ""}})
+    );
+
+    // The copied marker keeps protecting the key in the secondary locale
+    let output = run_cli(project, &["--config", config_arg, "extract"]);
+    assert!(output.status.success(), "{:?}", output);
+    let de = read_json(&project.join("locales/de/translation.json"));
+    assert_eq!(de["dyn"]["x"], "");
+}
+
 #[test]
 fn sync_remove_unused_respects_dry_run() {
     let tmp = tempdir().unwrap();
@@ -410,6 +577,123 @@ fn typegen_command_generates_file() {
     assert!(content.contains("bye"));
 }
 
+#[test]
+fn extract_conflict_report_is_resolved_by_resolve_conflicts() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/app.ts"),
+        "t('button.submit', { defaultValue: 'Submit' }); t('menu');",
+    )
+    .unwrap();
+    // Keep the values in the way, which are not used in source
+    let config_path = project.join("i18next-turbo.json");
+    fs::write(
+        &config_path,
+        json!({
+            "input": ["src/**/*.ts"],
+            "output": "locales",
+            "locales": ["en"],
+            "removeUnusedKeys": false
+        })
+        .to_string(),
+    )
+    .unwrap();
+    let locale_path = project.join("locales/en/translation.json");
+    write_locale_json(
+        &locale_path,
+        json!({ "button": "Button", "menu": { "open": "Open" } }),
+    );
+    let config = config_path.to_str().unwrap();
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config,
+            "extract",
+            "--conflict-report",
+            "reports/conflicts.json",
+        ],
+    );
+    assert!(output.status.success());
+    let report = read_json(&project.join("reports/conflicts.json"));
+    let conflicts = report["conflicts"].as_array().unwrap();
+    assert_eq!(conflicts.len(), 2, "{:#}", report);
+    let button = conflicts
+        .iter()
+        .find(|conflict| conflict["key"] == "button.submit")
+        .unwrap();
+    assert_eq!(button["path"], json!(["button", "submit"]));
+    assert_eq!(button["conflictPath"], "button");
+    assert_eq!(button["existingValue"], "Button");
+    assert_eq!(button["wantedValue"], "Submit");
+    assert_eq!(button["usedIn"][0].as_str().unwrap(), "src/app.ts");
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config,
+            "resolve-conflicts",
+            "--report",
+            "reports/conflicts.json",
+            "--apply",
+            "strategy=nest",
+        ],
+    );
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Resolved 1 conflict(s)"), "{}", stdout);
+    assert!(
+        stdout.contains("Left 1 conflict(s) unchanged"),
+        "{}",
+        stdout
+    );
+    assert_eq!(
+        read_json(&locale_path),
+        json!({
+            "button": { "_value": "Button", "submit": "Submit" },
+            "menu": { "open": "Open" }
+        })
+    );
+
+    let extract_with_report = || {
+        let output = run_cli(
+            project,
+            &[
+                "--config",
+                config,
+                "extract",
+                "--conflict-report",
+                "reports/conflicts.json",
+            ],
+        );
+        assert!(output.status.success());
+        read_json(&project.join("reports/conflicts.json"))["conflicts"].clone()
+    };
+    let conflicts = extract_with_report();
+    assert_eq!(conflicts.as_array().unwrap().len(), 1);
+    assert_eq!(conflicts[0]["key"], "menu");
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config,
+            "resolve-conflicts",
+            "--report",
+            "reports/conflicts.json",
+            "--apply",
+            "overwrite",
+        ],
+    );
+    assert!(output.status.success());
+    assert_eq!(read_json(&locale_path)["menu"], "");
+    assert_eq!(extract_with_report(), json!([]));
+}
+
 #[test]
 fn check_dry_run_reports_dead_keys() {
     let tmp = tempdir().unwrap();
@@ -449,51 +733,132 @@ fn check_dry_run_reports_dead_keys() {
 }
 
 #[test]
-fn lint_fail_on_error_returns_non_zero() {
+fn check_all_locales_reports_drift_and_removes_from_every_locale() {
+    use std::io::Write;
+    use std::process::Stdio;
+
     let tmp = tempdir().unwrap();
     let project = tmp.path();
     fs::create_dir_all(project.join("src")).unwrap();
-    fs::write(
-        project.join("src/app.tsx"),
-        "export const App = () => <div>Hello hardcoded</div>;",
-    )
-    .unwrap();
-    let config_path = write_config(project);
+    fs::write(project.join("src/app.ts"), "t('alive');").unwrap();
+    let config_path = write_config_with_locales(project, &["en", "de", "fr"]);
+    for (locale, content) in [
+        (
+            "en",
+            json!({ "alive": "Alive", "old": "Old", "legacy": "Legacy" }),
+        ),
+        ("de", json!({ "alive": "Lebt", "old": "Alt" })),
+        ("fr", json!({ "alive": "Vivant", "old": "Vieux" })),
+    ] {
+        write_locale_json(
+            &project.join(format!("locales/{}/translation.json", locale)),
+            content,
+        );
+    }
 
     let output = run_cli(
         project,
         &[
             "--config",
             config_path.to_str().unwrap(),
-            "lint",
-            "--fail-on-error",
+            "check",
+            "--all-locales",
+            "--emit-plan",
+            "dead.json",
         ],
     );
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(
-        !output.status.success(),
-        "expected lint failure; stdout: {} stderr: {}",
-        String::from_utf8_lossy(&output.stdout),
-        String::from_utf8_lossy(&output.stderr)
+        stdout.contains("Checking locales: en, de, fr"),
+        "{}",
+        stdout
     );
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    assert!(stderr.contains("lint issue"));
+    assert!(
+        stdout.contains("[translation] legacy -> in en; missing in de, fr"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("[translation] old -> in"), "{}", stdout);
+    assert_eq!(
+        read_json(&project.join("dead.json"))["changes"],
+        json!([
+            { "op": "deleteKey", "key": "translation:legacy", "locales": ["en"] },
+            { "op": "deleteKey", "key": "translation:old", "locales": ["de", "en", "fr"] }
+        ])
+    );
+
+    let mut child = Command::new(cli_bin())
+        .current_dir(project)
+        .args([
+            "--config",
+            config_path.to_str().unwrap(),
+            "check",
+            "--all-locales",
+            "--remove",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"y\n").unwrap();
+    let output = child.wait_with_output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "stdout: {}", stdout);
+    assert!(stdout.contains("Removed 4 key(s)"), "{}", stdout);
+    for locale in ["en", "de", "fr"] {
+        let json = read_json(&project.join(format!("locales/{}/translation.json", locale)));
+        assert_eq!(json.as_object().unwrap().len(), 1, "{}: {}", locale, json);
+        assert!(json["alive"].is_string());
+    }
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "check",
+            "--all-locales",
+            "--locale",
+            "de",
+        ],
+    );
+    assert!(!output.status.success());
 }
 
 #[test]
-fn lint_passes_when_only_translated_text_exists() {
+fn check_write_config_preserves_keys_of_dynamic_template_literals() {
     let tmp = tempdir().unwrap();
     let project = tmp.path();
     fs::create_dir_all(project.join("src")).unwrap();
     fs::write(
-        project.join("src/app.tsx"),
-        "import { t } from 'i18next'; export const App = () => <div>{t('ui.title')}</div>;",
+        project.join("src/app.ts"),
+        "t('alive.key');\nt(`status.${state}`);",
     )
     .unwrap();
-    let config_path = write_config(project);
+    let config_path = project.join("i18next-turbo.json");
+    let config_text = "{\n  \"output\": \"locales\",\n  \"locales\": [\"en\"],\n  \"input\": [\"src/**/*.ts\"],\n  \"functions\": [\"t\"]\n}\n";
+    fs::write(&config_path, config_text).unwrap();
+
+    write_locale_json(
+        &project.join("locales/en/translation.json"),
+        json!({
+            "alive": { "key": "" },
+            "dead": { "key": "" },
+            "status": { "active": "", "paused": "" }
+        }),
+    );
 
     let output = run_cli(
         project,
-        &["--config", config_path.to_str().unwrap(), "lint"],
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "check",
+            "--dry-run",
+            "--write-config",
+        ],
     );
     assert!(
         output.status.success(),
@@ -502,33 +867,4010 @@ fn lint_passes_when_only_translated_text_exists() {
         String::from_utf8_lossy(&output.stderr)
     );
     let stdout = String::from_utf8_lossy(&output.stdout);
-    assert!(stdout.contains("No hardcoded strings found"));
+    assert!(stdout.contains("status.* (src/app.ts:2) covers 2 key(s)"));
+    assert!(stdout.contains("Would remove 1 key(s)"));
+    assert!(read_json(&config_path).get("preservePatterns").is_none());
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "check",
+            "--write-config",
+        ],
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        read_json(&config_path)["preservePatterns"],
+        json!(["status.*"])
+    );
+    assert_eq!(
+        fs::read_to_string(&config_path).unwrap(),
+        config_text.replace(
+            "[\"t\"]\n}",
+            "[\"t\"],\n  \"preservePatterns\": [\"status.*\"]\n}"
+        )
+    );
+
+    let output = run_cli(
+        project,
+        &["--config", config_path.to_str().unwrap(), "check"],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("status.active"));
+    assert!(stdout.contains("Found 1 dead key(s)"));
 }
 
 #[test]
-fn migrate_dry_run_prints_preview_for_existing_turbo_config() {
+fn check_values_reports_unsafe_markup_per_locale() {
     let tmp = tempdir().unwrap();
     let project = tmp.path();
     fs::create_dir_all(project.join("src")).unwrap();
-    let config_path = write_config(project);
+    let config_path = write_config_with_locales(project, &["en", "de", "fr"]);
+    for (locale, content) in [
+        (
+            "en",
+            json!({ "terms": "Read the <1>terms</1>", "title": "Home" }),
+        ),
+        (
+            "de",
+            json!({ "terms": "Lies die <1>Bedingungen</2>", "title": "<b>Start</b>" }),
+        ),
+        (
+            "fr",
+            json!({ "terms": "Lisez les <1>conditions</1>", "title": "" }),
+        ),
+    ] {
+        fs::create_dir_all(project.join("locales").join(locale)).unwrap();
+        fs::write(
+            project
+                .join("locales")
+                .join(locale)
+                .join("translation.json"),
+            content.to_string(),
+        )
+        .unwrap();
+    }
 
     let output = run_cli(
         project,
         &[
             "--config",
             config_path.to_str().unwrap(),
-            "migrate",
-            "--dry-run",
-            "--yes",
+            "check",
+            "--values",
         ],
     );
     assert!(
-        output.status.success(),
-        "stdout: {} stderr: {}",
+        !output.status.success(),
+        "expected value check failure; stdout: {} stderr: {}",
         String::from_utf8_lossy(&output.stdout),
         String::from_utf8_lossy(&output.stderr)
     );
     let stdout = String::from_utf8_lossy(&output.stdout);
-    assert!(stdout.contains("変換後プレビュー"));
-    assert!(stdout.contains("dry-run"));
+    assert!(stdout.contains("[de] 2 issue(s)"), "stdout: {}", stdout);
+    assert!(stdout.contains("translation:terms unbalanced tags: </2> closes <1>"));
+    assert!(stdout.contains("translation:title introduces <b>"));
+    assert!(stdout.contains("[fr] OK"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("2 translation value issue(s)"));
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "check",
+            "--values",
+            "--locale",
+            "fr",
+        ],
+    );
+    assert!(output.status.success());
+}
+
+#[test]
+fn check_nesting_reports_cycles_and_missing_references() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    let config_path = write_config_with_locales(project, &["en", "ja"]);
+    write_locale_json(
+        &project.join("locales/en/translation.json"),
+        json!({
+            "a": "A $t(b)",
+            "b": "B $t(a)",
+            "greeting": "Hello $t(name)",
+            "items_one": "one item",
+            "items_other": "{{count}} items",
+            "summary": "You have $t(items, { \"count\": {{count}} })"
+        }),
+    );
+    write_locale_json(
+        &project.join("locales/ja/translation.json"),
+        json!({ "title": "$t(common:app)" }),
+    );
+    write_locale_json(
+        &project.join("locales/ja/common.json"),
+        json!({ "app": "アプリ" }),
+    );
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "check",
+            "--nesting",
+        ],
+    );
+    assert!(
+        !output.status.success(),
+        "expected nesting check failure; stdout: {} stderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("[en] 2 issue(s)"), "stdout: {}", stdout);
+    assert!(stdout
+        .contains("translation:a nesting cycle: translation:a -> translation:b -> translation:a"));
+    assert!(stdout.contains("translation:greeting references missing key 'name'"));
+    assert!(stdout.contains("[ja] OK"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("2 nesting issue(s)"));
+
+    // sync warns about the primary locale but still succeeds
+    let output = run_cli(
+        project,
+        &["--config", config_path.to_str().unwrap(), "sync"],
+    );
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Nesting warnings (en):"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(stdout.contains("translation:greeting references missing key 'name'"));
+}
+
+#[test]
+fn metadata_file_tracks_last_seen_for_dead_key_age() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/app.ts"),
+        "t('home.title'); t('home.old'); t('home.recent');",
+    )
+    .unwrap();
+    let config_path = write_config(project);
+    let mut config = read_json(&config_path);
+    config["metadataFile"] = json!("locales/.meta.json");
+    fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+
+    let output = run_cli(
+        project,
+        &["--config", config_path.to_str().unwrap(), "extract"],
+    );
+    assert!(output.status.success());
+    let metadata_path = project.join("locales/.meta.json");
+    let mut metadata = read_json(&metadata_path);
+    let today = metadata["keys"]["translation"]["home.title"]["lastSeen"].clone();
+    assert_eq!(
+        metadata["keys"]["translation"]["home.title"]["firstSeen"],
+        today
+    );
+
+    metadata["keys"]["translation"]["home.old"]["lastSeen"] = json!("2000-01-01");
+    fs::write(&metadata_path, metadata.to_string()).unwrap();
+    fs::write(project.join("src/app.ts"), "t('home.title');").unwrap();
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "check",
+            "--dead-for",
+            "90",
+        ],
+    );
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Found 1 dead key(s)"), "stdout: {}", stdout);
+    assert!(stdout.contains("home.old"));
+    assert!(!stdout.contains("home.recent ->"));
+    assert!(stdout.contains("Skipping 1 dead key(s) seen within the last 90 day(s)"));
+
+    let output = run_cli(
+        project,
+        &["--config", config_path.to_str().unwrap(), "status"],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Key growth"));
+    assert!(stdout.contains("+3 (3 total)"));
+}
+
+#[test]
+fn mark_sets_review_status_reset_when_primary_value_changes() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/app.ts"),
+        "t('home.title', 'Home'); t('home.save', 'Save'); t('footer');",
+    )
+    .unwrap();
+    let config_path = write_config_with_locales(project, &["en", "de"]);
+    let mut config = read_json(&config_path);
+    config["metadataFile"] = json!("locales/.meta.json");
+    fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+    let config = config_path.to_str().unwrap();
+
+    assert!(run_cli(project, &["--config", config, "extract"])
+        .status
+        .success());
+    let output = run_cli(project, &["--config", config, "mark", "approved", "home.*"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Updated 2 review status(es)"),
+        "stdout: {}",
+        stdout
+    );
+
+    let output = run_cli(project, &["--config", config, "status"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Review: 2 approved, 0 needs-review, 0 machine-translated, 1 unmarked"),
+        "stdout: {}",
+        stdout
+    );
+
+    let en = project.join("locales/en/translation.json");
+    let mut values = read_json(&en);
+    values["home"]["title"] = json!("Welcome");
+    write_locale_json(&en, values);
+    assert!(run_cli(project, &["--config", config, "extract"])
+        .status
+        .success());
+
+    let metadata = read_json(&project.join("locales/.meta.json"));
+    let keys = &metadata["keys"]["translation"];
+    assert_eq!(keys["home.title"]["review"]["de"], "needs-review");
+    assert_eq!(keys["home.title"]["sourceValue"], "Welcome");
+    assert_eq!(keys["home.save"]["review"]["de"], "approved");
+
+    let output = run_cli(project, &["--config", config, "mark", "done", "footer"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn extract_reports_plural_variants_colliding_with_literal_keys() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(project.join("src/cart.ts"), "\nt('item', { count: n });").unwrap();
+    fs::write(project.join("src/summary.ts"), "t('item_other');").unwrap();
+    let config_path = write_config_with_locales(project, &["en"]);
+
+    let output = run_cli(
+        project,
+        &["--config", config_path.to_str().unwrap(), "extract"],
+    );
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("1 key(s) are both generated by plural/context expansion"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(stderr.contains("item_other"));
+    assert!(stderr.contains("generated from 'item' at "));
+    assert!(stderr.contains("cart.ts:2"));
+    assert!(stderr.contains("literal at "));
+    assert!(stderr.contains("summary.ts:1"));
+}
+
+#[test]
+fn extract_update_defaults_rewrites_changed_source_copy() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/app.ts"),
+        "t('cta', { defaultValue: 'Buy now' });",
+    )
+    .unwrap();
+    let config_path = write_config_with_locales(project, &["en", "de"]);
+    let config = config_path.to_str().unwrap();
+    assert!(run_cli(project, &["--config", config, "extract"])
+        .status
+        .success());
+    write_locale_json(
+        &project.join("locales/de/translation.json"),
+        json!({ "cta": "Jetzt kaufen" }),
+    );
+
+    fs::write(
+        project.join("src/app.ts"),
+        "t('cta', { defaultValue: 'Start free trial' });",
+    )
+    .unwrap();
+    let output = run_cli(project, &["--config", config, "extract"]);
+    assert!(output.status.success());
+    let en = project.join("locales/en/translation.json");
+    assert_eq!(read_json(&en)["cta"], "Buy now");
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config,
+            "extract",
+            "--update-defaults",
+            "--clear-stale",
+        ],
+    );
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(
+            "~ [translation] cta: \"Buy now\" -> \"Start free trial\" (cleared stale: de)"
+        ),
+        "stdout: {}",
+        stdout
+    );
+    assert_eq!(read_json(&en)["cta"], "Start free trial");
+    assert_eq!(
+        read_json(&project.join("locales/de/translation.json"))["cta"],
+        ""
+    );
+
+    let output = run_cli(project, &["--config", config, "extract", "--clear-stale"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn nextjs_preset_writes_flat_messages_files() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("app/dashboard")).unwrap();
+    fs::create_dir_all(project.join("components")).unwrap();
+    fs::write(
+        project.join("app/dashboard/page.tsx"),
+        r#"export default async function Page() {
+  const t = await getTranslations('Dashboard');
+  return <h1>{t('title')}</h1>;
+}"#,
+    )
+    .unwrap();
+    fs::write(
+        project.join("components/Nav.tsx"),
+        r#"'use client';
+export function Nav() {
+  const t = useTranslations('Nav');
+  return <a>{t('home')}</a>;
+}"#,
+    )
+    .unwrap();
+    fs::write(
+        project.join("i18next-turbo.json"),
+        r#"{ "preset": "nextjs", "locales": ["en", "de"] }"#,
+    )
+    .unwrap();
+
+    let output = run_cli(project, &["extract"]);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    for locale in ["en", "de"] {
+        assert_eq!(
+            read_json(&project.join(format!("messages/{}.json", locale))),
+            json!({ "Dashboard": { "title": "" }, "Nav": { "home": "" } })
+        );
+    }
+    assert!(!project.join("messages/en").exists());
+
+    let output = run_cli(project, &["check"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("No dead keys found"), "stdout: {}", stdout);
+}
+
+#[test]
+fn typegen_reads_flat_locale_files_and_fails_without_any() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::write(
+        project.join("i18next-turbo.json"),
+        r#"{ "preset": "nextjs", "locales": ["en", "de"] }"#,
+    )
+    .unwrap();
+    write_locale_json(
+        &project.join("messages/en.json"),
+        json!({ "Nav": { "home": "Home" } }),
+    );
+
+    let output = run_cli(project, &["typegen", "--output", "types.d.ts"]);
+    assert!(output.status.success(), "{:?}", output);
+    let content = fs::read_to_string(project.join("types.d.ts")).unwrap();
+    assert!(content.contains("home"), "{}", content);
+
+    let output = run_cli(
+        project,
+        &["typegen", "--output", "de.d.ts", "--default-locale", "fr"],
+    );
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("No locale files for \"fr\""), "{}", stderr);
+    assert!(!project.join("de.d.ts").exists());
+}
+
+#[test]
+fn key_transforms_rewrite_locale_keys_and_write_key_map() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/App.tsx"),
+        r#"t('app.Home.title'); t('common:app.Buttons.save'); t('footer.text');"#,
+    )
+    .unwrap();
+    fs::write(
+        project.join("i18next-turbo.json"),
+        r#"{
+  "input": ["src/**/*.tsx"],
+  "output": "locales",
+  "locales": ["en"],
+  "keyTransforms": [{ "stripPrefix": "app." }, "lowercaseFirstSegment"],
+  "keyMapFile": "src/i18n/key-map.json"
+}"#,
+    )
+    .unwrap();
+
+    let output = run_cli(project, &["extract"]);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        read_json(&project.join("locales/en/translation.json")),
+        json!({ "home": { "title": "" }, "footer": { "text": "" } })
+    );
+    assert_eq!(
+        read_json(&project.join("locales/en/common.json")),
+        json!({ "buttons": { "save": "" } })
+    );
+    assert_eq!(
+        read_json(&project.join("src/i18n/key-map.json")),
+        json!({
+            "common": { "buttons.save": "app.Buttons.save" },
+            "translation": { "home.title": "app.Home.title" }
+        })
+    );
+
+    let output = run_cli(project, &["check"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("No dead keys found"), "stdout: {}", stdout);
+}
+
+#[test]
+fn rename_namespace_updates_locales_sources_and_types() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/Invoice.tsx"),
+        r#"const { t } = useTranslation('billing');
+t('invoice.total');
+<Trans i18nKey="invoice.due" ns="billing" />;
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project.join("src/api.ts"),
+        "t('billing:invoice.title'); t('home.title');",
+    )
+    .unwrap();
+    let config_path = write_config_with_locales(project, &["en", "de"]);
+    let mut config = read_json(&config_path);
+    config["types"] = json!({ "output": "types/i18next.d.ts" });
+    fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+
+    for command in ["extract", "typegen"] {
+        let output = run_cli(project, &[command]);
+        assert!(
+            output.status.success(),
+            "{} stderr: {}",
+            command,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    assert!(project.join("locales/de/billing.json").exists());
+
+    let output = run_cli(project, &["rename-namespace", "billing", "payments"]);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    for locale in ["en", "de"] {
+        assert!(!project
+            .join(format!("locales/{}/billing.json", locale))
+            .exists());
+        assert_eq!(
+            read_json(&project.join(format!("locales/{}/payments.json", locale))),
+            json!({ "invoice": { "due": "", "title": "", "total": "" } })
+        );
+    }
+    let source = fs::read_to_string(project.join("src/Invoice.tsx")).unwrap();
+    assert!(source.contains("useTranslation('payments')"));
+    assert!(source.contains(r#"ns="payments""#));
+    let source = fs::read_to_string(project.join("src/api.ts")).unwrap();
+    assert!(source.contains("t('payments:invoice.title')"));
+    let types = fs::read_to_string(project.join("types/i18next.d.ts")).unwrap();
+    assert!(types.contains("payments"), "types: {}", types);
+    assert!(!types.contains("billing"), "types: {}", types);
+
+    let output = run_cli(project, &["check"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("No dead keys found"), "stdout: {}", stdout);
+}
+
+#[test]
+fn diff_compares_locale_trees_as_json_and_lines() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    write_config_with_locales(project, &["en", "de"]);
+    write_locale_json(
+        &project.join("locales/de/common.json"),
+        json!({ "nav": { "home": "Start", "old": "Alt" }, "ok": "OK" }),
+    );
+    write_locale_json(
+        &project.join("drop/de/common.json"),
+        json!({ "nav": { "home": "Startseite" }, "ok": "OK", "save": "Speichern" }),
+    );
+
+    let output = run_cli(project, &["diff", "locales", "drop", "--format", "json"]);
+    assert!(output.status.success());
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        report,
+        json!({
+            "summary": { "added": 1, "removed": 1, "changed": 1 },
+            "locales": { "de": { "common": {
+                "added": { "save": "Speichern" },
+                "removed": { "nav.old": "Alt" },
+                "changed": { "nav.home": { "from": "Start", "to": "Startseite" } }
+            } } }
+        })
+    );
+
+    let output = run_cli(project, &["diff", "locales", "drop", "--format", "lines"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("de/common\n"));
+    assert!(stdout.contains("-nav.home: \"Start\"\n+nav.home: \"Startseite\"\n"));
+
+    for format in ["xml", "patch"] {
+        let output = run_cli(project, &["diff", "locales", "drop", "--format", format]);
+        assert!(!output.status.success());
+    }
+}
+
+#[test]
+fn check_validates_plural_forms_and_fixes_missing_ones() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(project.join("src/app.ts"), "t('item', { count: 2 });").unwrap();
+    write_config_with_locales(project, &["ja", "ru"]);
+    write_locale_json(
+        &project.join("locales/ja/translation.json"),
+        json!({ "item_one": "", "item_two": "{{count}} 個", "item_other": "{{count}} 個" }),
+    );
+    write_locale_json(
+        &project.join("locales/ru/translation.json"),
+        json!({ "item_one": "{{count}} товар", "item_other": "{{count}} товара" }),
+    );
+
+    let output = run_cli(project, &["check"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("[ja] item: item_two not a plural form of 'ja'"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(!stdout.contains("item_one not a plural form"));
+    assert!(stdout.contains("[ru] item: missing few, many"));
+    assert!(stdout.contains("Run with --fix-plurals"));
+
+    let output = run_cli(project, &["check", "--fix-plurals"]);
+    assert!(output.status.success());
+    let ru = read_json(&project.join("locales/ru/translation.json"));
+    assert_eq!(ru["item_few"], json!("{{count}} товара"));
+    assert_eq!(ru["item_many"], json!("{{count}} товара"));
+    assert_eq!(ru["item_one"], json!("{{count}} товар"));
+}
+
+#[test]
+fn check_reports_missing_and_misspelled_namespaces() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/app.ts"),
+        "t('title'); t('common:save'); t('comon:cancel');",
+    )
+    .unwrap();
+    let config_path = write_config_with_locales(project, &["en", "de"]);
+    for (locale, namespaces) in [
+        ("en", &["translation", "common"][..]),
+        ("de", &["translation"][..]),
+    ] {
+        for namespace in namespaces {
+            write_locale_json(
+                &project.join(format!("locales/{}/{}.json", locale, namespace)),
+                json!({}),
+            );
+        }
+    }
+
+    let output = run_cli(project, &["check"]);
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("common (used in "), "stderr: {}", stderr);
+    assert!(stderr.contains("no file for de"));
+    assert!(stderr.contains("comon (used in "));
+    assert!(stderr.contains("unknown namespace, did you mean 'common'?"));
+
+    let mut config = read_json(&config_path);
+    config["strictNamespaces"] = json!(true);
+    fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+    let output = run_cli(project, &["check"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("2 namespace(s) missing from locale files (strictNamespaces enabled)"));
+}
+
+#[test]
+fn lint_fail_on_error_returns_non_zero() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/app.tsx"),
+        "export const App = () => <div>Hello hardcoded</div>;",
+    )
+    .unwrap();
+    let config_path = write_config(project);
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "lint",
+            "--fail-on-error",
+        ],
+    );
+    assert!(
+        !output.status.success(),
+        "expected lint failure; stdout: {} stderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("lint issue"));
+}
+
+#[test]
+fn lint_passes_when_only_translated_text_exists() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/app.tsx"),
+        "import { t } from 'i18next'; export const App = () => <div>{t('ui.title')}</div>;",
+    )
+    .unwrap();
+    let config_path = write_config(project);
+
+    let output = run_cli(
+        project,
+        &["--config", config_path.to_str().unwrap(), "lint"],
+    );
+    assert!(
+        output.status.success(),
+        "stdout: {} stderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("No hardcoded strings found"));
+}
+
+#[test]
+fn lint_reports_translations_over_length_budget() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/app.tsx"),
+        "import { t } from 'i18next'; export const App = () => <div>{t('buttons.save')}</div>;",
+    )
+    .unwrap();
+    let config_path = write_config_with_locales(project, &["en", "de"]);
+    let mut config = read_json(&config_path);
+    config["lint"] = json!({ "maxLength": { "buttons.*": 10 }, "maxLengthRatio": 2.0 });
+    fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+    for (locale, save) in [("en", "Save"), ("de", "Speichern")] {
+        fs::create_dir_all(project.join("locales").join(locale)).unwrap();
+        fs::write(
+            project
+                .join("locales")
+                .join(locale)
+                .join("translation.json"),
+            json!({ "buttons": { "save": save } }).to_string(),
+        )
+        .unwrap();
+    }
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "lint",
+            "--fail-on-error",
+        ],
+    );
+    assert!(
+        !output.status.success(),
+        "expected lint failure; stdout: {} stderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("No hardcoded strings found"));
+    assert!(stdout.contains("[de] translation:buttons.save: 9 characters"));
+    assert!(!stdout.contains("[en]"));
+}
+
+#[test]
+fn lint_reports_sensitive_translation_values() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/app.tsx"),
+        "import { t } from 'i18next'; export const App = () => <div>{t('help')}</div>;",
+    )
+    .unwrap();
+    let config_path = write_config(project);
+    write_locale_json(
+        &project.join("locales/en/translation.json"),
+        json!({ "help": "Docs: http://localhost:3000/docs", "title": "Home" }),
+    );
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "lint",
+            "--fail-on-error",
+        ],
+    );
+    assert!(!output.status.success(), "expected lint failure");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("[en] translation:help: matches internal-url rule: \"http://localhost\""),
+        "stdout: {}",
+        stdout
+    );
+    assert!(stdout.contains("Total: 1 sensitive value(s)"));
+}
+
+#[test]
+fn migrate_dry_run_prints_preview_for_existing_turbo_config() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    let config_path = write_config(project);
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "migrate",
+            "--dry-run",
+            "--yes",
+        ],
+    );
+    assert!(
+        output.status.success(),
+        "stdout: {} stderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("変換後プレビュー"));
+    assert!(stdout.contains("dry-run"));
+}
+
+fn run_git<P: AsRef<Path>>(cwd: P, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .current_dir(cwd)
+        .args(args)
+        .output()
+        .expect("failed to run git");
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn hook_staged_extracts_only_staged_files_and_stages_locales() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    run_git(project, &["init", "-q"]);
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(project.join("src/staged.ts"), "t('staged.key');").unwrap();
+    fs::write(project.join("src/unstaged.ts"), "t('unstaged.key');").unwrap();
+    write_locale_json(
+        &project.join("locales/en/translation.json"),
+        json!({ "existing": "Existing" }),
+    );
+    let config_path = write_config(project);
+    run_git(
+        project,
+        &["add", "src/staged.ts", "locales/en/translation.json"],
+    );
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "hook",
+            "--staged",
+        ],
+    );
+    assert!(
+        output.status.success(),
+        "stdout: {}\nstderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json = read_json(&project.join("locales/en/translation.json"));
+    assert_eq!(json["staged"]["key"], "");
+    assert_eq!(json["existing"], "Existing");
+    assert!(json.get("unstaged").is_none());
+
+    let unstaged_changes = run_git(project, &["diff", "--name-only"]);
+    assert!(!unstaged_changes.contains("locales/en/translation.json"));
+}
+
+#[test]
+fn hook_staged_extracts_the_staged_content_not_the_working_copy() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    run_git(project, &["init", "-q"]);
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(project.join("src/app.ts"), "t('staged.key');").unwrap();
+    fs::write(project.join("src/removed.ts"), "t('removed.key');").unwrap();
+    let config_path = write_config(project);
+    run_git(project, &["add", "src/app.ts", "src/removed.ts"]);
+    // Unstaged edits and deletions must not change what the commit extracts
+    fs::write(
+        project.join("src/app.ts"),
+        "t('staged.key');\nt('wip.key');",
+    )
+    .unwrap();
+    fs::remove_file(project.join("src/removed.ts")).unwrap();
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "hook",
+            "--staged",
+        ],
+    );
+    assert!(
+        output.status.success(),
+        "stdout: {}\nstderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json = read_json(&project.join("locales/en/translation.json"));
+    assert_eq!(json["staged"]["key"], "");
+    assert_eq!(json["removed"]["key"], "");
+    assert!(json.get("wip").is_none());
+}
+
+#[test]
+fn hook_staged_rejects_invalid_staged_locale_file() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    run_git(project, &["init", "-q"]);
+    fs::create_dir_all(project.join("locales/en")).unwrap();
+    fs::write(project.join("locales/en/translation.json"), "{ broken").unwrap();
+    let config_path = write_config(project);
+    run_git(project, &["add", "locales/en/translation.json"]);
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "hook",
+            "--staged",
+        ],
+    );
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Staged locale file is invalid"));
+}
+
+#[test]
+fn gate_fails_when_locale_is_below_threshold() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    let config_path = project.join("i18next-turbo.json");
+    fs::write(
+        &config_path,
+        serde_json::to_string_pretty(&json!({
+            "input": ["src/**/*.ts"],
+            "output": "locales",
+            "locales": ["en", "de", "fr"],
+            "releaseGates": { "de": 0.5, "fr": 0.9 }
+        }))
+        .unwrap(),
+    )
+    .unwrap();
+    write_locale_json(
+        &project.join("locales/en/common.json"),
+        json!({ "a": "A", "b": "B" }),
+    );
+    write_locale_json(
+        &project.join("locales/de/common.json"),
+        json!({ "a": "A-de", "b": "" }),
+    );
+    write_locale_json(
+        &project.join("locales/fr/common.json"),
+        json!({ "a": "A-fr" }),
+    );
+
+    let output = run_cli(
+        project,
+        &["--config", config_path.to_str().unwrap(), "gate"],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "stdout: {}", stdout);
+    assert!(stdout.contains("common: 1 missing"), "stdout: {}", stdout);
+    assert!(
+        stderr.contains("Release gate failed for 1 locale(s): fr"),
+        "stderr: {}",
+        stderr
+    );
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "gate",
+            "--locale",
+            "de",
+        ],
+    );
+    assert!(output.status.success());
+}
+
+#[test]
+fn ingest_splits_bundled_resources_into_locale_files() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/i18n.ts"),
+        r#"
+const resources = {
+  en: { translation: { title: 'Title', nav: { home: 'Home' } }, common: { ok: 'OK' } },
+  ja: { translation: { title: 'タイトル' } },
+};
+export default resources;
+"#,
+    )
+    .unwrap();
+    let config_path = write_config_with_locales(project, &["en", "ja"]);
+    write_locale_json(
+        &project.join("locales/en/translation.json"),
+        json!({ "title": "Existing title" }),
+    );
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "ingest",
+            "src/i18n.ts",
+        ],
+    );
+    assert!(
+        output.status.success(),
+        "stdout: {} stderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert_eq!(
+        read_json(&project.join("locales/en/translation.json")),
+        json!({ "title": "Existing title", "nav": { "home": "Home" } })
+    );
+    assert_eq!(
+        read_json(&project.join("locales/en/common.json")),
+        json!({ "ok": "OK" })
+    );
+    assert_eq!(
+        read_json(&project.join("locales/ja/translation.json")),
+        json!({ "title": "タイトル" })
+    );
+}
+
+#[test]
+fn import_from_fills_only_empty_values_from_directory() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path().join("project");
+    let other = tmp.path().join("other");
+    let config_path = project.join("i18next-turbo.json");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(
+        &config_path,
+        serde_json::to_string_pretty(&json!({
+            "input": ["src/**/*.ts"],
+            "output": "locales",
+            "locales": ["en", "de"]
+        }))
+        .unwrap(),
+    )
+    .unwrap();
+    write_locale_json(
+        &project.join("locales/de/common.json"),
+        json!({ "empty": "", "differs": "Lokal", "nested": { "title": "" } }),
+    );
+    write_locale_json(
+        &other.join("locales/de/common.json"),
+        json!({
+            "empty": "Importiert",
+            "differs": "Anders",
+            "nested": { "title": "Titel" },
+            "extra": "Extra"
+        }),
+    );
+
+    let output = run_cli(
+        &project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "import-from",
+            other.to_str().unwrap(),
+        ],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "stdout: {}", stdout);
+    assert!(
+        stdout.contains("de/common.json - imported 2 value(s)"),
+        "stdout: {}",
+        stdout
+    );
+
+    let de = read_json(&project.join("locales/de/common.json"));
+    assert_eq!(
+        de,
+        json!({ "empty": "Importiert", "differs": "Lokal", "nested": { "title": "Titel" } })
+    );
+}
+
+#[test]
+fn import_from_resolves_flat_locale_files_and_fails_without_sources() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path().join("project");
+    let other = tmp.path().join("other");
+    let config_path = project.join("i18next-turbo.json");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(
+        &config_path,
+        serde_json::to_string_pretty(&json!({
+            "input": ["src/**/*.ts"],
+            "preset": "nextjs",
+            "locales": ["en", "de"]
+        }))
+        .unwrap(),
+    )
+    .unwrap();
+    write_locale_json(&project.join("messages/de.json"), json!({ "title": "" }));
+    let config_arg = config_path.to_str().unwrap();
+
+    // Nothing is laid out where the flat layout expects it
+    write_locale_json(
+        &other.join("messages/de/translation.json"),
+        json!({ "title": "Titel" }),
+    );
+    let output = run_cli(
+        &project,
+        &[
+            "--config",
+            config_arg,
+            "import-from",
+            other.to_str().unwrap(),
+        ],
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "stderr: {}", stderr);
+    assert!(
+        stderr.contains("No locale files found in import source")
+            && stderr.contains("messages/de.json"),
+        "stderr: {}",
+        stderr
+    );
+
+    write_locale_json(&other.join("messages/de.json"), json!({ "title": "Titel" }));
+    let output = run_cli(
+        &project,
+        &[
+            "--config",
+            config_arg,
+            "import-from",
+            other.to_str().unwrap(),
+        ],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "stdout: {}", stdout);
+    assert!(
+        stdout.contains("de.json - imported 1 value(s)"),
+        "stdout: {}",
+        stdout
+    );
+    assert_eq!(
+        read_json(&project.join("messages/de.json")),
+        json!({ "title": "Titel" })
+    );
+}
+
+#[test]
+fn migrate_plurals_converts_legacy_suffixes_per_locale() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    let config_path = project.join("i18next-turbo.json");
+    fs::write(
+        &config_path,
+        serde_json::to_string_pretty(&json!({
+            "input": ["src/**/*.ts"],
+            "output": "locales",
+            "locales": ["en", "ru"],
+            "pluralCompat": "v3"
+        }))
+        .unwrap(),
+    )
+    .unwrap();
+    write_locale_json(
+        &project.join("locales/en/common.json"),
+        json!({ "item": "{{count}} item", "item_plural": "{{count}} items" }),
+    );
+    write_locale_json(
+        &project.join("locales/ru/common.json"),
+        json!({ "item_0": "предмет", "item_1": "предмета", "item_2": "предметов" }),
+    );
+
+    let output = run_cli(
+        project,
+        &["--config", config_path.to_str().unwrap(), "migrate-plurals"],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "stdout: {}", stdout);
+
+    assert_eq!(
+        read_json(&project.join("locales/en/common.json")),
+        json!({ "item_one": "{{count}} item", "item_other": "{{count}} items" })
+    );
+    assert_eq!(
+        read_json(&project.join("locales/ru/common.json")),
+        json!({
+            "item_one": "предмет",
+            "item_few": "предмета",
+            "item_many": "предметов",
+            "item_other": "предметов"
+        })
+    );
+}
+
+#[test]
+fn extract_writes_error_report_with_parse_positions() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    let config_path = write_config(project);
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(project.join("src/ok.ts"), "t('hello');\n").unwrap();
+    fs::write(project.join("src/broken.ts"), "const a = 1;\nt('x', {;\n").unwrap();
+
+    let report_path = project.join("reports/errors.json");
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "extract",
+            "--error-report",
+            report_path.to_str().unwrap(),
+        ],
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    assert!(stderr.contains("Top failing files"), "stderr: {}", stderr);
+    assert!(
+        stderr.contains("broken.ts (1 error(s))"),
+        "stderr: {}",
+        stderr
+    );
+
+    let report = read_json(&report_path);
+    assert_eq!(report["errorCount"], json!(1));
+    assert_eq!(report["fileCount"], json!(1));
+    let error = &report["errors"][0];
+    assert_eq!(error["kind"], json!("parse"));
+    assert_eq!(error["line"], json!(2));
+    assert!(error["filePath"].as_str().unwrap().ends_with("broken.ts"));
+}
+
+#[test]
+fn extract_merges_vue_i18n_blocks_into_locale_files() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    let config = json!({
+        "input": ["src/**/*.vue"],
+        "output": "locales",
+        "locales": ["en", "de", "fr"],
+        "functions": ["t"],
+        "extractFromComments": false
+    });
+    let config_path = project.join("i18next-turbo.json");
+    fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/Banner.vue"),
+        r#"<template>
+  <h1 v-t="'banner.title'"></h1>
+  <p
+    :title="$t('banner.hint', {
+      defaultValue: 'Don\'t miss it'
+    })"
+  >{{ $t("banner.body") }}</p>
+</template>
+
+<i18n>
+{
+  "en": { "banner": { "title": "Sale!" } },
+  "de": { "banner": { "title": "Ausverkauf!" } }
+}
+</i18n>
+"#,
+    )
+    .unwrap();
+    write_locale_json(
+        &project.join("locales/de/translation.json"),
+        json!({ "banner": { "title": "Angebot" } }),
+    );
+
+    let output = run_cli(
+        project,
+        &["--config", config_path.to_str().unwrap(), "extract"],
+    );
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let en = read_json(&project.join("locales/en/translation.json"));
+    assert_eq!(en["banner"]["title"], json!("Sale!"));
+    assert_eq!(en["banner"]["hint"], json!("Don't miss it"));
+    assert_eq!(en["banner"]["body"], json!(""));
+    // Existing translations are never overwritten
+    let de = read_json(&project.join("locales/de/translation.json"));
+    assert_eq!(de["banner"]["title"], json!("Angebot"));
+    let fr = read_json(&project.join("locales/fr/translation.json"));
+    assert_eq!(fr["banner"]["title"], json!(""));
+}
+
+#[test]
+fn extract_runs_email_templates_into_their_own_flat_locale_root() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    let config = json!({
+        "input": ["src/**/*.{ts,hbs}"],
+        "output": "locales",
+        "locales": ["en", "de"],
+        "functions": ["t"],
+        "emailTemplates": {
+            "input": ["src/emails/**/*.{mjml,hbs}"],
+            "output": "emails/locales"
+        }
+    });
+    let config_path = project.join("i18next-turbo.json");
+    fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+    fs::create_dir_all(project.join("src/emails/partials")).unwrap();
+    fs::write(
+        project.join("src/app.ts"),
+        "t('nav.home'); tEmail('not.web');\n",
+    )
+    .unwrap();
+    fs::write(
+        project.join("src/emails/welcome.mjml"),
+        r#"<mjml>
+  <mj-body>
+    <mj-text>{{tEmail "welcome.title" defaultValue="Welcome aboard" name=user.name}}</mj-text>
+    {{> footer label=(tEmail "welcome.cta")}}
+  </mj-body>
+</mjml>
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project.join("src/emails/partials/footer.hbs"),
+        "{{!-- {{tEmail \"commented.out\"}} --}}\n<p>{{t \"web.helper\"}} {{tEmail 'footer.legal'}}</p>\n",
+    )
+    .unwrap();
+
+    let output = run_cli(
+        project,
+        &["--config", config_path.to_str().unwrap(), "extract"],
+    );
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let web = read_json(&project.join("locales/en/translation.json"));
+    assert_eq!(web, json!({ "nav": { "home": "" } }));
+    assert!(!project.join("locales/en/email.json").exists());
+
+    let email = read_json(&project.join("emails/locales/en/email.json"));
+    assert_eq!(
+        email,
+        json!({
+            "footer.legal": "",
+            "welcome.cta": "",
+            "welcome.title": "Welcome aboard"
+        })
+    );
+    assert!(project.join("emails/locales/de/email.json").exists());
+}
+
+#[test]
+fn extract_reads_jsx_islands_of_mdx_documents() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    let config = json!({
+        "input": ["docs/**/*.mdx"],
+        "output": "locales",
+        "locales": ["en"],
+        "functions": ["t"],
+        "extractFromComments": false
+    });
+    let config_path = project.join("i18next-turbo.json");
+    fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+    fs::create_dir_all(project.join("docs")).unwrap();
+    fs::write(
+        project.join("docs/intro.mdx"),
+        r#"---
+title: Intro
+---
+import { Trans } from 'react-i18next'
+export const description = t('docs.description', { defaultValue: 'Getting started' })
+
+# Call `t('docs.inline')` anywhere
+
+<Note>
+  <Trans i18nKey="docs.note">Install the CLI first</Trans>
+</Note>
+
+```tsx
+const title = t('docs.sample');
+```
+
+Pages: {t('docs.pages')}
+"#,
+    )
+    .unwrap();
+
+    let output = run_cli(
+        project,
+        &["--config", config_path.to_str().unwrap(), "extract"],
+    );
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let en = read_json(&project.join("locales/en/translation.json"));
+    assert_eq!(
+        en,
+        json!({
+            "docs": {
+                "description": "Getting started",
+                "note": "Install the CLI first",
+                "pages": ""
+            }
+        })
+    );
+}
+
+#[test]
+fn hook_and_import_missing_route_package_keys_to_package_output() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    let config = json!({
+        "input": ["src/**/*.ts", "packages/*/src/**/*.ts"],
+        "output": "locales",
+        "outputs": [{ "match": "packages/ui/**", "output": "packages/ui/locales" }],
+        "locales": ["en", "de"],
+        "functions": ["t"],
+        "extractFromComments": false
+    });
+    let config_path = project.join("i18next-turbo.json");
+    fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::create_dir_all(project.join("packages/ui/src")).unwrap();
+    fs::write(project.join("src/app.ts"), "t('app.title');\n").unwrap();
+    fs::write(
+        project.join("packages/ui/src/button.ts"),
+        "t('ui:button.label');\n",
+    )
+    .unwrap();
+    let config = config_path.to_str().unwrap();
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config,
+            "hook",
+            "src/app.ts",
+            "packages/ui/src/button.ts",
+        ],
+    );
+    assert!(output.status.success(), "{:?}", output);
+    for locale in ["en", "de"] {
+        let app = read_json(&project.join(format!("locales/{}/translation.json", locale)));
+        assert_eq!(app, json!({ "app": { "title": "" } }));
+        let ui = read_json(&project.join(format!("packages/ui/locales/{}/ui.json", locale)));
+        assert_eq!(ui, json!({ "button": { "label": "" } }));
+        assert!(!project.join(format!("locales/{}/ui.json", locale)).exists());
+    }
+
+    // A runtime key of a namespace only the package has goes to the package
+    let log = project.join("missing.jsonl");
+    fs::write(
+        &log,
+        concat!(
+            "{\"ns\":\"ui\",\"key\":\"button.hint\"}\n",
+            "{\"ns\":\"translation\",\"key\":\"app.subtitle\"}\n",
+        ),
+    )
+    .unwrap();
+    let output = run_cli(
+        project,
+        &["--config", config, "import-missing", log.to_str().unwrap()],
+    );
+    assert!(output.status.success(), "{:?}", output);
+    for locale in ["en", "de"] {
+        let ui = read_json(&project.join(format!("packages/ui/locales/{}/ui.json", locale)));
+        assert_eq!(ui["button"]["hint"], "", "{}", ui);
+        let app = read_json(&project.join(format!("locales/{}/translation.json", locale)));
+        assert_eq!(app["app"]["subtitle"], "", "{}", app);
+        assert!(!project.join(format!("locales/{}/ui.json", locale)).exists());
+    }
+}
+
+#[test]
+fn extract_routes_package_keys_to_package_output() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    let config = json!({
+        "input": ["src/**/*.ts", "packages/*/src/**/*.ts"],
+        "output": "locales",
+        "outputs": [{ "match": "packages/ui/**", "output": "packages/ui/locales" }],
+        "locales": ["en", "de"],
+        "functions": ["t"],
+        "extractFromComments": false
+    });
+    let config_path = project.join("i18next-turbo.json");
+    fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::create_dir_all(project.join("packages/ui/src")).unwrap();
+    fs::write(project.join("src/app.ts"), "t('app.title');\n").unwrap();
+    fs::write(
+        project.join("packages/ui/src/button.ts"),
+        "t('button.label');\n",
+    )
+    .unwrap();
+
+    let output = run_cli(
+        project,
+        &["--config", config_path.to_str().unwrap(), "extract"],
+    );
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    for locale in ["en", "de"] {
+        let app = read_json(&project.join(format!("locales/{}/translation.json", locale)));
+        assert_eq!(app, json!({ "app": { "title": "" } }));
+        let ui =
+            read_json(&project.join(format!("packages/ui/locales/{}/translation.json", locale)));
+        assert_eq!(ui, json!({ "button": { "label": "" } }));
+    }
+
+    // check, status and typegen read every root against its own keys
+    let ui_en = project.join("packages/ui/locales/en/translation.json");
+    write_locale_json(&ui_en, json!({ "button": { "label": "OK", "old": "" } }));
+    let config = config_path.to_str().unwrap();
+    let output = run_cli(project, &["--config", config, "check", "--dry-run"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("[translation] button.old") && stdout.contains("Found 1 dead key(s)"),
+        "stdout: {}",
+        stdout
+    );
+    let output = run_cli(project, &["--config", config, "status", "--locale", "en"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("en: 0 missing"), "stdout: {}", stdout);
+    assert!(stdout.contains("1 dead"), "stdout: {}", stdout);
+    let output = run_cli(
+        project,
+        &["--config", config, "typegen", "--output", "types.d.ts"],
+    );
+    assert!(output.status.success(), "{:?}", output);
+    let types = fs::read_to_string(project.join("types.d.ts")).unwrap();
+    assert!(
+        types.contains("title") && types.contains("label"),
+        "{}",
+        types
+    );
+
+    // A root no source maps to any more is pruned like the others
+    fs::remove_file(project.join("packages/ui/src/button.ts")).unwrap();
+    let output = run_cli(project, &["--config", config, "extract"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(read_json(&ui_en), json!({}));
+}
+
+#[test]
+fn extract_and_check_round_trip_prefixed_merged_layout() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/app.ts"),
+        "t('common:button.save');\nt('home:title');",
+    )
+    .unwrap();
+    let config_path = project.join("i18next-turbo.json");
+    fs::write(
+        &config_path,
+        serde_json::to_string_pretty(&json!({
+            "input": ["src/**/*.ts"],
+            "output": "locales",
+            "locales": ["en"],
+            "functions": ["t"],
+            "mergeNamespaces": true,
+            "mergedNamespaceFilename": "messages.{{lng}}",
+            "mergedNamespaceLayout": "prefixed",
+            "mergedNestingDepth": 1
+        }))
+        .unwrap(),
+    )
+    .unwrap();
+
+    let output = run_cli(
+        project,
+        &["--config", config_path.to_str().unwrap(), "extract"],
+    );
+    assert!(
+        output.status.success(),
+        "stdout: {} stderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let locale = read_json(&project.join("locales/en/messages.en.json"));
+    assert!(locale["common:button.save"].is_string());
+    assert!(locale["home:title"].is_string());
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "check",
+            "--dry-run",
+        ],
+    );
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("No dead keys found"), "stdout: {}", stdout);
+
+    let types_out = project.join("types.d.ts");
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "typegen",
+            "--output",
+            types_out.to_str().unwrap(),
+            "--default-locale",
+            "en",
+            "--locales-dir",
+            "locales",
+        ],
+    );
+    assert!(output.status.success());
+    let content = fs::read_to_string(&types_out).unwrap();
+    assert!(content.contains("interface Common"));
+    assert!(content.contains("\"button.save\""));
+}
+
+#[test]
+fn filter_flags_scope_extract_and_check_to_one_feature() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src/features/auth")).unwrap();
+    fs::write(
+        project.join("src/features/auth/login.ts"),
+        "t('auth.login');\nt('auth.logout');",
+    )
+    .unwrap();
+    fs::write(project.join("src/app.ts"), "t('home.title');").unwrap();
+    let config_path = write_config(project);
+
+    let locale_path = project.join("locales/en/translation.json");
+    write_locale_json(
+        &locale_path,
+        json!({
+            "auth": { "login": "Login", "stale": "Old" },
+            "home": { "title": "Home", "stale": "Old" }
+        }),
+    );
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "check",
+            "--dry-run",
+            "--filter-key",
+            "auth.*",
+        ],
+    );
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Found 1 dead key(s)"), "stdout: {}", stdout);
+    assert!(stdout.contains("auth.stale"));
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "check",
+            "--filter-file",
+            "src/features/auth/**",
+        ],
+    );
+    assert!(!output.status.success());
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "extract",
+            "--filter-file",
+            "src/features/auth/**",
+        ],
+    );
+    assert!(
+        output.status.success(),
+        "stdout: {} stderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let locale = read_json(&locale_path);
+    assert!(locale["auth"]["logout"].is_string());
+    assert_eq!(locale["auth"]["stale"], "Old");
+    assert_eq!(locale["home"]["stale"], "Old");
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "extract",
+            "--filter-file",
+            "src/features/auth/**",
+            "--filter-key",
+            "auth.*",
+        ],
+    );
+    assert!(output.status.success());
+    let locale = read_json(&locale_path);
+    assert!(locale["auth"].get("stale").is_none());
+    assert_eq!(locale["auth"]["login"], "Login");
+    assert_eq!(locale["home"]["stale"], "Old");
+    assert_eq!(locale["home"]["title"], "Home");
+}
+
+#[test]
+fn config_show_resolved_reports_source_of_each_value() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    let config_path = write_config(project);
+
+    let output = Command::new(cli_bin())
+        .current_dir(project)
+        .env("I18NEXT_TURBO_LOCALES", "en,de")
+        .args([
+            "--config",
+            config_path.to_str().unwrap(),
+            "--set",
+            "output=public/locales",
+            "config",
+            "show",
+            "--resolved",
+        ])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "stdout: {} stderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(r#"output: "public/locales"  (--set)"#));
+    assert!(stdout.contains(r#"locales: ["en","de"]  (env I18NEXT_TURBO_LOCALES)"#));
+    assert!(stdout.contains(r#"functions: ["t"]  (config file)"#));
+    assert!(stdout.contains(r#"keySeparator: "."  (default)"#));
+}
+
+#[test]
+fn extract_file_reports_keys_with_locations_for_one_file() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    let config_path = write_config(project);
+    // Outside the configured input globs
+    fs::create_dir_all(project.join("scripts")).unwrap();
+    fs::write(
+        project.join("scripts/tool.ts"),
+        "import { t } from 'i18next';\nexport const title = t('tool.title', { defaultValue: 'Tool' });\nt('common:save');\n",
+    )
+    .unwrap();
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "extract-file",
+            "scripts/tool.ts",
+            "--json",
+        ],
+    );
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["keys"][0]["key"], "tool.title");
+    assert_eq!(report["keys"][0]["defaultValue"], "Tool");
+    assert_eq!(
+        report["keys"][0]["span"],
+        json!({ "line": 2, "column": 22, "endLine": 2, "endColumn": 63 })
+    );
+    assert_eq!(report["keys"][0]["sourceKey"], "tool.title");
+    assert_eq!(report["keys"][1]["namespace"], "common");
+    assert_eq!(report["keys"][1]["span"]["line"], 3);
+    assert!(report["elapsedMs"].is_number());
+    assert!(!project.join("locales").exists());
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "extract-file",
+            "scripts/tool.ts",
+        ],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("2:22  tool.title  (default: \"Tool\")"));
+    assert!(stdout.contains("Extracted 2 keys in"));
+
+    let mut config: Value =
+        serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    config["keyTransforms"] = json!([{ "stripPrefix": "tool." }]);
+    fs::write(&config_path, config.to_string()).unwrap();
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "extract-file",
+            "scripts/tool.ts",
+            "--json",
+        ],
+    );
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["keys"][0]["key"], "title");
+    assert_eq!(report["keys"][0]["sourceKey"], "tool.title");
+    assert_eq!(report["keys"][0]["span"]["column"], 22);
+}
+
+#[test]
+fn watch_configs_runs_one_labelled_watcher_per_config() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    for package in ["packages/ui", "packages/web"] {
+        let dir = project.join(package);
+        fs::create_dir_all(&dir).unwrap();
+        // No source directory: each watcher stops right after starting
+        write_config(&dir);
+    }
+
+    let output = run_cli(project, &["watch", "--workspace"]);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stdout.contains("[packages/ui] packages/ui/src/**/*.ts, packages/ui/src/**/*.tsx -> packages/ui/locales"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(stdout.contains("[packages/web]"));
+    assert!(stderr.contains("[packages/web] Watcher stopped: No valid directories"));
+    assert!(stderr.contains("2 of 2 watcher(s) stopped with errors"));
+}
+
+#[test]
+fn status_prints_namespace_table_per_locale_and_enforces_min_completeness() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/app.ts"),
+        "t('common:ok'); t('common:cancel'); t('home.title');",
+    )
+    .unwrap();
+    let config_path = write_config_with_locales(project, &["en", "de"]);
+    write_locale_json(
+        &project.join("locales/en/common.json"),
+        json!({ "ok": "OK", "cancel": "Cancel" }),
+    );
+    write_locale_json(
+        &project.join("locales/en/translation.json"),
+        json!({ "home": { "title": "Home" } }),
+    );
+    write_locale_json(
+        &project.join("locales/de/common.json"),
+        json!({ "ok": "OK", "cancel": "" }),
+    );
+
+    let output = run_cli(
+        project,
+        &["--config", config_path.to_str().unwrap(), "status"],
+    );
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Checking locales: en, de"), "{}", stdout);
+    assert!(stdout.contains("Locale: de"));
+    assert!(stdout.contains("common            2       2        0     0     50.0%"));
+    assert!(stdout.contains("translation       1       0        1     0      0.0%"));
+    assert!(stdout.contains("de: 1 missing"));
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "status",
+            "--min-completeness",
+            "90",
+        ],
+    );
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("de/common 50.0%, de/translation 0.0%"),
+        "{}",
+        stderr
+    );
+    assert!(!stderr.contains("en/"));
+}
+
+#[test]
+fn extract_skips_generated_files_and_disabled_regions_silently() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src/__generated__")).unwrap();
+    fs::write(
+        project.join("src/app.ts"),
+        "t('kept');\n/* i18next-extract-disable */\nt('vendored');\n/* i18next-extract-enable */\n",
+    )
+    .unwrap();
+    fs::write(
+        project.join("src/__generated__/schema.ts"),
+        "t('generated.key');\nexport const broken = {;\n",
+    )
+    .unwrap();
+    let config_path = write_config(project);
+    let mut config = read_json(&config_path);
+    config["generatedFiles"] = json!(["src/__generated__/**"]);
+    fs::write(&config_path, config.to_string()).unwrap();
+
+    let output = run_cli(
+        project,
+        &["--config", config_path.to_str().unwrap(), "extract"],
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {}", stderr);
+    assert!(!stderr.contains("schema.ts"), "stderr: {}", stderr);
+
+    let json = read_json(&project.join("locales/en/translation.json"));
+    assert_eq!(json, json!({ "kept": "" }));
+}
+
+#[test]
+fn extract_writes_metrics_file_as_json_or_prometheus() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/app.ts"),
+        "t('home.title'); t('common:save'); t(`status.${state}`);",
+    )
+    .unwrap();
+    let config_path = write_config(project);
+
+    for file in ["metrics/extract.json", "metrics/extract.prom"] {
+        let output = run_cli(
+            project,
+            &[
+                "--config",
+                config_path.to_str().unwrap(),
+                "extract",
+                "--metrics-file",
+                file,
+            ],
+        );
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    let json = read_json(&project.join("metrics/extract.json"));
+    assert_eq!(json["files"], 1);
+    assert_eq!(json["keys"], 2);
+    assert_eq!(json["keysAdded"], 2);
+    assert_eq!(json["dynamicKeys"], 1);
+    assert_eq!(
+        json["keysByNamespace"],
+        json!({ "common": 1, "translation": 1 })
+    );
+    assert!(json["durationsMs"]["total"].as_f64().unwrap() > 0.0);
+
+    let prom = fs::read_to_string(project.join("metrics/extract.prom")).unwrap();
+    assert!(prom.contains("i18next_turbo_keys 2\n"), "{}", prom);
+    // Second run finds the keys already synced
+    assert!(prom.contains("i18next_turbo_keys_added 0\n"), "{}", prom);
+    assert!(prom.contains("i18next_turbo_phase_duration_seconds{phase=\"total\"}"));
+}
+
+#[test]
+fn browse_applies_queued_delete_on_quit() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(project.join("src/app.ts"), "t('home.title');\n").unwrap();
+    let config_path = write_config_with_locales(project, &["en", "de"]);
+    write_locale_json(
+        &project.join("locales/en/translation.json"),
+        json!({ "home": { "title": "Home", "old": "Old" } }),
+    );
+    write_locale_json(
+        &project.join("locales/de/translation.json"),
+        json!({ "home": { "title": "Start", "old": "Alt" } }),
+    );
+
+    let mut child = Command::new(cli_bin())
+        .current_dir(project)
+        .args(["--config", config_path.to_str().unwrap(), "browse"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"search home\n2\nd 1\nquit\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "stdout: {}", stdout);
+    assert!(stdout.contains("translation:home.old  [2/2 locales, 0 usage(s)]"));
+    assert!(stdout.contains("src/app.ts:1:1"), "stdout: {}", stdout);
+    assert!(stdout.contains("[1/1] delete key translation:home.old"));
+    assert!(stdout.contains("Updated 2 locale file(s)"));
+
+    for locale in ["en", "de"] {
+        let json = read_json(&project.join(format!("locales/{}/translation.json", locale)));
+        assert!(json["home"].get("old").is_none());
+        assert!(json["home"]["title"].is_string());
+    }
+}
+
+#[test]
+fn check_and_rename_emit_plans_applied_with_plan_apply() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(project.join("src/app.ts"), "t('home.title');\n").unwrap();
+    let config_path = write_config_with_locales(project, &["en", "de"]);
+    for locale in ["en", "de"] {
+        write_locale_json(
+            &project.join(format!("locales/{}/translation.json", locale)),
+            json!({ "home": { "title": "Home", "old": "Old" } }),
+        );
+    }
+    let config = config_path.to_str().unwrap();
+
+    let output = Command::new(cli_bin())
+        .current_dir(project)
+        .args([
+            "--config",
+            config,
+            "check",
+            "--emit-plan",
+            "plans/dead.json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let plan = read_json(&project.join("plans/dead.json"));
+    assert_eq!(
+        plan,
+        json!({
+            "version": 1,
+            "changes": [
+                { "op": "deleteKey", "key": "translation:home.old", "locales": ["en"] }
+            ]
+        })
+    );
+    let en = project.join("locales/en/translation.json");
+    assert_eq!(read_json(&en)["home"]["old"], "Old");
+
+    let output = Command::new(cli_bin())
+        .current_dir(project)
+        .args([
+            "--config",
+            config,
+            "rename-key",
+            "home.title",
+            "home.heading",
+            "--emit-plan",
+            "rename.json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        read_json(&project.join("rename.json"))["changes"][0],
+        json!({ "op": "renameKey", "from": "home.title", "to": "home.heading" })
+    );
+    assert_eq!(
+        fs::read_to_string(project.join("src/app.ts")).unwrap(),
+        "t('home.title');\n"
+    );
+
+    let output = Command::new(cli_bin())
+        .current_dir(project)
+        .args(["--config", config, "plan", "show", "plans/dead.json"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("1. delete key translation:home.old (en)"));
+
+    let output = Command::new(cli_bin())
+        .current_dir(project)
+        .args(["--config", config, "plan", "apply", "plans/dead.json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(read_json(&en)["home"].get("old").is_none());
+    assert_eq!(
+        read_json(&project.join("locales/de/translation.json"))["home"]["old"],
+        "Old"
+    );
+}
+
+#[test]
+fn sync_and_migrate_plurals_emit_plans_applied_with_plan_apply() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    let config_path = write_config_with_locales(project, &["en", "de"]);
+    let config = config_path.to_str().unwrap();
+    write_locale_json(
+        &project.join("locales/en/translation.json"),
+        json!({ "home": { "title": "Home" }, "item": "{{count}} item", "item_plural": "{{count}} items" }),
+    );
+    let de = project.join("locales/de/translation.json");
+    write_locale_json(
+        &de,
+        json!({ "home": { "old": "Alt" }, "item": "{{count}} Ding", "item_plural": "{{count}} Dinge" }),
+    );
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config,
+            "sync",
+            "--remove-unused",
+            "--emit-plan",
+            "sync.json",
+        ],
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        read_json(&project.join("sync.json"))["changes"],
+        json!([
+            { "op": "setValue", "locale": "de", "key": "translation:home.title", "value": "" },
+            { "op": "deleteKey", "key": "translation:home.old", "locales": ["de"] }
+        ])
+    );
+    assert_eq!(read_json(&de)["home"], json!({ "old": "Alt" }));
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config,
+            "migrate-plurals",
+            "--emit-plan",
+            "plurals.json",
+        ],
+    );
+    assert!(output.status.success());
+    let changes = read_json(&project.join("plurals.json"))["changes"].clone();
+    assert!(changes.as_array().unwrap().contains(
+        &json!({ "op": "setValue", "locale": "de", "key": "translation:item_other", "value": "{{count}} Dinge" })
+    ));
+    assert!(changes.as_array().unwrap().contains(
+        &json!({ "op": "deleteKey", "key": "translation:item_plural", "locales": ["de"] })
+    ));
+    assert!(read_json(&de).get("item_plural").is_some());
+
+    for plan in ["sync.json", "plurals.json"] {
+        let output = run_cli(project, &["--config", config, "plan", "apply", plan]);
+        assert!(output.status.success());
+    }
+    assert_eq!(
+        read_json(&de),
+        json!({
+            "home": { "title": "" },
+            "item_one": "{{count}} Ding",
+            "item_other": "{{count}} Dinge"
+        })
+    );
+}
+
+#[test]
+fn extract_minify_flag_writes_single_line_files_reported_by_size() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/app.ts"),
+        "t('home.title'); t('home.cta');",
+    )
+    .unwrap();
+    let config_path = write_config_with_locales(project, &["en", "de"]);
+    let config = config_path.to_str().unwrap();
+    let en = project.join("locales/en/translation.json");
+
+    assert!(run_cli(project, &["--config", config, "extract"])
+        .status
+        .success());
+    assert!(fs::read_to_string(&en).unwrap().ends_with("}\n"));
+
+    let output = run_cli(project, &["--config", config, "extract", "--minify"]);
+    assert!(output.status.success());
+    assert_eq!(
+        fs::read_to_string(&en).unwrap(),
+        r#"{"home":{"cta":"","title":""}}"#
+    );
+
+    let output = Command::new(cli_bin())
+        .current_dir(project)
+        .env("I18NEXT_TURBO_MINIFY", "false")
+        .args(["--config", config, "extract"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(fs::read_to_string(&en)
+        .unwrap()
+        .contains("\n  \"home\": {\n"));
+
+    let output = run_cli(project, &["--config", config, "size", "--locale", "en"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("[en]"), "stdout: {}", stdout);
+    let raw = fs::metadata(&en).unwrap().len();
+    assert!(stdout.contains(&format!("translation  {:>10}", format!("{} B", raw))));
+    assert!(stdout.contains("Total: 1 file(s)"));
+}
+
+#[test]
+fn extract_shards_large_namespaces_and_readers_merge_them() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/app.ts"),
+        "t('home.title'); t('home.cta'); t('nav.back'); t('nav.next');",
+    )
+    .unwrap();
+    let config_path = write_config_with_locales(project, &["en"]);
+    let mut config = read_json(&config_path);
+    config["shardMaxKeys"] = json!(2);
+    fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+    let config = config_path.to_str().unwrap();
+    let locales = project.join("locales/en");
+
+    let output = run_cli(project, &["--config", config, "extract"]);
+    assert!(output.status.success());
+    assert!(!locales.join("translation.json").exists());
+    assert_eq!(
+        read_json(&locales.join("translation.shard-1.json")),
+        json!({ "home": { "cta": "", "title": "" } })
+    );
+    assert_eq!(
+        read_json(&locales.join("translation.shard-2.json")),
+        json!({ "nav": { "back": "", "next": "" } })
+    );
+
+    // An unused key in a shard is dead; keys of every shard count as one namespace
+    write_locale_json(
+        &locales.join("translation.shard-2.json"),
+        json!({ "nav": { "back": "", "next": "", "old": "" } }),
+    );
+    let output = run_cli(project, &["--config", config, "check", "--dry-run"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("[translation] nav.old") && stdout.contains("Found 1 dead key(s)"),
+        "stdout: {}",
+        stdout
+    );
+
+    let types_out = project.join("types.d.ts");
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config,
+            "typegen",
+            "--output",
+            types_out.to_str().unwrap(),
+            "--default-locale",
+            "en",
+            "--locales-dir",
+            "locales",
+        ],
+    );
+    assert!(output.status.success());
+    let types = fs::read_to_string(&types_out).unwrap();
+    assert!(
+        types.contains("title") && types.contains("back"),
+        "{}",
+        types
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn doctor_reports_escaping_symlinks_and_case_collisions() {
+    use std::os::unix::fs::symlink;
+
+    let tmp = tempdir().unwrap();
+    let project = tmp.path().join("app");
+    let shared = tmp.path().join("shared");
+    fs::create_dir_all(project.join("src/features")).unwrap();
+    fs::create_dir_all(&shared).unwrap();
+    fs::write(project.join("src/features/home.ts"), "t('home.title');").unwrap();
+    fs::write(shared.join("outside.ts"), "t('outside.key');").unwrap();
+    symlink(project.join("src/features"), project.join("src/alias")).unwrap();
+    symlink(&shared, project.join("src/shared")).unwrap();
+    symlink(project.join("src/cycle"), project.join("src/cycle")).unwrap();
+    let config_path = write_config_with_locales(&project, &["en"]);
+    let config = config_path.to_str().unwrap();
+
+    let output = run_cli(&project, &["--config", config, "extract"]);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let en = read_json(&project.join("locales/en/translation.json"));
+    assert_eq!(en, json!({ "home": { "title": "" } }));
+
+    fs::write(project.join("locales/en/Translation.json"), "{}").unwrap();
+    let output = run_cli(&project, &["--config", config, "doctor"]);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("outside.ts: symlink escapes the project root"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(stdout.contains("Translation.json, translation.json only differ in case"));
+    assert!(!stdout.contains("alias"));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("problem(s) found"), "stderr: {}", stderr);
+}
+
+#[test]
+fn defaults_reports_drift_and_reconciles_either_way() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    let app = project.join("src/app.ts");
+    fs::write(
+        &app,
+        "t('save', { defaultValue: 'Save changes' });\nt('cancel', 'Cancel');\nt('draft', { defaultValue: 'Save changes' });\n",
+    )
+    .unwrap();
+    let config_path = write_config(project);
+    let config = config_path.to_str().unwrap();
+    let en = project.join("locales/en/translation.json");
+    write_locale_json(
+        &en,
+        json!({ "save": "Save", "cancel": "Cancel", "draft": "Save changes" }),
+    );
+
+    let output = run_cli(project, &["--config", config, "defaults"]);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("[translation] save"), "stdout: {}", stdout);
+    assert!(stdout.contains("code:    \"Save changes\""));
+    assert!(stdout.contains("catalog: \"Save\""));
+    assert!(!stdout.contains("cancel"));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("1 default value mismatch(es)"),
+        "stderr: {}",
+        stderr
+    );
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config,
+            "defaults",
+            "--prefer-catalog",
+            "--dry-run",
+        ],
+    );
+    assert!(output.status.success());
+    assert!(fs::read_to_string(&app).unwrap().contains("'Save changes'"));
+
+    let output = run_cli(
+        project,
+        &["--config", config, "defaults", "--prefer-catalog"],
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        fs::read_to_string(&app).unwrap(),
+        "t('save', { defaultValue: 'Save' });\nt('cancel', 'Cancel');\nt('draft', { defaultValue: 'Save changes' });\n"
+    );
+    assert!(run_cli(project, &["--config", config, "defaults"])
+        .status
+        .success());
+
+    write_locale_json(&en, json!({ "save": "Save now", "cancel": "Cancel" }));
+    fs::write(&app, "t('save', { defaultValue: 'Save changes' });\n").unwrap();
+    let output = run_cli(project, &["--config", config, "defaults", "--prefer-code"]);
+    assert!(output.status.success());
+    assert_eq!(read_json(&en)["save"], "Save changes");
+}
+
+#[test]
+fn extract_explain_traces_why_a_key_is_skipped() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(project.join("src/ok.ts"), "t('cart.title');\n").unwrap();
+    fs::write(
+        project.join("src/skipped.ts"),
+        "translate('cart.title');\n// i18next-extract-disable-next-line\nt('cart.title');\n",
+    )
+    .unwrap();
+    fs::write(
+        project.join("src/scoped.tsx"),
+        "const { t } = useTranslation('shop');\nt('cart.title');\n",
+    )
+    .unwrap();
+    fs::write(
+        project.join("src/unresolved.tsx"),
+        "const { t } = useTranslation(ns);\nt('cart.title');\nt(`cart.${name}`);\n",
+    )
+    .unwrap();
+    let config_path = write_config(project);
+    let config = config_path.to_str().unwrap();
+
+    let output = run_cli(
+        project,
+        &["--config", config, "extract", "--explain", "cart.title"],
+    );
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Key: translation:cart.title"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(stdout.contains("✓ 1:1: extracted"));
+    assert!(stdout.contains("✗ 1:12: `translate(...)` is not in functions (t)"));
+    assert!(stdout.contains("✗ 3:1: disabled by a `i18next-extract-disable-next-line` comment"));
+    assert!(stdout.contains("✗ 2:1: extracted into namespace 'shop'"));
+    assert!(stdout.contains("~ 3:1: dynamic template literal (cart.*) can produce this key"));
+    assert!(stdout.contains("Extracted from 2 of 4 file(s) mentioning 'cart.title'."));
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config,
+            "extract",
+            "--explain",
+            "shop:cart.title",
+        ],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("✗ 2:1: the ns option or the useTranslation/getFixedT namespace is not a string literal, so the key went to namespace 'translation'"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(stdout.contains("Extracted from 1 of 4 file(s) mentioning 'cart.title'."));
+    assert!(!project.join("locales").exists());
+}
+
+#[test]
+fn extract_verify_determinism_compares_two_runs_without_writing() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    for index in 0..12 {
+        fs::write(
+            project.join(format!("src/page{}.ts", index)),
+            format!(
+                "t('shared', 'from {}');\nt('page{}.title');\n",
+                index, index
+            ),
+        )
+        .unwrap();
+    }
+    let config_path = write_config_with_locales(project, &["en", "de"]);
+    let en = project.join("locales/en/translation.json");
+    write_locale_json(&en, json!({ "kept": "Kept" }));
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "extract",
+            "--verify-determinism",
+        ],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "stdout: {}", stdout);
+    assert!(
+        stdout.contains("2 locale file(s) are byte-identical across both runs"),
+        "stdout: {}",
+        stdout
+    );
+    assert_eq!(read_json(&en), json!({ "kept": "Kept" }));
+    assert!(!project.join("locales/de").exists());
+}
+
+/// Requests received by [`mock_tms`] as (`METHOD /path`, body)
+type RecordedRequests = std::sync::Arc<std::sync::Mutex<Vec<(String, String)>>>;
+
+/// Serve `responses` (by `METHOD /path`; POST answers `{}`, anything else 404)
+/// on a local port and record every request
+fn mock_tms(responses: Vec<(&'static str, Value)>) -> (String, RecordedRequests) {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::sync::{Arc, Mutex};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&requests);
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = header.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            let mut parts = request_line.split_whitespace();
+            let request = format!(
+                "{} {}",
+                parts.next().unwrap_or_default(),
+                parts.next().unwrap_or_default()
+            );
+            let response = responses
+                .iter()
+                .find(|(route, _)| *route == request)
+                .map(|(_, value)| value.to_string());
+            recorded
+                .lock()
+                .unwrap()
+                .push((request, String::from_utf8(body).unwrap()));
+            let (status, body) = match response {
+                Some(body) => ("200 OK", body),
+                None if request_line.starts_with("POST") => ("200 OK", "{}".to_string()),
+                None => ("404 Not Found", "{}".to_string()),
+            };
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+    });
+    (base_url, requests)
+}
+
+#[test]
+fn pull_merges_tms_translations_and_push_sends_new_keys() {
+    let (base_url, requests) = mock_tms(vec![
+        (
+            "GET /proj/latest/de/translation",
+            json!({
+                "home": { "title": "Startseite", "subtitle": "Untertitel" },
+                "remote_only": "Nur entfernt"
+            }),
+        ),
+        (
+            "GET /proj/latest/en/translation",
+            json!({ "home.title": "Home", "home.subtitle": "Subtitle" }),
+        ),
+    ]);
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    let config_path = project.join("i18next-turbo.json");
+    fs::write(
+        &config_path,
+        json!({
+            "input": ["src/**/*.ts"],
+            "output": "locales",
+            "locales": ["en", "de"],
+            "tms": {
+                "provider": "locize",
+                "projectId": "proj",
+                "apiKey": "secret",
+                "baseUrl": base_url
+            }
+        })
+        .to_string(),
+    )
+    .unwrap();
+    write_locale_json(
+        &project.join("locales/en/translation.json"),
+        json!({ "home": { "title": "Home", "subtitle": "Subtitle", "cta": "Start" } }),
+    );
+    let de_path = project.join("locales/de/translation.json");
+    fs::create_dir_all(de_path.parent().unwrap()).unwrap();
+    fs::write(
+        &de_path,
+        "{\n    \"home\": {\n        \"cta\": \"\",\n        \"subtitle\": \"Alt\",\n        \"title\": \"\"\n    }\n}\n",
+    )
+    .unwrap();
+    let config = config_path.to_str().unwrap();
+
+    let output = run_cli(project, &["--config", config, "pull", "--locale", "de"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Updated 2 value(s) from Locize"));
+    // Local structure and indentation are kept; remote-only keys are not added
+    assert_eq!(
+        fs::read_to_string(&de_path).unwrap(),
+        "{\n    \"home\": {\n        \"cta\": \"\",\n        \"subtitle\": \"Untertitel\",\n        \"title\": \"Startseite\"\n    }\n}\n"
+    );
+
+    let output = run_cli(project, &["--config", config, "push"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Pushed 1 new key(s) to Locize"));
+    let requests = requests.lock().unwrap();
+    let (_, body) = requests
+        .iter()
+        .find(|(request, _)| request == "POST /missing/proj/latest/en/translation")
+        .expect("no push request");
+    assert_eq!(
+        serde_json::from_str::<Value>(body).unwrap(),
+        json!({ "home.cta": "Start" })
+    );
+}
+
+#[test]
+fn extract_reports_keys_with_different_defaults_in_code_and_comments() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/app.ts"),
+        "t('title', { defaultValue: 'Title' });\n// t('title', 'Heading')\n",
+    )
+    .unwrap();
+    let config_path = project.join("i18next-turbo.json");
+    fs::write(
+        &config_path,
+        json!({
+            "input": ["src/**/*.ts"],
+            "output": "locales",
+            "locales": ["en"],
+            "extractFromComments": true
+        })
+        .to_string(),
+    )
+    .unwrap();
+    let config = config_path.to_str().unwrap();
+
+    let output = run_cli(
+        project,
+        &["--config", config, "extract", "--strict-duplicates"],
+    );
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("code:    \"Title\" at src/app.ts:1"),
+        "{}",
+        stderr
+    );
+    assert!(
+        stderr.contains("comment: \"Heading\" at src/app.ts:2"),
+        "{}",
+        stderr
+    );
+    assert!(stderr.contains("--strict-duplicates enabled"), "{}", stderr);
+    assert!(!project.join("locales/en/translation.json").exists());
+
+    let output = run_cli(project, &["--config", config, "extract"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("different default values"));
+    assert_eq!(
+        read_json(&project.join("locales/en/translation.json")),
+        json!({ "title": "Title" })
+    );
+}
+
+#[test]
+fn extract_dynamic_key_severity_is_configurable_per_file() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src/icons")).unwrap();
+    fs::write(
+        project.join("src/app.ts"),
+        "t('title');\nt(`status.${state}`);\n",
+    )
+    .unwrap();
+    fs::write(project.join("src/icons/icon.ts"), "t(`icon.${name}`);\n").unwrap();
+    let config_path = project.join("i18next-turbo.json");
+    let write_config = |severity: &str| {
+        fs::write(
+            &config_path,
+            json!({
+                "input": ["src/**/*.ts"],
+                "output": "locales",
+                "locales": ["en"],
+                "dynamicKeySeverity": severity,
+                "dynamicKeyRules": [{ "match": "src/icons/**", "severity": "off" }]
+            })
+            .to_string(),
+        )
+        .unwrap();
+    };
+    let config = config_path.to_str().unwrap();
+
+    write_config("error");
+    let output = run_cli(project, &["--config", config, "extract"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Error: Dynamic template literal found at src/app.ts:2:1"),
+        "{}",
+        stderr
+    );
+    assert!(!stderr.contains("icon.ts"), "{}", stderr);
+    assert!(!project.join("locales/en/translation.json").exists());
+
+    write_config("off");
+    let output = run_cli(
+        project,
+        &["--config", config, "extract", "--fail-on-warnings"],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("Dynamic template literal"));
+}
+
+#[test]
+fn extract_follows_key_maps_into_configured_functions() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/labels.ts"),
+        r#"const labels = defineLabels({ save: t('label.save') });
+const keys = Object.freeze({ open: 'label.open', close: k('label.close') } as const);
+export const status = { draft: 'status.draft', live: 'status.live' } satisfies Record<string, string>;
+tFromKey(keys.open);
+tFromKey(status[state]);
+"#,
+    )
+    .unwrap();
+    let config_path = project.join("i18next-turbo.json");
+    fs::write(
+        &config_path,
+        json!({
+            "input": ["src/**/*.ts"],
+            "output": "locales",
+            "locales": ["en"],
+            "functions": ["t", "tFromKey", "defineLabels"],
+            "keyProducingFunctions": ["k"]
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let output = run_cli(
+        project,
+        &["--config", config_path.to_str().unwrap(), "extract"],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        read_json(&project.join("locales/en/translation.json")),
+        json!({
+            "label": { "close": "", "open": "", "save": "" },
+            "status": { "draft": "", "live": "" }
+        })
+    );
+}
+
+#[test]
+fn extract_and_lint_read_source_from_stdin() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    let config_path = write_config(project);
+    let config = config_path.to_str().unwrap();
+    let run_with_stdin = |args: &[&str], source: &str| {
+        let mut child = Command::new(cli_bin())
+            .current_dir(project)
+            .args(["--config", config])
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(source.as_bytes())
+            .unwrap();
+        child.wait_with_output().unwrap()
+    };
+    let source = "export const App = () => <p title={t('app.title')}>Hello world</p>;\n";
+
+    let output = run_with_stdin(
+        &["extract", "--stdin", "--stdin-filepath", "src/App.tsx"],
+        source,
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["file"], "src/App.tsx");
+    assert_eq!(report["keys"][0]["key"], "app.title");
+    assert_eq!(report["keys"][0]["span"]["line"], 1);
+    assert!(!project.join("locales").exists());
+
+    let output = run_with_stdin(
+        &[
+            "lint",
+            "--stdin",
+            "--stdin-filepath",
+            "src/App.tsx",
+            "--fail-on-error",
+        ],
+        source,
+    );
+    assert!(!output.status.success());
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["issues"][0]["text"], "Hello world");
+    assert_eq!(report["issues"][0]["line"], 1);
+
+    let output = run_with_stdin(&["extract", "--stdin"], source);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn fmt_rewrites_locale_files_and_check_fails_until_formatted() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    let config_path = write_config_with_locales(project, &["en", "de"]);
+    let config = config_path.to_str().unwrap();
+    let en = project.join("locales/en/translation.json");
+    fs::create_dir_all(en.parent().unwrap()).unwrap();
+    fs::write(
+        &en,
+        "{\"b\": \"B\",\r\n    \"a\": {\"y\": \"\", \"x\": \"X\"}}",
+    )
+    .unwrap();
+    write_locale_json(
+        &project.join("locales/de/translation.json"),
+        json!({ "a": { "x": "X" } }),
+    );
+
+    let output = run_cli(project, &["--config", config, "fmt", "--check"]);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("en/translation.json - not formatted"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("de/translation.json"), "{}", stdout);
+    assert!(fs::read_to_string(&en).unwrap().starts_with("{\"b\""));
+
+    let output = run_cli(project, &["--config", config, "fmt"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        fs::read_to_string(&en).unwrap(),
+        // Without `indentation` and `endOfLine` the file keeps its own
+        "{\r\n    \"a\": {\r\n        \"x\": \"X\",\r\n        \"y\": \"\"\r\n    },\r\n    \"b\": \"B\"\r\n}\r\n"
+    );
+
+    let output = run_cli(project, &["--config", config, "fmt", "--check"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn set_value_and_edit_write_values_keeping_file_style() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    let config_path = write_config_with_locales(project, &["en", "de"]);
+    let config = config_path.to_str().unwrap();
+    let en = project.join("locales/en/translation.json");
+    let de = project.join("locales/de/translation.json");
+    fs::create_dir_all(en.parent().unwrap()).unwrap();
+    fs::write(
+        &en,
+        "{\n    \"home\": {\n        \"title\": \"Home\"\n    }\n}\n",
+    )
+    .unwrap();
+    write_locale_json(&de, json!({ "home": { "title": "" } }));
+
+    let output = run_cli(
+        project,
+        &["--config", config, "set-value", "en", "home.title", "Start"],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        fs::read_to_string(&en).unwrap(),
+        "{\n    \"home\": {\n        \"title\": \"Start\"\n    }\n}\n"
+    );
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config,
+            "set-value",
+            "fr",
+            "home.title",
+            "Accueil",
+        ],
+    );
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown locale 'fr'"));
+
+    // The "editor" replaces the values with a prepared file
+    let edited = project.join("edited.json");
+    fs::write(&edited, r#"{ "en": "Start", "de": "Startseite" }"#).unwrap();
+    let output = Command::new(cli_bin())
+        .current_dir(project)
+        .args(["--config", config, "edit", "translation:home.title"])
+        .env_remove("VISUAL")
+        .env("EDITOR", format!("cp {}", edited.display()))
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Updated 1 locale file(s)."), "{}", stdout);
+    assert_eq!(read_json(&de)["home"]["title"], "Startseite");
+    assert_eq!(read_json(&en)["home"]["title"], "Start");
+}
+
+#[test]
+fn remove_empty_files_deletes_emptied_namespaces_and_locale_dirs() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(project.join("src/app.ts"), "t('alive');").unwrap();
+    let config_path = write_config_with_locales(project, &["en", "de"]);
+    let mut config = read_json(&config_path);
+    config["removeEmptyFiles"] = json!(true);
+    fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+    let config = config_path.to_str().unwrap();
+
+    write_locale_json(
+        &project.join("locales/en/translation.json"),
+        json!({ "alive": "Alive" }),
+    );
+    write_locale_json(
+        &project.join("locales/en/legacy.json"),
+        json!({ "old": { "title": "Old" } }),
+    );
+    write_locale_json(
+        &project.join("locales/de/legacy.json"),
+        json!({ "old": { "title": "Alt" } }),
+    );
+
+    let mut child = Command::new(cli_bin())
+        .current_dir(project)
+        .args(["--config", config, "check", "--all-locales", "--remove"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"y\n").unwrap();
+    let output = child.wait_with_output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "stdout: {}", stdout);
+    assert!(
+        stdout.contains("Empty locale files (removeEmptyFiles):"),
+        "{}",
+        stdout
+    );
+    assert!(!project.join("locales/en/legacy.json").exists());
+    assert!(project.join("locales/en/translation.json").exists());
+    assert!(!project.join("locales/de").exists(), "{}", stdout);
+
+    // `sync --remove-unused` empties the secondary file of an empty primary namespace
+    write_locale_json(&project.join("locales/en/misc.json"), json!({}));
+    write_locale_json(
+        &project.join("locales/de/misc.json"),
+        json!({ "stale": "Veraltet" }),
+    );
+    let output = run_cli(
+        project,
+        &["--config", config, "sync", "--remove-unused", "--dry-run"],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Would delete"), "{}", stdout);
+    assert!(project.join("locales/de/misc.json").exists());
+
+    let output = run_cli(project, &["--config", config, "sync", "--remove-unused"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", stdout);
+    assert!(!project.join("locales/de/misc.json").exists(), "{}", stdout);
+    assert!(project.join("locales/de/translation.json").exists());
+}
+
+#[test]
+fn check_references_uses_fallbacks_and_allowed_formatters() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    let config_path = write_config_with_locales(project, &["en", "de"]);
+    let mut config = read_json(&config_path);
+    config["allowedFormatters"] = json!(["uppercase"]);
+    fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+    let config = config_path.to_str().unwrap();
+
+    write_locale_json(
+        &project.join("locales/en/translation.json"),
+        json!({
+            "brand": "Turbo",
+            "title": "$t(brand) {{name, uppercase}}",
+            "price": "{{amount, currency(USD)}}"
+        }),
+    );
+    write_locale_json(
+        &project.join("locales/de/translation.json"),
+        json!({
+            "title": "$t(brand) {{name, shout}}",
+            "footer": "$t(legal)"
+        }),
+    );
+
+    let output = run_cli(project, &["--config", config, "check", "--references"]);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("[en] OK"), "{}", stdout);
+    assert!(stdout.contains("[de] 2 issue(s)"), "{}", stdout);
+    // `brand` falls back to the primary language
+    assert!(!stdout.contains("missing key 'brand'"), "{}", stdout);
+    let de_file = Path::new("locales")
+        .join("de")
+        .join("translation.json")
+        .display()
+        .to_string();
+    assert!(
+        stdout.contains(&format!(
+            "translation:footer references missing key 'legal' ({})",
+            de_file
+        )),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains(&format!(
+            "translation:title uses unknown formatter 'shout' ({})",
+            de_file
+        )),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn extract_json_report_traces_added_keys_to_their_source() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/a.ts"),
+        "const x = 1;\nt('home.title');\n  t('common:save');\nconst n = t('item', { count: 2 });\n",
+    )
+    .unwrap();
+    fs::write(project.join("src/b.ts"), "t('home.title');\nt('kept');\n").unwrap();
+    let config_path = write_config_with_locales(project, &["en"]);
+    let config = config_path.to_str().unwrap();
+    write_locale_json(
+        &project.join("locales/en/translation.json"),
+        json!({ "kept": "Kept", "stale": "Stale" }),
+    );
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config,
+            "extract",
+            "--json-report",
+            "out/report.json",
+        ],
+    );
+    assert!(output.status.success(), "{:?}", output);
+
+    let report = read_json(&project.join("out/report.json"));
+    assert_eq!(report["dryRun"], false);
+    assert_eq!(report["keysAdded"], 4);
+    let translation = Path::new("locales")
+        .join("en")
+        .join("translation.json")
+        .display()
+        .to_string();
+    let files = report["updatedFiles"].as_array().unwrap();
+    let file = |name: &str| {
+        files
+            .iter()
+            .find(|file| file["file"].as_str().unwrap().ends_with(name))
+            .unwrap_or_else(|| panic!("{} missing from {}", name, report))
+    };
+    assert_eq!(
+        file("common.json")["addedKeys"],
+        json!([{
+            "namespace": "common",
+            "key": "save",
+            "source": "src/a.ts",
+            "line": 3,
+            "column": 3
+        }])
+    );
+    let translation_file = file(&translation);
+    assert_eq!(
+        translation_file["addedKeys"][0]["source"], "src/a.ts",
+        "{}",
+        report
+    );
+    let added = |key: &str| {
+        translation_file["addedKeys"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|added| added["key"] == key)
+            .unwrap_or_else(|| panic!("{} missing from {}", key, report))
+    };
+    assert_eq!(added("home.title")["line"], 2);
+    assert_eq!(added("home.title")["column"], 1);
+    // Plural forms point at the call that generated them
+    assert_eq!(added("item_other")["line"], 4);
+    assert_eq!(added("item_other")["column"], 11);
+    assert_eq!(translation_file["removedKeys"], json!(["stale"]));
+}
+
+#[test]
+fn typegen_writes_selected_namespaces_one_file_each() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    let config_path = write_config_with_locales(project, &["en"]);
+    let mut config = read_json(&config_path);
+    config["types"] = json!({
+        "excludeNamespaces": ["legacy"],
+        "outputDir": "src/@types/i18n"
+    });
+    fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+    let config = config_path.to_str().unwrap();
+    write_locale_json(
+        &project.join("locales/en/common.json"),
+        json!({ "save": "Save" }),
+    );
+    write_locale_json(
+        &project.join("locales/en/legacy.json"),
+        json!({ "old": "Old" }),
+    );
+
+    let output = run_cli(project, &["--config", config, "typegen"]);
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Output: src/@types/i18n"), "{}", stdout);
+
+    let types_dir = project.join("src/@types/i18n");
+    let index = fs::read_to_string(types_dir.join("index.d.ts")).unwrap();
+    assert!(index.contains("import type Common from \"./common\";"));
+    assert!(!index.contains("Legacy"), "{}", index);
+    assert!(types_dir.join("common.d.ts").exists());
+    assert!(!types_dir.join("legacy.d.ts").exists());
+}
+
+#[test]
+fn typegen_constants_writes_frozen_key_modules() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    let config_path = write_config_with_locales(project, &["en"]);
+    let config = config_path.to_str().unwrap();
+    write_locale_json(
+        &project.join("locales/en/auth.json"),
+        json!({ "login": { "title": "Log in" } }),
+    );
+
+    let output = run_cli(project, &["--config", config, "typegen", "--constants"]);
+    assert!(output.status.success(), "{:?}", output);
+    let auth = fs::read_to_string(project.join("src/i18n/keys/auth.ts")).unwrap();
+    assert!(
+        auth.contains("    title: \"auth:login.title\","),
+        "{}",
+        auth
+    );
+    let index = fs::read_to_string(project.join("src/i18n/keys/index.ts")).unwrap();
+    assert!(index.contains("export const Keys = Object.freeze({"));
+    assert!(!project.join("src/@types/i18next.d.ts").exists());
+}
+
+#[test]
+fn check_spacing_reports_and_fixes_invisible_differences() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    let config_path = write_config_with_locales(project, &["en", "de"]);
+    let config = config_path.to_str().unwrap();
+    write_locale_json(
+        &project.join("locales/en/common.json"),
+        json!({ "save": "Save", "saved": "Saved.", "wait": "Please wait" }),
+    );
+    write_locale_json(
+        &project.join("locales/de/common.json"),
+        json!({ "save": "Speichern ", "saved": "Gespeichert", "wait": "Bitte warten" }),
+    );
+
+    let output = run_cli(project, &["--config", config, "check", "--spacing"]);
+    assert!(!output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("[de] 2 issue(s):"), "{}", stdout);
+    assert!(
+        stdout.contains("  common:save trailing whitespace differs from the source"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("  common:saved drops the final '.' of the source"),
+        "{}",
+        stdout
+    );
+
+    let output = run_cli(
+        project,
+        &["--config", config, "check", "--spacing", "--fix"],
+    );
+    assert!(output.status.success(), "{:?}", output);
+    let de = read_json(&project.join("locales/de/common.json"));
+    assert_eq!(de["save"], "Speichern");
+    assert_eq!(de["saved"], "Gespeichert.");
+    assert_eq!(de["wait"], "Bitte warten");
+
+    let output = run_cli(project, &["--config", config, "check", "--spacing"]);
+    assert!(output.status.success(), "{:?}", output);
+}
+
+#[test]
+fn extract_namespace_directive_sets_the_file_namespace() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/checkout.ts"),
+        "/* i18next-extract-namespace: checkout */\nt('title');\nt('common:save');\n",
+    )
+    .unwrap();
+    fs::write(project.join("src/app.ts"), "t('app.title');\n").unwrap();
+    let config_path = write_config(project);
+
+    let output = run_cli(
+        project,
+        &["--config", config_path.to_str().unwrap(), "extract"],
+    );
+    assert!(output.status.success(), "{:?}", output);
+    let checkout = read_json(&project.join("locales/en/checkout.json"));
+    assert_eq!(checkout, json!({ "title": "" }));
+    let common = read_json(&project.join("locales/en/common.json"));
+    assert_eq!(common, json!({ "save": "" }));
+    let translation = read_json(&project.join("locales/en/translation.json"));
+    assert_eq!(translation, json!({ "app": { "title": "" } }));
+}
+
+#[test]
+fn doctor_and_check_report_untracked_locale_files() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(project.join("src/app.ts"), "t('app.title');\n").unwrap();
+    let config_path = write_config_with_locales(project, &["en"]);
+    let config = config_path.to_str().unwrap();
+    write_locale_json(
+        &project.join("locales/en/translation.json"),
+        json!({ "app": { "title": "App" } }),
+    );
+
+    let output = run_cli(project, &["--config", config, "doctor"]);
+    assert!(output.status.success(), "{:?}", output);
+
+    write_locale_json(
+        &project.join("locales/en-US/translation.json"),
+        json!({ "app": { "title": "App" } }),
+    );
+    fs::write(project.join("locales/en/translation.yaml"), "app: App\n").unwrap();
+
+    let output = run_cli(project, &["--config", config, "doctor"]);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("en-US: never read or written ('en-US' is not a configured locale)"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains(
+        "translation.yaml: never read or written (extension is not read by outputFormat)"
+    ));
+
+    let output = run_cli(project, &["--config", config, "check"]);
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("2 untracked file(s) in locales (never read or written):"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn check_key_case_reports_and_unifies_case_only_differences() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/profile.ts"),
+        "t('user.name');\nt('user.email');\n",
+    )
+    .unwrap();
+    fs::write(project.join("src/header.ts"), "t('user.name');\n").unwrap();
+    fs::write(project.join("src/legacy.ts"), "t('user.Name');\n").unwrap();
+    let config_path = write_config_with_locales(project, &["en", "de"]);
+    let config = config_path.to_str().unwrap();
+    write_locale_json(
+        &project.join("locales/en/translation.json"),
+        json!({ "user": { "name": "Name", "Name": "Name", "email": "Email" } }),
+    );
+    write_locale_json(
+        &project.join("locales/de/translation.json"),
+        json!({ "user": { "name": "", "Name": "Name (de)", "email": "E-Mail" } }),
+    );
+
+    let output = run_cli(project, &["--config", config, "check", "--key-case"]);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(
+            "  translation:user.name (2 file(s), 2 locale(s)) <- user.Name (1 file(s), 2 locale(s))"
+        ),
+        "{}",
+        stdout
+    );
+
+    let output = run_cli(
+        project,
+        &["--config", config, "check", "--key-case", "--fix"],
+    );
+    assert!(output.status.success(), "{:?}", output);
+    let en = read_json(&project.join("locales/en/translation.json"));
+    assert_eq!(en["user"], json!({ "email": "Email", "name": "Name" }));
+    let de = read_json(&project.join("locales/de/translation.json"));
+    assert_eq!(
+        de["user"],
+        json!({ "email": "E-Mail", "name": "Name (de)" })
+    );
+    let legacy = fs::read_to_string(project.join("src/legacy.ts")).unwrap();
+    assert_eq!(legacy, "t('user.name');\n");
+
+    // A policy is enforced on its own
+    let mut config_json = read_json(&config_path);
+    config_json["keyCase"] = json!("snake_case");
+    fs::write(&config_path, config_json.to_string()).unwrap();
+    fs::write(project.join("src/legacy.ts"), "t('user.firstName');\n").unwrap();
+    let output = run_cli(project, &["--config", config, "check", "--key-case"]);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("  translation:user.firstName (segment 'firstName')"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn add_and_remove_locale_manage_files_and_config() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/i18n.ts"),
+        "import de from './locales/de/common.json';\nswitch (lng) {\n  case 'de': break;\n  case 'deutsch': break;\n}\n",
+    )
+    .unwrap();
+    let config_path = project.join("i18next-turbo.json");
+    fs::write(
+        &config_path,
+        "{\n  \"output\": \"locales\",\n  \"locales\": [\"en\", \"de\"],\n  \"input\": [\"src/**/*.ts\"]\n}\n",
+    )
+    .unwrap();
+    let config = config_path.to_str().unwrap();
+    write_locale_json(
+        &project.join("locales/en/common.json"),
+        json!({ "save": "Save", "item_one": "{{count}} item", "item_other": "{{count}} items" }),
+    );
+    write_locale_json(
+        &project.join("locales/de/common.json"),
+        json!({ "save": "Speichern" }),
+    );
+
+    let output = run_cli(project, &["--config", config, "add-locale", "ru"]);
+    assert!(output.status.success(), "{:?}", output);
+    let ru = read_json(&project.join("locales/ru/common.json"));
+    assert_eq!(
+        ru,
+        json!({ "item_few": "", "item_many": "", "item_one": "", "item_other": "", "save": "" })
+    );
+    assert_eq!(
+        read_json(&config_path)["locales"],
+        json!(["en", "de", "ru"])
+    );
+
+    let output = run_cli(
+        project,
+        &["--config", config, "add-locale", "fr", "--copy-values"],
+    );
+    assert!(output.status.success(), "{:?}", output);
+    let fr = read_json(&project.join("locales/fr/common.json"));
+    assert_eq!(fr["save"], "Save");
+
+    let output = run_cli(project, &["--config", config, "remove-locale", "de"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(!project.join("locales/de").exists());
+    assert_eq!(
+        fs::read_to_string(&config_path).unwrap(),
+        "{\n  \"output\": \"locales\",\n  \"locales\": [\"en\", \"ru\", \"fr\"],\n  \"input\": [\"src/**/*.ts\"]\n}\n"
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("2 line(s) of source code still refer to 'de'"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("i18n.ts:3: case 'de': break;"),
+        "{}",
+        stdout
+    );
+
+    let output = run_cli(project, &["--config", config, "remove-locale", "en"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn export_eslint_data_lists_keys_and_file_namespaces() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/app.ts"),
+        "t('title');\nt('common:save');\nt('item', { count: 2 });\n",
+    )
+    .unwrap();
+    let config_path = write_config_with_locales(project, &["en"]);
+    let mut config_json = read_json(&config_path);
+    config_json["eslintDataFile"] = json!("eslint/i18n-keys.json");
+    fs::write(
+        &config_path,
+        serde_json::to_string_pretty(&config_json).unwrap(),
+    )
+    .unwrap();
+    let config = config_path.to_str().unwrap();
+
+    let output = run_cli(project, &["--config", config, "extract"]);
+    assert!(output.status.success(), "{:?}", output);
+    let data = read_json(&project.join("eslint/i18n-keys.json"));
+    assert_eq!(data["namespaces"], json!(["common", "translation"]));
+    assert_eq!(data["keys"]["common"], json!(["save"]));
+    assert_eq!(
+        data["keys"]["translation"],
+        json!(["item", "item_one", "item_other", "title"])
+    );
+    assert_eq!(
+        data["files"]["src/app.ts"]["namespaces"],
+        json!(["common", "translation"])
+    );
+
+    let output = run_cli(
+        project,
+        &["--config", config, "export-eslint-data", "-o", "keys.json"],
+    );
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(read_json(&project.join("keys.json")), data);
+}
+
+#[test]
+fn removal_grace_days_keep_dead_keys_until_the_grace_period_is_over() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(project.join("src/app.ts"), "t('title');\n").unwrap();
+    let config_path = write_config_with_locales(project, &["en"]);
+    let mut config_json = read_json(&config_path);
+    config_json["metadataFile"] = json!("locales/.meta.json");
+    config_json["removalGraceDays"] = json!(30);
+    fs::write(
+        &config_path,
+        serde_json::to_string_pretty(&config_json).unwrap(),
+    )
+    .unwrap();
+    let config = config_path.to_str().unwrap();
+    write_locale_json(
+        &project.join("locales/en/translation.json"),
+        json!({ "title": "Title", "summer": { "sale": "Summer sale" } }),
+    );
+
+    let output = run_cli(project, &["--config", config, "extract"]);
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Dead keys kept for removalGraceDays: 1"),
+        "{}",
+        stdout
+    );
+    let en = read_json(&project.join("locales/en/translation.json"));
+    assert_eq!(en["summer"]["sale"], "Summer sale");
+    let meta_path = project.join("locales/.meta.json");
+    let mut meta = read_json(&meta_path);
+    assert!(meta["keys"]["translation"]["summer.sale"]["deadSince"].is_string());
+
+    let output = run_cli(project, &["--config", config, "status"]);
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Pending removal (1 dead key(s), removalGraceDays: 30):"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("translation:summer.sale (dead since"),
+        "{}",
+        stdout
+    );
+
+    meta["keys"]["translation"]["summer.sale"]["deadSince"] = json!("2000-01-01");
+    fs::write(&meta_path, serde_json::to_string_pretty(&meta).unwrap()).unwrap();
+    let output = run_cli(project, &["--config", config, "extract"]);
+    assert!(output.status.success(), "{:?}", output);
+    let en = read_json(&project.join("locales/en/translation.json"));
+    assert_eq!(en, json!({ "title": "Title" }));
+    let meta = read_json(&meta_path);
+    assert!(meta["keys"]["translation"].get("summer.sale").is_none());
+}
+
+#[test]
+fn check_remove_and_sync_remove_unused_honor_removal_grace_days() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(project.join("src/app.ts"), "t('title');\n").unwrap();
+    let config_path = write_config_with_locales(project, &["en", "de"]);
+    let mut config_json = read_json(&config_path);
+    config_json["metadataFile"] = json!("locales/.meta.json");
+    config_json["removalGraceDays"] = json!(30);
+    fs::write(
+        &config_path,
+        serde_json::to_string_pretty(&config_json).unwrap(),
+    )
+    .unwrap();
+    let config = config_path.to_str().unwrap();
+    write_locale_json(
+        &project.join("locales/en/translation.json"),
+        json!({ "title": "Title", "summer": { "sale": "Summer sale" } }),
+    );
+    write_locale_json(
+        &project.join("locales/de/translation.json"),
+        json!({ "title": "Titel", "summer": { "sale": "Sommerschlussverkauf" }, "legacy": "Alt" }),
+    );
+
+    let check_remove = || {
+        let mut child = Command::new(cli_bin())
+            .current_dir(project)
+            .args(["--config", config, "check", "--remove"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child.stdin.take().unwrap().write_all(b"y\n").unwrap();
+        child.wait_with_output().unwrap()
+    };
+
+    let output = run_cli(project, &["--config", config, "sync", "--remove-unused"]);
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Kept 1 key(s) within removalGraceDays"),
+        "{}",
+        stdout
+    );
+    assert_eq!(
+        read_json(&project.join("locales/de/translation.json"))["legacy"],
+        "Alt"
+    );
+
+    let output = check_remove();
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("within removalGraceDays"), "{}", stdout);
+    let en = read_json(&project.join("locales/en/translation.json"));
+    assert_eq!(en["summer"]["sale"], "Summer sale");
+    let meta_path = project.join("locales/.meta.json");
+    let mut meta = read_json(&meta_path);
+    assert!(meta["keys"]["translation"]["legacy"]["deadSince"].is_string());
+    assert!(meta["keys"]["translation"]["summer.sale"]["deadSince"].is_string());
+
+    for key in ["legacy", "summer.sale"] {
+        meta["keys"]["translation"][key]["deadSince"] = json!("2000-01-01");
+    }
+    fs::write(&meta_path, serde_json::to_string_pretty(&meta).unwrap()).unwrap();
+    let output = run_cli(project, &["--config", config, "sync", "--remove-unused"]);
+    assert!(output.status.success(), "{:?}", output);
+    let output = check_remove();
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        read_json(&project.join("locales/en/translation.json")),
+        json!({ "title": "Title", "summer": {} })
+    );
+    assert_eq!(
+        read_json(&project.join("locales/de/translation.json")),
+        json!({ "title": "Titel", "summer": { "sale": "Sommerschlussverkauf" } })
+    );
+    let meta = read_json(&meta_path);
+    assert!(meta["keys"]["translation"].get("legacy").is_none());
+    assert!(meta["keys"]["translation"].get("summer.sale").is_none());
+}
+
+#[test]
+fn extract_lint_reports_hardcoded_strings_from_the_same_parse() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/app.tsx"),
+        "export const App = () => <div><h1>{t('title')}</h1><p>Hello hardcoded</p></div>;",
+    )
+    .unwrap();
+    let config_path = write_config(project);
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "extract",
+            "--lint",
+        ],
+    );
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Scanning for hardcoded strings (same parse)"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("Hello hardcoded"), "{}", stdout);
+    let en = read_json(&project.join("locales/en/translation.json"));
+    assert!(en.get("title").is_some());
+
+    let output = run_cli(
+        project,
+        &["--config", config_path.to_str().unwrap(), "extract"],
+    );
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("Hello hardcoded"), "{}", stdout);
+}
+
+#[test]
+fn extract_counts_diagnostics_by_kind_and_filters_them() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/app.ts"),
+        "t('title');\nt(`status.${state}`);\nt('common:save', { ns: 'admin' });\n",
+    )
+    .unwrap();
+    let config_path = write_config(project);
+    let config = config_path.to_str().unwrap();
+
+    let output = run_cli(project, &["--config", config, "extract"]);
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("  Warnings: 2"), "{}", stdout);
+    assert!(stdout.contains("    dynamic-key: 1"), "{}", stdout);
+    assert!(stdout.contains("    conflicting-options: 1"), "{}", stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(
+            "Warning: Key namespace 'common' and ns option 'admin' disagree at src/app.ts:3:1"
+        ),
+        "{}",
+        stderr
+    );
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config,
+            "extract",
+            "--diagnostic",
+            "conflicting-options",
+            "--fail-on-warnings",
+        ],
+    );
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("Dynamic template literal"), "{}", stderr);
+    assert!(
+        stderr.contains("1 warning(s) encountered (--fail-on-warnings enabled): conflicting-options: 1, 0 key conflicts"),
+        "{}",
+        stderr
+    );
+
+    let output = run_cli(
+        project,
+        &["--config", config, "extract", "--diagnostic", "typo"],
+    );
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown diagnostic kind 'typo'"));
+}
+
+#[test]
+fn typegen_types_return_objects_roots_from_the_sources() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/app.ts"),
+        "t('countries', { returnObjects: true });\n",
+    )
+    .unwrap();
+    let config_path = write_config(project);
+    let mut config_json = read_json(&config_path);
+    config_json["types"] = json!({ "enableSelector": "optimize" });
+    fs::write(
+        &config_path,
+        serde_json::to_string_pretty(&config_json).unwrap(),
+    )
+    .unwrap();
+    write_locale_json(
+        &project.join("locales/en/translation.json"),
+        json!({ "countries.jp": "Japan", "countries.us": "USA" }),
+    );
+    let types = project.join("types/i18next.d.ts");
+
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_path.to_str().unwrap(),
+            "typegen",
+            "--output",
+            types.to_str().unwrap(),
+        ],
+    );
+    assert!(output.status.success(), "{:?}", output);
+    let content = fs::read_to_string(&types).unwrap();
+    assert!(content.contains("countries: {"), "{}", content);
+    assert!(
+        content.contains("| \"translation.countries\""),
+        "{}",
+        content
+    );
+}
+
+#[test]
+fn extract_republishes_hashed_bundles_to_publish_dir() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(project.join("src/app.ts"), "t('title');\n").unwrap();
+    let config_path = write_config(project);
+    let mut config_json = read_json(&config_path);
+    config_json["publishDir"] = json!("public/i18n");
+    fs::write(
+        &config_path,
+        serde_json::to_string_pretty(&config_json).unwrap(),
+    )
+    .unwrap();
+    let config_arg = config_path.to_str().unwrap();
+
+    let output = run_cli(project, &["--config", config_arg, "extract"]);
+    assert!(output.status.success(), "{:?}", output);
+    let publish_dir = project.join("public/i18n");
+    let manifest = read_json(&publish_dir.join("manifest.json"));
+    let first = manifest["locales"]["en"]["file"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert!(publish_dir.join(&first).exists());
+
+    write_locale_json(
+        &project.join("locales/en/translation.json"),
+        json!({ "title": "Title" }),
+    );
+    let output = run_cli(project, &["--config", config_arg, "publish-dir"]);
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(&format!("Removed {}", first)), "{}", stdout);
+    let manifest = read_json(&publish_dir.join("manifest.json"));
+    let bundle = read_json(&publish_dir.join(manifest["locales"]["en"]["file"].as_str().unwrap()));
+    assert_eq!(bundle, json!({ "translation": { "title": "Title" } }));
+}
+
+#[test]
+fn import_missing_merges_runtime_keys_that_extract_keeps() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(project.join("src/app.ts"), "t('title');\n").unwrap();
+    let config_path = write_config_with_locales(project, &["en", "ja"]);
+    let config_arg = config_path.to_str().unwrap();
+    let output = run_cli(project, &["--config", config_arg, "extract"]);
+    assert!(output.status.success(), "{:?}", output);
+
+    let log = project.join("missing.jsonl");
+    fs::write(
+        &log,
+        concat!(
+            "{\"lng\":[\"ja\"],\"ns\":\"translation\",\"key\":\"status.active\",\"fallbackValue\":\"Active\"}\n",
+            "{\"lng\":[\"en\"],\"ns\":\"translation\",\"key\":\"status.active\"}\n",
+            "{\"lng\":[\"en\"],\"key\":\"errors:code.E42\"}\n",
+        ),
+    )
+    .unwrap();
+    let output = run_cli(
+        project,
+        &[
+            "--config",
+            config_arg,
+            "import-missing",
+            log.to_str().unwrap(),
+        ],
+    );
+    assert!(output.status.success(), "{:?}", output);
+
+    let output = run_cli(project, &["--config", config_arg, "extract"]);
+    assert!(output.status.success(), "{:?}", output);
+    let en = read_json(&project.join("locales/en/translation.json"));
+    assert_eq!(en["title"], "");
+    assert_eq!(en["status"]["active"], "Active");
+    assert_eq!(en["status"]["__preserve__"], json!(["active"]));
+    let ja = read_json(&project.join("locales/ja/translation.json"));
+    assert!(ja["status"].get("active").is_some(), "{}", ja);
+    let errors = read_json(&project.join("locales/en/errors.json"));
+    assert_eq!(errors["code"]["E42"], "");
+}
+
+#[test]
+fn extract_and_import_missing_never_write_outside_the_output_dir() {
+    let tmp = tempdir().unwrap();
+    let project = tmp.path().join("project");
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("src/app.ts"),
+        "t('title');\nt('../../../pwned:owned');\n",
+    )
+    .unwrap();
+    let config_path = write_config(&project);
+    let config_arg = config_path.to_str().unwrap();
+
+    let output = run_cli(&project, &["--config", config_arg, "extract"]);
+    assert!(output.status.success(), "{:?}", output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Namespace '../../../pwned' at src/app.ts:2 is not a plain file name"),
+        "{}",
+        stderr
+    );
+    let en = read_json(&project.join("locales/en/translation.json"));
+    assert_eq!(en, json!({ "title": "" }));
+
+    let log = project.join("missing.jsonl");
+    fs::write(
+        &log,
+        concat!(
+            "{\"ns\":\"../../../pwned\",\"key\":\"owned\"}\n",
+            "{\"ns\":\"translation\",\"key\":\"status\"}\n",
+        ),
+    )
+    .unwrap();
+    let output = run_cli(
+        &project,
+        &[
+            "--config",
+            config_arg,
+            "import-missing",
+            log.to_str().unwrap(),
+        ],
+    );
+    assert!(output.status.success(), "{:?}", output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("not a plain file name"), "{}", stderr);
+    let en = read_json(&project.join("locales/en/translation.json"));
+    assert_eq!(en["status"], "");
+    assert!(!tmp.path().join("pwned.json").exists());
+    assert!(!project.join("pwned.json").exists());
 }